@@ -0,0 +1,54 @@
+use criterion::Criterion;
+use tfhe::prelude::*;
+use tfhe::{generate_keys, ConfigBuilder, FheUint64, FheUint8};
+use tfhe_trivium::{Aes128CtrStream, TransCiphering};
+
+pub fn aes_ctr_gen(c: &mut Criterion) {
+    let config = ConfigBuilder::default().build();
+    let (client_key, server_key) = generate_keys(config);
+
+    let key_string = "2B7E151628AED2A6ABF7158809CF4F3C".to_string();
+    let mut key = [0u8; 16];
+
+    for i in (0..key_string.len()).step_by(2) {
+        key[i >> 1] = u8::from_str_radix(&key_string[i..i + 2], 16).unwrap();
+    }
+
+    let cipher_key = key.map(|x| FheUint8::encrypt(x, &client_key));
+
+    let mut aes = Aes128CtrStream::new(cipher_key, [0u8; 16], &server_key);
+
+    c.bench_function("aes ctr generate 64 bits", |b| b.iter(|| aes.next_64()));
+}
+
+pub fn aes_ctr_trans(c: &mut Criterion) {
+    let config = ConfigBuilder::default().build();
+    let (client_key, server_key) = generate_keys(config);
+
+    let key_string = "2B7E151628AED2A6ABF7158809CF4F3C".to_string();
+    let mut key = [0u8; 16];
+
+    for i in (0..key_string.len()).step_by(2) {
+        key[i >> 1] = u8::from_str_radix(&key_string[i..i + 2], 16).unwrap();
+    }
+
+    let cipher_key = key.map(|x| FheUint8::encrypt(x, &client_key));
+
+    let ciphered_message = FheUint64::try_encrypt(0u64, &client_key).unwrap();
+    let mut aes = Aes128CtrStream::new(cipher_key, [0u8; 16], &server_key);
+
+    c.bench_function("aes ctr transencrypt 64 bits", |b| {
+        b.iter(|| aes.trans_encrypt_64(ciphered_message.clone()))
+    });
+}
+
+pub fn aes_ctr_key_schedule(c: &mut Criterion) {
+    let config = ConfigBuilder::default().build();
+    let (client_key, server_key) = generate_keys(config);
+
+    let cipher_key = [0u8; 16].map(|x| FheUint8::encrypt(x, &client_key));
+
+    c.bench_function("aes ctr key schedule", |b| {
+        b.iter(|| Aes128CtrStream::new(cipher_key.clone(), [0u8; 16], &server_key))
+    });
+}
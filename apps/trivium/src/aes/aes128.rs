@@ -0,0 +1,410 @@
+//! This module implements the AES-128 block cipher with an encrypted key, on the 2 bit blocks of
+//! FheUint8 ciphertexts (default parameters, with 2 bits of message and 2 bits of carry).
+
+use rayon::prelude::*;
+use tfhe::integer::IntegerRadixCiphertext;
+use tfhe::shortint::ciphertext::NoiseLevel;
+use tfhe::shortint::prelude::*;
+use tfhe::shortint::server_key::{BivariateLookupTableOwned, LookupTableOwned};
+use tfhe::FheUint8;
+
+#[rustfmt::skip]
+const SBOX: [u8; 256] = [
+    0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
+    0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4, 0x72, 0xc0,
+    0xb7, 0xfd, 0x93, 0x26, 0x36, 0x3f, 0xf7, 0xcc, 0x34, 0xa5, 0xe5, 0xf1, 0x71, 0xd8, 0x31, 0x15,
+    0x04, 0xc7, 0x23, 0xc3, 0x18, 0x96, 0x05, 0x9a, 0x07, 0x12, 0x80, 0xe2, 0xeb, 0x27, 0xb2, 0x75,
+    0x09, 0x83, 0x2c, 0x1a, 0x1b, 0x6e, 0x5a, 0xa0, 0x52, 0x3b, 0xd6, 0xb3, 0x29, 0xe3, 0x2f, 0x84,
+    0x53, 0xd1, 0x00, 0xed, 0x20, 0xfc, 0xb1, 0x5b, 0x6a, 0xcb, 0xbe, 0x39, 0x4a, 0x4c, 0x58, 0xcf,
+    0xd0, 0xef, 0xaa, 0xfb, 0x43, 0x4d, 0x33, 0x85, 0x45, 0xf9, 0x02, 0x7f, 0x50, 0x3c, 0x9f, 0xa8,
+    0x51, 0xa3, 0x40, 0x8f, 0x92, 0x9d, 0x38, 0xf5, 0xbc, 0xb6, 0xda, 0x21, 0x10, 0xff, 0xf3, 0xd2,
+    0xcd, 0x0c, 0x13, 0xec, 0x5f, 0x97, 0x44, 0x17, 0xc4, 0xa7, 0x7e, 0x3d, 0x64, 0x5d, 0x19, 0x73,
+    0x60, 0x81, 0x4f, 0xdc, 0x22, 0x2a, 0x90, 0x88, 0x46, 0xee, 0xb8, 0x14, 0xde, 0x5e, 0x0b, 0xdb,
+    0xe0, 0x32, 0x3a, 0x0a, 0x49, 0x06, 0x24, 0x5c, 0xc2, 0xd3, 0xac, 0x62, 0x91, 0x95, 0xe4, 0x79,
+    0xe7, 0xc8, 0x37, 0x6d, 0x8d, 0xd5, 0x4e, 0xa9, 0x6c, 0x56, 0xf4, 0xea, 0x65, 0x7a, 0xae, 0x08,
+    0xba, 0x78, 0x25, 0x2e, 0x1c, 0xa6, 0xb4, 0xc6, 0xe8, 0xdd, 0x74, 0x1f, 0x4b, 0xbd, 0x8b, 0x8a,
+    0x70, 0x3e, 0xb5, 0x66, 0x48, 0x03, 0xf6, 0x0e, 0x61, 0x35, 0x57, 0xb9, 0x86, 0xc1, 0x1d, 0x9e,
+    0xe1, 0xf8, 0x98, 0x11, 0x69, 0xd9, 0x8e, 0x94, 0x9b, 0x1e, 0x87, 0xe9, 0xce, 0x55, 0x28, 0xdf,
+    0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb, 0x16,
+];
+
+/// Round constants XORed in the first byte of the first word of each round key
+const RCON: [u8; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+
+/// A byte, as the 4 blocks of 2 bits of a FheUint8 (least significant first)
+pub(super) type Byte = [Ciphertext; 4];
+
+fn into_array<T, const N: usize>(values: Vec<T>) -> [T; N] {
+    values
+        .try_into()
+        .unwrap_or_else(|values: Vec<T>| panic!("Expected {N} values, got {}", values.len()))
+}
+
+/// Lookup tables used by the AES operations, generated once for the server key
+struct LookupTables {
+    /// `sbox_parts[h][j]` maps the 4 low bits `l` of a byte to the block `j` of
+    /// `SBOX[16 * h + l]`
+    sbox_parts: Vec<[LookupTableOwned; 4]>,
+    /// `select[v]` outputs its second input if the first one is `v`, and 0 otherwise
+    select: [BivariateLookupTableOwned; 4],
+    xor: BivariateLookupTableOwned,
+    /// `xor_clear[c]` XORs its input with `c`
+    xor_clear: [LookupTableOwned; 4],
+    /// Outputs the high bit of the first input, and the high bit of the second input (the most
+    /// significant block of the byte) as the second bit
+    xtime_carries: BivariateLookupTableOwned,
+    /// `xtime_blocks[j]` outputs the block `j` of the multiplication by x, from the block `j` of
+    /// the input and:
+    /// - the most significant block for `j = 0`,
+    /// - the carries of the block `j - 1` for `j = 1` and `j = 2`,
+    /// - the block `2` for `j = 3`.
+    ///
+    /// The reduction by x^8 + x^4 + x^3 + x + 1 is applied for the first three blocks, 0x1b has
+    /// the blocks 3, 2, 1 and 0.
+    xtime_blocks: [BivariateLookupTableOwned; 4],
+}
+
+impl LookupTables {
+    fn new(sk: &ServerKey) -> Self {
+        let sbox_parts = (0..16)
+            .map(|high| {
+                std::array::from_fn(|j| {
+                    sk.generate_lookup_table(|low| {
+                        let value = SBOX[16 * high + low as usize];
+                        u64::from(value >> (2 * j)) & 3
+                    })
+                })
+            })
+            .collect();
+        let select = std::array::from_fn(|v| {
+            sk.generate_lookup_table_bivariate(
+                move |index, value| if index == v as u64 { value } else { 0 },
+            )
+        });
+        let xor_clear =
+            std::array::from_fn(|c| sk.generate_lookup_table(move |block| (block ^ c as u64) & 3));
+
+        let shifted = |block: u64| (block << 1) & 3;
+        let xtime_blocks = [
+            sk.generate_lookup_table_bivariate(move |block, high| {
+                shifted(block) ^ (3 * (high >> 1))
+            }),
+            sk.generate_lookup_table_bivariate(move |block, carries| {
+                (shifted(block) | (carries & 1)) ^ (2 * (carries >> 1))
+            }),
+            sk.generate_lookup_table_bivariate(move |block, carries| {
+                (shifted(block) | (carries & 1)) ^ (carries >> 1)
+            }),
+            sk.generate_lookup_table_bivariate(move |block, previous| {
+                shifted(block) | (previous >> 1)
+            }),
+        ];
+
+        Self {
+            sbox_parts,
+            select,
+            xor: sk.generate_lookup_table_bivariate(|lhs, rhs| lhs ^ rhs),
+            xor_clear,
+            xtime_carries: sk
+                .generate_lookup_table_bivariate(|block, high| (block >> 1) | ((high >> 1) << 1)),
+            xtime_blocks,
+        }
+    }
+}
+
+/// Aes128: a struct implementing the AES-128 block cipher with an encrypted key. Bytes are
+/// represented by the 4 shortint blocks of FheUint8 ciphertexts, the S-box is evaluated with
+/// lookup tables on these blocks, and MixColumns with XORs and multiplications by x in GF(2^8).
+/// To be able to compute FHE operations, it also owns the server keys.
+pub struct Aes128 {
+    round_keys: Vec<[Byte; 16]>,
+    lookup_tables: LookupTables,
+    internal_server_key: ServerKey,
+    hl_server_key: tfhe::ServerKey,
+}
+
+impl Aes128 {
+    /// Constructor for Aes128: arguments are the encrypted secret key and the FHE server key,
+    /// which must have 2 bits of message and 2 bits of carry (as the default parameters).
+    /// Outputs an Aes128 object with the round keys already computed
+    pub fn new(key: [FheUint8; 16], server_key: &tfhe::ServerKey) -> Self {
+        let internal_server_key: ServerKey = (*server_key.as_ref()).clone().into();
+        assert!(
+            internal_server_key.message_modulus.0 == 4 && internal_server_key.carry_modulus.0 == 4,
+            "AES-128 requires parameters with 2 bits of message and 2 bits of carry"
+        );
+
+        let mut ret = Self {
+            round_keys: Vec::with_capacity(11),
+            lookup_tables: LookupTables::new(&internal_server_key),
+            internal_server_key,
+            hl_server_key: server_key.clone(),
+        };
+        let key = key.map(|byte| ret.byte_from_fhe_uint8(byte));
+        ret.round_keys = ret.expand_key(key);
+        ret
+    }
+
+    /// Encrypts a clear block with the encrypted key, outputting the encrypted ciphertext
+    pub fn encrypt_block(&self, block: [u8; 16]) -> [FheUint8; 16] {
+        self.encrypt_block_bytes(block).map(byte_into_fhe_uint8)
+    }
+
+    pub fn get_internal_server_key(&self) -> &ServerKey {
+        &self.internal_server_key
+    }
+
+    pub fn get_server_key(&self) -> &tfhe::ServerKey {
+        &self.hl_server_key
+    }
+
+    /// The state is the 16 bytes of the block in order, so the byte of row `r` and column `c` is
+    /// `state[r + 4 * c]`
+    pub(super) fn encrypt_block_bytes(&self, block: [u8; 16]) -> [Byte; 16] {
+        let mut state: [Byte; 16] = into_array(
+            (0..16)
+                .into_par_iter()
+                .map(|i| self.xor_clear(&self.round_keys[0][i], block[i]))
+                .collect(),
+        );
+
+        for round in 1..11 {
+            let substituted = state
+                .par_iter()
+                .map(|byte| self.sub_byte(byte))
+                .collect::<Vec<_>>();
+            let shifted = shift_rows(substituted);
+            let mixed = if round < 10 {
+                shifted
+                    .par_chunks_exact(4)
+                    .flat_map_iter(|column| self.mix_column(column))
+                    .collect()
+            } else {
+                shifted
+            };
+            state = into_array(
+                mixed
+                    .par_iter()
+                    .zip(self.round_keys[round].par_iter())
+                    .map(|(byte, key_byte)| self.xor(byte, key_byte))
+                    .collect(),
+            );
+        }
+
+        state
+    }
+
+    /// Computes the 11 round keys, each word being the XOR of the previous word (rotated,
+    /// substituted and XORed with a round constant for the first word of a round key) and of
+    /// the word of the previous round key at the same position
+    fn expand_key(&self, key: [Byte; 16]) -> Vec<[Byte; 16]> {
+        let mut words: Vec<[Byte; 4]> = key
+            .into_iter()
+            .collect::<Vec<_>>()
+            .chunks_exact(4)
+            .map(|word| into_array(word.to_vec()))
+            .collect();
+
+        for i in 4..44 {
+            let previous = &words[i - 1];
+            let temp: [Byte; 4] = if i % 4 == 0 {
+                let mut substituted: [Byte; 4] = into_array(
+                    (0..4)
+                        .into_par_iter()
+                        .map(|j| self.sub_byte(&previous[(j + 1) % 4]))
+                        .collect(),
+                );
+                substituted[0] = self.xor_clear(&substituted[0], RCON[i / 4 - 1]);
+                substituted
+            } else {
+                previous.clone()
+            };
+            let word = into_array(
+                (0..4)
+                    .into_par_iter()
+                    .map(|j| self.xor(&words[i - 4][j], &temp[j]))
+                    .collect(),
+            );
+            words.push(word);
+        }
+
+        words
+            .chunks_exact(4)
+            .map(|round_key| into_array(round_key.iter().flatten().cloned().collect()))
+            .collect()
+    }
+
+    /// Splits a FheUint8 in its blocks, cleaning the blocks that have carries or too much noise
+    /// for the lookup tables
+    fn byte_from_fhe_uint8(&self, byte: FheUint8) -> Byte {
+        let (radix, _, _) = byte.into_raw_parts();
+        let mut blocks: Byte = into_array(radix.into_blocks());
+        blocks.par_iter_mut().for_each(|block| {
+            if block.degree.get() >= 4 || block.noise_level() > NoiseLevel::NOMINAL {
+                self.internal_server_key.message_extract_assign(block);
+            }
+        });
+        blocks
+    }
+
+    pub(super) fn xor(&self, lhs: &Byte, rhs: &Byte) -> Byte {
+        let sk = &self.internal_server_key;
+        into_array(
+            (0..4)
+                .into_par_iter()
+                .map(|j| {
+                    sk.unchecked_apply_lookup_table_bivariate(
+                        &lhs[j],
+                        &rhs[j],
+                        &self.lookup_tables.xor,
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// XOR with a clear byte, blocks XORed with 0 are left untouched
+    pub(super) fn xor_clear(&self, lhs: &Byte, rhs: u8) -> Byte {
+        into_array(
+            (0..4)
+                .into_par_iter()
+                .map(|j| match (rhs >> (2 * j)) & 3 {
+                    0 => lhs[j].clone(),
+                    c => self
+                        .internal_server_key
+                        .apply_lookup_table(&lhs[j], &self.lookup_tables.xor_clear[c as usize]),
+                })
+                .collect(),
+        )
+    }
+
+    /// Evaluates the S-box: for each value of the 4 high bits, the blocks of the output are
+    /// computed from the 4 low bits (packed in one block). The right values are then selected
+    /// with the block 2, and the block 3 of the input
+    fn sub_byte(&self, byte: &Byte) -> Byte {
+        let sk = &self.internal_server_key;
+
+        // Degree 15 and noise level 5 are the maximum for the parameters
+        let mut low = sk.unchecked_scalar_mul(&byte[1], 4);
+        sk.unchecked_add_assign(&mut low, &byte[0]);
+
+        let parts = self
+            .lookup_tables
+            .sbox_parts
+            .par_iter()
+            .flat_map_iter(|tables| tables.iter())
+            .map(|table| sk.apply_lookup_table(&low, table))
+            .collect::<Vec<_>>();
+
+        // parts[4 * h + j] is the block j of the output for the high bits h
+        let selected = (0..16)
+            .into_par_iter()
+            .map(|i| {
+                let (high, j) = (i / 4, i % 4);
+                self.select(
+                    &byte[2],
+                    std::array::from_fn(|v| &parts[4 * (4 * high + v) + j]),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        into_array(
+            (0..4)
+                .into_par_iter()
+                .map(|j| self.select(&byte[3], std::array::from_fn(|v| &selected[4 * v + j])))
+                .collect(),
+        )
+    }
+
+    /// Outputs `values[index]`, the lookup tables keep only the value at `index` so the sum of
+    /// their outputs is the selected value
+    fn select(&self, index: &Ciphertext, values: [&Ciphertext; 4]) -> Ciphertext {
+        let sk = &self.internal_server_key;
+        let terms = (0..4)
+            .into_par_iter()
+            .map(|v| {
+                sk.unchecked_apply_lookup_table_bivariate(
+                    index,
+                    values[v],
+                    &self.lookup_tables.select[v],
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut sum = terms[0].clone();
+        for term in &terms[1..] {
+            sk.unchecked_add_assign(&mut sum, term);
+        }
+        sk.message_extract_assign(&mut sum);
+        sum
+    }
+
+    /// Multiplication by x in GF(2^8)
+    fn xtime(&self, byte: &Byte) -> Byte {
+        let sk = &self.internal_server_key;
+        let tables = &self.lookup_tables;
+
+        let (carries_0, carries_1) = rayon::join(
+            || sk.unchecked_apply_lookup_table_bivariate(&byte[0], &byte[3], &tables.xtime_carries),
+            || sk.unchecked_apply_lookup_table_bivariate(&byte[1], &byte[3], &tables.xtime_carries),
+        );
+        let inputs = [&byte[3], &carries_0, &carries_1, &byte[2]];
+
+        into_array(
+            (0..4)
+                .into_par_iter()
+                .map(|j| {
+                    sk.unchecked_apply_lookup_table_bivariate(
+                        &byte[j],
+                        inputs[j],
+                        &tables.xtime_blocks[j],
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// MixColumns on a column: with `t` the XOR of the 4 bytes, the byte `i` is
+    /// `a[i] ^ t ^ xtime(a[i] ^ a[i + 1])`
+    fn mix_column(&self, column: &[Byte]) -> Vec<Byte> {
+        let (pairs, total) = rayon::join(
+            || {
+                (0..4)
+                    .into_par_iter()
+                    .map(|i| self.xor(&column[i], &column[(i + 1) % 4]))
+                    .collect::<Vec<_>>()
+            },
+            || {
+                let (first, second) = rayon::join(
+                    || self.xor(&column[0], &column[1]),
+                    || self.xor(&column[2], &column[3]),
+                );
+                self.xor(&first, &second)
+            },
+        );
+
+        (0..4)
+            .into_par_iter()
+            .map(|i| {
+                let (doubled, sum) =
+                    rayon::join(|| self.xtime(&pairs[i]), || self.xor(&column[i], &total));
+                self.xor(&sum, &doubled)
+            })
+            .collect()
+    }
+}
+
+/// The row `r` is rotated by `r` bytes to the left
+fn shift_rows(state: Vec<Byte>) -> Vec<Byte> {
+    let mut bytes = state.into_iter().map(Some).collect::<Vec<_>>();
+    (0..16)
+        .map(|i| {
+            let (row, column) = (i % 4, i / 4);
+            bytes[row + 4 * ((column + row) % 4)].take().unwrap()
+        })
+        .collect()
+}
+
+pub(super) fn byte_into_fhe_uint8(byte: Byte) -> FheUint8 {
+    FheUint8::try_from(byte.to_vec()).unwrap()
+}
@@ -0,0 +1,67 @@
+//! This module implements AES-128 in counter mode (CTR), with an encrypted key, so that data
+//! encrypted with AES-CTR can be trans ciphered to FheUint8 or FheUint64 values.
+
+use super::aes128::{byte_into_fhe_uint8, Byte};
+use crate::Aes128;
+use rayon::prelude::*;
+use std::collections::VecDeque;
+use tfhe::{FheUint8, ServerKey};
+
+/// Aes128CtrStream: a struct implementing AES-128 in counter mode, as a stream cipher. The
+/// counter blocks are in the clear, and are encrypted with the encrypted key to produce the
+/// keystream; the counter is incremented as a 128 bits big-endian integer (as in NIST SP
+/// 800-38A). Bytes of the keystream are buffered until they are used.
+pub struct Aes128CtrStream {
+    aes: Aes128,
+    counter: u128,
+    keystream: VecDeque<Byte>,
+}
+
+impl Aes128CtrStream {
+    /// Constructor for Aes128CtrStream: arguments are the encrypted secret key, the initial
+    /// counter block and the FHE server key, which must have 2 bits of message and 2 bits of
+    /// carry (as the default parameters).
+    pub fn new(key: [FheUint8; 16], initial_counter: [u8; 16], server_key: &ServerKey) -> Self {
+        Self {
+            aes: Aes128::new(key, server_key),
+            counter: u128::from_be_bytes(initial_counter),
+            keystream: VecDeque::with_capacity(16),
+        }
+    }
+
+    /// Outputs the next `n` bytes of the keystream, encrypting as many counter blocks as needed
+    fn next_keystream_bytes(&mut self, n: usize) -> Vec<Byte> {
+        while self.keystream.len() < n {
+            let block = self.aes.encrypt_block_bytes(self.counter.to_be_bytes());
+            self.keystream.extend(block);
+            self.counter = self.counter.wrapping_add(1);
+        }
+        self.keystream.drain(..n).collect()
+    }
+
+    /// Computes the next 8 bytes of the keystream, outputted all at once in a Vec (first value
+    /// is the first byte of the keystream)
+    pub fn next_64(&mut self) -> Vec<FheUint8> {
+        self.next_keystream_bytes(8)
+            .into_iter()
+            .map(byte_into_fhe_uint8)
+            .collect()
+    }
+
+    /// Trans ciphers bytes encrypted with AES-CTR: they are XORed with the keystream, giving
+    /// the encrypted plaintext. Since the ciphertext is in the clear, this only costs the
+    /// computation of the keystream. Encryption and decryption being the same, this can also
+    /// be used to encrypt a clear message with the encrypted key.
+    pub fn trans_decrypt_bytes(&mut self, ciphertext: &[u8]) -> Vec<FheUint8> {
+        let keystream = self.next_keystream_bytes(ciphertext.len());
+        keystream
+            .par_iter()
+            .zip(ciphertext)
+            .map(|(byte, &clear)| byte_into_fhe_uint8(self.aes.xor_clear(byte, clear)))
+            .collect()
+    }
+
+    pub fn get_server_key(&self) -> &ServerKey {
+        self.aes.get_server_key()
+    }
+}
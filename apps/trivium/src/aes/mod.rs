@@ -0,0 +1,8 @@
+mod aes128;
+pub use aes128::Aes128;
+
+mod aes128_ctr;
+pub use aes128_ctr::Aes128CtrStream;
+
+#[cfg(test)]
+mod test;
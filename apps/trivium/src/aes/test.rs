@@ -0,0 +1,79 @@
+use crate::{Aes128, Aes128CtrStream, TransCiphering};
+use tfhe::prelude::*;
+use tfhe::{generate_keys, ConfigBuilder, FheUint64, FheUint8};
+// Values for these tests come from FIPS-197 (appendix C.1) and NIST SP 800-38A (appendix F.5.1,
+// CTR-AES128.Encrypt)
+
+fn get_bytes_from_hexadecimal_string<const N: usize>(hexadecimal: &str) -> [u8; N] {
+    assert_eq!(hexadecimal.len(), 2 * N);
+    let mut bytes = [0u8; N];
+    for i in (0..hexadecimal.len()).step_by(2) {
+        bytes[i >> 1] = u8::from_str_radix(&hexadecimal[i..i + 2], 16).unwrap();
+    }
+    bytes
+}
+
+fn get_hexadecimal_string_from_bytes(a: Vec<u8>) -> String {
+    let mut hexadecimal: String = "".to_string();
+    for test in a {
+        hexadecimal.push_str(&format!("{:02X?}", test));
+    }
+    hexadecimal
+}
+
+#[test]
+fn aes_test_fips_197() {
+    let config = ConfigBuilder::default().build();
+    let (client_key, server_key) = generate_keys(config);
+
+    let key = get_bytes_from_hexadecimal_string::<16>("000102030405060708090A0B0C0D0E0F");
+    let plaintext = get_bytes_from_hexadecimal_string::<16>("00112233445566778899AABBCCDDEEFF");
+    let ciphertext = "69C4E0D86A7B0430D8CDB78070B4C55A".to_string();
+
+    let cipher_key = key.map(|x| FheUint8::encrypt(x, &client_key));
+
+    let aes = Aes128::new(cipher_key, &server_key);
+
+    let vec = aes
+        .encrypt_block(plaintext)
+        .iter()
+        .map(|c| c.decrypt(&client_key))
+        .collect::<Vec<u8>>();
+
+    assert_eq!(ciphertext, get_hexadecimal_string_from_bytes(vec));
+}
+
+#[test]
+fn aes_ctr_test_fhe_byte_transciphering() {
+    let config = ConfigBuilder::default().build();
+    let (client_key, server_key) = generate_keys(config);
+
+    let key = get_bytes_from_hexadecimal_string::<16>("2B7E151628AED2A6ABF7158809CF4F3C");
+    let initial_counter =
+        get_bytes_from_hexadecimal_string::<16>("F0F1F2F3F4F5F6F7F8F9FAFBFCFDFEFF");
+
+    // First two blocks of the example
+    let ciphertext = get_bytes_from_hexadecimal_string::<32>(
+        "874D6191B620E3261BEF6864990DB6CE9806F66B7970FDFF8617187BB9FFFDFF",
+    );
+    let plaintext = "6BC1BEE22E409F96E93D7E117393172AAE2D8A571E03AC9C9EB76FAC45AF8E51".to_string();
+
+    let cipher_key = key.map(|x| FheUint8::encrypt(x, &client_key));
+
+    let mut aes = Aes128CtrStream::new(cipher_key, initial_counter, &server_key);
+
+    // The first block and a half as bytes
+    let mut vec = aes
+        .trans_decrypt_bytes(&ciphertext[..24])
+        .iter()
+        .map(|c| c.decrypt(&client_key))
+        .collect::<Vec<u8>>();
+
+    // The end of the second block as a 64 bits word
+    let word = u64::from_be_bytes(ciphertext[24..].try_into().unwrap());
+    let trans_ciphered_word = aes.trans_decrypt_64(FheUint64::encrypt(word, &client_key));
+    let word: u64 = trans_ciphered_word.decrypt(&client_key);
+    vec.extend(word.to_be_bytes());
+
+    assert_eq!(plaintext, get_hexadecimal_string_from_bytes(vec));
+}
@@ -8,5 +8,8 @@ pub use kreyvium::{KreyviumStream, KreyviumStreamByte, KreyviumStreamShortint};
 mod trivium;
 pub use trivium::{TriviumStream, TriviumStreamByte, TriviumStreamShortint};
 
+mod aes;
+pub use aes::{Aes128, Aes128CtrStream};
+
 mod trans_ciphering;
 pub use trans_ciphering::TransCiphering;
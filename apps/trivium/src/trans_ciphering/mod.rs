@@ -1,7 +1,10 @@
 //! This module will contain extensions of some TriviumStream of KreyviumStream objects,
-//! when trans ciphering is available to them.
+//! when trans ciphering is available to them, and of the AES-CTR stream.
 
-use crate::{KreyviumStreamByte, KreyviumStreamShortint, TriviumStreamByte, TriviumStreamShortint};
+use crate::{
+    Aes128CtrStream, KreyviumStreamByte, KreyviumStreamShortint, TriviumStreamByte,
+    TriviumStreamShortint,
+};
 use rayon::prelude::*;
 use tfhe::prelude::*;
 use tfhe::shortint::Ciphertext;
@@ -26,12 +29,13 @@ fn transcipher_from_fheu8_stream(
     set_server_key(fhe_server_key.clone());
     rayon::broadcast(|_| set_server_key(fhe_server_key.clone()));
 
-    let ret: FheUint64 = stream
+    let keystream: FheUint64 = stream
         .into_par_iter()
         .enumerate()
-        .map(|(i, x)| &cipher ^ &(FheUint64::cast_from(x) << (8 * (7 - i) as u8)))
+        .map(|(i, x)| FheUint64::cast_from(x) << (8 * (7 - i) as u8))
         .reduce_with(|a, b| a | b)
         .unwrap();
+    let ret = &cipher ^ &keystream;
 
     unset_server_key();
     rayon::broadcast(|_| unset_server_key());
@@ -89,6 +93,14 @@ impl TransCiphering for KreyviumStreamByte<FheUint8> {
     }
 }
 
+impl TransCiphering for Aes128CtrStream {
+    /// Aes128CtrStream: the next 8 bytes of the keystream are each shifted by a number
+    /// in [0, 8), and XORed with the input cipher
+    fn trans_encrypt_64(&mut self, cipher: FheUint64) -> FheUint64 {
+        transcipher_from_fheu8_stream(self.next_64(), cipher, self.get_server_key())
+    }
+}
+
 impl TransCiphering for TriviumStreamShortint {
     /// TriviumStreamShortint: since a full step outputs 64 shortints, these bits
     /// are paired 2 by 2 in the HL parameter space and packed in a full word,
@@ -1,8 +1,8 @@
 use crate::generators::aes_ctr::index::AesIndex;
-use crate::generators::aes_ctr::BYTES_PER_BATCH;
+use crate::generators::aes_ctr::{BYTES_PER_AES_CALL, BYTES_PER_BATCH};
 
 /// Represents a key used in the AES block cipher.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct AesKey(pub u128);
 
 /// A trait for AES block ciphers.
@@ -11,10 +11,19 @@ pub struct AesKey(pub u128);
 /// -----
 ///
 /// The block cipher is used in a batched manner (to reduce amortized cost on special hardware).
-/// For this reason we only expose a `generate_batch` method.
+/// For this reason the main method is `generate_batch`, single blocks can be encrypted with
+/// `encrypt_block` when the block cipher is not used in counter mode.
 pub trait AesBlockCipher: Clone + Send + Sync {
     /// Instantiate a new generator from a secret key.
     fn new(key: AesKey) -> Self;
+    /// Returns the secret key the block cipher was instantiated with.
+    fn key(&self) -> AesKey;
     /// Generates the batch corresponding to the given index.
     fn generate_batch(&mut self, index: AesIndex) -> [u8; BYTES_PER_BATCH];
+
+    /// Encrypts a single block.
+    ///
+    /// This is meant for the rare places where the block cipher is not used in counter mode, and
+    /// avoids the cost of a full batch.
+    fn encrypt_block(&mut self, block: [u8; BYTES_PER_AES_CALL]) -> [u8; BYTES_PER_AES_CALL];
 }
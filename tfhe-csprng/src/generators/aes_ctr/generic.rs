@@ -2,7 +2,11 @@ use crate::generators::aes_ctr::block_cipher::{AesBlockCipher, AesKey};
 use crate::generators::aes_ctr::index::TableIndex;
 use crate::generators::aes_ctr::states::{BufferPointer, ShiftAction, State};
 use crate::generators::aes_ctr::BYTES_PER_BATCH;
-use crate::generators::{ByteCount, BytesPerChild, ChildrenCount, ForkError};
+use crate::generators::{
+    ByteCount, BytesPerChild, ChildrenCount, ForkError, GeneratorState, GeneratorStateError,
+    SeekError,
+};
+use crate::seeders::Seed;
 
 // Usually, to work with iterators and parallel iterators, we would use opaque types such as
 // `impl Iterator<..>`. Unfortunately, it is not yet possible to return existential types in
@@ -82,6 +86,46 @@ impl<BlockCipher: AesBlockCipher> AesCtrGenerator<BlockCipher> {
         }
     }
 
+    /// Generates a csprng resuming the stream at a given position.
+    ///
+    /// Note :
+    /// ------
+    ///
+    /// Contrary to [`AesCtrGenerator::from_block_cipher`], the `next_index` may be equal to the
+    /// `bound_index`, in which case the generator is exhausted.
+    pub fn resume(
+        key: AesKey,
+        next_index: TableIndex,
+        bound_index: TableIndex,
+    ) -> Result<AesCtrGenerator<BlockCipher>, GeneratorStateError> {
+        if next_index == TableIndex::FIRST {
+            return Err(GeneratorStateError::FirstTableIndex);
+        }
+        if next_index > bound_index {
+            return Err(GeneratorStateError::IndexAfterBound);
+        }
+        Ok(AesCtrGenerator {
+            block_cipher: Box::new(BlockCipher::new(key)),
+            state: State::new(next_index),
+            last: bound_index.decremented(),
+            buffer: [0u8; BYTES_PER_BATCH],
+        })
+    }
+
+    /// Returns the key used by the generator.
+    pub fn key(&self) -> AesKey {
+        self.block_cipher.key()
+    }
+
+    /// Returns a snapshot of the generator state.
+    pub fn state(&self) -> GeneratorState {
+        GeneratorState {
+            seed: Seed(self.key().0),
+            next_index: self.table_index().incremented(),
+            bound: self.get_bound(),
+        }
+    }
+
     /// Returns the table index related to the previous random byte.
     pub fn table_index(&self) -> TableIndex {
         self.state.table_index()
@@ -117,6 +161,24 @@ impl<BlockCipher: AesBlockCipher> AesCtrGenerator<BlockCipher> {
             .expect("Tried to generate a byte after the bound.")
     }
 
+    /// Moves the generator forward so that the next outputted byte is the one at `index`.
+    ///
+    /// Note :
+    /// ------
+    ///
+    /// Moving backward is not allowed, since it would make the generator output the same bytes
+    /// twice. Moving to the bound is allowed, and leaves the generator exhausted.
+    pub fn seek(&mut self, index: TableIndex) -> Result<(), SeekError> {
+        if index < self.table_index().incremented() {
+            return Err(SeekError::BackwardSeek);
+        }
+        if index > self.get_bound() {
+            return Err(SeekError::SeekAfterBound);
+        }
+        self.state = State::new(index);
+        Ok(())
+    }
+
     /// Tries to fork the current generator into `n_child` generators each able to output
     /// `child_bytes` random bytes.
     pub fn try_fork(
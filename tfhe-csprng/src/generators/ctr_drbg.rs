@@ -0,0 +1,663 @@
+//! A module implementing the CTR_DRBG deterministic random bit generator.
+use crate::generators::aes_ctr::{AesBlockCipher, AesKey, BYTES_PER_AES_CALL};
+use crate::seeders::{Seed, Seeder};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// The length of a block of the AES block cipher in bytes (`outlen` in the recommendation).
+const BLOCK_LEN: usize = BYTES_PER_AES_CALL;
+/// The length of an AES-128 key in bytes (`keylen` in the recommendation).
+const KEY_LEN: usize = 16;
+/// The length of the seed material in bytes (`seedlen` in the recommendation).
+const SEED_LEN: usize = KEY_LEN + BLOCK_LEN;
+
+/// The maximal number of bytes that can be generated by a single request.
+pub const MAX_BYTES_PER_REQUEST: usize = 1 << 16;
+/// The maximal number of requests between two reseeds.
+pub const MAX_RESEED_INTERVAL: u64 = 1 << 48;
+/// The maximal length in bytes of personalization strings and additional inputs.
+pub const MAX_INPUT_LENGTH: u64 = u32::MAX as u64;
+
+type Block = [u8; BLOCK_LEN];
+type SeedMaterial = [u8; SEED_LEN];
+
+/// An error occurring when using a [`CtrDrbg`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CtrDrbgError {
+    RequestTooLarge(usize),
+    InputTooLong(usize),
+    InvalidReseedInterval(u64),
+    HealthTestFailed,
+}
+
+impl Display for CtrDrbgError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CtrDrbgError::RequestTooLarge(requested) => {
+                write!(
+                    f,
+                    "Requested {requested} bytes, but at most {MAX_BYTES_PER_REQUEST} bytes can be \
+                    generated per request."
+                )
+            }
+            CtrDrbgError::InputTooLong(length) => {
+                write!(
+                    f,
+                    "Input of {length} bytes is too long, at most {MAX_INPUT_LENGTH} bytes are \
+                    allowed."
+                )
+            }
+            CtrDrbgError::InvalidReseedInterval(interval) => {
+                write!(
+                    f,
+                    "Invalid reseed interval {interval}, it must be in [1, {MAX_RESEED_INTERVAL}]."
+                )
+            }
+            CtrDrbgError::HealthTestFailed => {
+                write!(f, "The CTR_DRBG known-answer health test failed.")
+            }
+        }
+    }
+}
+impl Error for CtrDrbgError {}
+
+fn key_from_bytes(bytes: &[u8]) -> AesKey {
+    // The block ciphers use the native-endian bytes of the key.
+    AesKey(u128::from_ne_bytes(bytes.try_into().unwrap()))
+}
+
+fn xor_in_place(lhs: &mut [u8], rhs: &[u8]) {
+    lhs.iter_mut().zip(rhs.iter()).for_each(|(l, r)| *l ^= r);
+}
+
+/// The `BCC` function of the recommendation (section 10.3.3), with `data = iv || s`.
+fn bcc<BlockCipher: AesBlockCipher>(cipher: &mut BlockCipher, iv: Block, s: &[u8]) -> Block {
+    let mut chaining_value = cipher.encrypt_block(iv);
+    for block in s.chunks_exact(BLOCK_LEN) {
+        xor_in_place(&mut chaining_value, block);
+        chaining_value = cipher.encrypt_block(chaining_value);
+    }
+    chaining_value
+}
+
+/// The `Block_Cipher_df` function of the recommendation (section 10.3.2), applied to the
+/// concatenation of `inputs`, and returning `seedlen` bits.
+///
+/// The length of the concatenation is encoded on 32 bits, longer inputs are rejected.
+fn block_cipher_df<BlockCipher: AesBlockCipher>(
+    inputs: &[&[u8]],
+) -> Result<SeedMaterial, CtrDrbgError> {
+    let input_len: usize = inputs.iter().map(|input| input.len()).sum();
+    let encoded_input_len =
+        u32::try_from(input_len).map_err(|_| CtrDrbgError::InputTooLong(input_len))?;
+
+    // S = L || N || input_string || 0x80, padded with zeros to a multiple of the block length.
+    let mut s = Vec::with_capacity(input_len + 2 * BLOCK_LEN);
+    s.extend_from_slice(&encoded_input_len.to_be_bytes());
+    s.extend_from_slice(&(SEED_LEN as u32).to_be_bytes());
+    inputs.iter().for_each(|input| s.extend_from_slice(input));
+    s.push(0x80);
+    while s.len() % BLOCK_LEN != 0 {
+        s.push(0);
+    }
+
+    let df_key: [u8; KEY_LEN] = std::array::from_fn(|i| i as u8);
+    let mut cipher = BlockCipher::new(key_from_bytes(&df_key));
+    let mut temp = [0u8; SEED_LEN];
+    for (i, chunk) in temp.chunks_exact_mut(BLOCK_LEN).enumerate() {
+        let mut iv = [0u8; BLOCK_LEN];
+        iv[..4].copy_from_slice(&(i as u32).to_be_bytes());
+        chunk.copy_from_slice(&bcc(&mut cipher, iv, &s));
+    }
+
+    let mut cipher = BlockCipher::new(key_from_bytes(&temp[..KEY_LEN]));
+    let mut x: Block = temp[KEY_LEN..].try_into().unwrap();
+    let mut output = [0u8; SEED_LEN];
+    for chunk in output.chunks_exact_mut(BLOCK_LEN) {
+        x = cipher.encrypt_block(x);
+        chunk.copy_from_slice(&x);
+    }
+    Ok(output)
+}
+
+/// The working state of the mechanism, and the raw algorithms of the recommendation.
+#[derive(Clone)]
+struct CtrDrbgState<BlockCipher: AesBlockCipher> {
+    cipher: BlockCipher,
+    v: Block,
+    reseed_counter: u64,
+}
+
+impl<BlockCipher: AesBlockCipher> CtrDrbgState<BlockCipher> {
+    /// `CTR_DRBG_Instantiate_algorithm` (section 10.2.1.3.2).
+    fn instantiate(
+        entropy_input: &[u8],
+        nonce: &[u8],
+        personalization_string: &[u8],
+    ) -> Result<Self, CtrDrbgError> {
+        let seed_material =
+            block_cipher_df::<BlockCipher>(&[entropy_input, nonce, personalization_string])?;
+        let mut state = CtrDrbgState {
+            cipher: BlockCipher::new(key_from_bytes(&[0u8; KEY_LEN])),
+            v: [0u8; BLOCK_LEN],
+            reseed_counter: 1,
+        };
+        state.update(&seed_material);
+        Ok(state)
+    }
+
+    /// `CTR_DRBG_Reseed_algorithm` (section 10.2.1.4.2).
+    fn reseed(
+        &mut self,
+        entropy_input: &[u8],
+        additional_input: &[u8],
+    ) -> Result<(), CtrDrbgError> {
+        let seed_material = block_cipher_df::<BlockCipher>(&[entropy_input, additional_input])?;
+        self.update(&seed_material);
+        self.reseed_counter = 1;
+        Ok(())
+    }
+
+    /// `CTR_DRBG_Generate_algorithm` (section 10.2.1.5.2).
+    ///
+    /// The caller is responsible for checking the request size and the reseed counter.
+    fn generate(&mut self, output: &mut [u8], additional_input: &[u8]) -> Result<(), CtrDrbgError> {
+        let additional_input = if additional_input.is_empty() {
+            [0u8; SEED_LEN]
+        } else {
+            let additional_input = block_cipher_df::<BlockCipher>(&[additional_input])?;
+            self.update(&additional_input);
+            additional_input
+        };
+
+        for chunk in output.chunks_mut(BLOCK_LEN) {
+            self.increment_v();
+            let block = self.cipher.encrypt_block(self.v);
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+
+        self.update(&additional_input);
+        self.reseed_counter += 1;
+        Ok(())
+    }
+
+    /// `CTR_DRBG_Update` (section 10.2.1.2).
+    fn update(&mut self, provided_data: &SeedMaterial) {
+        let mut temp = [0u8; SEED_LEN];
+        for chunk in temp.chunks_exact_mut(BLOCK_LEN) {
+            self.increment_v();
+            chunk.copy_from_slice(&self.cipher.encrypt_block(self.v));
+        }
+        xor_in_place(&mut temp, provided_data);
+        self.cipher = BlockCipher::new(key_from_bytes(&temp[..KEY_LEN]));
+        self.v.copy_from_slice(&temp[KEY_LEN..]);
+    }
+
+    fn increment_v(&mut self) {
+        self.v = u128::from_be_bytes(self.v).wrapping_add(1).to_be_bytes();
+    }
+}
+
+/// A CTR_DRBG generator, drawing its entropy from a [`Seeder`].
+///
+/// This structure implements the CTR_DRBG mechanism of the
+/// [NIST SP 800-90A Rev. 1](https://doi.org/10.6028/NIST.SP.800-90Ar1) recommendation, using
+/// AES-128 as block cipher and the block cipher derivation function. Contrary to the
+/// [`RandomGenerator`](super::RandomGenerator) implementations, which expand a single seed into a
+/// very long stream, this generator:
+/// + updates its internal state after each request, providing backtracking resistance,
+/// + is periodically reseeded from a [`Seeder`], every `reseed_interval` requests,
+/// + can optionally be reseeded before every request, providing prediction resistance,
+/// + accepts additional inputs on instantiation (personalization string), reseed and generation,
+/// + runs a known-answer health test before being instantiated.
+///
+/// Since it implements the [`Seeder`] trait itself, a [`CtrDrbg`] can be used to seed the other
+/// generators of this crate.
+pub struct CtrDrbg<BlockCipher: AesBlockCipher, S: Seeder> {
+    state: CtrDrbgState<BlockCipher>,
+    seeder: S,
+    reseed_interval: u64,
+    prediction_resistance: bool,
+}
+
+impl<BlockCipher: AesBlockCipher, S: Seeder> CtrDrbg<BlockCipher, S> {
+    /// Instantiates a new generator.
+    ///
+    /// The entropy input and the nonce are drawn from `seeder`, and the `personalization_string`
+    /// is mixed in the initial state. Before instantiation, a known-answer health test of the
+    /// mechanism is performed.
+    ///
+    /// The generator is created without prediction resistance, and with the maximal reseed
+    /// interval.
+    pub fn new(mut seeder: S, personalization_string: &[u8]) -> Result<Self, CtrDrbgError> {
+        check_input_length(personalization_string)?;
+        Self::health_test()?;
+
+        let entropy_input = seeder.seed().0.to_le_bytes();
+        let nonce = seeder.seed().0.to_le_bytes();
+        Ok(CtrDrbg {
+            state: CtrDrbgState::instantiate(&entropy_input, &nonce, personalization_string)?,
+            seeder,
+            reseed_interval: MAX_RESEED_INTERVAL,
+            prediction_resistance: false,
+        })
+    }
+
+    /// Sets the number of requests after which the generator is automatically reseeded.
+    pub fn set_reseed_interval(&mut self, reseed_interval: u64) -> Result<(), CtrDrbgError> {
+        if reseed_interval == 0 || reseed_interval > MAX_RESEED_INTERVAL {
+            return Err(CtrDrbgError::InvalidReseedInterval(reseed_interval));
+        }
+        self.reseed_interval = reseed_interval;
+        Ok(())
+    }
+
+    /// Returns the number of requests after which the generator is automatically reseeded.
+    pub fn reseed_interval(&self) -> u64 {
+        self.reseed_interval
+    }
+
+    /// Enables or disables prediction resistance, that is, reseeding before every request.
+    pub fn set_prediction_resistance(&mut self, prediction_resistance: bool) {
+        self.prediction_resistance = prediction_resistance;
+    }
+
+    /// Returns whether prediction resistance is enabled.
+    pub fn prediction_resistance(&self) -> bool {
+        self.prediction_resistance
+    }
+
+    /// Returns the number of requests served since the last (re)seeding, plus one.
+    pub fn reseed_counter(&self) -> u64 {
+        self.state.reseed_counter
+    }
+
+    /// Reseeds the generator with a fresh entropy input drawn from the seeder.
+    pub fn reseed(&mut self, additional_input: &[u8]) -> Result<(), CtrDrbgError> {
+        check_input_length(additional_input)?;
+        let entropy_input = self.seeder.seed().0.to_le_bytes();
+        self.state.reseed(&entropy_input, additional_input)
+    }
+
+    /// Fills `output` with pseudo-random bytes.
+    ///
+    /// The generator is reseeded beforehand if prediction resistance is enabled or if the reseed
+    /// interval was reached. In this case, the `additional_input` is used for the reseed.
+    pub fn generate(
+        &mut self,
+        output: &mut [u8],
+        additional_input: &[u8],
+    ) -> Result<(), CtrDrbgError> {
+        if output.len() > MAX_BYTES_PER_REQUEST {
+            return Err(CtrDrbgError::RequestTooLarge(output.len()));
+        }
+        check_input_length(additional_input)?;
+
+        if self.prediction_resistance || self.state.reseed_counter > self.reseed_interval {
+            self.reseed(additional_input)?;
+            self.state.generate(output, &[])
+        } else {
+            self.state.generate(output, additional_input)
+        }
+    }
+
+    /// Runs the known-answer health test of the mechanism.
+    ///
+    /// The test instantiates the mechanism with prediction resistance, and checks the output
+    /// against the `[AES-128 use df]`, `[PredictionResistance = True]` vector of the NIST CAVP
+    /// `CTR_DRBG.rsp` file.
+    pub fn health_test() -> Result<(), CtrDrbgError> {
+        let mut state = CtrDrbgState::<BlockCipher>::instantiate(
+            &HEALTH_TEST_ENTROPY_INPUT,
+            &HEALTH_TEST_NONCE,
+            &HEALTH_TEST_PERSONALIZATION_STRING,
+        )?;
+        let mut output = [0u8; HEALTH_TEST_EXPECTED.len()];
+        state.reseed(
+            &HEALTH_TEST_ENTROPY_INPUT_PR_0,
+            &HEALTH_TEST_ADDITIONAL_INPUT_0,
+        )?;
+        state.generate(&mut output, &[])?;
+        state.reseed(
+            &HEALTH_TEST_ENTROPY_INPUT_PR_1,
+            &HEALTH_TEST_ADDITIONAL_INPUT_1,
+        )?;
+        state.generate(&mut output, &[])?;
+        if output == HEALTH_TEST_EXPECTED {
+            Ok(())
+        } else {
+            Err(CtrDrbgError::HealthTestFailed)
+        }
+    }
+}
+
+impl<BlockCipher: AesBlockCipher, S: Seeder> Seeder for CtrDrbg<BlockCipher, S> {
+    fn seed(&mut self) -> Seed {
+        let mut bytes = [0u8; 16];
+        self.generate(&mut bytes, &[])
+            .expect("Failed to generate a seed with the CTR_DRBG generator.");
+        Seed(u128::from_le_bytes(bytes))
+    }
+
+    fn is_available() -> bool {
+        S::is_available()
+    }
+}
+
+fn check_input_length(input: &[u8]) -> Result<(), CtrDrbgError> {
+    if input.len() as u64 > MAX_INPUT_LENGTH {
+        Err(CtrDrbgError::InputTooLong(input.len()))
+    } else {
+        Ok(())
+    }
+}
+
+// CTR_DRBG.rsp, [AES-128 use df], [PredictionResistance = True], [EntropyInputLen = 128],
+// [NonceLen = 64], [PersonalizationStringLen = 128], [AdditionalInputLen = 128],
+// [ReturnedBitsLen = 512], COUNT = 0
+const HEALTH_TEST_ENTROPY_INPUT: [u8; 16] = [
+    0x92, 0x89, 0x8f, 0x31, 0xfa, 0x1c, 0xff, 0x6d, 0x18, 0x2f, 0x26, 0x06, 0x43, 0xdf, 0xf8, 0x18,
+];
+const HEALTH_TEST_NONCE: [u8; 8] = [0xc2, 0xa4, 0xd9, 0x72, 0xc3, 0xb9, 0xb6, 0x97];
+const HEALTH_TEST_PERSONALIZATION_STRING: [u8; 16] = [
+    0xea, 0x65, 0xee, 0x60, 0x26, 0x4e, 0x7e, 0xb6, 0x0e, 0x82, 0x68, 0xc4, 0x37, 0x3c, 0x5c, 0x0b,
+];
+const HEALTH_TEST_ENTROPY_INPUT_PR_0: [u8; 16] = [
+    0x20, 0x72, 0x8a, 0x06, 0xf8, 0x6f, 0x8d, 0xd4, 0x41, 0xe2, 0x72, 0xb7, 0xc4, 0x2c, 0xe8, 0x10,
+];
+const HEALTH_TEST_ENTROPY_INPUT_PR_1: [u8; 16] = [
+    0x3d, 0xb0, 0xf0, 0x94, 0xf3, 0x05, 0x50, 0x33, 0x17, 0x86, 0x3e, 0x22, 0x08, 0xf7, 0xa5, 0x01,
+];
+const HEALTH_TEST_ADDITIONAL_INPUT_0: [u8; 16] = [
+    0x1a, 0x40, 0xfa, 0xe3, 0xcc, 0x6c, 0x7c, 0xa0, 0xf8, 0xda, 0xba, 0x59, 0x23, 0x6d, 0xad, 0x1d,
+];
+const HEALTH_TEST_ADDITIONAL_INPUT_1: [u8; 16] = [
+    0x9f, 0x72, 0x76, 0x6c, 0xc7, 0x46, 0xe5, 0xed, 0x2e, 0x53, 0x20, 0x12, 0xbc, 0x59, 0x31, 0x8c,
+];
+const HEALTH_TEST_EXPECTED: [u8; 64] = [
+    0x5a, 0x35, 0x39, 0x87, 0x0f, 0x4d, 0x22, 0xa4, 0x09, 0x24, 0xee, 0x71, 0xc9, 0x6f, 0xac, 0x72,
+    0x0a, 0xd6, 0xf0, 0x88, 0x82, 0xd0, 0x83, 0x28, 0x73, 0xec, 0x3f, 0x93, 0xd8, 0xab, 0x45, 0x23,
+    0xf0, 0x7e, 0xac, 0x45, 0x14, 0x5e, 0x93, 0x9f, 0xb1, 0xd6, 0x76, 0x43, 0x3d, 0xb6, 0xe8, 0x08,
+    0x88, 0xf6, 0xda, 0x89, 0x08, 0x77, 0x42, 0xfe, 0x1a, 0xf4, 0x3f, 0xc4, 0x23, 0xc5, 0x1f, 0x68,
+];
+
+#[cfg(test)]
+#[allow(unused)] // to please clippy when tests are not activated
+pub mod ctr_drbg_generic_test {
+    use super::*;
+
+    fn hex(string: &str) -> Vec<u8> {
+        (0..string.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&string[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    /// A seeder replaying a fixed list of entropy inputs.
+    struct ReplaySeeder(std::vec::IntoIter<u128>);
+
+    impl Seeder for ReplaySeeder {
+        fn seed(&mut self) -> Seed {
+            Seed(self.0.next().expect("No more entropy to replay."))
+        }
+
+        fn is_available() -> bool {
+            true
+        }
+    }
+
+    /// A CAVP test vector with `[AES-128 use df]`, `[EntropyInputLen = 128]`,
+    /// `[NonceLen = 64]` and `[ReturnedBitsLen = 512]`.
+    struct KnownAnswer {
+        entropy_input: &'static str,
+        nonce: &'static str,
+        personalization_string: &'static str,
+        entropy_input_reseed: &'static str,
+        additional_input_reseed: &'static str,
+        additional_input_0: &'static str,
+        additional_input_1: &'static str,
+        returned_bits: &'static str,
+    }
+
+    // CTR_DRBG.rsp (CAVS 14.3), [PredictionResistance = False] vectors.
+    const NO_RESEED_KNOWN_ANSWERS: [KnownAnswer; 4] = [
+        KnownAnswer {
+            entropy_input: "890eb067acf7382eff80b0c73bc872c6",
+            nonce: "aad471ef3ef1d203",
+            personalization_string: "",
+            entropy_input_reseed: "",
+            additional_input_reseed: "",
+            additional_input_0: "",
+            additional_input_1: "",
+            returned_bits: "a5514ed7095f64f3d0d3a5760394ab42062f373a25072a6ea6bcfd8489e94af6\
+                            cf18659fea22ed1ca0a9e33f718b115ee536b12809c31b72b08ddd8be1910fa3",
+        },
+        KnownAnswer {
+            entropy_input: "b408cefb5bc7157d3f26cb95a8b1d7ac",
+            nonce: "026c768fd577b92a",
+            personalization_string: "",
+            entropy_input_reseed: "",
+            additional_input_reseed: "",
+            additional_input_0: "5737ef81dee365b6dadb3feebf5d1084",
+            additional_input_1: "3368a516b3431a3daaa60dc8743c8297",
+            returned_bits: "4e909ebb24147a0004063a5e47ee044fead610d62324bd0f963f756fb91361e8\
+                            b87e3a76a398143fe88130fe1b547b661a6480c711b739f18a9df3ae51d41bc9",
+        },
+        KnownAnswer {
+            entropy_input: "2d2ab564202918c4ef5b102dda385a18",
+            nonce: "259195269ec11af6",
+            personalization_string: "",
+            entropy_input_reseed: "",
+            additional_input_reseed: "",
+            additional_input_0: "",
+            additional_input_1: "",
+            returned_bits: "2c5cd79ed87622a91b8654c8903d852242cd49cb5df2d4b4150584301c59f01f\
+                            d95a702ac157c84cc15f42c8211335672d8ce1291ef9b1def78149a04fa2697c",
+        },
+        KnownAnswer {
+            entropy_input: "8b80936e69c67edb771c28f9b9452124",
+            nonce: "7ee2614ead3c128e",
+            personalization_string: "",
+            entropy_input_reseed: "",
+            additional_input_reseed: "",
+            additional_input_0: "fc35cba97a1e211bc420e8af53f8e13c",
+            additional_input_1: "fba438aaa75a3cd4cd0cce399bfec74a",
+            returned_bits: "6721cc1ada5ebc1713f74c759000765652eeb5f3f9c24fb9341b36a369cec1d2\
+                            7ea80d6b73b56047af07138c5a43c99a87753115c471b8587ea65fa2065e3ce0",
+        },
+    ];
+
+    // CTR_DRBG.rsp (CAVS 14.3), [PredictionResistance = False] vector with a reseed before the
+    // first generation.
+    const RESEED_KNOWN_ANSWER: KnownAnswer = KnownAnswer {
+        entropy_input: "0f65da13dca407999d4773c2b4a11d85",
+        nonce: "5209e5b4ed82a234",
+        personalization_string: "",
+        entropy_input_reseed: "1dea0a12c52bf64339dd291c80d8ca89",
+        additional_input_reseed: "",
+        additional_input_0: "",
+        additional_input_1: "",
+        returned_bits: "2859cc468a76b08661ffd23b28547ffd0997ad526a0f51261b99ed3a37bd407b\
+                        f418dbe6c6c3e26ed0ddefcb7474d899bd99f3655427519fc5b4057bcaf306d4",
+    };
+
+    // CTR_DRBG.rsp (CAVS 14.3), [PredictionResistance = True] vectors, where the two entropy
+    // inputs used for prediction resistance are stored in `entropy_input_reseed`.
+    const PREDICTION_RESISTANCE_KNOWN_ANSWERS: [KnownAnswer; 2] = [
+        KnownAnswer {
+            entropy_input: "5d4041942bcf68864a4997d8171f1f9f",
+            nonce: "d4f1f4ae08bcb3e1",
+            personalization_string: "",
+            entropy_input_reseed: "ef55a769b7eaf03fe082029bb32a2b9d\
+                                   8239e865c0a42e14b964b9c09de85a20",
+            additional_input_reseed: "",
+            additional_input_0: "",
+            additional_input_1: "",
+            returned_bits: "4155320287eedcf7d484c2c2a1e2eb64b9c9ce77c87202a1ae1616c7a5cfd1c6\
+                            87c7a0bfcc85bda48fdd4629fd330c22d0a76076f88fc7cd04037ee06b7af602",
+        },
+        KnownAnswer {
+            entropy_input: "92898f31fa1cff6d182f260643dff818",
+            nonce: "c2a4d972c3b9b697",
+            personalization_string: "ea65ee60264e7eb60e8268c4373c5c0b",
+            entropy_input_reseed: "20728a06f86f8dd441e272b7c42ce810\
+                                   3db0f094f305503317863e2208f7a501",
+            additional_input_reseed: "",
+            additional_input_0: "1a40fae3cc6c7ca0f8daba59236dad1d",
+            additional_input_1: "9f72766cc746e5ed2e532012bc59318c",
+            returned_bits: "5a3539870f4d22a40924ee71c96fac720ad6f08882d0832873ec3f93d8ab4523\
+                            f07eac45145e939fb1d676433db6e80888f6da89087742fe1af43fc423c51f68",
+        },
+    ];
+
+    fn check_known_answer<BlockCipher: AesBlockCipher>(
+        known_answer: &KnownAnswer,
+        prediction_resistance: bool,
+    ) {
+        let mut state = CtrDrbgState::<BlockCipher>::instantiate(
+            &hex(known_answer.entropy_input),
+            &hex(known_answer.nonce),
+            &hex(known_answer.personalization_string),
+        )
+        .unwrap();
+        let entropy_input_reseed = hex(known_answer.entropy_input_reseed);
+        let mut entropy_input_reseed = entropy_input_reseed.chunks_exact(16);
+        let additional_inputs = [
+            hex(known_answer.additional_input_0),
+            hex(known_answer.additional_input_1),
+        ];
+        let expected = hex(known_answer.returned_bits);
+        let mut output = vec![0u8; expected.len()];
+
+        if !prediction_resistance {
+            if let Some(entropy_input) = entropy_input_reseed.next() {
+                state
+                    .reseed(entropy_input, &hex(known_answer.additional_input_reseed))
+                    .unwrap();
+            }
+        }
+        for additional_input in additional_inputs.iter() {
+            if prediction_resistance {
+                state
+                    .reseed(entropy_input_reseed.next().unwrap(), additional_input)
+                    .unwrap();
+                state.generate(&mut output, &[]).unwrap();
+            } else {
+                state.generate(&mut output, additional_input).unwrap();
+            }
+        }
+        assert_eq!(output, expected);
+    }
+
+    /// Checks the mechanism against NIST CAVP known-answer tests.
+    pub fn test_known_answers<BlockCipher: AesBlockCipher>() {
+        for known_answer in NO_RESEED_KNOWN_ANSWERS.iter() {
+            check_known_answer::<BlockCipher>(known_answer, false);
+        }
+        check_known_answer::<BlockCipher>(&RESEED_KNOWN_ANSWER, false);
+        for known_answer in PREDICTION_RESISTANCE_KNOWN_ANSWERS.iter() {
+            check_known_answer::<BlockCipher>(known_answer, true);
+        }
+    }
+
+    /// Checks that a generator drawing entropy from a seeder behaves as the mechanism driven
+    /// with the same entropy inputs, in prediction resistance mode.
+    pub fn test_seeder_prediction_resistance<BlockCipher: AesBlockCipher>() {
+        let known_answer = &PREDICTION_RESISTANCE_KNOWN_ANSWERS[1];
+        // The seeder delivers 128 bits of entropy, so the nonce is extended to 128 bits, and the
+        // expected output is computed with the raw mechanism.
+        let entropies: Vec<u128> = [
+            known_answer.entropy_input,
+            "c2a4d972c3b9b697c2a4d972c3b9b697",
+            &known_answer.entropy_input_reseed[..32],
+            &known_answer.entropy_input_reseed[32..],
+        ]
+        .iter()
+        .map(|entropy| u128::from_le_bytes(hex(entropy).try_into().unwrap()))
+        .collect();
+
+        let mut state = CtrDrbgState::<BlockCipher>::instantiate(
+            &entropies[0].to_le_bytes(),
+            &entropies[1].to_le_bytes(),
+            &hex(known_answer.personalization_string),
+        )
+        .unwrap();
+        let mut expected = [0u8; 64];
+        for (entropy, additional_input) in entropies[2..].iter().zip([
+            known_answer.additional_input_0,
+            known_answer.additional_input_1,
+        ]) {
+            state
+                .reseed(&entropy.to_le_bytes(), &hex(additional_input))
+                .unwrap();
+            state.generate(&mut expected, &[]).unwrap();
+        }
+
+        let mut drbg = CtrDrbg::<BlockCipher, _>::new(
+            ReplaySeeder(entropies.into_iter()),
+            &hex(known_answer.personalization_string),
+        )
+        .unwrap();
+        drbg.set_prediction_resistance(true);
+        let mut output = [0u8; 64];
+        drbg.generate(&mut output, &hex(known_answer.additional_input_0))
+            .unwrap();
+        drbg.generate(&mut output, &hex(known_answer.additional_input_1))
+            .unwrap();
+        assert_eq!(output, expected);
+    }
+
+    /// Checks that the generator is reseeded once the reseed interval is reached.
+    pub fn test_reseed_interval<BlockCipher: AesBlockCipher>() {
+        const INTERVAL: u64 = 5;
+        let entropies: Vec<u128> = (0..4).collect();
+        let mut drbg =
+            CtrDrbg::<BlockCipher, _>::new(ReplaySeeder(entropies.into_iter()), b"").unwrap();
+        assert_eq!(
+            drbg.set_reseed_interval(0),
+            Err(CtrDrbgError::InvalidReseedInterval(0))
+        );
+        assert_eq!(
+            drbg.set_reseed_interval(MAX_RESEED_INTERVAL + 1),
+            Err(CtrDrbgError::InvalidReseedInterval(MAX_RESEED_INTERVAL + 1))
+        );
+        drbg.set_reseed_interval(INTERVAL).unwrap();
+
+        let mut output = [0u8; 16];
+        for expected_counter in 1..=INTERVAL {
+            assert_eq!(drbg.reseed_counter(), expected_counter);
+            drbg.generate(&mut output, &[]).unwrap();
+        }
+        // The next request triggers a reseed, which consumes an entropy input.
+        drbg.generate(&mut output, &[]).unwrap();
+        assert_eq!(drbg.reseed_counter(), 2);
+        assert_eq!(drbg.seeder.0.len(), 1);
+    }
+
+    /// Checks the limits on request sizes.
+    pub fn test_request_too_large<BlockCipher: AesBlockCipher>() {
+        let entropies: Vec<u128> = (0..2).collect();
+        let mut drbg =
+            CtrDrbg::<BlockCipher, _>::new(ReplaySeeder(entropies.into_iter()), b"").unwrap();
+        let mut output = vec![0u8; MAX_BYTES_PER_REQUEST + 1];
+        assert_eq!(
+            drbg.generate(&mut output, &[]),
+            Err(CtrDrbgError::RequestTooLarge(MAX_BYTES_PER_REQUEST + 1))
+        );
+        drbg.generate(&mut output[..MAX_BYTES_PER_REQUEST], &[])
+            .unwrap();
+    }
+
+    /// Checks that inputs whose length does not fit the 32 bits length encoding of the derivation
+    /// function are rejected.
+    #[cfg(target_pointer_width = "64")]
+    pub fn test_input_too_long<BlockCipher: AesBlockCipher>() {
+        const CHUNK_LEN: usize = 1 << 20;
+        let chunk = vec![0u8; CHUNK_LEN];
+        let inputs = vec![chunk.as_slice(); (MAX_INPUT_LENGTH as usize + 1) / CHUNK_LEN];
+        assert_eq!(
+            block_cipher_df::<BlockCipher>(&inputs),
+            Err(CtrDrbgError::InputTooLong(MAX_INPUT_LENGTH as usize + 1))
+        );
+    }
+}
@@ -7,3 +7,13 @@ pub type DefaultRandomGenerator = super::NeonAesRandomGenerator;
     not(any(target_arch = "x86_64", target_arch = "aarch64"))
 ))]
 pub type DefaultRandomGenerator = super::SoftwareRandomGenerator;
+
+#[cfg(all(target_arch = "x86_64", not(feature = "software-prng")))]
+pub type DefaultCtrDrbg<S> = super::AesniCtrDrbg<S>;
+#[cfg(all(target_arch = "aarch64", not(feature = "software-prng")))]
+pub type DefaultCtrDrbg<S> = super::NeonAesCtrDrbg<S>;
+#[cfg(any(
+    feature = "software-prng",
+    not(any(target_arch = "x86_64", target_arch = "aarch64"))
+))]
+pub type DefaultCtrDrbg<S> = super::SoftwareCtrDrbg<S>;
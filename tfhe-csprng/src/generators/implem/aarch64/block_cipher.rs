@@ -1,4 +1,6 @@
-use crate::generators::aes_ctr::{AesBlockCipher, AesIndex, AesKey, BYTES_PER_BATCH};
+use crate::generators::aes_ctr::{
+    AesBlockCipher, AesIndex, AesKey, BYTES_PER_AES_CALL, BYTES_PER_BATCH,
+};
 use core::arch::aarch64::{
     uint8x16_t, vaeseq_u8, vaesmcq_u8, vdupq_n_u32, vdupq_n_u8, veorq_u8, vgetq_lane_u32,
     vreinterpretq_u32_u8, vreinterpretq_u8_u32,
@@ -15,6 +17,8 @@ const NUM_ROUND_KEYS: usize = NUM_ROUNDS + 1;
 #[derive(Clone)]
 pub struct ArmAesBlockCipher {
     round_keys: [uint8x16_t; NUM_ROUND_KEYS],
+    // The key used to generate the round keys
+    key: AesKey,
 }
 
 impl AesBlockCipher for ArmAesBlockCipher {
@@ -32,7 +36,11 @@ impl AesBlockCipher for ArmAesBlockCipher {
         }
 
         let round_keys = unsafe { generate_round_keys(key) };
-        ArmAesBlockCipher { round_keys }
+        ArmAesBlockCipher { round_keys, key }
+    }
+
+    fn key(&self) -> AesKey {
+        self.key
     }
 
     fn generate_batch(&mut self, AesIndex(aes_ctr): AesIndex) -> [u8; BYTES_PER_BATCH] {
@@ -54,6 +62,18 @@ impl AesBlockCipher for ArmAesBlockCipher {
         // SAFETY: we checked for aes and neon availability in `Self::new`
         unsafe { implementation(self, AesIndex(aes_ctr)) }
     }
+
+    fn encrypt_block(&mut self, block: [u8; BYTES_PER_AES_CALL]) -> [u8; BYTES_PER_AES_CALL] {
+        #[target_feature(enable = "aes,neon")]
+        unsafe fn implementation(
+            this: &ArmAesBlockCipher,
+            block: [u8; BYTES_PER_AES_CALL],
+        ) -> [u8; BYTES_PER_AES_CALL] {
+            encrypt(u128::from_ne_bytes(block), &this.round_keys).to_ne_bytes()
+        }
+        // SAFETY: we checked for aes and neon availability in `Self::new`
+        unsafe { implementation(self, block) }
+    }
 }
 
 /// Does the AES SubWord operation for the Key Expansion step
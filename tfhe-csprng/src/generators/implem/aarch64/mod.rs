@@ -10,6 +10,9 @@ mod block_cipher;
 mod generator;
 pub use generator::*;
 
+/// A CTR_DRBG generator, using the same block cipher as [`NeonAesRandomGenerator`].
+pub type NeonAesCtrDrbg<S> = crate::generators::CtrDrbg<block_cipher::ArmAesBlockCipher, S>;
+
 #[cfg(feature = "parallel")]
 mod parallel;
 #[cfg(feature = "parallel")]
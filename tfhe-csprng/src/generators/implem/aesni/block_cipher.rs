@@ -1,4 +1,6 @@
-use crate::generators::aes_ctr::{AesBlockCipher, AesIndex, AesKey, BYTES_PER_BATCH};
+use crate::generators::aes_ctr::{
+    AesBlockCipher, AesIndex, AesKey, BYTES_PER_AES_CALL, BYTES_PER_BATCH,
+};
 use std::arch::x86_64::{
     __m128i, _mm_aesenc_si128, _mm_aesenclast_si128, _mm_aeskeygenassist_si128, _mm_shuffle_epi32,
     _mm_slli_si128, _mm_store_si128, _mm_xor_si128,
@@ -10,6 +12,8 @@ use std::mem::transmute;
 pub struct AesniBlockCipher {
     // The set of round keys used for the aes encryption
     round_keys: [__m128i; 11],
+    // The key used to generate the round keys
+    key: AesKey,
 }
 
 impl AesBlockCipher for AesniBlockCipher {
@@ -28,7 +32,11 @@ impl AesBlockCipher for AesniBlockCipher {
 
         // SAFETY: we checked for aes and sse2 availability
         let round_keys = unsafe { generate_round_keys(key) };
-        AesniBlockCipher { round_keys }
+        AesniBlockCipher { round_keys, key }
+    }
+
+    fn key(&self) -> AesKey {
+        self.key
     }
 
     fn generate_batch(&mut self, AesIndex(aes_ctr): AesIndex) -> [u8; BYTES_PER_BATCH] {
@@ -52,6 +60,19 @@ impl AesBlockCipher for AesniBlockCipher {
         // SAFETY: we checked for aes and sse2 availability in `Self::new`
         unsafe { implementation(self, AesIndex(aes_ctr)) }
     }
+
+    fn encrypt_block(&mut self, block: [u8; BYTES_PER_AES_CALL]) -> [u8; BYTES_PER_AES_CALL] {
+        #[target_feature(enable = "sse2,aes")]
+        unsafe fn implementation(
+            this: &AesniBlockCipher,
+            block: [u8; BYTES_PER_AES_CALL],
+        ) -> [u8; BYTES_PER_AES_CALL] {
+            let message = u128_to_si128(u128::from_ne_bytes(block));
+            si128_to_u128(aes_encrypt_one(message, &this.round_keys)).to_ne_bytes()
+        }
+        // SAFETY: we checked for aes and sse2 availability in `Self::new`
+        unsafe { implementation(self, block) }
+    }
 }
 
 #[target_feature(enable = "sse2,aes")]
@@ -111,6 +132,18 @@ fn aes_encrypt_many(
     }
 }
 
+// Uses aes to encrypt a single value.
+#[inline(always)]
+fn aes_encrypt_one(message: __m128i, keys: &[__m128i; 11]) -> __m128i {
+    unsafe {
+        let mut tmp = _mm_xor_si128(message, keys[0]);
+        for key in keys.iter().take(10).skip(1) {
+            tmp = _mm_aesenc_si128(tmp, *key);
+        }
+        _mm_aesenclast_si128(tmp, keys[10])
+    }
+}
+
 fn aes_128_assist(temp1: __m128i, temp2: __m128i) -> __m128i {
     let mut temp3: __m128i;
     let mut temp2 = temp2;
@@ -172,7 +205,7 @@ fn u128_to_si128(input: u128) -> __m128i {
     unsafe { transmute(input) }
 }
 
-#[allow(unused)] // to please clippy when tests are not activated
+#[inline(always)]
 fn si128_to_u128(input: __m128i) -> u128 {
     unsafe { transmute(input) }
 }
@@ -215,6 +248,17 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_encrypt_one_message() {
+        // Checks that encrypting a single plaintext gives the correct output.
+        let message = u128_to_si128(PLAINTEXT);
+        let key = u128_to_si128(CIPHER_KEY);
+        let mut keys: [__m128i; 11] = [u128_to_si128(0); 11];
+        aes_128_key_expansion(key, &mut keys);
+        let ciphertext = aes_encrypt_one(message, &keys);
+        assert_eq!(CIPHERTEXT, si128_to_u128(ciphertext));
+    }
+
     #[test]
     fn test_encrypt_many_messages() {
         // Checks that encrypting many plaintext at the same time gives the correct output.
@@ -1,6 +1,9 @@
 use crate::generators::aes_ctr::{AesCtrGenerator, AesKey, ChildrenIterator};
 use crate::generators::implem::aesni::block_cipher::AesniBlockCipher;
-use crate::generators::{ByteCount, BytesPerChild, ChildrenCount, ForkError, RandomGenerator};
+use crate::generators::{
+    ByteCount, BytesPerChild, ChildrenCount, ForkError, GeneratorState, GeneratorStateError,
+    RandomGenerator, SeekError, SeekableRandomGenerator, TableIndex,
+};
 use crate::seeders::Seed;
 
 /// A random number generator using the `aesni` instructions.
@@ -38,6 +41,19 @@ impl RandomGenerator for AesniRandomGenerator {
     }
 }
 
+impl SeekableRandomGenerator for AesniRandomGenerator {
+    fn state(&self) -> GeneratorState {
+        self.0.state()
+    }
+    fn from_state(state: &GeneratorState) -> Result<Self, GeneratorStateError> {
+        AesCtrGenerator::resume(AesKey(state.seed.0), state.next_index, state.bound)
+            .map(AesniRandomGenerator)
+    }
+    fn seek(&mut self, index: TableIndex) -> Result<(), SeekError> {
+        self.0.seek(index)
+    }
+}
+
 impl Iterator for AesniRandomGenerator {
     type Item = u8;
 
@@ -50,7 +66,10 @@ impl Iterator for AesniRandomGenerator {
 mod test {
     use crate::generators::aes_ctr::aes_ctr_generic_test;
     use crate::generators::implem::aesni::block_cipher::AesniBlockCipher;
-    use crate::generators::{generator_generic_test, AesniRandomGenerator};
+    use crate::generators::{
+        ctr_drbg_generic_test, generator_generic_test, AesniRandomGenerator,
+        SoftwareRandomGenerator,
+    };
 
     #[test]
     fn prop_fork_first_state_table_index() {
@@ -102,6 +121,50 @@ mod test {
         generator_generic_test::test_fork_children::<AesniRandomGenerator>();
     }
 
+    #[test]
+    fn test_state_restore() {
+        generator_generic_test::test_state_restore::<AesniRandomGenerator>();
+    }
+
+    #[test]
+    fn test_state_restore_from_software() {
+        generator_generic_test::test_state_restore_across_implementations::<
+            SoftwareRandomGenerator,
+            AesniRandomGenerator,
+        >();
+    }
+
+    #[test]
+    fn test_seek() {
+        generator_generic_test::test_seek::<AesniRandomGenerator>();
+    }
+
+    #[test]
+    fn test_ctr_drbg_known_answers() {
+        ctr_drbg_generic_test::test_known_answers::<AesniBlockCipher>();
+    }
+
+    #[test]
+    fn test_ctr_drbg_seeder_prediction_resistance() {
+        ctr_drbg_generic_test::test_seeder_prediction_resistance::<AesniBlockCipher>();
+    }
+
+    #[test]
+    fn test_ctr_drbg_reseed_interval() {
+        ctr_drbg_generic_test::test_reseed_interval::<AesniBlockCipher>();
+    }
+
+    #[test]
+    fn test_ctr_drbg_request_too_large() {
+        ctr_drbg_generic_test::test_request_too_large::<AesniBlockCipher>();
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_ctr_drbg_input_too_long() {
+        ctr_drbg_generic_test::test_input_too_long::<AesniBlockCipher>();
+    }
+
     #[test]
     #[should_panic(expected = "expected test panic")]
     fn test_bounded_panic() {
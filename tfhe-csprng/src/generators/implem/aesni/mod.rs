@@ -9,6 +9,9 @@ mod block_cipher;
 mod generator;
 pub use generator::*;
 
+/// A CTR_DRBG generator, using the same block cipher as [`AesniRandomGenerator`].
+pub type AesniCtrDrbg<S> = crate::generators::CtrDrbg<block_cipher::AesniBlockCipher, S>;
+
 #[cfg(feature = "parallel")]
 mod parallel;
 #[cfg(feature = "parallel")]
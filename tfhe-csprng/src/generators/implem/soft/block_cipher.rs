@@ -9,14 +9,20 @@ use aes::Aes128;
 pub struct SoftwareBlockCipher {
     // Aes structure
     aes: Aes128,
+    // The key used to instantiate the aes structure
+    key: AesKey,
 }
 
 impl AesBlockCipher for SoftwareBlockCipher {
     fn new(key: AesKey) -> SoftwareBlockCipher {
-        let key: [u8; BYTES_PER_AES_CALL] = key.0.to_ne_bytes();
-        let key = GenericArray::clone_from_slice(&key[..]);
-        let aes = Aes128::new(&key);
-        SoftwareBlockCipher { aes }
+        let key_bytes: [u8; BYTES_PER_AES_CALL] = key.0.to_ne_bytes();
+        let key_bytes = GenericArray::clone_from_slice(&key_bytes[..]);
+        let aes = Aes128::new(&key_bytes);
+        SoftwareBlockCipher { aes, key }
+    }
+
+    fn key(&self) -> AesKey {
+        self.key
     }
 
     fn generate_batch(&mut self, AesIndex(aes_ctr): AesIndex) -> [u8; BYTES_PER_BATCH] {
@@ -32,6 +38,12 @@ impl AesBlockCipher for SoftwareBlockCipher {
             &self.aes,
         )
     }
+
+    fn encrypt_block(&mut self, block: [u8; BYTES_PER_AES_CALL]) -> [u8; BYTES_PER_AES_CALL] {
+        let mut block = GenericArray::from(block);
+        self.aes.encrypt_block(&mut block);
+        block.into()
+    }
 }
 
 // Uses aes to encrypt many values at once. This allows a substantial speedup (around 30%)
@@ -89,6 +101,14 @@ mod test {
     const PLAINTEXT: u128 = u128::from_be(0x00112233445566778899aabbccddeeff);
     const CIPHERTEXT: u128 = u128::from_be(0x69c4e0d86a7b0430d8cdb78070b4c55a);
 
+    #[test]
+    fn test_encrypt_block() {
+        // Checks that encrypting a single block gives the correct output.
+        let mut cipher = SoftwareBlockCipher::new(AesKey(CIPHER_KEY));
+        let ciphertext = cipher.encrypt_block(PLAINTEXT.to_ne_bytes());
+        assert_eq!(u128::from_ne_bytes(ciphertext), CIPHERTEXT);
+    }
+
     #[test]
     fn test_encrypt_many_messages() {
         // Checks that encrypting many plaintext at the same time gives the correct output.
@@ -1,6 +1,9 @@
 use crate::generators::aes_ctr::{AesCtrGenerator, AesKey, ChildrenIterator};
 use crate::generators::implem::soft::block_cipher::SoftwareBlockCipher;
-use crate::generators::{ByteCount, BytesPerChild, ChildrenCount, ForkError, RandomGenerator};
+use crate::generators::{
+    ByteCount, BytesPerChild, ChildrenCount, ForkError, GeneratorState, GeneratorStateError,
+    RandomGenerator, SeekError, SeekableRandomGenerator, TableIndex,
+};
 use crate::seeders::Seed;
 
 /// A random number generator using a software implementation.
@@ -38,6 +41,19 @@ impl RandomGenerator for SoftwareRandomGenerator {
     }
 }
 
+impl SeekableRandomGenerator for SoftwareRandomGenerator {
+    fn state(&self) -> GeneratorState {
+        self.0.state()
+    }
+    fn from_state(state: &GeneratorState) -> Result<Self, GeneratorStateError> {
+        AesCtrGenerator::resume(AesKey(state.seed.0), state.next_index, state.bound)
+            .map(SoftwareRandomGenerator)
+    }
+    fn seek(&mut self, index: TableIndex) -> Result<(), SeekError> {
+        self.0.seek(index)
+    }
+}
+
 impl Iterator for SoftwareRandomGenerator {
     type Item = u8;
 
@@ -50,7 +66,7 @@ impl Iterator for SoftwareRandomGenerator {
 mod test {
     use super::*;
     use crate::generators::aes_ctr::aes_ctr_generic_test;
-    use crate::generators::generator_generic_test;
+    use crate::generators::{ctr_drbg_generic_test, generator_generic_test};
 
     #[test]
     fn prop_fork_first_state_table_index() {
@@ -102,6 +118,42 @@ mod test {
         generator_generic_test::test_generator_determinism::<SoftwareRandomGenerator>();
     }
 
+    #[test]
+    fn test_state_restore() {
+        generator_generic_test::test_state_restore::<SoftwareRandomGenerator>();
+    }
+
+    #[test]
+    fn test_seek() {
+        generator_generic_test::test_seek::<SoftwareRandomGenerator>();
+    }
+
+    #[test]
+    fn test_ctr_drbg_known_answers() {
+        ctr_drbg_generic_test::test_known_answers::<SoftwareBlockCipher>();
+    }
+
+    #[test]
+    fn test_ctr_drbg_seeder_prediction_resistance() {
+        ctr_drbg_generic_test::test_seeder_prediction_resistance::<SoftwareBlockCipher>();
+    }
+
+    #[test]
+    fn test_ctr_drbg_reseed_interval() {
+        ctr_drbg_generic_test::test_reseed_interval::<SoftwareBlockCipher>();
+    }
+
+    #[test]
+    fn test_ctr_drbg_request_too_large() {
+        ctr_drbg_generic_test::test_request_too_large::<SoftwareBlockCipher>();
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_ctr_drbg_input_too_long() {
+        ctr_drbg_generic_test::test_input_too_long::<SoftwareBlockCipher>();
+    }
+
     #[test]
     #[should_panic(expected = "expected test panic")]
     fn test_bounded_panic() {
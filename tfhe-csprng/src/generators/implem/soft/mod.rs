@@ -5,6 +5,9 @@ mod block_cipher;
 mod generator;
 pub use generator::*;
 
+/// A CTR_DRBG generator, using the same block cipher as [`SoftwareRandomGenerator`].
+pub type SoftwareCtrDrbg<S> = crate::generators::CtrDrbg<block_cipher::SoftwareBlockCipher, S>;
+
 #[cfg(feature = "parallel")]
 mod parallel;
 #[cfg(feature = "parallel")]
@@ -97,6 +97,33 @@ pub trait RandomGenerator: Iterator<Item = u8> {
     ) -> Result<Self::ChildrenIter, ForkError>;
 }
 
+/// A trait for random generators whose stream position can be captured, restored and moved.
+///
+/// This is implemented by the AES-CTR generators, whose output is fully determined by the seed
+/// and the table index of the next byte.
+pub trait SeekableRandomGenerator: RandomGenerator {
+    /// Returns a snapshot of the generator state, from which the stream can be resumed with
+    /// [`from_state`](SeekableRandomGenerator::from_state).
+    fn state(&self) -> GeneratorState;
+
+    /// Creates a new generator resuming the stream described by `state`.
+    ///
+    /// The restored generator outputs exactly the bytes the original generator would have
+    /// outputted after `state` was captured, and shares its bound.
+    fn from_state(state: &GeneratorState) -> Result<Self, GeneratorStateError>
+    where
+        Self: Sized;
+
+    /// Moves the generator forward so that the next outputted byte is the one at `index`.
+    ///
+    /// Note:
+    /// -----
+    ///
+    /// To be successful, `index` must not be before the next byte of the generator, nor after its
+    /// bound.
+    fn seek(&mut self, index: TableIndex) -> Result<(), SeekError>;
+}
+
 /// A trait extending [`RandomGenerator`] to the parallel iterators of `rayon`.
 #[cfg(feature = "parallel")]
 pub trait ParallelRandomGenerator: RandomGenerator + Send {
@@ -119,11 +146,20 @@ pub trait ParallelRandomGenerator: RandomGenerator + Send {
 }
 
 mod aes_ctr;
+pub use aes_ctr::{AesIndex, ByteIndex, TableIndex};
+
+mod state;
+pub use state::*;
+
+mod ctr_drbg;
+pub use ctr_drbg::*;
 
 mod implem;
 pub use implem::*;
 
 pub mod default;
+/// Convenience alias for the most efficient CTR_DRBG implementation available.
+pub use default::DefaultCtrDrbg;
 /// Convenience alias for the most efficient CSPRNG implementation available.
 pub use default::DefaultRandomGenerator;
 
@@ -216,6 +252,90 @@ pub mod generator_generic_test {
         }
     }
 
+    /// Checks that a generator restored from a serialized state outputs the same bytes as the
+    /// original one, and reaches the bound at the same point.
+    pub fn test_state_restore<G: SeekableRandomGenerator>() {
+        test_state_restore_across_implementations::<G, G>();
+    }
+
+    /// Checks that a state captured on a generator of type `G` can be restored in a generator of
+    /// type `H` and yields the same stream.
+    pub fn test_state_restore_across_implementations<
+        G: SeekableRandomGenerator,
+        H: SeekableRandomGenerator,
+    >() {
+        for _ in 0..REPEATS {
+            let ((seed, n_children), n_bytes) = any_seed()
+                .zip(some_children_count())
+                .zip(some_bytes_per_child())
+                .next()
+                .unwrap();
+            let mut gen = G::new(seed);
+            let skipped = rand::thread_rng().gen::<usize>() % 1024;
+            gen.by_ref().take(skipped).for_each(drop);
+
+            // Unbounded generator
+            let bytes = gen.state().to_bytes();
+            let mut restored = H::from_state(&GeneratorState::from_bytes(&bytes).unwrap()).unwrap();
+            assert_eq!(restored.state(), gen.state());
+            assert_eq!(restored.remaining_bytes(), gen.remaining_bytes());
+            for _ in 0..1024 {
+                assert_eq!(gen.next(), restored.next());
+            }
+
+            // Bounded generator, restored midway
+            let mut bounded = gen.try_fork(n_children, n_bytes).unwrap().next().unwrap();
+            let consumed = rand::thread_rng().gen::<usize>() % (n_bytes.0 + 1);
+            bounded.by_ref().take(consumed).for_each(drop);
+            let bytes = bounded.state().to_bytes();
+            let restored = H::from_state(&GeneratorState::from_bytes(&bytes).unwrap()).unwrap();
+            assert_eq!(restored.remaining_bytes(), bounded.remaining_bytes());
+            assert!(bounded.eq(restored));
+        }
+    }
+
+    /// Checks that seeking forward is equivalent to skipping bytes, and that seeking backward or
+    /// after the bound fails.
+    pub fn test_seek<G: SeekableRandomGenerator>() {
+        for _ in 0..REPEATS {
+            let ((seed, n_children), n_bytes) = any_seed()
+                .zip(some_children_count())
+                .zip(some_bytes_per_child())
+                .next()
+                .unwrap();
+            let mut skipping = G::new(seed);
+            let mut seeking = G::new(seed);
+            let skipped = rand::thread_rng().gen::<usize>() % 1024;
+            let target = seeking.state().next_index.increased(skipped);
+            skipping.by_ref().take(skipped).for_each(drop);
+            seeking.seek(target).unwrap();
+            assert_eq!(skipping.state(), seeking.state());
+            for _ in 0..1024 {
+                assert_eq!(skipping.next(), seeking.next());
+            }
+
+            let current = seeking.state().next_index;
+            assert_eq!(
+                seeking.seek(current.decremented()),
+                Err(SeekError::BackwardSeek)
+            );
+
+            let mut bounded = seeking
+                .try_fork(n_children, n_bytes)
+                .unwrap()
+                .next()
+                .unwrap();
+            let bound = bounded.state().bound;
+            assert_eq!(
+                bounded.seek(bound.incremented()),
+                Err(SeekError::SeekAfterBound)
+            );
+            bounded.seek(bound).unwrap();
+            assert_eq!(bounded.remaining_bytes(), ByteCount(0));
+            assert!(bounded.next().is_none());
+        }
+    }
+
     /// Checks that a bounded prng returns none when exceeding the allowed number of bytes.
     ///
     /// To properly check for panic use `#[should_panic(expected = "expected test panic")]` as an
@@ -0,0 +1,277 @@
+use crate::generators::aes_ctr::{AesIndex, ByteIndex, TableIndex, BYTES_PER_AES_CALL};
+use crate::seeders::Seed;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// A snapshot of the position of a generator in the pseudo-random stream.
+///
+/// A generator state contains everything needed to resume the generation of the stream at the
+/// exact byte where it was captured, possibly on a different machine or with a different
+/// implementation of [`SeekableRandomGenerator`](super::SeekableRandomGenerator):
+/// + the seed the generator was created with,
+/// + the table index of the next byte to be outputted,
+/// + the bound of the generator, that is the table index of the first byte that can __not__ be
+///   outputted.
+///
+/// Note:
+/// -----
+///
+/// The seed is stored in the state. Consequently, a serialized state is as sensitive as the seed
+/// itself and must be handled accordingly.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct GeneratorState {
+    pub seed: Seed,
+    pub next_index: TableIndex,
+    pub bound: TableIndex,
+}
+
+/// The versions of the serialized [`GeneratorState`] format.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u8)]
+pub enum GeneratorStateVersion {
+    V0 = 0,
+}
+
+impl GeneratorStateVersion {
+    /// The version used when serializing a state.
+    pub const CURRENT: Self = Self::V0;
+
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::V0),
+            _ => None,
+        }
+    }
+}
+
+/// An error occurring when a generator state can not be deserialized or restored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GeneratorStateError {
+    UnsupportedVersion(u8),
+    InvalidLength { expected: usize, actual: usize },
+    InvalidByteIndex(u8),
+    FirstTableIndex,
+    IndexAfterBound,
+}
+
+impl Display for GeneratorStateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GeneratorStateError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported generator state version: {version}.")
+            }
+            GeneratorStateError::InvalidLength { expected, actual } => {
+                write!(
+                    f,
+                    "Invalid serialized generator state length, expected {expected} bytes, \
+                    got {actual}."
+                )
+            }
+            GeneratorStateError::InvalidByteIndex(byte_index) => {
+                write!(
+                    f,
+                    "Invalid byte index {byte_index}, it must be smaller than {BYTES_PER_AES_CALL}."
+                )
+            }
+            GeneratorStateError::FirstTableIndex => {
+                write!(
+                    f,
+                    "The first table index can not be used as the next index of a generator."
+                )
+            }
+            GeneratorStateError::IndexAfterBound => {
+                write!(f, "The next index of the generator is after its bound.")
+            }
+        }
+    }
+}
+impl Error for GeneratorStateError {}
+
+/// An error occurring when seeking a generator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeekError {
+    BackwardSeek,
+    SeekAfterBound,
+}
+
+impl Display for SeekError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SeekError::BackwardSeek => {
+                write!(
+                    f,
+                    "The generator can not be moved before the next byte it would output."
+                )
+            }
+            SeekError::SeekAfterBound => {
+                write!(f, "The generator can not be moved after its bound.")
+            }
+        }
+    }
+}
+impl Error for SeekError {}
+
+impl GeneratorState {
+    /// The size in bytes of a serialized state.
+    ///
+    /// The serialized format is, in order:
+    /// + the format version (1 byte),
+    /// + the seed (16 bytes, little endian),
+    /// + the aes index of the next index (16 bytes, little endian),
+    /// + the byte index of the next index (1 byte),
+    /// + the aes index of the bound (16 bytes, little endian),
+    /// + the byte index of the bound (1 byte).
+    pub const SERIALIZED_SIZE: usize = 1 + 16 + (16 + 1) + (16 + 1);
+
+    /// Checks that the state can be used to build a generator.
+    pub fn validate(&self) -> Result<(), GeneratorStateError> {
+        if self.next_index == TableIndex::FIRST {
+            return Err(GeneratorStateError::FirstTableIndex);
+        }
+        if self.next_index > self.bound {
+            return Err(GeneratorStateError::IndexAfterBound);
+        }
+        Ok(())
+    }
+
+    /// Serializes the state using the current format version.
+    pub fn to_bytes(&self) -> [u8; Self::SERIALIZED_SIZE] {
+        let mut output = [0u8; Self::SERIALIZED_SIZE];
+        output[0] = GeneratorStateVersion::CURRENT as u8;
+        output[1..17].copy_from_slice(&self.seed.0.to_le_bytes());
+        write_table_index(&mut output[17..34], self.next_index);
+        write_table_index(&mut output[34..51], self.bound);
+        output
+    }
+
+    /// Deserializes and validates a state.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, GeneratorStateError> {
+        let version = *bytes.first().ok_or(GeneratorStateError::InvalidLength {
+            expected: Self::SERIALIZED_SIZE,
+            actual: 0,
+        })?;
+        match GeneratorStateVersion::from_u8(version) {
+            Some(GeneratorStateVersion::V0) => {}
+            None => return Err(GeneratorStateError::UnsupportedVersion(version)),
+        }
+        if bytes.len() != Self::SERIALIZED_SIZE {
+            return Err(GeneratorStateError::InvalidLength {
+                expected: Self::SERIALIZED_SIZE,
+                actual: bytes.len(),
+            });
+        }
+
+        let state = GeneratorState {
+            seed: Seed(u128::from_le_bytes(bytes[1..17].try_into().unwrap())),
+            next_index: read_table_index(&bytes[17..34])?,
+            bound: read_table_index(&bytes[34..51])?,
+        };
+        state.validate()?;
+        Ok(state)
+    }
+}
+
+fn write_table_index(output: &mut [u8], index: TableIndex) {
+    output[..16].copy_from_slice(&index.aes_index.0.to_le_bytes());
+    // The byte index is always smaller than `BYTES_PER_AES_CALL` so it fits in a byte.
+    output[16] = index.byte_index.0 as u8;
+}
+
+fn read_table_index(input: &[u8]) -> Result<TableIndex, GeneratorStateError> {
+    let aes_index = AesIndex(u128::from_le_bytes(input[..16].try_into().unwrap()));
+    let byte_index = input[16];
+    if byte_index as usize >= BYTES_PER_AES_CALL {
+        return Err(GeneratorStateError::InvalidByteIndex(byte_index));
+    }
+    Ok(TableIndex::new(aes_index, ByteIndex(byte_index as usize)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{thread_rng, Rng};
+
+    const REPEATS: usize = 10_000;
+
+    fn any_table_index() -> impl Iterator<Item = TableIndex> {
+        std::iter::repeat_with(|| {
+            TableIndex::new(
+                AesIndex(thread_rng().gen()),
+                ByteIndex(thread_rng().gen::<usize>() % BYTES_PER_AES_CALL),
+            )
+        })
+    }
+
+    fn any_valid_state() -> impl Iterator<Item = GeneratorState> {
+        any_table_index()
+            .zip(any_table_index())
+            .map(|(a, b)| GeneratorState {
+                seed: Seed(thread_rng().gen()),
+                next_index: std::cmp::min(a, b),
+                bound: std::cmp::max(a, b),
+            })
+            .filter(|state| state.validate().is_ok())
+    }
+
+    #[test]
+    /// Check the property:
+    ///     For all valid states s,
+    ///         from_bytes(to_bytes(s)) = s.
+    fn prop_state_serialization_roundtrip() {
+        for _ in 0..REPEATS {
+            let state = any_valid_state().next().unwrap();
+            let bytes = state.to_bytes();
+            assert_eq!(GeneratorState::from_bytes(&bytes), Ok(state));
+        }
+    }
+
+    #[test]
+    fn test_state_serialization_errors() {
+        let state = any_valid_state().next().unwrap();
+        let bytes = state.to_bytes();
+
+        assert_eq!(
+            GeneratorState::from_bytes(&bytes[..GeneratorState::SERIALIZED_SIZE - 1]),
+            Err(GeneratorStateError::InvalidLength {
+                expected: GeneratorState::SERIALIZED_SIZE,
+                actual: GeneratorState::SERIALIZED_SIZE - 1
+            })
+        );
+
+        let mut wrong_version = bytes;
+        wrong_version[0] = 42;
+        assert_eq!(
+            GeneratorState::from_bytes(&wrong_version),
+            Err(GeneratorStateError::UnsupportedVersion(42))
+        );
+
+        let mut wrong_byte_index = bytes;
+        wrong_byte_index[33] = BYTES_PER_AES_CALL as u8;
+        assert_eq!(
+            GeneratorState::from_bytes(&wrong_byte_index),
+            Err(GeneratorStateError::InvalidByteIndex(
+                BYTES_PER_AES_CALL as u8
+            ))
+        );
+
+        let reversed = GeneratorState {
+            seed: state.seed,
+            next_index: TableIndex::LAST,
+            bound: TableIndex::SECOND,
+        };
+        assert_eq!(
+            GeneratorState::from_bytes(&reversed.to_bytes()),
+            Err(GeneratorStateError::IndexAfterBound)
+        );
+
+        let first = GeneratorState {
+            seed: state.seed,
+            next_index: TableIndex::FIRST,
+            bound: TableIndex::LAST,
+        };
+        assert_eq!(
+            GeneratorState::from_bytes(&first.to_bytes()),
+            Err(GeneratorStateError::FirstTableIndex)
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// An error occurring when a health test detects a failure of an entropy source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthTestError {
+    /// The same sample was repeated `count` times in a row.
+    RepetitionCount { sample: u8, count: usize },
+}
+
+impl Display for HealthTestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HealthTestError::RepetitionCount { sample, count } => {
+                write!(
+                    f,
+                    "Entropy source health test failure: the byte {sample:#04x} was repeated \
+                    {count} times in a row."
+                )
+            }
+        }
+    }
+}
+impl Error for HealthTestError {}
+
+/// The continuous repetition count test of the
+/// [NIST SP 800-90B](https://doi.org/10.6028/NIST.SP.800-90B) recommendation (section 4.4.1),
+/// applied to byte samples.
+///
+/// The test fails as soon as the same byte is observed `cutoff` times in a row, which is a sign
+/// that the entropy source is stuck.
+#[derive(Debug, Clone)]
+pub struct RepetitionCountTest {
+    cutoff: usize,
+    last_sample: Option<u8>,
+    count: usize,
+}
+
+impl RepetitionCountTest {
+    /// The base 2 logarithm of the false positive probability targeted by
+    /// [`with_min_entropy`](RepetitionCountTest::with_min_entropy), for each sample.
+    pub const FALSE_POSITIVE_EXPONENT: u32 = 40;
+
+    /// The cutoff used by default, which assumes that each byte carries 8 bits of min-entropy.
+    pub const DEFAULT_CUTOFF: usize = 6;
+
+    /// Creates a new test failing when a byte is repeated `cutoff` times in a row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cutoff` is smaller than 2.
+    pub fn new(cutoff: usize) -> Self {
+        assert!(
+            cutoff >= 2,
+            "The repetition count cutoff must be at least 2."
+        );
+        RepetitionCountTest {
+            cutoff,
+            last_sample: None,
+            count: 0,
+        }
+    }
+
+    /// Creates a new test for a source whose bytes carry at least `min_entropy` bits of
+    /// min-entropy.
+    ///
+    /// The cutoff is computed as `1 + ⌈FALSE_POSITIVE_EXPONENT / min_entropy⌉`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_entropy` is not in ]0, 8].
+    pub fn with_min_entropy(min_entropy: f64) -> Self {
+        assert!(
+            min_entropy > 0. && min_entropy <= 8.,
+            "The min-entropy of a byte must be in ]0, 8]."
+        );
+        Self::new(1 + (Self::FALSE_POSITIVE_EXPONENT as f64 / min_entropy).ceil() as usize)
+    }
+
+    /// Returns the cutoff of the test.
+    pub fn cutoff(&self) -> usize {
+        self.cutoff
+    }
+
+    /// Feeds a new sample to the test.
+    pub fn feed(&mut self, sample: u8) -> Result<(), HealthTestError> {
+        if self.last_sample == Some(sample) {
+            self.count += 1;
+            if self.count >= self.cutoff {
+                return Err(HealthTestError::RepetitionCount {
+                    sample,
+                    count: self.count,
+                });
+            }
+        } else {
+            self.last_sample = Some(sample);
+            self.count = 1;
+        }
+        Ok(())
+    }
+
+    /// Feeds all the bytes of `samples` to the test.
+    pub fn feed_all(&mut self, samples: &[u8]) -> Result<(), HealthTestError> {
+        samples.iter().try_for_each(|sample| self.feed(*sample))
+    }
+}
+
+impl Default for RepetitionCountTest {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CUTOFF)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_repetition_count_cutoff() {
+        assert_eq!(RepetitionCountTest::with_min_entropy(8.).cutoff(), 6);
+        assert_eq!(RepetitionCountTest::with_min_entropy(1.).cutoff(), 41);
+        assert_eq!(
+            RepetitionCountTest::default().cutoff(),
+            RepetitionCountTest::with_min_entropy(8.).cutoff()
+        );
+    }
+
+    #[test]
+    fn test_repetition_count_detects_stuck_source() {
+        let mut test = RepetitionCountTest::new(4);
+        test.feed_all(&[1, 1, 1, 2, 2, 2, 1, 1, 1]).unwrap();
+        assert_eq!(
+            test.feed(1),
+            Err(HealthTestError::RepetitionCount {
+                sample: 1,
+                count: 4
+            })
+        );
+    }
+}
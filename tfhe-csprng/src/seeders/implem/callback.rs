@@ -0,0 +1,58 @@
+use crate::seeders::{Seed, Seeder};
+
+/// A seeder delegating the generation of seeds to a user-provided closure.
+///
+/// This makes it possible to draw seeds from an entropy source that this crate does not know
+/// about, such as a hardware security module.
+///
+/// # Example
+///
+/// ```rust
+/// use tfhe_csprng::seeders::{CallbackSeeder, Seed, Seeder};
+///
+/// let mut counter = 0u128;
+/// let mut seeder = CallbackSeeder::new(move || {
+///     // Query your entropy source here.
+///     counter += 1;
+///     Seed(counter)
+/// });
+/// assert_eq!(seeder.seed(), Seed(1));
+/// assert_eq!(seeder.seed(), Seed(2));
+/// ```
+pub struct CallbackSeeder<F: FnMut() -> Seed> {
+    callback: F,
+}
+
+impl<F: FnMut() -> Seed> CallbackSeeder<F> {
+    /// Creates a new seeder calling `callback` each time a seed is needed.
+    pub fn new(callback: F) -> Self {
+        CallbackSeeder { callback }
+    }
+}
+
+impl<F: FnMut() -> Seed> Seeder for CallbackSeeder<F> {
+    fn seed(&mut self) -> Seed {
+        (self.callback)()
+    }
+
+    fn is_available() -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::seeders::generic_tests::check_seeder_fixed_sequences_different;
+
+    #[test]
+    fn check_bounded_sequence_difference() {
+        check_seeder_fixed_sequences_different(|start| {
+            let mut counter = start;
+            CallbackSeeder::new(move || {
+                counter = counter.wrapping_add(1);
+                Seed(counter)
+            })
+        });
+    }
+}
@@ -0,0 +1,128 @@
+use crate::seeders::{Seed, Seeder};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+
+/// A seeder mixing the seeds of several other seeders.
+///
+/// Each time a seed is requested, one seed is drawn from every source and the concatenation of
+/// those is hashed with the SHAKE256 extendable output function (together with a domain separator
+/// and a counter). The output seed is unpredictable as long as at least one of the sources is.
+///
+/// # Example
+///
+/// ```rust
+/// use tfhe_csprng::seeders::{CallbackSeeder, CombinedSeeder, Seed, Seeder};
+///
+/// let mut seeder = CombinedSeeder::new(vec![
+///     Box::new(CallbackSeeder::new(|| Seed(1))),
+///     Box::new(CallbackSeeder::new(|| Seed(2))),
+/// ]);
+/// let first_seed = seeder.seed();
+/// let second_seed = seeder.seed();
+/// assert_ne!(first_seed, second_seed);
+/// ```
+pub struct CombinedSeeder {
+    sources: Vec<Box<dyn Seeder>>,
+    counter: u128,
+}
+
+impl CombinedSeeder {
+    const DOMAIN_SEPARATOR: &'static [u8] = b"TFHE-CSPRNG_COMBINED_SEEDER";
+
+    /// Creates a new seeder mixing the seeds of `sources`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sources` is empty.
+    pub fn new(sources: Vec<Box<dyn Seeder>>) -> Self {
+        assert!(
+            !sources.is_empty(),
+            "A CombinedSeeder needs at least one source."
+        );
+        CombinedSeeder {
+            sources,
+            counter: 0,
+        }
+    }
+
+    /// Adds a source to the seeder.
+    pub fn push(&mut self, source: Box<dyn Seeder>) {
+        self.sources.push(source);
+    }
+
+    /// Returns the number of sources mixed by the seeder.
+    pub fn source_count(&self) -> usize {
+        self.sources.len()
+    }
+}
+
+impl Seeder for CombinedSeeder {
+    fn seed(&mut self) -> Seed {
+        let mut hasher = Shake256::default();
+        hasher.update(Self::DOMAIN_SEPARATOR);
+        hasher.update(&self.counter.to_le_bytes());
+        hasher.update(&(self.sources.len() as u64).to_le_bytes());
+        for source in self.sources.iter_mut() {
+            hasher.update(&source.seed().0.to_le_bytes());
+        }
+        self.counter = self.counter.wrapping_add(1);
+
+        let mut output = [0u8; 16];
+        hasher.finalize_xof().read(&mut output);
+        Seed(u128::from_le_bytes(output))
+    }
+
+    fn is_available() -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::seeders::generic_tests::check_seeder_fixed_sequences_different;
+    use crate::seeders::CallbackSeeder;
+
+    fn constant_seeder(value: u128) -> Box<dyn Seeder> {
+        Box::new(CallbackSeeder::new(move || Seed(value)))
+    }
+
+    #[test]
+    fn check_bounded_sequence_difference() {
+        // Even when all the sources are constant, the counter makes the seeds different.
+        check_seeder_fixed_sequences_different(|i| {
+            CombinedSeeder::new(vec![constant_seeder(i), constant_seeder(i)])
+        });
+    }
+
+    #[test]
+    fn test_combined_seeder_determinism() {
+        let mut first = CombinedSeeder::new(vec![constant_seeder(1), constant_seeder(2)]);
+        let mut second = CombinedSeeder::new(vec![constant_seeder(1), constant_seeder(2)]);
+        for _ in 0..100 {
+            assert_eq!(first.seed(), second.seed());
+        }
+    }
+
+    #[test]
+    fn test_combined_seeder_depends_on_all_sources() {
+        let reference = CombinedSeeder::new(vec![constant_seeder(1), constant_seeder(2)]).seed();
+        let first_changed =
+            CombinedSeeder::new(vec![constant_seeder(3), constant_seeder(2)]).seed();
+        let second_changed =
+            CombinedSeeder::new(vec![constant_seeder(1), constant_seeder(3)]).seed();
+        let swapped = CombinedSeeder::new(vec![constant_seeder(2), constant_seeder(1)]).seed();
+        let mut pushed = CombinedSeeder::new(vec![constant_seeder(1)]);
+        pushed.push(constant_seeder(2));
+        assert_ne!(reference, first_changed);
+        assert_ne!(reference, second_changed);
+        assert_ne!(reference, swapped);
+        assert_eq!(reference, pushed.seed());
+    }
+
+    #[test]
+    #[should_panic(expected = "A CombinedSeeder needs at least one source.")]
+    fn test_combined_seeder_no_source() {
+        CombinedSeeder::new(vec![]);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::seeders::{HealthTestError, RepetitionCountTest, Seed, Seeder};
+use std::fs::File;
+use std::io::Read;
+
+/// A seeder which uses the `/dev/random` source on unix-like systems, and continuously checks
+/// its health.
+///
+/// Contrary to [`UnixSeeder`](super::UnixSeeder), the seeds are the raw output of
+/// `/dev/random`, on which a [`RepetitionCountTest`] is run. To catch a source which is broken
+/// from the start, the test is also run on 1024 bytes when the seeder is created, as the start-up
+/// tests of NIST SP 800-90B recommend.
+pub struct DevRandomSeeder {
+    file: File,
+    health_test: RepetitionCountTest,
+}
+
+impl DevRandomSeeder {
+    /// The number of bytes drawn and tested when the seeder is created.
+    pub const STARTUP_TEST_BYTES: usize = 1024;
+
+    /// Creates a new seeder, using the default [`RepetitionCountTest`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `/dev/random` can not be opened or if the start-up health test fails.
+    pub fn new() -> Self {
+        Self::with_health_test(RepetitionCountTest::default())
+    }
+
+    /// Creates a new seeder, using the given [`RepetitionCountTest`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `/dev/random` can not be opened or if the start-up health test fails.
+    pub fn with_health_test(health_test: RepetitionCountTest) -> Self {
+        let file = std::fs::File::open("/dev/random").expect("Failed to open /dev/random .");
+        let mut seeder = DevRandomSeeder { file, health_test };
+        let mut startup_bytes = [0u8; Self::STARTUP_TEST_BYTES];
+        seeder
+            .try_fill_bytes(&mut startup_bytes)
+            .expect("/dev/random start-up health test failed.");
+        seeder
+    }
+
+    /// Generates a new seed, or returns an error if the health test failed.
+    pub fn try_seed(&mut self) -> Result<Seed, HealthTestError> {
+        let mut buf = [0u8; 16];
+        self.try_fill_bytes(&mut buf)?;
+        Ok(Seed(u128::from_ne_bytes(buf)))
+    }
+
+    fn try_fill_bytes(&mut self, bytes: &mut [u8]) -> Result<(), HealthTestError> {
+        self.file
+            .read_exact(bytes)
+            .expect("Failed to read from /dev/random .");
+        self.health_test.feed_all(bytes)
+    }
+}
+
+impl Default for DevRandomSeeder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Seeder for DevRandomSeeder {
+    fn seed(&mut self) -> Seed {
+        match self.try_seed() {
+            Ok(seed) => seed,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    fn is_available() -> bool {
+        cfg!(target_family = "unix")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::seeders::generic_tests::check_seeder_fixed_sequences_different;
+
+    #[test]
+    fn check_bounded_sequence_difference() {
+        check_seeder_fixed_sequences_different(|_| DevRandomSeeder::new());
+    }
+}
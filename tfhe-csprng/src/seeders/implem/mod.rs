@@ -12,3 +12,16 @@ pub use rdseed::RdseedSeeder;
 mod unix;
 #[cfg(target_family = "unix")]
 pub use unix::UnixSeeder;
+
+#[cfg(target_family = "unix")]
+mod dev_random;
+#[cfg(target_family = "unix")]
+pub use dev_random::DevRandomSeeder;
+
+mod callback;
+pub use callback::CallbackSeeder;
+
+#[cfg(feature = "combined-seeder")]
+mod combined;
+#[cfg(feature = "combined-seeder")]
+pub use combined::CombinedSeeder;
@@ -21,6 +21,9 @@ pub trait Seeder {
         Self: Sized;
 }
 
+mod health;
+pub use health::*;
+
 mod implem;
 // This import statement can be empty if seeder features are disabled, rustc's behavior changed to
 // warn of empty modules, we know this can happen, so allow it.
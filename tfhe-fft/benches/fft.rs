@@ -333,11 +333,46 @@ pub fn bench_fft128(c: &mut Criterion) {
     }
 }
 
+#[cfg(feature = "fft32")]
+pub fn bench_fft32(c: &mut Criterion) {
+    // FFT sizes of the `tfhe::boolean` parameter sets `DEFAULT_PARAMETERS` (polynomial size 512)
+    // and `PARAMETERS_ERROR_PROB_2_POW_MINUS_165` (polynomial size 1024)
+    for n in [256, 512] {
+        use tfhe_fft::fft32::*;
+        let plan = Plan::new(n);
+
+        let mut data_re = vec![0.0; n];
+        let mut data_im = vec![0.0; n];
+
+        let bench_id = format!("tfhe-fft32-fwd-{n}");
+        c.bench_function(&bench_id, |bench| {
+            bench.iter(|| {
+                plan.fwd(&mut data_re, &mut data_im);
+            });
+        });
+        write_to_json(&bench_id, "fft32-fwd", n);
+
+        let bench_id = format!("tfhe-fft32-inv-{n}");
+        c.bench_function(&bench_id, |bench| {
+            bench.iter(|| {
+                plan.inv(&mut data_re, &mut data_im);
+            });
+        });
+        write_to_json(&bench_id, "fft32-inv", n);
+    }
+}
+
 criterion_group!(fft, bench_ffts);
 #[cfg(feature = "fft128")]
 criterion_group!(fft128, bench_fft128);
+#[cfg(feature = "fft32")]
+criterion_group!(fft32, bench_fft32);
 
-#[cfg(not(feature = "fft128"))]
+#[cfg(all(not(feature = "fft128"), not(feature = "fft32")))]
 criterion_main!(fft);
-#[cfg(feature = "fft128")]
+#[cfg(all(feature = "fft128", not(feature = "fft32")))]
 criterion_main!(fft, fft128);
+#[cfg(all(not(feature = "fft128"), feature = "fft32"))]
+criterion_main!(fft, fft32);
+#[cfg(all(feature = "fft128", feature = "fft32"))]
+criterion_main!(fft, fft128, fft32);
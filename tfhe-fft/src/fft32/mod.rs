@@ -0,0 +1,692 @@
+//! Unordered 32-bit negacyclic FFT module.
+//!
+//! The forward transform takes its inputs in standard order, and outputs the terms of the
+//! negacyclic FFT in an unspecified permuted order, without any reordering pass. The inverse
+//! transform takes its inputs in that permuted order, and outputs the inverse transform in standard
+//! order. This is enough for negacyclic convolutions, where the only operations performed in the
+//! Fourier domain are elementwise.
+
+use crate::fft_simd::sincospi64;
+use aligned_vec::{avec, ABox};
+use pulp::Simd;
+
+#[cfg(target_arch = "aarch64")]
+use pulp::aarch64::Neon;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use pulp::x86::V3;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[cfg(feature = "nightly")]
+use pulp::x86::V4;
+
+/// `(z0, z1) <- (z0 + z1 * w, z0 - z1 * w)`
+#[inline(always)]
+fn fwd_butterfly<S: Simd>(
+    simd: S,
+    z0_re: &mut [S::f32s],
+    z0_im: &mut [S::f32s],
+    z1_re: &mut [S::f32s],
+    z1_im: &mut [S::f32s],
+    w_re: f32,
+    w_im: f32,
+) {
+    let w_re = simd.splat_f32s(w_re);
+    let w_im = simd.splat_f32s(w_im);
+    let neg_w_im = simd.sub_f32s(simd.splat_f32s(0.0), w_im);
+
+    for (z0_re, z0_im, z1_re, z1_im) in izip!(z0_re, z0_im, z1_re, z1_im) {
+        let z1w_re = simd.mul_add_e_f32s(*z1_im, neg_w_im, simd.mul_f32s(*z1_re, w_re));
+        let z1w_im = simd.mul_add_e_f32s(*z1_im, w_re, simd.mul_f32s(*z1_re, w_im));
+
+        (*z0_re, *z1_re) = (simd.add_f32s(*z0_re, z1w_re), simd.sub_f32s(*z0_re, z1w_re));
+        (*z0_im, *z1_im) = (simd.add_f32s(*z0_im, z1w_im), simd.sub_f32s(*z0_im, z1w_im));
+    }
+}
+
+/// `(z0, z1) <- (z0 + z1, (z0 - z1) * conj(w))`
+#[inline(always)]
+fn inv_butterfly<S: Simd>(
+    simd: S,
+    z0_re: &mut [S::f32s],
+    z0_im: &mut [S::f32s],
+    z1_re: &mut [S::f32s],
+    z1_im: &mut [S::f32s],
+    w_re: f32,
+    w_im: f32,
+) {
+    let w_re = simd.splat_f32s(w_re);
+    let w_im = simd.splat_f32s(w_im);
+    let neg_w_im = simd.sub_f32s(simd.splat_f32s(0.0), w_im);
+
+    for (z0_re, z0_im, z1_re, z1_im) in izip!(z0_re, z0_im, z1_re, z1_im) {
+        let diff_re = simd.sub_f32s(*z0_re, *z1_re);
+        let diff_im = simd.sub_f32s(*z0_im, *z1_im);
+
+        *z0_re = simd.add_f32s(*z0_re, *z1_re);
+        *z0_im = simd.add_f32s(*z0_im, *z1_im);
+
+        *z1_re = simd.mul_add_e_f32s(diff_im, w_im, simd.mul_f32s(diff_re, w_re));
+        *z1_im = simd.mul_add_e_f32s(diff_re, neg_w_im, simd.mul_f32s(diff_im, w_re));
+    }
+}
+
+/// Applies the forward or inverse butterflies to the elements that fill whole `S::f32s` registers,
+/// then to the remaining ones.
+///
+/// The last stages of the transform, where the butterflies span fewer elements than a register,
+/// go through the scalar path entirely.
+#[inline(always)]
+fn split_butterfly<S: Simd>(
+    simd: S,
+    z0_re: &mut [f32],
+    z0_im: &mut [f32],
+    z1_re: &mut [f32],
+    z1_im: &mut [f32],
+    w_re: f32,
+    w_im: f32,
+    fwd: bool,
+) {
+    let (z0_re_head, z0_re_tail) = S::as_mut_simd_f32s(z0_re);
+    let (z0_im_head, z0_im_tail) = S::as_mut_simd_f32s(z0_im);
+    let (z1_re_head, z1_re_tail) = S::as_mut_simd_f32s(z1_re);
+    let (z1_im_head, z1_im_tail) = S::as_mut_simd_f32s(z1_im);
+
+    let scalar = pulp::Scalar::new();
+    if fwd {
+        fwd_butterfly(
+            simd, z0_re_head, z0_im_head, z1_re_head, z1_im_head, w_re, w_im,
+        );
+        fwd_butterfly(
+            scalar, z0_re_tail, z0_im_tail, z1_re_tail, z1_im_tail, w_re, w_im,
+        );
+    } else {
+        inv_butterfly(
+            simd, z0_re_head, z0_im_head, z1_re_head, z1_im_head, w_re, w_im,
+        );
+        inv_butterfly(
+            scalar, z0_re_tail, z0_im_tail, z1_re_tail, z1_im_tail, w_re, w_im,
+        );
+    }
+}
+
+#[inline(always)]
+fn negacyclic_fwd_fft_simd<S: Simd>(
+    simd: S,
+    data_re: &mut [f32],
+    data_im: &mut [f32],
+    twid_re: &[f32],
+    twid_im: &[f32],
+) {
+    let n = data_re.len();
+    let mut t = n;
+    let mut m = 1;
+
+    while m < n {
+        t /= 2;
+
+        for i in 0..m {
+            let w_re = twid_re[m + i];
+            let w_im = twid_im[m + i];
+
+            let start = 2 * i * t;
+
+            let data_re = &mut data_re[start..][..2 * t];
+            let data_im = &mut data_im[start..][..2 * t];
+
+            let (z0_re, z1_re) = data_re.split_at_mut(t);
+            let (z0_im, z1_im) = data_im.split_at_mut(t);
+
+            split_butterfly(simd, z0_re, z0_im, z1_re, z1_im, w_re, w_im, true);
+        }
+
+        m *= 2;
+    }
+}
+
+#[inline(always)]
+fn negacyclic_inv_fft_simd<S: Simd>(
+    simd: S,
+    data_re: &mut [f32],
+    data_im: &mut [f32],
+    twid_re: &[f32],
+    twid_im: &[f32],
+) {
+    let n = data_re.len();
+    let mut t = 1;
+    let mut m = n;
+
+    while m > 1 {
+        m /= 2;
+
+        for i in 0..m {
+            let w_re = twid_re[m + i];
+            let w_im = twid_im[m + i];
+
+            let start = 2 * i * t;
+
+            let data_re = &mut data_re[start..][..2 * t];
+            let data_im = &mut data_im[start..][..2 * t];
+
+            let (z0_re, z1_re) = data_re.split_at_mut(t);
+            let (z0_im, z1_im) = data_im.split_at_mut(t);
+
+            split_butterfly(simd, z0_re, z0_im, z1_re, z1_im, w_re, w_im, false);
+        }
+
+        t *= 2;
+    }
+}
+
+struct Impl<'a> {
+    fwd: bool,
+    data_re: &'a mut [f32],
+    data_im: &'a mut [f32],
+    twid_re: &'a [f32],
+    twid_im: &'a [f32],
+}
+
+impl pulp::WithSimd for Impl<'_> {
+    type Output = ();
+
+    #[inline(always)]
+    fn with_simd<S: Simd>(self, simd: S) -> Self::Output {
+        let Self {
+            fwd,
+            data_re,
+            data_im,
+            twid_re,
+            twid_im,
+        } = self;
+
+        if fwd {
+            negacyclic_fwd_fft_simd(simd, data_re, data_im, twid_re, twid_im);
+        } else {
+            negacyclic_inv_fft_simd(simd, data_re, data_im, twid_re, twid_im);
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn negacyclic_fwd_fft_scalar(
+    data_re: &mut [f32],
+    data_im: &mut [f32],
+    twid_re: &[f32],
+    twid_im: &[f32],
+) {
+    negacyclic_fwd_fft_simd(pulp::Scalar::new(), data_re, data_im, twid_re, twid_im);
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[doc(hidden)]
+pub fn negacyclic_fwd_fft_avx2(
+    simd: V3,
+    data_re: &mut [f32],
+    data_im: &mut [f32],
+    twid_re: &[f32],
+    twid_im: &[f32],
+) {
+    Simd::vectorize(
+        simd,
+        Impl {
+            fwd: true,
+            data_re,
+            data_im,
+            twid_re,
+            twid_im,
+        },
+    );
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[cfg(feature = "nightly")]
+#[doc(hidden)]
+pub fn negacyclic_fwd_fft_avx512(
+    simd: V4,
+    data_re: &mut [f32],
+    data_im: &mut [f32],
+    twid_re: &[f32],
+    twid_im: &[f32],
+) {
+    Simd::vectorize(
+        simd,
+        Impl {
+            fwd: true,
+            data_re,
+            data_im,
+            twid_re,
+            twid_im,
+        },
+    );
+}
+
+#[cfg(target_arch = "aarch64")]
+#[doc(hidden)]
+pub fn negacyclic_fwd_fft_neon(
+    simd: Neon,
+    data_re: &mut [f32],
+    data_im: &mut [f32],
+    twid_re: &[f32],
+    twid_im: &[f32],
+) {
+    Simd::vectorize(
+        simd,
+        Impl {
+            fwd: true,
+            data_re,
+            data_im,
+            twid_re,
+            twid_im,
+        },
+    );
+}
+
+#[doc(hidden)]
+pub fn negacyclic_inv_fft_scalar(
+    data_re: &mut [f32],
+    data_im: &mut [f32],
+    twid_re: &[f32],
+    twid_im: &[f32],
+) {
+    negacyclic_inv_fft_simd(pulp::Scalar::new(), data_re, data_im, twid_re, twid_im);
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[doc(hidden)]
+pub fn negacyclic_inv_fft_avx2(
+    simd: V3,
+    data_re: &mut [f32],
+    data_im: &mut [f32],
+    twid_re: &[f32],
+    twid_im: &[f32],
+) {
+    Simd::vectorize(
+        simd,
+        Impl {
+            fwd: false,
+            data_re,
+            data_im,
+            twid_re,
+            twid_im,
+        },
+    );
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[cfg(feature = "nightly")]
+#[doc(hidden)]
+pub fn negacyclic_inv_fft_avx512(
+    simd: V4,
+    data_re: &mut [f32],
+    data_im: &mut [f32],
+    twid_re: &[f32],
+    twid_im: &[f32],
+) {
+    Simd::vectorize(
+        simd,
+        Impl {
+            fwd: false,
+            data_re,
+            data_im,
+            twid_re,
+            twid_im,
+        },
+    );
+}
+
+#[cfg(target_arch = "aarch64")]
+#[doc(hidden)]
+pub fn negacyclic_inv_fft_neon(
+    simd: Neon,
+    data_re: &mut [f32],
+    data_im: &mut [f32],
+    twid_re: &[f32],
+    twid_im: &[f32],
+) {
+    Simd::vectorize(
+        simd,
+        Impl {
+            fwd: false,
+            data_re,
+            data_im,
+            twid_re,
+            twid_im,
+        },
+    );
+}
+
+#[doc(hidden)]
+pub fn negacyclic_fwd_fft(
+    data_re: &mut [f32],
+    data_im: &mut [f32],
+    twid_re: &[f32],
+    twid_im: &[f32],
+) {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        #[cfg(feature = "nightly")]
+        if let Some(simd) = V4::try_new() {
+            return negacyclic_fwd_fft_avx512(simd, data_re, data_im, twid_re, twid_im);
+        }
+        if let Some(simd) = V3::try_new() {
+            return negacyclic_fwd_fft_avx2(simd, data_re, data_im, twid_re, twid_im);
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if let Some(simd) = Neon::try_new() {
+            return negacyclic_fwd_fft_neon(simd, data_re, data_im, twid_re, twid_im);
+        }
+    }
+    negacyclic_fwd_fft_scalar(data_re, data_im, twid_re, twid_im)
+}
+
+#[doc(hidden)]
+pub fn negacyclic_inv_fft(
+    data_re: &mut [f32],
+    data_im: &mut [f32],
+    twid_re: &[f32],
+    twid_im: &[f32],
+) {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        #[cfg(feature = "nightly")]
+        if let Some(simd) = V4::try_new() {
+            return negacyclic_inv_fft_avx512(simd, data_re, data_im, twid_re, twid_im);
+        }
+        if let Some(simd) = V3::try_new() {
+            return negacyclic_inv_fft_avx2(simd, data_re, data_im, twid_re, twid_im);
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if let Some(simd) = Neon::try_new() {
+            return negacyclic_inv_fft_neon(simd, data_re, data_im, twid_re, twid_im);
+        }
+    }
+    negacyclic_inv_fft_scalar(data_re, data_im, twid_re, twid_im)
+}
+
+fn bitreverse(i: usize, n: usize) -> usize {
+    let logn = n.trailing_zeros();
+    let mut result = 0;
+    for k in 0..logn {
+        let kth_bit = (i >> k) & 1_usize;
+        result |= kth_bit << (logn - k - 1);
+    }
+    result
+}
+
+#[doc(hidden)]
+pub fn init_negacyclic_twiddles(twid_re: &mut [f32], twid_im: &mut [f32]) {
+    let n = twid_re.len();
+    let mut m = 1_usize;
+
+    while m < n {
+        for i in 0..m {
+            let k = 2 * m + i;
+            let pos = m + i;
+
+            // the twiddles are computed in double precision, then rounded
+            let theta_over_pi = bitreverse(k, 2 * n) as f64 / (2 * n) as f64;
+            let (s, c) = sincospi64(theta_over_pi);
+            twid_re[pos] = c as f32;
+            twid_im[pos] = s as f32;
+        }
+        m *= 2;
+    }
+}
+
+/// Unordered 32-bit negacyclic FFT plan.
+///
+/// This plan trades precision for speed: with single precision floating point numbers, twice as
+/// many values fit in a SIMD register compared to the 64-bit FFT. The precision of the results
+/// is around 24 bits, so it is only suitable for convolutions whose result fits in that budget.
+#[derive(Clone)]
+pub struct Plan {
+    twid_re: ABox<[f32]>,
+    twid_im: ABox<[f32]>,
+}
+
+impl core::fmt::Debug for Plan {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Plan")
+            .field("fft_size", &self.fft_size())
+            .finish()
+    }
+}
+
+impl Plan {
+    /// Returns a new negacyclic FFT plan for the given vector size.
+    ///
+    /// A real polynomial of size `2 * n` is transformed by storing its first half in the real
+    /// parts and its second half in the imaginary parts of the buffer.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `n` is not a power of two, or if it is less than `2`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tfhe_fft::fft32::Plan;
+    /// let plan = Plan::new(32);
+    /// ```
+    #[track_caller]
+    pub fn new(n: usize) -> Self {
+        assert!(n.is_power_of_two());
+        assert!(n >= 2);
+
+        let mut twid_re = avec![0.0f32; n].into_boxed_slice();
+        let mut twid_im = avec![0.0f32; n].into_boxed_slice();
+
+        init_negacyclic_twiddles(&mut twid_re, &mut twid_im);
+
+        Self { twid_re, twid_im }
+    }
+
+    /// Returns the vector size of the negacyclic FFT.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tfhe_fft::fft32::Plan;
+    /// let plan = Plan::new(32);
+    /// assert_eq!(plan.fft_size(), 32);
+    /// ```
+    pub fn fft_size(&self) -> usize {
+        self.twid_re.len()
+    }
+
+    /// Performs a forward negacyclic FFT in place.
+    ///
+    /// # Note
+    ///
+    /// The values in `buf_re`, `buf_im` must be in standard order prior to calling this function.
+    /// When this function returns, the values in `buf_re`, `buf_im` will contain the terms of the
+    /// forward transform in permuted order.
+    #[track_caller]
+    pub fn fwd(&self, buf_re: &mut [f32], buf_im: &mut [f32]) {
+        assert_eq!(buf_re.len(), self.fft_size());
+        assert_eq!(buf_im.len(), self.fft_size());
+
+        negacyclic_fwd_fft(buf_re, buf_im, &self.twid_re, &self.twid_im);
+    }
+
+    /// Performs an unnormalized inverse negacyclic FFT in place.
+    ///
+    /// # Note
+    ///
+    /// The values in `buf_re`, `buf_im` must be in permuted order prior to calling this function.
+    /// When this function returns, the values in `buf_re`, `buf_im` will contain the terms of the
+    /// inverse transform in standard order, multiplied by the FFT size.
+    #[track_caller]
+    pub fn inv(&self, buf_re: &mut [f32], buf_im: &mut [f32]) {
+        assert_eq!(buf_re.len(), self.fft_size());
+        assert_eq!(buf_im.len(), self.fft_size());
+
+        negacyclic_inv_fft(buf_re, buf_im, &self.twid_re, &self.twid_im);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use rand::random;
+
+    extern crate alloc;
+
+    fn negacyclic_convolution_f64(lhs: &[f64], rhs: &[f64]) -> Vec<f64> {
+        let n = lhs.len();
+        let mut full_convolution = vec![0.0f64; 2 * n];
+        for i in 0..n {
+            for j in 0..n {
+                full_convolution[i + j] += lhs[i] * rhs[j];
+            }
+        }
+        (0..n)
+            .map(|i| full_convolution[i] - full_convolution[i + n])
+            .collect()
+    }
+
+    fn negacyclic_convolution_f32(
+        lhs: &[f32],
+        rhs: &[f32],
+        fwd: impl Fn(&mut [f32], &mut [f32]),
+        inv: impl Fn(&mut [f32], &mut [f32]),
+    ) -> Vec<f32> {
+        let n = lhs.len();
+
+        let (mut lhs_re, mut lhs_im) = (lhs[..n / 2].to_vec(), lhs[n / 2..].to_vec());
+        let (mut rhs_re, mut rhs_im) = (rhs[..n / 2].to_vec(), rhs[n / 2..].to_vec());
+
+        fwd(&mut lhs_re, &mut lhs_im);
+        fwd(&mut rhs_re, &mut rhs_im);
+
+        let factor = 2.0 / n as f32;
+        for (lhs_re, lhs_im, rhs_re, rhs_im) in izip!(&mut lhs_re, &mut lhs_im, &rhs_re, &rhs_im) {
+            (*lhs_re, *lhs_im) = (
+                (*lhs_re * rhs_re - *lhs_im * rhs_im) * factor,
+                (*lhs_re * rhs_im + *lhs_im * rhs_re) * factor,
+            );
+        }
+
+        inv(&mut lhs_re, &mut lhs_im);
+
+        lhs_re.into_iter().chain(lhs_im).collect()
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for n in [4, 8, 16, 32, 64, 128, 256, 512, 1024, 2048] {
+            let plan = Plan::new(n);
+
+            let data_re: Vec<f32> = (0..n).map(|_| random::<f32>() - 0.5).collect();
+            let data_im: Vec<f32> = (0..n).map(|_| random::<f32>() - 0.5).collect();
+
+            let mut roundtrip_re = data_re.clone();
+            let mut roundtrip_im = data_im.clone();
+            plan.fwd(&mut roundtrip_re, &mut roundtrip_im);
+            plan.inv(&mut roundtrip_re, &mut roundtrip_im);
+
+            let threshold = 1e-6 * n.trailing_zeros() as f32;
+            for (expected, actual) in izip!(
+                data_re.iter().chain(&data_im),
+                roundtrip_re.iter().chain(&roundtrip_im)
+            ) {
+                let actual = actual / n as f32;
+                assert!((expected - actual).abs() < threshold);
+            }
+        }
+    }
+
+    /// Polynomial sizes and decomposition base logs of the `tfhe::boolean` parameter sets
+    /// `DEFAULT_PARAMETERS` and `PARAMETERS_ERROR_PROB_2_POW_MINUS_165`, which the 32-bit FFT
+    /// targets.
+    const BOOLEAN_PARAMETERS: [(usize, u32); 2] = [(512, 10), (1024, 10)];
+
+    #[test]
+    fn test_product_error_bound() {
+        for (n, base_log) in BOOLEAN_PARAMETERS {
+            for _ in 0..10 {
+                // torus elements, as the bootstrap key in the external product
+                let lhs: Vec<f32> = (0..n)
+                    .map(|_| (random::<u32>() as i32 as f64 / 2.0f64.powi(32)) as f32)
+                    .collect();
+                // balanced decomposition terms, as the decomposed accumulator
+                let half_base = 1_i32 << (base_log - 1);
+                let rhs: Vec<f32> = (0..n)
+                    .map(|_| (random::<i32>().rem_euclid(2 * half_base) - half_base) as f32)
+                    .collect();
+
+                let lhs_f64: Vec<f64> = lhs.iter().map(|&x| x as f64).collect();
+                let rhs_f64: Vec<f64> = rhs.iter().map(|&x| x as f64).collect();
+
+                let plan = Plan::new(n / 2);
+                let actual = negacyclic_convolution_f32(
+                    &lhs,
+                    &rhs,
+                    |re, im| plan.fwd(re, im),
+                    |re, im| plan.inv(re, im),
+                );
+                let expected = negacyclic_convolution_f64(&lhs_f64, &rhs_f64);
+
+                // the error of the f32 FFT grows with the magnitude of the inputs, the square root
+                // of the size of the convolution and the depth of the transform
+                let log2_n = n.trailing_zeros();
+                let sqrt_n_upper_bound = (1 << log2_n.div_ceil(2)) as f64;
+                let threshold = f32::EPSILON as f64
+                    * half_base as f64
+                    * 0.5
+                    * sqrt_n_upper_bound
+                    * log2_n as f64
+                    * 2.0;
+                for (expected, actual) in izip!(expected, actual) {
+                    let abs_diff = (expected - actual as f64).abs();
+                    assert!(
+                        abs_diff < threshold,
+                        "abs_diff: {abs_diff}, threshold: {threshold}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_kernels_match_scalar() {
+        for n in [2, 4, 8, 16, 32, 64, 128, 256, 512, 1024] {
+            let plan = Plan::new(n);
+
+            let data_re: Vec<f32> = (0..n).map(|_| random::<f32>() - 0.5).collect();
+            let data_im: Vec<f32> = (0..n).map(|_| random::<f32>() - 0.5).collect();
+
+            let mut scalar_re = data_re.clone();
+            let mut scalar_im = data_im.clone();
+            negacyclic_fwd_fft_scalar(&mut scalar_re, &mut scalar_im, &plan.twid_re, &plan.twid_im);
+
+            let mut simd_re = data_re.clone();
+            let mut simd_im = data_im.clone();
+            plan.fwd(&mut simd_re, &mut simd_im);
+
+            // the simd kernels may use fused multiply-adds, so the results are only close
+            let threshold = 1e-5 * n.trailing_zeros().max(1) as f32;
+            for (scalar, simd) in izip!(
+                scalar_re.iter().chain(&scalar_im),
+                simd_re.iter().chain(&simd_im)
+            ) {
+                assert!((scalar - simd).abs() < threshold);
+            }
+
+            negacyclic_inv_fft_scalar(&mut scalar_re, &mut scalar_im, &plan.twid_re, &plan.twid_im);
+            plan.inv(&mut simd_re, &mut simd_im);
+
+            let threshold = threshold * n as f32;
+            for (scalar, simd) in izip!(
+                scalar_re.iter().chain(&scalar_im),
+                simd_re.iter().chain(&simd_im)
+            ) {
+                assert!((scalar - simd).abs() < threshold);
+            }
+        }
+    }
+}
@@ -13,7 +13,8 @@
 //!  convolution. The only operations that are performed in the Fourier domain are elementwise, and
 //!  so the order of the coefficients does not affect the results.
 //!
-//! Additionally, an optional 128-bit negacyclic FFT module is provided.
+//! Additionally, an optional 128-bit negacyclic FFT module and an optional unordered 32-bit
+//! negacyclic FFT module are provided.
 //!
 //! # Features
 //!
@@ -21,6 +22,8 @@
 //!  an FFT plan that measures the various implementations to choose the fastest one at runtime.
 //!  - `fft128`: This flag provides access to the 128-bit FFT, which is accessible in the
 //!  `fft128` module.
+//!  - `fft32`: This flag provides access to the 32-bit FFT, which is accessible in the
+//!  `fft32` module.
 //!  - `nightly`: This enables unstable Rust features to further speed up the FFT, by enabling
 //!  AVX512F instructions on CPUs that support them. This feature requires a nightly Rust
 //!  toolchain.
@@ -244,3 +247,7 @@ pub mod unordered;
 #[cfg(feature = "fft128")]
 #[cfg_attr(docsrs, doc(cfg(feature = "fft128")))]
 pub mod fft128;
+
+#[cfg(feature = "fft32")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fft32")))]
+pub mod fft32;
@@ -408,6 +408,24 @@ int uint8_safe_serialization(const ClientKey *client_key, const ServerKey *serve
 
   assert(clear == lhs_clear);
 
+  destroy_dynamic_buffer(&value_buffer);
+  fhe_uint8_destroy(deserialized_lhs);
+  deserialized_lhs = NULL;
+
+  ok = fhe_uint8_safe_serialize_with_compression(lhs, &value_buffer, max_serialization_size);
+  assert(ok == 0);
+
+  deser_view.pointer = value_buffer.pointer;
+  deser_view.length = value_buffer.length;
+  ok = fhe_uint8_safe_deserialize_conformant(deser_view, max_serialization_size, server_key,
+                                             &deserialized_lhs);
+  assert(ok == 0);
+
+  ok = fhe_uint8_decrypt(deserialized_lhs, deserialized_client_key, &clear);
+  assert(ok == 0);
+
+  assert(clear == lhs_clear);
+
   if (value_buffer.pointer != NULL) {
     destroy_dynamic_buffer(&value_buffer);
   }
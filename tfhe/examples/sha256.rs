@@ -8,7 +8,10 @@ use tfhe::shortint::parameters::v1_1::{
     V1_1_PARAM_MULTI_BIT_GROUP_2_MESSAGE_2_CARRY_2_KS_PBS_GAUSSIAN_2M64,
     V1_1_PARAM_MULTI_BIT_GROUP_3_MESSAGE_2_CARRY_2_KS_PBS_GAUSSIAN_2M64,
 };
-use tfhe::{set_server_key, ClientKey, CompressedServerKey, ConfigBuilder, Device, FheUint32};
+use tfhe::{
+    set_server_key, ClientKey, CompressedServerKey, ConfigBuilder, Device, FheUint32,
+    SimulationServerKey,
+};
 
 // might improve error message on type error
 #[doc(hidden)]
@@ -161,6 +164,7 @@ impl Args {
                     "gpu" | "cuda" => {
                         panic!("Needs to be compiled with gpu feature to support gpu")
                     }
+                    "simulation" => args.device = Device::Simulation,
                     _ => panic!("Unsupported device {value}"),
                 }
             } else if arg == "--multibit" {
@@ -229,6 +233,16 @@ fn main() -> Result<(), std::io::Error> {
             });
             set_server_key(server_key);
         }
+        (Device::Simulation, false) => {
+            set_server_key(SimulationServerKey::new(&client_key));
+        }
+        (Device::Simulation, true) => {
+            let server_key = SimulationServerKey::new(&client_key);
+            rayon::broadcast(|_| {
+                set_server_key(server_key.clone());
+            });
+            set_server_key(server_key);
+        }
     }
     println!("key gen end");
 
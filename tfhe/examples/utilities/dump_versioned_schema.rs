@@ -0,0 +1,103 @@
+use clap::{value_parser, Arg, Command};
+use std::io::Write;
+use std::path::PathBuf;
+use tfhe_versionable::schema::SchemaRegistry;
+
+/// Builds the schema of the public types of `tfhe` that can be serialized with versioning.
+///
+/// Generic types are only listed once since their layout does not depend on their type
+/// parameter (for example `FheUint8` and `FheUint64` are described by the same schema).
+fn tfhe_schema_registry() -> SchemaRegistry {
+    let mut registry = SchemaRegistry::new();
+
+    // High level api
+    registry
+        .add::<tfhe::ClientKey>()
+        .add::<tfhe::ServerKey>()
+        .add::<tfhe::CompressedServerKey>()
+        .add::<tfhe::PublicKey>()
+        .add::<tfhe::CompressedPublicKey>()
+        .add::<tfhe::CompactPublicKey>()
+        .add::<tfhe::CompressedCompactPublicKey>()
+        .add::<tfhe::KeySwitchingKey>()
+        .add::<tfhe::Config>()
+        .add::<tfhe::Tag>()
+        .add::<tfhe::FheBool>()
+        .add::<tfhe::CompressedFheBool>()
+        .add::<tfhe::SquashedNoiseFheBool>()
+        .add::<tfhe::FheUint8>()
+        .add::<tfhe::CompressedFheUint8>()
+        .add::<tfhe::SquashedNoiseFheUint>()
+        .add::<tfhe::FheInt8>()
+        .add::<tfhe::CompressedFheInt8>()
+        .add::<tfhe::SquashedNoiseFheInt>()
+        .add::<tfhe::FheAsciiString>()
+        .add::<tfhe::CompactCiphertextList>()
+        .add::<tfhe::ProvenCompactCiphertextList>()
+        .add::<tfhe::CompressedCiphertextList>();
+
+    // Zero knowledge proofs
+    registry
+        .add::<tfhe::zk::CompactPkeCrs>()
+        .add::<tfhe::zk::CompressedCompactPkeCrs>()
+        .add::<tfhe::zk::CompactPkeProof>();
+
+    // Integer api
+    registry
+        .add::<tfhe::integer::ClientKey>()
+        .add::<tfhe::integer::ServerKey>()
+        .add::<tfhe::integer::CompressedServerKey>()
+        .add::<tfhe::integer::PublicKey>()
+        .add::<tfhe::integer::CompressedPublicKey>()
+        .add::<tfhe::integer::CompactPublicKey>()
+        .add::<tfhe::integer::CompressedCompactPublicKey>()
+        .add::<tfhe::integer::RadixCiphertext>()
+        .add::<tfhe::integer::SignedRadixCiphertext>()
+        .add::<tfhe::integer::CrtCiphertext>()
+        .add::<tfhe::integer::BooleanBlock>();
+
+    // Shortint api
+    registry
+        .add::<tfhe::shortint::ClientKey>()
+        .add::<tfhe::shortint::ServerKey>()
+        .add::<tfhe::shortint::CompressedServerKey>()
+        .add::<tfhe::shortint::KeySwitchingKey>()
+        .add::<tfhe::shortint::CompressedKeySwitchingKey>()
+        .add::<tfhe::shortint::Ciphertext>()
+        .add::<tfhe::shortint::CompressedCiphertext>();
+
+    // Boolean api
+    registry
+        .add::<tfhe::boolean::client_key::ClientKey>()
+        .add::<tfhe::boolean::server_key::ServerKey>()
+        .add::<tfhe::boolean::ciphertext::Ciphertext>();
+
+    registry
+}
+
+/// Writes the json schema of the versioned `tfhe` types to the file given with `--output`, or to
+/// stdout. The output is stable for a given version of the code so it can be diffed between
+/// releases.
+fn main() {
+    let matches = Command::new("dump versioned schema")
+        .about("Dump the schema of the versioned tfhe types as json")
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_parser(value_parser!(PathBuf))
+                .help("File where the schema is written, defaults to stdout"),
+        )
+        .get_matches();
+
+    let registry = tfhe_schema_registry();
+    let json = serde_json::to_string_pretty(&registry).unwrap();
+
+    match matches.get_one::<PathBuf>("output") {
+        Some(path) => {
+            println!("Writing versioned schema to {}", path.display());
+            std::fs::write(path, json + "\n").unwrap();
+        }
+        None => writeln!(std::io::stdout(), "{json}").unwrap(),
+    }
+}
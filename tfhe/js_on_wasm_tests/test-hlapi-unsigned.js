@@ -115,6 +115,15 @@ test("hlapi_client_key_encrypt_decrypt_uint8_big", (t) => {
   );
   let safe_deserialized_decrypted = safe_deserialized.decrypt(clientKey);
   assert.deepStrictEqual(safe_deserialized_decrypted, clear);
+
+  let compressed_safe_serialized = encrypted.safe_serialize_with_compression(
+    BigInt(10000000),
+  );
+  let compressed_safe_deserialized = FheUint8.safe_deserialize(
+    compressed_safe_serialized,
+    BigInt(10000000),
+  );
+  assert.deepStrictEqual(compressed_safe_deserialized.decrypt(clientKey), clear);
 });
 
 test("hlapi_compressed_public_client_uint8_big", (t) => {
@@ -300,6 +300,35 @@ macro_rules! impl_safe_serialize_on_type {
                     *result = buffer.into();
                 })
             }
+
+            #[no_mangle]
+            /// Serializes safely and compresses the serialized data with lz4.
+            ///
+            /// The result can be loaded with the `safe_deserialize` functions, the data is
+            /// decompressed transparently.
+            ///
+            /// - `serialized_size_limit`: size limit (in number of byte) of the serialized object,
+            ///    both before and after compression (to avoid out of memory attacks)
+            pub unsafe extern "C" fn [<$wrapper_type:snake _safe_serialize_with_compression>](
+                sself: *const $wrapper_type,
+                result: *mut crate::c_api::buffer::DynamicBuffer,
+                serialized_size_limit: u64,
+            ) -> ::std::os::raw::c_int {
+                crate::c_api::utils::catch_panic(|| {
+                    crate::c_api::utils::check_ptr_is_non_null_and_aligned(result).unwrap();
+
+                    let mut buffer = vec![];
+
+                    let sself = crate::c_api::utils::get_ref_checked(sself).unwrap();
+
+                    crate::safe_serialization::SerializationConfig::new(serialized_size_limit)
+                        .enable_compression(crate::safe_serialization::CompressionCodec::Lz4)
+                        .serialize_into(&sself.0, &mut buffer)
+                        .unwrap();
+
+                    *result = buffer.into();
+                })
+            }
         }
     }
 }
@@ -3,10 +3,12 @@
 //! like the Fourier domain.
 
 use crate::core_crypto::commons::computation_buffers::ComputationBuffers;
+use crate::core_crypto::commons::math::ntt::ntt128::Ntt128;
 use crate::core_crypto::commons::math::ntt::ntt64::Ntt64;
 use crate::core_crypto::commons::traits::*;
 use crate::core_crypto::entities::*;
 use crate::core_crypto::fft_impl::fft128::math::fft::Fft128;
+use crate::core_crypto::fft_impl::fft32::math::fft::Fft32;
 use crate::core_crypto::fft_impl::fft64::crypto::bootstrap::fill_with_forward_fourier_scratch;
 use crate::core_crypto::fft_impl::fft64::math::fft::{Fft, FftView};
 use dyn_stack::{PodStack, SizeOverflow, StackReq};
@@ -441,3 +443,197 @@ pub fn par_convert_standard_lwe_bootstrap_key_to_ntt64<InputCont, OutputCont>(
             }
         });
 }
+
+/// Convert an [`LWE bootstrap key`](`LweBootstrapKey`) with standard coefficients to the Fourier
+/// domain.
+///
+/// See [`programmable_bootstrap_f32_lwe_ciphertext`](`crate::core_crypto::algorithms::programmable_bootstrap_f32_lwe_ciphertext`) for usage.
+pub fn convert_standard_lwe_bootstrap_key_to_fourier_32<Scalar, InputCont, OutputCont>(
+    input_bsk: &LweBootstrapKey<InputCont>,
+    output_bsk: &mut Fourier32LweBootstrapKey<OutputCont>,
+) where
+    Scalar: UnsignedTorus,
+    InputCont: Container<Element = Scalar>,
+    OutputCont: ContainerMut<Element = f32>,
+{
+    assert_eq!(
+        input_bsk.polynomial_size(),
+        output_bsk.polynomial_size(),
+        "Mismatched PolynomialSize between input_bsk {:?} and output_bsk {:?}",
+        input_bsk.polynomial_size(),
+        output_bsk.polynomial_size(),
+    );
+
+    assert_eq!(
+        input_bsk.glwe_size(),
+        output_bsk.glwe_size(),
+        "Mismatched GlweSize"
+    );
+
+    assert_eq!(
+        input_bsk.decomposition_base_log(),
+        output_bsk.decomposition_base_log(),
+        "Mismatched DecompositionBaseLog between input_bsk {:?} and output_bsk {:?}",
+        input_bsk.glwe_size(),
+        output_bsk.glwe_size(),
+    );
+
+    assert_eq!(
+        input_bsk.decomposition_level_count(),
+        output_bsk.decomposition_level_count(),
+        "Mismatched DecompositionLevelCount between input_bsk {:?} and output_bsk {:?}",
+        input_bsk.decomposition_level_count(),
+        output_bsk.decomposition_level_count(),
+    );
+
+    assert_eq!(
+        input_bsk.input_lwe_dimension(),
+        output_bsk.input_lwe_dimension(),
+        "Mismatched input LweDimension between input_bsk {:?} and output_bsk {:?}",
+        input_bsk.input_lwe_dimension(),
+        output_bsk.input_lwe_dimension(),
+    );
+
+    let fft = Fft32::new(output_bsk.polynomial_size());
+    let fft = fft.as_view();
+
+    output_bsk.fill_with_forward_fourier(input_bsk, fft);
+}
+
+/// Convert an [`LWE bootstrap key`](`LweBootstrapKey`) with standard `u128` coefficients to the
+/// NTT domain using a 128 bits CRT based NTT.
+///
+/// Only the native `2^128` ciphertext modulus is supported.
+///
+/// See [`programmable_bootstrap_ntt128_lwe_ciphertext`](`crate::core_crypto::algorithms::programmable_bootstrap_ntt128_lwe_ciphertext`) for usage.
+pub fn convert_standard_lwe_bootstrap_key_to_ntt128<InputCont, OutputCont>(
+    input_bsk: &LweBootstrapKey<InputCont>,
+    output_bsk: &mut Ntt128LweBootstrapKey<OutputCont>,
+) where
+    InputCont: Container<Element = u128>,
+    OutputCont: ContainerMut<Element = u32>,
+{
+    assert_eq!(
+        input_bsk.polynomial_size(),
+        output_bsk.polynomial_size(),
+        "Mismatched PolynomialSize between input_bsk {:?} and output_bsk {:?}",
+        input_bsk.polynomial_size(),
+        output_bsk.polynomial_size(),
+    );
+
+    assert_eq!(
+        input_bsk.glwe_size(),
+        output_bsk.glwe_size(),
+        "Mismatched GlweSize"
+    );
+
+    assert_eq!(
+        input_bsk.decomposition_base_log(),
+        output_bsk.decomposition_base_log(),
+        "Mismatched DecompositionBaseLog between input_bsk {:?} and output_bsk {:?}",
+        input_bsk.decomposition_base_log(),
+        output_bsk.decomposition_base_log(),
+    );
+
+    assert_eq!(
+        input_bsk.decomposition_level_count(),
+        output_bsk.decomposition_level_count(),
+        "Mismatched DecompositionLevelCount between input_bsk {:?} and output_bsk {:?}",
+        input_bsk.decomposition_level_count(),
+        output_bsk.decomposition_level_count(),
+    );
+
+    assert_eq!(
+        input_bsk.input_lwe_dimension(),
+        output_bsk.input_lwe_dimension(),
+        "Mismatched input LweDimension between input_bsk {:?} and output_bsk {:?}",
+        input_bsk.input_lwe_dimension(),
+        output_bsk.input_lwe_dimension(),
+    );
+
+    assert!(
+        input_bsk.ciphertext_modulus().is_native_modulus(),
+        "The 128 bits NTT only supports the native ciphertext modulus, got {:?}",
+        input_bsk.ciphertext_modulus(),
+    );
+
+    let ntt = Ntt128::new(input_bsk.polynomial_size());
+    let ntt = ntt.as_view();
+
+    for (input_poly, output_poly) in input_bsk.as_polynomial_list().iter().zip(
+        output_bsk
+            .as_mut()
+            .chunks_exact_mut(ntt.ntt_polynomial_len()),
+    ) {
+        ntt.forward_normalized(output_poly, input_poly.as_ref());
+    }
+}
+
+pub fn par_convert_standard_lwe_bootstrap_key_to_ntt128<InputCont, OutputCont>(
+    input_bsk: &LweBootstrapKey<InputCont>,
+    output_bsk: &mut Ntt128LweBootstrapKey<OutputCont>,
+) where
+    InputCont: Container<Element = u128> + std::marker::Sync,
+    OutputCont: ContainerMut<Element = u32>,
+{
+    assert_eq!(
+        input_bsk.polynomial_size(),
+        output_bsk.polynomial_size(),
+        "Mismatched PolynomialSize between input_bsk {:?} and output_bsk {:?}",
+        input_bsk.polynomial_size(),
+        output_bsk.polynomial_size(),
+    );
+
+    assert_eq!(
+        input_bsk.glwe_size(),
+        output_bsk.glwe_size(),
+        "Mismatched GlweSize"
+    );
+
+    assert_eq!(
+        input_bsk.decomposition_base_log(),
+        output_bsk.decomposition_base_log(),
+        "Mismatched DecompositionBaseLog between input_bsk {:?} and output_bsk {:?}",
+        input_bsk.decomposition_base_log(),
+        output_bsk.decomposition_base_log(),
+    );
+
+    assert_eq!(
+        input_bsk.decomposition_level_count(),
+        output_bsk.decomposition_level_count(),
+        "Mismatched DecompositionLevelCount between input_bsk {:?} and output_bsk {:?}",
+        input_bsk.decomposition_level_count(),
+        output_bsk.decomposition_level_count(),
+    );
+
+    assert_eq!(
+        input_bsk.input_lwe_dimension(),
+        output_bsk.input_lwe_dimension(),
+        "Mismatched input LweDimension between input_bsk {:?} and output_bsk {:?}",
+        input_bsk.input_lwe_dimension(),
+        output_bsk.input_lwe_dimension(),
+    );
+
+    assert!(
+        input_bsk.ciphertext_modulus().is_native_modulus(),
+        "The 128 bits NTT only supports the native ciphertext modulus, got {:?}",
+        input_bsk.ciphertext_modulus(),
+    );
+
+    let ntt = Ntt128::new(input_bsk.polynomial_size());
+    let ntt = ntt.as_view();
+
+    let polynomial_size = input_bsk.polynomial_size().0;
+
+    input_bsk
+        .as_ref()
+        .par_chunks_exact(polynomial_size)
+        .zip(
+            output_bsk
+                .as_mut()
+                .par_chunks_exact_mut(ntt.ntt_polynomial_len()),
+        )
+        .for_each(|(input_poly, output_poly)| {
+            ntt.forward_normalized(output_poly, input_poly);
+        });
+}
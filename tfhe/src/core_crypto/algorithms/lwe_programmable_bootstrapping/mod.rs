@@ -1,10 +1,14 @@
 pub mod fft128_pbs;
+pub mod fft32_pbs;
 pub mod fft64_pbs;
+pub mod ntt128_pbs;
 pub mod ntt64_bnf_pbs;
 pub mod ntt64_pbs;
 
 pub use fft128_pbs::*;
+pub use fft32_pbs::*;
 pub use fft64_pbs::*;
+pub use ntt128_pbs::*;
 pub use ntt64_bnf_pbs::*;
 pub use ntt64_pbs::*;
 
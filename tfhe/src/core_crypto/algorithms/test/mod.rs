@@ -306,6 +306,24 @@ pub const FFT_U32_PARAMS: FftTestParams<u32> = FftTestParams {
     ciphertext_modulus: CiphertextModulus::new_native(),
 };
 
+// Same values as the `boolean` DEFAULT_PARAMETERS, the 32 bits parameter set the f32 FFT
+// implementation is meant for.
+pub const FFT32_U32_PARAMS: FftTestParams<u32> = FftTestParams {
+    lwe_dimension: LweDimension(805),
+    glwe_dimension: GlweDimension(3),
+    polynomial_size: PolynomialSize(512),
+    #[allow(clippy::excessive_precision)]
+    lwe_noise_distribution: DynamicDistribution::new_gaussian_from_std_dev(StandardDev(
+        5.8615896642671336e-06,
+    )),
+    glwe_noise_distribution: DynamicDistribution::new_gaussian_from_std_dev(StandardDev(
+        9.315272083503367e-10,
+    )),
+    pbs_base_log: DecompositionBaseLog(10),
+    pbs_level: DecompositionLevelCount(2),
+    ciphertext_modulus: CiphertextModulus::new_native(),
+};
+
 pub const FFT_U64_PARAMS: FftTestParams<u64> = FftTestParams {
     lwe_dimension: LweDimension(742),
     glwe_dimension: GlweDimension(1),
@@ -22,6 +22,8 @@ pub mod lwe_private_functional_packing_keyswitch_key;
 pub mod lwe_private_functional_packing_keyswitch_key_list;
 pub mod lwe_public_key;
 pub mod lwe_secret_key;
+pub mod ntt128_ggsw_ciphertext;
+pub mod ntt128_lwe_bootstrap_key;
 pub mod ntt_ggsw_ciphertext;
 pub mod ntt_ggsw_ciphertext_list;
 pub mod ntt_lwe_bootstrap_key;
@@ -0,0 +1,8 @@
+use tfhe_versionable::VersionsDispatch;
+
+use crate::core_crypto::prelude::{Container, Ntt128GgswCiphertext};
+
+#[derive(VersionsDispatch)]
+pub enum Ntt128GgswCiphertextVersions<C: Container<Element = u32>> {
+    V0(Ntt128GgswCiphertext<C>),
+}
@@ -7,8 +7,8 @@ use tfhe_fft::c64;
 
 use crate::core_crypto::fft_impl::fft64::math::fft::FourierPolynomialList;
 use crate::core_crypto::prelude::{
-    Container, Fourier128GgswCiphertext, Fourier128LweBootstrapKey, FourierGgswCiphertext,
-    FourierLweBootstrapKey, IntoContainerOwned,
+    Container, Fourier128GgswCiphertext, Fourier128LweBootstrapKey, Fourier32GgswCiphertext,
+    Fourier32LweBootstrapKey, FourierGgswCiphertext, FourierLweBootstrapKey, IntoContainerOwned,
 };
 
 #[derive(Serialize)]
@@ -102,3 +102,13 @@ pub enum Fourier128GgswCiphertextVersions<C: Container<Element = f64>> {
     V0(Deprecated<Fourier128GgswCiphertext<C>>),
     V1(Fourier128GgswCiphertext<C>),
 }
+
+#[derive(VersionsDispatch)]
+pub enum Fourier32LweBootstrapKeyVersions<C: Container<Element = f32>> {
+    V0(Fourier32LweBootstrapKey<C>),
+}
+
+#[derive(VersionsDispatch)]
+pub enum Fourier32GgswCiphertextVersions<C: Container<Element = f32>> {
+    V0(Fourier32GgswCiphertext<C>),
+}
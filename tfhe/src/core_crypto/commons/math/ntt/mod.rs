@@ -1 +1,2 @@
+pub mod ntt128;
 pub mod ntt64;
@@ -0,0 +1,182 @@
+use crate::core_crypto::commons::utils::izip;
+use crate::core_crypto::prelude::*;
+use aligned_vec::CACHELINE_ALIGN;
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+use tfhe_ntt::native128::Plan32;
+use tfhe_ntt::prime32::Plan;
+
+/// Number of 32 bits primes used to represent a polynomial with `u128` coefficients in the NTT
+/// domain.
+///
+/// The product of the primes is larger than `2^300`, which leaves enough room to compute the
+/// external product of a bootstrap exactly before reconstructing the result modulo `2^128`.
+pub const NTT128_PRIME_COUNT: usize = 10;
+
+/// Negacyclic NTT over `u128` using the Chinese remainder theorem on [`NTT128_PRIME_COUNT`] 32
+/// bits primes. See [`Ntt128View`] for transform functions.
+///
+/// A polynomial of size `N` in the NTT domain is stored as `NTT128_PRIME_COUNT * N` residues,
+/// grouped by prime.
+#[derive(Clone, Debug)]
+pub struct Ntt128 {
+    plan: Arc<Plan32>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Ntt128View<'a> {
+    pub(crate) plan: &'a Plan32,
+}
+
+impl Ntt128 {
+    #[inline]
+    pub fn as_view(&self) -> Ntt128View<'_> {
+        Ntt128View { plan: &self.plan }
+    }
+}
+
+type PlanMap = RwLock<HashMap<usize, Arc<OnceLock<Arc<Plan32>>>>>;
+pub(crate) static PLANS: OnceLock<PlanMap> = OnceLock::new();
+fn plans() -> &'static PlanMap {
+    PLANS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+impl Ntt128 {
+    /// Real polynomial of size `size`.
+    pub fn new(size: PolynomialSize) -> Self {
+        let global_plans = plans();
+
+        let n = size.0;
+        let get_plan = || {
+            let plans = global_plans.read().unwrap();
+            let plan = plans.get(&n).cloned();
+            drop(plans);
+
+            plan.map(|p| {
+                p.get_or_init(|| {
+                    Arc::new(Plan32::try_new(n).unwrap_or_else(|| {
+                        panic!("could not generate a 128 bits NTT plan for the given size ({n})")
+                    }))
+                })
+                .clone()
+            })
+        };
+
+        // could not find a plan of the given size, we lock the map again and try to insert it
+        let mut plans = global_plans.write().unwrap();
+        if let Entry::Vacant(v) = plans.entry(n) {
+            v.insert(Arc::new(OnceLock::new()));
+        }
+
+        drop(plans);
+
+        Self {
+            plan: get_plan().unwrap(),
+        }
+    }
+}
+
+impl<'a> Ntt128View<'a> {
+    pub fn polynomial_size(self) -> PolynomialSize {
+        PolynomialSize(self.plan.ntt_size())
+    }
+
+    /// Return the number of `u32` residues used to store a single polynomial in the NTT domain.
+    pub fn ntt_polynomial_len(self) -> usize {
+        NTT128_PRIME_COUNT * self.plan.ntt_size()
+    }
+
+    fn prime_plans(self) -> [&'a Plan; NTT128_PRIME_COUNT] {
+        let plan = self.plan;
+        [
+            plan.ntt_0(),
+            plan.ntt_1(),
+            plan.ntt_2(),
+            plan.ntt_3(),
+            plan.ntt_4(),
+            plan.ntt_5(),
+            plan.ntt_6(),
+            plan.ntt_7(),
+            plan.ntt_8(),
+            plan.ntt_9(),
+        ]
+    }
+
+    /// Return the memory required for [`Self::add_backward`].
+    pub fn backward_scratch(self) -> Result<StackReq, SizeOverflow> {
+        StackReq::try_new_aligned::<u128>(self.plan.ntt_size(), CACHELINE_ALIGN)
+    }
+
+    /// Perform a forward negacyclic NTT of `standard` and store the residues in `ntt`.
+    ///
+    /// Negative values are expected to be encoded in two's complement, the transform is then
+    /// exact as long as the results accumulated in the NTT domain stay below half the product of
+    /// the primes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ntt.len() != NTT128_PRIME_COUNT * standard.len()`.
+    pub fn forward(self, ntt: &mut [u32], standard: &[u128]) {
+        let n = standard.len();
+        assert_eq!(ntt.len(), NTT128_PRIME_COUNT * n);
+
+        let [p0, p1, p2, p3, p4, p5, p6, p7, p8, p9] = split_residues(ntt, n);
+        self.plan
+            .fwd(standard, p0, p1, p2, p3, p4, p5, p6, p7, p8, p9);
+    }
+
+    /// Same as [`Self::forward`], the residues are additionally normalized so that the inverse
+    /// transform of a product involving `ntt` does not need an extra division by the polynomial
+    /// size.
+    pub fn forward_normalized(self, ntt: &mut [u32], standard: &[u128]) {
+        self.forward(ntt, standard);
+
+        let n = standard.len();
+        for (plan, residues) in izip!(self.prime_plans(), ntt.chunks_exact_mut(n)) {
+            plan.normalize(residues);
+        }
+    }
+
+    /// Accumulate the product of `lhs` and `rhs` in `acc`, all of them in the NTT domain.
+    pub fn mul_accumulate(self, acc: &mut [u32], lhs: &[u32], rhs: &[u32]) {
+        let n = self.plan.ntt_size();
+        debug_assert_eq!(acc.len(), NTT128_PRIME_COUNT * n);
+
+        for (plan, acc, lhs, rhs) in izip!(
+            self.prime_plans(),
+            acc.chunks_exact_mut(n),
+            lhs.chunks_exact(n),
+            rhs.chunks_exact(n),
+        ) {
+            plan.mul_accumulate(acc, lhs, rhs);
+        }
+    }
+
+    /// Perform an inverse negacyclic NTT of `ntt` and add the result to `standard` modulo
+    /// `2^128`.
+    ///
+    /// The content of `ntt` is used as scratch space and is left in an unspecified state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ntt.len() != NTT128_PRIME_COUNT * standard.len()`.
+    pub fn add_backward(self, standard: &mut [u128], ntt: &mut [u32], stack: &mut PodStack) {
+        let n = standard.len();
+        assert_eq!(ntt.len(), NTT128_PRIME_COUNT * n);
+
+        let (tmp, _) = stack.make_aligned_raw::<u128>(n, CACHELINE_ALIGN);
+        let [p0, p1, p2, p3, p4, p5, p6, p7, p8, p9] = split_residues(ntt, n);
+        self.plan.inv(tmp, p0, p1, p2, p3, p4, p5, p6, p7, p8, p9);
+
+        for (out, inp) in izip!(standard, &*tmp) {
+            *out = out.wrapping_add(*inp);
+        }
+    }
+}
+
+fn split_residues(ntt: &mut [u32], n: usize) -> [&mut [u32]; NTT128_PRIME_COUNT] {
+    let mut chunks = ntt.chunks_exact_mut(n);
+    core::array::from_fn(|_| chunks.next().unwrap())
+}
@@ -31,6 +31,7 @@ pub mod serialization_proxy {
 }
 
 pub(crate) use serialization_proxy::*;
+use tfhe_versionable::schema::{DataSchema, SchemaRegistry, VersionSchema};
 use tfhe_versionable::{Unversionize, Versionize, VersionizeOwned};
 
 #[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
@@ -54,6 +55,18 @@ impl VersionizeOwned for CompressionSeed {
     fn versionize_owned(self) -> Self::VersionedOwned {
         self.into()
     }
+
+    // The seed is stored with its own serde impl inside the single version of the dispatch enum
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        registry.register_versioned::<Self, Self::VersionedOwned>(|_| {
+            vec![VersionSchema {
+                version: "V0".to_string(),
+                type_name: std::any::type_name::<Self>().to_string(),
+                layout: DataSchema::opaque::<Self>(),
+                upgrades_to: None,
+            }]
+        })
+    }
 }
 
 impl Unversionize for CompressionSeed {
@@ -6,6 +6,7 @@ use crate::core_crypto::backward_compatibility::entities::lwe_bootstrap_key::Lwe
 use crate::core_crypto::commons::parameters::*;
 use crate::core_crypto::commons::traits::*;
 use crate::core_crypto::entities::*;
+use crate::named::Named;
 
 /// An [`LWE bootstrap key`](`LweBootstrapKey`).
 ///
@@ -109,6 +110,13 @@ where
     ggsw_list: GgswCiphertextList<C>,
 }
 
+impl<C: Container> Named for LweBootstrapKey<C>
+where
+    C::Element: UnsignedInteger,
+{
+    const NAME: &'static str = "core_crypto::LweBootstrapKey";
+}
+
 impl<Scalar: UnsignedInteger, C: Container<Element = Scalar>> std::ops::Deref
     for LweBootstrapKey<C>
 {
@@ -7,6 +7,7 @@ use crate::core_crypto::backward_compatibility::entities::lwe_keyswitch_key::Lwe
 use crate::core_crypto::commons::parameters::*;
 use crate::core_crypto::commons::traits::*;
 use crate::core_crypto::entities::*;
+use crate::named::Named;
 
 /// An [`LWE keyswitch key`](`LweKeyswitchKey`).
 ///
@@ -89,6 +90,13 @@ where
     ciphertext_modulus: CiphertextModulus<C::Element>,
 }
 
+impl<C: Container> Named for LweKeyswitchKey<C>
+where
+    C::Element: UnsignedInteger,
+{
+    const NAME: &'static str = "core_crypto::LweKeyswitchKey";
+}
+
 impl<T: UnsignedInteger, C: Container<Element = T>> AsRef<[T]> for LweKeyswitchKey<C> {
     fn as_ref(&self) -> &[T] {
         self.data.as_ref()
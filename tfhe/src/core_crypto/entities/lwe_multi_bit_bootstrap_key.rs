@@ -10,6 +10,7 @@ use crate::core_crypto::commons::parameters::*;
 use crate::core_crypto::commons::traits::*;
 use crate::core_crypto::entities::*;
 use crate::core_crypto::fft_impl::fft64::math::fft::FourierPolynomialList;
+use crate::named::Named;
 use aligned_vec::{avec, ABox};
 use tfhe_fft::c64;
 use tfhe_versionable::Versionize;
@@ -27,6 +28,13 @@ where
     grouping_factor: LweBskGroupingFactor,
 }
 
+impl<C: Container> Named for LweMultiBitBootstrapKey<C>
+where
+    C::Element: UnsignedInteger,
+{
+    const NAME: &'static str = "core_crypto::LweMultiBitBootstrapKey";
+}
+
 impl<Scalar: UnsignedInteger, C: Container<Element = Scalar>> std::ops::Deref
     for LweMultiBitBootstrapKey<C>
 {
@@ -400,6 +408,10 @@ pub struct FourierLweMultiBitBootstrapKey<C: Container<Element = c64>> {
     grouping_factor: LweBskGroupingFactor,
 }
 
+impl<C: Container<Element = c64>> Named for FourierLweMultiBitBootstrapKey<C> {
+    const NAME: &'static str = "core_crypto::FourierLweMultiBitBootstrapKey";
+}
+
 pub type FourierLweMultiBitBootstrapKeyOwned = FourierLweMultiBitBootstrapKey<ABox<[c64]>>;
 pub type FourierLweMultiBitBootstrapKeyView<'a> = FourierLweMultiBitBootstrapKey<&'a [c64]>;
 pub type FourierLweMultiBitBootstrapKeyMutView<'a> = FourierLweMultiBitBootstrapKey<&'a mut [c64]>;
@@ -10,6 +10,7 @@ use crate::core_crypto::entities::glwe_ciphertext::glwe_ciphertext_size;
 use crate::core_crypto::entities::glwe_ciphertext_list::{
     GlweCiphertextListCreationMetadata, GlweCiphertextListMutView, GlweCiphertextListView,
 };
+use crate::named::Named;
 
 /// A keyswitching key allowing to keyswitch [`an LWE ciphertext`](super::LweCiphertext) to
 /// [`a GLWE ciphertext`](super::GlweCiphertext) allowing to pack several LWE ciphertexts into a
@@ -28,6 +29,13 @@ where
     ciphertext_modulus: CiphertextModulus<C::Element>,
 }
 
+impl<C: Container> Named for LwePackingKeyswitchKey<C>
+where
+    C::Element: UnsignedInteger,
+{
+    const NAME: &'static str = "core_crypto::LwePackingKeyswitchKey";
+}
+
 impl<T: UnsignedInteger, C: Container<Element = T>> AsRef<[T]> for LwePackingKeyswitchKey<C> {
     fn as_ref(&self) -> &[T] {
         self.data.as_ref()
@@ -27,6 +27,8 @@ pub mod lwe_private_functional_packing_keyswitch_key;
 pub mod lwe_private_functional_packing_keyswitch_key_list;
 pub mod lwe_public_key;
 pub mod lwe_secret_key;
+pub mod ntt128_ggsw_ciphertext;
+pub mod ntt128_lwe_bootstrap_key;
 pub mod ntt_ggsw_ciphertext;
 pub mod ntt_ggsw_ciphertext_list;
 pub mod ntt_lwe_bootstrap_key;
@@ -55,6 +57,12 @@ pub use crate::core_crypto::fft_impl::fft128::crypto::bootstrap::{
 pub use crate::core_crypto::fft_impl::fft128::crypto::ggsw::{
     Fourier128GgswCiphertext, Fourier128GgswLevelMatrix, Fourier128GgswLevelRow,
 };
+pub use crate::core_crypto::fft_impl::fft32::crypto::bootstrap::{
+    Fourier32LweBootstrapKey, Fourier32LweBootstrapKeyOwned,
+};
+pub use crate::core_crypto::fft_impl::fft32::crypto::ggsw::{
+    Fourier32GgswCiphertext, Fourier32GgswLevelMatrix, Fourier32GgswLevelRow,
+};
 pub use crate::core_crypto::fft_impl::fft64::crypto::bootstrap::{
     FourierLweBootstrapKey, FourierLweBootstrapKeyOwned,
 };
@@ -87,6 +95,8 @@ pub use lwe_private_functional_packing_keyswitch_key::*;
 pub use lwe_private_functional_packing_keyswitch_key_list::*;
 pub use lwe_public_key::*;
 pub use lwe_secret_key::*;
+pub use ntt128_ggsw_ciphertext::*;
+pub use ntt128_lwe_bootstrap_key::*;
 pub use ntt_ggsw_ciphertext::*;
 pub use ntt_ggsw_ciphertext_list::*;
 pub use ntt_lwe_bootstrap_key::*;
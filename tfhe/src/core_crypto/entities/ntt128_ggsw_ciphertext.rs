@@ -0,0 +1,347 @@
+use crate::core_crypto::backward_compatibility::entities::ntt128_ggsw_ciphertext::Ntt128GgswCiphertextVersions;
+use crate::core_crypto::commons::math::decomposition::DecompositionLevel;
+use crate::core_crypto::commons::math::ntt::ntt128::NTT128_PRIME_COUNT;
+use crate::core_crypto::commons::parameters::{
+    DecompositionBaseLog, DecompositionLevelCount, GlweSize, PolynomialSize,
+};
+use crate::core_crypto::commons::traits::{Container, ContainerMut, Split};
+use crate::core_crypto::entities::ggsw_ciphertext::{ggsw_ciphertext_size, ggsw_level_matrix_size};
+use crate::core_crypto::entities::glwe_ciphertext::glwe_ciphertext_size;
+use aligned_vec::{avec, ABox};
+use tfhe_versionable::Versionize;
+
+/// A [`GGSW ciphertext`](`crate::core_crypto::entities::GgswCiphertext`) with `u128` coefficients
+/// in the 128 bits NTT domain.
+///
+/// Each polynomial is stored as its residues modulo the
+/// [`NTT128_PRIME_COUNT`](`crate::core_crypto::commons::math::ntt::ntt128::NTT128_PRIME_COUNT`)
+/// primes of the [`Ntt128`](`crate::core_crypto::commons::math::ntt::ntt128::Ntt128`), grouped by
+/// prime.
+///
+/// See [`the formal definition of a GGSW
+/// ciphertext`](`crate::core_crypto::entities::GgswCiphertext#formal-definition`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, Versionize)]
+#[versionize(Ntt128GgswCiphertextVersions)]
+pub struct Ntt128GgswCiphertext<C: Container<Element = u32>> {
+    data: C,
+    polynomial_size: PolynomialSize,
+    glwe_size: GlweSize,
+    decomposition_base_log: DecompositionBaseLog,
+    decomposition_level_count: DecompositionLevelCount,
+}
+
+/// Return the number of `u32` residues required to store an [`Ntt128GgswCiphertext`].
+pub fn ntt128_ggsw_ciphertext_size(
+    glwe_size: GlweSize,
+    polynomial_size: PolynomialSize,
+    decomposition_level_count: DecompositionLevelCount,
+) -> usize {
+    NTT128_PRIME_COUNT * ggsw_ciphertext_size(glwe_size, polynomial_size, decomposition_level_count)
+}
+
+impl<C: Container<Element = u32>> AsRef<[u32]> for Ntt128GgswCiphertext<C> {
+    fn as_ref(&self) -> &[u32] {
+        self.data.as_ref()
+    }
+}
+
+impl<C: ContainerMut<Element = u32>> AsMut<[u32]> for Ntt128GgswCiphertext<C> {
+    fn as_mut(&mut self) -> &mut [u32] {
+        self.data.as_mut()
+    }
+}
+
+impl<C: Container<Element = u32>> Ntt128GgswCiphertext<C> {
+    /// Create an [`Ntt128GgswCiphertext`] from an existing container.
+    ///
+    /// # Note
+    ///
+    /// This docstring exhibits [`Ntt128GgswCiphertext`] primitives usage.
+    ///
+    /// ```rust
+    /// use tfhe::core_crypto::prelude::*;
+    ///
+    /// // DISCLAIMER: these toy example parameters are not guaranteed to be secure or yield correct
+    /// // computations
+    /// // Define parameters for Ntt128GgswCiphertext creation
+    /// let glwe_size = GlweSize(2);
+    /// let polynomial_size = PolynomialSize(1024);
+    /// let decomp_base_log = DecompositionBaseLog(8);
+    /// let decomp_level_count = DecompositionLevelCount(3);
+    ///
+    /// // Create a new Ntt128GgswCiphertext
+    /// let ggsw = Ntt128GgswCiphertext::new(
+    ///     glwe_size,
+    ///     polynomial_size,
+    ///     decomp_base_log,
+    ///     decomp_level_count,
+    /// );
+    ///
+    /// assert_eq!(ggsw.glwe_size(), glwe_size);
+    /// assert_eq!(ggsw.polynomial_size(), polynomial_size);
+    /// assert_eq!(ggsw.decomposition_base_log(), decomp_base_log);
+    /// assert_eq!(ggsw.decomposition_level_count(), decomp_level_count);
+    ///
+    /// // Demonstrate how to recover the allocated container
+    /// let underlying_container = ggsw.into_container();
+    ///
+    /// // Recreate a ciphertext using from_container
+    /// let ggsw = Ntt128GgswCiphertext::from_container(
+    ///     underlying_container,
+    ///     glwe_size,
+    ///     polynomial_size,
+    ///     decomp_base_log,
+    ///     decomp_level_count,
+    /// );
+    ///
+    /// assert_eq!(ggsw.glwe_size(), glwe_size);
+    /// assert_eq!(ggsw.polynomial_size(), polynomial_size);
+    /// assert_eq!(ggsw.decomposition_base_log(), decomp_base_log);
+    /// assert_eq!(ggsw.decomposition_level_count(), decomp_level_count);
+    /// ```
+    pub fn from_container(
+        data: C,
+        glwe_size: GlweSize,
+        polynomial_size: PolynomialSize,
+        decomposition_base_log: DecompositionBaseLog,
+        decomposition_level_count: DecompositionLevelCount,
+    ) -> Self {
+        assert_eq!(
+            data.container_len(),
+            ntt128_ggsw_ciphertext_size(glwe_size, polynomial_size, decomposition_level_count)
+        );
+
+        Self {
+            data,
+            polynomial_size,
+            glwe_size,
+            decomposition_base_log,
+            decomposition_level_count,
+        }
+    }
+
+    /// Return the [`PolynomialSize`] of the [`Ntt128GgswCiphertext`].
+    ///
+    /// See [`Ntt128GgswCiphertext::from_container`] for usage.
+    pub fn polynomial_size(&self) -> PolynomialSize {
+        self.polynomial_size
+    }
+
+    /// Return the [`GlweSize`] of the [`Ntt128GgswCiphertext`].
+    ///
+    /// See [`Ntt128GgswCiphertext::from_container`] for usage.
+    pub fn glwe_size(&self) -> GlweSize {
+        self.glwe_size
+    }
+
+    /// Return the [`DecompositionBaseLog`] of the [`Ntt128GgswCiphertext`].
+    ///
+    /// See [`Ntt128GgswCiphertext::from_container`] for usage.
+    pub fn decomposition_base_log(&self) -> DecompositionBaseLog {
+        self.decomposition_base_log
+    }
+
+    /// Return the [`DecompositionLevelCount`] of the [`Ntt128GgswCiphertext`].
+    ///
+    /// See [`Ntt128GgswCiphertext::from_container`] for usage.
+    pub fn decomposition_level_count(&self) -> DecompositionLevelCount {
+        self.decomposition_level_count
+    }
+
+    /// Consume the entity and return its underlying container.
+    ///
+    /// See [`Ntt128GgswCiphertext::from_container`] for usage.
+    pub fn into_container(self) -> C {
+        self.data
+    }
+
+    /// Return a view of the [`Ntt128GgswCiphertext`]. This is useful if an algorithm takes a view
+    /// by value.
+    pub fn as_view(&self) -> Ntt128GgswCiphertextView<'_> {
+        Ntt128GgswCiphertextView {
+            data: self.data.as_ref(),
+            polynomial_size: self.polynomial_size,
+            glwe_size: self.glwe_size,
+            decomposition_base_log: self.decomposition_base_log,
+            decomposition_level_count: self.decomposition_level_count,
+        }
+    }
+
+    /// Return an iterator over the level matrices.
+    pub fn into_levels(self) -> impl DoubleEndedIterator<Item = Ntt128GgswLevelMatrix<C>>
+    where
+        C: Split,
+    {
+        let decomposition_level_count = self.decomposition_level_count.0;
+        self.data
+            .split_into(decomposition_level_count)
+            .enumerate()
+            .map(move |(i, slice)| {
+                Ntt128GgswLevelMatrix::from_container(
+                    slice,
+                    self.glwe_size,
+                    self.polynomial_size,
+                    DecompositionLevel(decomposition_level_count - i),
+                )
+            })
+    }
+}
+
+impl<C: ContainerMut<Element = u32>> Ntt128GgswCiphertext<C> {
+    /// Mutable variant of [`Ntt128GgswCiphertext::as_view`].
+    pub fn as_mut_view(&mut self) -> Ntt128GgswCiphertextMutView<'_> {
+        Ntt128GgswCiphertextMutView {
+            data: self.data.as_mut(),
+            polynomial_size: self.polynomial_size,
+            glwe_size: self.glwe_size,
+            decomposition_base_log: self.decomposition_base_log,
+            decomposition_level_count: self.decomposition_level_count,
+        }
+    }
+}
+
+impl Ntt128GgswCiphertext<ABox<[u32]>> {
+    /// Allocate memory and create a new owned [`Ntt128GgswCiphertext`].
+    ///
+    /// See [`Ntt128GgswCiphertext::from_container`] for usage.
+    pub fn new(
+        glwe_size: GlweSize,
+        polynomial_size: PolynomialSize,
+        decomposition_base_log: DecompositionBaseLog,
+        decomposition_level_count: DecompositionLevelCount,
+    ) -> Self {
+        let boxed = avec![
+            0u32;
+            ntt128_ggsw_ciphertext_size(glwe_size, polynomial_size, decomposition_level_count)
+        ]
+        .into_boxed_slice();
+
+        Self::from_container(
+            boxed,
+            glwe_size,
+            polynomial_size,
+            decomposition_base_log,
+            decomposition_level_count,
+        )
+    }
+}
+
+pub type Ntt128GgswCiphertextOwned = Ntt128GgswCiphertext<ABox<[u32]>>;
+pub type Ntt128GgswCiphertextView<'data> = Ntt128GgswCiphertext<&'data [u32]>;
+pub type Ntt128GgswCiphertextMutView<'data> = Ntt128GgswCiphertext<&'data mut [u32]>;
+
+/// A matrix containing a single level of gadget decomposition, in the 128 bits NTT domain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ntt128GgswLevelMatrix<C: Container<Element = u32>> {
+    data: C,
+    glwe_size: GlweSize,
+    polynomial_size: PolynomialSize,
+    decomposition_level: DecompositionLevel,
+}
+
+impl<C: Container<Element = u32>> Ntt128GgswLevelMatrix<C> {
+    pub fn from_container(
+        data: C,
+        glwe_size: GlweSize,
+        polynomial_size: PolynomialSize,
+        decomposition_level: DecompositionLevel,
+    ) -> Self {
+        assert_eq!(
+            data.container_len(),
+            NTT128_PRIME_COUNT * ggsw_level_matrix_size(glwe_size, polynomial_size)
+        );
+        Self {
+            data,
+            glwe_size,
+            polynomial_size,
+            decomposition_level,
+        }
+    }
+
+    /// Return an iterator over the rows of the level matrices.
+    pub fn into_rows(self) -> impl DoubleEndedIterator<Item = Ntt128GgswLevelRow<C>>
+    where
+        C: Split,
+    {
+        let row_count = self.row_count();
+        self.data
+            .split_into(row_count)
+            .map(move |slice| Ntt128GgswLevelRow {
+                data: slice,
+                polynomial_size: self.polynomial_size,
+                glwe_size: self.glwe_size,
+                decomposition_level: self.decomposition_level,
+            })
+    }
+
+    pub fn polynomial_size(&self) -> PolynomialSize {
+        self.polynomial_size
+    }
+
+    pub fn glwe_size(&self) -> GlweSize {
+        self.glwe_size
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.glwe_size.0
+    }
+
+    pub fn decomposition_level(&self) -> DecompositionLevel {
+        self.decomposition_level
+    }
+
+    pub fn into_container(self) -> C {
+        self.data
+    }
+}
+
+/// A row of a GGSW level matrix, in the 128 bits NTT domain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ntt128GgswLevelRow<C: Container<Element = u32>> {
+    data: C,
+    glwe_size: GlweSize,
+    polynomial_size: PolynomialSize,
+    decomposition_level: DecompositionLevel,
+}
+
+impl<C: Container<Element = u32>> AsRef<[u32]> for Ntt128GgswLevelRow<C> {
+    fn as_ref(&self) -> &[u32] {
+        self.data.as_ref()
+    }
+}
+
+impl<C: Container<Element = u32>> Ntt128GgswLevelRow<C> {
+    pub fn from_container(
+        data: C,
+        glwe_size: GlweSize,
+        polynomial_size: PolynomialSize,
+        decomposition_level: DecompositionLevel,
+    ) -> Self {
+        assert_eq!(
+            data.container_len(),
+            NTT128_PRIME_COUNT * glwe_ciphertext_size(glwe_size, polynomial_size)
+        );
+        Self {
+            data,
+            glwe_size,
+            polynomial_size,
+            decomposition_level,
+        }
+    }
+
+    pub fn polynomial_size(&self) -> PolynomialSize {
+        self.polynomial_size
+    }
+
+    pub fn glwe_size(&self) -> GlweSize {
+        self.glwe_size
+    }
+
+    pub fn decomposition_level(&self) -> DecompositionLevel {
+        self.decomposition_level
+    }
+
+    pub fn into_container(self) -> C {
+        self.data
+    }
+}
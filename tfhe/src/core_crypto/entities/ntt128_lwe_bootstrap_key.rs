@@ -0,0 +1,279 @@
+use crate::core_crypto::backward_compatibility::entities::ntt128_lwe_bootstrap_key::Ntt128LweBootstrapKeyVersions;
+use crate::core_crypto::commons::parameters::{
+    DecompositionBaseLog, DecompositionLevelCount, GlweSize, LweDimension, PolynomialSize,
+};
+use crate::core_crypto::commons::traits::{Container, ContainerMut, Split};
+use crate::core_crypto::entities::ntt128_ggsw_ciphertext::{
+    ntt128_ggsw_ciphertext_size, Ntt128GgswCiphertext,
+};
+use aligned_vec::{avec, ABox};
+use tfhe_versionable::Versionize;
+
+/// An [`LWE bootstrap key`](`crate::core_crypto::entities::LweBootstrapKey`) with `u128`
+/// coefficients in the 128 bits NTT domain.
+///
+/// The key is a list of [`Ntt128GgswCiphertext`], each polynomial being stored as its residues
+/// modulo the primes of the
+/// [`Ntt128`](`crate::core_crypto::commons::math::ntt::ntt128::Ntt128`). Only the native `2^128`
+/// ciphertext modulus is supported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, Versionize)]
+#[versionize(Ntt128LweBootstrapKeyVersions)]
+pub struct Ntt128LweBootstrapKey<C: Container<Element = u32>> {
+    data: C,
+    polynomial_size: PolynomialSize,
+    input_lwe_dimension: LweDimension,
+    glwe_size: GlweSize,
+    decomposition_base_log: DecompositionBaseLog,
+    decomposition_level_count: DecompositionLevelCount,
+}
+
+impl<C: Container<Element = u32>> AsRef<[u32]> for Ntt128LweBootstrapKey<C> {
+    fn as_ref(&self) -> &[u32] {
+        self.data.as_ref()
+    }
+}
+
+impl<C: ContainerMut<Element = u32>> AsMut<[u32]> for Ntt128LweBootstrapKey<C> {
+    fn as_mut(&mut self) -> &mut [u32] {
+        self.data.as_mut()
+    }
+}
+
+impl<C: Container<Element = u32>> Ntt128LweBootstrapKey<C> {
+    /// Create an [`Ntt128LweBootstrapKey`] from an existing container.
+    ///
+    /// # Note
+    ///
+    /// This function only wraps a container in the appropriate type. If you want to have useful
+    /// data in the [`Ntt128LweBootstrapKey`] you will first need to convert it from a standard
+    /// [`LweBootstrapKey`](`crate::core_crypto::entities::LweBootstrapKey`) by calling
+    /// [`convert_standard_lwe_bootstrap_key_to_ntt128`](crate::core_crypto::algorithms::convert_standard_lwe_bootstrap_key_to_ntt128).
+    ///
+    /// This docstring exhibits [`Ntt128LweBootstrapKey`] primitives usage.
+    ///
+    /// ```rust
+    /// use tfhe::core_crypto::prelude::*;
+    ///
+    /// // DISCLAIMER: these toy example parameters are not guaranteed to be secure or yield correct
+    /// // computations
+    /// // Define parameters for Ntt128LweBootstrapKey creation
+    /// let glwe_size = GlweSize(2);
+    /// let polynomial_size = PolynomialSize(1024);
+    /// let decomp_base_log = DecompositionBaseLog(8);
+    /// let decomp_level_count = DecompositionLevelCount(3);
+    /// let input_lwe_dimension = LweDimension(600);
+    ///
+    /// // Create a new Ntt128LweBootstrapKey
+    /// let bsk = Ntt128LweBootstrapKey::new(
+    ///     input_lwe_dimension,
+    ///     glwe_size,
+    ///     polynomial_size,
+    ///     decomp_base_log,
+    ///     decomp_level_count,
+    /// );
+    ///
+    /// assert_eq!(bsk.glwe_size(), glwe_size);
+    /// assert_eq!(bsk.polynomial_size(), polynomial_size);
+    /// assert_eq!(bsk.decomposition_base_log(), decomp_base_log);
+    /// assert_eq!(bsk.decomposition_level_count(), decomp_level_count);
+    /// assert_eq!(bsk.input_lwe_dimension(), input_lwe_dimension);
+    /// assert_eq!(
+    ///     bsk.output_lwe_dimension(),
+    ///     glwe_size
+    ///         .to_glwe_dimension()
+    ///         .to_equivalent_lwe_dimension(polynomial_size)
+    /// );
+    ///
+    /// // Demonstrate how to recover the allocated container
+    /// let underlying_container = bsk.into_container();
+    ///
+    /// // Recreate a key using from_container
+    /// let bsk = Ntt128LweBootstrapKey::from_container(
+    ///     underlying_container,
+    ///     input_lwe_dimension,
+    ///     glwe_size,
+    ///     polynomial_size,
+    ///     decomp_base_log,
+    ///     decomp_level_count,
+    /// );
+    ///
+    /// assert_eq!(bsk.glwe_size(), glwe_size);
+    /// assert_eq!(bsk.polynomial_size(), polynomial_size);
+    /// assert_eq!(bsk.decomposition_base_log(), decomp_base_log);
+    /// assert_eq!(bsk.decomposition_level_count(), decomp_level_count);
+    /// assert_eq!(bsk.input_lwe_dimension(), input_lwe_dimension);
+    /// ```
+    pub fn from_container(
+        data: C,
+        input_lwe_dimension: LweDimension,
+        glwe_size: GlweSize,
+        polynomial_size: PolynomialSize,
+        decomposition_base_log: DecompositionBaseLog,
+        decomposition_level_count: DecompositionLevelCount,
+    ) -> Self {
+        assert_eq!(
+            data.container_len(),
+            input_lwe_dimension.0
+                * ntt128_ggsw_ciphertext_size(
+                    glwe_size,
+                    polynomial_size,
+                    decomposition_level_count
+                )
+        );
+
+        Self {
+            data,
+            polynomial_size,
+            input_lwe_dimension,
+            glwe_size,
+            decomposition_base_log,
+            decomposition_level_count,
+        }
+    }
+
+    /// Return an iterator over the contiguous [`Ntt128GgswCiphertext`]. This consumes the entity,
+    /// consider calling [`Ntt128LweBootstrapKey::as_view`] or
+    /// [`Ntt128LweBootstrapKey::as_mut_view`] first to have an iterator over borrowed contents
+    /// instead of consuming the original entity.
+    pub fn into_ggsw_iter(self) -> impl DoubleEndedIterator<Item = Ntt128GgswCiphertext<C>>
+    where
+        C: Split,
+    {
+        self.data
+            .split_into(self.input_lwe_dimension.0)
+            .map(move |slice| {
+                Ntt128GgswCiphertext::from_container(
+                    slice,
+                    self.glwe_size,
+                    self.polynomial_size,
+                    self.decomposition_base_log,
+                    self.decomposition_level_count,
+                )
+            })
+    }
+
+    /// Return the [`LweDimension`] of the input
+    /// [`LweSecretKey`](`crate::core_crypto::entities::LweSecretKey`).
+    ///
+    /// See [`Ntt128LweBootstrapKey::from_container`] for usage.
+    pub fn input_lwe_dimension(&self) -> LweDimension {
+        self.input_lwe_dimension
+    }
+
+    /// Return the [`PolynomialSize`] of the [`Ntt128LweBootstrapKey`].
+    ///
+    /// See [`Ntt128LweBootstrapKey::from_container`] for usage.
+    pub fn polynomial_size(&self) -> PolynomialSize {
+        self.polynomial_size
+    }
+
+    /// Return the [`GlweSize`] of the [`Ntt128LweBootstrapKey`].
+    ///
+    /// See [`Ntt128LweBootstrapKey::from_container`] for usage.
+    pub fn glwe_size(&self) -> GlweSize {
+        self.glwe_size
+    }
+
+    /// Return the [`DecompositionBaseLog`] of the [`Ntt128LweBootstrapKey`].
+    ///
+    /// See [`Ntt128LweBootstrapKey::from_container`] for usage.
+    pub fn decomposition_base_log(&self) -> DecompositionBaseLog {
+        self.decomposition_base_log
+    }
+
+    /// Return the [`DecompositionLevelCount`] of the [`Ntt128LweBootstrapKey`].
+    ///
+    /// See [`Ntt128LweBootstrapKey::from_container`] for usage.
+    pub fn decomposition_level_count(&self) -> DecompositionLevelCount {
+        self.decomposition_level_count
+    }
+
+    /// Return the [`LweDimension`] of the equivalent output
+    /// [`LweSecretKey`](`crate::core_crypto::entities::LweSecretKey`).
+    ///
+    /// See [`Ntt128LweBootstrapKey::from_container`] for usage.
+    pub fn output_lwe_dimension(&self) -> LweDimension {
+        self.glwe_size
+            .to_glwe_dimension()
+            .to_equivalent_lwe_dimension(self.polynomial_size)
+    }
+
+    /// Consume the entity and return its underlying container.
+    ///
+    /// See [`Ntt128LweBootstrapKey::from_container`] for usage.
+    pub fn into_container(self) -> C {
+        self.data
+    }
+
+    /// Return a view of the [`Ntt128LweBootstrapKey`]. This is useful if an algorithm takes a
+    /// view by value.
+    pub fn as_view(&self) -> Ntt128LweBootstrapKeyView<'_> {
+        Ntt128LweBootstrapKeyView {
+            data: self.data.as_ref(),
+            polynomial_size: self.polynomial_size,
+            input_lwe_dimension: self.input_lwe_dimension,
+            glwe_size: self.glwe_size,
+            decomposition_base_log: self.decomposition_base_log,
+            decomposition_level_count: self.decomposition_level_count,
+        }
+    }
+}
+
+impl<C: ContainerMut<Element = u32>> Ntt128LweBootstrapKey<C> {
+    /// Mutable variant of [`Ntt128LweBootstrapKey::as_view`].
+    pub fn as_mut_view(&mut self) -> Ntt128LweBootstrapKeyMutView<'_> {
+        Ntt128LweBootstrapKeyMutView {
+            data: self.data.as_mut(),
+            polynomial_size: self.polynomial_size,
+            input_lwe_dimension: self.input_lwe_dimension,
+            glwe_size: self.glwe_size,
+            decomposition_base_log: self.decomposition_base_log,
+            decomposition_level_count: self.decomposition_level_count,
+        }
+    }
+}
+
+pub type Ntt128LweBootstrapKeyOwned = Ntt128LweBootstrapKey<ABox<[u32]>>;
+pub type Ntt128LweBootstrapKeyView<'data> = Ntt128LweBootstrapKey<&'data [u32]>;
+pub type Ntt128LweBootstrapKeyMutView<'data> = Ntt128LweBootstrapKey<&'data mut [u32]>;
+
+impl Ntt128LweBootstrapKey<ABox<[u32]>> {
+    /// Allocate memory and create a new owned [`Ntt128LweBootstrapKey`].
+    ///
+    /// # Note
+    ///
+    /// This function allocates a vector of the appropriate size and wraps it in the appropriate
+    /// type. If you want to have useful data in the [`Ntt128LweBootstrapKey`] you will first need
+    /// to convert it from a standard
+    /// [`LweBootstrapKey`](`crate::core_crypto::entities::LweBootstrapKey`) by calling
+    /// [`convert_standard_lwe_bootstrap_key_to_ntt128`](crate::core_crypto::algorithms::convert_standard_lwe_bootstrap_key_to_ntt128).
+    ///
+    /// See [`Ntt128LweBootstrapKey::from_container`] for usage.
+    pub fn new(
+        input_lwe_dimension: LweDimension,
+        glwe_size: GlweSize,
+        polynomial_size: PolynomialSize,
+        decomposition_base_log: DecompositionBaseLog,
+        decomposition_level_count: DecompositionLevelCount,
+    ) -> Self {
+        let boxed = avec![
+            0u32;
+            input_lwe_dimension.0
+                * ntt128_ggsw_ciphertext_size(
+                    glwe_size,
+                    polynomial_size,
+                    decomposition_level_count
+                )
+        ]
+        .into_boxed_slice();
+
+        Self::from_container(
+            boxed,
+            input_lwe_dimension,
+            glwe_size,
+            polynomial_size,
+            decomposition_base_log,
+            decomposition_level_count,
+        )
+    }
+}
@@ -12,6 +12,7 @@ use crate::core_crypto::commons::math::random::{
 use crate::core_crypto::commons::parameters::*;
 use crate::core_crypto::commons::traits::*;
 use crate::core_crypto::entities::*;
+use crate::named::Named;
 use tfhe_versionable::Versionize;
 
 /// A [`seeded LWE multi bit bootstrap key`](`SeededLweMultiBitBootstrapKey`).
@@ -33,6 +34,13 @@ where
     grouping_factor: LweBskGroupingFactor,
 }
 
+impl<C: Container> Named for SeededLweMultiBitBootstrapKey<C>
+where
+    C::Element: UnsignedInteger,
+{
+    const NAME: &'static str = "core_crypto::SeededLweMultiBitBootstrapKey";
+}
+
 impl<Scalar: UnsignedInteger, C: Container<Element = Scalar>> std::ops::Deref
     for SeededLweMultiBitBootstrapKey<C>
 {
@@ -7,6 +7,7 @@ use crate::core_crypto::commons::math::random::{CompressionSeed, DefaultRandomGe
 use crate::core_crypto::commons::parameters::*;
 use crate::core_crypto::commons::traits::*;
 use crate::core_crypto::entities::*;
+use crate::named::Named;
 use tfhe_versionable::Versionize;
 
 /// A [`seeded LWE packing keyswitch key`](`SeededLwePackingKeyswitchKey`).
@@ -25,6 +26,13 @@ where
     ciphertext_modulus: CiphertextModulus<C::Element>,
 }
 
+impl<C: Container> Named for SeededLwePackingKeyswitchKey<C>
+where
+    C::Element: UnsignedInteger,
+{
+    const NAME: &'static str = "core_crypto::SeededLwePackingKeyswitchKey";
+}
+
 impl<T: UnsignedInteger, C: Container<Element = T>> AsRef<[T]> for SeededLwePackingKeyswitchKey<C> {
     fn as_ref(&self) -> &[T] {
         self.data.as_ref()
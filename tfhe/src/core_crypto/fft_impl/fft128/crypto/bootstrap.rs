@@ -20,6 +20,7 @@ use crate::core_crypto::entities::*;
 use crate::core_crypto::fft_impl::common::{pbs_modulus_switch, FourierBootstrapKey};
 use crate::core_crypto::fft_impl::fft64::crypto::bootstrap::LweBootstrapKeyConformanceParams;
 use crate::core_crypto::prelude::ContainerMut;
+use crate::named::Named;
 use aligned_vec::{avec, ABox, CACHELINE_ALIGN};
 use core::any::TypeId;
 use core::mem::transmute;
@@ -40,6 +41,10 @@ pub struct Fourier128LweBootstrapKey<C: Container<Element = f64>> {
     decomposition_level_count: DecompositionLevelCount,
 }
 
+impl<C: Container<Element = f64>> Named for Fourier128LweBootstrapKey<C> {
+    const NAME: &'static str = "core_crypto::Fourier128LweBootstrapKey";
+}
+
 impl<C: Container<Element = f64>> Fourier128LweBootstrapKey<C> {
     pub fn from_container(
         data_re0: C,
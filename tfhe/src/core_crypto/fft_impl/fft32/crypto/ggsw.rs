@@ -0,0 +1,655 @@
+use super::super::math::fft::Fft32View;
+use crate::core_crypto::backward_compatibility::fft_impl::Fourier32GgswCiphertextVersions;
+use crate::core_crypto::commons::math::decomposition::{DecompositionLevel, SignedDecomposer};
+use crate::core_crypto::commons::math::torus::UnsignedTorus;
+use crate::core_crypto::commons::parameters::{
+    DecompositionBaseLog, DecompositionLevelCount, GlweSize, PolynomialSize,
+};
+use crate::core_crypto::commons::traits::{
+    Container, ContiguousEntityContainer, ContiguousEntityContainerMut, Split,
+};
+use crate::core_crypto::commons::utils::izip;
+use crate::core_crypto::entities::ggsw_ciphertext::{
+    fourier_ggsw_level_matrix_size, GgswCiphertext,
+};
+use crate::core_crypto::entities::glwe_ciphertext::{GlweCiphertext, GlweCiphertextView};
+use crate::core_crypto::fft_impl::fft64::math::decomposition::TensorSignedDecompositionLendingIter;
+use crate::core_crypto::prelude::ContainerMut;
+use aligned_vec::CACHELINE_ALIGN;
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use tfhe_versionable::Versionize;
+
+/// A GGSW ciphertext in the Fourier domain, using single precision floating point numbers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, Versionize)]
+#[versionize(Fourier32GgswCiphertextVersions)]
+pub struct Fourier32GgswCiphertext<C: Container<Element = f32>> {
+    data_re: C,
+    data_im: C,
+
+    polynomial_size: PolynomialSize,
+    glwe_size: GlweSize,
+    decomposition_base_log: DecompositionBaseLog,
+    decomposition_level_count: DecompositionLevelCount,
+}
+
+/// A matrix containing a single level of gadget decomposition, in the Fourier domain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Fourier32GgswLevelMatrix<C: Container<Element = f32>> {
+    data_re: C,
+    data_im: C,
+
+    polynomial_size: PolynomialSize,
+    glwe_size: GlweSize,
+    decomposition_level: DecompositionLevel,
+}
+
+/// A row of a GGSW level matrix, in the Fourier domain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Fourier32GgswLevelRow<C: Container<Element = f32>> {
+    data_re: C,
+    data_im: C,
+
+    polynomial_size: PolynomialSize,
+    glwe_size: GlweSize,
+    decomposition_level: DecompositionLevel,
+}
+
+impl<C: Container<Element = f32>> Fourier32GgswCiphertext<C> {
+    pub fn from_container(
+        data_re: C,
+        data_im: C,
+        polynomial_size: PolynomialSize,
+        glwe_size: GlweSize,
+        decomposition_base_log: DecompositionBaseLog,
+        decomposition_level_count: DecompositionLevelCount,
+    ) -> Self {
+        assert_eq!(polynomial_size.0 % 2, 0);
+        let container_len = polynomial_size.to_fourier_polynomial_size().0
+            * glwe_size.0
+            * glwe_size.0
+            * decomposition_level_count.0;
+        assert_eq!(data_re.container_len(), container_len);
+        assert_eq!(data_im.container_len(), container_len);
+
+        Self {
+            data_re,
+            data_im,
+            polynomial_size,
+            glwe_size,
+            decomposition_base_log,
+            decomposition_level_count,
+        }
+    }
+
+    pub fn polynomial_size(&self) -> PolynomialSize {
+        self.polynomial_size
+    }
+
+    pub fn glwe_size(&self) -> GlweSize {
+        self.glwe_size
+    }
+
+    pub fn decomposition_base_log(&self) -> DecompositionBaseLog {
+        self.decomposition_base_log
+    }
+
+    pub fn decomposition_level_count(&self) -> DecompositionLevelCount {
+        self.decomposition_level_count
+    }
+
+    pub fn data(self) -> (C, C) {
+        (self.data_re, self.data_im)
+    }
+
+    pub fn as_view(&self) -> Fourier32GgswCiphertext<&[C::Element]>
+    where
+        C: AsRef<[C::Element]>,
+    {
+        Fourier32GgswCiphertext {
+            data_re: self.data_re.as_ref(),
+            data_im: self.data_im.as_ref(),
+            polynomial_size: self.polynomial_size,
+            glwe_size: self.glwe_size,
+            decomposition_base_log: self.decomposition_base_log,
+            decomposition_level_count: self.decomposition_level_count,
+        }
+    }
+
+    pub fn as_mut_view(&mut self) -> Fourier32GgswCiphertext<&mut [C::Element]>
+    where
+        C: AsMut<[C::Element]>,
+    {
+        Fourier32GgswCiphertext {
+            data_re: self.data_re.as_mut(),
+            data_im: self.data_im.as_mut(),
+            polynomial_size: self.polynomial_size,
+            glwe_size: self.glwe_size,
+            decomposition_base_log: self.decomposition_base_log,
+            decomposition_level_count: self.decomposition_level_count,
+        }
+    }
+
+    /// Return an iterator over the level matrices.
+    pub fn into_levels(self) -> impl DoubleEndedIterator<Item = Fourier32GgswLevelMatrix<C>>
+    where
+        C: Split,
+    {
+        let decomposition_level_count = self.decomposition_level_count.0;
+        izip!(
+            self.data_re.split_into(decomposition_level_count),
+            self.data_im.split_into(decomposition_level_count)
+        )
+        .enumerate()
+        .map(move |(i, (data_re, data_im))| {
+            Fourier32GgswLevelMatrix::from_container(
+                data_re,
+                data_im,
+                self.polynomial_size,
+                self.glwe_size,
+                DecompositionLevel(decomposition_level_count - i),
+            )
+        })
+    }
+}
+
+impl<C: Container<Element = f32>> Fourier32GgswLevelMatrix<C> {
+    pub fn from_container(
+        data_re: C,
+        data_im: C,
+        polynomial_size: PolynomialSize,
+        glwe_size: GlweSize,
+        decomposition_level: DecompositionLevel,
+    ) -> Self {
+        assert_eq!(polynomial_size.0 % 2, 0);
+        let container_len =
+            fourier_ggsw_level_matrix_size(glwe_size, polynomial_size.to_fourier_polynomial_size());
+        assert_eq!(data_re.container_len(), container_len);
+        assert_eq!(data_im.container_len(), container_len);
+
+        Self {
+            data_re,
+            data_im,
+            polynomial_size,
+            glwe_size,
+            decomposition_level,
+        }
+    }
+
+    /// Return an iterator over the rows of the level matrices.
+    pub fn into_rows(self) -> impl DoubleEndedIterator<Item = Fourier32GgswLevelRow<C>>
+    where
+        C: Split,
+    {
+        let row_count = self.row_count();
+        izip!(
+            self.data_re.split_into(row_count),
+            self.data_im.split_into(row_count)
+        )
+        .map(move |(data_re, data_im)| Fourier32GgswLevelRow {
+            data_re,
+            data_im,
+            polynomial_size: self.polynomial_size,
+            glwe_size: self.glwe_size,
+            decomposition_level: self.decomposition_level,
+        })
+    }
+
+    pub fn polynomial_size(&self) -> PolynomialSize {
+        self.polynomial_size
+    }
+
+    pub fn glwe_size(&self) -> GlweSize {
+        self.glwe_size
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.glwe_size.0
+    }
+
+    pub fn decomposition_level(&self) -> DecompositionLevel {
+        self.decomposition_level
+    }
+
+    pub fn data(self) -> (C, C) {
+        (self.data_re, self.data_im)
+    }
+}
+
+impl<C: Container<Element = f32>> Fourier32GgswLevelRow<C> {
+    pub fn from_container(
+        data_re: C,
+        data_im: C,
+        glwe_size: GlweSize,
+        polynomial_size: PolynomialSize,
+        decomposition_level: DecompositionLevel,
+    ) -> Self {
+        assert_eq!(polynomial_size.0 % 2, 0);
+        let container_len = polynomial_size.to_fourier_polynomial_size().0 * glwe_size.0;
+        assert_eq!(data_re.container_len(), container_len);
+        assert_eq!(data_im.container_len(), container_len);
+
+        Self {
+            data_re,
+            data_im,
+            polynomial_size,
+            glwe_size,
+            decomposition_level,
+        }
+    }
+
+    pub fn polynomial_size(&self) -> PolynomialSize {
+        self.polynomial_size
+    }
+
+    pub fn glwe_size(&self) -> GlweSize {
+        self.glwe_size
+    }
+
+    pub fn decomposition_level(&self) -> DecompositionLevel {
+        self.decomposition_level
+    }
+
+    pub fn data(self) -> (C, C) {
+        (self.data_re, self.data_im)
+    }
+}
+
+impl<Cont> Fourier32GgswCiphertext<Cont>
+where
+    Cont: ContainerMut<Element = f32>,
+{
+    /// Fill a GGSW ciphertext with the Fourier transform of a GGSW ciphertext in the standard
+    /// domain.
+    pub fn fill_with_forward_fourier<Scalar, ContGgsw>(
+        &mut self,
+        coef_ggsw: &GgswCiphertext<ContGgsw>,
+        fft: Fft32View<'_>,
+    ) where
+        Scalar: UnsignedTorus,
+        ContGgsw: Container<Element = Scalar>,
+    {
+        fn implementation<Scalar: UnsignedTorus>(
+            this: Fourier32GgswCiphertext<&mut [f32]>,
+            coef_ggsw: GgswCiphertext<&[Scalar]>,
+            fft: Fft32View<'_>,
+        ) {
+            debug_assert_eq!(coef_ggsw.polynomial_size(), this.polynomial_size());
+            let poly_size = coef_ggsw.polynomial_size().to_fourier_polynomial_size().0;
+
+            let (data_re, data_im) = this.data();
+
+            for (fourier_re, fourier_im, coef_poly) in izip!(
+                data_re.into_chunks(poly_size),
+                data_im.into_chunks(poly_size),
+                coef_ggsw.as_polynomial_list().iter()
+            ) {
+                fft.forward_as_torus(fourier_re, fourier_im, coef_poly.as_ref());
+            }
+        }
+        implementation(self.as_mut_view(), coef_ggsw.as_view(), fft);
+    }
+}
+
+/// Return the required memory for [`add_external_product_assign`].
+pub fn add_external_product_assign_scratch<Scalar>(
+    glwe_size: GlweSize,
+    polynomial_size: PolynomialSize,
+    fft: Fft32View<'_>,
+) -> Result<StackReq, SizeOverflow> {
+    let align = CACHELINE_ALIGN;
+    let standard_scratch =
+        StackReq::try_new_aligned::<Scalar>(glwe_size.0 * polynomial_size.0, align)?;
+    let fourier_scratch = StackReq::try_new_aligned::<f32>(
+        glwe_size.0 * polynomial_size.to_fourier_polynomial_size().0,
+        align,
+    )?;
+    let fourier_scratch_single =
+        StackReq::try_new_aligned::<f32>(polynomial_size.to_fourier_polynomial_size().0, align)?;
+
+    let substack2 = StackReq::try_all_of([fourier_scratch_single; 2])?;
+    let substack1 = substack2.try_and(standard_scratch)?;
+    let substack0 = StackReq::try_any_of([
+        substack1.try_and(standard_scratch)?,
+        fft.backward_scratch()?,
+    ])?;
+    substack0.try_and(StackReq::try_all_of([fourier_scratch; 2])?)
+}
+
+#[cfg_attr(feature = "__profiling", inline(never))]
+pub fn add_external_product_assign<Scalar, ContOut, ContGgsw, ContGlwe>(
+    out: &mut GlweCiphertext<ContOut>,
+    ggsw: &Fourier32GgswCiphertext<ContGgsw>,
+    glwe: &GlweCiphertext<ContGlwe>,
+    fft: Fft32View<'_>,
+    stack: &mut PodStack,
+) where
+    Scalar: UnsignedTorus,
+    ContOut: ContainerMut<Element = Scalar>,
+    ContGgsw: Container<Element = f32>,
+    ContGlwe: Container<Element = Scalar>,
+{
+    fn implementation<Scalar: UnsignedTorus>(
+        mut out: GlweCiphertext<&mut [Scalar]>,
+        ggsw: Fourier32GgswCiphertext<&[f32]>,
+        glwe: GlweCiphertext<&[Scalar]>,
+        fft: Fft32View<'_>,
+        stack: &mut PodStack,
+    ) {
+        // we check that the polynomial sizes match
+        debug_assert_eq!(ggsw.polynomial_size(), glwe.polynomial_size());
+        debug_assert_eq!(ggsw.polynomial_size(), out.polynomial_size());
+        // we check that the glwe sizes match
+        debug_assert_eq!(ggsw.glwe_size(), glwe.glwe_size());
+        debug_assert_eq!(ggsw.glwe_size(), out.glwe_size());
+
+        debug_assert_eq!(glwe.ciphertext_modulus(), out.ciphertext_modulus());
+
+        let align = CACHELINE_ALIGN;
+        let fourier_poly_size = ggsw.polynomial_size().to_fourier_polynomial_size().0;
+        let ciphertext_modulus = glwe.ciphertext_modulus();
+
+        // we round the input mask and body
+        let decomposer = SignedDecomposer::<Scalar>::new(
+            ggsw.decomposition_base_log(),
+            ggsw.decomposition_level_count(),
+        );
+
+        let (output_fft_buffer_re, stack) =
+            stack.make_aligned_raw::<f32>(fourier_poly_size * ggsw.glwe_size().0, align);
+        let (output_fft_buffer_im, substack0) =
+            stack.make_aligned_raw::<f32>(fourier_poly_size * ggsw.glwe_size().0, align);
+
+        // output_fft_buffer is initially uninitialized, considered to be implicitly zero, to avoid
+        // the cost of filling it up with zeros. `is_output_uninit` is set to `false` once
+        // it has been fully initialized for the first time.
+        let mut is_output_uninit = true;
+
+        {
+            // ------------------------------------------------------ EXTERNAL PRODUCT IN FOURIER
+            // DOMAIN In this section, we perform the external product in the fourier
+            // domain, and accumulate the result in the output_fft_buffer variable.
+            let (mut decomposition, substack1) = TensorSignedDecompositionLendingIter::new(
+                glwe.as_ref()
+                    .iter()
+                    .map(|s| decomposer.init_decomposer_state(*s)),
+                DecompositionBaseLog(decomposer.base_log),
+                DecompositionLevelCount(decomposer.level_count),
+                substack0,
+            );
+
+            // We loop through the levels (we reverse to match the order of the decomposition
+            // iterator.)
+            for ggsw_decomp_matrix in ggsw.into_levels() {
+                // We retrieve the decomposition of this level.
+                let (glwe_level, glwe_decomp_term, substack2) =
+                    collect_next_term(&mut decomposition, substack1, align);
+                let glwe_decomp_term = GlweCiphertextView::from_container(
+                    &*glwe_decomp_term,
+                    ggsw.polynomial_size(),
+                    ciphertext_modulus,
+                );
+                debug_assert_eq!(ggsw_decomp_matrix.decomposition_level(), glwe_level);
+
+                // For each level we have to add the result of the vector-matrix product between the
+                // decomposition of the glwe, and the ggsw level matrix to the output. To do so, we
+                // iteratively add to the output, the product between every line of the matrix, and
+                // the corresponding (scalar) polynomial in the glwe decomposition:
+                //
+                //                ggsw_mat                        ggsw_mat
+                //   glwe_dec   | - - - - | <        glwe_dec   | - - - - |
+                //  | - - - | x | - - - - |         | - - - | x | - - - - | <
+                //    ^         | - - - - |             ^       | - - - - |
+                //
+                //        t = 1                           t = 2                     ...
+
+                for (ggsw_row, glwe_poly) in izip!(
+                    ggsw_decomp_matrix.into_rows(),
+                    glwe_decomp_term.as_polynomial_list().iter()
+                ) {
+                    let len = fourier_poly_size;
+                    let stack = &mut *substack2;
+                    let (fourier_re, stack) = stack.make_aligned_raw::<f32>(len, align);
+                    let (fourier_im, _) = stack.make_aligned_raw::<f32>(len, align);
+                    // We perform the forward fft transform for the glwe polynomial
+                    fft.forward_as_integer(fourier_re, fourier_im, glwe_poly.as_ref());
+                    // Now we loop through the polynomials of the output, and add the
+                    // corresponding product of polynomials.
+                    update_with_fmadd(
+                        output_fft_buffer_re,
+                        output_fft_buffer_im,
+                        ggsw_row,
+                        fourier_re,
+                        fourier_im,
+                        is_output_uninit,
+                        fourier_poly_size,
+                    );
+
+                    // we initialized `output_fft_buffer, so we can set this to false
+                    is_output_uninit = false;
+                }
+            }
+        }
+
+        // --------------------------------------------  TRANSFORMATION OF RESULT TO STANDARD DOMAIN
+        // In this section, we bring the result from the fourier domain, back to the standard
+        // domain, and add it to the output.
+        //
+        // We iterate over the polynomials in the output.
+        if !is_output_uninit {
+            for (mut out, fourier_re, fourier_im) in izip!(
+                out.as_mut_polynomial_list().iter_mut(),
+                output_fft_buffer_re.into_chunks(fourier_poly_size),
+                output_fft_buffer_im.into_chunks(fourier_poly_size),
+            ) {
+                fft.add_backward_as_torus(out.as_mut(), fourier_re, fourier_im, substack0);
+            }
+        }
+    }
+
+    implementation(
+        out.as_mut_view(),
+        ggsw.as_view(),
+        glwe.as_view(),
+        fft,
+        stack,
+    );
+}
+
+fn collect_next_term<'a, Scalar: UnsignedTorus>(
+    decomposition: &mut TensorSignedDecompositionLendingIter<'_, Scalar>,
+    substack1: &'a mut PodStack,
+    align: usize,
+) -> (DecompositionLevel, &'a mut [Scalar], &'a mut PodStack) {
+    let (glwe_level, _, glwe_decomp_term) = decomposition.next_term().unwrap();
+    let (glwe_decomp_term, substack2) = substack1.collect_aligned(align, glwe_decomp_term);
+    (glwe_level, glwe_decomp_term, substack2)
+}
+
+/// # Note
+///
+/// this function leaves all the elements of `output_fourier` in an initialized state.
+#[inline(always)]
+fn update_with_fmadd_simd<S: pulp::Simd>(
+    simd: S,
+    output_fourier_re: &mut [f32],
+    output_fourier_im: &mut [f32],
+    ggsw_poly_re: &[f32],
+    ggsw_poly_im: &[f32],
+    fourier_re: &[f32],
+    fourier_im: &[f32],
+    is_output_uninit: bool,
+) {
+    let (out_re_head, out_re_tail) = S::as_mut_simd_f32s(output_fourier_re);
+    let (out_im_head, out_im_tail) = S::as_mut_simd_f32s(output_fourier_im);
+    let (lhs_re_head, lhs_re_tail) = S::as_simd_f32s(ggsw_poly_re);
+    let (lhs_im_head, lhs_im_tail) = S::as_simd_f32s(ggsw_poly_im);
+    let (rhs_re_head, rhs_re_tail) = S::as_simd_f32s(fourier_re);
+    let (rhs_im_head, rhs_im_tail) = S::as_simd_f32s(fourier_im);
+
+    if is_output_uninit {
+        // we're writing to output_fft_buffer for the first time
+        // so its contents are uninitialized
+        for (out_re, out_im, &lhs_re, &lhs_im, &rhs_re, &rhs_im) in izip!(
+            out_re_head,
+            out_im_head,
+            lhs_re_head,
+            lhs_im_head,
+            rhs_re_head,
+            rhs_im_head,
+        ) {
+            *out_re =
+                simd.mul_add_e_f32s(lhs_re, rhs_re, simd.neg_f32s(simd.mul_f32s(lhs_im, rhs_im)));
+            *out_im = simd.mul_add_e_f32s(lhs_im, rhs_re, simd.mul_f32s(lhs_re, rhs_im));
+        }
+        for (out_re, out_im, &lhs_re, &lhs_im, &rhs_re, &rhs_im) in izip!(
+            out_re_tail,
+            out_im_tail,
+            lhs_re_tail,
+            lhs_im_tail,
+            rhs_re_tail,
+            rhs_im_tail,
+        ) {
+            *out_re = lhs_re * rhs_re - lhs_im * rhs_im;
+            *out_im = lhs_im * rhs_re + lhs_re * rhs_im;
+        }
+    } else {
+        // we already wrote to output_fft_buffer, so we can assume its contents are
+        // initialized.
+        for (out_re, out_im, &lhs_re, &lhs_im, &rhs_re, &rhs_im) in izip!(
+            out_re_head,
+            out_im_head,
+            lhs_re_head,
+            lhs_im_head,
+            rhs_re_head,
+            rhs_im_head,
+        ) {
+            *out_re = simd.mul_add_e_f32s(
+                lhs_re,
+                rhs_re,
+                simd.sub_f32s(*out_re, simd.mul_f32s(lhs_im, rhs_im)),
+            );
+            *out_im =
+                simd.mul_add_e_f32s(lhs_im, rhs_re, simd.mul_add_e_f32s(lhs_re, rhs_im, *out_im));
+        }
+        for (out_re, out_im, &lhs_re, &lhs_im, &rhs_re, &rhs_im) in izip!(
+            out_re_tail,
+            out_im_tail,
+            lhs_re_tail,
+            lhs_im_tail,
+            rhs_re_tail,
+            rhs_im_tail,
+        ) {
+            *out_re += lhs_re * rhs_re - lhs_im * rhs_im;
+            *out_im += lhs_im * rhs_re + lhs_re * rhs_im;
+        }
+    }
+}
+
+pub fn update_with_fmadd(
+    output_fft_buffer_re: &mut [f32],
+    output_fft_buffer_im: &mut [f32],
+    ggsw_row: Fourier32GgswLevelRow<&[f32]>,
+    fourier_re: &[f32],
+    fourier_im: &[f32],
+    is_output_uninit: bool,
+    fourier_poly_size: usize,
+) {
+    let arch = pulp::Arch::new();
+    for (output_fourier_re, output_fourier_im, ggsw_poly_re, ggsw_poly_im) in izip!(
+        output_fft_buffer_re.into_chunks(fourier_poly_size),
+        output_fft_buffer_im.into_chunks(fourier_poly_size),
+        ggsw_row.data_re.into_chunks(fourier_poly_size),
+        ggsw_row.data_im.into_chunks(fourier_poly_size),
+    ) {
+        struct Impl<'a> {
+            output_fourier_re: &'a mut [f32],
+            output_fourier_im: &'a mut [f32],
+            ggsw_poly_re: &'a [f32],
+            ggsw_poly_im: &'a [f32],
+            fourier_re: &'a [f32],
+            fourier_im: &'a [f32],
+            is_output_uninit: bool,
+        }
+        impl pulp::WithSimd for Impl<'_> {
+            type Output = ();
+
+            #[inline(always)]
+            fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
+                let Self {
+                    output_fourier_re,
+                    output_fourier_im,
+                    ggsw_poly_re,
+                    ggsw_poly_im,
+                    fourier_re,
+                    fourier_im,
+                    is_output_uninit,
+                } = self;
+
+                update_with_fmadd_simd(
+                    simd,
+                    output_fourier_re,
+                    output_fourier_im,
+                    ggsw_poly_re,
+                    ggsw_poly_im,
+                    fourier_re,
+                    fourier_im,
+                    is_output_uninit,
+                );
+            }
+        }
+
+        arch.dispatch(Impl {
+            output_fourier_re,
+            output_fourier_im,
+            ggsw_poly_re,
+            ggsw_poly_im,
+            fourier_re,
+            fourier_im,
+            is_output_uninit,
+        });
+    }
+}
+
+/// Return the required memory for [`cmux`].
+pub fn cmux_scratch<Scalar>(
+    glwe_size: GlweSize,
+    polynomial_size: PolynomialSize,
+    fft: Fft32View<'_>,
+) -> Result<StackReq, SizeOverflow> {
+    add_external_product_assign_scratch::<Scalar>(glwe_size, polynomial_size, fft)
+}
+
+/// This cmux mutates both ct1 and ct0. The result is in ct0 after the method was called.
+pub fn cmux<Scalar, ContCt0, ContCt1, ContGgsw>(
+    ct0: &mut GlweCiphertext<ContCt0>,
+    ct1: &mut GlweCiphertext<ContCt1>,
+    ggsw: &Fourier32GgswCiphertext<ContGgsw>,
+    fft: Fft32View<'_>,
+    stack: &mut PodStack,
+) where
+    Scalar: UnsignedTorus,
+    ContCt0: ContainerMut<Element = Scalar>,
+    ContCt1: ContainerMut<Element = Scalar>,
+    ContGgsw: Container<Element = f32>,
+{
+    fn implementation<Scalar: UnsignedTorus>(
+        mut ct0: GlweCiphertext<&mut [Scalar]>,
+        mut ct1: GlweCiphertext<&mut [Scalar]>,
+        ggsw: Fourier32GgswCiphertext<&[f32]>,
+        fft: Fft32View<'_>,
+        stack: &mut PodStack,
+    ) {
+        for (c1, c0) in izip!(ct1.as_mut(), ct0.as_ref()) {
+            *c1 = c1.wrapping_sub(*c0);
+        }
+        add_external_product_assign(&mut ct0, &ggsw, &ct1, fft, stack);
+    }
+
+    implementation(
+        ct0.as_mut_view(),
+        ct1.as_mut_view(),
+        ggsw.as_view(),
+        fft,
+        stack,
+    );
+}
@@ -0,0 +1,80 @@
+use crate::core_crypto::algorithms::test::TestResources;
+use crate::core_crypto::fft_impl::common::tests::{
+    gen_keys_or_get_from_cache_if_enabled, generate_keys,
+};
+use crate::core_crypto::prelude::test::FFT32_U32_PARAMS;
+use crate::core_crypto::prelude::*;
+
+// The parameters are the ones of the boolean DEFAULT_PARAMETERS, which only have room for a single
+// bit of message: the messages are encoded as in the boolean module, true as 1/8 and false as -1/8.
+#[test]
+fn test_bootstrap_boolean_parameters() {
+    let params = FFT32_U32_PARAMS;
+    let glwe_size = params.glwe_dimension.to_glwe_size();
+    let polynomial_size = params.polynomial_size;
+    let ciphertext_modulus = params.ciphertext_modulus;
+
+    let mut rsc = TestResources::new();
+
+    let mut keys_gen = |params| generate_keys(params, &mut rsc);
+    let keys = gen_keys_or_get_from_cache_if_enabled(params, &mut keys_gen);
+    let (std_bootstrapping_key, small_lwe_sk, big_lwe_sk) =
+        (keys.bsk, keys.small_lwe_sk, keys.big_lwe_sk);
+
+    let mut fourier_bsk = Fourier32LweBootstrapKey::new(
+        std_bootstrapping_key.input_lwe_dimension(),
+        std_bootstrapping_key.glwe_size(),
+        std_bootstrapping_key.polynomial_size(),
+        std_bootstrapping_key.decomposition_base_log(),
+        std_bootstrapping_key.decomposition_level_count(),
+    );
+    convert_standard_lwe_bootstrap_key_to_fourier_32(&std_bootstrapping_key, &mut fourier_bsk);
+
+    // 1/8 on the torus
+    let encoding = 1u32 << (u32::BITS - 3);
+
+    // The boolean bootstrap accumulator: a trivial GLWE whose body is 1/8 everywhere, mapping the
+    // positive half of the torus to 1/8 and the negative one to -1/8.
+    let mut accumulator = GlweCiphertext::new(0u32, glwe_size, polynomial_size, ciphertext_modulus);
+    accumulator.get_mut_body().as_mut().fill(encoding);
+
+    for _ in 0..10 {
+        for message in [false, true] {
+            let plaintext = Plaintext(if message {
+                encoding
+            } else {
+                encoding.wrapping_neg()
+            });
+
+            let lwe_ciphertext_in: LweCiphertextOwned<u32> =
+                allocate_and_encrypt_new_lwe_ciphertext(
+                    &small_lwe_sk,
+                    plaintext,
+                    params.lwe_noise_distribution,
+                    ciphertext_modulus,
+                    &mut rsc.encryption_random_generator,
+                );
+
+            let mut pbs_ct = LweCiphertext::new(
+                0u32,
+                big_lwe_sk.lwe_dimension().to_lwe_size(),
+                ciphertext_modulus,
+            );
+
+            programmable_bootstrap_f32_lwe_ciphertext(
+                &lwe_ciphertext_in,
+                &mut pbs_ct,
+                &accumulator,
+                &fourier_bsk,
+            );
+
+            let decrypted = decrypt_lwe_ciphertext(&big_lwe_sk, &pbs_ct);
+            // The result must be closer to the expected encoding than to the opposite one
+            let error = decrypted.0.wrapping_sub(plaintext.0) as i32;
+            assert!(
+                error.unsigned_abs() < encoding,
+                "message: {message}, error: {error}, encoding: {encoding}"
+            );
+        }
+    }
+}
@@ -0,0 +1,279 @@
+use crate::core_crypto::commons::math::torus::UnsignedTorus;
+use crate::core_crypto::commons::numeric::CastInto;
+use crate::core_crypto::commons::parameters::PolynomialSize;
+use crate::core_crypto::commons::utils::izip;
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+use tfhe_fft::fft32::Plan;
+
+#[derive(Clone)]
+pub(crate) struct PlanWrapper(Plan);
+impl core::ops::Deref for PlanWrapper {
+    type Target = Plan;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl core::fmt::Debug for PlanWrapper {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        "[?]".fmt(f)
+    }
+}
+
+/// Negacyclic Fast Fourier Transform over `f32`. See [`Fft32View`] for transform functions.
+///
+/// This structure contains the twisting factors as well as the
+/// FFT plan needed for the negacyclic convolution over the reals.
+///
+/// Single precision only leaves 24 bits of mantissa, so this transform is meant for 32 bits
+/// torus elements and parameter sets with small decomposition bases, where the accumulated
+/// error stays well below the precision required for decryption.
+#[derive(Clone, Debug)]
+pub struct Fft32 {
+    plan: Arc<PlanWrapper>,
+}
+
+/// View type for [`Fft32`].
+#[derive(Clone, Copy, Debug)]
+pub struct Fft32View<'a> {
+    pub(crate) plan: &'a PlanWrapper,
+}
+
+impl Fft32 {
+    pub fn as_view(&self) -> Fft32View<'_> {
+        Fft32View { plan: &self.plan }
+    }
+}
+
+type PlanMap = RwLock<HashMap<usize, Arc<OnceLock<Arc<PlanWrapper>>>>>;
+pub(crate) static PLANS: OnceLock<PlanMap> = OnceLock::new();
+fn plans() -> &'static PlanMap {
+    PLANS.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+impl Fft32 {
+    /// Real polynomial of size `size`.
+    pub fn new(size: PolynomialSize) -> Self {
+        let global_plans = plans();
+
+        let n = size.0;
+        let get_plan = || {
+            let plans = global_plans.read().unwrap();
+            let plan = plans.get(&n).cloned();
+            drop(plans);
+
+            plan.map(|p| {
+                p.get_or_init(|| Arc::new(PlanWrapper(Plan::new(n / 2))))
+                    .clone()
+            })
+        };
+
+        // could not find a plan of the given size, we lock the map again and try to insert it
+        let mut plans = global_plans.write().unwrap();
+        if let Entry::Vacant(v) = plans.entry(n) {
+            v.insert(Arc::new(OnceLock::new()));
+        }
+
+        drop(plans);
+
+        Self {
+            plan: get_plan().unwrap(),
+        }
+    }
+}
+
+#[inline(always)]
+fn to_signed_to_f64<Scalar: UnsignedTorus>(x: Scalar) -> f64 {
+    x.into_signed().cast_into()
+}
+
+pub fn convert_forward_torus<Scalar: UnsignedTorus>(
+    out_re: &mut [f32],
+    out_im: &mut [f32],
+    in_re: &[Scalar],
+    in_im: &[Scalar],
+) {
+    let normalization = 2.0_f64.powi(-(Scalar::BITS as i32));
+
+    for (out_re, out_im, &in_re, &in_im) in izip!(out_re, out_im, in_re, in_im) {
+        *out_re = (to_signed_to_f64(in_re) * normalization) as f32;
+        *out_im = (to_signed_to_f64(in_im) * normalization) as f32;
+    }
+}
+
+pub fn convert_forward_integer<Scalar: UnsignedTorus>(
+    out_re: &mut [f32],
+    out_im: &mut [f32],
+    in_re: &[Scalar],
+    in_im: &[Scalar],
+) {
+    for (out_re, out_im, &in_re, &in_im) in izip!(out_re, out_im, in_re, in_im) {
+        *out_re = to_signed_to_f64(in_re) as f32;
+        *out_im = to_signed_to_f64(in_im) as f32;
+    }
+}
+
+fn convert_add_backward_torus<Scalar: UnsignedTorus>(
+    out_re: &mut [Scalar],
+    out_im: &mut [Scalar],
+    in_re: &[f32],
+    in_im: &[f32],
+) {
+    let norm = 1.0 / in_re.len() as f64;
+    for (out_re, out_im, &in_re, &in_im) in izip!(out_re, out_im, in_re, in_im) {
+        *out_re = out_re.wrapping_add(Scalar::from_torus(in_re as f64 * norm));
+        *out_im = out_im.wrapping_add(Scalar::from_torus(in_im as f64 * norm));
+    }
+}
+
+fn convert_backward_torus<Scalar: UnsignedTorus>(
+    out_re: &mut [Scalar],
+    out_im: &mut [Scalar],
+    in_re: &[f32],
+    in_im: &[f32],
+) {
+    let norm = 1.0 / in_re.len() as f64;
+    for (out_re, out_im, &in_re, &in_im) in izip!(out_re, out_im, in_re, in_im) {
+        *out_re = Scalar::from_torus(in_re as f64 * norm);
+        *out_im = Scalar::from_torus(in_im as f64 * norm);
+    }
+}
+
+impl Fft32View<'_> {
+    pub fn polynomial_size(self) -> PolynomialSize {
+        PolynomialSize(2 * self.plan.fft_size())
+    }
+
+    /// Return the memory required for a backward negacyclic FFT.
+    pub fn backward_scratch(self) -> Result<StackReq, SizeOverflow> {
+        let one = StackReq::try_new_aligned::<f32>(
+            self.polynomial_size().0 / 2,
+            aligned_vec::CACHELINE_ALIGN,
+        )?;
+        StackReq::try_all_of([one; 2])
+    }
+
+    /// Perform a negacyclic real FFT of `standard`, viewed as torus elements, and stores the
+    /// result in `fourier_re` and `fourier_im`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `standard` and `fourier_re`/`fourier_im` do not have matching sizes, i.e.
+    /// `standard.len() != 2 * fourier_re.len()`.
+    pub fn forward_as_torus<Scalar: UnsignedTorus>(
+        self,
+        fourier_re: &mut [f32],
+        fourier_im: &mut [f32],
+        standard: &[Scalar],
+    ) {
+        self.forward_with_conv(fourier_re, fourier_im, standard, convert_forward_torus);
+    }
+
+    /// Perform a negacyclic real FFT of `standard`, viewed as integers, and stores the result in
+    /// `fourier_re` and `fourier_im`.
+    ///
+    /// # Panics
+    ///
+    /// See [`Self::forward_as_torus`]
+    pub fn forward_as_integer<Scalar: UnsignedTorus>(
+        self,
+        fourier_re: &mut [f32],
+        fourier_im: &mut [f32],
+        standard: &[Scalar],
+    ) {
+        self.forward_with_conv(fourier_re, fourier_im, standard, convert_forward_integer);
+    }
+
+    fn forward_with_conv<Scalar: UnsignedTorus>(
+        self,
+        fourier_re: &mut [f32],
+        fourier_im: &mut [f32],
+        standard: &[Scalar],
+        conv_fn: impl Fn(&mut [f32], &mut [f32], &[Scalar], &[Scalar]),
+    ) {
+        let n = standard.len();
+        assert_eq!(n, 2 * fourier_re.len());
+        assert_eq!(n, 2 * fourier_im.len());
+
+        let (standard_re, standard_im) = standard.split_at(n / 2);
+        conv_fn(fourier_re, fourier_im, standard_re, standard_im);
+        self.plan.fwd(fourier_re, fourier_im);
+    }
+
+    /// Perform an inverse negacyclic real FFT of `fourier` and stores the result in `standard`,
+    /// viewed as torus elements.
+    ///
+    /// # Panics
+    ///
+    /// See [`Self::forward_as_torus`]
+    pub fn backward_as_torus<Scalar: UnsignedTorus>(
+        self,
+        standard: &mut [Scalar],
+        fourier_re: &[f32],
+        fourier_im: &[f32],
+        stack: &mut PodStack,
+    ) {
+        self.backward_with_conv(
+            standard,
+            fourier_re,
+            fourier_im,
+            convert_backward_torus,
+            stack,
+        );
+    }
+
+    /// Perform an inverse negacyclic real FFT of `fourier` and adds the result to `standard`,
+    /// viewed as torus elements.
+    ///
+    /// # Panics
+    ///
+    /// See [`Self::forward_as_torus`]
+    pub fn add_backward_as_torus<Scalar: UnsignedTorus>(
+        self,
+        standard: &mut [Scalar],
+        fourier_re: &[f32],
+        fourier_im: &[f32],
+        stack: &mut PodStack,
+    ) {
+        self.backward_with_conv(
+            standard,
+            fourier_re,
+            fourier_im,
+            convert_add_backward_torus,
+            stack,
+        );
+    }
+
+    fn backward_with_conv<
+        Scalar: UnsignedTorus,
+        F: Fn(&mut [Scalar], &mut [Scalar], &[f32], &[f32]),
+    >(
+        self,
+        standard: &mut [Scalar],
+        fourier_re: &[f32],
+        fourier_im: &[f32],
+        conv_fn: F,
+        stack: &mut PodStack,
+    ) {
+        let n = standard.len();
+        assert_eq!(n, 2 * fourier_re.len());
+        assert_eq!(n, 2 * fourier_im.len());
+
+        let (tmp_re, stack) =
+            stack.collect_aligned(aligned_vec::CACHELINE_ALIGN, fourier_re.iter().copied());
+        let (tmp_im, _) =
+            stack.collect_aligned(aligned_vec::CACHELINE_ALIGN, fourier_im.iter().copied());
+
+        self.plan.inv(tmp_re, tmp_im);
+
+        let (standard_re, standard_im) = standard.split_at_mut(n / 2);
+        conv_fn(standard_re, standard_im, tmp_re, tmp_im);
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,163 @@
+use super::*;
+use crate::core_crypto::commons::test_tools::{modular_distance, new_random_generator};
+use crate::core_crypto::entities::Polynomial;
+use crate::core_crypto::fft_impl::fft64::math::fft::Fft;
+use crate::core_crypto::fft_impl::fft64::math::polynomial::FourierPolynomial;
+use aligned_vec::avec;
+use dyn_stack::GlobalPodBuffer;
+use tfhe_fft::c64;
+
+// f32 only carries 24 bits of mantissa, the transforms below are checked against error bounds
+// derived from that precision rather than for exactness
+const MANTISSA_BITS: usize = f32::MANTISSA_DIGITS as usize;
+
+#[test]
+fn test_roundtrip_u32() {
+    let mut generator = new_random_generator();
+    for size_log in 5..=14 {
+        let size = 1_usize << size_log;
+        let fourier_size = PolynomialSize(size).to_fourier_polynomial_size().0;
+
+        let fft = Fft32::new(PolynomialSize(size));
+        let fft = fft.as_view();
+
+        let mut poly = avec![0u32; size].into_boxed_slice();
+        let mut roundtrip = avec![0u32; size].into_boxed_slice();
+        let mut fourier_re = avec![0.0f32; fourier_size].into_boxed_slice();
+        let mut fourier_im = avec![0.0f32; fourier_size].into_boxed_slice();
+
+        for x in poly.as_mut().iter_mut() {
+            *x = generator.random_uniform();
+        }
+
+        let mut mem = GlobalPodBuffer::new(fft.backward_scratch().unwrap());
+        let stack = PodStack::new(&mut mem);
+
+        fft.forward_as_torus(&mut fourier_re, &mut fourier_im, &poly);
+        fft.backward_as_torus(&mut roundtrip, &fourier_re, &fourier_im, stack);
+
+        let threshold = 1u32 << (u32::BITS as usize + 2 + size_log / 2 - MANTISSA_BITS);
+        for (expected, actual) in izip!(poly.iter(), roundtrip.iter()) {
+            let abs_diff = modular_distance(*expected, *actual);
+            assert!(
+                abs_diff <= threshold,
+                "abs_diff: {abs_diff}, threshold: {threshold}",
+            );
+        }
+
+        // Need to zero out the buffer to have a correct result as we will be adding the result
+        roundtrip.fill(0);
+        fft.add_backward_as_torus(&mut roundtrip, &fourier_re, &fourier_im, stack);
+
+        for (expected, actual) in izip!(poly.iter(), roundtrip.iter()) {
+            let abs_diff = modular_distance(*expected, *actual);
+            assert!(
+                abs_diff <= threshold,
+                "abs_diff: {abs_diff}, threshold: {threshold}",
+            );
+        }
+    }
+}
+
+#[test]
+fn test_product_against_f64_u32() {
+    let mut generator = new_random_generator();
+    // Polynomial sizes and decomposition base log of the boolean parameter sets
+    // DEFAULT_PARAMETERS and PARAMETERS_ERROR_PROB_2_POW_MINUS_165
+    let pbs_base_log = 10;
+    for size_log in [9, 10] {
+        for _ in 0..10 {
+            let size = 1_usize << size_log;
+            let fourier_size = PolynomialSize(size).to_fourier_polynomial_size().0;
+
+            let fft32 = Fft32::new(PolynomialSize(size));
+            let fft32 = fft32.as_view();
+            let fft64 = Fft::new(PolynomialSize(size));
+            let fft64 = fft64.as_view();
+
+            let mut poly0 = Polynomial::from_container(avec![0u32; size].into_boxed_slice());
+            let mut poly1 = Polynomial::from_container(avec![0u32; size].into_boxed_slice());
+
+            let mut convolution_from_fft32 = avec![0u32; size].into_boxed_slice();
+            let mut convolution_from_fft64 =
+                Polynomial::from_container(avec![0u32; size].into_boxed_slice());
+
+            let mut fourier0_re = avec![0.0f32; fourier_size].into_boxed_slice();
+            let mut fourier0_im = avec![0.0f32; fourier_size].into_boxed_slice();
+            let mut fourier1_re = avec![0.0f32; fourier_size].into_boxed_slice();
+            let mut fourier1_im = avec![0.0f32; fourier_size].into_boxed_slice();
+
+            let mut fourier0 = FourierPolynomial {
+                data: avec![c64::default(); fourier_size].into_boxed_slice(),
+            };
+            let mut fourier1 = FourierPolynomial {
+                data: avec![c64::default(); fourier_size].into_boxed_slice(),
+            };
+
+            // Magnitude of a signed decomposition term for the boolean decomposition base
+            let integer_magnitude = pbs_base_log;
+            for (x, y) in izip!(poly0.as_mut().iter_mut(), poly1.as_mut().iter_mut()) {
+                *x = generator.random_uniform();
+                *y = generator.random_uniform();
+                *y = ((*y as i32) >> (u32::BITS as usize - integer_magnitude)) as u32;
+            }
+
+            let mut mem = GlobalPodBuffer::new(
+                fft32
+                    .backward_scratch()
+                    .unwrap()
+                    .or(fft64.forward_scratch().unwrap())
+                    .or(fft64.backward_scratch().unwrap()),
+            );
+            let stack = PodStack::new(&mut mem);
+
+            fft32.forward_as_torus(&mut fourier0_re, &mut fourier0_im, poly0.as_ref());
+            fft32.forward_as_integer(&mut fourier1_re, &mut fourier1_im, poly1.as_ref());
+
+            for (f0_re, f0_im, f1_re, f1_im) in izip!(
+                &mut *fourier0_re,
+                &mut *fourier0_im,
+                &*fourier1_re,
+                &*fourier1_im,
+            ) {
+                let re = *f0_re * *f1_re - *f0_im * *f1_im;
+                let im = *f0_im * *f1_re + *f0_re * *f1_im;
+                *f0_re = re;
+                *f0_im = im;
+            }
+
+            fft32.backward_as_torus(
+                &mut convolution_from_fft32,
+                &fourier0_re,
+                &fourier0_im,
+                stack,
+            );
+
+            fft64.forward_as_torus(fourier0.as_mut_view(), poly0.as_view(), stack);
+            fft64.forward_as_integer(fourier1.as_mut_view(), poly1.as_view(), stack);
+
+            for (f0, f1) in izip!(&mut *fourier0.data, &*fourier1.data) {
+                *f0 *= *f1;
+            }
+
+            fft64.backward_as_torus(
+                convolution_from_fft64.as_mut_view(),
+                fourier0.as_view(),
+                stack,
+            );
+
+            let threshold =
+                1u32 << (u32::BITS as usize + 3 + integer_magnitude + size_log / 2 - MANTISSA_BITS);
+            for (expected, actual) in izip!(
+                convolution_from_fft64.as_ref().iter(),
+                convolution_from_fft32.iter()
+            ) {
+                let abs_diff = modular_distance(*expected, *actual);
+                assert!(
+                    abs_diff <= threshold,
+                    "abs_diff: {abs_diff}, threshold: {threshold}",
+                );
+            }
+        }
+    }
+}
@@ -19,6 +19,7 @@ use crate::core_crypto::entities::*;
 use crate::core_crypto::fft_impl::common::{pbs_modulus_switch, FourierBootstrapKey};
 use crate::core_crypto::fft_impl::fft64::math::fft::par_convert_polynomials_list_to_fourier;
 use crate::core_crypto::prelude::{CiphertextCount, CiphertextModulus, ContainerMut};
+use crate::named::Named;
 use aligned_vec::{avec, ABox, CACHELINE_ALIGN};
 use dyn_stack::{PodStack, SizeOverflow, StackReq};
 use tfhe_fft::c64;
@@ -38,6 +39,10 @@ pub struct FourierLweBootstrapKey<C: Container<Element = c64>> {
 pub type FourierLweBootstrapKeyView<'a> = FourierLweBootstrapKey<&'a [c64]>;
 pub type FourierLweBootstrapKeyMutView<'a> = FourierLweBootstrapKey<&'a mut [c64]>;
 
+impl<C: Container<Element = c64>> Named for FourierLweBootstrapKey<C> {
+    const NAME: &'static str = "core_crypto::FourierLweBootstrapKey";
+}
+
 impl<C: Container<Element = c64>> FourierLweBootstrapKey<C> {
     pub fn from_container(
         data: C,
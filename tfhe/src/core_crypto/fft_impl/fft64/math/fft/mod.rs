@@ -20,6 +20,7 @@ use std::sync::{Arc, OnceLock, RwLock};
 use std::time::Duration;
 use tfhe_fft::c64;
 use tfhe_fft::unordered::{Method, Plan};
+use tfhe_versionable::schema::{DataSchema, SchemaRegistry, VersionSchema};
 use tfhe_versionable::{Unversionize, UnversionizeError, Versionize, VersionizeOwned};
 
 #[cfg(any(target_arch = "x86_64", target_arch = "x86"))]
@@ -606,6 +607,21 @@ impl<C: Container<Element = c64>> VersionizeOwned for FourierPolynomialList<C> {
     fn versionize_owned(self) -> Self::VersionedOwned {
         self.into()
     }
+
+    // The list is stored with its own serde impl inside the single version of the dispatch enum
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        registry.register_versioned::<FourierPolynomialList<ABox<[c64]>>, Self::VersionedOwned>(
+            |_| {
+                vec![VersionSchema {
+                    version: "V0".to_string(),
+                    type_name: std::any::type_name::<FourierPolynomialList<ABox<[c64]>>>()
+                        .to_string(),
+                    layout: DataSchema::opaque::<FourierPolynomialList<ABox<[c64]>>>(),
+                    upgrades_to: None,
+                }]
+            },
+        )
+    }
 }
 
 impl<C: IntoContainerOwned<Element = c64>> Unversionize for FourierPolynomialList<C> {
@@ -7,3 +7,4 @@ pub mod fft64;
 
 pub mod fft128;
 mod fft128_u128;
+pub mod fft32;
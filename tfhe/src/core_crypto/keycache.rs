@@ -195,11 +195,11 @@ pub static KEY_CACHE: LazyLock<KeyCache> = LazyLock::new(KeyCache::default);
 pub fn generate_keys() {
     use crate::core_crypto::algorithms::test::{
         lwe_multi_bit_programmable_bootstrapping, lwe_programmable_bootstrapping, TestResources,
-        DUMMY_31_U32, DUMMY_NATIVE_U32, FFT128_U128_PARAMS, FFT_U128_PARAMS, FFT_U32_PARAMS,
-        FFT_U64_PARAMS, FFT_WOPBS_N1024_PARAMS, FFT_WOPBS_N2048_PARAMS, FFT_WOPBS_N512_PARAMS,
-        FFT_WOPBS_PARAMS, MULTI_BIT_2_2_2_CUSTOM_MOD_PARAMS, MULTI_BIT_2_2_2_PARAMS,
-        MULTI_BIT_2_2_3_CUSTOM_MOD_PARAMS, MULTI_BIT_2_2_3_PARAMS, TEST_PARAMS_3_BITS_63_U64,
-        TEST_PARAMS_4_BITS_NATIVE_U64,
+        DUMMY_31_U32, DUMMY_NATIVE_U32, FFT128_U128_PARAMS, FFT32_U32_PARAMS, FFT_U128_PARAMS,
+        FFT_U32_PARAMS, FFT_U64_PARAMS, FFT_WOPBS_N1024_PARAMS, FFT_WOPBS_N2048_PARAMS,
+        FFT_WOPBS_N512_PARAMS, FFT_WOPBS_PARAMS, MULTI_BIT_2_2_2_CUSTOM_MOD_PARAMS,
+        MULTI_BIT_2_2_2_PARAMS, MULTI_BIT_2_2_3_CUSTOM_MOD_PARAMS, MULTI_BIT_2_2_3_PARAMS,
+        TEST_PARAMS_3_BITS_63_U64, TEST_PARAMS_4_BITS_NATIVE_U64,
     };
     use crate::core_crypto::fft_impl;
     use crate::core_crypto::fft_impl::fft64::crypto::wop_pbs;
@@ -267,6 +267,10 @@ pub fn generate_keys() {
         fft_impl::common::tests::generate_keys(FFT128_U128_PARAMS, &mut rsc)
     });
 
+    generate_and_store(FFT32_U32_PARAMS, &mut |_| {
+        fft_impl::common::tests::generate_keys(FFT32_U32_PARAMS, &mut rsc)
+    });
+
     generate_and_store(FFT_WOPBS_PARAMS, &mut |_| {
         wop_pbs::tests::generate_keys(FFT_WOPBS_PARAMS, &mut rsc)
     });
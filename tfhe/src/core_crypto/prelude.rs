@@ -15,5 +15,6 @@ pub use super::commons::parameters::*;
 pub use super::commons::traits::*;
 pub use super::entities::*;
 pub use super::fft_impl::fft128::math::fft::Fft128;
+pub use super::fft_impl::fft32::math::fft::Fft32;
 pub use super::fft_impl::fft64::math::fft::Fft;
 pub use super::seeders::*;
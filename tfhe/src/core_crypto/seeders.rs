@@ -5,12 +5,62 @@
 //! for cryptographically secure pseudo random number generators.
 
 pub use crate::core_crypto::commons::math::random::Seeder;
+use std::sync::{Arc, RwLock};
 #[cfg(all(target_os = "macos", not(feature = "__wasm_api")))]
 pub use tfhe_csprng::seeders::AppleSecureEnclaveSeeder;
 #[cfg(all(target_arch = "x86_64", not(feature = "__wasm_api")))]
 pub use tfhe_csprng::seeders::RdseedSeeder;
+pub use tfhe_csprng::seeders::{CallbackSeeder, CombinedSeeder};
 #[cfg(all(target_family = "unix", not(feature = "__wasm_api")))]
-pub use tfhe_csprng::seeders::UnixSeeder;
+pub use tfhe_csprng::seeders::{DevRandomSeeder, UnixSeeder};
+
+type SeederFactory = dyn Fn() -> Box<dyn Seeder> + Send + Sync;
+
+static CUSTOM_SEEDER_FACTORY: RwLock<Option<Arc<SeederFactory>>> = RwLock::new(None);
+
+/// Register a factory of custom seeders, which [`new_seeder`] will use in priority over the
+/// built-in seeders.
+///
+/// This allows to draw the seeds used by the library from your own entropy source, e.g. with a
+/// [`CallbackSeeder`] querying a hardware security module, or with a [`CombinedSeeder`] mixing it
+/// with the built-in seeders.
+///
+/// Registering a new factory replaces the previous one, the registration is global to the
+/// process.
+///
+/// # Example
+///
+/// ```rust
+/// use tfhe::core_crypto::commons::math::random::Seed;
+/// use tfhe::core_crypto::prelude::*;
+///
+/// register_custom_seeder(|| {
+///     // Replace this closure by a call to your own entropy source
+///     let custom_source = CallbackSeeder::new(|| Seed(42));
+///     Box::new(CombinedSeeder::new(vec![
+///         Box::new(custom_source),
+///         new_builtin_seeder(),
+///     ]))
+/// });
+///
+/// let mut seeder = new_seeder();
+/// let seeder = seeder.as_mut();
+/// assert_ne!(seeder.seed(), seeder.seed());
+///
+/// unregister_custom_seeder();
+/// ```
+pub fn register_custom_seeder<F>(factory: F)
+where
+    F: Fn() -> Box<dyn Seeder> + Send + Sync + 'static,
+{
+    *CUSTOM_SEEDER_FACTORY.write().unwrap() = Some(Arc::new(factory));
+}
+
+/// Remove the factory registered with [`register_custom_seeder`], if any, so that
+/// [`new_seeder`] uses the built-in seeders again.
+pub fn unregister_custom_seeder() {
+    *CUSTOM_SEEDER_FACTORY.write().unwrap() = None;
+}
 
 #[cfg(feature = "__wasm_api")]
 mod wasm_seeder {
@@ -41,6 +91,34 @@ mod wasm_seeder {
 ///
 /// # Note
 ///
+/// If a custom seeder factory was registered with [`register_custom_seeder`], it is used to
+/// create the seeder. Otherwise, the seeder returned by [`new_builtin_seeder`] is used.
+///
+/// # Example
+///
+/// ```rust
+/// use tfhe::core_crypto::prelude::*;
+///
+/// let mut seeder = new_seeder();
+/// let seeder = seeder.as_mut();
+///
+/// let first_seed = seeder.seed();
+/// let second_seed = seeder.seed();
+/// assert_ne!(first_seed, second_seed);
+/// ```
+pub fn new_seeder() -> Box<dyn Seeder> {
+    // Clone the factory so that the lock is not held while the seeder is created
+    let custom_factory = CUSTOM_SEEDER_FACTORY.read().unwrap().clone();
+    match custom_factory {
+        Some(factory) => factory(),
+        None => new_builtin_seeder(),
+    }
+}
+
+/// Return an available boxed built-in [`Seeder`] prioritizing hardware entropy sources.
+///
+/// # Note
+///
 /// When the `rdseed` CPU feature is detected on `x86_64` CPUs the rdseed seeder is
 /// prioritized.
 ///
@@ -60,14 +138,14 @@ mod wasm_seeder {
 /// ```rust
 /// use tfhe::core_crypto::prelude::*;
 ///
-/// let mut seeder = new_seeder();
+/// let mut seeder = new_builtin_seeder();
 /// let seeder = seeder.as_mut();
 ///
 /// let first_seed = seeder.seed();
 /// let second_seed = seeder.seed();
 /// assert_ne!(first_seed, second_seed);
 /// ```
-pub fn new_seeder() -> Box<dyn Seeder> {
+pub fn new_builtin_seeder() -> Box<dyn Seeder> {
     let mut seeder: Option<Box<dyn Seeder>> = None;
 
     let err_msg;
@@ -111,3 +189,184 @@ pub fn new_seeder() -> Box<dyn Seeder> {
 
     seeder.expect(err_msg)
 }
+
+// Master seeds are derived with SHA3, which is only a dependency of the shortint feature
+#[cfg(feature = "shortint")]
+mod master_seed {
+    use crate::core_crypto::commons::generators::DeterministicSeeder;
+    use crate::core_crypto::commons::math::random::{DefaultRandomGenerator, Seed};
+    use rand_core::{OsRng, RngCore};
+    use sha3::{Digest, Sha3_256};
+    use std::fmt::Debug;
+
+    /// Size in bytes of a [`MasterSeed`]
+    pub const MASTER_SEED_SIZE: usize = 32;
+
+    /// Domain separator of the seeds derived from a [`MasterSeed`]
+    const DERIVE_SEED_DOMAIN: &[u8] = b"TFHE-rs MasterSeed derive v1";
+
+    /// Domain separator of the child master seeds derived from a [`MasterSeed`]
+    const DERIVE_CHILD_DOMAIN: &[u8] = b"TFHE-rs MasterSeed child v1";
+
+    /// A secret from which many independent [`Seed`]s can be derived.
+    ///
+    /// Each seed is derived from the master seed and a label, e.g. the name of a tenant or an
+    /// epoch. The same master seed and label always give the same seed, while different labels
+    /// give independent seeds. Child master seeds can also be derived to build a hierarchy,
+    /// e.g. one child per tenant from which the seeds of each epoch are derived.
+    ///
+    /// The derivation hashes the master seed and the label with SHA3-256, prefixed with a domain
+    /// separator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::core_crypto::prelude::*;
+    ///
+    /// let master_seed = MasterSeed::new([7u8; 32]);
+    ///
+    /// let tenant = master_seed.derive_child("tenant-1");
+    /// assert_eq!(tenant.derive("epoch-3"), tenant.derive("epoch-3"));
+    /// assert_ne!(tenant.derive("epoch-3"), tenant.derive("epoch-4"));
+    ///
+    /// // Derived seeders can be used to create engines
+    /// let mut seeder = tenant.derive_seeder("epoch-3");
+    /// let _seed = seeder.seed();
+    /// ```
+    #[derive(Clone, PartialEq, Eq)]
+    pub struct MasterSeed([u8; MASTER_SEED_SIZE]);
+
+    impl MasterSeed {
+        /// Creates a master seed from raw bytes. The bytes should come from a cryptographically
+        /// secure source.
+        pub fn new(bytes: [u8; MASTER_SEED_SIZE]) -> Self {
+            Self(bytes)
+        }
+
+        /// Generates a new random master seed from the operating system's random number generator.
+        ///
+        /// [`new_seeder`](super::new_seeder) is not used as it returns the registered custom seeder
+        /// if any, which may be deterministic.
+        pub fn generate() -> Self {
+            let mut bytes = [0u8; MASTER_SEED_SIZE];
+            OsRng.fill_bytes(&mut bytes);
+            Self(bytes)
+        }
+
+        /// Returns the raw bytes of the master seed
+        pub fn as_bytes(&self) -> &[u8; MASTER_SEED_SIZE] {
+            &self.0
+        }
+
+        /// Derives the seed associated to `label`
+        pub fn derive(&self, label: impl AsRef<[u8]>) -> Seed {
+            let digest = self.hash(DERIVE_SEED_DOMAIN, label.as_ref());
+
+            let mut seed_bytes = [0u8; std::mem::size_of::<u128>()];
+            seed_bytes.copy_from_slice(&digest[..seed_bytes.len()]);
+            Seed(u128::from_le_bytes(seed_bytes))
+        }
+
+        /// Derives a deterministic seeder from the seed associated to `label`, that can be given to
+        /// the `new_from_seeder` constructors of the engines
+        pub fn derive_seeder(
+            &self,
+            label: impl AsRef<[u8]>,
+        ) -> DeterministicSeeder<DefaultRandomGenerator> {
+            DeterministicSeeder::new(self.derive(label))
+        }
+
+        /// Derives the child master seed associated to `label`. The seeds derived from the child
+        /// are independent from the ones derived from the parent.
+        pub fn derive_child(&self, label: impl AsRef<[u8]>) -> Self {
+            Self(self.hash(DERIVE_CHILD_DOMAIN, label.as_ref()))
+        }
+
+        fn hash(&self, domain: &[u8], label: &[u8]) -> [u8; MASTER_SEED_SIZE] {
+            // Lengths are encoded so that the concatenation is not ambiguous
+            let mut hasher = Sha3_256::new();
+            hasher.update((domain.len() as u64).to_le_bytes());
+            hasher.update(domain);
+            hasher.update(self.0);
+            hasher.update((label.len() as u64).to_le_bytes());
+            hasher.update(label);
+            hasher.finalize().into()
+        }
+    }
+
+    // The master seed should not end up in logs
+    impl Debug for MasterSeed {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_tuple("MasterSeed").finish_non_exhaustive()
+        }
+    }
+
+    impl Drop for MasterSeed {
+        fn drop(&mut self) {
+            self.0.fill(0);
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn test_master_seed() -> MasterSeed {
+            let mut bytes = [0u8; MASTER_SEED_SIZE];
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = i as u8;
+            }
+            MasterSeed::new(bytes)
+        }
+
+        #[test]
+        fn test_master_seed_derivation_vectors() {
+            let master_seed = test_master_seed();
+
+            assert_eq!(
+                master_seed.derive(""),
+                Seed(0x53fd1e9cbcb547ad24c811501eaff239)
+            );
+            assert_eq!(
+                master_seed.derive("tenant-1"),
+                Seed(0xf4362bc8670181111df60d8ff6b30213)
+            );
+            assert_eq!(
+                master_seed.derive(b"tenant-2/epoch-3"),
+                Seed(0x9c5a885dd00cc0fb0edf2a994db18dfa)
+            );
+
+            let child = master_seed.derive_child("tenant-1");
+            assert_eq!(
+                child.as_bytes(),
+                &[
+                    0x7c, 0x82, 0x0c, 0xa3, 0xcd, 0xe1, 0xd8, 0x73, 0xeb, 0x30, 0x74, 0xf0, 0xd0,
+                    0x9b, 0xae, 0x85, 0xbe, 0xe5, 0xef, 0xf9, 0xe3, 0x30, 0x24, 0x17, 0x6d, 0xc1,
+                    0x21, 0xa1, 0x3d, 0x88, 0x2b, 0x68
+                ]
+            );
+            assert_eq!(
+                child.derive("epoch-3"),
+                Seed(0xa7f330b7e89419a71abc605ed2683309)
+            );
+        }
+
+        #[test]
+        fn test_master_seed_domain_separation() {
+            let master_seed = test_master_seed();
+
+            // Children and seeds with the same label are unrelated
+            let child = master_seed.derive_child("label");
+            let seed = master_seed.derive("label");
+            assert_ne!(&child.as_bytes()[..16], &seed.0.to_le_bytes());
+
+            // The seeder is seeded with the derived seed
+            let mut seeder = master_seed.derive_seeder("label");
+            let mut expected = DeterministicSeeder::<DefaultRandomGenerator>::new(seed);
+            assert_eq!(seeder.seed(), expected.seed());
+        }
+    }
+}
+
+#[cfg(feature = "shortint")]
+pub use master_seed::{MasterSeed, MASTER_SEED_SIZE};
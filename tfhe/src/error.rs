@@ -5,6 +5,9 @@ pub enum ErrorKind {
     Message(String),
     /// The provide range for a slicing operation was invalid
     InvalidRange(InvalidRangeError),
+    /// The tag of a ciphertext did not match the tag of the server key
+    #[cfg(feature = "integer")]
+    TagMismatch(crate::TagMismatchError),
     /// The zero knowledge proof and the content it is supposed to prove
     /// failed to correctly prove
     #[cfg(feature = "zk-pok")]
@@ -47,6 +50,8 @@ impl Display for Error {
                 write!(f, "The zero knowledge proof and the content it is supposed to prove were not valid")
             }
             ErrorKind::InvalidRange(err) => write!(f, "Invalid range: {err}"),
+            #[cfg(feature = "integer")]
+            ErrorKind::TagMismatch(err) => write!(f, "{err}"),
         }
     }
 }
@@ -113,6 +113,9 @@ where
         Some(Device::CudaGpu) => {
             panic!("Not supported by Cuda devices")
         }
+        Some(Device::Simulation) => {
+            panic!("Not supported by the simulation backend")
+        }
         None => {
             panic!("{}", crate::high_level_api::errors::UninitializedServerKey);
         }
@@ -140,6 +143,9 @@ where
         Some(Device::CudaGpu) => {
             panic!("Not supported by Cuda devices")
         }
+        Some(Device::Simulation) => {
+            panic!("Not supported by the simulation backend")
+        }
         None => {
             panic!("{}", crate::high_level_api::errors::UninitializedServerKey);
         }
@@ -174,6 +180,9 @@ where
         Some(Device::CudaGpu) => {
             panic!("Not supported by Cuda devices")
         }
+        Some(Device::Simulation) => {
+            panic!("Not supported by the simulation backend")
+        }
         None => {
             panic!("{}", crate::high_level_api::errors::UninitializedServerKey);
         }
@@ -195,6 +195,9 @@ where
         Some(Device::CudaGpu) => {
             panic!("Not supported by Cuda devices")
         }
+        Some(Device::Simulation) => {
+            panic!("Not supported by the simulation backend")
+        }
         None => {
             panic!("{}", crate::high_level_api::errors::UninitializedServerKey);
         }
@@ -222,6 +225,9 @@ where
         Some(Device::CudaGpu) => {
             panic!("Not supported by Cuda devices")
         }
+        Some(Device::Simulation) => {
+            panic!("Not supported by the simulation backend")
+        }
         None => {
             panic!("{}", crate::high_level_api::errors::UninitializedServerKey);
         }
@@ -346,6 +352,9 @@ where
         Some(Device::CudaGpu) => {
             panic!("Not supported by Cuda devices")
         }
+        Some(Device::Simulation) => {
+            panic!("Not supported by the simulation backend")
+        }
         None => {
             panic!("{}", crate::high_level_api::errors::UninitializedServerKey);
         }
@@ -202,6 +202,9 @@ where
         Some(Device::CudaGpu) => {
             panic!("Not supported by Cuda devices")
         }
+        Some(Device::Simulation) => {
+            panic!("Not supported by the simulation backend")
+        }
         None => {
             panic!("{}", crate::high_level_api::errors::UninitializedServerKey);
         }
@@ -229,6 +232,9 @@ where
         Some(Device::CudaGpu) => {
             panic!("Not supported by Cuda devices")
         }
+        Some(Device::Simulation) => {
+            panic!("Not supported by the simulation backend")
+        }
         None => {
             panic!("{}", crate::high_level_api::errors::UninitializedServerKey);
         }
@@ -353,6 +359,9 @@ where
         Some(Device::CudaGpu) => {
             panic!("Not supported by Cuda devices")
         }
+        Some(Device::Simulation) => {
+            panic!("Not supported by the simulation backend")
+        }
         None => {
             panic!("{}", crate::high_level_api::errors::UninitializedServerKey);
         }
@@ -12,11 +12,13 @@ pub(in crate::high_level_api) mod traits;
 
 use crate::array::traits::TensorSlice;
 use crate::high_level_api::array::traits::HasClear;
+use crate::high_level_api::booleans::InnerBoolean;
 use crate::high_level_api::global_state;
 #[cfg(feature = "gpu")]
 use crate::high_level_api::global_state::with_thread_local_cuda_streams;
 use crate::high_level_api::integers::{FheIntId, FheUintId};
 use crate::high_level_api::keys::InternalServerKey;
+use crate::high_level_api::simulation::SimulatedInteger;
 use crate::{FheBool, FheId, FheInt, FheUint, Tag};
 use std::ops::{AddAssign, Mul, RangeBounds};
 use traits::{ArrayBackend, BackendDataContainer, BackendDataContainerMut};
@@ -352,6 +354,7 @@ declare_concrete_array_types!(
 );
 
 pub fn fhe_uint_array_eq<Id: FheUintId>(lhs: &[FheUint<Id>], rhs: &[FheUint<Id>]) -> FheBool {
+    global_state::check_tags(lhs.iter().chain(rhs).map(|fhe_uint| &fhe_uint.tag));
     global_state::with_internal_keys(|sks| match sks {
         InternalServerKey::Cpu(cpu_key) => {
             let tmp_lhs = lhs
@@ -382,6 +385,13 @@ pub fn fhe_uint_array_eq<Id: FheUintId>(lhs: &[FheUint<Id>], rhs: &[FheUint<Id>]
             let result = gpu_key.key.key.all_eq_slices(&tmp_lhs, &tmp_rhs, streams);
             FheBool::new(result, gpu_key.tag.clone())
         }),
+        InternalServerKey::Simulation(sim_key) => {
+            let result = lhs.len() == rhs.len()
+                && lhs.iter().zip(rhs).all(|(l, r)| {
+                    l.ciphertext.on_simulation(sim_key) == r.ciphertext.on_simulation(sim_key)
+                });
+            FheBool::new(InnerBoolean::Simulation(result), sim_key.tag.clone())
+        }
     })
 }
 
@@ -389,6 +399,7 @@ pub fn fhe_uint_array_contains_sub_slice<Id: FheUintId>(
     lhs: &[FheUint<Id>],
     pattern: &[FheUint<Id>],
 ) -> FheBool {
+    global_state::check_tags(lhs.iter().chain(pattern).map(|fhe_uint| &fhe_uint.tag));
     global_state::with_internal_keys(|sks| match sks {
         InternalServerKey::Cpu(cpu_key) => {
             let tmp_lhs = lhs
@@ -422,6 +433,22 @@ pub fn fhe_uint_array_contains_sub_slice<Id: FheUintId>(
                 .contains_sub_slice(&tmp_lhs, &tmp_pattern, streams);
             FheBool::new(result, gpu_key.tag.clone())
         }),
+        InternalServerKey::Simulation(sim_key) => {
+            let tmp_lhs = lhs
+                .iter()
+                .map(|fhe_uint| fhe_uint.ciphertext.on_simulation(sim_key))
+                .collect::<Vec<_>>();
+            let tmp_pattern = pattern
+                .iter()
+                .map(|fhe_uint| fhe_uint.ciphertext.on_simulation(sim_key))
+                .collect::<Vec<_>>();
+
+            let result = tmp_pattern.len() <= tmp_lhs.len()
+                && tmp_lhs
+                    .windows(tmp_pattern.len())
+                    .any(|window| window == tmp_pattern.as_slice());
+            FheBool::new(InnerBoolean::Simulation(result), sim_key.tag.clone())
+        }
     })
 }
 
@@ -443,6 +470,7 @@ where
         + AddAssign<Clear>
         + OverflowingAdd<Clear, Output = Clear>,
 {
+    global_state::check_tags(bools.iter().map(|b| &b.tag));
     global_state::with_internal_keys(|keys| match keys {
         InternalServerKey::Cpu(cpu_key) => {
             let boolean_blocks = bools
@@ -461,6 +489,20 @@ where
         InternalServerKey::Cuda(_) => {
             panic!("Cuda does not support FheBool dot product")
         }
+        InternalServerKey::Simulation(sim_key) => {
+            let result = bools
+                .iter()
+                .zip(clears)
+                .filter(|(b, _)| b.ciphertext.on_simulation(sim_key))
+                .fold(
+                    SimulatedInteger::zero(num_bits, false),
+                    |acc, (_, clear)| {
+                        acc.add(&SimulatedInteger::from_scalar(*clear, num_bits, false))
+                    },
+                );
+
+            (result.to_radix(sim_key).blocks, sim_key.tag.clone())
+        }
     })
 }
 
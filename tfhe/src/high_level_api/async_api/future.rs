@@ -0,0 +1,108 @@
+use std::future::Future;
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+struct State<T> {
+    result: Option<std::thread::Result<T>>,
+    waker: Option<Waker>,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    // Set when the future is dropped, jobs that did not start yet are then skipped
+    cancelled: AtomicBool,
+}
+
+/// Future resolving to the result of an operation executed by an
+/// [AsyncServerKey](crate::AsyncServerKey).
+///
+/// The future does not depend on any particular async runtime, it is woken up by the thread pool
+/// once the operation is done.
+///
+/// Dropping the future before it resolves cancels the operation if it has not started yet.
+/// An operation that already started runs to completion, its result is then discarded. As
+/// operations only work on copies of their inputs, cancelling never leaves a ciphertext in a
+/// partially updated state.
+///
+/// If the operation panics, the panic is resumed when the future is polled.
+#[must_use = "futures do nothing unless polled, dropping it cancels the operation"]
+pub struct FheFuture<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> FheFuture<T>
+where
+    T: Send + 'static,
+{
+    pub(super) fn spawn<F>(pool: &rayon::ThreadPool, func: F) -> Self
+    where
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                result: None,
+                waker: None,
+            }),
+            cancelled: AtomicBool::new(false),
+        });
+
+        let job_shared = Arc::clone(&shared);
+        pool.spawn(move || {
+            if job_shared.cancelled.load(Ordering::Acquire) {
+                return;
+            }
+
+            let result = catch_unwind(AssertUnwindSafe(func));
+
+            let waker = {
+                let mut state = job_shared.state.lock().unwrap();
+                state.result = Some(result);
+                state.waker.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        });
+
+        Self { shared }
+    }
+}
+
+impl<T> FheFuture<T> {
+    /// Returns whether the operation is done, in which case polling the future returns
+    /// [Poll::Ready]
+    pub fn is_finished(&self) -> bool {
+        self.shared.state.lock().unwrap().result.is_some()
+    }
+}
+
+impl<T> Future for FheFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.shared.state.lock().unwrap();
+        match state.result.take() {
+            Some(Ok(value)) => Poll::Ready(value),
+            Some(Err(panic)) => {
+                drop(state);
+                resume_unwind(panic)
+            }
+            None => {
+                match &mut state.waker {
+                    Some(waker) if waker.will_wake(cx.waker()) => {}
+                    waker => *waker = Some(cx.waker().clone()),
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for FheFuture<T> {
+    fn drop(&mut self) {
+        self.shared.cancelled.store(true, Ordering::Release);
+    }
+}
@@ -0,0 +1,309 @@
+//! Asynchronous interface to the high-level API.
+//!
+//! The server key used by the high-level API is stored in a thread local, which does not play
+//! well with async runtimes where tasks move between threads. An [AsyncServerKey] instead owns
+//! a dedicated rayon thread pool whose threads all have the key set, operations are executed on
+//! that pool and return a [FheFuture] that can be awaited from any runtime.
+
+mod future;
+
+pub use future::FheFuture;
+
+use crate::high_level_api::global_state;
+use crate::high_level_api::keys::InternalServerKey;
+#[cfg(feature = "strings")]
+use crate::high_level_api::strings::traits::{FheStringFind, FheStringMatching};
+use crate::prelude::{FheEq, FheMax, FheMin, FheOrd, IfThenElse};
+use crate::{Error, FheBool};
+#[cfg(feature = "strings")]
+use crate::{FheAsciiString, FheStringLen, FheUint32};
+use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Neg, Not, Rem, Shl, Shr, Sub};
+use std::sync::Arc;
+
+macro_rules! impl_async_binary_operations {
+    (
+        $(
+            $(#[$outer:meta])*
+            $rust_trait_name:ident($rust_trait_method:ident)
+        ),* $(,)?
+    ) => {
+        $(
+            $(#[$outer])*
+            pub fn $rust_trait_method<T>(&self, lhs: &T, rhs: &T) -> FheFuture<T>
+            where
+                T: Clone + Send + 'static,
+                for<'a> &'a T: $rust_trait_name<&'a T, Output = T>,
+            {
+                let (lhs, rhs) = (lhs.clone(), rhs.clone());
+                self.spawn(move || $rust_trait_name::$rust_trait_method(&lhs, &rhs))
+            }
+        )*
+    };
+}
+
+macro_rules! impl_async_comparisons {
+    (
+        $(
+            $rust_trait_name:ident($($rust_trait_method:ident),*)
+        ),* $(,)?
+    ) => {
+        $(
+            $(
+                #[doc = concat!("Returns a future resolving to `lhs.", stringify!($rust_trait_method), "(rhs)`")]
+                pub fn $rust_trait_method<T>(&self, lhs: &T, rhs: &T) -> FheFuture<FheBool>
+                where
+                    T: Clone + Send + 'static + for<'a> $rust_trait_name<&'a T>,
+                {
+                    let (lhs, rhs) = (lhs.clone(), rhs.clone());
+                    self.spawn(move || $rust_trait_name::$rust_trait_method(&lhs, &rhs))
+                }
+            )*
+        )*
+    };
+}
+
+/// A server key executing high-level operations asynchronously.
+///
+/// The key is shared by all the threads of a dedicated rayon thread pool, so there is no need to
+/// call [set_server_key](crate::set_server_key) and the thread calling the methods does not need
+/// to have a key set. Cloning an [AsyncServerKey] is cheap, clones share the same thread pool.
+///
+/// Operations take their inputs by reference and run on copies of them, the returned
+/// [FheFuture] resolves to the result of the operation. Dropping the future cancels the
+/// operation, see [FheFuture] for details.
+///
+/// [AsyncServerKey::spawn] can be used to run any computation on the thread pool, including
+/// operations for which no method exist.
+///
+/// # Example
+///
+/// ```rust
+/// use tfhe::prelude::*;
+/// use tfhe::{AsyncServerKey, ClientKey, ConfigBuilder, FheUint8, ServerKey};
+///
+/// let config = ConfigBuilder::default().build();
+/// let client_key = ClientKey::generate(config);
+/// let async_key = AsyncServerKey::new(ServerKey::new(&client_key));
+///
+/// let a = FheUint8::encrypt(27u8, &client_key);
+/// let b = FheUint8::encrypt(100u8, &client_key);
+///
+/// // Futures can be awaited from any async runtime
+/// let sum = futures::executor::block_on(async_key.add(&a, &b));
+/// let is_greater = futures::executor::block_on(async_key.gt(&sum, &b));
+///
+/// let decrypted: u8 = sum.decrypt(&client_key);
+/// assert_eq!(decrypted, 127);
+/// assert!(is_greater.decrypt(&client_key));
+/// ```
+#[derive(Clone)]
+pub struct AsyncServerKey {
+    // Each thread of the pool sets the server key when it starts
+    pool: Arc<rayon::ThreadPool>,
+}
+
+impl AsyncServerKey {
+    /// Creates an [AsyncServerKey] with as many threads as rayon uses by default
+    ///
+    /// # Panics
+    ///
+    /// Panics if the thread pool could not be created
+    pub fn new<T: Into<InternalServerKey>>(key: T) -> Self {
+        Self::with_num_threads(key, 0).unwrap()
+    }
+
+    /// Creates an [AsyncServerKey] whose thread pool has `num_threads` threads
+    ///
+    /// If `num_threads` is 0, rayon's default number of threads is used.
+    pub fn with_num_threads<T: Into<InternalServerKey>>(
+        key: T,
+        num_threads: usize,
+    ) -> Result<Self, Error> {
+        let key = Arc::new(key.into());
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .thread_name(|index| format!("tfhe-async-{index}"))
+            .start_handler(move |_| global_state::set_server_key((*key).clone()))
+            .build()
+            .map_err(|err| crate::error!("Failed to create the thread pool: {err}"))?;
+
+        Ok(Self {
+            pool: Arc::new(pool),
+        })
+    }
+
+    /// Returns the number of threads of the thread pool
+    pub fn num_threads(&self) -> usize {
+        self.pool.current_num_threads()
+    }
+
+    /// Runs `func` on the thread pool, with the server key set
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::prelude::*;
+    /// use tfhe::{AsyncServerKey, ClientKey, ConfigBuilder, FheUint16, ServerKey};
+    ///
+    /// let config = ConfigBuilder::default().build();
+    /// let client_key = ClientKey::generate(config);
+    /// let async_key = AsyncServerKey::new(ServerKey::new(&client_key));
+    ///
+    /// let a = FheUint16::encrypt(1000u16, &client_key);
+    /// let b = FheUint16::encrypt(37u16, &client_key);
+    ///
+    /// let future = async_key.spawn(move || (&a * &b).rotate_left(3u16));
+    /// let result = futures::executor::block_on(future);
+    ///
+    /// let decrypted: u16 = result.decrypt(&client_key);
+    /// assert_eq!(decrypted, 37000u16.rotate_left(3));
+    /// ```
+    pub fn spawn<F, R>(&self, func: F) -> FheFuture<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        FheFuture::spawn(&self.pool, func)
+    }
+
+    impl_async_binary_operations!(
+        /// Returns a future resolving to `lhs + rhs`
+        Add(add),
+        /// Returns a future resolving to `lhs - rhs`
+        Sub(sub),
+        /// Returns a future resolving to `lhs * rhs`
+        Mul(mul),
+        /// Returns a future resolving to `lhs / rhs`
+        Div(div),
+        /// Returns a future resolving to `lhs % rhs`
+        Rem(rem),
+        /// Returns a future resolving to `lhs & rhs`
+        BitAnd(bitand),
+        /// Returns a future resolving to `lhs | rhs`
+        BitOr(bitor),
+        /// Returns a future resolving to `lhs ^ rhs`
+        BitXor(bitxor),
+        /// Returns a future resolving to `lhs << rhs`
+        Shl(shl),
+        /// Returns a future resolving to `lhs >> rhs`
+        Shr(shr),
+    );
+
+    impl_async_comparisons!(FheEq(eq, ne), FheOrd(lt, le, gt, ge));
+
+    /// Returns a future resolving to `lhs.min(rhs)`
+    pub fn min<T>(&self, lhs: &T, rhs: &T) -> FheFuture<T>
+    where
+        T: Clone + Send + 'static + for<'a> FheMin<&'a T, Output = T>,
+    {
+        let (lhs, rhs) = (lhs.clone(), rhs.clone());
+        self.spawn(move || lhs.min(&rhs))
+    }
+
+    /// Returns a future resolving to `lhs.max(rhs)`
+    pub fn max<T>(&self, lhs: &T, rhs: &T) -> FheFuture<T>
+    where
+        T: Clone + Send + 'static + for<'a> FheMax<&'a T, Output = T>,
+    {
+        let (lhs, rhs) = (lhs.clone(), rhs.clone());
+        self.spawn(move || lhs.max(&rhs))
+    }
+
+    /// Returns a future resolving to `!value`
+    pub fn not<T>(&self, value: &T) -> FheFuture<T>
+    where
+        T: Clone + Send + 'static,
+        for<'a> &'a T: Not<Output = T>,
+    {
+        let value = value.clone();
+        self.spawn(move || !&value)
+    }
+
+    /// Returns a future resolving to `-value`
+    pub fn neg<T>(&self, value: &T) -> FheFuture<T>
+    where
+        T: Clone + Send + 'static,
+        for<'a> &'a T: Neg<Output = T>,
+    {
+        let value = value.clone();
+        self.spawn(move || -&value)
+    }
+
+    /// Returns a future resolving to `condition.if_then_else(ct_then, ct_else)`
+    pub fn if_then_else<T>(&self, condition: &FheBool, ct_then: &T, ct_else: &T) -> FheFuture<T>
+    where
+        T: Clone + Send + 'static,
+        FheBool: IfThenElse<T>,
+    {
+        let (condition, ct_then, ct_else) = (condition.clone(), ct_then.clone(), ct_else.clone());
+        self.spawn(move || condition.if_then_else(&ct_then, &ct_else))
+    }
+}
+
+#[cfg(feature = "strings")]
+impl AsyncServerKey {
+    /// Returns a future resolving to `string.contains(pattern)`
+    pub fn contains(
+        &self,
+        string: &FheAsciiString,
+        pattern: &FheAsciiString,
+    ) -> FheFuture<FheBool> {
+        let (string, pattern) = (string.clone(), pattern.clone());
+        self.spawn(move || string.contains(&pattern))
+    }
+
+    /// Returns a future resolving to `string.starts_with(pattern)`
+    pub fn starts_with(
+        &self,
+        string: &FheAsciiString,
+        pattern: &FheAsciiString,
+    ) -> FheFuture<FheBool> {
+        let (string, pattern) = (string.clone(), pattern.clone());
+        self.spawn(move || string.starts_with(&pattern))
+    }
+
+    /// Returns a future resolving to `string.ends_with(pattern)`
+    pub fn ends_with(
+        &self,
+        string: &FheAsciiString,
+        pattern: &FheAsciiString,
+    ) -> FheFuture<FheBool> {
+        let (string, pattern) = (string.clone(), pattern.clone());
+        self.spawn(move || string.ends_with(&pattern))
+    }
+
+    /// Returns a future resolving to `string.find(pattern)`
+    pub fn find(
+        &self,
+        string: &FheAsciiString,
+        pattern: &FheAsciiString,
+    ) -> FheFuture<(FheUint32, FheBool)> {
+        let (string, pattern) = (string.clone(), pattern.clone());
+        self.spawn(move || string.find(&pattern))
+    }
+
+    /// Returns a future resolving to `lhs.concat(rhs)`
+    pub fn concat(&self, lhs: &FheAsciiString, rhs: &FheAsciiString) -> FheFuture<FheAsciiString> {
+        let (lhs, rhs) = (lhs.clone(), rhs.clone());
+        self.spawn(move || lhs.concat(&rhs))
+    }
+
+    /// Returns a future resolving to `string.len()`
+    pub fn len(&self, string: &FheAsciiString) -> FheFuture<FheStringLen> {
+        let string = string.clone();
+        self.spawn(move || string.len())
+    }
+
+    /// Returns a future resolving to `string.to_lowercase()`
+    pub fn to_lowercase(&self, string: &FheAsciiString) -> FheFuture<FheAsciiString> {
+        let string = string.clone();
+        self.spawn(move || string.to_lowercase())
+    }
+
+    /// Returns a future resolving to `string.to_uppercase()`
+    pub fn to_uppercase(&self, string: &FheAsciiString) -> FheFuture<FheAsciiString> {
+        let string = string.clone();
+        self.spawn(move || string.to_uppercase())
+    }
+}
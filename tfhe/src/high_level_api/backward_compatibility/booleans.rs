@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
-use tfhe_versionable::{Upgrade, Version, VersionsDispatch};
+use tfhe_versionable::{Downgrade, Upgrade, Version, VersionsDispatch};
 
+use crate::high_level_api::backward_compatibility::tag::{check_empty_tag, NonEmptyTagError};
 use crate::high_level_api::booleans::{
     InnerBoolean, InnerBooleanVersionOwned, InnerCompressedFheBool,
     InnerSquashedNoiseBooleanVersionOwned, SquashedNoiseFheBool,
@@ -30,7 +31,19 @@ impl Upgrade<FheBool> for FheBoolV0 {
     }
 }
 
+impl Downgrade<FheBoolV0> for FheBool {
+    type Error = NonEmptyTagError;
+
+    fn downgrade(&self) -> Result<FheBoolV0, Self::Error> {
+        check_empty_tag(&self.tag)?;
+        Ok(FheBoolV0 {
+            ciphertext: self.ciphertext.clone(),
+        })
+    }
+}
+
 #[derive(VersionsDispatch)]
+#[versionize(downgrade)]
 pub enum FheBoolVersions {
     V0(FheBoolV0),
     V1(FheBool),
@@ -59,7 +72,17 @@ impl Upgrade<CompressedFheBool> for CompressedFheBoolV0 {
     }
 }
 
+impl Downgrade<CompressedFheBoolV0> for CompressedFheBool {
+    type Error = NonEmptyTagError;
+
+    fn downgrade(&self) -> Result<CompressedFheBoolV0, Self::Error> {
+        check_empty_tag(&self.tag)?;
+        Ok(self.inner.clone())
+    }
+}
+
 #[derive(VersionsDispatch)]
+#[versionize(downgrade)]
 pub enum CompressedFheBoolVersions {
     V0(CompressedFheBoolV0),
     V1(CompressedFheBool),
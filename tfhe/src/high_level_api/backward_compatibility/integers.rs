@@ -1,8 +1,9 @@
 use std::convert::Infallible;
 
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use tfhe_versionable::{Upgrade, Version, VersionsDispatch};
+use tfhe_versionable::{Downgrade, Upgrade, Version, VersionsDispatch};
 
+use crate::high_level_api::backward_compatibility::tag::{check_empty_tag, NonEmptyTagError};
 use crate::high_level_api::global_state::with_cpu_internal_keys;
 use crate::high_level_api::integers::*;
 use crate::integer::backward_compatibility::ciphertext::{
@@ -141,7 +142,20 @@ impl<Id: FheIntId> Upgrade<FheInt<Id>> for FheIntV0<Id> {
     }
 }
 
+impl<Id: FheIntId> Downgrade<FheIntV0<Id>> for FheInt<Id> {
+    type Error = NonEmptyTagError;
+
+    fn downgrade(&self) -> Result<FheIntV0<Id>, Self::Error> {
+        check_empty_tag(&self.tag)?;
+        Ok(FheIntV0 {
+            ciphertext: self.ciphertext.clone(),
+            id: self.id,
+        })
+    }
+}
+
 #[derive(VersionsDispatch)]
+#[versionize(downgrade)]
 pub enum FheIntVersions<Id: FheIntId> {
     V0(FheIntV0<Id>),
     V1(FheInt<Id>),
@@ -168,7 +182,20 @@ impl<Id: FheIntId> Upgrade<CompressedFheInt<Id>> for CompressedFheIntV0<Id> {
     }
 }
 
+impl<Id: FheIntId> Downgrade<CompressedFheIntV0<Id>> for CompressedFheInt<Id> {
+    type Error = NonEmptyTagError;
+
+    fn downgrade(&self) -> Result<CompressedFheIntV0<Id>, Self::Error> {
+        check_empty_tag(&self.tag)?;
+        Ok(CompressedFheIntV0 {
+            ciphertext: self.ciphertext.clone(),
+            id: self.id,
+        })
+    }
+}
+
 #[derive(VersionsDispatch)]
+#[versionize(downgrade)]
 pub enum CompressedFheIntVersions<Id: FheIntId> {
     V0(CompressedFheIntV0<Id>),
     V1(CompressedFheInt<Id>),
@@ -192,7 +219,20 @@ impl<Id: FheUintId> Upgrade<FheUint<Id>> for FheUintV0<Id> {
     }
 }
 
+impl<Id: FheUintId> Downgrade<FheUintV0<Id>> for FheUint<Id> {
+    type Error = NonEmptyTagError;
+
+    fn downgrade(&self) -> Result<FheUintV0<Id>, Self::Error> {
+        check_empty_tag(&self.tag)?;
+        Ok(FheUintV0 {
+            ciphertext: self.ciphertext.clone(),
+            id: self.id,
+        })
+    }
+}
+
 #[derive(VersionsDispatch)]
+#[versionize(downgrade)]
 pub enum FheUintVersions<Id: FheUintId> {
     V0(FheUintV0<Id>),
     V1(FheUint<Id>),
@@ -215,7 +255,18 @@ impl<Id: FheUintId> Upgrade<CompressedFheUint<Id>> for CompressedFheUintV0<Id> {
     }
 }
 
+impl<Id: FheUintId> Downgrade<CompressedFheUintV0<Id>> for CompressedFheUint<Id> {
+    type Error = NonEmptyTagError;
+
+    fn downgrade(&self) -> Result<CompressedFheUintV0<Id>, Self::Error> {
+        check_empty_tag(&self.tag)?;
+        let (ciphertext, id, _) = self.clone().into_raw_parts();
+        Ok(CompressedFheUintV0 { ciphertext, id })
+    }
+}
+
 #[derive(VersionsDispatch)]
+#[versionize(downgrade)]
 pub enum CompressedFheUintVersions<Id: FheUintId> {
     V0(CompressedFheUintV0<Id>),
     V1(CompressedFheUint<Id>),
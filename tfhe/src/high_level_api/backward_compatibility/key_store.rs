@@ -0,0 +1,13 @@
+use tfhe_versionable::VersionsDispatch;
+
+use crate::high_level_api::key_store::{KeyKind, KeyMetadata};
+
+#[derive(VersionsDispatch)]
+pub enum KeyKindVersions {
+    V0(KeyKind),
+}
+
+#[derive(VersionsDispatch)]
+pub enum KeyMetadataVersions {
+    V0(KeyMetadata),
+}
@@ -1,3 +1,4 @@
+use crate::high_level_api::backward_compatibility::tag::{check_empty_tag, NonEmptyTagError};
 use crate::high_level_api::keys::*;
 use crate::integer::compression_keys::{
     CompressedCompressionKey, CompressedDecompressionKey, CompressionKey, DecompressionKey,
@@ -5,9 +6,10 @@ use crate::integer::compression_keys::{
 use crate::Tag;
 use std::convert::Infallible;
 use tfhe_versionable::deprecation::{Deprecable, Deprecated};
-use tfhe_versionable::{Upgrade, Version, VersionsDispatch};
+use tfhe_versionable::{Downgrade, Upgrade, Version, VersionsDispatch};
 
 #[derive(VersionsDispatch)]
+#[versionize(downgrade)]
 pub enum ClientKeyVersions {
     V0(ClientKeyV0),
     V1(ClientKey),
@@ -30,12 +32,24 @@ impl Upgrade<ClientKey> for ClientKeyV0 {
     }
 }
 
+impl Downgrade<ClientKeyV0> for ClientKey {
+    type Error = NonEmptyTagError;
+
+    fn downgrade(&self) -> Result<ClientKeyV0, Self::Error> {
+        check_empty_tag(&self.tag)?;
+        Ok(ClientKeyV0 {
+            key: self.key.clone(),
+        })
+    }
+}
+
 impl Deprecable for ServerKey {
     const TYPE_NAME: &'static str = "ServerKey";
     const MIN_SUPPORTED_APP_VERSION: &'static str = "TFHE-rs v0.10";
 }
 
 #[derive(VersionsDispatch)]
+#[versionize(downgrade)]
 pub enum ServerKeyVersions {
     V0(Deprecated<ServerKey>),
     V1(Deprecated<ServerKey>),
@@ -49,6 +63,7 @@ impl Deprecable for CompressedServerKey {
 }
 
 #[derive(VersionsDispatch)]
+#[versionize(downgrade)]
 pub enum CompressedServerKeyVersions {
     V0(Deprecated<CompressedServerKey>),
     V1(Deprecated<CompressedServerKey>),
@@ -71,7 +86,19 @@ impl Upgrade<PublicKey> for PublicKeyV0 {
     }
 }
 
+impl Downgrade<PublicKeyV0> for PublicKey {
+    type Error = NonEmptyTagError;
+
+    fn downgrade(&self) -> Result<PublicKeyV0, Self::Error> {
+        check_empty_tag(&self.tag)?;
+        Ok(PublicKeyV0 {
+            key: self.key.clone(),
+        })
+    }
+}
+
 #[derive(VersionsDispatch)]
+#[versionize(downgrade)]
 pub enum PublicKeyVersions {
     V0(PublicKeyV0),
     V1(PublicKey),
@@ -93,7 +120,19 @@ impl Upgrade<CompactPublicKey> for CompactPublicKeyV0 {
     }
 }
 
+impl Downgrade<CompactPublicKeyV0> for CompactPublicKey {
+    type Error = NonEmptyTagError;
+
+    fn downgrade(&self) -> Result<CompactPublicKeyV0, Self::Error> {
+        check_empty_tag(&self.tag)?;
+        Ok(CompactPublicKeyV0 {
+            key: self.key.clone(),
+        })
+    }
+}
+
 #[derive(VersionsDispatch)]
+#[versionize(downgrade)]
 pub enum CompactPublicKeyVersions {
     V0(CompactPublicKeyV0),
     V1(CompactPublicKey),
@@ -115,7 +154,19 @@ impl Upgrade<CompressedPublicKey> for CompressedPublicKeyV0 {
     }
 }
 
+impl Downgrade<CompressedPublicKeyV0> for CompressedPublicKey {
+    type Error = NonEmptyTagError;
+
+    fn downgrade(&self) -> Result<CompressedPublicKeyV0, Self::Error> {
+        check_empty_tag(&self.tag)?;
+        Ok(CompressedPublicKeyV0 {
+            key: self.key.clone(),
+        })
+    }
+}
+
 #[derive(VersionsDispatch)]
+#[versionize(downgrade)]
 pub enum CompressedPublicKeyVersions {
     V0(CompressedPublicKeyV0),
     V1(CompressedPublicKey),
@@ -137,7 +188,19 @@ impl Upgrade<CompressedCompactPublicKey> for CompressedCompactPublicKeyV0 {
     }
 }
 
+impl Downgrade<CompressedCompactPublicKeyV0> for CompressedCompactPublicKey {
+    type Error = NonEmptyTagError;
+
+    fn downgrade(&self) -> Result<CompressedCompactPublicKeyV0, Self::Error> {
+        check_empty_tag(&self.tag)?;
+        Ok(CompressedCompactPublicKeyV0 {
+            key: self.key.clone(),
+        })
+    }
+}
+
 #[derive(VersionsDispatch)]
+#[versionize(downgrade)]
 pub enum CompressedCompactPublicKeyVersions {
     V0(CompressedCompactPublicKeyV0),
     V1(CompressedCompactPublicKey),
@@ -321,3 +384,8 @@ pub enum KeySwitchingKeyVersions {
     V0(Deprecated<KeySwitchingKey>),
     V1(KeySwitchingKey),
 }
+
+#[derive(VersionsDispatch)]
+pub enum KeyRotationKeyVersions {
+    V0(KeyRotationKey),
+}
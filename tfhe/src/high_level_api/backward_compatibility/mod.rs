@@ -7,7 +7,10 @@ pub mod compact_list;
 pub mod compressed_ciphertext_list;
 pub mod config;
 pub mod integers;
+#[cfg(feature = "key-store")]
+pub mod key_store;
 pub mod keys;
+pub mod program;
 #[cfg(feature = "strings")]
 pub mod strings;
 pub mod tag;
@@ -0,0 +1,40 @@
+use tfhe_versionable::VersionsDispatch;
+
+use crate::high_level_api::program::{
+    BinaryOp, ClearValue, ComparisonOp, Instruction, Program, UnaryOp, ValueType,
+};
+
+#[derive(VersionsDispatch)]
+pub enum ValueTypeVersions {
+    V0(ValueType),
+}
+
+#[derive(VersionsDispatch)]
+pub enum ClearValueVersions {
+    V0(ClearValue),
+}
+
+#[derive(VersionsDispatch)]
+pub enum UnaryOpVersions {
+    V0(UnaryOp),
+}
+
+#[derive(VersionsDispatch)]
+pub enum BinaryOpVersions {
+    V0(BinaryOp),
+}
+
+#[derive(VersionsDispatch)]
+pub enum ComparisonOpVersions {
+    V0(ComparisonOp),
+}
+
+#[derive(VersionsDispatch)]
+pub enum InstructionVersions {
+    V0(Instruction),
+}
+
+#[derive(VersionsDispatch)]
+pub enum ProgramVersions {
+    V0(Program),
+}
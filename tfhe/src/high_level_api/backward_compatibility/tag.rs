@@ -1,3 +1,6 @@
+use std::error::Error;
+use std::fmt::Display;
+
 use crate::high_level_api::tag::Tag;
 use tfhe_versionable::VersionsDispatch;
 
@@ -5,3 +8,28 @@ use tfhe_versionable::VersionsDispatch;
 pub enum TagVersions {
     V0(Tag),
 }
+
+/// Error returned when an object with a non-empty [`Tag`] is downgraded into a version that was
+/// defined before tags were added
+#[derive(Debug)]
+pub struct NonEmptyTagError;
+
+impl Display for NonEmptyTagError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "A non-empty tag cannot be represented in a version without tag"
+        )
+    }
+}
+
+impl Error for NonEmptyTagError {}
+
+/// Checks that the tag can be dropped when downgrading into a version without tag
+pub(crate) fn check_empty_tag(tag: &Tag) -> Result<(), NonEmptyTagError> {
+    if tag.is_empty() {
+        Ok(())
+    } else {
+        Err(NonEmptyTagError)
+    }
+}
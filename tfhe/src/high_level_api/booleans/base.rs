@@ -7,6 +7,7 @@ use crate::high_level_api::global_state;
 use crate::high_level_api::global_state::with_thread_local_cuda_streams;
 use crate::high_level_api::integers::{FheInt, FheIntId, FheUint, FheUintId};
 use crate::high_level_api::keys::InternalServerKey;
+use crate::high_level_api::simulation::SimulatedInteger;
 use crate::high_level_api::traits::{FheEq, IfThenElse, ScalarIfThenElse, Tagged};
 use crate::integer::block_decomposition::DecomposableInto;
 #[cfg(feature = "gpu")]
@@ -116,12 +117,43 @@ impl FheBool {
         self.ciphertext.move_to_device(device)
     }
 
+    /// Evaluates (in-place) the operations this value depends on, if it was computed
+    /// with lazy evaluation enabled
+    ///
+    /// All the other values recorded in the same graph are evaluated at the same time.
+    /// Does nothing if the value is already evaluated.
+    ///
+    /// See [with_lazy_evaluation](crate::with_lazy_evaluation)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::prelude::*;
+    /// use tfhe::{generate_keys, set_server_key, with_lazy_evaluation, ConfigBuilder, FheUint8};
+    ///
+    /// let (client_key, server_key) = generate_keys(ConfigBuilder::default());
+    /// set_server_key(server_key);
+    ///
+    /// let a = FheUint8::encrypt(3u8, &client_key);
+    /// let b = FheUint8::encrypt(37u8, &client_key);
+    ///
+    /// let mut result = with_lazy_evaluation(|| (&a + &b).gt(&b));
+    /// result.eval();
+    ///
+    /// assert!(result.decrypt(&client_key));
+    /// ```
+    pub fn eval(&mut self) {
+        self.ciphertext.materialize();
+    }
+
     pub fn into_raw_parts(mut self) -> crate::shortint::Ciphertext {
+        self.ciphertext.materialize();
         self.ciphertext.move_to_device(Device::Cpu);
         match self.ciphertext {
             InnerBoolean::Cpu(ct) => ct.into_raw_parts(),
             #[cfg(feature = "gpu")]
             InnerBoolean::Cuda(_) => unreachable!(),
+            InnerBoolean::Lazy(_) | InnerBoolean::Simulation(_) => unreachable!(),
         }
     }
 
@@ -157,6 +189,10 @@ impl FheBool {
     /// assert!(result.is_err());
     /// ```
     pub fn try_decrypt_trivial(&self) -> Result<bool, NotTrivialCiphertextError> {
+        // Values of the simulation backend are in clear
+        if let InnerBoolean::Simulation(value) = &self.ciphertext {
+            return Ok(*value);
+        }
         self.ciphertext.on_cpu().decrypt_trivial()
     }
 
@@ -178,6 +214,9 @@ impl FheBool {
     /// assert!(trivial.is_trivial());
     /// ```
     pub fn is_trivial(&self) -> bool {
+        if let InnerBoolean::Simulation(_) = &self.ciphertext {
+            return true;
+        }
         self.ciphertext.on_cpu().is_trivial()
     }
 }
@@ -212,6 +251,7 @@ where
     /// ```
     fn scalar_if_then_else(&self, then_value: &FheUint<Id>, else_value: Scalar) -> Self::Output {
         let ct_condition = self;
+        global_state::check_tags([&ct_condition.tag, &then_value.tag]);
         global_state::with_internal_keys(|sks| match sks {
             InternalServerKey::Cpu(cpu_sks) => {
                 let inner = cpu_sks.pbs_key().if_then_else_parallelized(
@@ -225,6 +265,17 @@ where
             InternalServerKey::Cuda(_) => {
                 panic!("Cuda does not support if_then_else with clear input")
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let then_value = then_value.ciphertext.on_simulation(sim_key);
+                let else_value =
+                    SimulatedInteger::from_scalar(else_value, then_value.num_bits(), false);
+                let inner = if ct_condition.ciphertext.on_simulation(sim_key) {
+                    then_value
+                } else {
+                    else_value
+                };
+                FheUint::new(inner, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -259,6 +310,7 @@ where
     /// ```
     fn scalar_if_then_else(&self, then_value: Scalar, else_value: &FheUint<Id>) -> Self::Output {
         let ct_condition = self;
+        global_state::check_tags([&ct_condition.tag, &else_value.tag]);
         global_state::with_internal_keys(|sks| match sks {
             InternalServerKey::Cpu(cpu_sks) => {
                 let inner = cpu_sks.pbs_key().if_then_else_parallelized(
@@ -272,6 +324,17 @@ where
             InternalServerKey::Cuda(_) => {
                 panic!("Cuda does not support if_then_else with clear input")
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let else_value = else_value.ciphertext.on_simulation(sim_key);
+                let then_value =
+                    SimulatedInteger::from_scalar(then_value, else_value.num_bits(), false);
+                let inner = if ct_condition.ciphertext.on_simulation(sim_key) {
+                    then_value
+                } else {
+                    else_value
+                };
+                FheUint::new(inner, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -306,6 +369,7 @@ where
     /// ```
     fn scalar_if_then_else(&self, then_value: &FheInt<Id>, else_value: Scalar) -> Self::Output {
         let ct_condition = self;
+        global_state::check_tags([&ct_condition.tag, &then_value.tag]);
         global_state::with_internal_keys(|sks| match sks {
             InternalServerKey::Cpu(cpu_sks) => {
                 let inner = cpu_sks.pbs_key().if_then_else_parallelized(
@@ -319,6 +383,17 @@ where
             InternalServerKey::Cuda(_) => {
                 panic!("Cuda does not support if_then_else with clear input")
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let then_value = then_value.ciphertext.on_simulation(sim_key);
+                let else_value =
+                    SimulatedInteger::from_scalar(else_value, then_value.num_bits(), true);
+                let inner = if ct_condition.ciphertext.on_simulation(sim_key) {
+                    then_value
+                } else {
+                    else_value
+                };
+                FheInt::new(inner, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -353,6 +428,7 @@ where
     /// ```
     fn scalar_if_then_else(&self, then_value: Scalar, else_value: &FheInt<Id>) -> Self::Output {
         let ct_condition = self;
+        global_state::check_tags([&ct_condition.tag, &else_value.tag]);
         global_state::with_internal_keys(|sks| match sks {
             InternalServerKey::Cpu(cpu_sks) => {
                 let inner = cpu_sks.pbs_key().if_then_else_parallelized(
@@ -366,6 +442,17 @@ where
             InternalServerKey::Cuda(_) => {
                 panic!("Cuda does not support if_then_else with clear input")
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let else_value = else_value.ciphertext.on_simulation(sim_key);
+                let then_value =
+                    SimulatedInteger::from_scalar(then_value, else_value.num_bits(), true);
+                let inner = if ct_condition.ciphertext.on_simulation(sim_key) {
+                    then_value
+                } else {
+                    else_value
+                };
+                FheInt::new(inner, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -375,6 +462,7 @@ impl ScalarIfThenElse<&Self, &Self> for FheBool {
 
     fn scalar_if_then_else(&self, ct_then: &Self, ct_else: &Self) -> Self::Output {
         let ct_condition = self;
+        global_state::check_tags([&ct_condition.tag, &ct_then.tag, &ct_else.tag]);
         let (ciphertext, tag) = global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(key) => {
                 let new_ct = key.pbs_key().if_then_else_parallelized(
@@ -395,6 +483,14 @@ impl ScalarIfThenElse<&Self, &Self> for FheBool {
                 let boolean_inner = CudaBooleanBlock(inner);
                 (InnerBoolean::Cuda(boolean_inner), cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner = if ct_condition.ciphertext.on_simulation(sim_key) {
+                    ct_then.ciphertext.on_simulation(sim_key)
+                } else {
+                    ct_else.ciphertext.on_simulation(sim_key)
+                };
+                (InnerBoolean::Simulation(inner), sim_key.tag.clone())
+            }
         });
         Self::new(ciphertext, tag)
     }
@@ -412,6 +508,7 @@ where
     /// - if `self` is false, the output will have the value of `ct_else`
     fn if_then_else(&self, ct_then: &FheUint<Id>, ct_else: &FheUint<Id>) -> FheUint<Id> {
         let ct_condition = self;
+        global_state::check_tags([&ct_condition.tag, &ct_then.tag, &ct_else.tag]);
         global_state::with_internal_keys(|sks| match sks {
             InternalServerKey::Cpu(cpu_sks) => {
                 let inner = cpu_sks.pbs_key().if_then_else_parallelized(
@@ -432,6 +529,14 @@ where
 
                 FheUint::new(inner, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner = if ct_condition.ciphertext.on_simulation(sim_key) {
+                    ct_then.ciphertext.on_simulation(sim_key)
+                } else {
+                    ct_else.ciphertext.on_simulation(sim_key)
+                };
+                FheUint::new(inner, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -445,6 +550,7 @@ impl<Id: FheIntId> IfThenElse<FheInt<Id>> for FheBool {
     /// - if `self` is false, the output will have the value of `ct_else`
     fn if_then_else(&self, ct_then: &FheInt<Id>, ct_else: &FheInt<Id>) -> FheInt<Id> {
         let ct_condition = self;
+        global_state::check_tags([&ct_condition.tag, &ct_then.tag, &ct_else.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(key) => {
                 let new_ct = key.pbs_key().if_then_else_parallelized(
@@ -465,6 +571,14 @@ impl<Id: FheIntId> IfThenElse<FheInt<Id>> for FheBool {
 
                 FheInt::new(inner, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner = if ct_condition.ciphertext.on_simulation(sim_key) {
+                    ct_then.ciphertext.on_simulation(sim_key)
+                } else {
+                    ct_else.ciphertext.on_simulation(sim_key)
+                };
+                FheInt::new(inner, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -472,6 +586,7 @@ impl<Id: FheIntId> IfThenElse<FheInt<Id>> for FheBool {
 impl IfThenElse<Self> for FheBool {
     fn if_then_else(&self, ct_then: &Self, ct_else: &Self) -> Self {
         let ct_condition = self;
+        global_state::check_tags([&ct_condition.tag, &ct_then.tag, &ct_else.tag]);
         let (ciphertext, tag) = global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(key) => {
                 let new_ct = key.pbs_key().if_then_else_parallelized(
@@ -492,6 +607,14 @@ impl IfThenElse<Self> for FheBool {
                 let boolean_inner = CudaBooleanBlock(inner);
                 (InnerBoolean::Cuda(boolean_inner), cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner = if ct_condition.ciphertext.on_simulation(sim_key) {
+                    ct_then.ciphertext.on_simulation(sim_key)
+                } else {
+                    ct_else.ciphertext.on_simulation(sim_key)
+                };
+                (InnerBoolean::Simulation(inner), sim_key.tag.clone())
+            }
         });
         Self::new(ciphertext, tag)
     }
@@ -531,6 +654,7 @@ where
     /// assert!(!decrypted);
     /// ```
     fn eq(&self, other: B) -> Self {
+        global_state::check_tags([&self.tag, &other.borrow().tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(key) => {
                 let inner = key.pbs_key().key.equal(
@@ -550,6 +674,11 @@ where
                 let ciphertext = InnerBoolean::Cuda(inner);
                 Self::new(ciphertext, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner = self.ciphertext.on_simulation(sim_key)
+                    == other.borrow().ciphertext.on_simulation(sim_key);
+                Self::new(InnerBoolean::Simulation(inner), sim_key.tag.clone())
+            }
         })
     }
 
@@ -573,6 +702,7 @@ where
     /// assert_eq!(decrypted, true != false);
     /// ```
     fn ne(&self, other: B) -> Self {
+        global_state::check_tags([&self.tag, &other.borrow().tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(key) => {
                 let inner = key.pbs_key().key.not_equal(
@@ -592,6 +722,11 @@ where
                 let ciphertext = InnerBoolean::Cuda(inner);
                 Self::new(ciphertext, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner = self.ciphertext.on_simulation(sim_key)
+                    != other.borrow().ciphertext.on_simulation(sim_key);
+                Self::new(InnerBoolean::Simulation(inner), sim_key.tag.clone())
+            }
         })
     }
 }
@@ -616,6 +751,7 @@ impl FheEq<bool> for FheBool {
     /// assert!(!decrypted);
     /// ```
     fn eq(&self, other: bool) -> FheBool {
+        global_state::check_tags([&self.tag]);
         let (ciphertext, tag) = global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(key) => {
                 let inner = key
@@ -636,6 +772,10 @@ impl FheEq<bool> for FheBool {
                 );
                 (InnerBoolean::Cuda(inner), cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner = self.ciphertext.on_simulation(sim_key) == other;
+                (InnerBoolean::Simulation(inner), sim_key.tag.clone())
+            }
         });
         Self::new(ciphertext, tag)
     }
@@ -659,6 +799,7 @@ impl FheEq<bool> for FheBool {
     /// assert_eq!(decrypted, true != false);
     /// ```
     fn ne(&self, other: bool) -> FheBool {
+        global_state::check_tags([&self.tag]);
         let (ciphertext, tag) = global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(key) => {
                 let inner = key
@@ -679,6 +820,10 @@ impl FheEq<bool> for FheBool {
                 );
                 (InnerBoolean::Cuda(inner), cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner = self.ciphertext.on_simulation(sim_key) != other;
+                (InnerBoolean::Simulation(inner), sim_key.tag.clone())
+            }
         });
         Self::new(ciphertext, tag)
     }
@@ -737,6 +882,7 @@ where
     /// assert!(result);
     /// ```
     fn bitand(self, rhs: B) -> Self::Output {
+        global_state::check_tags([&self.tag, &rhs.borrow().tag]);
         let (ciphertext, tag) = global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(key) => {
                 let inner_ct = key
@@ -759,6 +905,11 @@ where
                     cuda_key.tag.clone(),
                 )
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner = self.ciphertext.on_simulation(sim_key)
+                    & rhs.borrow().ciphertext.on_simulation(sim_key);
+                (InnerBoolean::Simulation(inner), sim_key.tag.clone())
+            }
         });
         FheBool::new(ciphertext, tag)
     }
@@ -818,6 +969,7 @@ where
     /// assert_eq!(result, true | false);
     /// ```
     fn bitor(self, rhs: B) -> Self::Output {
+        global_state::check_tags([&self.tag, &rhs.borrow().tag]);
         let (ciphertext, tag) = global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(key) => {
                 let inner_ct = key.pbs_key().key.bitor(
@@ -843,6 +995,11 @@ where
                     cuda_key.tag.clone(),
                 )
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner = self.ciphertext.on_simulation(sim_key)
+                    | rhs.borrow().ciphertext.on_simulation(sim_key);
+                (InnerBoolean::Simulation(inner), sim_key.tag.clone())
+            }
         });
         FheBool::new(ciphertext, tag)
     }
@@ -902,6 +1059,7 @@ where
     /// assert!(!result);
     /// ```
     fn bitxor(self, rhs: B) -> Self::Output {
+        global_state::check_tags([&self.tag, &rhs.borrow().tag]);
         let (ciphertext, tag) = global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(key) => {
                 let inner_ct = key.pbs_key().key.bitxor(
@@ -927,6 +1085,11 @@ where
                     cuda_key.tag.clone(),
                 )
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner = self.ciphertext.on_simulation(sim_key)
+                    ^ rhs.borrow().ciphertext.on_simulation(sim_key);
+                (InnerBoolean::Simulation(inner), sim_key.tag.clone())
+            }
         });
         FheBool::new(ciphertext, tag)
     }
@@ -978,6 +1141,7 @@ impl BitAnd<bool> for &FheBool {
     /// assert_eq!(decrypted, true & false);
     /// ```
     fn bitand(self, rhs: bool) -> Self::Output {
+        global_state::check_tags([&self.tag]);
         let (ciphertext, tag) = global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(key) => {
                 let inner_ct = key
@@ -1003,6 +1167,10 @@ impl BitAnd<bool> for &FheBool {
                     cuda_key.tag.clone(),
                 )
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner = self.ciphertext.on_simulation(sim_key) & rhs;
+                (InnerBoolean::Simulation(inner), sim_key.tag.clone())
+            }
         });
         FheBool::new(ciphertext, tag)
     }
@@ -1054,6 +1222,7 @@ impl BitOr<bool> for &FheBool {
     /// assert_eq!(decrypted, true | false);
     /// ```
     fn bitor(self, rhs: bool) -> Self::Output {
+        global_state::check_tags([&self.tag]);
         let (ciphertext, tag) = global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(key) => {
                 let inner_ct = key
@@ -1079,6 +1248,10 @@ impl BitOr<bool> for &FheBool {
                     cuda_key.tag.clone(),
                 )
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner = self.ciphertext.on_simulation(sim_key) | rhs;
+                (InnerBoolean::Simulation(inner), sim_key.tag.clone())
+            }
         });
         FheBool::new(ciphertext, tag)
     }
@@ -1130,6 +1303,7 @@ impl BitXor<bool> for &FheBool {
     /// assert_eq!(decrypted, true ^ false);
     /// ```
     fn bitxor(self, rhs: bool) -> Self::Output {
+        global_state::check_tags([&self.tag]);
         let (ciphertext, tag) = global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(key) => {
                 let inner_ct = key
@@ -1155,6 +1329,10 @@ impl BitXor<bool> for &FheBool {
                     cuda_key.tag.clone(),
                 )
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner = self.ciphertext.on_simulation(sim_key) ^ rhs;
+                (InnerBoolean::Simulation(inner), sim_key.tag.clone())
+            }
         });
         FheBool::new(ciphertext, tag)
     }
@@ -1338,6 +1516,7 @@ where
     /// ```
     fn bitand_assign(&mut self, rhs: B) {
         let rhs = rhs.borrow();
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(key) => {
                 key.pbs_key().key.bitand_assign(
@@ -1353,6 +1532,11 @@ where
                     streams,
                 );
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner =
+                    self.ciphertext.on_simulation(sim_key) & rhs.ciphertext.on_simulation(sim_key);
+                self.ciphertext = InnerBoolean::Simulation(inner);
+            }
         });
     }
 }
@@ -1381,6 +1565,7 @@ where
     /// ```
     fn bitor_assign(&mut self, rhs: B) {
         let rhs = rhs.borrow();
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(key) => {
                 key.pbs_key().key.bitor_assign(
@@ -1396,6 +1581,11 @@ where
                     streams,
                 );
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner =
+                    self.ciphertext.on_simulation(sim_key) | rhs.ciphertext.on_simulation(sim_key);
+                self.ciphertext = InnerBoolean::Simulation(inner);
+            }
         });
     }
 }
@@ -1424,6 +1614,7 @@ where
     /// ```
     fn bitxor_assign(&mut self, rhs: B) {
         let rhs = rhs.borrow();
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(key) => {
                 key.pbs_key().key.bitxor_assign(
@@ -1439,6 +1630,11 @@ where
                     streams,
                 );
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner =
+                    self.ciphertext.on_simulation(sim_key) ^ rhs.ciphertext.on_simulation(sim_key);
+                self.ciphertext = InnerBoolean::Simulation(inner);
+            }
         });
     }
 }
@@ -1462,6 +1658,7 @@ impl BitAndAssign<bool> for FheBool {
     /// assert_eq!(result, true & false);
     /// ```
     fn bitand_assign(&mut self, rhs: bool) {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(key) => {
                 key.pbs_key()
@@ -1476,6 +1673,10 @@ impl BitAndAssign<bool> for FheBool {
                     streams,
                 );
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner = self.ciphertext.on_simulation(sim_key) & rhs;
+                self.ciphertext = InnerBoolean::Simulation(inner);
+            }
         });
     }
 }
@@ -1499,6 +1700,7 @@ impl BitOrAssign<bool> for FheBool {
     /// assert_eq!(result, true | false);
     /// ```
     fn bitor_assign(&mut self, rhs: bool) {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(key) => {
                 key.pbs_key()
@@ -1513,6 +1715,10 @@ impl BitOrAssign<bool> for FheBool {
                     streams,
                 );
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner = self.ciphertext.on_simulation(sim_key) | rhs;
+                self.ciphertext = InnerBoolean::Simulation(inner);
+            }
         });
     }
 }
@@ -1536,6 +1742,7 @@ impl BitXorAssign<bool> for FheBool {
     /// assert_eq!(result, true ^ false);
     /// ```
     fn bitxor_assign(&mut self, rhs: bool) {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(key) => {
                 key.pbs_key()
@@ -1550,6 +1757,10 @@ impl BitXorAssign<bool> for FheBool {
                     streams,
                 );
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner = self.ciphertext.on_simulation(sim_key) ^ rhs;
+                self.ciphertext = InnerBoolean::Simulation(inner);
+            }
         });
     }
 }
@@ -1600,6 +1811,7 @@ impl std::ops::Not for &FheBool {
     /// assert!(!result);
     /// ```
     fn not(self) -> Self::Output {
+        global_state::check_tags([&self.tag]);
         let (ciphertext, tag) = global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(key) => {
                 let inner = key.pbs_key().boolean_bitnot(&self.ciphertext.on_cpu());
@@ -1619,6 +1831,10 @@ impl std::ops::Not for &FheBool {
                     cuda_key.tag.clone(),
                 )
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner = !self.ciphertext.on_simulation(sim_key);
+                (InnerBoolean::Simulation(inner), sim_key.tag.clone())
+            }
         });
         FheBool::new(ciphertext, tag)
     }
@@ -2,7 +2,7 @@ use crate::backward_compatibility::booleans::{
     CompressedFheBoolVersions, InnerCompressedFheBoolVersions,
 };
 use crate::conformance::ParameterSetConformant;
-use crate::high_level_api::global_state::with_cpu_internal_keys;
+use crate::high_level_api::global_state::{check_tags, with_cpu_internal_keys};
 use crate::high_level_api::traits::Tagged;
 use crate::integer::BooleanBlock;
 use crate::named::Named;
@@ -112,6 +112,7 @@ impl Named for CompressedFheBool {
 
 impl FheBool {
     pub fn compress(&self) -> CompressedFheBool {
+        check_tags([&self.tag]);
         with_cpu_internal_keys(|sk| {
             let inner = InnerCompressedFheBool::ModulusSwitched(
                 sk.pbs_key()
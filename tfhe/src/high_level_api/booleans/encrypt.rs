@@ -76,6 +76,9 @@ impl FheTryEncrypt<bool, PublicKey> for FheBool {
 impl FheDecrypt<bool> for FheBool {
     /// Decrypts the value
     fn decrypt(&self, key: &ClientKey) -> bool {
+        if let InnerBoolean::Simulation(value) = &self.ciphertext {
+            return *value;
+        }
         key.key.key.decrypt_bool(&self.ciphertext.on_cpu())
     }
 }
@@ -101,6 +104,9 @@ impl FheTryTrivialEncrypt<bool> for FheBool {
                 ));
                 (ct, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                (InnerBoolean::Simulation(value), sim_key.tag.clone())
+            }
         });
         Ok(Self::new(ciphertext, tag))
     }
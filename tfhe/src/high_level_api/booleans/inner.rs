@@ -1,15 +1,18 @@
 use crate::backward_compatibility::booleans::InnerBooleanVersionedOwned;
 #[cfg(feature = "gpu")]
 use crate::core_crypto::gpu::CudaStreams;
-use crate::high_level_api::details::MaybeCloned;
+use crate::high_level_api::details::{cpu_data_versioned_schema, MaybeCloned};
 use crate::high_level_api::global_state;
 #[cfg(feature = "gpu")]
 use crate::high_level_api::global_state::{
     with_thread_local_cuda_streams, with_thread_local_cuda_streams_for_gpu_indexes,
 };
+use crate::high_level_api::keys::SimulationServerKey;
+use crate::high_level_api::lazy::LazyValue;
 use crate::integer::BooleanBlock;
 use crate::Device;
 use serde::{Deserializer, Serializer};
+use tfhe_versionable::schema::{DataSchema, SchemaRegistry};
 use tfhe_versionable::{Unversionize, UnversionizeError, Versionize, VersionizeOwned};
 
 /// Enum that manages the current inner representation of a boolean.
@@ -17,6 +20,10 @@ pub(in crate::high_level_api) enum InnerBoolean {
     Cpu(BooleanBlock),
     #[cfg(feature = "gpu")]
     Cuda(crate::integer::gpu::ciphertext::boolean_value::CudaBooleanBlock),
+    /// Result of a comparison recorded with lazy evaluation, computed on the CPU on demand
+    Lazy(LazyValue),
+    /// Clear value of the simulation backend
+    Simulation(bool),
 }
 
 impl Clone for InnerBoolean {
@@ -27,6 +34,8 @@ impl Clone for InnerBoolean {
             Self::Cuda(inner) => {
                 with_thread_local_cuda_streams(|streams| Self::Cuda(inner.duplicate(streams)))
             }
+            Self::Lazy(value) => Self::Lazy(value.clone()),
+            Self::Simulation(value) => Self::Simulation(*value),
         }
     }
 }
@@ -39,6 +48,7 @@ impl serde::Serialize for InnerBoolean {
             Self::Cpu(cpu_ct) => cpu_ct.serialize(serializer),
             #[cfg(feature = "gpu")]
             Self::Cuda(_) => self.on_cpu().serialize(serializer),
+            Self::Lazy(_) | Self::Simulation(_) => self.on_cpu().serialize(serializer),
         }
     }
 }
@@ -79,6 +89,15 @@ impl VersionizeOwned for InnerBoolean {
             cpu_data.into_owned().versionize_owned(),
         ))
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        cpu_data_versioned_schema::<
+            Self,
+            Self::VersionedOwned,
+            InnerBooleanVersionOwned,
+            crate::integer::BooleanBlock,
+        >(registry)
+    }
 }
 
 impl Unversionize for InnerBoolean {
@@ -112,6 +131,8 @@ impl InnerBoolean {
             Self::Cpu(_) => Device::Cpu,
             #[cfg(feature = "gpu")]
             Self::Cuda(_) => Device::CudaGpu,
+            Self::Lazy(_) => Device::Cpu,
+            Self::Simulation(_) => Device::Simulation,
         }
     }
 
@@ -126,6 +147,14 @@ impl InnerBoolean {
                     MaybeCloned::Cloned(ct.to_boolean_block(streams))
                 })
             }
+            Self::Lazy(value) => MaybeCloned::Cloned(BooleanBlock::new_unchecked(
+                value.materialize().blocks[0].clone(),
+            )),
+            Self::Simulation(value) => {
+                MaybeCloned::Cloned(global_state::with_simulation_internal_keys(|key| {
+                    key.client_key.key.key.encrypt_bool(*value)
+                }))
+            }
         }
     }
 
@@ -146,6 +175,16 @@ impl InnerBoolean {
                     );
                 MaybeCloned::Cloned(cuda_ct)
             }),
+            Self::Lazy(_) | Self::Simulation(_) => {
+                let ct = self.on_cpu();
+                let ct_as_radix = crate::integer::RadixCiphertext::from(vec![ct.0.clone()]);
+                let cuda_ct =
+                    crate::integer::gpu::ciphertext::CudaUnsignedRadixCiphertext::from_radix_ciphertext(
+                        &ct_as_radix,
+                        streams,
+                    );
+                MaybeCloned::Cloned(cuda_ct)
+            }
             #[cfg(feature = "gpu")]
             Self::Cuda(ct) => {
                 if ct.gpu_indexes() == streams.gpu_indexes() {
@@ -160,7 +199,10 @@ impl InnerBoolean {
     pub(crate) fn as_cpu_mut(&mut self) -> &mut BooleanBlock {
         match self {
             Self::Cpu(block) => block,
-            #[cfg(feature = "gpu")]
+            Self::Lazy(_) => {
+                self.materialize();
+                self.as_cpu_mut()
+            }
             _ => {
                 self.move_to_device(Device::Cpu);
                 self.as_cpu_mut()
@@ -193,6 +235,14 @@ impl InnerBoolean {
                 }
                 &mut cuda_ct.0
             }
+            Self::Lazy(_) => {
+                self.materialize();
+                self.as_gpu_mut(streams)
+            }
+            Self::Simulation(_) => {
+                self.move_to_device(Device::Cpu);
+                self.as_gpu_mut(streams)
+            }
         }
     }
 
@@ -208,15 +258,37 @@ impl InnerBoolean {
                 )
             }),
             Self::Cuda(ct) => ct.move_to_stream(streams),
+            Self::Lazy(_) | Self::Simulation(_) => {
+                crate::integer::gpu::ciphertext::boolean_value::CudaBooleanBlock::from_boolean_block(
+                    &self.on_cpu(),
+                    streams,
+                )
+            }
         }
     }
 
     #[allow(clippy::needless_pass_by_ref_mut)]
     pub(crate) fn move_to_device(&mut self, device: Device) {
         match (&self, device) {
-            (Self::Cpu(_), Device::Cpu) => {
+            (Self::Cpu(_), Device::Cpu) | (Self::Simulation(_), Device::Simulation) => {
                 // Nothing to do, we already are on the correct device
             }
+            (Self::Lazy(_), Device::Cpu) => {
+                // Nothing to do, lazy values are evaluated on the CPU
+            }
+            (Self::Lazy(_), _) => {
+                self.materialize();
+                self.move_to_device(device);
+            }
+            (Self::Simulation(_), _) => {
+                *self = Self::Cpu(self.on_cpu().into_owned());
+                self.move_to_device(device);
+            }
+            (_, Device::Simulation) => {
+                let value =
+                    global_state::with_simulation_internal_keys(|key| self.on_simulation(key));
+                *self = Self::Simulation(value);
+            }
             #[cfg(feature = "gpu")]
             (Self::Cuda(cuda_ct), Device::CudaGpu) => {
                 // We are on a GPU, but it may not be the correct one
@@ -252,6 +324,23 @@ impl InnerBoolean {
         }
     }
 
+    /// Returns the clear value of the simulation backend, decrypting the ciphertext with the
+    /// client key of the simulation key if needed
+    pub(crate) fn on_simulation(&self, key: &SimulationServerKey) -> bool {
+        match self {
+            Self::Simulation(value) => *value,
+            _ => key.client_key.key.key.decrypt_bool(&self.on_cpu()),
+        }
+    }
+
+    /// Evaluates the value if it is lazy, the result stays on the CPU
+    pub(crate) fn materialize(&mut self) {
+        if let Self::Lazy(value) = self {
+            let block = value.materialize().blocks[0].clone();
+            *self = Self::Cpu(BooleanBlock::new_unchecked(block));
+        }
+    }
+
     #[inline]
     pub(crate) fn move_to_device_of_server_key_if_set(&mut self) {
         if let Some(device) = global_state::device_of_internal_keys() {
@@ -3,6 +3,7 @@ use crate::high_level_api::global_state;
 #[cfg(feature = "gpu")]
 use crate::high_level_api::global_state::with_thread_local_cuda_streams;
 use crate::high_level_api::keys::InternalServerKey;
+use crate::high_level_api::simulation::SimulatedInteger;
 #[cfg(feature = "gpu")]
 use crate::integer::gpu::ciphertext::boolean_value::CudaBooleanBlock;
 #[cfg(feature = "gpu")]
@@ -51,6 +52,10 @@ impl FheBool {
                     cuda_key.tag.clone(),
                 )
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let value = SimulatedInteger::pseudo_random(seed, 1, 1, false).to_bool();
+                (InnerBoolean::Simulation(value), sim_key.tag.clone())
+            }
         });
         Self::new(ciphertext, tag)
     }
@@ -2,16 +2,17 @@ use super::base::FheBool;
 use crate::backward_compatibility::booleans::{
     InnerSquashedNoiseBooleanVersionedOwned, SquashedNoiseFheBoolVersions,
 };
-use crate::high_level_api::details::MaybeCloned;
+use crate::high_level_api::details::{cpu_data_versioned_schema, MaybeCloned};
 use crate::high_level_api::errors::UninitializedNoiseSquashing;
 use crate::high_level_api::global_state;
-use crate::high_level_api::global_state::with_internal_keys;
+use crate::high_level_api::global_state::{try_check_tags, with_internal_keys};
 use crate::high_level_api::keys::InternalServerKey;
 use crate::high_level_api::traits::{FheDecrypt, SquashNoise};
 use crate::integer::ciphertext::SquashedNoiseBooleanBlock;
 use crate::named::Named;
 use crate::{ClientKey, Device, Tag};
 use serde::{Deserializer, Serializer};
+use tfhe_versionable::schema::{DataSchema, SchemaRegistry};
 use tfhe_versionable::{Unversionize, UnversionizeError, Versionize, VersionizeOwned};
 
 /// Enum that manages the current inner representation of a boolean.
@@ -75,6 +76,15 @@ impl VersionizeOwned for InnerSquashedNoiseBoolean {
             cpu_data.into_owned().versionize_owned(),
         ))
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        cpu_data_versioned_schema::<
+            Self,
+            Self::VersionedOwned,
+            InnerSquashedNoiseBooleanVersionOwned,
+            crate::integer::ciphertext::SquashedNoiseBooleanBlock,
+        >(registry)
+    }
 }
 
 impl Unversionize for InnerSquashedNoiseBoolean {
@@ -106,6 +116,9 @@ impl InnerSquashedNoiseBoolean {
             (Self::Cpu(_), Device::Cpu) => {
                 // Nothing to do, we already are on the correct device
             }
+            (Self::Cpu(_), Device::Simulation) => {
+                // Squashed noise ciphertexts are not simulated, they stay on the CPU
+            }
             #[cfg(feature = "gpu")]
             _ => panic!("Cuda devices do not support noise squashing yet"),
         }
@@ -158,6 +171,7 @@ impl SquashNoise for FheBool {
     type Output = SquashedNoiseFheBool;
 
     fn squash_noise(&self) -> crate::Result<Self::Output> {
+        try_check_tags([&self.tag])?;
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(server_key) => {
                 let noise_squashing_key = server_key
@@ -180,6 +194,9 @@ impl SquashNoise for FheBool {
             InternalServerKey::Cuda(_) => Err(crate::error!(
                 "Cuda devices do not support noise squashing yet"
             )),
+            InternalServerKey::Simulation(_) => Err(crate::error!(
+                "The simulation backend does not support noise squashing"
+            )),
         })
     }
 }
@@ -0,0 +1,55 @@
+use super::{BinaryGate, Circuit, Gate};
+use crate::high_level_api::context::FheContext;
+use crate::prelude::FheTrivialEncrypt;
+use crate::FheBool;
+
+impl Circuit {
+    /// Evaluates the circuit on encrypted booleans, using the server key set for the current
+    /// thread
+    ///
+    /// The gates of each level are evaluated in parallel, with the key propagated to the rayon
+    /// threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the server key is not set
+    #[track_caller]
+    pub fn evaluate(&self, inputs: &[Vec<FheBool>]) -> crate::Result<Vec<Vec<FheBool>>> {
+        let mut wires = self.input_wires(inputs)?;
+        let context = FheContext::from_current_thread();
+
+        for (level_gates, dead_wires) in self.levels.iter().zip(&self.dead_wires) {
+            let results = context.par_map(level_gates, |&index| {
+                evaluate_gate(&self.gates[index], &wires)
+            });
+            for (&index, result) in level_gates.iter().zip(results) {
+                wires[self.gates[index].output()] = Some(result);
+            }
+            for &wire in dead_wires {
+                wires[wire] = None;
+            }
+        }
+
+        Ok(self.output_values(&mut wires))
+    }
+}
+
+fn evaluate_gate(gate: &Gate, wires: &[Option<FheBool>]) -> FheBool {
+    let wire = |index: usize| wires[index].as_ref().unwrap();
+    match *gate {
+        Gate::Binary { op, lhs, rhs, .. } => {
+            let (lhs, rhs) = (wire(lhs), wire(rhs));
+            match op {
+                BinaryGate::And => lhs & rhs,
+                BinaryGate::Or => lhs | rhs,
+                BinaryGate::Xor => lhs ^ rhs,
+                BinaryGate::Nand => !(lhs & rhs),
+                BinaryGate::Nor => !(lhs | rhs),
+                BinaryGate::Xnor => !(lhs ^ rhs),
+            }
+        }
+        Gate::Inv { input, .. } => !wire(input),
+        Gate::Copy { input, .. } => wire(input).clone(),
+        Gate::Constant { value, .. } => FheBool::encrypt_trivial(value),
+    }
+}
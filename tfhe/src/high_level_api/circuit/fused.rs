@@ -0,0 +1,489 @@
+use super::{Circuit, Gate};
+use crate::shortint::ciphertext::NoiseLevel;
+use crate::shortint::{Ciphertext, ServerKey};
+use rayon::prelude::*;
+
+/// Maximum number of ciphertexts combined into a single lookup table
+const MAX_LUT_INPUTS: usize = 4;
+
+/// A [Circuit] compiled to lookup tables over shortint ciphertexts, see [Circuit::fuse]
+#[derive(Clone, Debug)]
+pub struct FusedCircuit {
+    input_sizes: Vec<usize>,
+    output_sizes: Vec<usize>,
+    // Maximum value of the weighted sum of the inputs of a lookup table
+    max_packed_value: u64,
+    // The first nodes are the input wires
+    nodes: Vec<Node>,
+    // Indices of the lookup table nodes of each level
+    levels: Vec<Vec<usize>>,
+    // Nodes that are not read after each level, and are not outputs
+    dead_nodes: Vec<Vec<usize>>,
+    outputs: Vec<usize>,
+}
+
+#[derive(Clone, Debug)]
+enum Node {
+    Input,
+    Constant(bool),
+    /// Lookup table applied to the sum of the inputs multiplied by their weight
+    Lut {
+        inputs: Vec<usize>,
+        weights: Vec<u8>,
+        // Indexed by the weighted sum of the inputs
+        table: Vec<bool>,
+    },
+}
+
+/// Boolean function of at most [MAX_LUT_INPUTS] nodes, which has not been computed
+#[derive(Clone, Debug)]
+struct Expression {
+    // Sorted and without duplicates
+    support: Vec<usize>,
+    // Bit `i` is the value of the function when the value of `support[j]` is bit `j` of `i`
+    table: u16,
+}
+
+impl Expression {
+    fn node(node: usize) -> Self {
+        Self {
+            support: vec![node],
+            table: 0b10,
+        }
+    }
+
+    fn constant(value: bool) -> Self {
+        Self {
+            support: Vec::new(),
+            table: u16::from(value),
+        }
+    }
+
+    fn value(&self, assignment: usize) -> bool {
+        (self.table >> assignment) & 1 == 1
+    }
+
+    fn not(&self) -> Self {
+        let mask = (1u32 << (1 << self.support.len())) - 1;
+        Self {
+            support: self.support.clone(),
+            table: !self.table & mask as u16,
+        }
+    }
+
+    fn as_node(&self) -> Option<usize> {
+        match self.support[..] {
+            [node] if self.table == 0b10 => Some(node),
+            _ => None,
+        }
+    }
+
+    /// Returns `f(lhs, rhs)`, unless it depends on more than [MAX_LUT_INPUTS] nodes
+    fn combine(lhs: &Self, rhs: &Self, f: impl Fn(bool, bool) -> bool) -> Option<Self> {
+        let mut support = lhs.support.clone();
+        support.extend_from_slice(&rhs.support);
+        support.sort_unstable();
+        support.dedup();
+        if support.len() > MAX_LUT_INPUTS {
+            return None;
+        }
+
+        let project = |operand: &Self, assignment: usize| {
+            operand
+                .support
+                .iter()
+                .enumerate()
+                .map(|(i, node)| {
+                    let position = support.binary_search(node).unwrap();
+                    ((assignment >> position) & 1) << i
+                })
+                .sum::<usize>()
+        };
+        let table = (0..1 << support.len())
+            .filter(|&assignment| {
+                f(
+                    lhs.value(project(lhs, assignment)),
+                    rhs.value(project(rhs, assignment)),
+                )
+            })
+            .fold(0u16, |table, assignment| table | (1 << assignment));
+
+        Some(Self { support, table }.without_unused_nodes())
+    }
+
+    /// Removes the nodes the function does not depend on from the support
+    fn without_unused_nodes(mut self) -> Self {
+        let mut position = 0;
+        while position < self.support.len() {
+            let num_assignments = 1usize << self.support.len();
+            let is_used = (0..num_assignments).any(|assignment| {
+                self.value(assignment) != self.value(assignment ^ (1 << position))
+            });
+            if is_used {
+                position += 1;
+                continue;
+            }
+
+            // Keep the assignments where the unused node is 0, and close the gap
+            let low_mask = (1usize << position) - 1;
+            self.table = (0..num_assignments / 2)
+                .filter(|&assignment| {
+                    let expanded = (assignment & low_mask) | ((assignment & !low_mask) << 1);
+                    self.value(expanded)
+                })
+                .fold(0u16, |table, assignment| table | (1 << assignment));
+            self.support.remove(position);
+        }
+        self
+    }
+
+    /// Finds weights such that the function only depends on the weighted sum of its inputs, and
+    /// that sum does not exceed `max_packed_value`
+    ///
+    /// Returns the weights and the value of the function for each value of the sum.
+    fn lut(&self, max_packed_value: u64) -> Option<(Vec<u8>, Vec<bool>)> {
+        let num_inputs = self.support.len();
+
+        // Powers of two always work, other weights are only tried if they do not fit
+        if (1u64 << num_inputs) - 1 <= max_packed_value {
+            let weights = (0..num_inputs).map(|i| 1u8 << i).collect::<Vec<_>>();
+            let table = self.packed_table(&weights).unwrap();
+            return Some((weights, table));
+        }
+
+        let mut weights = Vec::with_capacity(num_inputs);
+        self.search_weights(&mut weights, max_packed_value)
+    }
+
+    fn search_weights(
+        &self,
+        weights: &mut Vec<u8>,
+        remaining: u64,
+    ) -> Option<(Vec<u8>, Vec<bool>)> {
+        let num_missing = self.support.len() - weights.len();
+        if num_missing == 0 {
+            return self
+                .packed_table(weights)
+                .map(|table| (weights.clone(), table));
+        }
+
+        // Each of the missing weights is at least 1
+        let max_weight = (remaining + 1).saturating_sub(num_missing as u64);
+        for weight in 1..=max_weight.min(u64::from(u8::MAX)) {
+            weights.push(weight as u8);
+            let found = self.search_weights(weights, remaining - weight);
+            weights.pop();
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+
+    /// Returns the value of the function for each value of the weighted sum of its inputs, if
+    /// the weighted sum determines it
+    fn packed_table(&self, weights: &[u8]) -> Option<Vec<bool>> {
+        let max_packed = weights.iter().map(|&w| usize::from(w)).sum::<usize>();
+        let mut table = vec![None; max_packed + 1];
+        for assignment in 0..1usize << self.support.len() {
+            let packed = weights
+                .iter()
+                .enumerate()
+                .map(|(i, &w)| ((assignment >> i) & 1) * usize::from(w))
+                .sum::<usize>();
+            let value = self.value(assignment);
+            match table[packed] {
+                None => table[packed] = Some(value),
+                Some(previous) if previous != value => return None,
+                Some(_) => {}
+            }
+        }
+        Some(table.into_iter().map(Option::unwrap_or_default).collect())
+    }
+}
+
+struct Fuser {
+    nodes: Vec<Node>,
+    max_packed_value: u64,
+}
+
+impl Fuser {
+    fn materialize(&mut self, expression: &Expression) -> usize {
+        if let Some(node) = expression.as_node() {
+            return node;
+        }
+
+        let node = if expression.support.is_empty() {
+            Node::Constant(expression.value(0))
+        } else {
+            // Expressions are only kept when they fit in a lookup table
+            let (weights, table) = expression.lut(self.max_packed_value).unwrap();
+            Node::Lut {
+                inputs: expression.support.clone(),
+                weights,
+                table,
+            }
+        };
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    fn fits(&self, expression: &Expression) -> bool {
+        expression.lut(self.max_packed_value).is_some()
+    }
+}
+
+impl Circuit {
+    /// Compiles the circuit to lookup tables evaluated with the given shortint key
+    ///
+    /// Gates are merged as long as the function they compute depends on at most 4 ciphertexts,
+    /// and can be computed by a single lookup table applied to a weighted sum of those
+    /// ciphertexts. The weighted sum must fit in the message and carry space of the key, and its
+    /// noise must stay below the maximum noise level of the key.
+    ///
+    /// The number of lookup tables of the fused circuit, that is the number of PBS to evaluate it,
+    /// is at most the number of gates of the circuit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::circuit::Circuit;
+    /// use tfhe::shortint::gen_keys;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2_KS_PBS;
+    ///
+    /// // Majority of three bits
+    /// let circuit = Circuit::from_bristol_fashion(
+    ///     "4 7
+    ///     3 1 1 1
+    ///     1 1
+    ///
+    ///     2 1 0 1 3 AND
+    ///     2 1 0 1 4 XOR
+    ///     2 1 4 2 5 AND
+    ///     2 1 3 5 6 OR",
+    /// )
+    /// .unwrap();
+    ///
+    /// let (client_key, server_key) = gen_keys(PARAM_MESSAGE_2_CARRY_2_KS_PBS);
+    /// let fused = circuit.fuse(&server_key).unwrap();
+    /// assert_eq!(fused.num_lookup_tables(), 1);
+    ///
+    /// let inputs = [1, 0, 1].map(|bit| vec![client_key.encrypt(bit)]);
+    /// let outputs = fused.evaluate(&server_key, &inputs).unwrap();
+    /// assert_eq!(client_key.decrypt(&outputs[0][0]), 1);
+    /// ```
+    pub fn fuse(&self, server_key: &ServerKey) -> crate::Result<FusedCircuit> {
+        let max_packed_value = max_packed_value(server_key);
+        // Any function of 2 bits can be computed from `2 * lhs + rhs`
+        if max_packed_value < 3 {
+            return Err(crate::error!(
+                "The parameters of the server key do not allow to combine two bits \
+                 in a lookup table"
+            ));
+        }
+
+        let num_input_wires = self.input_sizes.iter().sum::<usize>();
+        let mut fuser = Fuser {
+            nodes: vec![Node::Input; num_input_wires],
+            max_packed_value,
+        };
+
+        let mut expressions = vec![None; self.num_wires];
+        for (wire, expression) in expressions.iter_mut().take(num_input_wires).enumerate() {
+            *expression = Some(Expression::node(wire));
+        }
+
+        for gate in &self.gates {
+            let expression = match *gate {
+                Gate::Binary { op, lhs, rhs, .. } => {
+                    let mut combined = None;
+                    // Materialize the operands, the biggest one first, until the result fits
+                    for _ in 0..3 {
+                        let (lhs_expr, rhs_expr) = (
+                            expressions[lhs].as_ref().unwrap(),
+                            expressions[rhs].as_ref().unwrap(),
+                        );
+                        combined = Expression::combine(lhs_expr, rhs_expr, |l, r| op.apply(l, r))
+                            .filter(|expression| fuser.fits(expression));
+                        if combined.is_some() {
+                            break;
+                        }
+
+                        let (wire, expression) = [(lhs, lhs_expr), (rhs, rhs_expr)]
+                            .into_iter()
+                            .filter(|(_, expression)| expression.as_node().is_none())
+                            .max_by_key(|(_, expression)| expression.support.len())
+                            .map(|(wire, expression)| (wire, expression.clone()))
+                            .unwrap();
+                        let node = fuser.materialize(&expression);
+                        expressions[wire] = Some(Expression::node(node));
+                    }
+                    combined.unwrap()
+                }
+                Gate::Inv { input, .. } => expressions[input].as_ref().unwrap().not(),
+                Gate::Copy { input, .. } => expressions[input].clone().unwrap(),
+                Gate::Constant { value, .. } => Expression::constant(value),
+            };
+            expressions[gate.output()] = Some(expression);
+        }
+
+        let num_output_wires = self.output_sizes.iter().sum::<usize>();
+        let outputs = expressions[self.num_wires - num_output_wires..]
+            .iter()
+            .map(|expression| fuser.materialize(expression.as_ref().unwrap()))
+            .collect::<Vec<_>>();
+
+        let nodes = fuser.nodes;
+        let mut node_levels = vec![0usize; nodes.len()];
+        let mut levels: Vec<Vec<usize>> = Vec::new();
+        let mut last_reads = vec![None; nodes.len()];
+        for (index, node) in nodes.iter().enumerate() {
+            if let Node::Lut { inputs, .. } = node {
+                let level = inputs.iter().map(|&i| node_levels[i]).max().unwrap_or(0);
+                node_levels[index] = level + 1;
+                if levels.len() <= level {
+                    levels.resize_with(level + 1, Vec::new);
+                }
+                levels[level].push(index);
+                for &input in inputs {
+                    let last_read = last_reads[input].get_or_insert(level);
+                    *last_read = (*last_read).max(level);
+                }
+            }
+        }
+
+        let mut dead_nodes = vec![Vec::new(); levels.len()];
+        for (node, last_read) in last_reads.into_iter().enumerate() {
+            if let Some(level) = last_read.filter(|_| !outputs.contains(&node)) {
+                dead_nodes[level].push(node);
+            }
+        }
+
+        Ok(FusedCircuit {
+            input_sizes: self.input_sizes.clone(),
+            output_sizes: self.output_sizes.clone(),
+            max_packed_value,
+            nodes,
+            levels,
+            dead_nodes,
+            outputs,
+        })
+    }
+}
+
+/// Returns the maximum value of a weighted sum of bits the key can apply a lookup table to
+fn max_packed_value(server_key: &ServerKey) -> u64 {
+    // Bits coming out of a PBS have a nominal noise level, so the noise level of the sum is the
+    // sum of the weights
+    let max_value = server_key.message_modulus.0 * server_key.carry_modulus.0 - 1;
+    max_value.min(server_key.max_noise_level.get())
+}
+
+impl FusedCircuit {
+    /// Returns the number of lookup tables, i.e. the number of PBS, evaluated by the circuit
+    pub fn num_lookup_tables(&self) -> usize {
+        self.levels.iter().map(Vec::len).sum()
+    }
+
+    /// Returns the number of levels of lookup tables evaluated one after the other
+    pub fn depth(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Returns the number of bits of each input value
+    pub fn input_sizes(&self) -> &[usize] {
+        &self.input_sizes
+    }
+
+    /// Returns the number of bits of each output value
+    pub fn output_sizes(&self) -> &[usize] {
+        &self.output_sizes
+    }
+
+    /// Evaluates the circuit on ciphertexts encrypting 0 or 1
+    ///
+    /// Inputs whose noise level is above nominal are first cleaned with a PBS. The lookup tables
+    /// of each level are applied in parallel.
+    ///
+    /// The key must allow weighted sums at least as large as the key the circuit was fused with.
+    pub fn evaluate(
+        &self,
+        server_key: &ServerKey,
+        inputs: &[Vec<Ciphertext>],
+    ) -> crate::Result<Vec<Vec<Ciphertext>>> {
+        if max_packed_value(server_key) < self.max_packed_value {
+            return Err(crate::error!(
+                "The server key does not allow lookup tables as large as the ones of the circuit"
+            ));
+        }
+        let sizes = inputs.iter().map(Vec::len).collect::<Vec<_>>();
+        if sizes != self.input_sizes {
+            return Err(crate::error!(
+                "Expected inputs of sizes {:?}, got {sizes:?}",
+                self.input_sizes
+            ));
+        }
+
+        let mut values = inputs
+            .iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|ct| {
+                if ct.noise_level() > NoiseLevel::NOMINAL {
+                    Some(server_key.message_extract(ct))
+                } else {
+                    Some(ct.clone())
+                }
+            })
+            .collect::<Vec<_>>();
+        values.extend(self.nodes[values.len()..].iter().map(|node| match node {
+            Node::Constant(value) => Some(server_key.create_trivial(u64::from(*value))),
+            Node::Input | Node::Lut { .. } => None,
+        }));
+
+        for (level_nodes, dead_nodes) in self.levels.iter().zip(&self.dead_nodes) {
+            let results = level_nodes
+                .par_iter()
+                .map(|&index| {
+                    let Node::Lut {
+                        inputs,
+                        weights,
+                        table,
+                    } = &self.nodes[index]
+                    else {
+                        unreachable!("levels only contain lookup tables")
+                    };
+
+                    let mut packed = server_key.create_trivial(0);
+                    for (&input, &weight) in inputs.iter().zip(weights) {
+                        let value = values[input].as_ref().unwrap();
+                        let weighted = server_key.unchecked_scalar_mul(value, weight);
+                        server_key.unchecked_add_assign(&mut packed, &weighted);
+                    }
+                    let lut = server_key.generate_lookup_table(|x| {
+                        u64::from(table.get(x as usize).copied().unwrap_or_default())
+                    });
+                    server_key.apply_lookup_table(&packed, &lut)
+                })
+                .collect::<Vec<_>>();
+
+            for (&index, result) in level_nodes.iter().zip(results) {
+                values[index] = Some(result);
+            }
+            for &node in dead_nodes {
+                values[node] = None;
+            }
+        }
+
+        let mut outputs = self
+            .outputs
+            .iter()
+            .map(|&node| values[node].clone().unwrap());
+        Ok(self
+            .output_sizes
+            .iter()
+            .map(|&size| outputs.by_ref().take(size).collect())
+            .collect())
+    }
+}
@@ -0,0 +1,492 @@
+//! Evaluation of boolean circuits described in the Bristol formats.
+//!
+//! A [Circuit] is a netlist of boolean gates, usually generated by MPC compilers and published
+//! in one of two formats:
+//! - [Bristol Fashion](https://nigelsmart.github.io/MPC-Circuits/), parsed by
+//!   [Circuit::from_bristol_fashion], where the circuit has any number of inputs and outputs;
+//! - the older Bristol format, parsed by [Circuit::from_bristol], where the circuit has two inputs
+//!   and one output.
+//!
+//! Besides the `XOR`, `AND`, `INV`, `EQ`, `EQW` and `MAND` gates of the Bristol formats, the
+//! `OR`, `NAND`, `NOR`, `XNOR` and `NOT` gates found in Bristol-like netlists are accepted.
+//!
+//! Circuits can be evaluated:
+//! - on clear booleans with [Circuit::evaluate_clear],
+//! - on [FheBool](crate::FheBool) with [Circuit::evaluate], using the server key set for the
+//!   current thread,
+//! - on shortint ciphertexts encrypting bits with [FusedCircuit::evaluate], where small clusters of
+//!   gates are merged into a single lookup table, see [Circuit::fuse].
+//!
+//! In all cases gates are grouped by level, and the gates of a level are evaluated in parallel.
+//!
+//! The values of the inputs and outputs are given as one `Vec` of bits per input/output value,
+//! in the order of the wires of the circuit.
+//!
+//! # Example
+//!
+//! ```rust
+//! use tfhe::circuit::Circuit;
+//! use tfhe::prelude::*;
+//! use tfhe::{generate_keys, set_server_key, ConfigBuilder, FheBool};
+//!
+//! // Full adder: inputs a, b, carry in; outputs sum, carry out
+//! let circuit = Circuit::from_bristol_fashion(
+//!     "5 8
+//!     3 1 1 1
+//!     2 1 1
+//!
+//!     2 1 0 1 3 XOR
+//!     2 1 3 2 6 XOR
+//!     2 1 0 1 4 AND
+//!     2 1 3 2 5 AND
+//!     2 1 4 5 7 XOR",
+//! )
+//! .unwrap();
+//!
+//! let (client_key, server_key) = generate_keys(ConfigBuilder::default());
+//! set_server_key(server_key);
+//!
+//! let clear_inputs = [vec![true], vec![false], vec![true]];
+//! let inputs = clear_inputs
+//!     .iter()
+//!     .map(|bits| {
+//!         bits.iter()
+//!             .map(|&b| FheBool::encrypt(b, &client_key))
+//!             .collect()
+//!     })
+//!     .collect::<Vec<Vec<_>>>();
+//!
+//! let outputs = circuit.evaluate(&inputs).unwrap();
+//! let sum = outputs[0][0].decrypt(&client_key);
+//! let carry = outputs[1][0].decrypt(&client_key);
+//! assert_eq!((sum, carry), (false, true));
+//! assert_eq!(
+//!     circuit.evaluate_clear(&clear_inputs).unwrap(),
+//!     vec![vec![false], vec![true]]
+//! );
+//! ```
+
+mod fhe_bool;
+mod fused;
+
+pub use fused::FusedCircuit;
+
+/// Boolean gates with two inputs
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BinaryGate {
+    And,
+    Or,
+    Xor,
+    Nand,
+    Nor,
+    Xnor,
+}
+
+impl BinaryGate {
+    /// Returns the output of the gate for the given inputs
+    pub fn apply(self, lhs: bool, rhs: bool) -> bool {
+        match self {
+            Self::And => lhs & rhs,
+            Self::Or => lhs | rhs,
+            Self::Xor => lhs ^ rhs,
+            Self::Nand => !(lhs & rhs),
+            Self::Nor => !(lhs | rhs),
+            Self::Xnor => !(lhs ^ rhs),
+        }
+    }
+}
+
+/// A gate of a [Circuit], wires are identified by their index
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Gate {
+    Binary {
+        op: BinaryGate,
+        lhs: usize,
+        rhs: usize,
+        output: usize,
+    },
+    /// `INV` or `NOT` gate
+    Inv { input: usize, output: usize },
+    /// `EQW` gate, copying a wire to another
+    Copy { input: usize, output: usize },
+    /// `EQ` gate, assigning a constant to a wire
+    Constant { value: bool, output: usize },
+}
+
+impl Gate {
+    /// Returns the wire the gate writes to
+    pub fn output(&self) -> usize {
+        match *self {
+            Self::Binary { output, .. }
+            | Self::Inv { output, .. }
+            | Self::Copy { output, .. }
+            | Self::Constant { output, .. } => output,
+        }
+    }
+
+    /// Returns the wires the gate reads from
+    pub fn inputs(&self) -> impl Iterator<Item = usize> {
+        let (first, second) = match *self {
+            Self::Binary { lhs, rhs, .. } => (Some(lhs), Some(rhs)),
+            Self::Inv { input, .. } | Self::Copy { input, .. } => (Some(input), None),
+            Self::Constant { .. } => (None, None),
+        };
+        first.into_iter().chain(second)
+    }
+
+    fn evaluate_clear(&self, wires: &[Option<bool>]) -> bool {
+        let wire = |index: usize| wires[index].unwrap();
+        match *self {
+            Self::Binary { op, lhs, rhs, .. } => op.apply(wire(lhs), wire(rhs)),
+            Self::Inv { input, .. } => !wire(input),
+            Self::Copy { input, .. } => wire(input),
+            Self::Constant { value, .. } => value,
+        }
+    }
+}
+
+/// A boolean circuit, see the [module level documentation](self)
+#[derive(Clone, Debug)]
+pub struct Circuit {
+    num_wires: usize,
+    input_sizes: Vec<usize>,
+    output_sizes: Vec<usize>,
+    gates: Vec<Gate>,
+    // Indices of the gates of each level, gates only depend on the gates of previous levels
+    levels: Vec<Vec<usize>>,
+    // Wires that are not read after each level, and are not outputs
+    dead_wires: Vec<Vec<usize>>,
+}
+
+impl Circuit {
+    /// Creates a circuit from its gates
+    ///
+    /// The inputs are the first wires of the circuit, in order, and the outputs are its last
+    /// wires. Each wire must be written at most once, by an input or a gate, before being read.
+    pub fn new(
+        num_wires: usize,
+        input_sizes: Vec<usize>,
+        output_sizes: Vec<usize>,
+        gates: Vec<Gate>,
+    ) -> crate::Result<Self> {
+        let total = |sizes: &[usize]| {
+            sizes
+                .iter()
+                .try_fold(0usize, |total, &size| total.checked_add(size))
+                .unwrap_or(usize::MAX)
+        };
+        let num_input_wires = total(&input_sizes);
+        let num_output_wires = total(&output_sizes);
+        if num_input_wires > num_wires || num_output_wires > num_wires {
+            return Err(crate::error!(
+                "The circuit has {num_wires} wires, \
+                 which is less than its {num_input_wires} input wires or {num_output_wires} output wires"
+            ));
+        }
+        // Each wire is assigned once, this bounds the allocations below by the size of the
+        // description instead of trusting the header of a netlist
+        if num_wires - num_input_wires > gates.len() {
+            return Err(crate::error!(
+                "The circuit has {num_wires} wires, which is more than its {num_input_wires} \
+                 input wires and the {} wires assigned by its gates",
+                gates.len()
+            ));
+        }
+
+        let mut wire_levels = vec![None; num_wires];
+        wire_levels[..num_input_wires].fill(Some(0usize));
+
+        let mut levels: Vec<Vec<usize>> = Vec::new();
+        for (index, gate) in gates.iter().enumerate() {
+            let mut level = 0;
+            for input in gate.inputs() {
+                match wire_levels.get(input) {
+                    Some(Some(input_level)) => level = level.max(*input_level),
+                    _ => {
+                        return Err(crate::error!(
+                            "Gate {index} reads wire {input} which is not assigned before"
+                        ))
+                    }
+                }
+            }
+
+            let output = gate.output();
+            match wire_levels.get(output) {
+                Some(None) => wire_levels[output] = Some(level + 1),
+                Some(Some(_)) => {
+                    return Err(crate::error!(
+                        "Gate {index} writes wire {output} which is already assigned"
+                    ))
+                }
+                None => {
+                    return Err(crate::error!(
+                        "Gate {index} writes wire {output} but the circuit has {num_wires} wires"
+                    ))
+                }
+            }
+
+            if levels.len() <= level {
+                levels.resize_with(level + 1, Vec::new);
+            }
+            levels[level].push(index);
+        }
+
+        let output_wires = num_wires - num_output_wires..num_wires;
+        if let Some(wire) = output_wires.clone().find(|&w| wire_levels[w].is_none()) {
+            return Err(crate::error!("Output wire {wire} is never assigned"));
+        }
+
+        let mut last_reads = vec![None; num_wires];
+        for (level, level_gates) in levels.iter().enumerate() {
+            for &index in level_gates {
+                for input in gates[index].inputs() {
+                    last_reads[input] = Some(level);
+                }
+            }
+        }
+        let mut dead_wires = vec![Vec::new(); levels.len()];
+        for (wire, last_read) in last_reads.into_iter().enumerate() {
+            if let Some(level) = last_read.filter(|_| !output_wires.contains(&wire)) {
+                dead_wires[level].push(wire);
+            }
+        }
+
+        Ok(Self {
+            num_wires,
+            input_sizes,
+            output_sizes,
+            gates,
+            levels,
+            dead_wires,
+        })
+    }
+
+    /// Parses a circuit in the Bristol Fashion format
+    pub fn from_bristol_fashion(netlist: &str) -> crate::Result<Self> {
+        let mut lines = netlist
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty());
+
+        let (num_gates, num_wires) = parse_gate_and_wire_counts(lines.next())?;
+        let input_sizes = parse_value_sizes(lines.next(), "inputs")?;
+        let output_sizes = parse_value_sizes(lines.next(), "outputs")?;
+        let gates = parse_gates(lines, num_gates)?;
+
+        Self::new(num_wires, input_sizes, output_sizes, gates)
+    }
+
+    /// Parses a circuit in the (old) Bristol format
+    ///
+    /// The header of such circuits gives the number of wires of the two inputs and of the
+    /// output.
+    pub fn from_bristol(netlist: &str) -> crate::Result<Self> {
+        let mut lines = netlist
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty());
+
+        let (num_gates, num_wires) = parse_gate_and_wire_counts(lines.next())?;
+        let sizes = parse_numbers(lines.next().unwrap_or_default())?;
+        let [lhs_size, rhs_size, output_size] = sizes[..] else {
+            return Err(crate::error!(
+                "Expected the sizes of the two inputs and of the output, got {sizes:?}"
+            ));
+        };
+        let gates = parse_gates(lines, num_gates)?;
+
+        Self::new(
+            num_wires,
+            vec![lhs_size, rhs_size],
+            vec![output_size],
+            gates,
+        )
+    }
+
+    pub fn num_wires(&self) -> usize {
+        self.num_wires
+    }
+
+    /// Returns the number of wires of each input value
+    pub fn input_sizes(&self) -> &[usize] {
+        &self.input_sizes
+    }
+
+    /// Returns the number of wires of each output value
+    pub fn output_sizes(&self) -> &[usize] {
+        &self.output_sizes
+    }
+
+    /// Returns the gates, a `MAND` gate is split into as many `AND` gates as it has outputs
+    pub fn gates(&self) -> &[Gate] {
+        &self.gates
+    }
+
+    /// Returns the number of levels of gates evaluated one after the other
+    pub fn depth(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Evaluates the circuit on clear values
+    pub fn evaluate_clear(&self, inputs: &[Vec<bool>]) -> crate::Result<Vec<Vec<bool>>> {
+        let mut wires = self.input_wires(inputs)?;
+        for gate in &self.gates {
+            wires[gate.output()] = Some(gate.evaluate_clear(&wires));
+        }
+        Ok(self.output_values(&mut wires))
+    }
+
+    fn check_input_sizes<T>(&self, inputs: &[Vec<T>]) -> crate::Result<()> {
+        let sizes = inputs.iter().map(Vec::len).collect::<Vec<_>>();
+        if sizes != self.input_sizes {
+            return Err(crate::error!(
+                "Expected inputs of sizes {:?}, got {sizes:?}",
+                self.input_sizes
+            ));
+        }
+        Ok(())
+    }
+
+    fn input_wires<T: Clone>(&self, inputs: &[Vec<T>]) -> crate::Result<Vec<Option<T>>> {
+        self.check_input_sizes(inputs)?;
+        let mut wires = inputs
+            .iter()
+            .flatten()
+            .cloned()
+            .map(Some)
+            .collect::<Vec<_>>();
+        wires.resize(self.num_wires, None);
+        Ok(wires)
+    }
+
+    fn output_values<T>(&self, wires: &mut [Option<T>]) -> Vec<Vec<T>> {
+        let num_output_wires = self.output_sizes.iter().sum::<usize>();
+        let mut output_wires = wires[self.num_wires - num_output_wires..]
+            .iter_mut()
+            .map(|wire| wire.take().unwrap());
+        self.output_sizes
+            .iter()
+            .map(|&size| output_wires.by_ref().take(size).collect())
+            .collect()
+    }
+}
+
+fn parse_numbers(line: &str) -> crate::Result<Vec<usize>> {
+    line.split_whitespace()
+        .map(|token| {
+            token
+                .parse()
+                .map_err(|_| crate::error!("Expected a number, got '{token}'"))
+        })
+        .collect()
+}
+
+fn parse_gate_and_wire_counts(line: Option<&str>) -> crate::Result<(usize, usize)> {
+    let counts = parse_numbers(line.unwrap_or_default())?;
+    match counts[..] {
+        [num_gates, num_wires] => Ok((num_gates, num_wires)),
+        _ => Err(crate::error!(
+            "Expected the number of gates and of wires, got {counts:?}"
+        )),
+    }
+}
+
+fn parse_value_sizes(line: Option<&str>, what: &str) -> crate::Result<Vec<usize>> {
+    let numbers = parse_numbers(line.unwrap_or_default())?;
+    match numbers.split_first() {
+        Some((&count, sizes)) if count == sizes.len() => Ok(sizes.to_vec()),
+        _ => Err(crate::error!(
+            "Invalid description of the {what}: {numbers:?}"
+        )),
+    }
+}
+
+fn parse_gates<'a>(
+    lines: impl Iterator<Item = &'a str>,
+    num_gates: usize,
+) -> crate::Result<Vec<Gate>> {
+    // num_gates comes from the header of the netlist, it is only checked once the gates are read
+    let mut gates = Vec::new();
+    let mut num_lines = 0;
+    for line in lines {
+        num_lines += 1;
+        parse_gate(line, &mut gates)
+            .map_err(|err| crate::error!("Invalid gate '{line}': {err}"))?;
+    }
+
+    if num_lines != num_gates {
+        return Err(crate::error!("Expected {num_gates} gates, got {num_lines}"));
+    }
+    Ok(gates)
+}
+
+fn parse_gate(line: &str, gates: &mut Vec<Gate>) -> crate::Result<()> {
+    let tokens = line.split_whitespace().collect::<Vec<_>>();
+    let Some((&name, tokens)) = tokens.split_last() else {
+        return Err(crate::error!("empty gate"));
+    };
+    let numbers = parse_numbers(&tokens.join(" "))?;
+    let (num_inputs, num_outputs, wires) = match numbers[..] {
+        [num_inputs, num_outputs, ref wires @ ..]
+            if Some(wires.len()) == num_inputs.checked_add(num_outputs) =>
+        {
+            (num_inputs, num_outputs, wires)
+        }
+        _ => return Err(crate::error!("wrong number of wires")),
+    };
+    let (inputs, outputs) = wires.split_at(num_inputs);
+
+    let binary = |op| match (inputs, outputs) {
+        (&[lhs, rhs], &[output]) => Ok(Gate::Binary {
+            op,
+            lhs,
+            rhs,
+            output,
+        }),
+        _ => Err(crate::error!("expected 2 inputs and 1 output")),
+    };
+    let unary = |make_gate: fn(usize, usize) -> Gate| match (inputs, outputs) {
+        (&[input], &[output]) => Ok(make_gate(input, output)),
+        _ => Err(crate::error!("expected 1 input and 1 output")),
+    };
+
+    match name {
+        "AND" => gates.push(binary(BinaryGate::And)?),
+        "OR" => gates.push(binary(BinaryGate::Or)?),
+        "XOR" => gates.push(binary(BinaryGate::Xor)?),
+        "NAND" => gates.push(binary(BinaryGate::Nand)?),
+        "NOR" => gates.push(binary(BinaryGate::Nor)?),
+        "XNOR" => gates.push(binary(BinaryGate::Xnor)?),
+        "INV" | "NOT" => gates.push(unary(|input, output| Gate::Inv { input, output })?),
+        "EQW" => gates.push(unary(|input, output| Gate::Copy { input, output })?),
+        "EQ" => {
+            let gate = unary(|value, output| Gate::Constant {
+                value: value != 0,
+                output,
+            })?;
+            if inputs[0] > 1 {
+                return Err(crate::error!("expected a constant of 0 or 1"));
+            }
+            gates.push(gate);
+        }
+        "MAND" => {
+            if num_inputs != 2 * num_outputs {
+                return Err(crate::error!("expected twice as many inputs as outputs"));
+            }
+            let (lhs, rhs) = inputs.split_at(num_outputs);
+            gates.extend(
+                lhs.iter()
+                    .zip(rhs)
+                    .zip(outputs)
+                    .map(|((&lhs, &rhs), &output)| Gate::Binary {
+                        op: BinaryGate::And,
+                        lhs,
+                        rhs,
+                        output,
+                    }),
+            );
+        }
+        _ => return Err(crate::error!("unknown gate {name}")),
+    }
+    Ok(())
+}
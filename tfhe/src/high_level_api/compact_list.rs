@@ -199,7 +199,6 @@ impl CompactCiphertextList {
                     inner,
                     tag: self.tag.clone(),
                 }),
-            #[cfg(feature = "gpu")]
             Some(_) => Err(crate::Error::new("Expected a CPU server key".to_string())),
         })
     }
@@ -314,7 +313,6 @@ mod zk {
                         inner: expander,
                         tag: self.tag.clone(),
                     }),
-                #[cfg(feature = "gpu")]
                 Some(_) => Err(crate::Error::new("Expected a CPU server key".to_string())),
             })
         }
@@ -346,7 +344,6 @@ mod zk {
                         inner: expander,
                         tag: self.tag.clone(),
                     }),
-                #[cfg(feature = "gpu")]
                 Some(_) => Err(crate::Error::new("Expected a CPU server key".to_string())),
             })
         }
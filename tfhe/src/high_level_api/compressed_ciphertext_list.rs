@@ -1,3 +1,4 @@
+use tfhe_versionable::schema::{DataSchema, SchemaRegistry};
 use tfhe_versionable::{Unversionize, UnversionizeError, Versionize, VersionizeOwned};
 
 use super::details::MaybeCloned;
@@ -12,9 +13,9 @@ use crate::core_crypto::commons::math::random::{Deserialize, Serialize};
 use crate::core_crypto::gpu::CudaStreams;
 use crate::high_level_api::booleans::InnerBoolean;
 use crate::high_level_api::errors::UninitializedServerKey;
-use crate::high_level_api::global_state::device_of_internal_keys;
 #[cfg(feature = "gpu")]
 use crate::high_level_api::global_state::with_thread_local_cuda_streams;
+use crate::high_level_api::global_state::{device_of_internal_keys, try_check_tags};
 use crate::high_level_api::integers::{FheIntId, FheUintId};
 use crate::integer::ciphertext::{DataKind, Expandable};
 #[cfg(feature = "gpu")]
@@ -42,6 +43,12 @@ impl<Id: FheUintId> HlCompressible for FheUint<Id> {
                 let kind = DataKind::Unsigned(blocks.info.blocks.len());
                 messages.push((ToBeCompressed::Cuda(blocks), kind));
             }
+            ciphertext @ (crate::high_level_api::integers::unsigned::RadixCiphertext::Lazy(_)
+            | crate::high_level_api::integers::unsigned::RadixCiphertext::Simulation(_)) => {
+                let blocks = ciphertext.into_cpu().blocks;
+                let kind = DataKind::Unsigned(blocks.len());
+                messages.push((ToBeCompressed::Cpu(blocks), kind));
+            }
         }
     }
 }
@@ -59,6 +66,12 @@ impl<Id: FheIntId> HlCompressible for FheInt<Id> {
                 let kind = DataKind::Signed(blocks.info.blocks.len());
                 messages.push((ToBeCompressed::Cuda(blocks), kind));
             }
+            ciphertext @ (crate::high_level_api::integers::signed::SignedRadixCiphertext::Lazy(_)
+            | crate::high_level_api::integers::signed::SignedRadixCiphertext::Simulation(_)) => {
+                let blocks = ciphertext.into_cpu().blocks;
+                let kind = DataKind::Signed(blocks.len());
+                messages.push((ToBeCompressed::Cpu(blocks), kind));
+            }
         }
     }
 }
@@ -74,6 +87,13 @@ impl HlCompressible for FheBool {
                 let kind = DataKind::Boolean;
                 messages.push((ToBeCompressed::Cuda(cuda_bool.0.ciphertext), kind));
             }
+            ciphertext @ (InnerBoolean::Lazy(_) | InnerBoolean::Simulation(_)) => {
+                let kind = DataKind::Boolean;
+                messages.push((
+                    ToBeCompressed::Cpu(vec![ciphertext.on_cpu().into_owned().0]),
+                    kind,
+                ));
+            }
         }
     }
 }
@@ -99,25 +119,30 @@ pub enum ToBeCompressed {
 
 pub struct CompressedCiphertextListBuilder {
     inner: Vec<(ToBeCompressed, DataKind)>,
+    tags: Vec<Tag>,
 }
 
 impl CompressedCiphertextListBuilder {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { inner: vec![] }
+        Self {
+            inner: vec![],
+            tags: vec![],
+        }
     }
 
     pub fn push<T>(&mut self, value: T) -> &mut Self
     where
-        T: HlCompressible,
+        T: HlCompressible + Tagged,
     {
+        self.tags.push(value.tag().clone());
         value.compress_into(&mut self.inner);
         self
     }
 
     pub fn extend<T>(&mut self, values: impl Iterator<Item = T>) -> &mut Self
     where
-        T: HlCompressible,
+        T: HlCompressible + Tagged,
     {
         for value in values {
             self.push(value);
@@ -126,6 +151,7 @@ impl CompressedCiphertextListBuilder {
     }
 
     pub fn build(&self) -> crate::Result<CompressedCiphertextList> {
+        try_check_tags(&self.tags)?;
         crate::high_level_api::global_state::try_with_internal_keys(|keys| match keys {
             Some(InternalServerKey::Cpu(cpu_key)) => {
                 let mut flat_cpu_blocks = vec![];
@@ -209,6 +235,9 @@ impl CompressedCiphertextListBuilder {
                         }
                     })
             }
+            Some(InternalServerKey::Simulation(_)) => Err(crate::Error::new(
+                "The simulation backend does not support compression".to_owned(),
+            )),
             None => Err(UninitializedServerKey.into()),
         })
     }
@@ -258,6 +287,8 @@ impl InnerCompressedCiphertextList {
     fn move_to_device(&mut self, device: crate::Device) {
         let new_value = match (&self, device) {
             (Self::Cpu(_), crate::Device::Cpu) => None,
+            // Compressed lists are not simulated, they stay on the CPU
+            (Self::Cpu(_), crate::Device::Simulation) => None,
             #[cfg(feature = "gpu")]
             (Self::Cuda(cuda_ct), crate::Device::CudaGpu) => {
                 with_thread_local_cuda_streams(|streams| {
@@ -290,7 +321,9 @@ impl InnerCompressedCiphertextList {
         }
     }
 
-    fn on_cpu(&self) -> MaybeCloned<crate::integer::ciphertext::CompressedCiphertextList> {
+    pub(in crate::high_level_api) fn on_cpu(
+        &self,
+    ) -> MaybeCloned<crate::integer::ciphertext::CompressedCiphertextList> {
         match self {
             Self::Cpu(cpu_ct) => MaybeCloned::Borrowed(cpu_ct),
             #[cfg(feature = "gpu")]
@@ -361,6 +394,12 @@ impl VersionizeOwned for InnerCompressedCiphertextList {
             }
         }
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        <crate::integer::ciphertext::CompressedCiphertextList as VersionizeOwned>::versioned_schema(
+            registry,
+        )
+    }
 }
 
 impl Unversionize for InnerCompressedCiphertextList {
@@ -429,6 +468,7 @@ impl CiphertextList for CompressedCiphertextList {
     where
         T: HlExpandable + Tagged,
     {
+        try_check_tags([&self.tag])?;
         // We use the server key to know where computation should happen,
         // if the data is not on the correct device, a temporary copy (and transfer) will happen
         //
@@ -468,6 +508,9 @@ impl CiphertextList for CompressedCiphertextList {
                     }
                     ct
                 }),
+            Some(InternalServerKey::Simulation(_)) => Err(crate::Error::new(
+                "The simulation backend does not support compression".to_owned(),
+            )),
             None => Err(UninitializedServerKey.into()),
         })
     }
@@ -0,0 +1,320 @@
+use crate::high_level_api::global_state;
+use crate::high_level_api::keys::InternalServerKey;
+#[cfg(feature = "strings")]
+use crate::high_level_api::strings::traits::{FheEqIgnoreCase, FheStringFind, FheStringMatching};
+use crate::prelude::{
+    DivRem, FheEq, FheMax, FheMin, FheOrd, IfThenElse, OverflowingAdd, OverflowingMul,
+    OverflowingSub, RotateLeft, RotateRight,
+};
+use crate::FheBool;
+#[cfg(feature = "strings")]
+use crate::{FheAsciiString, FheStringIsEmpty, FheStringLen, FheUint32};
+use rayon::prelude::*;
+use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Neg, Not, Rem, Shl, Shr, Sub};
+
+macro_rules! impl_context_binary_operations {
+    (
+        $(
+            $rust_trait_name:ident($rust_trait_method:ident) => $symbol:literal
+        ),* $(,)?
+    ) => {
+        $(
+            #[doc = concat!("Computes `lhs ", $symbol, " rhs` using the key of the context")]
+            pub fn $rust_trait_method<Lhs, Rhs>(&self, lhs: Lhs, rhs: Rhs) -> Lhs::Output
+            where
+                Lhs: $rust_trait_name<Rhs>,
+            {
+                self.run(|| $rust_trait_name::$rust_trait_method(lhs, rhs))
+            }
+        )*
+    };
+}
+
+macro_rules! impl_context_by_ref_operations {
+    (
+        $(
+            $rust_trait_name:ident($($rust_trait_method:ident),*) -> $output:ty
+        ),* $(,)?
+    ) => {
+        $(
+            $(
+                #[doc = concat!("Computes `lhs.", stringify!($rust_trait_method), "(rhs)` using the key of the context")]
+                pub fn $rust_trait_method<Lhs, Rhs>(&self, lhs: &Lhs, rhs: Rhs) -> $output
+                where
+                    Lhs: $rust_trait_name<Rhs> + ?Sized,
+                {
+                    self.run(|| $rust_trait_name::$rust_trait_method(lhs, rhs))
+                }
+            )*
+        )*
+    };
+}
+
+/// Explicit handle on a server key, to compute on high-level types without relying on the
+/// server key set for the current thread.
+///
+/// The methods of the context run the corresponding operation with the key of the context. They
+/// are generic over the operation traits, so they work for [FheUint](crate::FheUint),
+/// [FheInt](crate::FheInt), [FheBool], arrays and, for the ones that apply, strings, with
+/// ciphertext or clear operands.
+///
+/// The key set for the current thread, if any, is left untouched: it is only replaced for the
+/// duration of the operation.
+///
+/// [FheContext::run] runs any computation with the key of the context, [FheContext::par_map]
+/// maps a function over a rayon parallel iterator with the key set in every worker thread.
+///
+/// Cloning a context is cheap, as keys are stored in an `Arc`.
+///
+/// # Example
+///
+/// ```rust
+/// use tfhe::prelude::*;
+/// use tfhe::{ClientKey, ConfigBuilder, FheContext, FheUint8, ServerKey};
+///
+/// let config = ConfigBuilder::default().build();
+/// let client_key = ClientKey::generate(config);
+/// let context = FheContext::new(ServerKey::new(&client_key));
+///
+/// let a = FheUint8::encrypt(27u8, &client_key);
+/// let b = FheUint8::encrypt(100u8, &client_key);
+///
+/// // No call to set_server_key
+/// let sum = context.add(&a, &b);
+/// let is_greater = context.gt(&sum, 120u8);
+/// let doubled = context.par_map(vec![a, b], |value| value * 2u8);
+///
+/// let decrypted: u8 = sum.decrypt(&client_key);
+/// assert_eq!(decrypted, 127);
+/// assert!(is_greater.decrypt(&client_key));
+/// let decrypted: Vec<u8> = doubled.iter().map(|v| v.decrypt(&client_key)).collect();
+/// assert_eq!(decrypted, vec![54, 200]);
+/// ```
+#[derive(Clone)]
+pub struct FheContext {
+    key: InternalServerKey,
+    strict_tag_checks: bool,
+}
+
+impl FheContext {
+    pub fn new<T: Into<InternalServerKey>>(key: T) -> Self {
+        Self {
+            key: key.into(),
+            strict_tag_checks: false,
+        }
+    }
+
+    /// Same as [FheContext::new], but the operations done with the context check the tags of
+    /// their inputs, see [set_server_key_with_strict_tags](crate::set_server_key_with_strict_tags)
+    pub fn new_with_strict_tags<T: Into<InternalServerKey>>(key: T) -> Self {
+        Self {
+            key: key.into(),
+            strict_tag_checks: true,
+        }
+    }
+
+    /// Returns a context using the key set for the current thread
+    ///
+    /// # Panics
+    ///
+    /// Panics if the server key is not set
+    #[track_caller]
+    pub(in crate::high_level_api) fn from_current_thread() -> Self {
+        Self {
+            key: global_state::with_internal_keys(|key| key.clone()),
+            strict_tag_checks: global_state::strict_tag_checks(),
+        }
+    }
+
+    /// Runs `func` on the current thread with the key of the context
+    ///
+    /// The key previously set for the current thread is restored afterward, even if `func`
+    /// panics.
+    pub fn run<F, R>(&self, func: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let _scope = global_state::scoped_server_key(self.key.clone(), self.strict_tag_checks);
+        func()
+    }
+
+    /// Maps `func` over the items in parallel, using rayon, with the key of the context set for
+    /// each call
+    pub fn par_map<I, F, R>(&self, items: I, func: F) -> Vec<R>
+    where
+        I: IntoParallelIterator,
+        F: Fn(I::Item) -> R + Send + Sync,
+        R: Send,
+    {
+        items
+            .into_par_iter()
+            .map(|item| self.run(|| func(item)))
+            .collect()
+    }
+
+    /// Runs both closures, potentially in parallel using `rayon::join`, with the key of the
+    /// context
+    pub fn join<A, B, RA, RB>(&self, func_a: A, func_b: B) -> (RA, RB)
+    where
+        A: FnOnce() -> RA + Send,
+        B: FnOnce() -> RB + Send,
+        RA: Send,
+        RB: Send,
+    {
+        rayon::join(|| self.run(func_a), || self.run(func_b))
+    }
+
+    impl_context_binary_operations!(
+        Add(add) => "+",
+        Sub(sub) => "-",
+        Mul(mul) => "*",
+        Div(div) => "/",
+        Rem(rem) => "%",
+        BitAnd(bitand) => "&",
+        BitOr(bitor) => "|",
+        BitXor(bitxor) => "^",
+        Shl(shl) => "<<",
+        Shr(shr) => ">>",
+    );
+
+    impl_context_by_ref_operations!(
+        FheEq(eq, ne) -> FheBool,
+        FheOrd(lt, le, gt, ge) -> FheBool,
+        FheMin(min) -> Lhs::Output,
+        FheMax(max) -> Lhs::Output,
+    );
+
+    /// Computes `!value` using the key of the context
+    pub fn not<T: Not>(&self, value: T) -> T::Output {
+        self.run(|| !value)
+    }
+
+    /// Computes `-value` using the key of the context
+    pub fn neg<T: Neg>(&self, value: T) -> T::Output {
+        self.run(|| -value)
+    }
+
+    /// Computes `value.rotate_left(amount)` using the key of the context
+    pub fn rotate_left<T, Amount>(&self, value: T, amount: Amount) -> T::Output
+    where
+        T: RotateLeft<Amount>,
+    {
+        self.run(|| value.rotate_left(amount))
+    }
+
+    /// Computes `value.rotate_right(amount)` using the key of the context
+    pub fn rotate_right<T, Amount>(&self, value: T, amount: Amount) -> T::Output
+    where
+        T: RotateRight<Amount>,
+    {
+        self.run(|| value.rotate_right(amount))
+    }
+
+    /// Computes `lhs.div_rem(rhs)` using the key of the context
+    pub fn div_rem<Lhs, Rhs>(&self, lhs: Lhs, rhs: Rhs) -> Lhs::Output
+    where
+        Lhs: DivRem<Rhs>,
+    {
+        self.run(|| lhs.div_rem(rhs))
+    }
+
+    /// Computes `lhs.overflowing_add(rhs)` using the key of the context
+    pub fn overflowing_add<Lhs, Rhs>(&self, lhs: Lhs, rhs: Rhs) -> (Lhs::Output, FheBool)
+    where
+        Lhs: OverflowingAdd<Rhs>,
+    {
+        self.run(|| lhs.overflowing_add(rhs))
+    }
+
+    /// Computes `lhs.overflowing_sub(rhs)` using the key of the context
+    pub fn overflowing_sub<Lhs, Rhs>(&self, lhs: Lhs, rhs: Rhs) -> (Lhs::Output, FheBool)
+    where
+        Lhs: OverflowingSub<Rhs>,
+    {
+        self.run(|| lhs.overflowing_sub(rhs))
+    }
+
+    /// Computes `lhs.overflowing_mul(rhs)` using the key of the context
+    pub fn overflowing_mul<Lhs, Rhs>(&self, lhs: Lhs, rhs: Rhs) -> (Lhs::Output, FheBool)
+    where
+        Lhs: OverflowingMul<Rhs>,
+    {
+        self.run(|| lhs.overflowing_mul(rhs))
+    }
+
+    /// Computes `condition.if_then_else(ct_then, ct_else)` using the key of the context
+    pub fn if_then_else<T>(&self, condition: &FheBool, ct_then: &T, ct_else: &T) -> T
+    where
+        FheBool: IfThenElse<T>,
+    {
+        self.run(|| condition.if_then_else(ct_then, ct_else))
+    }
+}
+
+#[cfg(feature = "strings")]
+impl FheContext {
+    /// Computes `string.contains(pattern)` using the key of the context
+    pub fn contains<Pattern>(&self, string: &FheAsciiString, pattern: Pattern) -> FheBool
+    where
+        FheAsciiString: FheStringMatching<Pattern>,
+    {
+        self.run(|| string.contains(pattern))
+    }
+
+    /// Computes `string.starts_with(pattern)` using the key of the context
+    pub fn starts_with<Pattern>(&self, string: &FheAsciiString, pattern: Pattern) -> FheBool
+    where
+        FheAsciiString: FheStringMatching<Pattern>,
+    {
+        self.run(|| string.starts_with(pattern))
+    }
+
+    /// Computes `string.ends_with(pattern)` using the key of the context
+    pub fn ends_with<Pattern>(&self, string: &FheAsciiString, pattern: Pattern) -> FheBool
+    where
+        FheAsciiString: FheStringMatching<Pattern>,
+    {
+        self.run(|| string.ends_with(pattern))
+    }
+
+    /// Computes `string.find(pattern)` using the key of the context
+    pub fn find<Pattern>(&self, string: &FheAsciiString, pattern: Pattern) -> (FheUint32, FheBool)
+    where
+        FheAsciiString: FheStringFind<Pattern>,
+    {
+        self.run(|| string.find(pattern))
+    }
+
+    /// Computes `lhs.eq_ignore_case(rhs)` using the key of the context
+    pub fn eq_ignore_case<Rhs>(&self, lhs: &FheAsciiString, rhs: &Rhs) -> FheBool
+    where
+        FheAsciiString: FheEqIgnoreCase<Rhs>,
+    {
+        self.run(|| lhs.eq_ignore_case(rhs))
+    }
+
+    /// Computes `lhs.concat(rhs)` using the key of the context
+    pub fn concat(&self, lhs: &FheAsciiString, rhs: &FheAsciiString) -> FheAsciiString {
+        self.run(|| lhs.concat(rhs))
+    }
+
+    /// Computes `string.len()` using the key of the context
+    pub fn len(&self, string: &FheAsciiString) -> FheStringLen {
+        self.run(|| string.len())
+    }
+
+    /// Computes `string.is_empty()` using the key of the context
+    pub fn is_empty(&self, string: &FheAsciiString) -> FheStringIsEmpty {
+        self.run(|| string.is_empty())
+    }
+
+    /// Computes `string.to_lowercase()` using the key of the context
+    pub fn to_lowercase(&self, string: &FheAsciiString) -> FheAsciiString {
+        self.run(|| string.to_lowercase())
+    }
+
+    /// Computes `string.to_uppercase()` using the key of the context
+    pub fn to_uppercase(&self, string: &FheAsciiString) -> FheAsciiString {
+        self.run(|| string.to_uppercase())
+    }
+}
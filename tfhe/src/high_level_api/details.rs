@@ -1,5 +1,9 @@
+use std::any::type_name;
 use std::ops::Deref;
 
+use tfhe_versionable::schema::{DataSchema, FieldsSchema, SchemaRegistry, VersionSchema};
+use tfhe_versionable::VersionizeOwned;
+
 /// 'smart-pointer' that holds either a borrowed T, or an owned T.
 ///
 /// This is essentially like a Cow, except T does not need to be ToOwned
@@ -30,3 +34,20 @@ impl<T> Deref for MaybeCloned<'_, T> {
         }
     }
 }
+
+/// Describes the versions of a type that only versions its CPU data, by wrapping it into the
+/// single `V0` variant of a manually written dispatch enum.
+pub(crate) fn cpu_data_versioned_schema<T, Dispatch, VersionOwned, CpuData: VersionizeOwned>(
+    registry: &mut SchemaRegistry,
+) -> DataSchema {
+    registry.register_versioned::<T, Dispatch>(|registry| {
+        vec![VersionSchema {
+            version: "V0".to_string(),
+            type_name: type_name::<VersionOwned>().to_string(),
+            layout: DataSchema::Struct {
+                fields: FieldsSchema::Unnamed(vec![CpuData::versioned_schema(registry)]),
+            },
+            upgrades_to: None,
+        }]
+    })
+}
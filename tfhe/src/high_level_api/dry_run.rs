@@ -0,0 +1,268 @@
+//! Cost of a computation, counted without keys.
+//!
+//! [dry_run] executes the real CPU algorithms of the high level API with a server key built from
+//! the parameters only. Its key material is zeroed and never used: the programmable
+//! bootstrappings and keyswitches the algorithms do are recorded through the scoped counters of
+//! [pbs_stats](crate::shortint::server_key::pbs_stats) and skipped, the ciphertexts only get the
+//! degree and noise level the real operation would give them. As the algorithms only depend on
+//! these, and not on the encrypted values, the counts are the ones of the real computation.
+
+use crate::high_level_api::config::Config;
+use crate::high_level_api::global_state;
+use crate::high_level_api::integers::{FheIntId, FheUintId};
+use crate::high_level_api::keys::{IntegerServerKey, ServerKey};
+use crate::integer::BooleanBlock;
+use crate::shortint::ciphertext::{Degree, NoiseLevel};
+use crate::shortint::parameters::PBSParameters;
+use crate::shortint::server_key::pbs_stats::{SharedStatsScope, Stats};
+use crate::{FheBool, FheInt, FheUint, Tag};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Runs `func` with a server key built from `config` and returns its result alongside the
+/// number of PBS and keyswitches it did, and the number of PBS that had to be done sequentially.
+///
+/// No key is generated: inputs are created with [DryRun::unsigned], [DryRun::signed] and
+/// [DryRun::boolean], and the values computed during the run are meaningless, only their
+/// cost is.
+///
+/// `func` runs in a dedicated thread pool, so that the operations executed in parallel by the
+/// algorithms are recorded, and operations executed by other threads of the program are not.
+///
+/// Only the integer computations are supported, operations that use another key (e.g.
+/// compression, noise squashing or casting from a compact list) cannot be dry run.
+///
+/// # Example
+///
+/// ```rust
+/// use tfhe::prelude::*;
+/// use tfhe::{dry_run, ConfigBuilder, FheUint32};
+///
+/// let config = ConfigBuilder::default().build();
+///
+/// let (_, report) = dry_run(config, |run| {
+///     let a: FheUint32 = run.unsigned();
+///     let b: FheUint32 = run.unsigned();
+///
+///     let product = run.measure("mul", || &a * &b);
+///     run.measure("add", || &product + &a)
+/// });
+///
+/// assert_eq!(report.operation("mul").unwrap().count, 1);
+/// assert!(report.operation("mul").unwrap().stats.pbs > 0);
+/// assert_eq!(
+///     report.pbs_count(),
+///     report.operation("mul").unwrap().stats.pbs + report.operation("add").unwrap().stats.pbs
+/// );
+/// // The addition uses the product, it deepens the computation
+/// assert_eq!(
+///     report.pbs_depth(),
+///     report.operation("mul").unwrap().stats.pbs_depth
+///         + report.operation("add").unwrap().stats.pbs_depth
+/// );
+/// assert!(report.pbs_depth() < report.pbs_count());
+/// ```
+pub fn dry_run<F, R>(config: impl Into<Config>, func: F) -> (R, DryRunReport)
+where
+    F: FnOnce(&DryRun) -> R + Send,
+    R: Send,
+{
+    let config = config.into();
+    let parameters = config.inner.block_parameters;
+
+    let key = crate::integer::ServerKey::new_radix_server_key_from_shortint(
+        crate::shortint::ServerKey::new_dry_run(parameters),
+    );
+    let server_key = ServerKey {
+        key: Arc::new(IntegerServerKey {
+            key,
+            cpk_key_switching_key_material: None,
+            compression_key: None,
+            decompression_key: None,
+            noise_squashing_key: None,
+        }),
+        tag: Tag::default(),
+    };
+
+    let run = DryRun {
+        server_key,
+        scope: SharedStatsScope::new_dry_run(),
+        operations: Mutex::new(BTreeMap::new()),
+    };
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .build()
+        .expect("Failed to create the dry run thread pool");
+    // Operations may be dispatched to any thread of the pool
+    pool.broadcast(|_| global_state::set_server_key(run.server_key.clone()));
+    let result = run.scope.install(&pool, || func(&run));
+
+    let report = DryRunReport {
+        parameters,
+        total: run.scope.stats(),
+        operations: run.operations.into_inner().unwrap(),
+    };
+    (result, report)
+}
+
+/// State of a [dry_run], given to the function being run
+pub struct DryRun {
+    server_key: ServerKey,
+    scope: SharedStatsScope,
+    operations: Mutex<BTreeMap<&'static str, OperationCost>>,
+}
+
+impl DryRun {
+    /// Returns an unsigned input, as if it had just been encrypted
+    pub fn unsigned<Id: FheUintId>(&self) -> FheUint<Id> {
+        let num_blocks = Id::num_blocks(self.server_key.key.message_modulus());
+        let ciphertext = crate::integer::RadixCiphertext::from(self.fresh_blocks(num_blocks));
+        FheUint::new(ciphertext, self.server_key.tag.clone())
+    }
+
+    /// Returns a signed input, as if it had just been encrypted
+    pub fn signed<Id: FheIntId>(&self) -> FheInt<Id> {
+        let num_blocks = Id::num_blocks(self.server_key.key.message_modulus());
+        let ciphertext = crate::integer::SignedRadixCiphertext::from(self.fresh_blocks(num_blocks));
+        FheInt::new(ciphertext, self.server_key.tag.clone())
+    }
+
+    /// Returns a boolean input, as if it had just been encrypted
+    pub fn boolean(&self) -> FheBool {
+        let mut block = self.fresh_blocks(1).pop().unwrap();
+        block.degree = Degree::new(1);
+        FheBool::new(
+            BooleanBlock::new_unchecked(block),
+            self.server_key.tag.clone(),
+        )
+    }
+
+    /// Runs `func`, recording the operations it does under `name` in the report
+    ///
+    /// Measuring several times with the same name adds up the costs. The operations executed
+    /// concurrently with `func` during the run are also recorded.
+    pub fn measure<R>(&self, name: &'static str, func: impl FnOnce() -> R) -> R {
+        let before = self.scope.stats();
+        let result = func();
+        let after = self.scope.stats();
+
+        let mut operations = self.operations.lock().unwrap();
+        let cost = operations.entry(name).or_default();
+        cost.count += 1;
+        cost.stats = cost.stats + (after - before);
+        result
+    }
+
+    /// Blocks which have the degree and noise level of freshly encrypted ones
+    fn fresh_blocks(&self, num_blocks: usize) -> Vec<crate::shortint::Ciphertext> {
+        let key = &self.server_key.key.pbs_key().key;
+        (0..num_blocks)
+            .map(|_| {
+                let mut block = key.create_trivial(0);
+                block.degree = Degree::new(key.message_modulus.0 - 1);
+                block.set_noise_level(NoiseLevel::NOMINAL, key.max_noise_level);
+                block
+            })
+            .collect()
+    }
+}
+
+/// Cost of the operations measured under the same name
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct OperationCost {
+    /// Number of times the operations were measured
+    pub count: u64,
+    /// Operations done by the server key, summed over all the measures
+    pub stats: Stats,
+}
+
+impl OperationCost {
+    /// See [DryRunReport::estimated_latency]
+    ///
+    /// This is the latency the operations added to the computation, operations which are not
+    /// on its critical path have no latency.
+    pub fn estimated_latency(
+        &self,
+        pbs_latency: Duration,
+        keyswitch_latency: Duration,
+    ) -> Duration {
+        depth_latency(&self.stats, pbs_latency, keyswitch_latency)
+    }
+}
+
+/// Cost of a computation, as returned by [dry_run]
+#[derive(Clone, Debug)]
+pub struct DryRunReport {
+    parameters: PBSParameters,
+    total: Stats,
+    operations: BTreeMap<&'static str, OperationCost>,
+}
+
+impl DryRunReport {
+    /// Parameters the computation was run with
+    pub fn parameters(&self) -> PBSParameters {
+        self.parameters
+    }
+
+    /// Operations done by the server key during the whole run
+    pub fn total(&self) -> Stats {
+        self.total
+    }
+
+    /// Cost of each name given to [DryRun::measure]
+    pub fn operations(&self) -> impl Iterator<Item = (&'static str, &OperationCost)> {
+        self.operations.iter().map(|(name, cost)| (*name, cost))
+    }
+
+    /// Cost of the operations measured under `name`, `None` if nothing was measured with it
+    pub fn operation(&self, name: &str) -> Option<&OperationCost> {
+        self.operations.get(name)
+    }
+
+    pub fn pbs_count(&self) -> u64 {
+        self.total.pbs
+    }
+
+    pub fn keyswitch_count(&self) -> u64 {
+        self.total.keyswitch
+    }
+
+    /// Number of PBS done with a classic bootstrapping key
+    pub fn classic_pbs_count(&self) -> u64 {
+        self.total.classic_pbs
+    }
+
+    /// Number of PBS done with a multi-bit bootstrapping key
+    pub fn multi_bit_pbs_count(&self) -> u64 {
+        self.total.multi_bit_pbs
+    }
+
+    /// Number of PBS that had to be done one after the other, i.e. the length of the critical
+    /// path of the computation
+    pub fn pbs_depth(&self) -> u64 {
+        self.total.pbs_depth
+    }
+
+    /// Latency of the computation if all the PBS that do not depend on each other were done in
+    /// parallel
+    ///
+    /// `pbs_latency` and `keyswitch_latency` are the latencies of a single PBS and keyswitch with
+    /// the [parameters](Self::parameters) of the run, as measured on the target machine (e.g.
+    /// with the `pbs-bench` and `ks-bench` benchmarks). Each level of the
+    /// [PBS depth](Self::pbs_depth) costs one keyswitch and one PBS. As the number of PBS that
+    /// can run at the same time is limited by the machine, this is a lower bound of the real
+    /// latency.
+    pub fn estimated_latency(
+        &self,
+        pbs_latency: Duration,
+        keyswitch_latency: Duration,
+    ) -> Duration {
+        depth_latency(&self.total, pbs_latency, keyswitch_latency)
+    }
+}
+
+fn depth_latency(stats: &Stats, pbs_latency: Duration, keyswitch_latency: Duration) -> Duration {
+    let depth = u32::try_from(stats.pbs_depth).unwrap_or(u32::MAX);
+    (pbs_latency + keyswitch_latency) * depth
+}
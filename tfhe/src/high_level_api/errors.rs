@@ -1,4 +1,5 @@
-use crate::Error;
+use crate::error::ErrorKind;
+use crate::{Error, Tag};
 use std::fmt::{Display, Formatter};
 
 /// Unwrap 'Extension' trait
@@ -66,3 +67,45 @@ impl From<UninitializedNoiseSquashing> for Error {
         Self::new(format!("{value}"))
     }
 }
+
+/// The tag of a ciphertext does not match the tag of the server key, this error is only raised
+/// when strict tag checks are enabled, see [`set_server_key_with_strict_tags`].
+///
+/// [`set_server_key_with_strict_tags`]: crate::set_server_key_with_strict_tags
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TagMismatchError {
+    pub(crate) expected: Tag,
+    pub(crate) found: Tag,
+}
+
+impl TagMismatchError {
+    /// Tag of the server key
+    pub fn expected(&self) -> &Tag {
+        &self.expected
+    }
+
+    /// Tag of the ciphertext that was given to the operation
+    pub fn found(&self) -> &Tag {
+        &self.found
+    }
+}
+
+impl Display for TagMismatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The tag of a ciphertext ({:?}) does not match the tag of the server key ({:?}).\n\
+             The ciphertext was likely encrypted under a different key set.",
+            self.found.data(),
+            self.expected.data(),
+        )
+    }
+}
+
+impl std::error::Error for TagMismatchError {}
+
+impl From<TagMismatchError> for Error {
+    fn from(value: TagMismatchError) -> Self {
+        Self::from(ErrorKind::TagMismatch(value))
+    }
+}
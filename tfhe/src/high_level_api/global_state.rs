@@ -4,11 +4,13 @@
 use crate::core_crypto::gpu::vec::GpuIndex;
 #[cfg(feature = "gpu")]
 use crate::core_crypto::gpu::CudaStreams;
-use crate::high_level_api::errors::{UninitializedServerKey, UnwrapResultExt};
-use crate::high_level_api::keys::{InternalServerKey, ServerKey};
+use crate::high_level_api::errors::{TagMismatchError, UninitializedServerKey, UnwrapResultExt};
+use crate::high_level_api::keys::{InternalServerKey, ServerKey, SimulationServerKey};
 #[cfg(feature = "gpu")]
 use crate::high_level_api::CudaServerKey;
-use std::cell::RefCell;
+use crate::Tag;
+use std::cell::{Cell, RefCell};
+use std::marker::PhantomData;
 
 /// We store the internal keys as thread local, meaning each thread has its own set of keys.
 ///
@@ -19,6 +21,11 @@ thread_local! {
     static INTERNAL_KEYS: RefCell<Option<InternalServerKey>> = const { RefCell::new(None) };
 }
 
+thread_local! {
+    /// Whether the tags of the ciphertexts are checked against the tag of the internal keys
+    static STRICT_TAG_CHECKS: Cell<bool> = const { Cell::new(false) };
+}
+
 /// The function used to initialize internal keys.
 ///
 /// As each thread has its own set of keys,
@@ -65,10 +72,50 @@ thread_local! {
 /// ```
 pub fn set_server_key<T: Into<InternalServerKey>>(keys: T) {
     let _old = replace_server_key(Some(keys));
+    STRICT_TAG_CHECKS.set(false);
+}
+
+/// Same as [set_server_key], but also enables strict tag checks for the current thread.
+///
+/// With strict tag checks, every operation verifies that the [Tag] of its input ciphertexts
+/// matches the tag of the server key, so that ciphertexts encrypted under another key set
+/// (e.g. from another tenant) are not silently mixed.
+///
+/// Operations that return a `Result` return a [TagMismatchError] on mismatch, the other
+/// operations panic.
+///
+/// Strict checks stay enabled until [set_server_key] or [unset_server_key] is called.
+///
+/// # Example
+///
+/// ```rust
+/// use tfhe::prelude::*;
+/// use tfhe::{set_server_key_with_strict_tags, ClientKey, ConfigBuilder, FheUint8, ServerKey};
+///
+/// let config = ConfigBuilder::default().build();
+/// let mut client_key = ClientKey::generate(config);
+/// client_key.tag_mut().set_u64(1);
+/// let server_key = ServerKey::new(&client_key);
+///
+/// set_server_key_with_strict_tags(server_key);
+///
+/// let a = FheUint8::encrypt(1u8, &client_key);
+/// let mut b = FheUint8::encrypt(2u8, &client_key);
+/// let _ = &a + &b;
+///
+/// // Pretend b comes from another key set
+/// b.tag_mut().set_u64(2);
+/// let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| &a + &b));
+/// assert!(result.is_err());
+/// ```
+pub fn set_server_key_with_strict_tags<T: Into<InternalServerKey>>(keys: T) {
+    let _old = replace_server_key(Some(keys));
+    STRICT_TAG_CHECKS.set(true);
 }
 
 pub fn unset_server_key() {
     let _old = INTERNAL_KEYS.take();
+    STRICT_TAG_CHECKS.set(false);
 }
 
 fn replace_server_key(new_one: Option<impl Into<InternalServerKey>>) -> Option<InternalServerKey> {
@@ -84,6 +131,41 @@ fn replace_server_key(new_one: Option<impl Into<InternalServerKey>>) -> Option<I
     INTERNAL_KEYS.replace(keys)
 }
 
+/// Guard returned by [scoped_server_key]
+pub(in crate::high_level_api) struct ServerKeyScope {
+    previous_keys: Option<InternalServerKey>,
+    previous_strict_tag_checks: bool,
+    // The previous keys are restored in the thread the guard was created in
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for ServerKeyScope {
+    fn drop(&mut self) {
+        let _ = replace_server_key(self.previous_keys.take());
+        STRICT_TAG_CHECKS.set(self.previous_strict_tag_checks);
+    }
+}
+
+/// Sets the server key of the current thread until the returned guard is dropped, the previous
+/// key and strict tag checks setting are then restored.
+pub(in crate::high_level_api) fn scoped_server_key(
+    keys: InternalServerKey,
+    strict_tag_checks: bool,
+) -> ServerKeyScope {
+    let previous_keys = replace_server_key(Some(keys));
+    let previous_strict_tag_checks = STRICT_TAG_CHECKS.replace(strict_tag_checks);
+    ServerKeyScope {
+        previous_keys,
+        previous_strict_tag_checks,
+        _not_send: PhantomData,
+    }
+}
+
+/// Returns whether the tags of the inputs are checked against the key of the current thread
+pub(in crate::high_level_api) fn strict_tag_checks() -> bool {
+    STRICT_TAG_CHECKS.get()
+}
+
 pub fn with_server_key_as_context<T, F>(keys: ServerKey, f: F) -> T
 where
     F: FnOnce() -> T,
@@ -133,6 +215,7 @@ pub(in crate::high_level_api) fn device_of_internal_keys() -> Option<crate::Devi
             InternalServerKey::Cpu(_) => crate::Device::Cpu,
             #[cfg(feature = "gpu")]
             InternalServerKey::Cuda(_) => crate::Device::CudaGpu,
+            InternalServerKey::Simulation(_) => crate::Device::Simulation,
         })
     })
 }
@@ -146,10 +229,50 @@ pub(in crate::high_level_api) fn tag_of_internal_server_key() -> crate::Result<c
             InternalServerKey::Cpu(cpu_key) => cpu_key.tag.clone(),
             #[cfg(feature = "gpu")]
             InternalServerKey::Cuda(cuda_key) => cuda_key.tag.clone(),
+            InternalServerKey::Simulation(sim_key) => sim_key.tag.clone(),
         })
     })
 }
 
+/// Checks that the given tags match the tag of the internal keys, if strict tag checks are
+/// enabled.
+///
+/// If the server key is not set, the check passes as the operation will report it.
+pub(in crate::high_level_api) fn try_check_tags<'a>(
+    tags: impl IntoIterator<Item = &'a Tag>,
+) -> Result<(), TagMismatchError> {
+    if !STRICT_TAG_CHECKS.get() {
+        return Ok(());
+    }
+
+    INTERNAL_KEYS.with(|keys| {
+        let cell = keys.borrow();
+        let expected = match cell.as_ref() {
+            Some(InternalServerKey::Cpu(cpu_key)) => &cpu_key.tag,
+            #[cfg(feature = "gpu")]
+            Some(InternalServerKey::Cuda(cuda_key)) => &cuda_key.tag,
+            Some(InternalServerKey::Simulation(sim_key)) => &sim_key.tag,
+            None => return Ok(()),
+        };
+
+        tags.into_iter()
+            .find(|tag| *tag != expected)
+            .map_or(Ok(()), |found| {
+                Err(TagMismatchError {
+                    expected: expected.clone(),
+                    found: found.clone(),
+                })
+            })
+    })
+}
+
+/// Same as [try_check_tags], but panics on mismatch
+#[track_caller]
+#[inline]
+pub(in crate::high_level_api) fn check_tags<'a>(tags: impl IntoIterator<Item = &'a Tag>) {
+    try_check_tags(tags).unwrap_display();
+}
+
 #[inline]
 pub(crate) fn with_cpu_internal_keys<T, F>(func: F) -> T
 where
@@ -168,6 +291,9 @@ where
             InternalServerKey::Cuda(_) => {
                 panic!("Cpu key requested but only cuda key is available")
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("Cpu key requested but only simulation key is available")
+            }
         }
     })
 }
@@ -190,6 +316,39 @@ where
             InternalServerKey::Cpu(_) => {
                 panic!("Cuda key requested but only cpu key is available")
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("Cuda key requested but only simulation key is available")
+            }
+        }
+    })
+}
+
+/// Calls `func` with the key of the simulation backend
+///
+/// # Panics
+///
+/// Panics if the server key is not set, or if it is not a [SimulationServerKey].
+///
+/// Simulated values can only be converted to and from ciphertexts while the simulation key is
+/// set, as the conversion uses the client key it wraps.
+#[track_caller]
+pub(in crate::high_level_api) fn with_simulation_internal_keys<T, F>(func: F) -> T
+where
+    F: FnOnce(&SimulationServerKey) -> T,
+{
+    // Should use `with_borrow` when its stabilized
+    INTERNAL_KEYS.with(|keys| {
+        let maybe_key = &*keys.borrow();
+        let key = maybe_key
+            .as_ref()
+            .ok_or(UninitializedServerKey)
+            .unwrap_display();
+        match key {
+            InternalServerKey::Simulation(key) => func(key),
+            _ => panic!(
+                "Simulated values can only be used while a SimulationServerKey is set, \
+                 current key is {key:?}"
+            ),
         }
     })
 }
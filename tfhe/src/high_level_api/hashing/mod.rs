@@ -0,0 +1,138 @@
+//! Hashing of encrypted data with SHA-256 and SHA3-256.
+//!
+//! The hash functions take encrypted bytes ([FheUint8](crate::FheUint8)) and return the
+//! encrypted digest, they are computed with the bitwise, rotation and addition operations of the
+//! radix integers, using the server key set for the current thread:
+//! - [sha256] hashes bytes, [sha256_words] hashes big-endian 32 bit words
+//!   ([FheUint32](crate::FheUint32)), as used by SHA-256 internally;
+//! - [sha3_256] hashes bytes, the [Keccak-f\[1600\]](keccak_f1600) permutation it is built on is
+//!   also available for other uses (e.g. SHAKE);
+//! - [sha256_with_encrypted_len] and [sha3_256_with_encrypted_len] hash the first `len` bytes of
+//!   the input, without revealing `len`, at the cost of hashing as many blocks as the whole input;
+//! - with the `strings` feature, `FheAsciiString`s are hashed with `sha256_ascii` and
+//!   `sha3_256_ascii`. When the string has padding its length is encrypted, and is not revealed
+//!   either.
+//!
+//! The length of the input is not hidden for slices of ciphertexts. Independent operations
+//! (e.g. the 25 lanes of the Keccak state) are computed in parallel.
+//!
+//! # Example
+//!
+//! ```rust
+//! use tfhe::hashing::sha256;
+//! use tfhe::prelude::*;
+//! use tfhe::{set_server_key, ClientKey, ConfigBuilder, FheUint8, SimulationServerKey};
+//!
+//! let client_key = ClientKey::generate(ConfigBuilder::default());
+//! // Hashing takes a few minutes with a real server key
+//! set_server_key(SimulationServerKey::new(&client_key));
+//!
+//! let message = b"abc"
+//!     .iter()
+//!     .map(|&byte| FheUint8::encrypt(byte, &client_key))
+//!     .collect::<Vec<_>>();
+//! let digest = sha256(&message);
+//!
+//! let clear_digest = digest
+//!     .iter()
+//!     .map(|byte| byte.decrypt(&client_key))
+//!     .collect::<Vec<u8>>();
+//! assert_eq!(clear_digest[..4], [0xba, 0x78, 0x16, 0xbf]);
+//! ```
+
+mod sha256;
+mod sha3;
+
+pub use sha256::{sha256, sha256_with_encrypted_len, sha256_words};
+pub use sha3::{keccak_f1600, sha3_256, sha3_256_with_encrypted_len};
+
+use crate::high_level_api::context::FheContext;
+use crate::prelude::*;
+#[cfg(feature = "strings")]
+use crate::FheAsciiString;
+use crate::{FheUint16, FheUint8};
+
+fn into_array<T, const N: usize>(values: Vec<T>) -> [T; N] {
+    values
+        .try_into()
+        .unwrap_or_else(|values: Vec<T>| panic!("Expected {N} values, got {}", values.len()))
+}
+
+/// Replaces the bytes of the message from the encrypted position `len` by the first padding
+/// byte followed by zeros, so that the end of the message is not revealed
+///
+/// The returned message has one more byte, for the padding byte when `len == message.len()`.
+fn mark_end(
+    context: &FheContext,
+    message: &[FheUint8],
+    len: &FheUint16,
+    marker: u8,
+) -> Vec<FheUint8> {
+    assert!(
+        message.len() < usize::from(u16::MAX),
+        "messages with an encrypted length must have less than {} bytes",
+        u16::MAX
+    );
+    let marker = FheUint8::encrypt_trivial(marker);
+    let zero = FheUint8::encrypt_trivial(0u8);
+    context.par_map(0..=message.len(), |i| {
+        let position = i as u16;
+        let padding = len.eq(position).select(&marker, &zero);
+        match message.get(i) {
+            Some(byte) => len.gt(position).select(byte, &padding),
+            None => padding,
+        }
+    })
+}
+
+/// Returns the encrypted bytes of a string and its length if it is encrypted
+#[cfg(feature = "strings")]
+fn ascii_bytes(string: &FheAsciiString) -> (Vec<FheUint8>, Option<FheUint16>) {
+    let inner = string.inner.on_cpu();
+    let bytes = inner
+        .chars()
+        .iter()
+        .map(|char| FheUint8::new(char.ciphertext().clone(), string.tag.clone()))
+        .collect();
+    let len = if inner.is_padded() {
+        match string.len() {
+            crate::FheStringLen::Padding(len) => Some(len),
+            crate::FheStringLen::NoPadding(_) => None,
+        }
+    } else {
+        None
+    };
+    (bytes, len)
+}
+
+/// Computes the SHA-256 digest of an encrypted string
+///
+/// The padding (null characters) at the end of the string is not hashed.
+///
+/// # Panics
+///
+/// Panics if the server key is not set
+#[cfg(feature = "strings")]
+#[track_caller]
+pub fn sha256_ascii(string: &FheAsciiString) -> [FheUint8; 32] {
+    match ascii_bytes(string) {
+        (bytes, Some(len)) => sha256_with_encrypted_len(&bytes, &len),
+        (bytes, None) => sha256(&bytes),
+    }
+}
+
+/// Computes the SHA3-256 digest of an encrypted string
+///
+/// The padding (null characters) at the end of the string is not hashed.
+///
+/// # Panics
+///
+/// Panics if the server key is not set
+#[cfg(feature = "strings")]
+#[track_caller]
+pub fn sha3_256_ascii(string: &FheAsciiString) -> [FheUint8; 32] {
+    match ascii_bytes(string) {
+        (bytes, Some(len)) => sha3_256_with_encrypted_len(&bytes, &len),
+        (bytes, None) => sha3_256(&bytes),
+    }
+}
@@ -0,0 +1,209 @@
+use super::{into_array, mark_end};
+use crate::high_level_api::context::FheContext;
+use crate::prelude::*;
+use crate::{FheBool, FheUint16, FheUint32, FheUint64, FheUint8};
+use rayon::prelude::*;
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Size of a block in bytes
+const BLOCK_SIZE: usize = 64;
+
+/// Size of the length of the message, at the end of the last block
+const LENGTH_SIZE: usize = 8;
+
+/// Computes the SHA-256 digest of encrypted bytes
+///
+/// # Panics
+///
+/// Panics if the server key is not set
+#[track_caller]
+pub fn sha256(message: &[FheUint8]) -> [FheUint8; 32] {
+    let context = FheContext::from_current_thread();
+
+    // Padding: 0x80, zeros and the length in bits, so that the length is a multiple of the block
+    // size
+    let num_blocks = (message.len() + LENGTH_SIZE) / BLOCK_SIZE + 1;
+    let bit_len = (message.len() as u64) * 8;
+    let mut padded = message.to_vec();
+    padded.push(FheUint8::encrypt_trivial(0x80u8));
+    padded.resize_with(num_blocks * BLOCK_SIZE - LENGTH_SIZE, || {
+        FheUint8::encrypt_trivial(0u8)
+    });
+    padded.extend(bit_len.to_be_bytes().map(FheUint8::encrypt_trivial));
+
+    let words = bytes_to_words(&context, &padded);
+    words_to_bytes(&context, &digest(&context, &words, None))
+}
+
+/// Computes the SHA-256 digest of the first `len` bytes of `message`, without revealing `len`
+///
+/// The digest is computed for each possible number of blocks, as many as required by
+/// `message.len()` bytes, and the one matching `len` is selected. If `len` is greater than
+/// `message.len()` the result is unspecified.
+///
+/// # Panics
+///
+/// - Panics if the server key is not set
+/// - Panics if the message has `u16::MAX` bytes or more
+#[track_caller]
+pub fn sha256_with_encrypted_len(message: &[FheUint8], len: &FheUint16) -> [FheUint8; 32] {
+    let context = FheContext::from_current_thread();
+
+    let max_num_blocks = (message.len() + LENGTH_SIZE) / BLOCK_SIZE + 1;
+    let mut padded = mark_end(&context, message, len, 0x80);
+    padded.resize_with(max_num_blocks * BLOCK_SIZE, || {
+        FheUint8::encrypt_trivial(0u8)
+    });
+
+    // The length is written at the end of the last block, whose index depends on `len`
+    let last_block = (FheUint32::cast_from(len.clone()) + LENGTH_SIZE as u32) / BLOCK_SIZE as u32;
+    let is_last_block = context.par_map(0..max_num_blocks, |block| last_block.eq(block as u32));
+    let bit_len = FheUint64::cast_from(len.clone()) << 3u64;
+    let length_bytes = context.par_map(0..LENGTH_SIZE, |i| {
+        FheUint8::cast_from(&bit_len >> (8 * (LENGTH_SIZE - 1 - i)) as u64)
+    });
+    for (block, is_last_block) in padded.chunks_exact_mut(BLOCK_SIZE).zip(&is_last_block) {
+        let length_field = &mut block[BLOCK_SIZE - LENGTH_SIZE..];
+        let selected = context.par_map(0..LENGTH_SIZE, |i| {
+            is_last_block.select(&length_bytes[i], &length_field[i])
+        });
+        length_field.clone_from_slice(&selected);
+    }
+
+    let words = bytes_to_words(&context, &padded);
+    words_to_bytes(&context, &digest(&context, &words, Some(&is_last_block)))
+}
+
+/// Computes the SHA-256 digest of a message of big-endian 32 bit words
+///
+/// The digest is returned as big-endian words, as the message.
+///
+/// # Panics
+///
+/// Panics if the server key is not set
+#[track_caller]
+pub fn sha256_words(message: &[FheUint32]) -> [FheUint32; 8] {
+    let context = FheContext::from_current_thread();
+
+    let word_size = 4;
+    let words_per_block = BLOCK_SIZE / word_size;
+    let num_blocks = (message.len() * word_size + LENGTH_SIZE) / BLOCK_SIZE + 1;
+    let bit_len = (message.len() * word_size * 8) as u64;
+    let mut padded = message.to_vec();
+    padded.push(FheUint32::encrypt_trivial(0x8000_0000u32));
+    padded.resize_with(num_blocks * words_per_block - 2, || {
+        FheUint32::encrypt_trivial(0u32)
+    });
+    padded.push(FheUint32::encrypt_trivial((bit_len >> 32) as u32));
+    padded.push(FheUint32::encrypt_trivial(bit_len as u32));
+
+    digest(&context, &padded, None)
+}
+
+/// Hashes padded blocks, if `is_last_block` is given the state after the block for which it is
+/// true is returned, instead of the state after the last block
+fn digest(
+    context: &FheContext,
+    words: &[FheUint32],
+    is_last_block: Option<&[FheBool]>,
+) -> [FheUint32; 8] {
+    let mut state = INITIAL_STATE.map(FheUint32::encrypt_trivial);
+    let mut result = state.clone();
+    for (block_index, block) in words.chunks_exact(BLOCK_SIZE / 4).enumerate() {
+        state = compress(context, &state, block);
+        match is_last_block {
+            Some(is_last_block) => {
+                let condition = &is_last_block[block_index];
+                let selected = context.par_map(0..8, |i| condition.select(&state[i], &result[i]));
+                result.clone_from_slice(&selected);
+            }
+            None => result.clone_from(&state),
+        }
+    }
+    result
+}
+
+fn compress(context: &FheContext, state: &[FheUint32; 8], block: &[FheUint32]) -> [FheUint32; 8] {
+    let sigma = |x: &FheUint32, rotations: [u32; 2], shift: u32| {
+        x.rotate_right(rotations[0]) ^ x.rotate_right(rotations[1]) ^ (x >> shift)
+    };
+    let big_sigma = |x: &FheUint32, rotations: [u32; 3]| {
+        x.rotate_right(rotations[0]) ^ x.rotate_right(rotations[1]) ^ x.rotate_right(rotations[2])
+    };
+
+    let mut schedule = block.to_vec();
+    for i in 16..64 {
+        let (s0, s1) = context.join(
+            || sigma(&schedule[i - 15], [7, 18], 3),
+            || sigma(&schedule[i - 2], [17, 19], 10),
+        );
+        schedule.push(
+            [&schedule[i - 16], &s0, &schedule[i - 7], &s1]
+                .into_iter()
+                .sum(),
+        );
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state.clone();
+    for i in 0..64 {
+        let ((s1, choice), (s0, majority)) = context.join(
+            || context.join(|| big_sigma(&e, [6, 11, 25]), || (&e & &f) ^ (!&e & &g)),
+            || {
+                context.join(
+                    || big_sigma(&a, [2, 13, 22]),
+                    || (&a & &b) ^ (&a & &c) ^ (&b & &c),
+                )
+            },
+        );
+        let t1 = [&h, &s1, &choice, &schedule[i]]
+            .into_iter()
+            .sum::<FheUint32>()
+            + K[i];
+        let t2 = s0 + majority;
+        let (new_e, new_a) = context.join(|| &d + &t1, || &t1 + &t2);
+
+        h = g;
+        g = f;
+        f = e;
+        e = new_e;
+        d = c;
+        c = b;
+        b = a;
+        a = new_a;
+    }
+
+    let working = [a, b, c, d, e, f, g, h];
+    into_array(context.par_map(0..8, |i| &state[i] + &working[i]))
+}
+
+/// Packs bytes into big-endian words
+fn bytes_to_words(context: &FheContext, bytes: &[FheUint8]) -> Vec<FheUint32> {
+    context.par_map(bytes.par_chunks_exact(4), |word_bytes| {
+        word_bytes
+            .iter()
+            .map(|byte| FheUint32::cast_from(byte.clone()))
+            .reduce(|word, byte| (word << 8u32) | byte)
+            .unwrap()
+    })
+}
+
+fn words_to_bytes(context: &FheContext, words: &[FheUint32; 8]) -> [FheUint8; 32] {
+    into_array(context.par_map(0..32, |i| {
+        let shift = 8 * (3 - i % 4) as u32;
+        FheUint8::cast_from(&words[i / 4] >> shift)
+    }))
+}
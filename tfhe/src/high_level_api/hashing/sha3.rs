@@ -0,0 +1,195 @@
+use super::{into_array, mark_end};
+use crate::high_level_api::context::FheContext;
+use crate::prelude::*;
+use crate::{FheBool, FheUint16, FheUint64, FheUint8};
+use rayon::prelude::*;
+
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// Rotation of the lane `x + 5 * y` in the rho step
+const ROTATIONS: [u32; 25] = [
+    0, 1, 62, 28, 27, //
+    36, 44, 6, 55, 20, //
+    3, 10, 43, 25, 39, //
+    41, 45, 15, 21, 8, //
+    18, 2, 61, 56, 14, //
+];
+
+/// Number of bytes absorbed per permutation by SHA3-256
+const RATE: usize = 136;
+
+/// Domain separation bits of SHA-3 and first bit of the padding
+const DOMAIN_PADDING: u8 = 0x06;
+
+/// Last bit of the padding
+const FINAL_PADDING: u8 = 0x80;
+
+/// Applies the Keccak-f\[1600\] permutation to the state
+///
+/// The lane `(x, y)` of the state is `state[x + 5 * y]`, and lanes are little-endian as in the
+/// SHA-3 standard (FIPS 202).
+///
+/// # Panics
+///
+/// Panics if the server key is not set
+#[track_caller]
+pub fn keccak_f1600(state: &mut [FheUint64; 25]) {
+    let context = FheContext::from_current_thread();
+    for round_constant in ROUND_CONSTANTS {
+        round(&context, state, round_constant);
+    }
+}
+
+fn round(context: &FheContext, state: &mut [FheUint64; 25], round_constant: u64) {
+    // Theta
+    let columns = context.par_map(0..5, |x| {
+        [
+            &state[x + 5],
+            &state[x + 10],
+            &state[x + 15],
+            &state[x + 20],
+        ]
+        .into_iter()
+        .fold(state[x].clone(), |column, lane| column ^ lane)
+    });
+    let offsets = context.par_map(0..5, |x| {
+        &columns[(x + 4) % 5] ^ columns[(x + 1) % 5].rotate_left(1u32)
+    });
+    let lanes = context.par_map(0..25, |i| &state[i] ^ &offsets[i % 5]);
+
+    // Rho and pi, the lane (x, y) moves to (y, 2x + 3y)
+    let moved = context.par_map(0..25, |i| {
+        let (x, y) = (i % 5, i / 5);
+        let source = (x + 3 * y) % 5 + 5 * x;
+        lanes[source].rotate_left(ROTATIONS[source])
+    });
+
+    // Chi and iota
+    let mixed = context.par_map(0..25, |i| {
+        let (x, y) = (i % 5, i / 5);
+        let lane = &moved[i] ^ (!&moved[(x + 1) % 5 + 5 * y] & &moved[(x + 2) % 5 + 5 * y]);
+        if i == 0 {
+            lane ^ round_constant
+        } else {
+            lane
+        }
+    });
+    *state = into_array(mixed);
+}
+
+/// Computes the SHA3-256 digest of encrypted bytes
+///
+/// # Panics
+///
+/// Panics if the server key is not set
+#[track_caller]
+pub fn sha3_256(message: &[FheUint8]) -> [FheUint8; 32] {
+    let context = FheContext::from_current_thread();
+
+    let num_blocks = message.len() / RATE + 1;
+    let mut padded = message.to_vec();
+    padded.push(FheUint8::encrypt_trivial(DOMAIN_PADDING));
+    padded.resize_with(num_blocks * RATE, || FheUint8::encrypt_trivial(0u8));
+    let last = padded.last_mut().unwrap();
+    *last = &*last | FINAL_PADDING;
+
+    digest(&context, &padded, None)
+}
+
+/// Computes the SHA3-256 digest of the first `len` bytes of `message`, without revealing `len`
+///
+/// The message is absorbed in as many blocks as required by `message.len()` bytes, and the
+/// state after the last block for `len` is selected. If `len` is greater than `message.len()`
+/// the result is unspecified.
+///
+/// # Panics
+///
+/// - Panics if the server key is not set
+/// - Panics if the message has `u16::MAX` bytes or more
+#[track_caller]
+pub fn sha3_256_with_encrypted_len(message: &[FheUint8], len: &FheUint16) -> [FheUint8; 32] {
+    let context = FheContext::from_current_thread();
+
+    let max_num_blocks = message.len() / RATE + 1;
+    let mut padded = mark_end(&context, message, len, DOMAIN_PADDING);
+    padded.resize_with(max_num_blocks * RATE, || FheUint8::encrypt_trivial(0u8));
+
+    // The padding ends with the last byte of the last block, whose index depends on `len`
+    let last_block = len / RATE as u16;
+    let is_last_block = context.par_map(0..max_num_blocks, |block| last_block.eq(block as u16));
+    let final_padding = FheUint8::encrypt_trivial(FINAL_PADDING);
+    let zero = FheUint8::encrypt_trivial(0u8);
+    let last_bytes = context.par_map(0..max_num_blocks, |block| {
+        let padding = is_last_block[block].select(&final_padding, &zero);
+        &padded[(block + 1) * RATE - 1] | padding
+    });
+    for (block, last_byte) in padded.chunks_exact_mut(RATE).zip(last_bytes) {
+        block[RATE - 1] = last_byte;
+    }
+
+    digest(&context, &padded, Some(&is_last_block))
+}
+
+/// Absorbs the padded blocks, if `is_last_block` is given the digest after the block for which
+/// it is true is returned, instead of the digest after the last block
+fn digest(
+    context: &FheContext,
+    padded: &[FheUint8],
+    is_last_block: Option<&[FheBool]>,
+) -> [FheUint8; 32] {
+    let lanes = context.par_map(padded.par_chunks_exact(8), |bytes| {
+        // Lanes are little-endian
+        bytes
+            .iter()
+            .rev()
+            .map(|byte| FheUint64::cast_from(byte.clone()))
+            .reduce(|lane, byte| (lane << 8u64) | byte)
+            .unwrap()
+    });
+
+    let mut state: [FheUint64; 25] = std::array::from_fn(|_| FheUint64::encrypt_trivial(0u64));
+    let mut result = state[..4].to_vec();
+    for (block_index, block) in lanes.chunks_exact(RATE / 8).enumerate() {
+        let absorbed = context.par_map(0..block.len(), |i| &state[i] ^ &block[i]);
+        state[..block.len()].clone_from_slice(&absorbed);
+        keccak_f1600(&mut state);
+
+        match is_last_block {
+            Some(is_last_block) => {
+                let condition = &is_last_block[block_index];
+                let selected = context.par_map(0..4, |i| condition.select(&state[i], &result[i]));
+                result = selected;
+            }
+            None => result.clone_from_slice(&state[..4]),
+        }
+    }
+
+    into_array(context.par_map(0..32, |i| {
+        FheUint8::cast_from(&result[i / 8] >> (8 * (i % 8)) as u64)
+    }))
+}
@@ -3,6 +3,7 @@ use crate::high_level_api::global_state;
 #[cfg(feature = "gpu")]
 use crate::high_level_api::global_state::with_thread_local_cuda_streams;
 use crate::high_level_api::keys::InternalServerKey;
+use crate::high_level_api::simulation::SimulatedInteger;
 #[cfg(feature = "gpu")]
 use crate::integer::gpu::ciphertext::{CudaSignedRadixCiphertext, CudaUnsignedRadixCiphertext};
 use crate::{FheInt, Seed};
@@ -50,6 +51,12 @@ impl<Id: FheUintId> FheUint<Id> {
 
                 Self::new(d_ct, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let num_bits = Id::num_bits() as u32;
+                let value =
+                    SimulatedInteger::pseudo_random(seed, u64::from(num_bits), num_bits, false);
+                Self::new(value, sim_key.tag.clone())
+            }
         })
     }
     /// Generates an encrypted `num_block` blocks unsigned integer
@@ -99,6 +106,12 @@ impl<Id: FheUintId> FheUint<Id> {
                     );
                 Self::new(d_ct, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let num_bits = Id::num_bits() as u32;
+                let value =
+                    SimulatedInteger::pseudo_random(seed, random_bits_count, num_bits, false);
+                Self::new(value, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -148,6 +161,12 @@ impl<Id: FheIntId> FheInt<Id> {
 
                 Self::new(d_ct, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let num_bits = Id::num_bits() as u32;
+                let value =
+                    SimulatedInteger::pseudo_random(seed, u64::from(num_bits), num_bits, true);
+                Self::new(value, sim_key.tag.clone())
+            }
         })
     }
 
@@ -199,6 +218,18 @@ impl<Id: FheIntId> FheInt<Id> {
                     );
                 Self::new(d_ct, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let num_bits = Id::num_bits() as u32;
+                let range_log_size = u64::from(num_bits);
+                assert!(
+                    random_bits_count + 1 <= range_log_size,
+                    "The range asked for a random value (=[0, 2^{}[) does not fit in the available range [-2^{}, 2^{}[",
+                    random_bits_count, range_log_size - 1, range_log_size - 1,
+                );
+                let value =
+                    SimulatedInteger::pseudo_random(seed, random_bits_count, num_bits, true);
+                Self::new(value, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -4,9 +4,11 @@ use super::inner::SignedRadixCiphertext;
 use crate::backward_compatibility::integers::FheIntVersions;
 use crate::conformance::ParameterSetConformant;
 use crate::core_crypto::prelude::SignedNumeric;
+use crate::high_level_api::booleans::InnerBoolean;
 use crate::high_level_api::global_state;
 use crate::high_level_api::integers::{FheUint, FheUintId, IntegerId};
 use crate::high_level_api::keys::InternalServerKey;
+use crate::high_level_api::simulation::SimulatedInteger;
 use crate::high_level_api::traits::Tagged;
 use crate::integer::block_decomposition::{DecomposableInto, RecomposableSignedInteger};
 use crate::integer::parameters::RadixCiphertextConformanceParams;
@@ -151,6 +153,36 @@ where
         self.ciphertext.move_to_device(device)
     }
 
+    /// Evaluates (in-place) the operations this value depends on, if it was computed
+    /// with lazy evaluation enabled
+    ///
+    /// All the other values recorded in the same graph are evaluated at the same time.
+    /// Does nothing if the value is already evaluated.
+    ///
+    /// See [with_lazy_evaluation](crate::with_lazy_evaluation)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::prelude::*;
+    /// use tfhe::{generate_keys, set_server_key, with_lazy_evaluation, ConfigBuilder, FheInt16};
+    ///
+    /// let (client_key, server_key) = generate_keys(ConfigBuilder::default());
+    /// set_server_key(server_key);
+    ///
+    /// let a = FheInt16::encrypt(-3i16, &client_key);
+    /// let b = FheInt16::encrypt(12849i16, &client_key);
+    ///
+    /// let mut result = with_lazy_evaluation(|| &a * &b - 5i16);
+    /// result.eval();
+    ///
+    /// let decrypted: i16 = result.decrypt(&client_key);
+    /// assert_eq!(decrypted, (-3i16).wrapping_mul(12849i16).wrapping_sub(5i16));
+    /// ```
+    pub fn eval(&mut self) {
+        self.ciphertext.materialize();
+    }
+
     /// Returns the device where the ciphertext is currently on
     pub fn current_device(&self) -> Device {
         self.ciphertext.current_device()
@@ -182,6 +214,7 @@ where
     /// assert_eq!(result, i16::MIN.wrapping_abs());
     /// ```
     pub fn abs(&self) -> Self {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let ciphertext = cpu_key
@@ -197,6 +230,10 @@ where
                     .abs(&*self.ciphertext.on_gpu(streams), streams);
                 Self::new(result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self.ciphertext.on_simulation(sim_key).abs();
+                Self::new(result, sim_key.tag.clone())
+            }
         })
     }
 
@@ -218,6 +255,7 @@ where
     /// assert!(decrypted);
     /// ```
     pub fn is_even(&self) -> FheBool {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let result = cpu_key
@@ -233,6 +271,10 @@ where
                     .is_even(&*self.ciphertext.on_gpu(streams), streams);
                 FheBool::new(result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self.ciphertext.on_simulation(sim_key).is_even();
+                FheBool::new(InnerBoolean::Simulation(result), sim_key.tag.clone())
+            }
         })
     }
 
@@ -254,6 +296,7 @@ where
     /// assert!(decrypted);
     /// ```
     pub fn is_odd(&self) -> FheBool {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let result = cpu_key
@@ -269,6 +312,10 @@ where
                     .is_odd(&*self.ciphertext.on_gpu(streams), streams);
                 FheBool::new(result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = !self.ciphertext.on_simulation(sim_key).is_even();
+                FheBool::new(InnerBoolean::Simulation(result), sim_key.tag.clone())
+            }
         })
     }
 
@@ -290,6 +337,7 @@ where
     /// assert_eq!(decrypted, 0);
     /// ```
     pub fn leading_zeros(&self) -> crate::FheUint32 {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let result = cpu_key
@@ -314,6 +362,17 @@ where
                 );
                 crate::FheUint32::new(result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self.ciphertext.on_simulation(sim_key).leading_zeros();
+                crate::FheUint32::new(
+                    SimulatedInteger::from_scalar(
+                        result,
+                        crate::FheUint32Id::num_bits() as u32,
+                        false,
+                    ),
+                    sim_key.tag.clone(),
+                )
+            }
         })
     }
 
@@ -335,6 +394,7 @@ where
     /// assert_eq!(decrypted, 16);
     /// ```
     pub fn leading_ones(&self) -> crate::FheUint32 {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let result = cpu_key
@@ -359,6 +419,17 @@ where
                 );
                 crate::FheUint32::new(result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self.ciphertext.on_simulation(sim_key).leading_ones();
+                crate::FheUint32::new(
+                    SimulatedInteger::from_scalar(
+                        result,
+                        crate::FheUint32Id::num_bits() as u32,
+                        false,
+                    ),
+                    sim_key.tag.clone(),
+                )
+            }
         })
     }
 
@@ -380,6 +451,7 @@ where
     /// assert_eq!(decrypted, 2);
     /// ```
     pub fn trailing_zeros(&self) -> crate::FheUint32 {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let result = cpu_key
@@ -404,6 +476,17 @@ where
                 );
                 crate::FheUint32::new(result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self.ciphertext.on_simulation(sim_key).trailing_zeros();
+                crate::FheUint32::new(
+                    SimulatedInteger::from_scalar(
+                        result,
+                        crate::FheUint32Id::num_bits() as u32,
+                        false,
+                    ),
+                    sim_key.tag.clone(),
+                )
+            }
         })
     }
 
@@ -425,6 +508,7 @@ where
     /// assert_eq!(decrypted, 2);
     /// ```
     pub fn trailing_ones(&self) -> crate::FheUint32 {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let result = cpu_key
@@ -449,6 +533,17 @@ where
                 );
                 crate::FheUint32::new(result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self.ciphertext.on_simulation(sim_key).trailing_ones();
+                crate::FheUint32::new(
+                    SimulatedInteger::from_scalar(
+                        result,
+                        crate::FheUint32Id::num_bits() as u32,
+                        false,
+                    ),
+                    sim_key.tag.clone(),
+                )
+            }
         })
     }
 
@@ -471,6 +566,7 @@ where
     /// assert_eq!(decrypted, clear_a.count_ones());
     /// ```
     pub fn count_ones(&self) -> crate::FheUint32 {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let result = cpu_key
@@ -486,6 +582,17 @@ where
             InternalServerKey::Cuda(_) => {
                 panic!("Cuda devices do not support count_ones yet");
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self.ciphertext.on_simulation(sim_key).count_ones();
+                crate::FheUint32::new(
+                    SimulatedInteger::from_scalar(
+                        result,
+                        crate::FheUint32Id::num_bits() as u32,
+                        false,
+                    ),
+                    sim_key.tag.clone(),
+                )
+            }
         })
     }
 
@@ -508,6 +615,7 @@ where
     /// assert_eq!(decrypted, clear_a.count_zeros());
     /// ```
     pub fn count_zeros(&self) -> crate::FheUint32 {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let result = cpu_key
@@ -523,6 +631,17 @@ where
             InternalServerKey::Cuda(_) => {
                 panic!("Cuda devices do not support count_zeros yet");
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self.ciphertext.on_simulation(sim_key).count_zeros();
+                crate::FheUint32::new(
+                    SimulatedInteger::from_scalar(
+                        result,
+                        crate::FheUint32Id::num_bits() as u32,
+                        false,
+                    ),
+                    sim_key.tag.clone(),
+                )
+            }
         })
     }
 
@@ -546,6 +665,7 @@ where
     /// assert_eq!(decrypted, 1);
     /// ```
     pub fn ilog2(&self) -> crate::FheUint32 {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let result = cpu_key
@@ -570,6 +690,17 @@ where
                 );
                 crate::FheUint32::new(result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self.ciphertext.on_simulation(sim_key).ilog2(sim_key);
+                crate::FheUint32::new(
+                    SimulatedInteger::from_scalar(
+                        result,
+                        crate::FheUint32Id::num_bits() as u32,
+                        false,
+                    ),
+                    sim_key.tag.clone(),
+                )
+            }
         })
     }
 
@@ -597,6 +728,7 @@ where
     /// assert_eq!(decrypted, 15); // result is meaningless
     /// ```
     pub fn checked_ilog2(&self) -> (crate::FheUint32, FheBool) {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let (result, is_ok) = cpu_key
@@ -627,6 +759,23 @@ where
                     FheBool::new(is_ok, cuda_key.tag.clone()),
                 )
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let (result, is_ok) = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .checked_ilog2(sim_key);
+                (
+                    crate::FheUint32::new(
+                        SimulatedInteger::from_scalar(
+                            result,
+                            crate::FheUint32Id::num_bits() as u32,
+                            false,
+                        ),
+                        sim_key.tag.clone(),
+                    ),
+                    FheBool::new(InnerBoolean::Simulation(is_ok), sim_key.tag.clone()),
+                )
+            }
         })
     }
 
@@ -665,6 +814,10 @@ where
     where
         Clear: RecomposableSignedInteger,
     {
+        // Values of the simulation backend are in clear
+        if let SignedRadixCiphertext::Simulation(value) = &self.ciphertext {
+            return Ok(value.to_signed());
+        }
         self.ciphertext.on_cpu().decrypt_trivial()
     }
 
@@ -689,6 +842,7 @@ where
     /// assert_eq!(decrypted, msg.reverse_bits());
     /// ```
     pub fn reverse_bits(&self) -> Self {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let sk = &cpu_key.pbs_key();
@@ -701,6 +855,10 @@ where
             InternalServerKey::Cuda(_) => {
                 panic!("Cuda devices do not support reverse yet");
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self.ciphertext.on_simulation(sim_key).reverse_bits();
+                Self::new(result, sim_key.tag.clone())
+            }
         })
     }
 
@@ -730,6 +888,7 @@ where
     where
         Clear: SignedNumeric + DecomposableInto<u64>,
     {
+        global_state::check_tags([&condition.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let sk = cpu_key.pbs_key();
@@ -748,6 +907,15 @@ where
             InternalServerKey::Cuda(_) => {
                 panic!("Cuda devices do not support if_then_else yet");
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let value = if condition.ciphertext.on_simulation(sim_key) {
+                    true_value
+                } else {
+                    false_value
+                };
+                let result = SimulatedInteger::from_scalar(value, Id::num_bits() as u32, true);
+                Self::new(result, sim_key.tag.clone())
+            }
         })
     }
 
@@ -791,6 +959,7 @@ where
     /// assert_eq!(decrypted, i32::MAX as i16);
     /// ```
     fn cast_from(input: FheInt<FromId>) -> Self {
+        global_state::check_tags([&input.tag]);
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let target_num_blocks = IntoId::num_blocks(cpu_key.message_modulus());
@@ -809,6 +978,13 @@ where
                 );
                 Self::new(new_ciphertext, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let casted = input
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cast(IntoId::num_bits() as u32, true);
+                Self::new(casted, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -836,6 +1012,7 @@ where
     /// assert_eq!(decrypted, u32::MAX as i16);
     /// ```
     fn cast_from(input: FheUint<FromId>) -> Self {
+        global_state::check_tags([&input.tag]);
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let new_ciphertext = cpu_key.pbs_key().cast_to_signed(
@@ -853,6 +1030,13 @@ where
                 );
                 Self::new(new_ciphertext, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let casted = input
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cast(IntoId::num_bits() as u32, true);
+                Self::new(casted, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -879,6 +1063,7 @@ where
     /// assert_eq!(decrypted, i16::from(true));
     /// ```
     fn cast_from(input: FheBool) -> Self {
+        global_state::check_tags([&input.tag]);
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let ciphertext = input
@@ -900,6 +1085,11 @@ where
                 );
                 Self::new(inner, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let value = input.ciphertext.on_simulation(sim_key);
+                let ciphertext = SimulatedInteger::from_bool(value, Id::num_bits() as u32, true);
+                Self::new(ciphertext, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -2,8 +2,10 @@ use crate::core_crypto::prelude::SignedNumeric;
 use crate::high_level_api::global_state;
 #[cfg(feature = "gpu")]
 use crate::high_level_api::global_state::with_thread_local_cuda_streams;
+use crate::high_level_api::integers::signed::inner::SignedRadixCiphertext;
 use crate::high_level_api::integers::FheIntId;
 use crate::high_level_api::keys::InternalServerKey;
+use crate::high_level_api::simulation::SimulatedInteger;
 use crate::integer::block_decomposition::{DecomposableInto, RecomposableSignedInteger};
 #[cfg(feature = "gpu")]
 use crate::integer::gpu::ciphertext::CudaSignedRadixCiphertext;
@@ -38,6 +40,9 @@ where
     /// assert_eq!(decrypted, 7288i32);
     /// ```
     fn decrypt(&self, key: &ClientKey) -> ClearType {
+        if let SignedRadixCiphertext::Simulation(value) = &self.ciphertext {
+            return value.to_signed();
+        }
         key.key.key.decrypt_signed_radix(&self.ciphertext.on_cpu())
     }
 }
@@ -121,6 +126,10 @@ where
                 );
                 Ok(Self::new(inner, cuda_key.tag.clone()))
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let value = SimulatedInteger::from_scalar(value, Id::num_bits() as u32, true);
+                Ok(Self::new(value, sim_key.tag.clone()))
+            }
         })
     }
 }
@@ -1,23 +1,31 @@
 use crate::backward_compatibility::integers::SignedRadixCiphertextVersionedOwned;
 #[cfg(feature = "gpu")]
 use crate::core_crypto::gpu::CudaStreams;
-use crate::high_level_api::details::MaybeCloned;
+use crate::high_level_api::details::{cpu_data_versioned_schema, MaybeCloned};
 use crate::high_level_api::global_state;
 #[cfg(feature = "gpu")]
 use crate::high_level_api::global_state::{
     with_thread_local_cuda_streams, with_thread_local_cuda_streams_for_gpu_indexes,
 };
+use crate::high_level_api::keys::SimulationServerKey;
+use crate::high_level_api::lazy::LazyValue;
+use crate::high_level_api::simulation::SimulatedInteger;
 #[cfg(feature = "gpu")]
 use crate::integer::gpu::ciphertext::CudaIntegerRadixCiphertext;
 #[cfg(feature = "gpu")]
 use crate::integer::gpu::ciphertext::CudaSignedRadixCiphertext;
 use crate::Device;
 use serde::{Deserializer, Serializer};
+use tfhe_versionable::schema::{DataSchema, SchemaRegistry};
 use tfhe_versionable::{Unversionize, UnversionizeError, Versionize, VersionizeOwned};
 pub(crate) enum SignedRadixCiphertext {
     Cpu(crate::integer::SignedRadixCiphertext),
     #[cfg(feature = "gpu")]
     Cuda(CudaSignedRadixCiphertext),
+    /// Result of operations recorded with lazy evaluation, computed on the CPU on demand
+    Lazy(LazyValue),
+    /// Clear value of the simulation backend
+    Simulation(SimulatedInteger),
 }
 
 impl From<crate::integer::SignedRadixCiphertext> for SignedRadixCiphertext {
@@ -26,6 +34,12 @@ impl From<crate::integer::SignedRadixCiphertext> for SignedRadixCiphertext {
     }
 }
 
+impl From<SimulatedInteger> for SignedRadixCiphertext {
+    fn from(value: SimulatedInteger) -> Self {
+        Self::Simulation(value)
+    }
+}
+
 #[cfg(feature = "gpu")]
 impl From<CudaSignedRadixCiphertext> for SignedRadixCiphertext {
     fn from(value: CudaSignedRadixCiphertext) -> Self {
@@ -42,6 +56,8 @@ impl Clone for SignedRadixCiphertext {
                 let inner = inner.duplicate(streams);
                 Self::Cuda(inner)
             }),
+            Self::Lazy(value) => Self::Lazy(value.clone()),
+            Self::Simulation(value) => Self::Simulation(*value),
         }
     }
 }
@@ -94,6 +110,15 @@ impl VersionizeOwned for SignedRadixCiphertext {
             cpu_data.into_owned().versionize_owned(),
         ))
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        cpu_data_versioned_schema::<
+            Self,
+            Self::VersionedOwned,
+            SignedRadixCiphertextVersionOwned,
+            crate::integer::SignedRadixCiphertext,
+        >(registry)
+    }
 }
 
 impl Unversionize for SignedRadixCiphertext {
@@ -115,6 +140,8 @@ impl SignedRadixCiphertext {
             Self::Cpu(_) => Device::Cpu,
             #[cfg(feature = "gpu")]
             Self::Cuda(_) => Device::CudaGpu,
+            Self::Lazy(_) => Device::Cpu,
+            Self::Simulation(_) => Device::Simulation,
         }
     }
 
@@ -130,6 +157,14 @@ impl SignedRadixCiphertext {
                     MaybeCloned::Cloned(cpu_ct)
                 })
             }
+            Self::Lazy(value) => MaybeCloned::Cloned(crate::integer::SignedRadixCiphertext::from(
+                value.materialize().blocks.clone(),
+            )),
+            Self::Simulation(value) => {
+                MaybeCloned::Cloned(global_state::with_simulation_internal_keys(|key| {
+                    value.to_signed_radix(key)
+                }))
+            }
         }
     }
 
@@ -145,6 +180,13 @@ impl SignedRadixCiphertext {
                 let ct = CudaSignedRadixCiphertext::from_signed_radix_ciphertext(ct, streams);
                 MaybeCloned::Cloned(ct)
             }),
+            Self::Lazy(_) | Self::Simulation(_) => {
+                let ct = CudaSignedRadixCiphertext::from_signed_radix_ciphertext(
+                    &self.on_cpu(),
+                    streams,
+                );
+                MaybeCloned::Cloned(ct)
+            }
             #[cfg(feature = "gpu")]
             Self::Cuda(ct) => {
                 if ct.gpu_indexes() == streams.gpu_indexes() {
@@ -159,7 +201,10 @@ impl SignedRadixCiphertext {
     pub(crate) fn as_cpu_mut(&mut self) -> &mut crate::integer::SignedRadixCiphertext {
         match self {
             Self::Cpu(radix_ct) => radix_ct,
-            #[cfg(feature = "gpu")]
+            Self::Lazy(_) => {
+                self.materialize();
+                self.as_cpu_mut()
+            }
             _ => {
                 self.move_to_device(Device::Cpu);
                 self.as_cpu_mut()
@@ -185,6 +230,14 @@ impl SignedRadixCiphertext {
                 }
                 cuda_ct
             }
+            Self::Lazy(_) => {
+                self.materialize();
+                self.as_gpu_mut(streams)
+            }
+            Self::Simulation(_) => {
+                self.move_to_device(Device::Cpu);
+                self.as_gpu_mut(streams)
+            }
         }
     }
 
@@ -197,6 +250,12 @@ impl SignedRadixCiphertext {
                     ct.to_signed_radix_ciphertext(streams)
                 })
             }
+            Self::Lazy(value) => {
+                crate::integer::SignedRadixCiphertext::from(value.into_value().blocks)
+            }
+            Self::Simulation(value) => {
+                global_state::with_simulation_internal_keys(|key| value.to_signed_radix(key))
+            }
         }
     }
 
@@ -208,15 +267,36 @@ impl SignedRadixCiphertext {
                 CudaSignedRadixCiphertext::from_signed_radix_ciphertext(&cpu_ct, streams)
             }
             Self::Cuda(ct) => ct.move_to_stream(streams),
+            Self::Lazy(_) | Self::Simulation(_) => {
+                CudaSignedRadixCiphertext::from_signed_radix_ciphertext(&self.on_cpu(), streams)
+            }
         }
     }
 
     #[allow(clippy::needless_pass_by_ref_mut)]
     pub(crate) fn move_to_device(&mut self, device: Device) {
         match (&self, device) {
-            (Self::Cpu(_), Device::Cpu) => {
+            (Self::Cpu(_), Device::Cpu) | (Self::Simulation(_), Device::Simulation) => {
                 // Nothing to do, we already are on the correct device
             }
+            (Self::Lazy(_), Device::Cpu) => {
+                // Nothing to do, lazy values are evaluated on the CPU
+            }
+            (Self::Lazy(_), _) => {
+                self.materialize();
+                self.move_to_device(device);
+            }
+            (Self::Simulation(value), _) => {
+                let cpu_ct =
+                    global_state::with_simulation_internal_keys(|key| value.to_signed_radix(key));
+                *self = Self::Cpu(cpu_ct);
+                self.move_to_device(device);
+            }
+            (_, Device::Simulation) => {
+                let value =
+                    global_state::with_simulation_internal_keys(|key| self.on_simulation(key));
+                *self = Self::Simulation(value);
+            }
             #[cfg(feature = "gpu")]
             (Self::Cuda(cuda_ct), Device::CudaGpu) => {
                 // We are on a GPU, but it may not be the correct one
@@ -249,6 +329,28 @@ impl SignedRadixCiphertext {
         }
     }
 
+    /// Returns the clear value of the simulation backend, decrypting the ciphertext with the
+    /// client key of the simulation key if needed
+    pub(crate) fn on_simulation(&self, key: &SimulationServerKey) -> SimulatedInteger {
+        match self {
+            Self::Simulation(value) => *value,
+            _ => SimulatedInteger::from_signed_radix(&self.on_cpu(), key),
+        }
+    }
+
+    /// Evaluates the value if it is lazy, the result stays on the CPU
+    pub(crate) fn materialize(&mut self) {
+        if matches!(self, Self::Lazy(_)) {
+            let placeholder = Self::Cpu(crate::integer::SignedRadixCiphertext::from(vec![]));
+            let Self::Lazy(value) = std::mem::replace(self, placeholder) else {
+                unreachable!()
+            };
+            *self = Self::Cpu(crate::integer::SignedRadixCiphertext::from(
+                value.into_value().blocks,
+            ));
+        }
+    }
+
     #[inline]
     pub(crate) fn move_to_device_of_server_key_if_set(&mut self) {
         if let Some(device) = global_state::device_of_internal_keys() {
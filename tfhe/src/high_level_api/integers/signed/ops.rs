@@ -1,14 +1,19 @@
+use super::inner::SignedRadixCiphertext;
+use crate::high_level_api::booleans::InnerBoolean;
 #[cfg(feature = "gpu")]
 use crate::high_level_api::details::MaybeCloned;
 use crate::high_level_api::global_state;
 use crate::high_level_api::integers::{FheIntId, FheUintId};
 use crate::high_level_api::keys::InternalServerKey;
+use crate::high_level_api::lazy::{self, LazyOp};
+use crate::high_level_api::simulation::SimulatedInteger;
 use crate::high_level_api::traits::{
     DivRem, FheEq, FheMax, FheMin, FheOrd, RotateLeft, RotateLeftAssign, RotateRight,
     RotateRightAssign,
 };
 #[cfg(feature = "gpu")]
 use crate::integer::gpu::ciphertext::CudaIntegerRadixCiphertext;
+use crate::integer::server_key::operation_span;
 use crate::{FheBool, FheInt, FheUint};
 use std::borrow::Borrow;
 use std::ops::{
@@ -51,6 +56,7 @@ where
     /// assert_eq!(decrypted, clears.into_iter().sum::<i16>());
     /// ```
     fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        let iter = iter.inspect(|elem| global_state::check_tags([&elem.tag]));
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let ciphertexts = iter
@@ -104,6 +110,13 @@ where
                     Self::new(inner, cuda_key.tag.clone())
                 })
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = iter.fold(
+                    SimulatedInteger::zero(Id::num_bits() as u32, true),
+                    |acc, elem| acc.add(&elem.ciphertext.on_simulation(sim_key)),
+                );
+                Self::new(result, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -134,6 +147,7 @@ where
     /// assert_eq!(decrypted_max, 2i16);
     /// ```
     fn max(&self, rhs: &Self) -> Self::Output {
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -150,6 +164,13 @@ where
                 );
                 Self::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .max(&rhs.ciphertext.on_simulation(sim_key));
+                Self::new(inner_result, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -180,6 +201,7 @@ where
     /// assert_eq!(decrypted_min, -1i16);
     /// ```
     fn min(&self, rhs: &Self) -> Self::Output {
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -196,6 +218,13 @@ where
                 );
                 Self::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .min(&rhs.ciphertext.on_simulation(sim_key));
+                Self::new(inner_result, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -237,6 +266,10 @@ where
     /// assert_eq!(decrypted, -1i16 == 2i16);
     /// ```
     fn eq(&self, rhs: &Self) -> FheBool {
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_comparison(LazyOp::Eq, self, rhs) {
+            return result;
+        }
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -253,6 +286,14 @@ where
                 );
                 FheBool::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp(&rhs.ciphertext.on_simulation(sim_key))
+                    .is_eq();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 
@@ -276,6 +317,10 @@ where
     /// assert_eq!(decrypted, -1i16 != 2i16);
     /// ```
     fn ne(&self, rhs: &Self) -> FheBool {
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_comparison(LazyOp::Ne, self, rhs) {
+            return result;
+        }
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -292,6 +337,14 @@ where
                 );
                 FheBool::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp(&rhs.ciphertext.on_simulation(sim_key))
+                    .is_ne();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 }
@@ -341,6 +394,10 @@ where
     /// assert_eq!(decrypted, -1i16 < 2i16);
     /// ```
     fn lt(&self, rhs: &Self) -> FheBool {
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_comparison(LazyOp::Lt, self, rhs) {
+            return result;
+        }
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -357,6 +414,14 @@ where
                 );
                 FheBool::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp(&rhs.ciphertext.on_simulation(sim_key))
+                    .is_lt();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 
@@ -380,6 +445,10 @@ where
     /// assert_eq!(decrypted, -1i16 <= 2i16);
     /// ```
     fn le(&self, rhs: &Self) -> FheBool {
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_comparison(LazyOp::Le, self, rhs) {
+            return result;
+        }
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -396,6 +465,14 @@ where
                 );
                 FheBool::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp(&rhs.ciphertext.on_simulation(sim_key))
+                    .is_le();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 
@@ -419,6 +496,10 @@ where
     /// assert_eq!(decrypted, -1i16 > 2i16);
     /// ```
     fn gt(&self, rhs: &Self) -> FheBool {
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_comparison(LazyOp::Gt, self, rhs) {
+            return result;
+        }
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -435,6 +516,14 @@ where
                 );
                 FheBool::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp(&rhs.ciphertext.on_simulation(sim_key))
+                    .is_gt();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 
@@ -458,6 +547,10 @@ where
     /// assert_eq!(decrypted, -1i16 >= 2i16);
     /// ```
     fn ge(&self, rhs: &Self) -> FheBool {
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_comparison(LazyOp::Ge, self, rhs) {
+            return result;
+        }
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -474,6 +567,14 @@ where
                 );
                 FheBool::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp(&rhs.ciphertext.on_simulation(sim_key))
+                    .is_ge();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 }
@@ -537,6 +638,7 @@ where
     /// assert_eq!(remainder, -23i16 % 3i16);
     /// ```
     fn div_rem(self, rhs: Self) -> Self::Output {
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let (q, r) = cpu_key
@@ -559,6 +661,16 @@ where
                     FheInt::<Id>::new(r, cuda_key.tag.clone()),
                 )
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let (q, r) = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .div_rem(&rhs.ciphertext.on_simulation(sim_key));
+                (
+                    FheInt::<Id>::new(q, sim_key.tag.clone()),
+                    FheInt::<Id>::new(r, sim_key.tag.clone()),
+                )
+            }
         })
     }
 }
@@ -593,6 +705,7 @@ macro_rules! generic_integer_impl_operation (
 
             $(#[$outer])*
             fn $rust_trait_method(self, rhs: B) -> Self::Output {
+                operation_span!(stringify!($rust_trait_method), FheInt::<Id>::num_bits());
                 $closure(self, rhs.borrow())
             }
         }
@@ -623,6 +736,10 @@ generic_integer_impl_operation!(
     rust_trait: Add(add),
     implem: {
         |lhs: &FheInt<_>, rhs: &FheInt<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
+            if let Some(result) = lazy::record_binary_op(LazyOp::Add, lhs, rhs) {
+                return result;
+            }
             global_state::with_internal_keys(|key| match key {
                 InternalServerKey::Cpu(cpu_key) => {
                     let inner_result = cpu_key
@@ -638,6 +755,10 @@ generic_integer_impl_operation!(
                         FheInt::new(inner_result, cuda_key.tag.clone())
                     })
                 }
+                InternalServerKey::Simulation(sim_key) => {
+                    let inner_result = lhs.ciphertext.on_simulation(sim_key).add(&rhs.ciphertext.on_simulation(sim_key));
+                    FheInt::new(inner_result, sim_key.tag.clone())
+                }
             })
         }
     },
@@ -666,6 +787,10 @@ generic_integer_impl_operation!(
     rust_trait: Sub(sub),
     implem: {
         |lhs: &FheInt<_>, rhs: &FheInt<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
+            if let Some(result) = lazy::record_binary_op(LazyOp::Sub, lhs, rhs) {
+                return result;
+            }
             global_state::with_internal_keys(|key| match key {
                 InternalServerKey::Cpu(cpu_key) => {
                     let inner_result = cpu_key
@@ -681,6 +806,10 @@ generic_integer_impl_operation!(
                         FheInt::new(inner_result, cuda_key.tag.clone())
                     })
                 }
+                InternalServerKey::Simulation(sim_key) => {
+                    let inner_result = lhs.ciphertext.on_simulation(sim_key).sub(&rhs.ciphertext.on_simulation(sim_key));
+                    FheInt::new(inner_result, sim_key.tag.clone())
+                }
             })
         }
     },
@@ -709,6 +838,10 @@ generic_integer_impl_operation!(
     rust_trait: Mul(mul),
     implem: {
         |lhs: &FheInt<_>, rhs: &FheInt<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
+            if let Some(result) = lazy::record_binary_op(LazyOp::Mul, lhs, rhs) {
+                return result;
+            }
             global_state::with_internal_keys(|key| match key {
                 InternalServerKey::Cpu(cpu_key) => {
                     let inner_result = cpu_key
@@ -724,6 +857,10 @@ generic_integer_impl_operation!(
                         FheInt::new(inner_result, cuda_key.tag.clone())
                     })
                 }
+                InternalServerKey::Simulation(sim_key) => {
+                    let inner_result = lhs.ciphertext.on_simulation(sim_key).mul(&rhs.ciphertext.on_simulation(sim_key));
+                    FheInt::new(inner_result, sim_key.tag.clone())
+                }
             })
         }
     },
@@ -750,6 +887,10 @@ generic_integer_impl_operation!(
     rust_trait: BitAnd(bitand),
     implem: {
         |lhs: &FheInt<_>, rhs: &FheInt<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
+            if let Some(result) = lazy::record_binary_op(LazyOp::BitAnd, lhs, rhs) {
+                return result;
+            }
             global_state::with_internal_keys(|key| match key {
                 InternalServerKey::Cpu(cpu_key) => {
                     let inner_result = cpu_key
@@ -765,6 +906,10 @@ generic_integer_impl_operation!(
                         FheInt::new(inner_result, cuda_key.tag.clone())
                     })
                 }
+                InternalServerKey::Simulation(sim_key) => {
+                    let inner_result = lhs.ciphertext.on_simulation(sim_key).bitand(&rhs.ciphertext.on_simulation(sim_key));
+                    FheInt::new(inner_result, sim_key.tag.clone())
+                }
             })
         }
     },
@@ -791,6 +936,10 @@ generic_integer_impl_operation!(
     rust_trait: BitOr(bitor),
     implem: {
         |lhs: &FheInt<_>, rhs: &FheInt<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
+            if let Some(result) = lazy::record_binary_op(LazyOp::BitOr, lhs, rhs) {
+                return result;
+            }
             global_state::with_internal_keys(|key| match key {
                 InternalServerKey::Cpu(cpu_key) => {
                     let inner_result = cpu_key
@@ -806,6 +955,10 @@ generic_integer_impl_operation!(
                         FheInt::new(inner_result, cuda_key.tag.clone())
                     })
                 }
+                InternalServerKey::Simulation(sim_key) => {
+                    let inner_result = lhs.ciphertext.on_simulation(sim_key).bitor(&rhs.ciphertext.on_simulation(sim_key));
+                    FheInt::new(inner_result, sim_key.tag.clone())
+                }
             })
         }
     },
@@ -832,6 +985,10 @@ generic_integer_impl_operation!(
     rust_trait: BitXor(bitxor),
     implem: {
         |lhs: &FheInt<_>, rhs: &FheInt<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
+            if let Some(result) = lazy::record_binary_op(LazyOp::BitXor, lhs, rhs) {
+                return result;
+            }
             global_state::with_internal_keys(|key| match key {
                 InternalServerKey::Cpu(cpu_key) => {
                     let inner_result = cpu_key
@@ -847,6 +1004,10 @@ generic_integer_impl_operation!(
                         FheInt::new(inner_result, cuda_key.tag.clone())
                     })
                 }
+                InternalServerKey::Simulation(sim_key) => {
+                    let inner_result = lhs.ciphertext.on_simulation(sim_key).bitxor(&rhs.ciphertext.on_simulation(sim_key));
+                    FheInt::new(inner_result, sim_key.tag.clone())
+                }
             })
         }
     },
@@ -881,6 +1042,7 @@ generic_integer_impl_operation!(
     rust_trait: Div(div),
     implem: {
         |lhs: &FheInt<_>, rhs: &FheInt<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
             global_state::with_internal_keys(|key| match key {
                 InternalServerKey::Cpu(cpu_key) => {
                     let inner_result = cpu_key
@@ -897,6 +1059,10 @@ generic_integer_impl_operation!(
                             .div(&*lhs.ciphertext.on_gpu(streams), &*rhs.ciphertext.on_gpu(streams), streams);
                     FheInt::new(inner_result, cuda_key.tag.clone())
                 }),
+                InternalServerKey::Simulation(sim_key) => {
+                    let inner_result = lhs.ciphertext.on_simulation(sim_key).div(&rhs.ciphertext.on_simulation(sim_key));
+                    FheInt::new(inner_result, sim_key.tag.clone())
+                }
             })
         }
     },
@@ -932,6 +1098,7 @@ generic_integer_impl_operation!(
     rust_trait: Rem(rem),
     implem: {
         |lhs: &FheInt<_>, rhs: &FheInt<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
             global_state::with_internal_keys(|key| match key {
                 InternalServerKey::Cpu(cpu_key) => {
                     let inner_result = cpu_key
@@ -948,6 +1115,10 @@ generic_integer_impl_operation!(
                             .rem(&*lhs.ciphertext.on_gpu(streams), &*rhs.ciphertext.on_gpu(streams), streams);
                     FheInt::new(inner_result, cuda_key.tag.clone())
                 }),
+                InternalServerKey::Simulation(sim_key) => {
+                    let inner_result = lhs.ciphertext.on_simulation(sim_key).rem(&rhs.ciphertext.on_simulation(sim_key));
+                    FheInt::new(inner_result, sim_key.tag.clone())
+                }
             })
         }
     },
@@ -1017,6 +1188,7 @@ macro_rules! generic_integer_impl_shift_rotate (
 
             $(#[$outer])*
             fn $rust_trait_method(self, rhs: &FheUint<Id2>) -> Self::Output {
+                operation_span!(stringify!($rust_trait_method), FheInt::<Id>::num_bits());
                 $closure(self, rhs.borrow())
             }
         }
@@ -1045,6 +1217,7 @@ generic_integer_impl_shift_rotate!(
     rust_trait: Shl(shl),
     implem: {
         |lhs: &FheInt<_>, rhs: &FheUint<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
             global_state::with_internal_keys(|key| {
                 match key {
                     InternalServerKey::Cpu(cpu_key) => {
@@ -1061,6 +1234,10 @@ generic_integer_impl_shift_rotate!(
                             FheInt::new(inner_result, cuda_key.tag.clone())
                         })
                     }
+                    InternalServerKey::Simulation(sim_key) => {
+                        let inner_result = lhs.ciphertext.on_simulation(sim_key).shl_encrypted(&rhs.ciphertext.on_simulation(sim_key));
+                        FheInt::new(inner_result, sim_key.tag.clone())
+                    }
                 }
             })
         }
@@ -1089,6 +1266,7 @@ generic_integer_impl_shift_rotate!(
     rust_trait: Shr(shr),
     implem: {
         |lhs: &FheInt<_>, rhs: &FheUint<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
             global_state::with_internal_keys(|key| {
                 match key {
                     InternalServerKey::Cpu(cpu_key) => {
@@ -1105,6 +1283,10 @@ generic_integer_impl_shift_rotate!(
                             FheInt::new(inner_result, cuda_key.tag.clone())
                         })
                     }
+                    InternalServerKey::Simulation(sim_key) => {
+                        let inner_result = lhs.ciphertext.on_simulation(sim_key).shr_encrypted(&rhs.ciphertext.on_simulation(sim_key));
+                        FheInt::new(inner_result, sim_key.tag.clone())
+                    }
                 }
             })
         }
@@ -1133,6 +1315,7 @@ generic_integer_impl_shift_rotate!(
     rust_trait: RotateLeft(rotate_left),
     implem: {
         |lhs: &FheInt<_>, rhs: &FheUint<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
             global_state::with_internal_keys(|key| {
                 match key {
                     InternalServerKey::Cpu(cpu_key) => {
@@ -1149,6 +1332,10 @@ generic_integer_impl_shift_rotate!(
                             FheInt::new(inner_result, cuda_key.tag.clone())
                         })
                     }
+                    InternalServerKey::Simulation(sim_key) => {
+                        let inner_result = lhs.ciphertext.on_simulation(sim_key).rotate_left_encrypted(&rhs.ciphertext.on_simulation(sim_key));
+                        FheInt::new(inner_result, sim_key.tag.clone())
+                    }
                 }
             })
         }
@@ -1177,6 +1364,7 @@ generic_integer_impl_shift_rotate!(
     rust_trait: RotateRight(rotate_right),
     implem: {
         |lhs: &FheInt<_>, rhs: &FheUint<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
             global_state::with_internal_keys(|key| {
                 match key {
                     InternalServerKey::Cpu(cpu_key) => {
@@ -1193,6 +1381,10 @@ generic_integer_impl_shift_rotate!(
                             FheInt::new(inner_result, cuda_key.tag.clone())
                         })
                     }
+                    InternalServerKey::Simulation(sim_key) => {
+                        let inner_result = lhs.ciphertext.on_simulation(sim_key).rotate_right_encrypted(&rhs.ciphertext.on_simulation(sim_key));
+                        FheInt::new(inner_result, sim_key.tag.clone())
+                    }
                 }
             })
         }
@@ -1227,7 +1419,13 @@ where
     /// assert_eq!(result, 3i16.wrapping_add(7849i16));
     /// ```
     fn add_assign(&mut self, rhs: I) {
+        operation_span!("add_assign", FheInt::<Id>::num_bits());
         let rhs = rhs.borrow();
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_binary_op(LazyOp::Add, self, rhs) {
+            *self = result;
+            return;
+        }
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().add_assign_parallelized(
@@ -1245,6 +1443,13 @@ where
                     );
                 })
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .add(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = SignedRadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1274,7 +1479,13 @@ where
     /// assert_eq!(result, 3i16.wrapping_sub(7849i16));
     /// ```
     fn sub_assign(&mut self, rhs: I) {
+        operation_span!("sub_assign", FheInt::<Id>::num_bits());
         let rhs = rhs.borrow();
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_binary_op(LazyOp::Sub, self, rhs) {
+            *self = result;
+            return;
+        }
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().sub_assign_parallelized(
@@ -1292,6 +1503,13 @@ where
                     );
                 })
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .sub(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = SignedRadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1321,7 +1539,13 @@ where
     /// assert_eq!(result, 3i16.wrapping_mul(7849i16));
     /// ```
     fn mul_assign(&mut self, rhs: I) {
+        operation_span!("mul_assign", FheInt::<Id>::num_bits());
         let rhs = rhs.borrow();
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_binary_op(LazyOp::Mul, self, rhs) {
+            *self = result;
+            return;
+        }
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().mul_assign_parallelized(
@@ -1339,6 +1563,13 @@ where
                     );
                 })
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .mul(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = SignedRadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1366,7 +1597,13 @@ where
     /// assert_eq!(result, 3i16 & 7849i16);
     /// ```
     fn bitand_assign(&mut self, rhs: I) {
+        operation_span!("bitand_assign", FheInt::<Id>::num_bits());
         let rhs = rhs.borrow();
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_binary_op(LazyOp::BitAnd, self, rhs) {
+            *self = result;
+            return;
+        }
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().bitand_assign_parallelized(
@@ -1384,6 +1621,13 @@ where
                     );
                 })
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .bitand(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = SignedRadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1411,7 +1655,13 @@ where
     /// assert_eq!(result, 3i16 | 7849i16);
     /// ```
     fn bitor_assign(&mut self, rhs: I) {
+        operation_span!("bitor_assign", FheInt::<Id>::num_bits());
         let rhs = rhs.borrow();
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_binary_op(LazyOp::BitOr, self, rhs) {
+            *self = result;
+            return;
+        }
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().bitor_assign_parallelized(
@@ -1429,6 +1679,13 @@ where
                     );
                 })
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .bitor(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = SignedRadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1456,7 +1713,13 @@ where
     /// assert_eq!(result, 3i16 ^ 7849i16);
     /// ```
     fn bitxor_assign(&mut self, rhs: I) {
+        operation_span!("bitxor_assign", FheInt::<Id>::num_bits());
         let rhs = rhs.borrow();
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_binary_op(LazyOp::BitXor, self, rhs) {
+            *self = result;
+            return;
+        }
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().bitxor_assign_parallelized(
@@ -1474,6 +1737,13 @@ where
                     );
                 })
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .bitxor(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = SignedRadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1506,7 +1776,9 @@ where
     /// assert_eq!(result, 7849i16 / 3i16);
     /// ```
     fn div_assign(&mut self, rhs: I) {
+        operation_span!("div_assign", FheInt::<Id>::num_bits());
         let rhs = rhs.borrow();
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().div_assign_parallelized(
@@ -1526,6 +1798,13 @@ where
                     *cuda_lhs = cuda_result;
                 });
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .div(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = SignedRadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1558,7 +1837,9 @@ where
     /// assert_eq!(result, 7849i16 % 3i16);
     /// ```
     fn rem_assign(&mut self, rhs: I) {
+        operation_span!("rem_assign", FheInt::<Id>::num_bits());
         let rhs = rhs.borrow();
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().rem_assign_parallelized(
@@ -1578,6 +1859,13 @@ where
                     *cuda_lhs = cuda_result;
                 });
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .rem(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = SignedRadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1616,6 +1904,7 @@ where
     /// assert_eq!(result, 7849i16 << 3u16);
     /// ```
     fn shl_assign(&mut self, rhs: &FheUint<Id2>) {
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().left_shift_assign_parallelized(
@@ -1633,6 +1922,13 @@ where
                     );
                 });
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .shl_encrypted(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = SignedRadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1670,6 +1966,7 @@ where
     /// assert_eq!(result, 7849i16 >> 3u16);
     /// ```
     fn shr_assign(&mut self, rhs: &FheUint<Id2>) {
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().right_shift_assign_parallelized(
@@ -1687,6 +1984,13 @@ where
                     );
                 });
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .shr_encrypted(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = SignedRadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1725,6 +2029,7 @@ where
     /// assert_eq!(result, 7849i16.rotate_left(3));
     /// ```
     fn rotate_left_assign(&mut self, rhs: &FheUint<Id2>) {
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().rotate_left_assign_parallelized(
@@ -1742,6 +2047,13 @@ where
                     );
                 });
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .rotate_left_encrypted(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = SignedRadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1780,6 +2092,7 @@ where
     /// assert_eq!(result, 7849i16.rotate_right(3));
     /// ```
     fn rotate_right_assign(&mut self, rhs: &FheUint<Id2>) {
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().rotate_right_assign_parallelized(
@@ -1797,6 +2110,13 @@ where
                     );
                 });
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .rotate_right_encrypted(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = SignedRadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1853,6 +2173,7 @@ where
     /// assert_eq!(result, 3i16);
     /// ```
     fn neg(self) -> Self::Output {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let ciphertext = cpu_key
@@ -1868,6 +2189,10 @@ where
                     .neg(&*self.ciphertext.on_gpu(streams), streams);
                 FheInt::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self.ciphertext.on_simulation(sim_key).neg();
+                FheInt::new(inner_result, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -1924,6 +2249,7 @@ where
     /// assert_eq!(result, !-3i16);
     /// ```
     fn not(self) -> Self::Output {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let ciphertext = cpu_key.pbs_key().bitnot(&*self.ciphertext.on_cpu());
@@ -1937,6 +2263,10 @@ where
                     .bitnot(&*self.ciphertext.on_gpu(streams), streams);
                 FheInt::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self.ciphertext.on_simulation(sim_key).bitnot();
+                FheInt::new(inner_result, sim_key.tag.clone())
+            }
         })
     }
 }
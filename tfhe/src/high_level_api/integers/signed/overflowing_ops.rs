@@ -1,4 +1,5 @@
 use crate::core_crypto::prelude::SignedNumeric;
+use crate::high_level_api::booleans::InnerBoolean;
 use crate::high_level_api::global_state;
 #[cfg(feature = "gpu")]
 use crate::high_level_api::global_state::with_thread_local_cuda_streams;
@@ -41,6 +42,7 @@ where
     /// assert!(overflowed.decrypt(&client_key));
     /// ```
     fn overflowing_add(self, other: Self) -> (Self::Output, FheBool) {
+        global_state::check_tags([&self.tag, &other.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let (result, overflow) = cpu_key.pbs_key().signed_overflowing_add_parallelized(
@@ -64,6 +66,15 @@ where
                     FheBool::new(overflow, cuda_key.tag.clone()),
                 )
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let value = self.ciphertext.on_simulation(sim_key);
+                let (result, overflow) =
+                    value.overflowing_add(&other.ciphertext.on_simulation(sim_key));
+                (
+                    FheInt::<Id>::new(result, sim_key.tag.clone()),
+                    FheBool::new(InnerBoolean::Simulation(overflow), sim_key.tag.clone()),
+                )
+            }
         })
     }
 }
@@ -138,6 +149,7 @@ where
     /// assert!(overflowed.decrypt(&client_key));
     /// ```
     fn overflowing_add(self, other: Clear) -> (Self::Output, FheBool) {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let (result, overflow) = cpu_key
@@ -160,6 +172,14 @@ where
                     FheBool::new(overflow, cuda_key.tag.clone()),
                 )
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let value = self.ciphertext.on_simulation(sim_key);
+                let (result, overflow) = value.overflowing_add(&value.scalar(other));
+                (
+                    FheInt::<Id>::new(result, sim_key.tag.clone()),
+                    FheBool::new(InnerBoolean::Simulation(overflow), sim_key.tag.clone()),
+                )
+            }
         })
     }
 }
@@ -271,6 +291,7 @@ where
     /// assert!(overflowed.decrypt(&client_key));
     /// ```
     fn overflowing_sub(self, other: Self) -> (Self::Output, FheBool) {
+        global_state::check_tags([&self.tag, &other.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let (result, overflow) = cpu_key.pbs_key().signed_overflowing_sub_parallelized(
@@ -294,6 +315,15 @@ where
                     FheBool::new(overflow, cuda_key.tag.clone()),
                 )
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let value = self.ciphertext.on_simulation(sim_key);
+                let (result, overflow) =
+                    value.overflowing_sub(&other.ciphertext.on_simulation(sim_key));
+                (
+                    FheInt::<Id>::new(result, sim_key.tag.clone()),
+                    FheBool::new(InnerBoolean::Simulation(overflow), sim_key.tag.clone()),
+                )
+            }
         })
     }
 }
@@ -367,6 +397,7 @@ where
     /// assert!(overflowed.decrypt(&client_key));
     /// ```
     fn overflowing_sub(self, other: Clear) -> (Self::Output, FheBool) {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let (result, overflow) = cpu_key
@@ -389,6 +420,14 @@ where
                     FheBool::new(overflow, cuda_key.tag.clone()),
                 )
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let value = self.ciphertext.on_simulation(sim_key);
+                let (result, overflow) = value.overflowing_sub(&value.scalar(other));
+                (
+                    FheInt::<Id>::new(result, sim_key.tag.clone()),
+                    FheBool::new(InnerBoolean::Simulation(overflow), sim_key.tag.clone()),
+                )
+            }
         })
     }
 }
@@ -462,6 +501,7 @@ where
     /// assert!(overflowed.decrypt(&client_key));
     /// ```
     fn overflowing_mul(self, other: Self) -> (Self::Output, FheBool) {
+        global_state::check_tags([&self.tag, &other.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let (result, overflow) = cpu_key.pbs_key().signed_overflowing_mul_parallelized(
@@ -477,6 +517,15 @@ where
             InternalServerKey::Cuda(_) => {
                 todo!("Cuda devices do not support signed integer");
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let value = self.ciphertext.on_simulation(sim_key);
+                let (result, overflow) =
+                    value.overflowing_mul(&other.ciphertext.on_simulation(sim_key));
+                (
+                    FheInt::<Id>::new(result, sim_key.tag.clone()),
+                    FheBool::new(InnerBoolean::Simulation(overflow), sim_key.tag.clone()),
+                )
+            }
         })
     }
 }
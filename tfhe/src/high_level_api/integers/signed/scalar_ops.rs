@@ -1,5 +1,6 @@
 #[cfg(feature = "gpu")]
 use crate::core_crypto::commons::numeric::CastFrom;
+use crate::high_level_api::booleans::InnerBoolean;
 use crate::high_level_api::errors::UnwrapResultExt;
 use crate::high_level_api::global_state;
 #[cfg(feature = "gpu")]
@@ -7,6 +8,7 @@ use crate::high_level_api::global_state::with_thread_local_cuda_streams;
 use crate::high_level_api::integers::signed::inner::SignedRadixCiphertext;
 use crate::high_level_api::integers::FheIntId;
 use crate::high_level_api::keys::InternalServerKey;
+use crate::high_level_api::lazy::{self, LazyOp};
 use crate::high_level_api::traits::{
     DivRem, FheEq, FheMax, FheMin, FheOrd, RotateLeft, RotateLeftAssign, RotateRight,
     RotateRightAssign,
@@ -46,6 +48,7 @@ where
     /// assert_eq!(decrypted_max, 2i16);
     /// ```
     fn max(&self, rhs: Clear) -> Self::Output {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -64,6 +67,11 @@ where
                     Self::new(inner_result, cuda_key.tag.clone())
                 })
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let value = self.ciphertext.on_simulation(sim_key);
+                let inner_result = value.max(&value.scalar(rhs));
+                Self::new(inner_result, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -94,6 +102,7 @@ where
     /// assert_eq!(decrypted_min, -1i16);
     /// ```
     fn min(&self, rhs: Clear) -> Self::Output {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -112,6 +121,11 @@ where
                     Self::new(inner_result, cuda_key.tag.clone())
                 })
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let value = self.ciphertext.on_simulation(sim_key);
+                let inner_result = value.min(&value.scalar(rhs));
+                Self::new(inner_result, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -141,6 +155,7 @@ where
     /// assert_eq!(decrypted, -1i16 == 2i16);
     /// ```
     fn eq(&self, rhs: Clear) -> FheBool {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -159,6 +174,14 @@ where
                     FheBool::new(inner_result, cuda_key.tag.clone())
                 })
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp_scalar(rhs)
+                    .is_eq();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 
@@ -182,6 +205,7 @@ where
     /// assert_eq!(decrypted, -1i16 != 2i16);
     /// ```
     fn ne(&self, rhs: Clear) -> FheBool {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -200,6 +224,14 @@ where
                     FheBool::new(inner_result, cuda_key.tag.clone())
                 })
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp_scalar(rhs)
+                    .is_ne();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 }
@@ -228,6 +260,7 @@ where
     /// assert_eq!(decrypted, -1i16 < 2i16);
     /// ```
     fn lt(&self, rhs: Clear) -> FheBool {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -246,6 +279,14 @@ where
                     FheBool::new(inner_result, cuda_key.tag.clone())
                 })
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp_scalar(rhs)
+                    .is_lt();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 
@@ -268,6 +309,7 @@ where
     /// assert_eq!(decrypted, -1i16 <= 2i16);
     /// ```
     fn le(&self, rhs: Clear) -> FheBool {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -286,6 +328,14 @@ where
                     FheBool::new(inner_result, cuda_key.tag.clone())
                 })
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp_scalar(rhs)
+                    .is_le();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 
@@ -308,6 +358,7 @@ where
     /// assert_eq!(decrypted, -1i16 > 2i16);
     /// ```
     fn gt(&self, rhs: Clear) -> FheBool {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -326,6 +377,14 @@ where
                     FheBool::new(inner_result, cuda_key.tag.clone())
                 })
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp_scalar(rhs)
+                    .is_gt();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 
@@ -348,6 +407,7 @@ where
     /// assert_eq!(decrypted, -1i16 >= 2i16);
     /// ```
     fn ge(&self, rhs: Clear) -> FheBool {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -366,6 +426,14 @@ where
                     FheBool::new(inner_result, cuda_key.tag.clone())
                 })
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp_scalar(rhs)
+                    .is_ge();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 }
@@ -396,6 +464,7 @@ macro_rules! generic_integer_impl_scalar_div_rem {
                     type Output = ($concrete_type, $concrete_type);
 
                     fn div_rem(self, rhs: $scalar_type) -> Self::Output {
+                        global_state::check_tags([&self.tag]);
                         global_state::with_internal_keys(|keys| match keys {
                             InternalServerKey::Cpu(cpu_key) => {
                                 let (q, r) = cpu_key
@@ -422,6 +491,13 @@ macro_rules! generic_integer_impl_scalar_div_rem {
                                     <$concrete_type>::new(r, cuda_key.tag.clone()),
                                 )
                             }
+                            InternalServerKey::Simulation(sim_key) => {
+                                let (q, r) = self.ciphertext.on_simulation(sim_key).scalar_div_rem(rhs);
+                                (
+                                    <$concrete_type>::new(q, sim_key.tag.clone()),
+                                    <$concrete_type>::new(r, sim_key.tag.clone())
+                                )
+                            }
                         })
                     }
                 }
@@ -447,6 +523,7 @@ macro_rules! define_scalar_rotate_shifts {
             rust_trait: Shl(shl),
             implem: {
                 |lhs: &FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -463,6 +540,10 @@ macro_rules! define_scalar_rotate_shifts {
                             });
                             SignedRadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            SignedRadixCiphertext::Simulation(value.shl(value.scalar_amount(rhs)))
+                        }
                     })
                 }
             },
@@ -476,6 +557,7 @@ macro_rules! define_scalar_rotate_shifts {
             rust_trait: Shr(shr),
             implem: {
                 |lhs: &FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -492,6 +574,10 @@ macro_rules! define_scalar_rotate_shifts {
                             });
                             SignedRadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            SignedRadixCiphertext::Simulation(value.shr(value.scalar_amount(rhs)))
+                        }
                     })
                 }
             },
@@ -505,6 +591,7 @@ macro_rules! define_scalar_rotate_shifts {
             rust_trait: RotateLeft(rotate_left),
             implem: {
                 |lhs: &FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -521,6 +608,10 @@ macro_rules! define_scalar_rotate_shifts {
                             });
                             SignedRadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            SignedRadixCiphertext::Simulation(value.rotate_left(value.scalar_amount(rhs)))
+                        }
                     })
                 }
             },
@@ -534,6 +625,7 @@ macro_rules! define_scalar_rotate_shifts {
             rust_trait: RotateRight(rotate_right),
             implem: {
                 |lhs: &FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -550,6 +642,10 @@ macro_rules! define_scalar_rotate_shifts {
                             });
                             SignedRadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            SignedRadixCiphertext::Simulation(value.rotate_right(value.scalar_amount(rhs)))
+                        }
                     })
                 }
             },
@@ -563,6 +659,7 @@ macro_rules! define_scalar_rotate_shifts {
             rust_trait: ShlAssign(shl_assign),
             implem: {
                 |lhs: &mut FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -576,6 +673,10 @@ macro_rules! define_scalar_rotate_shifts {
                                     .scalar_left_shift_assign(lhs.ciphertext.as_gpu_mut(streams), rhs, streams);
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            lhs.ciphertext = SignedRadixCiphertext::Simulation(value.shl(value.scalar_amount(rhs)));
+                        }
                     })
                 }
             },
@@ -589,6 +690,7 @@ macro_rules! define_scalar_rotate_shifts {
             rust_trait: ShrAssign(shr_assign),
             implem: {
                 |lhs: &mut FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -602,6 +704,10 @@ macro_rules! define_scalar_rotate_shifts {
                                     .scalar_right_shift_assign(lhs.ciphertext.as_gpu_mut(streams), rhs, streams);
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            lhs.ciphertext = SignedRadixCiphertext::Simulation(value.shr(value.scalar_amount(rhs)));
+                        }
                     })
                 }
             },
@@ -615,6 +721,7 @@ macro_rules! define_scalar_rotate_shifts {
             rust_trait: RotateLeftAssign(rotate_left_assign),
             implem: {
                 |lhs: &mut FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -628,6 +735,10 @@ macro_rules! define_scalar_rotate_shifts {
                                     .scalar_rotate_left_assign(lhs.ciphertext.as_gpu_mut(streams), rhs, streams);
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            lhs.ciphertext = SignedRadixCiphertext::Simulation(value.rotate_left(value.scalar_amount(rhs)));
+                        }
                     })
                 }
             },
@@ -641,6 +752,7 @@ macro_rules! define_scalar_rotate_shifts {
             rust_trait: RotateRightAssign(rotate_right_assign),
             implem: {
                 |lhs: &mut FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -654,6 +766,10 @@ macro_rules! define_scalar_rotate_shifts {
                                     .scalar_rotate_right_assign(lhs.ciphertext.as_gpu_mut(streams), rhs, streams);
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            lhs.ciphertext = SignedRadixCiphertext::Simulation(value.rotate_right(value.scalar_amount(rhs)));
+                        }
                     })
                 }
             },
@@ -753,6 +869,10 @@ macro_rules! define_scalar_ops {
             rust_trait: Add(add),
             implem: {
                 |lhs: &FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::Add, lhs, rhs) {
+                        return result.ciphertext;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -769,6 +889,10 @@ macro_rules! define_scalar_ops {
                             });
                             SignedRadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            SignedRadixCiphertext::Simulation(value.add(&value.scalar(rhs)))
+                        }
                     })
                 }
             },
@@ -782,6 +906,10 @@ macro_rules! define_scalar_ops {
             rust_trait: Sub(sub),
             implem: {
                 |lhs: &FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::Sub, lhs, rhs) {
+                        return result.ciphertext;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -798,6 +926,10 @@ macro_rules! define_scalar_ops {
                             });
                             SignedRadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            SignedRadixCiphertext::Simulation(value.sub(&value.scalar(rhs)))
+                        }
                     })
                 }
             },
@@ -811,6 +943,10 @@ macro_rules! define_scalar_ops {
             rust_trait: Mul(mul),
             implem: {
                 |lhs: &FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::Mul, lhs, rhs) {
+                        return result.ciphertext;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -827,6 +963,10 @@ macro_rules! define_scalar_ops {
                             });
                             SignedRadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            SignedRadixCiphertext::Simulation(value.mul(&value.scalar(rhs)))
+                        }
                     })
                 }
             },
@@ -841,6 +981,10 @@ macro_rules! define_scalar_ops {
             rust_trait: BitAnd(bitand),
             implem: {
                 |lhs: &FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::BitAnd, lhs, rhs) {
+                        return result.ciphertext;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -857,6 +1001,10 @@ macro_rules! define_scalar_ops {
                             });
                             SignedRadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            SignedRadixCiphertext::Simulation(value.bitand(&value.scalar(rhs)))
+                        }
                     })
                 }
             },
@@ -870,6 +1018,10 @@ macro_rules! define_scalar_ops {
             rust_trait: BitOr(bitor),
             implem: {
                 |lhs: &FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::BitOr, lhs, rhs) {
+                        return result.ciphertext;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -886,6 +1038,10 @@ macro_rules! define_scalar_ops {
                             });
                             SignedRadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            SignedRadixCiphertext::Simulation(value.bitor(&value.scalar(rhs)))
+                        }
                     })
                 }
             },
@@ -899,6 +1055,10 @@ macro_rules! define_scalar_ops {
             rust_trait: BitXor(bitxor),
             implem: {
                 |lhs: &FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::BitXor, lhs, rhs) {
+                        return result.ciphertext;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -916,6 +1076,10 @@ macro_rules! define_scalar_ops {
                             });
                             SignedRadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            SignedRadixCiphertext::Simulation(value.bitxor(&value.scalar(rhs)))
+                        }
                     })
                 }
             },
@@ -929,6 +1093,7 @@ macro_rules! define_scalar_ops {
             rust_trait: Div(div),
             implem: {
                 |lhs: &FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -945,6 +1110,9 @@ macro_rules! define_scalar_ops {
                             });
                             SignedRadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            SignedRadixCiphertext::Simulation(lhs.ciphertext.on_simulation(sim_key).scalar_div_rem(rhs).0)
+                        }
                     })
                 }
             },
@@ -959,6 +1127,7 @@ macro_rules! define_scalar_ops {
             rust_trait: Rem(rem),
             implem: {
                 |lhs: &FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -975,6 +1144,9 @@ macro_rules! define_scalar_ops {
                             });
                             SignedRadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            SignedRadixCiphertext::Simulation(lhs.ciphertext.on_simulation(sim_key).scalar_div_rem(rhs).1)
+                        }
                     })
                 }
             },
@@ -1006,6 +1178,7 @@ macro_rules! define_scalar_ops {
             rust_trait: Sub(sub),
             implem: {
                 |lhs, rhs: &FheInt<_>| {
+                    global_state::check_tags([&rhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let result = cpu_key.pbs_key().left_scalar_sub_parallelized(lhs, &*rhs.ciphertext.on_cpu());
@@ -1021,6 +1194,10 @@ macro_rules! define_scalar_ops {
                                 SignedRadixCiphertext::Cuda(result)
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = rhs.ciphertext.on_simulation(sim_key);
+                            SignedRadixCiphertext::Simulation(value.scalar(lhs).sub(&value))
+                        }
                     })
                 }
             },
@@ -1102,6 +1279,11 @@ macro_rules! define_scalar_ops {
             rust_trait: AddAssign(add_assign),
             implem: {
                 |lhs: &mut FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::Add, &*lhs, rhs) {
+                        *lhs = result;
+                        return;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -1115,6 +1297,10 @@ macro_rules! define_scalar_ops {
                                     .scalar_add_assign(lhs.ciphertext.as_gpu_mut(streams), rhs, streams);
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            lhs.ciphertext = SignedRadixCiphertext::Simulation(value.add(&value.scalar(rhs)));
+                        }
                     })
                 }
             },
@@ -1133,6 +1319,11 @@ macro_rules! define_scalar_ops {
             rust_trait: SubAssign(sub_assign),
             implem: {
                 |lhs: &mut FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::Sub, &*lhs, rhs) {
+                        *lhs = result;
+                        return;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -1146,6 +1337,10 @@ macro_rules! define_scalar_ops {
                                     .scalar_sub_assign(lhs.ciphertext.as_gpu_mut(streams), rhs, streams);
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            lhs.ciphertext = SignedRadixCiphertext::Simulation(value.sub(&value.scalar(rhs)));
+                        }
                     })
                 }
             },
@@ -1160,6 +1355,11 @@ macro_rules! define_scalar_ops {
             rust_trait: MulAssign(mul_assign),
             implem: {
                 |lhs: &mut FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::Mul, &*lhs, rhs) {
+                        *lhs = result;
+                        return;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -1173,6 +1373,10 @@ macro_rules! define_scalar_ops {
                                     .scalar_mul_assign(lhs.ciphertext.as_gpu_mut(streams), rhs, streams);
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            lhs.ciphertext = SignedRadixCiphertext::Simulation(value.mul(&value.scalar(rhs)));
+                        }
                     })
                 }
             },
@@ -1188,6 +1392,11 @@ macro_rules! define_scalar_ops {
             rust_trait: BitAndAssign(bitand_assign),
             implem: {
                 |lhs: &mut FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::BitAnd, &*lhs, rhs) {
+                        *lhs = result;
+                        return;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -1201,6 +1410,10 @@ macro_rules! define_scalar_ops {
                                     .scalar_bitand_assign(lhs.ciphertext.as_gpu_mut(streams), rhs, streams);
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            lhs.ciphertext = SignedRadixCiphertext::Simulation(value.bitand(&value.scalar(rhs)));
+                        }
                     })
                 }
             },
@@ -1215,6 +1428,11 @@ macro_rules! define_scalar_ops {
             rust_trait: BitOrAssign(bitor_assign),
             implem: {
                 |lhs: &mut FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::BitOr, &*lhs, rhs) {
+                        *lhs = result;
+                        return;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -1228,6 +1446,10 @@ macro_rules! define_scalar_ops {
                                     .scalar_bitor_assign(lhs.ciphertext.as_gpu_mut(streams), rhs, streams);
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            lhs.ciphertext = SignedRadixCiphertext::Simulation(value.bitor(&value.scalar(rhs)));
+                        }
                     })
                 }
             },
@@ -1241,6 +1463,11 @@ macro_rules! define_scalar_ops {
             rust_trait: BitXorAssign(bitxor_assign),
             implem: {
                 |lhs: &mut FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::BitXor, &*lhs, rhs) {
+                        *lhs = result;
+                        return;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -1254,6 +1481,10 @@ macro_rules! define_scalar_ops {
                                     .scalar_bitxor_assign(lhs.ciphertext.as_gpu_mut(streams), rhs, streams);
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            lhs.ciphertext = SignedRadixCiphertext::Simulation(value.bitxor(&value.scalar(rhs)));
+                        }
                     })
                 }
             },
@@ -1267,6 +1498,7 @@ macro_rules! define_scalar_ops {
             rust_trait: DivAssign(div_assign),
             implem: {
                 |lhs: &mut FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -1279,6 +1511,10 @@ macro_rules! define_scalar_ops {
                             let cuda_result = cuda_key.pbs_key().signed_scalar_div(&cuda_lhs, rhs, streams);
                             *cuda_lhs = cuda_result;
                         })
+                        InternalServerKey::Simulation(sim_key) => {
+                            let result = lhs.ciphertext.on_simulation(sim_key).scalar_div_rem(rhs).0;
+                            lhs.ciphertext = SignedRadixCiphertext::Simulation(result);
+                        }
                     })
                 }
             },
@@ -1292,6 +1528,7 @@ macro_rules! define_scalar_ops {
             rust_trait: RemAssign(rem_assign),
             implem: {
                 |lhs: &mut FheInt<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -1304,6 +1541,10 @@ macro_rules! define_scalar_ops {
                             let cuda_result = cuda_key.pbs_key().signed_scalar_rem(&cuda_lhs, rhs, streams);
                             *cuda_lhs = cuda_result;
                         })
+                        InternalServerKey::Simulation(sim_key) => {
+                            let result = lhs.ciphertext.on_simulation(sim_key).scalar_div_rem(rhs).1;
+                            lhs.ciphertext = SignedRadixCiphertext::Simulation(result);
+                        }
                     })
                 }
             },
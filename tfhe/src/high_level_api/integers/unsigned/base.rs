@@ -4,18 +4,20 @@ use super::inner::RadixCiphertext;
 use crate::backward_compatibility::integers::FheUintVersions;
 use crate::conformance::ParameterSetConformant;
 use crate::core_crypto::prelude::{CastFrom, UnsignedInteger, UnsignedNumeric};
+use crate::high_level_api::booleans::InnerBoolean;
 #[cfg(feature = "gpu")]
 use crate::high_level_api::global_state::with_thread_local_cuda_streams;
 use crate::high_level_api::integers::signed::{FheInt, FheIntId};
 use crate::high_level_api::integers::IntegerId;
 use crate::high_level_api::keys::InternalServerKey;
+use crate::high_level_api::simulation::SimulatedInteger;
 use crate::high_level_api::traits::Tagged;
 use crate::high_level_api::{global_state, Device};
 use crate::integer::block_decomposition::{DecomposableInto, RecomposableFrom};
 #[cfg(feature = "gpu")]
 use crate::integer::gpu::ciphertext::CudaIntegerRadixCiphertext;
 use crate::integer::parameters::RadixCiphertextConformanceParams;
-use crate::integer::server_key::MatchValues;
+use crate::integer::server_key::{num_bits_to_represent_unsigned_value, MatchValues};
 use crate::named::Named;
 use crate::prelude::CastInto;
 use crate::shortint::ciphertext::NotTrivialCiphertextError;
@@ -211,6 +213,36 @@ where
         self.ciphertext.move_to_device_of_server_key_if_set();
     }
 
+    /// Evaluates (in-place) the operations this value depends on, if it was computed
+    /// with lazy evaluation enabled
+    ///
+    /// All the other values recorded in the same graph are evaluated at the same time.
+    /// Does nothing if the value is already evaluated.
+    ///
+    /// See [with_lazy_evaluation](crate::with_lazy_evaluation)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::prelude::*;
+    /// use tfhe::{generate_keys, set_server_key, with_lazy_evaluation, ConfigBuilder, FheUint16};
+    ///
+    /// let (client_key, server_key) = generate_keys(ConfigBuilder::default());
+    /// set_server_key(server_key);
+    ///
+    /// let a = FheUint16::encrypt(3u16, &client_key);
+    /// let b = FheUint16::encrypt(37849u16, &client_key);
+    ///
+    /// let mut result = with_lazy_evaluation(|| &a * &b + &a);
+    /// result.eval();
+    ///
+    /// let decrypted: u16 = result.decrypt(&client_key);
+    /// assert_eq!(decrypted, 3u16.wrapping_mul(37849u16).wrapping_add(3u16));
+    /// ```
+    pub fn eval(&mut self) {
+        self.ciphertext.materialize();
+    }
+
     /// Returns the indexes of the GPUs where the ciphertext lives
     ///
     /// If the ciphertext is on another deive (e.g CPU) then the returned
@@ -218,7 +250,9 @@ where
     #[cfg(feature = "gpu")]
     pub fn gpu_indexes(&self) -> &[GpuIndex] {
         match &self.ciphertext {
-            RadixCiphertext::Cpu(_) => &[],
+            RadixCiphertext::Cpu(_) | RadixCiphertext::Lazy(_) | RadixCiphertext::Simulation(_) => {
+                &[]
+            }
             RadixCiphertext::Cuda(cuda_ct) => cuda_ct.gpu_indexes(),
         }
     }
@@ -241,6 +275,7 @@ where
     /// assert!(decrypted);
     /// ```
     pub fn is_even(&self) -> FheBool {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let result = cpu_key
@@ -256,6 +291,10 @@ where
                     .is_even(&*self.ciphertext.on_gpu(streams), streams);
                 FheBool::new(result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self.ciphertext.on_simulation(sim_key).is_even();
+                FheBool::new(InnerBoolean::Simulation(result), sim_key.tag.clone())
+            }
         })
     }
 
@@ -277,6 +316,7 @@ where
     /// assert!(decrypted);
     /// ```
     pub fn is_odd(&self) -> FheBool {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let result = cpu_key
@@ -292,6 +332,10 @@ where
                     .is_odd(&*self.ciphertext.on_gpu(streams), streams);
                 FheBool::new(result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = !self.ciphertext.on_simulation(sim_key).is_even();
+                FheBool::new(InnerBoolean::Simulation(result), sim_key.tag.clone())
+            }
         })
     }
 
@@ -330,6 +374,10 @@ where
     where
         Clear: UnsignedNumeric + RecomposableFrom<u64>,
     {
+        // Values of the simulation backend are in clear
+        if let RadixCiphertext::Simulation(value) = &self.ciphertext {
+            return Ok(value.to_unsigned());
+        }
         self.ciphertext.on_cpu().decrypt_trivial()
     }
 
@@ -351,6 +399,9 @@ where
     /// assert!(trivial.is_trivial());
     /// ```
     pub fn is_trivial(&self) -> bool {
+        if let RadixCiphertext::Simulation(_) = &self.ciphertext {
+            return true;
+        }
         self.ciphertext.on_cpu().is_trivial()
     }
 
@@ -406,6 +457,7 @@ where
     /// assert_eq!(decrypted, 2);
     /// ```
     pub fn leading_zeros(&self) -> super::FheUint32 {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let result = cpu_key
@@ -430,6 +482,17 @@ where
                 );
                 super::FheUint32::new(result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self.ciphertext.on_simulation(sim_key).leading_zeros();
+                super::FheUint32::new(
+                    SimulatedInteger::from_scalar(
+                        result,
+                        super::FheUint32Id::num_bits() as u32,
+                        false,
+                    ),
+                    sim_key.tag.clone(),
+                )
+            }
         })
     }
 
@@ -451,6 +514,7 @@ where
     /// assert_eq!(decrypted, 2);
     /// ```
     pub fn leading_ones(&self) -> super::FheUint32 {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let result = cpu_key
@@ -475,6 +539,17 @@ where
                 );
                 super::FheUint32::new(result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self.ciphertext.on_simulation(sim_key).leading_ones();
+                super::FheUint32::new(
+                    SimulatedInteger::from_scalar(
+                        result,
+                        super::FheUint32Id::num_bits() as u32,
+                        false,
+                    ),
+                    sim_key.tag.clone(),
+                )
+            }
         })
     }
 
@@ -496,6 +571,7 @@ where
     /// assert_eq!(decrypted, 3);
     /// ```
     pub fn trailing_zeros(&self) -> super::FheUint32 {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let result = cpu_key
@@ -520,6 +596,17 @@ where
                 );
                 super::FheUint32::new(result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self.ciphertext.on_simulation(sim_key).trailing_zeros();
+                super::FheUint32::new(
+                    SimulatedInteger::from_scalar(
+                        result,
+                        super::FheUint32Id::num_bits() as u32,
+                        false,
+                    ),
+                    sim_key.tag.clone(),
+                )
+            }
         })
     }
 
@@ -541,6 +628,7 @@ where
     /// assert_eq!(decrypted, 3);
     /// ```
     pub fn trailing_ones(&self) -> super::FheUint32 {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let result = cpu_key
@@ -565,6 +653,17 @@ where
                 );
                 super::FheUint32::new(result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self.ciphertext.on_simulation(sim_key).trailing_ones();
+                super::FheUint32::new(
+                    SimulatedInteger::from_scalar(
+                        result,
+                        super::FheUint32Id::num_bits() as u32,
+                        false,
+                    ),
+                    sim_key.tag.clone(),
+                )
+            }
         })
     }
 
@@ -587,6 +686,7 @@ where
     /// assert_eq!(decrypted, clear_a.count_ones());
     /// ```
     pub fn count_ones(&self) -> super::FheUint32 {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let result = cpu_key
@@ -602,6 +702,17 @@ where
             InternalServerKey::Cuda(_) => {
                 panic!("Cuda devices do not support count_ones yet");
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self.ciphertext.on_simulation(sim_key).count_ones();
+                super::FheUint32::new(
+                    SimulatedInteger::from_scalar(
+                        result,
+                        super::FheUint32Id::num_bits() as u32,
+                        false,
+                    ),
+                    sim_key.tag.clone(),
+                )
+            }
         })
     }
 
@@ -624,6 +735,7 @@ where
     /// assert_eq!(decrypted, clear_a.count_zeros());
     /// ```
     pub fn count_zeros(&self) -> super::FheUint32 {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let result = cpu_key
@@ -639,6 +751,17 @@ where
             InternalServerKey::Cuda(_) => {
                 panic!("Cuda devices do not support count_zeros yet");
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self.ciphertext.on_simulation(sim_key).count_zeros();
+                super::FheUint32::new(
+                    SimulatedInteger::from_scalar(
+                        result,
+                        super::FheUint32Id::num_bits() as u32,
+                        false,
+                    ),
+                    sim_key.tag.clone(),
+                )
+            }
         })
     }
 
@@ -662,6 +785,7 @@ where
     /// assert_eq!(decrypted, 1);
     /// ```
     pub fn ilog2(&self) -> super::FheUint32 {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let result = cpu_key
@@ -686,6 +810,17 @@ where
                 );
                 super::FheUint32::new(result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self.ciphertext.on_simulation(sim_key).ilog2(sim_key);
+                super::FheUint32::new(
+                    SimulatedInteger::from_scalar(
+                        result,
+                        super::FheUint32Id::num_bits() as u32,
+                        false,
+                    ),
+                    sim_key.tag.clone(),
+                )
+            }
         })
     }
 
@@ -713,6 +848,7 @@ where
     /// assert_eq!(decrypted, 63); // result is meaningless
     /// ```
     pub fn checked_ilog2(&self) -> (super::FheUint32, FheBool) {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let (result, is_ok) = cpu_key
@@ -743,6 +879,23 @@ where
                     FheBool::new(is_ok, cuda_key.tag.clone()),
                 )
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let (result, is_ok) = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .checked_ilog2(sim_key);
+                (
+                    super::FheUint32::new(
+                        SimulatedInteger::from_scalar(
+                            result,
+                            super::FheUint32Id::num_bits() as u32,
+                            false,
+                        ),
+                        sim_key.tag.clone(),
+                    ),
+                    FheBool::new(InnerBoolean::Simulation(is_ok), sim_key.tag.clone()),
+                )
+            }
         })
     }
 
@@ -791,6 +944,7 @@ where
         Clear: UnsignedInteger + DecomposableInto<u64> + CastInto<usize>,
         OutId: FheUintId,
     {
+        global_state::try_check_tags([&self.tag])?;
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let (result, matched) = cpu_key
@@ -826,6 +980,34 @@ where
                     Err(crate::Error::new("Output type does not have enough bits to represent all possible output values".to_string()))
                 }
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let value = self.ciphertext.on_simulation(sim_key);
+                let max_output_value = matches
+                    .get_values()
+                    .iter()
+                    .map(|(_, output)| *output)
+                    .max()
+                    .unwrap_or(Clear::ZERO);
+                if OutId::num_bits() >= num_bits_to_represent_unsigned_value(max_output_value) {
+                    let matched = matches
+                        .get_values()
+                        .iter()
+                        .find(|(input, _)| value.cmp_scalar(*input).is_eq());
+                    let result = matched.map_or(Clear::ZERO, |(_, output)| *output);
+                    Ok((
+                        FheUint::new(
+                            SimulatedInteger::from_scalar(result, OutId::num_bits() as u32, false),
+                            sim_key.tag.clone(),
+                        ),
+                        FheBool::new(
+                            InnerBoolean::Simulation(matched.is_some()),
+                            sim_key.tag.clone(),
+                        ),
+                    ))
+                } else {
+                    Err(crate::Error::new("Output type does not have enough bits to represent all possible output values".to_string()))
+                }
+            }
         })
     }
 
@@ -870,6 +1052,7 @@ where
         Clear: UnsignedInteger + DecomposableInto<u64> + CastInto<usize>,
         OutId: FheUintId,
     {
+        global_state::try_check_tags([&self.tag])?;
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let result = cpu_key.pbs_key().match_value_or_parallelized(
@@ -902,6 +1085,28 @@ where
                     Err(crate::Error::new("Output type does not have enough bits to represent all possible output values".to_string()))
                 }
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let value = self.ciphertext.on_simulation(sim_key);
+                let max_output_value = matches
+                    .get_values()
+                    .iter()
+                    .map(|(_, output)| *output)
+                    .max()
+                    .map_or(or_value, |max| max.max(or_value));
+                if OutId::num_bits() >= num_bits_to_represent_unsigned_value(max_output_value) {
+                    let result = matches
+                        .get_values()
+                        .iter()
+                        .find(|(input, _)| value.cmp_scalar(*input).is_eq())
+                        .map_or(or_value, |(_, output)| *output);
+                    Ok(FheUint::new(
+                        SimulatedInteger::from_scalar(result, OutId::num_bits() as u32, false),
+                        sim_key.tag.clone(),
+                    ))
+                } else {
+                    Err(crate::Error::new("Output type does not have enough bits to represent all possible output values".to_string()))
+                }
+            }
         })
     }
 
@@ -926,6 +1131,7 @@ where
     /// assert_eq!(decrypted, msg.reverse_bits());
     /// ```
     pub fn reverse_bits(&self) -> Self {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let sk = &cpu_key.pbs_key();
@@ -938,6 +1144,10 @@ where
             InternalServerKey::Cuda(_) => {
                 panic!("Cuda devices do not support reverse yet");
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self.ciphertext.on_simulation(sim_key).reverse_bits();
+                Self::new(result, sim_key.tag.clone())
+            }
         })
     }
 
@@ -967,6 +1177,7 @@ where
     where
         Clear: UnsignedNumeric + DecomposableInto<u64>,
     {
+        global_state::check_tags([&condition.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let sk = cpu_key.pbs_key();
@@ -984,6 +1195,15 @@ where
             InternalServerKey::Cuda(_) => {
                 panic!("Cuda devices do not support if_then_else yet");
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let value = if condition.ciphertext.on_simulation(sim_key) {
+                    true_value
+                } else {
+                    false_value
+                };
+                let result = SimulatedInteger::from_scalar(value, Id::num_bits() as u32, false);
+                Self::new(result, sim_key.tag.clone())
+            }
         })
     }
 
@@ -1023,6 +1243,9 @@ where
                     cuda_key.key.key.carry_modulus,
                     cuda_key.key.key.message_modulus,
                 ),
+                InternalServerKey::Simulation(sim_key) => {
+                    (sim_key.carry_modulus(), sim_key.message_modulus())
+                }
             });
 
         // Check number of blocks
@@ -1092,6 +1315,7 @@ where
     /// assert_eq!(decrypted, i32::MIN as u16);
     /// ```
     fn cast_from(input: FheInt<FromId>) -> Self {
+        global_state::check_tags([&input.tag]);
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let casted = cpu_key.pbs_key().cast_to_unsigned(
@@ -1109,6 +1333,13 @@ where
                 );
                 Self::new(casted, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let casted = input
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cast(IntoId::num_bits() as u32, false);
+                Self::new(casted, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -1136,6 +1367,7 @@ where
     /// assert_eq!(decrypted, u32::MAX as u16);
     /// ```
     fn cast_from(input: FheUint<FromId>) -> Self {
+        global_state::check_tags([&input.tag]);
         global_state::with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let casted = cpu_key.pbs_key().cast_to_unsigned(
@@ -1153,6 +1385,13 @@ where
                 );
                 Self::new(casted, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let casted = input
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cast(IntoId::num_bits() as u32, false);
+                Self::new(casted, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -1179,6 +1418,7 @@ where
     /// assert_eq!(decrypted, u16::from(true));
     /// ```
     fn cast_from(input: FheBool) -> Self {
+        global_state::check_tags([&input.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let ciphertext: crate::integer::RadixCiphertext = input
@@ -1197,6 +1437,11 @@ where
                 );
                 Self::new(inner, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let value = input.ciphertext.on_simulation(sim_key);
+                let ciphertext = SimulatedInteger::from_bool(value, Id::num_bits() as u32, false);
+                Self::new(ciphertext, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -5,7 +5,7 @@ use crate::backward_compatibility::integers::{
 };
 use crate::conformance::ParameterSetConformant;
 use crate::core_crypto::prelude::UnsignedNumeric;
-use crate::high_level_api::global_state::with_cpu_internal_keys;
+use crate::high_level_api::global_state::{check_tags, with_cpu_internal_keys};
 use crate::high_level_api::integers::unsigned::base::{
     FheUint, FheUintConformanceParams, FheUintId,
 };
@@ -177,6 +177,7 @@ where
     Id: FheUintId,
 {
     pub fn compress(&self) -> CompressedFheUint<Id> {
+        check_tags([&self.tag]);
         let ciphertext = CompressedRadixCiphertext::ModulusSwitched(with_cpu_internal_keys(|sk| {
             sk.pbs_key()
                 .switch_modulus_and_compress_parallelized(&self.ciphertext.on_cpu())
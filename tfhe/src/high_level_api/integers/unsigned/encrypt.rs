@@ -2,8 +2,10 @@ use crate::core_crypto::prelude::UnsignedNumeric;
 use crate::high_level_api::global_state;
 #[cfg(feature = "gpu")]
 use crate::high_level_api::global_state::with_thread_local_cuda_streams;
+use crate::high_level_api::integers::unsigned::inner::RadixCiphertext;
 use crate::high_level_api::integers::FheUintId;
 use crate::high_level_api::keys::InternalServerKey;
+use crate::high_level_api::simulation::SimulatedInteger;
 use crate::integer::block_decomposition::{DecomposableInto, RecomposableFrom};
 #[cfg(feature = "gpu")]
 use crate::integer::gpu::ciphertext::CudaUnsignedRadixCiphertext;
@@ -38,6 +40,9 @@ where
     /// assert_eq!(decrypted, 7288u32);
     /// ```
     fn decrypt(&self, key: &ClientKey) -> ClearType {
+        if let RadixCiphertext::Simulation(value) = &self.ciphertext {
+            return value.to_unsigned();
+        }
         key.key.key.decrypt_radix(&self.ciphertext.on_cpu())
     }
 }
@@ -123,6 +128,10 @@ where
                 );
                 Ok(Self::new(inner, cuda_key.tag.clone()))
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let value = SimulatedInteger::from_scalar(value, Id::num_bits() as u32, false);
+                Ok(Self::new(value, sim_key.tag.clone()))
+            }
         })
     }
 }
@@ -1,22 +1,30 @@
 use crate::backward_compatibility::integers::UnsignedRadixCiphertextVersionedOwned;
 #[cfg(feature = "gpu")]
 use crate::core_crypto::gpu::CudaStreams;
-use crate::high_level_api::details::MaybeCloned;
+use crate::high_level_api::details::{cpu_data_versioned_schema, MaybeCloned};
 use crate::high_level_api::global_state;
 #[cfg(feature = "gpu")]
 use crate::high_level_api::global_state::{
     with_thread_local_cuda_streams, with_thread_local_cuda_streams_for_gpu_indexes,
 };
+use crate::high_level_api::keys::SimulationServerKey;
+use crate::high_level_api::lazy::LazyValue;
+use crate::high_level_api::simulation::SimulatedInteger;
 #[cfg(feature = "gpu")]
 use crate::integer::gpu::ciphertext::{CudaIntegerRadixCiphertext, CudaUnsignedRadixCiphertext};
 use crate::Device;
 use serde::{Deserializer, Serializer};
+use tfhe_versionable::schema::{DataSchema, SchemaRegistry};
 use tfhe_versionable::{Unversionize, UnversionizeError, Versionize, VersionizeOwned};
 
 pub(crate) enum RadixCiphertext {
     Cpu(crate::integer::RadixCiphertext),
     #[cfg(feature = "gpu")]
     Cuda(crate::integer::gpu::ciphertext::CudaUnsignedRadixCiphertext),
+    /// Result of operations recorded with lazy evaluation, computed on the CPU on demand
+    Lazy(LazyValue),
+    /// Clear value of the simulation backend
+    Simulation(SimulatedInteger),
 }
 
 impl From<crate::integer::RadixCiphertext> for RadixCiphertext {
@@ -25,6 +33,12 @@ impl From<crate::integer::RadixCiphertext> for RadixCiphertext {
     }
 }
 
+impl From<SimulatedInteger> for RadixCiphertext {
+    fn from(value: SimulatedInteger) -> Self {
+        Self::Simulation(value)
+    }
+}
+
 #[cfg(feature = "gpu")]
 impl From<crate::integer::gpu::ciphertext::CudaUnsignedRadixCiphertext> for RadixCiphertext {
     fn from(value: crate::integer::gpu::ciphertext::CudaUnsignedRadixCiphertext) -> Self {
@@ -40,6 +54,8 @@ impl Clone for RadixCiphertext {
             Self::Cuda(inner) => {
                 with_thread_local_cuda_streams(|streams| Self::Cuda(inner.duplicate(streams)))
             }
+            Self::Lazy(value) => Self::Lazy(value.clone()),
+            Self::Simulation(value) => Self::Simulation(*value),
         }
     }
 }
@@ -91,6 +107,15 @@ impl VersionizeOwned for RadixCiphertext {
             cpu_data.into_owned().versionize_owned(),
         ))
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        cpu_data_versioned_schema::<
+            Self,
+            Self::VersionedOwned,
+            RadixCiphertextVersionOwned,
+            crate::integer::RadixCiphertext,
+        >(registry)
+    }
 }
 
 impl Unversionize for RadixCiphertext {
@@ -112,6 +137,8 @@ impl RadixCiphertext {
             Self::Cpu(_) => Device::Cpu,
             #[cfg(feature = "gpu")]
             Self::Cuda(_) => Device::CudaGpu,
+            Self::Lazy(_) => Device::Cpu,
+            Self::Simulation(_) => Device::Simulation,
         }
     }
 
@@ -127,6 +154,12 @@ impl RadixCiphertext {
                     MaybeCloned::Cloned(cpu_ct)
                 })
             }
+            Self::Lazy(value) => MaybeCloned::Borrowed(value.materialize()),
+            Self::Simulation(value) => {
+                MaybeCloned::Cloned(global_state::with_simulation_internal_keys(|key| {
+                    value.to_radix(key)
+                }))
+            }
         }
     }
 
@@ -145,6 +178,19 @@ impl RadixCiphertext {
                     );
                 MaybeCloned::Cloned(ct)
             }),
+            Self::Lazy(value) => {
+                let ct = crate::integer::gpu::ciphertext::CudaUnsignedRadixCiphertext::from_radix_ciphertext(
+                    value.materialize(), streams,
+                );
+                MaybeCloned::Cloned(ct)
+            }
+            Self::Simulation(value) => {
+                let cpu_ct = global_state::with_simulation_internal_keys(|key| value.to_radix(key));
+                let ct = crate::integer::gpu::ciphertext::CudaUnsignedRadixCiphertext::from_radix_ciphertext(
+                    &cpu_ct, streams,
+                );
+                MaybeCloned::Cloned(ct)
+            }
             #[cfg(feature = "gpu")]
             Self::Cuda(ct) => {
                 if ct.gpu_indexes() == streams.gpu_indexes() {
@@ -159,8 +205,16 @@ impl RadixCiphertext {
     pub(crate) fn as_cpu_mut(&mut self) -> &mut crate::integer::RadixCiphertext {
         match self {
             Self::Cpu(radix_ct) => radix_ct,
+            Self::Lazy(_) => {
+                self.materialize();
+                self.as_cpu_mut()
+            }
+            Self::Simulation(_) => {
+                self.move_to_device(Device::Cpu);
+                self.as_cpu_mut()
+            }
             #[cfg(feature = "gpu")]
-            _ => {
+            Self::Cuda(_) => {
                 self.move_to_device(Device::Cpu);
                 self.as_cpu_mut()
             }
@@ -181,6 +235,14 @@ impl RadixCiphertext {
                 };
                 cuda_ct
             }
+            Self::Lazy(_) => {
+                self.materialize();
+                self.as_gpu_mut(streams)
+            }
+            Self::Simulation(_) => {
+                self.move_to_device(Device::Cpu);
+                self.as_gpu_mut(streams)
+            }
             Self::Cuda(cuda_ct) => {
                 if cuda_ct.gpu_indexes() != streams.gpu_indexes() {
                     *cuda_ct = cuda_ct.duplicate(streams);
@@ -199,6 +261,10 @@ impl RadixCiphertext {
                     ct.to_radix_ciphertext(streams)
                 })
             }
+            Self::Lazy(value) => value.into_value(),
+            Self::Simulation(value) => {
+                global_state::with_simulation_internal_keys(|key| value.to_radix(key))
+            }
         }
     }
 
@@ -209,6 +275,13 @@ impl RadixCiphertext {
                 CudaUnsignedRadixCiphertext::from_radix_ciphertext(&cpu_ct, streams)
             }
             Self::Cuda(ct) => ct.move_to_stream(streams),
+            Self::Lazy(value) => {
+                CudaUnsignedRadixCiphertext::from_radix_ciphertext(&value.into_value(), streams)
+            }
+            Self::Simulation(value) => {
+                let cpu_ct = global_state::with_simulation_internal_keys(|key| value.to_radix(key));
+                CudaUnsignedRadixCiphertext::from_radix_ciphertext(&cpu_ct, streams)
+            }
         }
     }
 
@@ -218,6 +291,27 @@ impl RadixCiphertext {
             (Self::Cpu(_), Device::Cpu) => {
                 // Nothing to do, we already are on the correct device
             }
+            (Self::Lazy(_), Device::Cpu) => {
+                // Nothing to do, lazy values are evaluated on the CPU
+            }
+            (Self::Simulation(_), Device::Simulation) => {
+                // Nothing to do, we already are on the correct device
+            }
+            (Self::Simulation(value), _) => {
+                let cpu_ct = global_state::with_simulation_internal_keys(|key| value.to_radix(key));
+                *self = Self::Cpu(cpu_ct);
+                self.move_to_device(device);
+            }
+            (_, Device::Simulation) => {
+                let value =
+                    global_state::with_simulation_internal_keys(|key| self.on_simulation(key));
+                *self = Self::Simulation(value);
+            }
+            #[cfg(feature = "gpu")]
+            (Self::Lazy(_), Device::CudaGpu) => {
+                self.materialize();
+                self.move_to_device(device);
+            }
             #[cfg(feature = "gpu")]
             (Self::Cuda(cuda_ct), Device::CudaGpu) => {
                 // We are on a GPU, but it may not be the correct one
@@ -252,6 +346,26 @@ impl RadixCiphertext {
         }
     }
 
+    /// Returns the clear value of the simulation backend, decrypting the ciphertext with the
+    /// client key of the simulation key if needed
+    pub(crate) fn on_simulation(&self, key: &SimulationServerKey) -> SimulatedInteger {
+        match self {
+            Self::Simulation(value) => *value,
+            _ => SimulatedInteger::from_radix(&self.on_cpu(), key),
+        }
+    }
+
+    /// Evaluates the value if it is lazy, the result stays on the CPU
+    pub(crate) fn materialize(&mut self) {
+        if matches!(self, Self::Lazy(_)) {
+            let placeholder = Self::Cpu(crate::integer::RadixCiphertext::from(vec![]));
+            let Self::Lazy(value) = std::mem::replace(self, placeholder) else {
+                unreachable!()
+            };
+            *self = Self::Cpu(value.into_value());
+        }
+    }
+
     #[inline]
     pub(crate) fn move_to_device_of_server_key_if_set(&mut self) {
         if let Some(device) = global_state::device_of_internal_keys() {
@@ -2,6 +2,7 @@
 // this is the pattern we use for the macros
 #![allow(clippy::redundant_closure_call)]
 use super::inner::RadixCiphertext;
+use crate::high_level_api::booleans::InnerBoolean;
 #[cfg(feature = "gpu")]
 use crate::high_level_api::details::MaybeCloned;
 use crate::high_level_api::global_state;
@@ -9,12 +10,15 @@ use crate::high_level_api::global_state;
 use crate::high_level_api::global_state::with_thread_local_cuda_streams;
 use crate::high_level_api::integers::FheUintId;
 use crate::high_level_api::keys::InternalServerKey;
+use crate::high_level_api::lazy::{self, LazyOp};
+use crate::high_level_api::simulation::SimulatedInteger;
 use crate::high_level_api::traits::{
     DivRem, FheEq, FheMax, FheMin, FheOrd, RotateLeft, RotateLeftAssign, RotateRight,
     RotateRightAssign,
 };
 #[cfg(feature = "gpu")]
 use crate::integer::gpu::ciphertext::CudaIntegerRadixCiphertext;
+use crate::integer::server_key::operation_span;
 use crate::{FheBool, FheUint};
 use std::borrow::Borrow;
 use std::ops::{
@@ -54,6 +58,7 @@ where
     /// assert_eq!(decrypted, clears.into_iter().sum::<u16>());
     /// ```
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        let iter = iter.inspect(|elem| global_state::check_tags([&elem.tag]));
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let ciphertexts = iter.map(|elem| elem.ciphertext.into_cpu()).collect();
@@ -91,6 +96,13 @@ where
                     });
                 Self::new(inner, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = iter.fold(
+                    SimulatedInteger::zero(Id::num_bits() as u32, false),
+                    |acc, elem| acc.add(&elem.ciphertext.on_simulation(sim_key)),
+                );
+                Self::new(result, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -127,6 +139,7 @@ where
     /// assert_eq!(decrypted, clears.into_iter().sum::<u16>());
     /// ```
     fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        let iter = iter.inspect(|elem| global_state::check_tags([&elem.tag]));
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let ciphertexts = iter
@@ -184,6 +197,13 @@ where
                     Self::new(inner, cuda_key.tag.clone())
                 })
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = iter.fold(
+                    SimulatedInteger::zero(Id::num_bits() as u32, false),
+                    |acc, elem| acc.add(&elem.ciphertext.on_simulation(sim_key)),
+                );
+                Self::new(result, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -214,6 +234,7 @@ where
     /// assert_eq!(decrypted_max, 2u16);
     /// ```
     fn max(&self, rhs: &Self) -> Self::Output {
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -230,6 +251,13 @@ where
                 );
                 Self::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .max(&rhs.ciphertext.on_simulation(sim_key));
+                Self::new(inner_result, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -260,6 +288,7 @@ where
     /// assert_eq!(decrypted_min, 1u16);
     /// ```
     fn min(&self, rhs: &Self) -> Self::Output {
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -276,6 +305,13 @@ where
                 );
                 Self::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .min(&rhs.ciphertext.on_simulation(sim_key));
+                Self::new(inner_result, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -317,6 +353,10 @@ where
     /// assert_eq!(decrypted, 1u16 == 2u16);
     /// ```
     fn eq(&self, rhs: &Self) -> FheBool {
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_comparison(LazyOp::Eq, self, rhs) {
+            return result;
+        }
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -333,6 +373,14 @@ where
                 );
                 FheBool::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp(&rhs.ciphertext.on_simulation(sim_key))
+                    .is_eq();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 
@@ -356,6 +404,10 @@ where
     /// assert_eq!(decrypted, 1u16 != 2u16);
     /// ```
     fn ne(&self, rhs: &Self) -> FheBool {
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_comparison(LazyOp::Ne, self, rhs) {
+            return result;
+        }
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -372,6 +424,14 @@ where
                 );
                 FheBool::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp(&rhs.ciphertext.on_simulation(sim_key))
+                    .is_ne();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 }
@@ -421,6 +481,10 @@ where
     /// assert_eq!(decrypted, 1u16 < 2u16);
     /// ```
     fn lt(&self, rhs: &Self) -> FheBool {
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_comparison(LazyOp::Lt, self, rhs) {
+            return result;
+        }
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -437,6 +501,14 @@ where
                 );
                 FheBool::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp(&rhs.ciphertext.on_simulation(sim_key))
+                    .is_lt();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 
@@ -460,6 +532,10 @@ where
     /// assert_eq!(decrypted, 1u16 <= 2u16);
     /// ```
     fn le(&self, rhs: &Self) -> FheBool {
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_comparison(LazyOp::Le, self, rhs) {
+            return result;
+        }
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -476,6 +552,14 @@ where
                 );
                 FheBool::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp(&rhs.ciphertext.on_simulation(sim_key))
+                    .is_le();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 
@@ -499,6 +583,10 @@ where
     /// assert_eq!(decrypted, 1u16 > 2u16);
     /// ```
     fn gt(&self, rhs: &Self) -> FheBool {
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_comparison(LazyOp::Gt, self, rhs) {
+            return result;
+        }
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -515,6 +603,14 @@ where
                 );
                 FheBool::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp(&rhs.ciphertext.on_simulation(sim_key))
+                    .is_gt();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 
@@ -538,6 +634,10 @@ where
     /// assert_eq!(decrypted, 1u16 > 2u16);
     /// ```
     fn ge(&self, rhs: &Self) -> FheBool {
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_comparison(LazyOp::Ge, self, rhs) {
+            return result;
+        }
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -554,6 +654,14 @@ where
                 );
                 FheBool::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp(&rhs.ciphertext.on_simulation(sim_key))
+                    .is_ge();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 }
@@ -618,6 +726,7 @@ where
     /// assert_eq!(remainder, 23u16 % 3u16);
     /// ```
     fn div_rem(self, rhs: Self) -> Self::Output {
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let (q, r) = cpu_key
@@ -640,6 +749,16 @@ where
                     FheUint::<Id>::new(inner_result.1, cuda_key.tag.clone()),
                 )
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let (q, r) = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .div_rem(&rhs.ciphertext.on_simulation(sim_key));
+                (
+                    FheUint::<Id>::new(q, sim_key.tag.clone()),
+                    FheUint::<Id>::new(r, sim_key.tag.clone()),
+                )
+            }
         })
     }
 }
@@ -677,6 +796,7 @@ macro_rules! generic_integer_impl_operation (
 
             $(#[$outer])*
             fn $rust_trait_method(self, rhs: B) -> Self::Output {
+                operation_span!(stringify!($rust_trait_method), FheUint::<Id>::num_bits());
                 $closure(self, rhs.borrow())
             }
         }
@@ -706,6 +826,10 @@ generic_integer_impl_operation!(
     rust_trait: Add(add),
     implem: {
         |lhs: &FheUint<_>, rhs: &FheUint<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
+            if let Some(result) = lazy::record_binary_op(LazyOp::Add, lhs, rhs) {
+                return result;
+            }
             global_state::with_internal_keys(|key| match key {
                 InternalServerKey::Cpu(cpu_key) => {
                     let inner_result = cpu_key
@@ -721,6 +845,10 @@ generic_integer_impl_operation!(
                         FheUint::new(inner_result, cuda_key.tag.clone())
                     })
                 }
+                InternalServerKey::Simulation(sim_key) => {
+                    let inner_result = lhs.ciphertext.on_simulation(sim_key).add(&rhs.ciphertext.on_simulation(sim_key));
+                    FheUint::new(inner_result, sim_key.tag.clone())
+                }
             })
         }
     },
@@ -749,6 +877,10 @@ generic_integer_impl_operation!(
     rust_trait: Sub(sub),
     implem: {
         |lhs: &FheUint<_>, rhs: &FheUint<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
+            if let Some(result) = lazy::record_binary_op(LazyOp::Sub, lhs, rhs) {
+                return result;
+            }
             global_state::with_internal_keys(|key| match key {
                 InternalServerKey::Cpu(cpu_key) => {
                     let inner_result = cpu_key
@@ -764,6 +896,10 @@ generic_integer_impl_operation!(
                         FheUint::new(inner_result, cuda_key.tag.clone())
                     })
                 }
+                InternalServerKey::Simulation(sim_key) => {
+                    let inner_result = lhs.ciphertext.on_simulation(sim_key).sub(&rhs.ciphertext.on_simulation(sim_key));
+                    FheUint::new(inner_result, sim_key.tag.clone())
+                }
             })
         }
     },
@@ -792,6 +928,10 @@ generic_integer_impl_operation!(
     rust_trait: Mul(mul),
     implem: {
         |lhs: &FheUint<_>, rhs: &FheUint<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
+            if let Some(result) = lazy::record_binary_op(LazyOp::Mul, lhs, rhs) {
+                return result;
+            }
             global_state::with_internal_keys(|key| match key {
                 InternalServerKey::Cpu(cpu_key) => {
                     let inner_result = cpu_key
@@ -807,6 +947,10 @@ generic_integer_impl_operation!(
                         FheUint::new(inner_result, cuda_key.tag.clone())
                     })
                 }
+                InternalServerKey::Simulation(sim_key) => {
+                    let inner_result = lhs.ciphertext.on_simulation(sim_key).mul(&rhs.ciphertext.on_simulation(sim_key));
+                    FheUint::new(inner_result, sim_key.tag.clone())
+                }
             })
         }
     },
@@ -833,6 +977,10 @@ generic_integer_impl_operation!(
     rust_trait: BitAnd(bitand),
     implem: {
         |lhs: &FheUint<_>, rhs: &FheUint<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
+            if let Some(result) = lazy::record_binary_op(LazyOp::BitAnd, lhs, rhs) {
+                return result;
+            }
             global_state::with_internal_keys(|key| match key {
                 InternalServerKey::Cpu(cpu_key) => {
                     let inner_result = cpu_key
@@ -848,6 +996,10 @@ generic_integer_impl_operation!(
                         FheUint::new(inner_result, cuda_key.tag.clone())
                     })
                 }
+                InternalServerKey::Simulation(sim_key) => {
+                    let inner_result = lhs.ciphertext.on_simulation(sim_key).bitand(&rhs.ciphertext.on_simulation(sim_key));
+                    FheUint::new(inner_result, sim_key.tag.clone())
+                }
             })
         }
     },
@@ -874,6 +1026,10 @@ generic_integer_impl_operation!(
     rust_trait: BitOr(bitor),
     implem: {
         |lhs: &FheUint<_>, rhs: &FheUint<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
+            if let Some(result) = lazy::record_binary_op(LazyOp::BitOr, lhs, rhs) {
+                return result;
+            }
             global_state::with_internal_keys(|key| match key {
                 InternalServerKey::Cpu(cpu_key) => {
                     let inner_result = cpu_key
@@ -889,6 +1045,10 @@ generic_integer_impl_operation!(
                         FheUint::new(inner_result, cuda_key.tag.clone())
                     })
                 }
+                InternalServerKey::Simulation(sim_key) => {
+                    let inner_result = lhs.ciphertext.on_simulation(sim_key).bitor(&rhs.ciphertext.on_simulation(sim_key));
+                    FheUint::new(inner_result, sim_key.tag.clone())
+                }
             })
         }
     },
@@ -915,6 +1075,10 @@ generic_integer_impl_operation!(
     rust_trait: BitXor(bitxor),
     implem: {
         |lhs: &FheUint<_>, rhs: &FheUint<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
+            if let Some(result) = lazy::record_binary_op(LazyOp::BitXor, lhs, rhs) {
+                return result;
+            }
             global_state::with_internal_keys(|key| match key {
                 InternalServerKey::Cpu(cpu_key) => {
                     let inner_result = cpu_key
@@ -930,6 +1094,10 @@ generic_integer_impl_operation!(
                         FheUint::new(inner_result, cuda_key.tag.clone())
                     })
                 }
+                InternalServerKey::Simulation(sim_key) => {
+                    let inner_result = lhs.ciphertext.on_simulation(sim_key).bitxor(&rhs.ciphertext.on_simulation(sim_key));
+                    FheUint::new(inner_result, sim_key.tag.clone())
+                }
             })
         }
     },
@@ -964,6 +1132,7 @@ generic_integer_impl_operation!(
     rust_trait: Div(div),
     implem: {
         |lhs: &FheUint<_>, rhs: &FheUint<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
             global_state::with_internal_keys(|key| match key {
                 InternalServerKey::Cpu(cpu_key) => {
                     let inner_result = cpu_key
@@ -980,6 +1149,10 @@ generic_integer_impl_operation!(
                             .div(&*lhs.ciphertext.on_gpu(streams), &*rhs.ciphertext.on_gpu(streams), streams);
                     FheUint::new(inner_result, cuda_key.tag.clone())
                 }),
+                InternalServerKey::Simulation(sim_key) => {
+                    let inner_result = lhs.ciphertext.on_simulation(sim_key).div(&rhs.ciphertext.on_simulation(sim_key));
+                    FheUint::new(inner_result, sim_key.tag.clone())
+                }
             })
         }
     },
@@ -1015,6 +1188,7 @@ generic_integer_impl_operation!(
     rust_trait: Rem(rem),
     implem: {
         |lhs: &FheUint<_>, rhs: &FheUint<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
             global_state::with_internal_keys(|key| match key {
                 InternalServerKey::Cpu(cpu_key) => {
                     let inner_result = cpu_key
@@ -1031,6 +1205,10 @@ generic_integer_impl_operation!(
                             .rem(&*lhs.ciphertext.on_gpu(streams), &*rhs.ciphertext.on_gpu(streams), streams);
                     FheUint::new(inner_result, cuda_key.tag.clone())
                 }),
+                InternalServerKey::Simulation(sim_key) => {
+                    let inner_result = lhs.ciphertext.on_simulation(sim_key).rem(&rhs.ciphertext.on_simulation(sim_key));
+                    FheUint::new(inner_result, sim_key.tag.clone())
+                }
             })
         }
     },
@@ -1100,6 +1278,7 @@ macro_rules! generic_integer_impl_shift_rotate (
 
             $(#[$outer])*
             fn $rust_trait_method(self, rhs: &FheUint<Id2>) -> Self::Output {
+                operation_span!(stringify!($rust_trait_method), FheUint::<Id>::num_bits());
                 $closure(self, rhs.borrow())
             }
         }
@@ -1128,6 +1307,7 @@ generic_integer_impl_shift_rotate!(
     rust_trait: Shl(shl),
     implem: {
         |lhs: &FheUint<_>, rhs: &FheUint<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
             global_state::with_internal_keys(|key| {
                 match key {
                     InternalServerKey::Cpu(cpu_key) => {
@@ -1144,6 +1324,10 @@ generic_integer_impl_shift_rotate!(
                             FheUint::new(inner_result, cuda_key.tag.clone())
                         })
                     }
+                    InternalServerKey::Simulation(sim_key) => {
+                        let inner_result = lhs.ciphertext.on_simulation(sim_key).shl_encrypted(&rhs.ciphertext.on_simulation(sim_key));
+                        FheUint::new(inner_result, sim_key.tag.clone())
+                    }
                 }
             })
         }
@@ -1172,6 +1356,7 @@ generic_integer_impl_shift_rotate!(
     rust_trait: Shr(shr),
     implem: {
         |lhs: &FheUint<_>, rhs: &FheUint<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
             global_state::with_internal_keys(|key| {
                 match key {
                     InternalServerKey::Cpu(cpu_key) => {
@@ -1188,6 +1373,10 @@ generic_integer_impl_shift_rotate!(
                             FheUint::new(inner_result, cuda_key.tag.clone())
                         })
                     }
+                    InternalServerKey::Simulation(sim_key) => {
+                        let inner_result = lhs.ciphertext.on_simulation(sim_key).shr_encrypted(&rhs.ciphertext.on_simulation(sim_key));
+                        FheUint::new(inner_result, sim_key.tag.clone())
+                    }
                 }
             })
         }
@@ -1216,6 +1405,7 @@ generic_integer_impl_shift_rotate!(
     rust_trait: RotateLeft(rotate_left),
     implem: {
         |lhs: &FheUint<_>, rhs: &FheUint<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
             global_state::with_internal_keys(|key| {
                 match key {
                     InternalServerKey::Cpu(cpu_key) => {
@@ -1232,6 +1422,10 @@ generic_integer_impl_shift_rotate!(
                             FheUint::new(inner_result, cuda_key.tag.clone())
                         })
                     }
+                    InternalServerKey::Simulation(sim_key) => {
+                        let inner_result = lhs.ciphertext.on_simulation(sim_key).rotate_left_encrypted(&rhs.ciphertext.on_simulation(sim_key));
+                        FheUint::new(inner_result, sim_key.tag.clone())
+                    }
                 }
             })
         }
@@ -1260,6 +1454,7 @@ generic_integer_impl_shift_rotate!(
     rust_trait: RotateRight(rotate_right),
     implem: {
         |lhs: &FheUint<_>, rhs: &FheUint<_>| {
+            global_state::check_tags([&lhs.tag, &rhs.tag]);
             global_state::with_internal_keys(|key| {
                 match key {
                     InternalServerKey::Cpu(cpu_key) => {
@@ -1276,6 +1471,10 @@ generic_integer_impl_shift_rotate!(
                             FheUint::new(inner_result, cuda_key.tag.clone())
                         })
                     }
+                    InternalServerKey::Simulation(sim_key) => {
+                        let inner_result = lhs.ciphertext.on_simulation(sim_key).rotate_right_encrypted(&rhs.ciphertext.on_simulation(sim_key));
+                        FheUint::new(inner_result, sim_key.tag.clone())
+                    }
                 }
             })
         }
@@ -1310,7 +1509,13 @@ where
     /// assert_eq!(result, 3u16.wrapping_add(37849u16));
     /// ```
     fn add_assign(&mut self, rhs: I) {
+        operation_span!("add_assign", FheUint::<Id>::num_bits());
         let rhs = rhs.borrow();
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_binary_op(LazyOp::Add, self, rhs) {
+            *self = result;
+            return;
+        }
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().add_assign_parallelized(
@@ -1326,6 +1531,13 @@ where
                     streams,
                 );
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .add(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = RadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1355,7 +1567,13 @@ where
     /// assert_eq!(result, 3u16.wrapping_sub(37849u16));
     /// ```
     fn sub_assign(&mut self, rhs: I) {
+        operation_span!("sub_assign", FheUint::<Id>::num_bits());
         let rhs = rhs.borrow();
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_binary_op(LazyOp::Sub, self, rhs) {
+            *self = result;
+            return;
+        }
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().sub_assign_parallelized(
@@ -1371,6 +1589,13 @@ where
                     streams,
                 );
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .sub(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = RadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1400,7 +1625,13 @@ where
     /// assert_eq!(result, 3u16.wrapping_mul(37849u16));
     /// ```
     fn mul_assign(&mut self, rhs: I) {
+        operation_span!("mul_assign", FheUint::<Id>::num_bits());
         let rhs = rhs.borrow();
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_binary_op(LazyOp::Mul, self, rhs) {
+            *self = result;
+            return;
+        }
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().mul_assign_parallelized(
@@ -1416,6 +1647,13 @@ where
                     streams,
                 );
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .mul(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = RadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1443,7 +1681,13 @@ where
     /// assert_eq!(result, 3u16 & 37849u16);
     /// ```
     fn bitand_assign(&mut self, rhs: I) {
+        operation_span!("bitand_assign", FheUint::<Id>::num_bits());
         let rhs = rhs.borrow();
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_binary_op(LazyOp::BitAnd, self, rhs) {
+            *self = result;
+            return;
+        }
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().bitand_assign_parallelized(
@@ -1459,6 +1703,13 @@ where
                     streams,
                 );
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .bitand(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = RadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1486,7 +1737,13 @@ where
     /// assert_eq!(result, 3u16 | 37849u16);
     /// ```
     fn bitor_assign(&mut self, rhs: I) {
+        operation_span!("bitor_assign", FheUint::<Id>::num_bits());
         let rhs = rhs.borrow();
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_binary_op(LazyOp::BitOr, self, rhs) {
+            *self = result;
+            return;
+        }
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().bitor_assign_parallelized(
@@ -1502,6 +1759,13 @@ where
                     streams,
                 );
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .bitor(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = RadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1529,7 +1793,13 @@ where
     /// assert_eq!(result, 3u16 ^ 37849u16);
     /// ```
     fn bitxor_assign(&mut self, rhs: I) {
+        operation_span!("bitxor_assign", FheUint::<Id>::num_bits());
         let rhs = rhs.borrow();
+        global_state::check_tags([&self.tag, &rhs.tag]);
+        if let Some(result) = lazy::record_binary_op(LazyOp::BitXor, self, rhs) {
+            *self = result;
+            return;
+        }
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().bitxor_assign_parallelized(
@@ -1545,6 +1815,13 @@ where
                     streams,
                 );
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .bitxor(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = RadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1577,7 +1854,9 @@ where
     /// assert_eq!(result, 37849u16 / 3u16);
     /// ```
     fn div_assign(&mut self, rhs: I) {
+        operation_span!("div_assign", FheUint::<Id>::num_bits());
         let rhs = rhs.borrow();
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().div_assign_parallelized(
@@ -1593,6 +1872,13 @@ where
                     streams,
                 );
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .div(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = RadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1625,7 +1911,9 @@ where
     /// assert_eq!(result, 37849u16 % 3u16);
     /// ```
     fn rem_assign(&mut self, rhs: I) {
+        operation_span!("rem_assign", FheUint::<Id>::num_bits());
         let rhs = rhs.borrow();
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().rem_assign_parallelized(
@@ -1641,6 +1929,13 @@ where
                     streams,
                 );
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .rem(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = RadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1679,6 +1974,7 @@ where
     /// assert_eq!(result, 37849u16 << 3u16);
     /// ```
     fn shl_assign(&mut self, rhs: &FheUint<Id2>) {
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().left_shift_assign_parallelized(
@@ -1696,6 +1992,13 @@ where
                     );
                 });
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .shl_encrypted(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = RadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1733,6 +2036,7 @@ where
     /// assert_eq!(result, 37849u16 >> 3u16);
     /// ```
     fn shr_assign(&mut self, rhs: &FheUint<Id2>) {
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().right_shift_assign_parallelized(
@@ -1750,6 +2054,13 @@ where
                     );
                 });
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .shr_encrypted(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = RadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1788,6 +2099,7 @@ where
     /// assert_eq!(result, 37849u16.rotate_left(3));
     /// ```
     fn rotate_left_assign(&mut self, rhs: &FheUint<Id2>) {
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().rotate_left_assign_parallelized(
@@ -1805,6 +2117,13 @@ where
                     );
                 });
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .rotate_left_encrypted(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = RadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1843,6 +2162,7 @@ where
     /// assert_eq!(result, 37849u16.rotate_right(3));
     /// ```
     fn rotate_right_assign(&mut self, rhs: &FheUint<Id2>) {
+        global_state::check_tags([&self.tag, &rhs.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 cpu_key.pbs_key().rotate_right_assign_parallelized(
@@ -1860,6 +2180,13 @@ where
                     );
                 });
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .rotate_right_encrypted(&rhs.ciphertext.on_simulation(sim_key));
+                self.ciphertext = RadixCiphertext::Simulation(result);
+            }
         })
     }
 }
@@ -1924,6 +2251,7 @@ where
     /// assert_eq!(result, 3u16.wrapping_neg());
     /// ```
     fn neg(self) -> Self::Output {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let ciphertext = cpu_key
@@ -1939,6 +2267,10 @@ where
                     .neg(&*self.ciphertext.on_gpu(streams), streams);
                 FheUint::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self.ciphertext.on_simulation(sim_key).neg();
+                FheUint::new(inner_result, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -1995,6 +2327,7 @@ where
     /// assert_eq!(result, !3u16);
     /// ```
     fn not(self) -> Self::Output {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let ciphertext = cpu_key.pbs_key().bitnot(&*self.ciphertext.on_cpu());
@@ -2008,6 +2341,10 @@ where
                     .bitnot(&*self.ciphertext.on_gpu(streams), streams);
                 FheUint::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self.ciphertext.on_simulation(sim_key).bitnot();
+                FheUint::new(inner_result, sim_key.tag.clone())
+            }
         })
     }
 }
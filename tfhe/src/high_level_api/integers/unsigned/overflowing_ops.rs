@@ -1,4 +1,5 @@
 use crate::core_crypto::prelude::UnsignedNumeric;
+use crate::high_level_api::booleans::InnerBoolean;
 use crate::high_level_api::global_state;
 #[cfg(feature = "gpu")]
 use crate::high_level_api::global_state::with_thread_local_cuda_streams;
@@ -41,6 +42,7 @@ where
     /// assert!(overflowed.decrypt(&client_key));
     /// ```
     fn overflowing_add(self, other: Self) -> (Self::Output, FheBool) {
+        global_state::check_tags([&self.tag, &other.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let (result, overflow) = cpu_key.pbs_key().unsigned_overflowing_add_parallelized(
@@ -64,6 +66,15 @@ where
                     FheBool::new(inner_result.1, cuda_key.tag.clone()),
                 )
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let value = self.ciphertext.on_simulation(sim_key);
+                let (result, overflow) =
+                    value.overflowing_add(&other.ciphertext.on_simulation(sim_key));
+                (
+                    FheUint::<Id>::new(result, sim_key.tag.clone()),
+                    FheBool::new(InnerBoolean::Simulation(overflow), sim_key.tag.clone()),
+                )
+            }
         })
     }
 }
@@ -138,6 +149,7 @@ where
     /// assert!(overflowed.decrypt(&client_key));
     /// ```
     fn overflowing_add(self, other: Clear) -> (Self::Output, FheBool) {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let (result, overflow) = cpu_key
@@ -160,6 +172,14 @@ where
                     FheBool::new(inner_result.1, cuda_key.tag.clone()),
                 )
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let value = self.ciphertext.on_simulation(sim_key);
+                let (result, overflow) = value.overflowing_add(&value.scalar(other));
+                (
+                    FheUint::<Id>::new(result, sim_key.tag.clone()),
+                    FheBool::new(InnerBoolean::Simulation(overflow), sim_key.tag.clone()),
+                )
+            }
         })
     }
 }
@@ -273,6 +293,7 @@ where
     /// assert!(overflowed.decrypt(&client_key));
     /// ```
     fn overflowing_sub(self, other: Self) -> (Self::Output, FheBool) {
+        global_state::check_tags([&self.tag, &other.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let (result, overflow) = cpu_key.pbs_key().unsigned_overflowing_sub_parallelized(
@@ -296,6 +317,15 @@ where
                     FheBool::new(inner_result.1, cuda_key.tag.clone()),
                 )
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let value = self.ciphertext.on_simulation(sim_key);
+                let (result, overflow) =
+                    value.overflowing_sub(&other.ciphertext.on_simulation(sim_key));
+                (
+                    FheUint::<Id>::new(result, sim_key.tag.clone()),
+                    FheBool::new(InnerBoolean::Simulation(overflow), sim_key.tag.clone()),
+                )
+            }
         })
     }
 }
@@ -370,6 +400,7 @@ where
     /// assert!(overflowed.decrypt(&client_key));
     /// ```
     fn overflowing_sub(self, other: Clear) -> (Self::Output, FheBool) {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let (result, overflow) = cpu_key
@@ -384,6 +415,14 @@ where
             InternalServerKey::Cuda(_) => {
                 panic!("Cuda devices do not support overflowing_add yet");
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let value = self.ciphertext.on_simulation(sim_key);
+                let (result, overflow) = value.overflowing_sub(&value.scalar(other));
+                (
+                    FheUint::<Id>::new(result, sim_key.tag.clone()),
+                    FheBool::new(InnerBoolean::Simulation(overflow), sim_key.tag.clone()),
+                )
+            }
         })
     }
 }
@@ -456,6 +495,7 @@ where
     /// assert!(overflowed.decrypt(&client_key));
     /// ```
     fn overflowing_mul(self, other: Self) -> (Self::Output, FheBool) {
+        global_state::check_tags([&self.tag, &other.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let (result, overflow) = cpu_key.pbs_key().unsigned_overflowing_mul_parallelized(
@@ -471,6 +511,15 @@ where
             InternalServerKey::Cuda(_) => {
                 todo!("Cuda devices do not support overflowing_mul");
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let value = self.ciphertext.on_simulation(sim_key);
+                let (result, overflow) =
+                    value.overflowing_mul(&other.ciphertext.on_simulation(sim_key));
+                (
+                    FheUint::<Id>::new(result, sim_key.tag.clone()),
+                    FheBool::new(InnerBoolean::Simulation(overflow), sim_key.tag.clone()),
+                )
+            }
         })
     }
 }
@@ -4,13 +4,14 @@
 
 use super::base::FheUint;
 use super::inner::RadixCiphertext;
-use crate::error::InvalidRangeError;
+use crate::high_level_api::booleans::InnerBoolean;
 use crate::high_level_api::errors::UnwrapResultExt;
 use crate::high_level_api::global_state;
 #[cfg(feature = "gpu")]
 use crate::high_level_api::global_state::with_thread_local_cuda_streams;
 use crate::high_level_api::integers::FheUintId;
 use crate::high_level_api::keys::InternalServerKey;
+use crate::high_level_api::lazy::{self, LazyOp};
 use crate::high_level_api::traits::{
     BitSlice, DivRem, FheEq, FheMax, FheMin, FheOrd, RotateLeft, RotateLeftAssign, RotateRight,
     RotateRightAssign,
@@ -19,6 +20,7 @@ use crate::integer::bigint::{U1024, U2048, U512};
 use crate::integer::block_decomposition::DecomposableInto;
 #[cfg(feature = "gpu")]
 use crate::integer::gpu::ciphertext::CudaUnsignedRadixCiphertext;
+use crate::integer::server_key::radix::slice::normalize_range;
 use crate::integer::U256;
 use crate::prelude::{CastFrom, CastInto};
 use crate::FheBool;
@@ -52,6 +54,7 @@ where
     /// assert_eq!(decrypted, 1u16 == 2u16);
     /// ```
     fn eq(&self, rhs: Clear) -> FheBool {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -68,6 +71,14 @@ where
                         .scalar_eq(&*self.ciphertext.on_gpu(streams), rhs, streams);
                 FheBool::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp_scalar(rhs)
+                    .is_eq();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 
@@ -91,6 +102,7 @@ where
     /// assert_eq!(decrypted, 1u16 != 2u16);
     /// ```
     fn ne(&self, rhs: Clear) -> FheBool {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -107,6 +119,14 @@ where
                         .scalar_ne(&*self.ciphertext.on_gpu(streams), rhs, streams);
                 FheBool::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp_scalar(rhs)
+                    .is_ne();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 }
@@ -136,6 +156,7 @@ where
     /// assert_eq!(decrypted, 1u16 < 2u16);
     /// ```
     fn lt(&self, rhs: Clear) -> FheBool {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -152,6 +173,14 @@ where
                         .scalar_lt(&*self.ciphertext.on_gpu(streams), rhs, streams);
                 FheBool::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp_scalar(rhs)
+                    .is_lt();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 
@@ -175,6 +204,7 @@ where
     /// assert_eq!(decrypted, 1u16 <= 2u16);
     /// ```
     fn le(&self, rhs: Clear) -> FheBool {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -191,6 +221,14 @@ where
                         .scalar_le(&*self.ciphertext.on_gpu(streams), rhs, streams);
                 FheBool::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp_scalar(rhs)
+                    .is_le();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 
@@ -214,6 +252,7 @@ where
     /// assert_eq!(decrypted, 1u16 > 2u16);
     /// ```
     fn gt(&self, rhs: Clear) -> FheBool {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -230,6 +269,14 @@ where
                         .scalar_gt(&*self.ciphertext.on_gpu(streams), rhs, streams);
                 FheBool::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp_scalar(rhs)
+                    .is_gt();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 
@@ -253,6 +300,7 @@ where
     /// assert_eq!(decrypted, 1u16 >= 2u16);
     /// ```
     fn ge(&self, rhs: Clear) -> FheBool {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -269,6 +317,14 @@ where
                         .scalar_ge(&*self.ciphertext.on_gpu(streams), rhs, streams);
                 FheBool::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let inner_result = self
+                    .ciphertext
+                    .on_simulation(sim_key)
+                    .cmp_scalar(rhs)
+                    .is_ge();
+                FheBool::new(InnerBoolean::Simulation(inner_result), sim_key.tag.clone())
+            }
         })
     }
 }
@@ -300,6 +356,7 @@ where
     /// assert_eq!(decrypted_max, 2u16);
     /// ```
     fn max(&self, rhs: Clear) -> Self::Output {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -316,6 +373,11 @@ where
                         .scalar_max(&*self.ciphertext.on_gpu(streams), rhs, streams);
                 Self::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let value = self.ciphertext.on_simulation(sim_key);
+                let inner_result = value.max(&value.scalar(rhs));
+                Self::new(inner_result, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -347,6 +409,7 @@ where
     /// assert_eq!(decrypted_min, 1u16);
     /// ```
     fn min(&self, rhs: Clear) -> Self::Output {
+        global_state::check_tags([&self.tag]);
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner_result = cpu_key
@@ -363,6 +426,11 @@ where
                         .scalar_min(&*self.ciphertext.on_gpu(streams), rhs, streams);
                 Self::new(inner_result, cuda_key.tag.clone())
             }),
+            InternalServerKey::Simulation(sim_key) => {
+                let value = self.ciphertext.on_simulation(sim_key);
+                let inner_result = value.min(&value.scalar(rhs));
+                Self::new(inner_result, sim_key.tag.clone())
+            }
         })
     }
 }
@@ -398,10 +466,11 @@ where
     /// let decrypted_slice: u16 = result.decrypt(&client_key);
     /// assert_eq!((msg % (1 << end_bit)) >> start_bit, decrypted_slice);
     /// ```
-    fn bitslice<R>(self, range: R) -> Result<Self::Output, InvalidRangeError>
+    fn bitslice<R>(self, range: R) -> crate::Result<Self::Output>
     where
         R: RangeBounds<Clear>,
     {
+        global_state::try_check_tags([&self.tag])?;
         global_state::with_internal_keys(|key| match key {
             InternalServerKey::Cpu(cpu_key) => {
                 let result = cpu_key
@@ -413,6 +482,11 @@ where
             InternalServerKey::Cuda(_) => {
                 panic!("Cuda devices do not support bitslice yet");
             }
+            InternalServerKey::Simulation(sim_key) => {
+                let value = self.ciphertext.on_simulation(sim_key);
+                let range = normalize_range::<Clear, _>(&range, value.num_bits() as usize)?;
+                Ok(FheUint::new(value.bitslice(range), sim_key.tag.clone()))
+            }
         })
     }
 }
@@ -448,7 +522,7 @@ where
     /// let decrypted_slice: u16 = result.decrypt(&client_key);
     /// assert_eq!((msg % (1 << end_bit)) >> start_bit, decrypted_slice);
     /// ```
-    fn bitslice<R>(self, range: R) -> Result<Self::Output, InvalidRangeError>
+    fn bitslice<R>(self, range: R) -> crate::Result<Self::Output>
     where
         R: RangeBounds<Clear>,
     {
@@ -482,6 +556,7 @@ macro_rules! generic_integer_impl_scalar_div_rem {
                     type Output = ($concrete_type, $concrete_type);
 
                     fn div_rem(self, rhs: $scalar_type) -> Self::Output {
+                        global_state::check_tags([&self.tag]);
                         global_state::with_internal_keys(|key| {
                             match key {
                                 InternalServerKey::Cpu(cpu_key) => {
@@ -504,6 +579,13 @@ macro_rules! generic_integer_impl_scalar_div_rem {
                                         <$concrete_type>::new(r, cuda_key.tag.clone())
                                     )
                                 }
+                                InternalServerKey::Simulation(sim_key) => {
+                                    let (q, r) = self.ciphertext.on_simulation(sim_key).scalar_div_rem(rhs);
+                                    (
+                                        <$concrete_type>::new(q, sim_key.tag.clone()),
+                                        <$concrete_type>::new(r, sim_key.tag.clone())
+                                    )
+                                }
                             }
                         })
                     }
@@ -543,6 +625,10 @@ macro_rules! generic_integer_impl_scalar_operation {
                     type Output = $concrete_type;
 
                     fn $rust_trait_method(self, rhs: $scalar_type) -> Self::Output {
+                        crate::integer::server_key::operation_span!(
+                            concat!("scalar_", stringify!($rust_trait_method)),
+                            <$concrete_type>::num_bits()
+                        );
                         let inner_result = $closure(self, rhs);
                         let tag = global_state::tag_of_internal_server_key().unwrap_display();
                         <$concrete_type>::new(inner_result, tag)
@@ -610,6 +696,10 @@ macro_rules! generic_integer_impl_scalar_left_operation {
 
                     $(#[$doc])*
                     fn $rust_trait_method(self, rhs: &$concrete_type) -> Self::Output {
+                        crate::integer::server_key::operation_span!(
+                            concat!("scalar_", stringify!($rust_trait_method)),
+                            <$concrete_type>::num_bits()
+                        );
                         let inner_result = $closure(*self, rhs);
                         let tag = global_state::tag_of_internal_server_key().unwrap_display();
                         <$concrete_type>::new(inner_result, tag)
@@ -642,6 +732,10 @@ macro_rules! generic_integer_impl_scalar_operation_assign {
                 {
                     $(#[$doc])*
                     fn $rust_trait_method(&mut self, rhs: $scalar_type) {
+                        crate::integer::server_key::operation_span!(
+                            concat!("scalar_", stringify!($rust_trait_method)),
+                            <$concrete_type>::num_bits()
+                        );
                         $closure(self, rhs);
                     }
                 }
@@ -664,6 +758,7 @@ macro_rules! define_scalar_rotate_shifts {
             rust_trait: Shl(shl),
             implem: {
                 |lhs: &FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -680,6 +775,10 @@ macro_rules! define_scalar_rotate_shifts {
                             });
                             RadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            RadixCiphertext::Simulation(value.shl(value.scalar_amount(rhs)))
+                        }
                     })
                 }
             },
@@ -693,6 +792,7 @@ macro_rules! define_scalar_rotate_shifts {
             rust_trait: Shr(shr),
             implem: {
                 |lhs: &FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -709,6 +809,10 @@ macro_rules! define_scalar_rotate_shifts {
                             });
                             RadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            RadixCiphertext::Simulation(value.shr(value.scalar_amount(rhs)))
+                        }
                     })
                 }
             },
@@ -722,6 +826,7 @@ macro_rules! define_scalar_rotate_shifts {
             rust_trait: RotateLeft(rotate_left),
             implem: {
                 |lhs: &FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -738,6 +843,10 @@ macro_rules! define_scalar_rotate_shifts {
                             });
                             RadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            RadixCiphertext::Simulation(value.rotate_left(value.scalar_amount(rhs)))
+                        }
                     })
                 }
             },
@@ -751,6 +860,7 @@ macro_rules! define_scalar_rotate_shifts {
             rust_trait: RotateRight(rotate_right),
             implem: {
                 |lhs: &FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -767,6 +877,10 @@ macro_rules! define_scalar_rotate_shifts {
                             });
                             RadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            RadixCiphertext::Simulation(value.rotate_right(value.scalar_amount(rhs)))
+                        }
                     })
                 }
             },
@@ -780,6 +894,7 @@ macro_rules! define_scalar_rotate_shifts {
             rust_trait: ShlAssign(shl_assign),
             implem: {
                 |lhs: &mut FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -793,6 +908,10 @@ macro_rules! define_scalar_rotate_shifts {
                                     .scalar_left_shift_assign(lhs.ciphertext.as_gpu_mut(streams), rhs, streams);
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            lhs.ciphertext = RadixCiphertext::Simulation(value.shl(value.scalar_amount(rhs)));
+                        }
                     })
                 }
             },
@@ -806,6 +925,7 @@ macro_rules! define_scalar_rotate_shifts {
             rust_trait: ShrAssign(shr_assign),
             implem: {
                 |lhs: &mut FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -819,6 +939,10 @@ macro_rules! define_scalar_rotate_shifts {
                                     .scalar_right_shift_assign(lhs.ciphertext.as_gpu_mut(streams), rhs, streams);
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            lhs.ciphertext = RadixCiphertext::Simulation(value.shr(value.scalar_amount(rhs)));
+                        }
                     })
                 }
             },
@@ -832,6 +956,7 @@ macro_rules! define_scalar_rotate_shifts {
             rust_trait: RotateLeftAssign(rotate_left_assign),
             implem: {
                 |lhs: &mut FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -845,6 +970,10 @@ macro_rules! define_scalar_rotate_shifts {
                                     .scalar_rotate_left_assign(lhs.ciphertext.as_gpu_mut(streams), rhs, streams);
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            lhs.ciphertext = RadixCiphertext::Simulation(value.rotate_left(value.scalar_amount(rhs)));
+                        }
                     })
                 }
             },
@@ -858,6 +987,7 @@ macro_rules! define_scalar_rotate_shifts {
             rust_trait: RotateRightAssign(rotate_right_assign),
             implem: {
                 |lhs: &mut FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -871,6 +1001,10 @@ macro_rules! define_scalar_rotate_shifts {
                                     .scalar_rotate_right_assign(lhs.ciphertext.as_gpu_mut(streams), rhs, streams);
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            lhs.ciphertext = RadixCiphertext::Simulation(value.rotate_right(value.scalar_amount(rhs)));
+                        }
                     })
                 }
             },
@@ -971,6 +1105,10 @@ macro_rules! define_scalar_ops {
             rust_trait: Add(add),
             implem: {
                 |lhs: &FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::Add, lhs, rhs) {
+                        return result.ciphertext;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -987,6 +1125,10 @@ macro_rules! define_scalar_ops {
                             });
                             RadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            RadixCiphertext::Simulation(value.add(&value.scalar(rhs)))
+                        }
                     })
                 }
             },
@@ -1000,6 +1142,10 @@ macro_rules! define_scalar_ops {
             rust_trait: Sub(sub),
             implem: {
                 |lhs: &FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::Sub, lhs, rhs) {
+                        return result.ciphertext;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -1016,6 +1162,10 @@ macro_rules! define_scalar_ops {
                             });
                             RadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            RadixCiphertext::Simulation(value.sub(&value.scalar(rhs)))
+                        }
                     })
                 }
             },
@@ -1029,6 +1179,10 @@ macro_rules! define_scalar_ops {
             rust_trait: Mul(mul),
             implem: {
                 |lhs: &FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::Mul, lhs, rhs) {
+                        return result.ciphertext;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -1045,6 +1199,10 @@ macro_rules! define_scalar_ops {
                             });
                             RadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            RadixCiphertext::Simulation(value.mul(&value.scalar(rhs)))
+                        }
                     })
                 }
             },
@@ -1059,6 +1217,10 @@ macro_rules! define_scalar_ops {
             rust_trait: BitAnd(bitand),
             implem: {
                 |lhs: &FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::BitAnd, lhs, rhs) {
+                        return result.ciphertext;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -1075,6 +1237,10 @@ macro_rules! define_scalar_ops {
                             });
                             RadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            RadixCiphertext::Simulation(value.bitand(&value.scalar(rhs)))
+                        }
                     })
                 }
             },
@@ -1088,6 +1254,10 @@ macro_rules! define_scalar_ops {
             rust_trait: BitOr(bitor),
             implem: {
                 |lhs: &FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::BitOr, lhs, rhs) {
+                        return result.ciphertext;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -1104,6 +1274,10 @@ macro_rules! define_scalar_ops {
                             });
                             RadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            RadixCiphertext::Simulation(value.bitor(&value.scalar(rhs)))
+                        }
                     })
                 }
             },
@@ -1117,6 +1291,10 @@ macro_rules! define_scalar_ops {
             rust_trait: BitXor(bitxor),
             implem: {
                 |lhs: &FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::BitXor, lhs, rhs) {
+                        return result.ciphertext;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -1134,6 +1312,10 @@ macro_rules! define_scalar_ops {
                             });
                             RadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            RadixCiphertext::Simulation(value.bitxor(&value.scalar(rhs)))
+                        }
                     })
                 }
             },
@@ -1147,6 +1329,7 @@ macro_rules! define_scalar_ops {
             rust_trait: Div(div),
             implem: {
                 |lhs: &FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -1163,6 +1346,9 @@ macro_rules! define_scalar_ops {
                             });
                             RadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            RadixCiphertext::Simulation(lhs.ciphertext.on_simulation(sim_key).scalar_div_rem(rhs).0)
+                        }
                     })
                 }
             },
@@ -1177,6 +1363,7 @@ macro_rules! define_scalar_ops {
             rust_trait: Rem(rem),
             implem: {
                 |lhs: &FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let inner_result = cpu_key
@@ -1193,6 +1380,9 @@ macro_rules! define_scalar_ops {
                             });
                             RadixCiphertext::Cuda(inner_result)
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            RadixCiphertext::Simulation(lhs.ciphertext.on_simulation(sim_key).scalar_div_rem(rhs).1)
+                        }
                     })
                 }
             },
@@ -1224,6 +1414,7 @@ macro_rules! define_scalar_ops {
             rust_trait: Sub(sub),
             implem: {
                 |lhs, rhs: &FheUint<_>| {
+                    global_state::check_tags([&rhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             let result = cpu_key.pbs_key().left_scalar_sub_parallelized(lhs, &*rhs.ciphertext.on_cpu());
@@ -1238,6 +1429,10 @@ macro_rules! define_scalar_ops {
                                 RadixCiphertext::Cuda(result)
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = rhs.ciphertext.on_simulation(sim_key);
+                            RadixCiphertext::Simulation(value.scalar(lhs).sub(&value))
+                        }
                     })
                 }
             },
@@ -1319,6 +1514,11 @@ macro_rules! define_scalar_ops {
             rust_trait: AddAssign(add_assign),
             implem: {
                 |lhs: &mut FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::Add, &*lhs, rhs) {
+                        *lhs = result;
+                        return;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -1332,6 +1532,10 @@ macro_rules! define_scalar_ops {
                                     .scalar_add_assign(lhs.ciphertext.as_gpu_mut(streams), rhs, streams);
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            lhs.ciphertext = RadixCiphertext::Simulation(value.add(&value.scalar(rhs)));
+                        }
                     })
                 }
             },
@@ -1350,6 +1554,11 @@ macro_rules! define_scalar_ops {
             rust_trait: SubAssign(sub_assign),
             implem: {
                 |lhs: &mut FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::Sub, &*lhs, rhs) {
+                        *lhs = result;
+                        return;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -1363,6 +1572,10 @@ macro_rules! define_scalar_ops {
                                     .scalar_sub_assign(lhs.ciphertext.as_gpu_mut(streams), rhs, streams);
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            lhs.ciphertext = RadixCiphertext::Simulation(value.sub(&value.scalar(rhs)));
+                        }
                     })
                 }
             },
@@ -1377,6 +1590,11 @@ macro_rules! define_scalar_ops {
             rust_trait: MulAssign(mul_assign),
             implem: {
                 |lhs: &mut FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::Mul, &*lhs, rhs) {
+                        *lhs = result;
+                        return;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -1390,6 +1608,10 @@ macro_rules! define_scalar_ops {
                                     .scalar_mul_assign(lhs.ciphertext.as_gpu_mut(streams), rhs, streams);
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            lhs.ciphertext = RadixCiphertext::Simulation(value.mul(&value.scalar(rhs)));
+                        }
                     })
                 }
             },
@@ -1405,6 +1627,11 @@ macro_rules! define_scalar_ops {
             rust_trait: BitAndAssign(bitand_assign),
             implem: {
                 |lhs: &mut FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::BitAnd, &*lhs, rhs) {
+                        *lhs = result;
+                        return;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -1418,6 +1645,10 @@ macro_rules! define_scalar_ops {
                                     .scalar_bitand_assign(lhs.ciphertext.as_gpu_mut(streams), rhs, streams);
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            lhs.ciphertext = RadixCiphertext::Simulation(value.bitand(&value.scalar(rhs)));
+                        }
                     })
                 }
             },
@@ -1432,6 +1663,11 @@ macro_rules! define_scalar_ops {
             rust_trait: BitOrAssign(bitor_assign),
             implem: {
                 |lhs: &mut FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::BitOr, &*lhs, rhs) {
+                        *lhs = result;
+                        return;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -1445,6 +1681,10 @@ macro_rules! define_scalar_ops {
                                     .scalar_bitor_assign(lhs.ciphertext.as_gpu_mut(streams), rhs, streams);
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            lhs.ciphertext = RadixCiphertext::Simulation(value.bitor(&value.scalar(rhs)));
+                        }
                     })
                 }
             },
@@ -1458,6 +1698,11 @@ macro_rules! define_scalar_ops {
             rust_trait: BitXorAssign(bitxor_assign),
             implem: {
                 |lhs: &mut FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
+                    if let Some(result) = lazy::record_scalar_op(LazyOp::BitXor, &*lhs, rhs) {
+                        *lhs = result;
+                        return;
+                    }
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -1471,6 +1716,10 @@ macro_rules! define_scalar_ops {
                                     .scalar_bitxor_assign(lhs.ciphertext.as_gpu_mut(streams), rhs, streams);
                             })
                         }
+                        InternalServerKey::Simulation(sim_key) => {
+                            let value = lhs.ciphertext.on_simulation(sim_key);
+                            lhs.ciphertext = RadixCiphertext::Simulation(value.bitxor(&value.scalar(rhs)));
+                        }
                     })
                 }
             },
@@ -1484,6 +1733,7 @@ macro_rules! define_scalar_ops {
             rust_trait: DivAssign(div_assign),
             implem: {
                 |lhs: &mut FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -1496,6 +1746,10 @@ macro_rules! define_scalar_ops {
                             let cuda_result = cuda_key.pbs_key().scalar_div(&cuda_lhs, rhs, streams);
                             *cuda_lhs = cuda_result;
                         })
+                        InternalServerKey::Simulation(sim_key) => {
+                            let result = lhs.ciphertext.on_simulation(sim_key).scalar_div_rem(rhs).0;
+                            lhs.ciphertext = RadixCiphertext::Simulation(result);
+                        }
                     })
                 }
             },
@@ -1509,6 +1763,7 @@ macro_rules! define_scalar_ops {
             rust_trait: RemAssign(rem_assign),
             implem: {
                 |lhs: &mut FheUint<_>, rhs| {
+                    global_state::check_tags([&lhs.tag]);
                     global_state::with_internal_keys(|key| match key {
                         InternalServerKey::Cpu(cpu_key) => {
                             cpu_key
@@ -1521,6 +1776,10 @@ macro_rules! define_scalar_ops {
                             let cuda_result = cuda_key.pbs_key().scalar_rem(&cuda_lhs, rhs, streams);
                             *cuda_lhs = cuda_result;
                         })
+                        InternalServerKey::Simulation(sim_key) => {
+                            let result = lhs.ciphertext.on_simulation(sim_key).scalar_div_rem(rhs).1;
+                            lhs.ciphertext = RadixCiphertext::Simulation(result);
+                        }
                     })
                 }
             },
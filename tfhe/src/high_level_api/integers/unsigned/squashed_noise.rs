@@ -3,16 +3,17 @@ use crate::backward_compatibility::integers::{
     InnerSquashedNoiseRadixCiphertextVersionedOwned, SquashedNoiseFheUintVersions,
 };
 use crate::core_crypto::commons::numeric::UnsignedNumeric;
-use crate::high_level_api::details::MaybeCloned;
+use crate::high_level_api::details::{cpu_data_versioned_schema, MaybeCloned};
 use crate::high_level_api::errors::UninitializedNoiseSquashing;
 use crate::high_level_api::global_state;
-use crate::high_level_api::global_state::with_internal_keys;
+use crate::high_level_api::global_state::{try_check_tags, with_internal_keys};
 use crate::high_level_api::keys::InternalServerKey;
 use crate::high_level_api::traits::{FheDecrypt, SquashNoise};
 use crate::integer::block_decomposition::RecomposableFrom;
 use crate::named::Named;
 use crate::{ClientKey, Device, Tag};
 use serde::{Deserializer, Serializer};
+use tfhe_versionable::schema::{DataSchema, SchemaRegistry};
 use tfhe_versionable::{Unversionize, UnversionizeError, Versionize, VersionizeOwned};
 
 /// Enum that manages the current inner representation of a squashed noise FheUint .
@@ -76,6 +77,15 @@ impl VersionizeOwned for InnerSquashedNoiseRadixCiphertext {
             InnerSquashedNoiseRadixCiphertextVersionOwned(cpu_data.into_owned().versionize_owned()),
         )
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        cpu_data_versioned_schema::<
+            Self,
+            Self::VersionedOwned,
+            InnerSquashedNoiseRadixCiphertextVersionOwned,
+            crate::integer::ciphertext::SquashedNoiseRadixCiphertext,
+        >(registry)
+    }
 }
 
 impl Unversionize for InnerSquashedNoiseRadixCiphertext {
@@ -109,6 +119,9 @@ impl InnerSquashedNoiseRadixCiphertext {
             (Self::Cpu(_), Device::Cpu) => {
                 // Nothing to do, we already are on the correct device
             }
+            (Self::Cpu(_), Device::Simulation) => {
+                // Squashed noise ciphertexts are not simulated, they stay on the CPU
+            }
             #[cfg(feature = "gpu")]
             _ => panic!("Cuda devices do not support noise squashing yet"),
         }
@@ -172,6 +185,7 @@ impl<Id: FheUintId> SquashNoise for FheUint<Id> {
     type Output = SquashedNoiseFheUint;
 
     fn squash_noise(&self) -> crate::Result<Self::Output> {
+        try_check_tags([&self.tag])?;
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(server_key) => {
                 let noise_squashing_key = server_key
@@ -194,6 +208,9 @@ impl<Id: FheUintId> SquashNoise for FheUint<Id> {
             InternalServerKey::Cuda(_) => Err(crate::error!(
                 "Cuda devices do not support noise squashing yet"
             )),
+            InternalServerKey::Simulation(_) => Err(crate::error!(
+                "The simulation backend does not support noise squashing"
+            )),
         })
     }
 }
@@ -0,0 +1,163 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+
+use fs2::FileExt;
+
+use super::KeyStoreBackend;
+
+/// Name of the file used to synchronize the accesses to the directory
+const LOCK_FILE_NAME: &str = ".lock";
+
+/// Extension of the files that hold the records
+const RECORD_EXTENSION: &str = "key";
+
+/// Backend that stores each record in its own file inside a directory.
+///
+/// Records are written to a temporary file that is then renamed, so a record is never left
+/// half-written. Accesses are synchronized across processes with a lock file in the directory:
+/// readers share the lock while writers hold it exclusively. Note that the lock is held as long
+/// as a reader returned by [`KeyStoreBackend::read`] is alive.
+#[derive(Clone, Debug)]
+pub struct FileSystemBackend {
+    directory: PathBuf,
+}
+
+impl FileSystemBackend {
+    /// Creates a backend that stores its records in `directory`, which is created if it does not
+    /// exist
+    pub fn new(directory: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let directory = directory.into();
+        std::fs::create_dir_all(&directory)?;
+        Ok(Self { directory })
+    }
+
+    /// The directory where the records are stored
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    fn record_path(&self, name: &str) -> PathBuf {
+        self.directory.join(format!("{name}.{RECORD_EXTENSION}"))
+    }
+
+    fn open_lock_file(&self) -> std::io::Result<File> {
+        OpenOptions::new()
+            .create(true)
+            .read(true)
+            .write(true)
+            .truncate(false)
+            .open(self.directory.join(LOCK_FILE_NAME))
+    }
+
+    fn lock_shared(&self) -> std::io::Result<File> {
+        let lock = self.open_lock_file()?;
+        lock.lock_shared()?;
+        Ok(lock)
+    }
+
+    fn lock_exclusive(&self) -> std::io::Result<File> {
+        let lock = self.open_lock_file()?;
+        lock.lock_exclusive()?;
+        Ok(lock)
+    }
+
+    /// Makes the renaming of the records durable
+    fn sync_directory(&self) -> std::io::Result<()> {
+        // Directories can't be opened as files on windows
+        #[cfg(unix)]
+        File::open(&self.directory)?.sync_all()?;
+        Ok(())
+    }
+}
+
+/// Writes a file and flushes it to the disk
+fn write_file(
+    path: &Path,
+    write_content: &mut dyn FnMut(&mut dyn Write) -> std::io::Result<()>,
+) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_content(&mut writer)?;
+    let file = writer.into_inner().map_err(|err| err.into_error())?;
+    file.sync_all()
+}
+
+/// Reader over a record that keeps the directory locked until it is dropped
+struct LockedReader {
+    reader: BufReader<File>,
+    // The lock is released when the file is closed
+    _lock: File,
+}
+
+impl Read for LockedReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.reader.read(buf)
+    }
+}
+
+impl KeyStoreBackend for FileSystemBackend {
+    fn read(&self, name: &str) -> std::io::Result<Option<Box<dyn Read + '_>>> {
+        let lock = self.lock_shared()?;
+
+        match File::open(self.record_path(name)) {
+            Ok(file) => Ok(Some(Box::new(LockedReader {
+                reader: BufReader::new(file),
+                _lock: lock,
+            }))),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn write(
+        &self,
+        name: &str,
+        write_record: &mut dyn FnMut(&mut dyn Write) -> std::io::Result<()>,
+    ) -> std::io::Result<()> {
+        let _lock = self.lock_exclusive()?;
+
+        let tmp_path = self.directory.join(format!(".{name}.tmp"));
+        let result = write_file(&tmp_path, write_record)
+            .and_then(|()| std::fs::rename(&tmp_path, self.record_path(name)));
+
+        if result.is_err() {
+            // The temporary file may not exist depending on where the error happened
+            let _ = std::fs::remove_file(&tmp_path);
+        }
+        result?;
+
+        self.sync_directory()
+    }
+
+    fn remove(&self, name: &str) -> std::io::Result<bool> {
+        let _lock = self.lock_exclusive()?;
+
+        match std::fs::remove_file(self.record_path(name)) {
+            Ok(()) => {
+                self.sync_directory()?;
+                Ok(true)
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn list(&self) -> std::io::Result<Vec<String>> {
+        let _lock = self.lock_shared()?;
+
+        let mut names = Vec::new();
+        for entry in std::fs::read_dir(&self.directory)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(RECORD_EXTENSION) {
+                continue;
+            }
+
+            if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                names.push(name.to_string());
+            }
+        }
+        names.sort_unstable();
+
+        Ok(names)
+    }
+}
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::sync::RwLock;
+
+use super::KeyStoreBackend;
+
+/// Backend that keeps the records in memory
+#[derive(Debug, Default)]
+pub struct MemoryBackend {
+    records: RwLock<HashMap<String, Vec<u8>>>,
+}
+
+impl MemoryBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl KeyStoreBackend for MemoryBackend {
+    fn read(&self, name: &str) -> std::io::Result<Option<Box<dyn Read + '_>>> {
+        let records = self.records.read().unwrap();
+
+        // The record is copied so the lock is not held by the reader
+        Ok(records
+            .get(name)
+            .map(|record| Box::new(std::io::Cursor::new(record.clone())) as Box<dyn Read>))
+    }
+
+    fn write(
+        &self,
+        name: &str,
+        write_record: &mut dyn FnMut(&mut dyn Write) -> std::io::Result<()>,
+    ) -> std::io::Result<()> {
+        let mut record = Vec::new();
+        write_record(&mut record)?;
+
+        self.records
+            .write()
+            .unwrap()
+            .insert(name.to_string(), record);
+        Ok(())
+    }
+
+    fn remove(&self, name: &str) -> std::io::Result<bool> {
+        Ok(self.records.write().unwrap().remove(name).is_some())
+    }
+
+    fn list(&self) -> std::io::Result<Vec<String>> {
+        let mut names: Vec<_> = self.records.read().unwrap().keys().cloned().collect();
+        names.sort_unstable();
+
+        Ok(names)
+    }
+}
@@ -0,0 +1,476 @@
+//! Persistent storage for the keys of the high level API.
+//!
+//! A [`KeyStore`] saves and loads keys by [`Tag`], on top of a [`KeyStoreBackend`] that handles
+//! the actual storage. Two backends are provided:
+//!
+//! * [`FileSystemBackend`], that stores each key in its own file inside a directory. Writes are
+//!   atomic and concurrent accesses from several processes are serialized with file locks.
+//! * [`MemoryBackend`], that keeps the keys in memory, mostly useful for tests.
+//!
+//! Each entry is stored with some versioned [`KeyMetadata`], followed by the key serialized with
+//! the [`safe_serialization`](crate::safe_serialization) module. Client keys can optionally be
+//! encrypted at rest with a [`KeyEncryptionKey`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use tfhe::key_store::{KeyEncryptionKey, KeyStore, MemoryBackend};
+//! use tfhe::prelude::*;
+//! use tfhe::{ClientKey, CompressedServerKey, ConfigBuilder};
+//!
+//! let store =
+//!     KeyStore::new(MemoryBackend::new()).with_encryption_key(KeyEncryptionKey::generate());
+//!
+//! let mut cks = ClientKey::generate(ConfigBuilder::default());
+//! cks.tag_mut().set_u64(42);
+//! let csks = CompressedServerKey::new(&cks);
+//!
+//! store.store_tagged(&cks).unwrap();
+//! store.store_tagged(&csks).unwrap();
+//!
+//! let loaded: ClientKey = store.load(cks.tag()).unwrap().unwrap();
+//! assert_eq!(loaded.tag(), cks.tag());
+//! assert_eq!(store.entries().unwrap().len(), 2);
+//! ```
+
+mod file;
+mod memory;
+
+pub use file::FileSystemBackend;
+pub use memory::MemoryBackend;
+
+pub use crate::key_wrapping::KeyEncryptionKey;
+
+use std::fmt::Display;
+use std::io::{Read, Write};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tfhe_versionable::{Unversionize, Versionize, VersionizeOwned};
+
+use crate::high_level_api::backward_compatibility::key_store::{
+    KeyKindVersions, KeyMetadataVersions,
+};
+use crate::high_level_api::prelude::Tagged;
+use crate::key_wrapping::NONCE_SIZE;
+use crate::named::Named;
+use crate::safe_serialization::{
+    DeserializationConfig, NonConformantDeserializationConfig, RecordingReader,
+    SafeDeserializationError, SerializationConfig,
+};
+use crate::{
+    ClientKey, CompactPublicKey, CompressedCompactPublicKey, CompressedPublicKey,
+    CompressedServerKey, PublicKey, ServerKey, Tag,
+};
+
+/// Maximum size of the serialized metadata of a key
+const METADATA_SIZE_LIMIT: u64 = 1 << 20;
+
+/// Extension-less name of the entry used for keys stored with an empty tag
+const DEFAULT_ENTRY_ID: &str = "default";
+
+/// Storage used by a [`KeyStore`].
+///
+/// A backend stores opaque records identified by a name. Names are only made of ascii lowercase
+/// letters, digits, `_` and `-`, so they can safely be used as file names.
+pub trait KeyStoreBackend: Send + Sync {
+    /// Returns a reader over the record with the given name, or `None` if there is no such record
+    fn read(&self, name: &str) -> std::io::Result<Option<Box<dyn Read + '_>>>;
+
+    /// Replaces the record with the given name by the bytes written by `write_record`.
+    ///
+    /// The update must be atomic: if an error occurs, the previous record, if any, must be left
+    /// untouched.
+    fn write(
+        &self,
+        name: &str,
+        write_record: &mut dyn FnMut(&mut dyn Write) -> std::io::Result<()>,
+    ) -> std::io::Result<()>;
+
+    /// Removes the record with the given name, returns `false` if there was no such record
+    fn remove(&self, name: &str) -> std::io::Result<bool>;
+
+    /// Returns the names of all the records of the backend
+    fn list(&self) -> std::io::Result<Vec<String>>;
+}
+
+/// The kinds of keys that can be stored in a [`KeyStore`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Versionize)]
+#[versionize(KeyKindVersions)]
+pub enum KeyKind {
+    ClientKey,
+    ServerKey,
+    CompressedServerKey,
+    PublicKey,
+    CompressedPublicKey,
+    CompactPublicKey,
+    CompressedCompactPublicKey,
+    CompactPkeCrs,
+}
+
+impl KeyKind {
+    /// Name of the kind, used as a prefix of the record names
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::ClientKey => "client_key",
+            Self::ServerKey => "server_key",
+            Self::CompressedServerKey => "compressed_server_key",
+            Self::PublicKey => "public_key",
+            Self::CompressedPublicKey => "compressed_public_key",
+            Self::CompactPublicKey => "compact_public_key",
+            Self::CompressedCompactPublicKey => "compressed_compact_public_key",
+            Self::CompactPkeCrs => "compact_pke_crs",
+        }
+    }
+
+    /// Returns true if keys of this kind must be kept private
+    pub fn is_secret(self) -> bool {
+        matches!(self, Self::ClientKey)
+    }
+}
+
+impl Display for KeyKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Information stored alongside each key of a [`KeyStore`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Versionize)]
+#[versionize(KeyMetadataVersions)]
+pub struct KeyMetadata {
+    kind: KeyKind,
+    tag: Tag,
+    encrypted: bool,
+    tfhe_version: String,
+}
+
+impl Named for KeyMetadata {
+    const NAME: &'static str = "high_level_api::KeyMetadata";
+}
+
+impl KeyMetadata {
+    /// The kind of the stored key
+    pub fn kind(&self) -> KeyKind {
+        self.kind
+    }
+
+    /// The tag used to identify the key in the store
+    pub fn tag(&self) -> &Tag {
+        &self.tag
+    }
+
+    /// Returns true if the key is encrypted at rest
+    pub fn is_encrypted(&self) -> bool {
+        self.encrypted
+    }
+
+    /// The version of *TFHE-rs* that stored the key
+    pub fn tfhe_version(&self) -> &str {
+        &self.tfhe_version
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Keys that can be stored in a [`KeyStore`].
+///
+/// This trait is sealed, it is implemented for the keys of the high level API.
+pub trait StorableKey:
+    sealed::Sealed + Serialize + DeserializeOwned + Versionize + VersionizeOwned + Unversionize + Named
+{
+    const KIND: KeyKind;
+}
+
+macro_rules! impl_storable_key {
+    ($($(#[$cfg:meta])? $key_type:ty => $kind:ident),* $(,)?) => {
+        $(
+            $(#[$cfg])?
+            impl sealed::Sealed for $key_type {}
+
+            $(#[$cfg])?
+            impl StorableKey for $key_type {
+                const KIND: KeyKind = KeyKind::$kind;
+            }
+        )*
+    };
+}
+
+impl_storable_key!(
+    ClientKey => ClientKey,
+    ServerKey => ServerKey,
+    CompressedServerKey => CompressedServerKey,
+    PublicKey => PublicKey,
+    CompressedPublicKey => CompressedPublicKey,
+    CompactPublicKey => CompactPublicKey,
+    CompressedCompactPublicKey => CompressedCompactPublicKey,
+    #[cfg(feature = "zk-pok")]
+    crate::zk::CompactPkeCrs => CompactPkeCrs,
+);
+
+/// Errors that can occur when interacting with a [`KeyStore`]
+#[derive(Debug)]
+pub enum KeyStoreError {
+    /// The backend failed to read or write a record
+    Io(std::io::Error),
+    /// A key or its metadata could not be serialized
+    Serialization(bincode::Error),
+    /// A stored key or its metadata could not be deserialized
+    Deserialization(SafeDeserializationError),
+    /// The stored key is not of the requested kind
+    KindMismatch { expected: KeyKind, found: KeyKind },
+    /// The stored key is encrypted but the store has no encryption key
+    MissingEncryptionKey,
+    /// The stored key could not be decrypted, either because the data has been corrupted or
+    /// because it was encrypted with another key
+    DecryptionFailed,
+}
+
+impl Display for KeyStoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Key store I/O error: {err}"),
+            Self::Serialization(err) => write!(f, "Failed to serialize key: {err}"),
+            Self::Deserialization(err) => write!(f, "Failed to deserialize key: {err}"),
+            Self::KindMismatch { expected, found } => {
+                write!(f, "Expected a {expected} in the key store, found a {found}")
+            }
+            Self::MissingEncryptionKey => write!(
+                f,
+                "The stored key is encrypted but no encryption key was provided"
+            ),
+            Self::DecryptionFailed => write!(f, "Failed to decrypt the stored key"),
+        }
+    }
+}
+
+impl std::error::Error for KeyStoreError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Serialization(err) => Some(err),
+            Self::Deserialization(err) => Some(err),
+            Self::KindMismatch { .. } | Self::MissingEncryptionKey | Self::DecryptionFailed => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for KeyStoreError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<bincode::Error> for KeyStoreError {
+    fn from(value: bincode::Error) -> Self {
+        Self::Serialization(value)
+    }
+}
+
+impl From<SafeDeserializationError> for KeyStoreError {
+    fn from(value: SafeDeserializationError) -> Self {
+        Self::Deserialization(value)
+    }
+}
+
+/// Returns the name of the record used to store the key of the given kind and tag
+fn entry_name(kind: KeyKind, tag: &Tag) -> String {
+    let id = if tag.is_empty() {
+        DEFAULT_ENTRY_ID.to_string()
+    } else {
+        tag.data()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    };
+
+    format!("{}-{id}", kind.name())
+}
+
+/// Stores and loads keys by [`Tag`]. See the [module level documentation](self) for more
+/// information.
+pub struct KeyStore<B> {
+    backend: B,
+    encryption_key: Option<KeyEncryptionKey>,
+    serialized_size_limit: Option<u64>,
+}
+
+impl<B: KeyStoreBackend> KeyStore<B> {
+    /// Creates a store over the given backend. Keys are stored in clear and there is no size
+    /// limit when loading them.
+    pub fn new(backend: B) -> Self {
+        Self {
+            backend,
+            encryption_key: None,
+            serialized_size_limit: None,
+        }
+    }
+
+    /// Encrypts the secret keys with the given key when they are stored, and decrypts them when
+    /// they are loaded
+    pub fn with_encryption_key(self, encryption_key: KeyEncryptionKey) -> Self {
+        Self {
+            encryption_key: Some(encryption_key),
+            ..self
+        }
+    }
+
+    /// Refuses to load keys whose serialized size is larger than `size`
+    pub fn with_size_limit(self, size: u64) -> Self {
+        Self {
+            serialized_size_limit: Some(size),
+            ..self
+        }
+    }
+
+    /// Returns the backend of the store
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Stores a key under the given tag, replacing any key of the same kind that was previously
+    /// stored with this tag.
+    ///
+    /// Secret keys are encrypted if the store has an encryption key.
+    pub fn store<K: StorableKey>(&self, tag: &Tag, key: &K) -> Result<(), KeyStoreError> {
+        let encryption_key = self.encryption_key.as_ref().filter(|_| K::KIND.is_secret());
+
+        let metadata = KeyMetadata {
+            kind: K::KIND,
+            tag: tag.clone(),
+            encrypted: encryption_key.is_some(),
+            tfhe_version: env!("CARGO_PKG_VERSION").to_string(),
+        };
+
+        let mut record = Vec::new();
+        SerializationConfig::new_with_unlimited_size().serialize_into(&metadata, &mut record)?;
+
+        let key_config = SerializationConfig::new_with_unlimited_size().enable_integrity_check();
+        if let Some(encryption_key) = encryption_key {
+            let mut serialized_key = Vec::new();
+            key_config.serialize_into(key, &mut serialized_key)?;
+
+            let encrypted = encryption_key.encrypt(&serialized_key, &record);
+            serialized_key.fill(0);
+
+            let (nonce, ciphertext) = encrypted.ok_or_else(|| {
+                KeyStoreError::Serialization(Box::new(bincode::ErrorKind::Custom(
+                    "failed to encrypt the key".to_string(),
+                )))
+            })?;
+
+            record.extend_from_slice(&nonce);
+            record.extend_from_slice(&ciphertext);
+        } else {
+            key_config.serialize_into(key, &mut record)?;
+        }
+
+        self.backend
+            .write(&entry_name(K::KIND, tag), &mut |writer| {
+                writer.write_all(&record)
+            })?;
+
+        Ok(())
+    }
+
+    /// Stores a key under its own tag
+    pub fn store_tagged<K: StorableKey + Tagged>(&self, key: &K) -> Result<(), KeyStoreError> {
+        self.store(key.tag(), key)
+    }
+
+    /// Loads the key stored under the given tag, returns `None` if there is no such key
+    pub fn load<K: StorableKey>(&self, tag: &Tag) -> Result<Option<K>, KeyStoreError> {
+        let Some(mut reader) = self.backend.read(&entry_name(K::KIND, tag))? else {
+            return Ok(None);
+        };
+
+        let mut metadata_reader = RecordingReader {
+            inner: &mut reader,
+            recorded: Vec::new(),
+        };
+        let metadata = read_metadata(&mut metadata_reader)?;
+        let metadata_bytes = metadata_reader.recorded;
+
+        if metadata.kind != K::KIND {
+            return Err(KeyStoreError::KindMismatch {
+                expected: K::KIND,
+                found: metadata.kind,
+            });
+        }
+
+        let key = if metadata.encrypted {
+            let encryption_key = self
+                .encryption_key
+                .as_ref()
+                .ok_or(KeyStoreError::MissingEncryptionKey)?;
+
+            let mut nonce = [0u8; NONCE_SIZE];
+            reader.read_exact(&mut nonce)?;
+            let mut ciphertext = Vec::new();
+            reader.read_to_end(&mut ciphertext)?;
+
+            let mut serialized_key = encryption_key
+                .decrypt(&nonce, &ciphertext, &metadata_bytes)
+                .ok_or(KeyStoreError::DecryptionFailed)?;
+
+            let key = self
+                .deserialization_config()
+                .deserialize_from_typed(serialized_key.as_slice());
+            serialized_key.fill(0);
+            key?
+        } else {
+            self.deserialization_config()
+                .deserialize_from_typed(reader)?
+        };
+
+        Ok(Some(key))
+    }
+
+    /// Returns the metadata of the key of the given kind stored under the given tag
+    pub fn metadata(&self, kind: KeyKind, tag: &Tag) -> Result<Option<KeyMetadata>, KeyStoreError> {
+        self.backend
+            .read(&entry_name(kind, tag))?
+            .map(read_metadata)
+            .transpose()
+    }
+
+    /// Returns true if a key of the given kind is stored under the given tag
+    pub fn contains(&self, kind: KeyKind, tag: &Tag) -> Result<bool, KeyStoreError> {
+        Ok(self.backend.read(&entry_name(kind, tag))?.is_some())
+    }
+
+    /// Removes the key of the given kind stored under the given tag, returns `false` if there was
+    /// no such key
+    pub fn remove(&self, kind: KeyKind, tag: &Tag) -> Result<bool, KeyStoreError> {
+        Ok(self.backend.remove(&entry_name(kind, tag))?)
+    }
+
+    /// Returns the metadata of all the keys of the store
+    pub fn entries(&self) -> Result<Vec<KeyMetadata>, KeyStoreError> {
+        let mut entries = Vec::new();
+        for name in self.backend.list()? {
+            // The record may have been removed concurrently
+            if let Some(reader) = self.backend.read(&name)? {
+                entries.push(read_metadata(reader)?);
+            }
+        }
+        Ok(entries)
+    }
+
+    fn deserialization_config(&self) -> NonConformantDeserializationConfig {
+        self.serialized_size_limit
+            .map_or_else(
+                DeserializationConfig::new_with_unlimited_size,
+                DeserializationConfig::new,
+            )
+            .disable_conformance()
+    }
+}
+
+fn read_metadata(reader: impl Read) -> Result<KeyMetadata, KeyStoreError> {
+    Ok(DeserializationConfig::new(METADATA_SIZE_LIMIT)
+        .disable_conformance()
+        .deserialize_from_typed(reader)?)
+}
@@ -40,7 +40,9 @@ impl ClientKey {
 
     /// Generates a key from a config and uses a seed.
     ///
-    /// Using the same seed between generations allows to regenerate the same key.
+    /// Using the same seed between generations allows to regenerate the same key, including its
+    /// compression, compact and noise squashing private keys. Independent seeds can be derived
+    /// from a [`MasterSeed`](crate::MasterSeed).
     ///
     /// ```rust
     /// use tfhe::{ClientKey, ConfigBuilder, Seed};
@@ -1,22 +1,39 @@
 use crate::conformance::ParameterSetConformant;
-use crate::core_crypto::commons::generators::DeterministicSeeder;
-use crate::core_crypto::prelude::{DefaultRandomGenerator, LweKeyswitchKeyConformanceParams};
+use crate::core_crypto::prelude::LweKeyswitchKeyConformanceParams;
 use crate::high_level_api::backward_compatibility::keys::*;
 use crate::integer::compression_keys::{
-    CompressedCompressionKey, CompressedDecompressionKey, CompressionKey, CompressionPrivateKeys,
-    DecompressionKey,
+    CompressedCompressionKey, CompressedCompressionKeyStreamingMetadata,
+    CompressedDecompressionKey, CompressedDecompressionKeyStreamingMetadata, CompressionKey,
+    CompressionKeyStreamingMetadata, CompressionPrivateKeys, DecompressionKey,
+    DecompressionKeyStreamingMetadata,
 };
 use crate::integer::noise_squashing::{
     CompressedNoiseSquashingKey, NoiseSquashingKey, NoiseSquashingPrivateKey,
 };
 use crate::integer::public_key::CompactPublicKey;
 use crate::integer::CompressedCompactPublicKey;
-use crate::shortint::key_switching_key::KeySwitchingKeyConformanceParams;
+use crate::shortint::key_switching_key::{
+    CompressedKeySwitchingKeyMaterial as ShortintCompressedKeySwitchingKeyMaterial,
+    CompressedKeySwitchingKeyMaterialStreamingMetadata, KeySwitchingKeyConformanceParams,
+    KeySwitchingKeyMaterial as ShortintKeySwitchingKeyMaterial,
+    KeySwitchingKeyMaterialStreamingMetadata,
+};
+use crate::shortint::noise_squashing::{
+    CompressedNoiseSquashingKey as ShortintCompressedNoiseSquashingKey,
+    CompressedNoiseSquashingKeyStreamingMetadata, NoiseSquashingKey as ShortintNoiseSquashingKey,
+    NoiseSquashingKeyStreamingMetadata,
+};
 use crate::shortint::parameters::list_compression::CompressionParameters;
 use crate::shortint::parameters::{
     CompactPublicKeyEncryptionParameters, NoiseSquashingParameters, ShortintKeySwitchingParameters,
 };
+use crate::shortint::server_key::compressed::CompressedServerKeyStreamingMetadata;
+use crate::shortint::server_key::ServerKeyStreamingMetadata;
 use crate::shortint::{EncryptionKeyChoice, MessageModulus, PBSParameters};
+use crate::streaming_serialization::{
+    checked_data_size, streaming_data_size, StreamingDataReader, StreamingDataWriter,
+    StreamingError, StreamingSerializable,
+};
 use crate::{Config, Error};
 use serde::{Deserialize, Serialize};
 use tfhe_csprng::seeders::Seed;
@@ -105,30 +122,34 @@ impl IntegerClientKey {
             (config.block_parameters.message_modulus().0) == 2 || config.block_parameters.message_modulus().0 == 4,
             "This API only supports parameters for which the MessageModulus is 2 or 4 (1 or 2 bits per block)",
         );
-        let mut seeder = DeterministicSeeder::<DefaultRandomGenerator>::new(seed);
-        let cks = crate::shortint::engine::ShortintEngine::new_from_seeder(&mut seeder)
-            .new_client_key(config.block_parameters.into());
-
-        let key = crate::integer::ClientKey::from(cks);
-
-        let compression_key = config
-            .compression_parameters
-            .map(|params| key.new_compression_private_key(params));
-
-        let dedicated_compact_private_key = config
-            .dedicated_compact_public_key_parameters
-            .map(|p| (crate::integer::CompactPrivateKey::new(p.0), p.1));
-
-        let noise_squashing_private_key = config
-            .noise_squashing_parameters
-            .map(NoiseSquashingPrivateKey::new);
-
-        Self {
-            key,
-            dedicated_compact_private_key,
-            compression_key,
-            noise_squashing_private_key,
-        }
+        // All the secret keys are generated with the seeded engine so that they can all be
+        // regenerated from the seed
+        super::with_seeded_engine(seed, || {
+            let cks = crate::shortint::engine::ShortintEngine::with_thread_local_mut(|engine| {
+                engine.new_client_key(config.block_parameters.into())
+            });
+
+            let key = crate::integer::ClientKey::from(cks);
+
+            let compression_key = config
+                .compression_parameters
+                .map(|params| key.new_compression_private_key(params));
+
+            let dedicated_compact_private_key = config
+                .dedicated_compact_public_key_parameters
+                .map(|p| (crate::integer::CompactPrivateKey::new(p.0), p.1));
+
+            let noise_squashing_private_key = config
+                .noise_squashing_parameters
+                .map(NoiseSquashingPrivateKey::new);
+
+            Self {
+                key,
+                dedicated_compact_private_key,
+                compression_key,
+                noise_squashing_private_key,
+            }
+        })
     }
 
     /// Deconstruct an [`IntegerClientKey`] into its constituents.
@@ -737,3 +758,254 @@ impl ParameterSetConformant for IntegerCompressedCompactPublicKey {
         key.is_conformant(parameter_set)
     }
 }
+
+/// Parameters of an [`IntegerServerKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct IntegerServerKeyStreamingMetadata {
+    pub key: ServerKeyStreamingMetadata,
+    pub cpk_key_switching_key_material: Option<KeySwitchingKeyMaterialStreamingMetadata>,
+    pub compression_key: Option<CompressionKeyStreamingMetadata>,
+    pub decompression_key: Option<DecompressionKeyStreamingMetadata>,
+    pub noise_squashing_key: Option<NoiseSquashingKeyStreamingMetadata>,
+}
+
+impl StreamingSerializable for IntegerServerKey {
+    type Element = u8;
+    type Metadata = IntegerServerKeyStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        IntegerServerKeyStreamingMetadata {
+            key: self.key.key.streaming_metadata(),
+            cpk_key_switching_key_material: self
+                .cpk_key_switching_key_material
+                .as_ref()
+                .map(|material| material.material.streaming_metadata()),
+            compression_key: self
+                .compression_key
+                .as_ref()
+                .map(StreamingSerializable::streaming_metadata),
+            decompression_key: self
+                .decompression_key
+                .as_ref()
+                .map(StreamingSerializable::streaming_metadata),
+            noise_squashing_key: self
+                .noise_squashing_key
+                .as_ref()
+                .map(|key| key.key.streaming_metadata()),
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        checked_data_size(&[
+            streaming_data_size::<crate::shortint::ServerKey>(&metadata.key)?,
+            metadata.cpk_key_switching_key_material.as_ref().map_or(
+                Ok(0),
+                streaming_data_size::<ShortintKeySwitchingKeyMaterial>,
+            )?,
+            metadata
+                .compression_key
+                .as_ref()
+                .map_or(Ok(0), streaming_data_size::<CompressionKey>)?,
+            metadata
+                .decompression_key
+                .as_ref()
+                .map_or(Ok(0), streaming_data_size::<DecompressionKey>)?,
+            metadata
+                .noise_squashing_key
+                .as_ref()
+                .map_or(Ok(0), streaming_data_size::<ShortintNoiseSquashingKey>)?,
+        ])
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        self.key.key.write_streaming_data(writer)?;
+        if let Some(material) = &self.cpk_key_switching_key_material {
+            material.material.write_streaming_data(writer)?;
+        }
+        if let Some(key) = &self.compression_key {
+            key.write_streaming_data(writer)?;
+        }
+        if let Some(key) = &self.decompression_key {
+            key.write_streaming_data(writer)?;
+        }
+        if let Some(key) = &self.noise_squashing_key {
+            key.key.write_streaming_data(writer)?;
+        }
+        Ok(())
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        let IntegerServerKeyStreamingMetadata {
+            key,
+            cpk_key_switching_key_material,
+            compression_key,
+            decompression_key,
+            noise_squashing_key,
+        } = metadata;
+
+        let key = crate::integer::ServerKey {
+            key: crate::shortint::ServerKey::read_streaming_data(key, reader)?,
+        };
+        let cpk_key_switching_key_material = cpk_key_switching_key_material
+            .map(|material| {
+                ShortintKeySwitchingKeyMaterial::read_streaming_data(material, reader).map(
+                    |material| crate::integer::key_switching_key::KeySwitchingKeyMaterial {
+                        material,
+                    },
+                )
+            })
+            .transpose()?;
+        let compression_key = compression_key
+            .map(|key| CompressionKey::read_streaming_data(key, reader))
+            .transpose()?;
+        let decompression_key = decompression_key
+            .map(|key| DecompressionKey::read_streaming_data(key, reader))
+            .transpose()?;
+        let noise_squashing_key = noise_squashing_key
+            .map(|key| {
+                ShortintNoiseSquashingKey::read_streaming_data(key, reader)
+                    .map(|key| NoiseSquashingKey { key })
+            })
+            .transpose()?;
+
+        Ok(Self {
+            key,
+            cpk_key_switching_key_material,
+            compression_key,
+            decompression_key,
+            noise_squashing_key,
+        })
+    }
+}
+
+/// Parameters of an [`IntegerCompressedServerKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct IntegerCompressedServerKeyStreamingMetadata {
+    pub key: CompressedServerKeyStreamingMetadata,
+    pub cpk_key_switching_key_material: Option<CompressedKeySwitchingKeyMaterialStreamingMetadata>,
+    pub compression_key: Option<CompressedCompressionKeyStreamingMetadata>,
+    pub decompression_key: Option<CompressedDecompressionKeyStreamingMetadata>,
+    pub noise_squashing_key: Option<CompressedNoiseSquashingKeyStreamingMetadata>,
+}
+
+impl StreamingSerializable for IntegerCompressedServerKey {
+    type Element = u8;
+    type Metadata = IntegerCompressedServerKeyStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        IntegerCompressedServerKeyStreamingMetadata {
+            key: self.key.key.streaming_metadata(),
+            cpk_key_switching_key_material: self
+                .cpk_key_switching_key_material
+                .as_ref()
+                .map(|material| material.material.streaming_metadata()),
+            compression_key: self
+                .compression_key
+                .as_ref()
+                .map(StreamingSerializable::streaming_metadata),
+            decompression_key: self
+                .decompression_key
+                .as_ref()
+                .map(StreamingSerializable::streaming_metadata),
+            noise_squashing_key: self
+                .noise_squashing_key
+                .as_ref()
+                .map(|key| key.key.streaming_metadata()),
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        checked_data_size(&[
+            streaming_data_size::<crate::shortint::CompressedServerKey>(&metadata.key)?,
+            metadata.cpk_key_switching_key_material.as_ref().map_or(
+                Ok(0),
+                streaming_data_size::<ShortintCompressedKeySwitchingKeyMaterial>,
+            )?,
+            metadata
+                .compression_key
+                .as_ref()
+                .map_or(Ok(0), streaming_data_size::<CompressedCompressionKey>)?,
+            metadata
+                .decompression_key
+                .as_ref()
+                .map_or(Ok(0), streaming_data_size::<CompressedDecompressionKey>)?,
+            metadata.noise_squashing_key.as_ref().map_or(
+                Ok(0),
+                streaming_data_size::<ShortintCompressedNoiseSquashingKey>,
+            )?,
+        ])
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        self.key.key.write_streaming_data(writer)?;
+        if let Some(material) = &self.cpk_key_switching_key_material {
+            material.material.write_streaming_data(writer)?;
+        }
+        if let Some(key) = &self.compression_key {
+            key.write_streaming_data(writer)?;
+        }
+        if let Some(key) = &self.decompression_key {
+            key.write_streaming_data(writer)?;
+        }
+        if let Some(key) = &self.noise_squashing_key {
+            key.key.write_streaming_data(writer)?;
+        }
+        Ok(())
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        let IntegerCompressedServerKeyStreamingMetadata {
+            key,
+            cpk_key_switching_key_material,
+            compression_key,
+            decompression_key,
+            noise_squashing_key,
+        } = metadata;
+
+        let key = crate::integer::CompressedServerKey {
+            key: crate::shortint::CompressedServerKey::read_streaming_data(key, reader)?,
+        };
+        let cpk_key_switching_key_material = cpk_key_switching_key_material
+            .map(|material| {
+                ShortintCompressedKeySwitchingKeyMaterial::read_streaming_data(material, reader)
+                    .map(|material| {
+                        crate::integer::key_switching_key::CompressedKeySwitchingKeyMaterial {
+                            material,
+                        }
+                    })
+            })
+            .transpose()?;
+        let compression_key = compression_key
+            .map(|key| CompressedCompressionKey::read_streaming_data(key, reader))
+            .transpose()?;
+        let decompression_key = decompression_key
+            .map(|key| CompressedDecompressionKey::read_streaming_data(key, reader))
+            .transpose()?;
+        let noise_squashing_key = noise_squashing_key
+            .map(|key| {
+                ShortintCompressedNoiseSquashingKey::read_streaming_data(key, reader)
+                    .map(|key| CompressedNoiseSquashingKey { key })
+            })
+            .transpose()?;
+
+        Ok(Self {
+            key,
+            cpk_key_switching_key_material,
+            compression_key,
+            decompression_key,
+            noise_squashing_key,
+        })
+    }
+}
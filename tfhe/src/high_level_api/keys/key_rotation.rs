@@ -0,0 +1,233 @@
+use tfhe_versionable::Versionize;
+
+use crate::backward_compatibility::keys::KeyRotationKeyVersions;
+use crate::high_level_api::integers::{FheIntId, FheUintId};
+use crate::high_level_api::keys::key_switching_key::IncompatibleParameters;
+use crate::integer::compression_keys::{CompressionKey, DecompressionKey};
+use crate::integer::BooleanBlock;
+use crate::named::Named;
+use crate::prelude::FheKeyswitch;
+use crate::shortint::parameters::key_switching::ShortintKeySwitchingParameters;
+use crate::{ClientKey, CompressedCiphertextList, FheBool, FheInt, FheUint, ServerKey, Tag};
+use rayon::prelude::*;
+
+/// Key used to move ciphertexts from an old key set to a new one, e.g. when rotating the keys of
+/// a tenant.
+///
+/// The rotation is done server side: each block is keyswitched to the new secret key and then
+/// bootstrapped with the new server key, so rotated ciphertexts have a fresh noise level.
+/// Rotated ciphertexts get the tag of the new [`ClientKey`].
+///
+/// If both key sets have compression enabled, whole [`CompressedCiphertextList`] can be rotated
+/// without being expanded by the caller.
+///
+/// # Example
+///
+/// ```rust
+/// use tfhe::prelude::*;
+/// use tfhe::{ClientKey, ConfigBuilder, FheUint8, KeyRotationKey, ServerKey};
+///
+/// let config = ConfigBuilder::default().build();
+/// let old_client_key = ClientKey::generate(config);
+/// let new_client_key = ClientKey::generate(config);
+/// let new_server_key = ServerKey::new(&new_client_key);
+///
+/// let rotation_key =
+///     KeyRotationKey::new(&old_client_key, (&new_client_key, &new_server_key)).unwrap();
+///
+/// let a = FheUint8::encrypt(42u8, &old_client_key);
+/// let rotated = rotation_key.keyswitch(&a);
+///
+/// let decrypted: u8 = rotated.decrypt(&new_client_key);
+/// assert_eq!(decrypted, 42);
+/// ```
+#[derive(serde::Deserialize, serde::Serialize, Versionize)]
+#[versionize(KeyRotationKeyVersions)]
+pub struct KeyRotationKey {
+    key: crate::integer::key_switching_key::KeySwitchingKey,
+    // Decompresses lists encrypted under the old keys
+    decompression_key: Option<DecompressionKey>,
+    // Compresses the rotated lists under the new keys
+    compression_key: Option<CompressionKey>,
+    tag_in: Tag,
+    tag_out: Tag,
+}
+
+impl KeyRotationKey {
+    /// Creates a key that rotates ciphertexts encrypted under `old_client_key` to the key pair
+    /// `new_key_pair`.
+    ///
+    /// Both client keys must use the same parameters.
+    pub fn new(
+        old_client_key: &ClientKey,
+        new_key_pair: (&ClientKey, &ServerKey),
+    ) -> Result<Self, IncompatibleParameters> {
+        let (new_client_key, new_server_key) = new_key_pair;
+
+        let params_from = old_client_key.key.block_parameters();
+        let params_to = new_client_key.key.block_parameters();
+
+        if params_to != params_from {
+            return Err(IncompatibleParameters);
+        }
+
+        let params = ShortintKeySwitchingParameters {
+            ks_base_log: params_to.ks_base_log(),
+            ks_level: params_to.ks_level(),
+            destination_key: params_to.encryption_key_choice(),
+        };
+
+        // The source server key is only needed when casting to a smaller message modulus,
+        // which is not the case here as both key sets use the same parameters
+        let key = crate::integer::key_switching_key::KeySwitchingKey::new(
+            (&old_client_key.key.key, None),
+            (&new_client_key.key.key, &new_server_key.key.key),
+            params,
+        );
+
+        let (decompression_key, compression_key) = match (
+            old_client_key.key.compression_key.as_ref(),
+            new_server_key.key.compression_key.as_ref(),
+        ) {
+            (Some(old_compression_private_key), Some(new_compression_key)) => {
+                let (_, decompression_key) = old_client_key
+                    .key
+                    .key
+                    .new_compression_decompression_keys(old_compression_private_key);
+                (Some(decompression_key), Some(new_compression_key.clone()))
+            }
+            _ => (None, None),
+        };
+
+        Ok(Self {
+            key,
+            decompression_key,
+            compression_key,
+            tag_in: old_client_key.tag.clone(),
+            tag_out: new_client_key.tag.clone(),
+        })
+    }
+
+    /// Tag of the client key the ciphertexts are rotated from
+    pub fn tag_in(&self) -> &Tag {
+        &self.tag_in
+    }
+
+    /// Tag of the client key the ciphertexts are rotated to
+    pub fn tag_out(&self) -> &Tag {
+        &self.tag_out
+    }
+
+    /// Returns whether this key can rotate [`CompressedCiphertextList`]
+    pub fn supports_compressed_lists(&self) -> bool {
+        self.decompression_key.is_some() && self.compression_key.is_some()
+    }
+
+    /// Rotates all the elements of a [`CompressedCiphertextList`], keeping their kinds and
+    /// order.
+    ///
+    /// Returns an error if either key set does not have compression enabled.
+    pub fn try_rotate_compressed_list(
+        &self,
+        input: &CompressedCiphertextList,
+    ) -> crate::Result<CompressedCiphertextList> {
+        let (Some(decompression_key), Some(compression_key)) =
+            (&self.decompression_key, &self.compression_key)
+        else {
+            return Err(crate::error!(
+                "Compression is not enabled in both key sets of the KeyRotationKey"
+            ));
+        };
+
+        let packed = input.inner.on_cpu();
+
+        let rotated_blocks: Vec<_> = (0..packed.packed_list.count.0)
+            .into_par_iter()
+            .map(|i| {
+                let block = decompression_key.key.unpack(&packed.packed_list, i)?;
+                Ok(self.key.key.cast(&block))
+            })
+            .collect::<crate::Result<_>>()?;
+
+        let packed_list = compression_key
+            .key
+            .compress_ciphertexts_into_list(&rotated_blocks);
+
+        let mut output = CompressedCiphertextList::from_raw_parts(
+            crate::integer::ciphertext::CompressedCiphertextList {
+                packed_list,
+                info: packed.info.clone(),
+            },
+            self.tag_out.clone(),
+        );
+        output.move_to_current_device();
+
+        Ok(output)
+    }
+}
+
+impl<Id> FheKeyswitch<FheUint<Id>> for KeyRotationKey
+where
+    Id: FheUintId,
+{
+    fn keyswitch(&self, input: &FheUint<Id>) -> FheUint<Id> {
+        let radix = input.ciphertext.on_cpu();
+        let casted = self.key.cast(&*radix);
+        FheUint::new(casted, self.tag_out.clone())
+    }
+}
+
+impl<Id> FheKeyswitch<FheInt<Id>> for KeyRotationKey
+where
+    Id: FheIntId,
+{
+    fn keyswitch(&self, input: &FheInt<Id>) -> FheInt<Id> {
+        let radix = input.ciphertext.on_cpu();
+        let casted = self.key.cast(&*radix);
+        FheInt::new(casted, self.tag_out.clone())
+    }
+}
+
+impl FheKeyswitch<FheBool> for KeyRotationKey {
+    fn keyswitch(&self, input: &FheBool) -> FheBool {
+        let boolean_block = input.ciphertext.on_cpu();
+        let casted = self.key.key.cast(boolean_block.as_ref());
+        FheBool::new(BooleanBlock::new_unchecked(casted), self.tag_out.clone())
+    }
+}
+
+#[cfg(feature = "strings")]
+impl FheKeyswitch<crate::FheAsciiString> for KeyRotationKey {
+    fn keyswitch(&self, input: &crate::FheAsciiString) -> crate::FheAsciiString {
+        let string = input.inner.on_cpu();
+        let enc_string = string
+            .chars()
+            .par_iter()
+            .map(|ascii_char| crate::strings::ciphertext::FheAsciiChar {
+                enc_char: self.key.cast(&ascii_char.enc_char),
+            })
+            .collect();
+
+        crate::FheAsciiString::new(
+            crate::strings::ciphertext::FheString {
+                enc_string,
+                padded: string.is_padded(),
+            },
+            self.tag_out.clone(),
+        )
+    }
+}
+
+impl FheKeyswitch<CompressedCiphertextList> for KeyRotationKey {
+    /// # Panics
+    ///
+    /// Panics if either key set does not have compression enabled, see
+    /// [`KeyRotationKey::try_rotate_compressed_list`] for a fallible version.
+    fn keyswitch(&self, input: &CompressedCiphertextList) -> CompressedCiphertextList {
+        self.try_rotate_compressed_list(input).unwrap()
+    }
+}
+
+impl Named for KeyRotationKey {
+    const NAME: &'static str = "high_level_api::KeyRotationKey";
+}
@@ -3,17 +3,23 @@ mod public;
 mod server;
 
 mod inner;
+mod key_rotation;
 mod key_switching_key;
 
+use crate::core_crypto::commons::generators::DeterministicSeeder;
+use crate::core_crypto::prelude::DefaultRandomGenerator;
 use crate::high_level_api::config::Config;
+use crate::shortint::engine::ShortintEngine;
+use crate::Seed;
 pub use client::ClientKey;
 pub(crate) use inner::CompactPrivateKey;
+pub use key_rotation::KeyRotationKey;
 pub use key_switching_key::KeySwitchingKey;
 pub use public::{CompactPublicKey, CompressedCompactPublicKey, CompressedPublicKey, PublicKey};
 #[cfg(feature = "gpu")]
 pub use server::CudaServerKey;
 pub(crate) use server::InternalServerKey;
-pub use server::{CompressedServerKey, ServerKey};
+pub use server::{CompressedServerKey, ServerKey, SimulationServerKey};
 
 pub(in crate::high_level_api) use inner::{
     IntegerClientKey, IntegerCompactPublicKey, IntegerCompressedCompactPublicKey,
@@ -36,3 +42,11 @@ pub fn generate_keys<C: Into<Config>>(config: C) -> (ClientKey, ServerKey) {
 
     (client_kc, server_kc)
 }
+
+/// Runs `func` with a `thread_local` engine seeded from `seed`, so that the keys generated by
+/// `func` can be regenerated from the same seed
+pub(in crate::high_level_api) fn with_seeded_engine<R>(seed: Seed, func: impl FnOnce() -> R) -> R {
+    let mut seeder = DeterministicSeeder::<DefaultRandomGenerator>::new(seed);
+    let mut engine = ShortintEngine::new_from_seeder(&mut seeder);
+    ShortintEngine::with_scoped_thread_local(&mut engine, func)
+}
@@ -21,12 +21,14 @@ use crate::backward_compatibility::keys::{
     PublicKeyVersions,
 };
 use crate::conformance::ParameterSetConformant;
-use crate::high_level_api::keys::{IntegerCompactPublicKey, IntegerCompressedCompactPublicKey};
+use crate::high_level_api::keys::{
+    with_seeded_engine, IntegerCompactPublicKey, IntegerCompressedCompactPublicKey,
+};
 use crate::named::Named;
 use crate::prelude::Tagged;
 use crate::shortint::parameters::CompactPublicKeyEncryptionParameters;
 use crate::shortint::MessageModulus;
-use crate::{Error, Tag};
+use crate::{Error, Seed, Tag};
 
 /// Classical public key.
 ///
@@ -48,6 +50,14 @@ impl PublicKey {
         }
     }
 
+    /// Creates a PublicKey like [`Self::new`], with randomness drawn from `seed`.
+    ///
+    /// The same client key and seed always give the same key, see
+    /// [`MasterSeed`](crate::core_crypto::seeders::MasterSeed) to derive independent seeds.
+    pub fn new_with_seed(client_key: &ClientKey, seed: Seed) -> Self {
+        with_seeded_engine(seed, || Self::new(client_key))
+    }
+
     pub fn into_raw_parts(self) -> (crate::integer::PublicKey, Tag) {
         (self.key, self.tag)
     }
@@ -92,6 +102,14 @@ impl CompressedPublicKey {
         }
     }
 
+    /// Creates a CompressedPublicKey like [`Self::new`], with randomness drawn from `seed`.
+    ///
+    /// The same client key and seed always give the same key, see
+    /// [`MasterSeed`](crate::core_crypto::seeders::MasterSeed) to derive independent seeds.
+    pub fn new_with_seed(client_key: &ClientKey, seed: Seed) -> Self {
+        with_seeded_engine(seed, || Self::new(client_key))
+    }
+
     pub fn into_raw_parts(self) -> (crate::integer::CompressedPublicKey, Tag) {
         (self.key, self.tag)
     }
@@ -150,6 +168,18 @@ impl CompactPublicKey {
         }
     }
 
+    /// Creates a CompactPublicKey like [`Self::new`], with randomness drawn from `seed`.
+    ///
+    /// The same client key and seed always give the same key, see
+    /// [`MasterSeed`](crate::core_crypto::seeders::MasterSeed) to derive independent seeds.
+    ///
+    /// # Panic
+    ///
+    /// This will panic if parameters are not compatible
+    pub fn new_with_seed(client_key: &ClientKey, seed: Seed) -> Self {
+        with_seeded_engine(seed, || Self::new(client_key))
+    }
+
     pub fn try_new(client_key: &ClientKey) -> Result<Self, Error> {
         IntegerCompactPublicKey::try_new(&client_key.key).map(|key| Self {
             key,
@@ -211,6 +241,18 @@ impl CompressedCompactPublicKey {
         }
     }
 
+    /// Creates a CompressedCompactPublicKey like [`Self::new`], with randomness drawn from `seed`.
+    ///
+    /// The same client key and seed always give the same key, see
+    /// [`MasterSeed`](crate::core_crypto::seeders::MasterSeed) to derive independent seeds.
+    ///
+    /// # Panic
+    ///
+    /// This will panic if parameters are not compatible
+    pub fn new_with_seed(client_key: &ClientKey, seed: Seed) -> Self {
+        with_seeded_engine(seed, || Self::new(client_key))
+    }
+
     /// Deconstruct a [`CompressedCompactPublicKey`] into its constituents.
     pub fn into_raw_parts(self) -> (crate::integer::CompressedCompactPublicKey, Tag) {
         (self.key.into_raw_parts(), self.tag)
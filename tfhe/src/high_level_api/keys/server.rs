@@ -7,7 +7,9 @@ use crate::conformance::ParameterSetConformant;
 use crate::core_crypto::gpu::{synchronize_devices, CudaStreams};
 #[cfg(feature = "gpu")]
 use crate::high_level_api::keys::inner::IntegerCudaServerKey;
-use crate::high_level_api::keys::{IntegerCompressedServerKey, IntegerServerKey};
+use crate::high_level_api::keys::{
+    with_seeded_engine, IntegerCompressedServerKey, IntegerServerKey,
+};
 use crate::integer::compression_keys::{
     CompressedCompressionKey, CompressedDecompressionKey, CompressionKey, DecompressionKey,
 };
@@ -15,10 +17,10 @@ use crate::integer::noise_squashing::{CompressedNoiseSquashingKey, NoiseSquashin
 use crate::integer::parameters::IntegerCompactCiphertextListExpansionMode;
 use crate::named::Named;
 use crate::prelude::Tagged;
-use crate::shortint::MessageModulus;
+use crate::shortint::{CarryModulus, MessageModulus};
 #[cfg(feature = "gpu")]
 use crate::GpuIndex;
-use crate::Tag;
+use crate::{Seed, Tag};
 use std::sync::Arc;
 
 /// Key of the server
@@ -47,6 +49,14 @@ impl ServerKey {
         }
     }
 
+    /// Creates a ServerKey like [`Self::new`], with randomness drawn from `seed`.
+    ///
+    /// The same client key and seed always give the same key, see
+    /// [`MasterSeed`](crate::core_crypto::seeders::MasterSeed) to derive independent seeds.
+    pub fn new_with_seed(keys: &ClientKey, seed: Seed) -> Self {
+        with_seeded_engine(seed, || Self::new(keys))
+    }
+
     pub fn into_raw_parts(
         self,
     ) -> (
@@ -231,6 +241,14 @@ impl CompressedServerKey {
         }
     }
 
+    /// Creates a CompressedServerKey like [`Self::new`], with randomness drawn from `seed`.
+    ///
+    /// The same client key and seed always give the same key, see
+    /// [`MasterSeed`](crate::core_crypto::seeders::MasterSeed) to derive independent seeds.
+    pub fn new_with_seed(keys: &ClientKey, seed: Seed) -> Self {
+        with_seeded_engine(seed, || Self::new(keys))
+    }
+
     pub fn into_raw_parts(
         self,
     ) -> (
@@ -387,10 +405,82 @@ impl Tagged for CudaServerKey {
     }
 }
 
+/// Server key of the clear-value simulation backend
+///
+/// When set with [set_server_key](crate::set_server_key), the high level types
+/// ([FheUint](crate::FheUint), [FheInt](crate::FheInt), [FheBool](crate::FheBool), strings and
+/// arrays) carry their clear values and operations run natively, with the same wrapping,
+/// overflow and error semantics as the real backends. This allows to quickly test the logic of
+/// an application, switching between backends by changing the key that is set.
+///
+/// The key wraps the [ClientKey] it is created from: ciphertexts encrypted with this client key
+/// are decrypted when they are first used with the simulation backend, and simulated values are
+/// encrypted back when a ciphertext is required (e.g. for serialization).
+///
+/// **This key offers no security, it must only be used for testing.**
+///
+/// # Example
+///
+/// ```rust
+/// use tfhe::prelude::*;
+/// use tfhe::{set_server_key, ClientKey, ConfigBuilder, FheUint8, SimulationServerKey};
+///
+/// let config = ConfigBuilder::default().build();
+/// let client_key = ClientKey::generate(config);
+///
+/// set_server_key(SimulationServerKey::new(&client_key));
+///
+/// let a = FheUint8::encrypt(200u8, &client_key);
+/// let b = FheUint8::encrypt(100u8, &client_key);
+///
+/// let c = &a + &b;
+/// let decrypted: u8 = c.decrypt(&client_key);
+/// assert_eq!(decrypted, 200u8.wrapping_add(100));
+/// ```
+#[derive(Clone)]
+pub struct SimulationServerKey {
+    pub(crate) client_key: Arc<ClientKey>,
+    pub(crate) tag: Tag,
+}
+
+impl SimulationServerKey {
+    pub fn new(client_key: &ClientKey) -> Self {
+        Self {
+            client_key: Arc::new(client_key.clone()),
+            tag: client_key.tag.clone(),
+        }
+    }
+
+    pub(in crate::high_level_api) fn message_modulus(&self) -> MessageModulus {
+        self.client_key.message_modulus()
+    }
+
+    pub(in crate::high_level_api) fn carry_modulus(&self) -> CarryModulus {
+        self.client_key.key.block_parameters().carry_modulus()
+    }
+
+    /// Returns the number of bits of a radix made of `num_blocks` blocks
+    pub(in crate::high_level_api) fn num_bits(&self, num_blocks: usize) -> u32 {
+        num_blocks as u32 * self.message_modulus().0.ilog2()
+    }
+}
+
+impl Tagged for SimulationServerKey {
+    fn tag(&self) -> &Tag {
+        &self.tag
+    }
+
+    fn tag_mut(&mut self) -> &mut Tag {
+        &mut self.tag
+    }
+}
+
+#[derive(Clone)]
 pub enum InternalServerKey {
     Cpu(ServerKey),
     #[cfg(feature = "gpu")]
     Cuda(CudaServerKey),
+    Simulation(SimulationServerKey),
 }
 
 impl std::fmt::Debug for InternalServerKey {
@@ -399,6 +489,7 @@ impl std::fmt::Debug for InternalServerKey {
             Self::Cpu(_) => f.debug_tuple("Cpu").finish(),
             #[cfg(feature = "gpu")]
             Self::Cuda(_) => f.debug_tuple("Cuda").finish(),
+            Self::Simulation(_) => f.debug_tuple("Simulation").finish(),
         }
     }
 }
@@ -415,7 +506,19 @@ impl From<CudaServerKey> for InternalServerKey {
     }
 }
 
-use crate::high_level_api::keys::inner::IntegerServerKeyConformanceParams;
+impl From<SimulationServerKey> for InternalServerKey {
+    fn from(value: SimulationServerKey) -> Self {
+        Self::Simulation(value)
+    }
+}
+
+use crate::high_level_api::keys::inner::{
+    IntegerCompressedServerKeyStreamingMetadata, IntegerServerKeyConformanceParams,
+    IntegerServerKeyStreamingMetadata,
+};
+use crate::streaming_serialization::{
+    StreamingDataReader, StreamingDataWriter, StreamingError, StreamingSerializable,
+};
 
 impl ParameterSetConformant for ServerKey {
     type ParameterSet = IntegerServerKeyConformanceParams;
@@ -440,6 +543,92 @@ impl ParameterSetConformant for CompressedServerKey {
     }
 }
 
+/// Parameters of a [`ServerKey`] in the streaming format
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct ServerKeyStreamingMetadata {
+    pub integer_key: IntegerServerKeyStreamingMetadata,
+    pub tag: Tag,
+}
+
+impl StreamingSerializable for ServerKey {
+    type Element = u8;
+    type Metadata = ServerKeyStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        ServerKeyStreamingMetadata {
+            integer_key: self.key.streaming_metadata(),
+            tag: self.tag.clone(),
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        IntegerServerKey::streaming_data_len(&metadata.integer_key)
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        self.key.write_streaming_data(writer)
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        Ok(Self {
+            key: Arc::new(IntegerServerKey::read_streaming_data(
+                metadata.integer_key,
+                reader,
+            )?),
+            tag: metadata.tag,
+        })
+    }
+}
+
+/// Parameters of a [`CompressedServerKey`] in the streaming format
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct CompressedServerKeyStreamingMetadata {
+    pub integer_key: IntegerCompressedServerKeyStreamingMetadata,
+    pub tag: Tag,
+}
+
+impl StreamingSerializable for CompressedServerKey {
+    type Element = u8;
+    type Metadata = CompressedServerKeyStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        CompressedServerKeyStreamingMetadata {
+            integer_key: self.integer_key.streaming_metadata(),
+            tag: self.tag.clone(),
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        IntegerCompressedServerKey::streaming_data_len(&metadata.integer_key)
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        self.integer_key.write_streaming_data(writer)
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        Ok(Self {
+            integer_key: IntegerCompressedServerKey::read_streaming_data(
+                metadata.integer_key,
+                reader,
+            )?,
+            tag: metadata.tag,
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::high_level_api::keys::inner::IntegerServerKeyConformanceParams;
@@ -0,0 +1,819 @@
+//! Lazy evaluation of [FheUint] and [FheInt] operations.
+//!
+//! Within [with_lazy_evaluation], the arithmetic, bitwise and comparison operators of [FheUint]
+//! and [FheInt] do not compute anything: they record a node in a graph instead. The graph is
+//! evaluated all at once the first time one of its results is needed, that is when it is
+//! decrypted, serialized, used in an operation that is not recorded, or when `eval` is called.
+//!
+//! The graph is evaluated level by level, a level being the operations whose inputs are all
+//! computed. This allows the executor to:
+//! - apply the lookup tables of all the bitwise operations of a level in a single parallel
+//!   iteration over their blocks,
+//! - run the multiplications and comparisons of a level in parallel,
+//! - chain additions and subtractions without propagating carries in between, carries are only
+//!   propagated when a value is consumed by an operation that needs clean inputs, or when it is
+//!   returned to the user,
+//! - skip the operations whose results are not used anymore.
+
+use crate::high_level_api::booleans::InnerBoolean;
+use crate::high_level_api::global_state;
+use crate::high_level_api::integers::signed::SignedRadixCiphertext;
+use crate::high_level_api::integers::unsigned::RadixCiphertext;
+use crate::high_level_api::integers::{FheIntId, FheUintId};
+use crate::high_level_api::keys::InternalServerKey;
+use crate::integer::bigint::U2048;
+use crate::integer::block_decomposition::{BlockDecomposer, DecomposableInto};
+use crate::integer::ciphertext::IntegerRadixCiphertext;
+use crate::integer::BooleanBlock;
+use crate::{FheBool, FheInt, FheUint, ServerKey, Tag};
+use rayon::prelude::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, OnceLock, Weak};
+
+thread_local! {
+    /// The graph in which operations are recorded, if lazy evaluation is enabled
+    static CURRENT_GRAPH: RefCell<Option<Arc<LazyGraph>>> = const { RefCell::new(None) };
+}
+
+/// Runs `func` with lazy evaluation enabled on the current thread.
+///
+/// The following operations on [FheUint] and [FheInt] done inside `func` are recorded in a graph
+/// instead of being computed right away:
+/// - `+`, `-`, `*`, `&`, `|`, `^` (and their assign versions), between two ciphertexts or with a
+///   clear value on the right hand side,
+/// - `eq`, `ne`, `lt`, `le`, `gt`, `ge` between two ciphertexts, which give lazy [FheBool]s.
+///
+/// The graph is evaluated the first time one of its values is needed, all the operations
+/// recorded are then computed together, which allows batching independent operations.
+///
+/// Values returned by `func` can be used normally, they are evaluated on demand. Operations
+/// that are not recorded accept lazy values as inputs and evaluate them first.
+///
+/// Nested calls record in the same graph as the outermost call.
+///
+/// # Note
+///
+/// - The setting is per thread, operations done in other threads (e.g. within rayon) are computed
+///   right away.
+/// - Only the CPU server key is supported, when a CUDA server key is set, operations are computed
+///   right away.
+///
+/// # Example
+///
+/// ```rust
+/// use tfhe::prelude::*;
+/// use tfhe::{generate_keys, set_server_key, with_lazy_evaluation, ConfigBuilder, FheUint8};
+///
+/// let (client_key, server_key) = generate_keys(ConfigBuilder::default());
+/// set_server_key(server_key);
+///
+/// let a = FheUint8::encrypt(27u8, &client_key);
+/// let b = FheUint8::encrypt(128u8, &client_key);
+/// let c = FheUint8::encrypt(3u8, &client_key);
+///
+/// let (sum, xor, is_lower) = with_lazy_evaluation(|| {
+///     // No carry propagation between the additions
+///     let sum = &a + &b + &c + 1u8;
+///     // Shares its lookup table iteration with the other bitwise operation
+///     let xor = (&a ^ &b) | (&a & &c);
+///     (sum, xor, a.lt(&b))
+/// });
+///
+/// // Evaluates `sum`, `xor` and `is_lower`
+/// let decrypted: u8 = sum.decrypt(&client_key);
+/// assert_eq!(
+///     decrypted,
+///     27u8.wrapping_add(128).wrapping_add(3).wrapping_add(1)
+/// );
+///
+/// let decrypted: u8 = xor.decrypt(&client_key);
+/// assert_eq!(decrypted, (27u8 ^ 128) | (27 & 3));
+///
+/// assert!(is_lower.decrypt(&client_key));
+/// ```
+pub fn with_lazy_evaluation<R>(func: impl FnOnce() -> R) -> R {
+    let _scope = LazyScope::enter();
+    func()
+}
+
+/// Resets the current graph when the outermost scope ends (even on panic)
+struct LazyScope {
+    is_outermost: bool,
+}
+
+impl LazyScope {
+    fn enter() -> Self {
+        let is_outermost = CURRENT_GRAPH.with(|graph| {
+            let mut graph = graph.borrow_mut();
+            if graph.is_some() {
+                false
+            } else {
+                *graph = Some(Arc::new(LazyGraph::default()));
+                true
+            }
+        });
+        Self { is_outermost }
+    }
+}
+
+impl Drop for LazyScope {
+    fn drop(&mut self) {
+        if self.is_outermost {
+            CURRENT_GRAPH.with(|graph| graph.borrow_mut().take());
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(in crate::high_level_api) enum LazyOp {
+    Add,
+    Sub,
+    Mul,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl LazyOp {
+    /// Operations that do not need any PBS, given that carries have room
+    fn is_linear(self) -> bool {
+        matches!(self, Self::Add | Self::Sub)
+    }
+
+    /// Operations computed with one lookup table per block
+    fn is_bitwise(self) -> bool {
+        matches!(self, Self::BitAnd | Self::BitOr | Self::BitXor)
+    }
+}
+
+/// Right hand side of an operation
+#[derive(Clone)]
+enum Operand {
+    Value(Arc<LazyNode>),
+    /// Clear value, modulo 2^num_bits of the left hand side
+    Scalar(U2048),
+}
+
+#[derive(Clone)]
+struct Operation {
+    op: LazyOp,
+    lhs: Arc<LazyNode>,
+    rhs: Operand,
+    // Comparisons depend on the signedness of the inputs
+    is_signed: bool,
+    server_key: ServerKey,
+}
+
+struct PendingOp {
+    operation: Operation,
+    graph: Arc<LazyGraph>,
+}
+
+/// A node of the graph
+///
+/// Either an input of the graph (the value is set from the start), or the result of an
+/// operation, which is set when the graph gets evaluated if the node still has a [LazyValue].
+///
+/// Values stored in nodes always have empty carries.
+struct LazyNode {
+    value: OnceLock<crate::integer::RadixCiphertext>,
+    pending: Mutex<Option<PendingOp>>,
+    // Number of `LazyValue`s referencing this node
+    num_values: AtomicUsize,
+}
+
+impl LazyNode {
+    fn evaluated(mut value: crate::integer::RadixCiphertext, server_key: &ServerKey) -> Arc<Self> {
+        if !value.block_carries_are_empty() {
+            server_key.pbs_key().full_propagate_parallelized(&mut value);
+        }
+        Arc::new(Self {
+            value: OnceLock::from(value),
+            pending: Mutex::new(None),
+            num_values: AtomicUsize::new(0),
+        })
+    }
+
+    fn graph(&self) -> Option<Arc<LazyGraph>> {
+        self.pending
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|pending| pending.graph.clone())
+    }
+
+    /// Returns the value of the node, evaluating its graph if needed
+    fn materialize(&self) -> &crate::integer::RadixCiphertext {
+        if let Some(value) = self.value.get() {
+            return value;
+        }
+
+        if let Some(graph) = self.graph() {
+            graph.evaluate();
+            // The node may be part of an evaluation running on another thread
+            graph.wait_until_evaluated(self);
+        }
+
+        self.value
+            .get()
+            .expect("Internal error: lazy value was not evaluated")
+    }
+}
+
+/// A value of a graph, held by a ciphertext
+///
+/// When the graph is evaluated, only the results of the nodes that still have a [LazyValue] are
+/// kept. The nodes only referenced by other nodes are intermediate results, they are computed
+/// if needed and dropped afterwards.
+pub(crate) struct LazyValue(Arc<LazyNode>);
+
+impl LazyValue {
+    fn new(node: Arc<LazyNode>) -> Self {
+        node.num_values.fetch_add(1, Ordering::SeqCst);
+        Self(node)
+    }
+
+    /// Returns the value, evaluating its graph if needed
+    pub(crate) fn materialize(&self) -> &crate::integer::RadixCiphertext {
+        self.0.materialize()
+    }
+
+    /// Returns the value, evaluating its graph if needed
+    ///
+    /// The value is only cloned if the node is shared.
+    pub(crate) fn into_value(self) -> crate::integer::RadixCiphertext {
+        self.materialize();
+        let node = self.0.clone();
+        drop(self);
+        match Arc::try_unwrap(node) {
+            Ok(node) => node.value.into_inner().unwrap(),
+            Err(node) => node.value.get().unwrap().clone(),
+        }
+    }
+}
+
+impl Clone for LazyValue {
+    fn clone(&self) -> Self {
+        Self::new(self.0.clone())
+    }
+}
+
+impl Drop for LazyValue {
+    fn drop(&mut self) {
+        self.0.num_values.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[derive(Default)]
+struct LazyGraph {
+    // Nodes waiting to be evaluated, in the order they were recorded,
+    // which is a valid topological order.
+    //
+    // Weak references are used so that nodes that are not used anymore
+    // are not evaluated
+    pending: Mutex<Vec<Weak<LazyNode>>>,
+    evaluation_done: Condvar,
+}
+
+/// Marks the nodes of an evaluation as done when dropped, even if the evaluation panicked,
+/// so that threads waiting for them are woken up
+struct RunningEvaluation<'a> {
+    graph: &'a LazyGraph,
+    nodes: &'a [Arc<LazyNode>],
+}
+
+impl Drop for RunningEvaluation<'_> {
+    fn drop(&mut self) {
+        for node in self.nodes {
+            node.pending
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .take();
+        }
+        // Taking the lock makes sure waiting threads either checked the nodes
+        // after they were marked, or are waiting for the notification
+        drop(
+            self.graph
+                .pending
+                .lock()
+                .unwrap_or_else(|err| err.into_inner()),
+        );
+        self.graph.evaluation_done.notify_all();
+    }
+}
+
+impl LazyGraph {
+    fn input_node<T: LazyInteger>(
+        self: &Arc<Self>,
+        input: &T,
+        server_key: &ServerKey,
+    ) -> Arc<LazyNode> {
+        if let Some(LazyValue(node)) = input.lazy_value() {
+            // Values recorded in another graph are evaluated, as this graph
+            // can only depend on values it owns
+            if node.graph().is_some_and(|graph| !Arc::ptr_eq(&graph, self)) {
+                node.materialize();
+            }
+            return node.clone();
+        }
+
+        LazyNode::evaluated(input.to_cpu_radix(), server_key)
+    }
+
+    fn record(self: &Arc<Self>, operation: Operation) -> LazyValue {
+        let node = Arc::new(LazyNode {
+            value: OnceLock::new(),
+            pending: Mutex::new(Some(PendingOp {
+                operation,
+                graph: self.clone(),
+            })),
+            num_values: AtomicUsize::new(0),
+        });
+        // The value must exist before the node can be part of an evaluation,
+        // otherwise the node would not be seen as an output
+        let value = LazyValue::new(node);
+        self.pending.lock().unwrap().push(Arc::downgrade(&value.0));
+        value
+    }
+
+    /// Evaluates all the nodes of the graph still in use
+    ///
+    /// The nodes are taken out of the graph before being evaluated, so that the graph is not
+    /// locked during the evaluation.
+    fn evaluate(&self) {
+        let nodes = self
+            .pending
+            .lock()
+            .unwrap()
+            .drain(..)
+            .filter_map(|node| node.upgrade())
+            .collect::<Vec<_>>();
+        if nodes.is_empty() {
+            return;
+        }
+
+        let _running = RunningEvaluation {
+            graph: self,
+            nodes: &nodes,
+        };
+        evaluate_nodes(&nodes);
+    }
+
+    /// Waits for the evaluation of `node` to be done, in case it was started by another thread
+    fn wait_until_evaluated(&self, node: &LazyNode) {
+        let mut pending = self.pending.lock().unwrap();
+        while node.value.get().is_none() && node.graph().is_some() {
+            pending = self.evaluation_done.wait(pending).unwrap();
+        }
+    }
+}
+
+/// Evaluates `nodes`, which are in topological order
+///
+/// Only the values of the nodes that are outputs are set.
+fn evaluate_nodes(nodes: &[Arc<LazyNode>]) {
+    let index_of = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node_key(node), i))
+        .collect::<HashMap<_, _>>();
+
+    let ops = nodes
+        .iter()
+        .map(|node| {
+            node.pending
+                .lock()
+                .unwrap()
+                .as_ref()
+                .expect("Internal error: node already evaluated")
+                .operation
+                .clone()
+        })
+        .collect::<Vec<_>>();
+
+    // Only the values held by the user are needed, along with the values they depend on.
+    // Inputs are always recorded before the nodes using them.
+    let is_output = nodes
+        .iter()
+        .map(|node| node.num_values.load(Ordering::SeqCst) > 0)
+        .collect::<Vec<_>>();
+    let mut is_needed = is_output.clone();
+    for i in (0..nodes.len()).rev() {
+        if is_needed[i] {
+            for input in ops[i].inputs() {
+                match index_of.get(&node_key(input)) {
+                    Some(&j) => is_needed[j] = true,
+                    // Inputs that are not part of this evaluation are either inputs of the
+                    // graph, or computed by a previous evaluation, which may still be running
+                    None => {
+                        input.materialize();
+                    }
+                }
+            }
+        }
+    }
+
+    let mut depths = vec![0usize; nodes.len()];
+    for i in (0..nodes.len()).filter(|&i| is_needed[i]) {
+        depths[i] = 1 + ops[i]
+            .inputs()
+            .filter_map(|input| index_of.get(&node_key(input)))
+            .map(|&j| depths[j])
+            .max()
+            .unwrap_or(0);
+    }
+    let max_depth = depths.iter().copied().max().unwrap_or(0);
+
+    let mut values: Vec<Option<crate::integer::RadixCiphertext>> = vec![None; nodes.len()];
+
+    for depth in 1..=max_depth {
+        let level = (0..nodes.len())
+            .filter(|&i| depths[i] == depth)
+            .collect::<Vec<_>>();
+
+        // Operations that need PBS require clean inputs, the carry propagations
+        // of all the inputs of the level are done in parallel
+        let mut dirty_inputs = level
+            .iter()
+            .filter(|&&i| !ops[i].op.is_linear())
+            .flat_map(|&i| ops[i].inputs())
+            .filter_map(|input| index_of.get(&node_key(input)).copied())
+            .filter(|&j| {
+                !values[j]
+                    .as_ref()
+                    .is_some_and(|value| value.block_carries_are_empty())
+            })
+            .collect::<Vec<_>>();
+        dirty_inputs.sort_unstable();
+        dirty_inputs.dedup();
+        let mut to_propagate = dirty_inputs
+            .iter()
+            .map(|&j| (j, values[j].take().unwrap()))
+            .collect::<Vec<_>>();
+        to_propagate.par_iter_mut().for_each(|(j, value)| {
+            ops[*j]
+                .server_key
+                .pbs_key()
+                .full_propagate_parallelized(value);
+        });
+        for (j, value) in to_propagate {
+            values[j] = Some(value);
+        }
+
+        let lhs_of = |i: usize| input_value(&index_of, &values, &ops[i].lhs);
+
+        // Additions and subtractions are done without PBS while the carries allow it
+        let linear_results = level
+            .iter()
+            .filter(|&&i| ops[i].op.is_linear())
+            .map(|&i| {
+                let lhs = lhs_of(i);
+                let key = ops[i].server_key.pbs_key();
+                let result = match (ops[i].op, &ops[i].rhs) {
+                    (LazyOp::Add, Operand::Value(rhs)) => {
+                        let rhs = input_value(&index_of, &values, rhs);
+                        key.is_add_possible(lhs, rhs)
+                            .map(|()| key.unchecked_add(lhs, rhs))
+                            .unwrap_or_else(|_| key.add_parallelized(lhs, rhs))
+                    }
+                    (LazyOp::Sub, Operand::Value(rhs)) => {
+                        let rhs = input_value(&index_of, &values, rhs);
+                        key.is_sub_possible(lhs, rhs)
+                            .map(|()| key.unchecked_sub(lhs, rhs))
+                            .unwrap_or_else(|_| key.sub_parallelized(lhs, rhs))
+                    }
+                    (LazyOp::Add, &Operand::Scalar(rhs)) => key
+                        .is_scalar_add_possible(lhs, rhs)
+                        .map(|()| key.unchecked_scalar_add(lhs, rhs))
+                        .unwrap_or_else(|_| key.scalar_add_parallelized(lhs, rhs)),
+                    (LazyOp::Sub, &Operand::Scalar(rhs)) => key
+                        .is_scalar_sub_possible(lhs, rhs)
+                        .map(|()| key.unchecked_scalar_sub(lhs, rhs))
+                        .unwrap_or_else(|_| key.scalar_sub_parallelized(lhs, rhs)),
+                    _ => unreachable!(),
+                };
+                (i, result)
+            })
+            .collect::<Vec<_>>();
+
+        // All the lookup tables of the bitwise operations are applied in a single parallel
+        // iteration, clear right hand sides are split in blocks beforehand
+        let bitwise = level
+            .iter()
+            .copied()
+            .filter(|&i| ops[i].op.is_bitwise())
+            .map(|i| {
+                let num_blocks = lhs_of(i).blocks.len();
+                let scalar_blocks = match ops[i].rhs {
+                    Operand::Value(_) => None,
+                    Operand::Scalar(rhs) => {
+                        let message_modulus = ops[i].server_key.pbs_key().message_modulus();
+                        let decomposer = BlockDecomposer::with_block_count(
+                            rhs,
+                            message_modulus.0.ilog2(),
+                            num_blocks,
+                        );
+                        Some(decomposer.iter_as::<u8>().collect::<Vec<_>>())
+                    }
+                };
+                (i, num_blocks, scalar_blocks)
+            })
+            .collect::<Vec<_>>();
+        let mut bitwise_blocks = bitwise
+            .iter()
+            .flat_map(|(i, num_blocks, scalar_blocks)| {
+                (0..*num_blocks).map(move |b| (*i, b, scalar_blocks.as_ref().map(|s| s[b])))
+            })
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|(i, b, scalar_block)| {
+                let lhs = &lhs_of(i).blocks[b];
+                let key = &ops[i].server_key.pbs_key().key;
+                match (ops[i].op, &ops[i].rhs, scalar_block) {
+                    (op, Operand::Value(rhs), _) => {
+                        let rhs = &input_value(&index_of, &values, rhs).blocks[b];
+                        match op {
+                            LazyOp::BitAnd => key.unchecked_bitand(lhs, rhs),
+                            LazyOp::BitOr => key.unchecked_bitor(lhs, rhs),
+                            LazyOp::BitXor => key.unchecked_bitxor(lhs, rhs),
+                            _ => unreachable!(),
+                        }
+                    }
+                    (LazyOp::BitAnd, Operand::Scalar(_), Some(rhs)) => {
+                        key.unchecked_scalar_bitand(lhs, rhs)
+                    }
+                    (LazyOp::BitOr, Operand::Scalar(_), Some(rhs)) => {
+                        key.unchecked_scalar_bitor(lhs, rhs)
+                    }
+                    (LazyOp::BitXor, Operand::Scalar(_), Some(rhs)) => {
+                        key.unchecked_scalar_bitxor(lhs, rhs)
+                    }
+                    _ => unreachable!(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .into_iter();
+        let bitwise_results = bitwise
+            .iter()
+            .map(|&(i, num_blocks, _)| {
+                let blocks = bitwise_blocks.by_ref().take(num_blocks).collect::<Vec<_>>();
+                (i, crate::integer::RadixCiphertext::from(blocks))
+            })
+            .collect::<Vec<_>>();
+
+        // Multiplications and comparisons are independent, they are run in parallel
+        let other_results = level
+            .iter()
+            .copied()
+            .filter(|&i| !ops[i].op.is_linear() && !ops[i].op.is_bitwise())
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|i| {
+                let lhs = lhs_of(i);
+                let key = ops[i].server_key.pbs_key();
+                let result = match (ops[i].op, &ops[i].rhs) {
+                    (LazyOp::Mul, Operand::Value(rhs)) => {
+                        key.mul_parallelized(lhs, input_value(&index_of, &values, rhs))
+                    }
+                    (LazyOp::Mul, &Operand::Scalar(rhs)) => key.scalar_mul_parallelized(lhs, rhs),
+                    (op, Operand::Value(rhs)) => {
+                        let rhs = input_value(&index_of, &values, rhs);
+                        let result = if ops[i].is_signed {
+                            compare(
+                                key,
+                                op,
+                                &crate::integer::SignedRadixCiphertext::from(lhs.blocks.clone()),
+                                &crate::integer::SignedRadixCiphertext::from(rhs.blocks.clone()),
+                            )
+                        } else {
+                            compare(key, op, lhs, rhs)
+                        };
+                        crate::integer::RadixCiphertext::from(vec![result.into_raw_parts()])
+                    }
+                    _ => unreachable!(),
+                };
+                (i, result)
+            })
+            .collect::<Vec<_>>();
+
+        for (i, result) in linear_results
+            .into_iter()
+            .chain(bitwise_results)
+            .chain(other_results)
+        {
+            values[i] = Some(result);
+        }
+    }
+
+    // Values returned to the user must have clean carries
+    let mut outputs = (0..nodes.len())
+        .filter(|&i| is_output[i])
+        .map(|i| (i, values[i].take().unwrap()))
+        .collect::<Vec<_>>();
+    outputs.par_iter_mut().for_each(|(i, value)| {
+        if !value.block_carries_are_empty() {
+            ops[*i]
+                .server_key
+                .pbs_key()
+                .full_propagate_parallelized(value);
+        }
+    });
+
+    for (i, value) in outputs {
+        // A node may only be evaluated once, as it is removed from the graph
+        let _ = nodes[i].value.set(value);
+    }
+}
+
+impl Operation {
+    fn inputs(&self) -> impl Iterator<Item = &Arc<LazyNode>> {
+        let rhs = match &self.rhs {
+            Operand::Value(rhs) => Some(rhs),
+            Operand::Scalar(_) => None,
+        };
+        std::iter::once(&self.lhs).chain(rhs)
+    }
+}
+
+fn compare<T: IntegerRadixCiphertext>(
+    key: &crate::integer::ServerKey,
+    op: LazyOp,
+    lhs: &T,
+    rhs: &T,
+) -> BooleanBlock {
+    match op {
+        LazyOp::Eq => key.eq_parallelized(lhs, rhs),
+        LazyOp::Ne => key.ne_parallelized(lhs, rhs),
+        LazyOp::Lt => key.lt_parallelized(lhs, rhs),
+        LazyOp::Le => key.le_parallelized(lhs, rhs),
+        LazyOp::Gt => key.gt_parallelized(lhs, rhs),
+        LazyOp::Ge => key.ge_parallelized(lhs, rhs),
+        _ => unreachable!(),
+    }
+}
+
+fn node_key(node: &Arc<LazyNode>) -> usize {
+    Arc::as_ptr(node) as usize
+}
+
+/// Returns the value of an input, which is either computed in the current evaluation,
+/// or was already evaluated
+fn input_value<'a>(
+    index_of: &HashMap<usize, usize>,
+    values: &'a [Option<crate::integer::RadixCiphertext>],
+    node: &'a Arc<LazyNode>,
+) -> &'a crate::integer::RadixCiphertext {
+    match index_of.get(&node_key(node)) {
+        Some(&j) => values[j].as_ref().unwrap(),
+        None => node.value.get().unwrap(),
+    }
+}
+
+/// `value` modulo 2^num_bits, sign extended first if `value` is signed
+fn truncated_scalar<Scalar: DecomposableInto<u8>>(value: Scalar, num_bits: usize) -> U2048 {
+    let mut words = [0u64; 32];
+    let bits = BlockDecomposer::with_block_count(value, 1, num_bits).iter_as::<u8>();
+    for (i, bit) in bits.enumerate() {
+        words[i / 64] |= u64::from(bit) << (i % 64);
+    }
+    U2048::from(words)
+}
+
+/// High level integer types whose operations can be recorded
+pub(in crate::high_level_api) trait LazyInteger {
+    const IS_SIGNED: bool;
+
+    fn num_bits() -> usize;
+
+    /// Returns the node of the value if it is lazy
+    fn lazy_value(&self) -> Option<&LazyValue>;
+
+    /// Returns the blocks of the value, on the CPU
+    fn to_cpu_radix(&self) -> crate::integer::RadixCiphertext;
+
+    fn from_lazy(value: LazyValue, tag: Tag) -> Self;
+}
+
+impl<Id: FheUintId> LazyInteger for FheUint<Id> {
+    const IS_SIGNED: bool = false;
+
+    fn num_bits() -> usize {
+        Id::num_bits()
+    }
+
+    fn lazy_value(&self) -> Option<&LazyValue> {
+        match &self.ciphertext {
+            RadixCiphertext::Lazy(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn to_cpu_radix(&self) -> crate::integer::RadixCiphertext {
+        self.ciphertext.on_cpu().into_owned()
+    }
+
+    fn from_lazy(value: LazyValue, tag: Tag) -> Self {
+        Self::new(RadixCiphertext::Lazy(value), tag)
+    }
+}
+
+impl<Id: FheIntId> LazyInteger for FheInt<Id> {
+    const IS_SIGNED: bool = true;
+
+    fn num_bits() -> usize {
+        Id::num_bits()
+    }
+
+    fn lazy_value(&self) -> Option<&LazyValue> {
+        match &self.ciphertext {
+            SignedRadixCiphertext::Lazy(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn to_cpu_radix(&self) -> crate::integer::RadixCiphertext {
+        crate::integer::RadixCiphertext::from(self.ciphertext.on_cpu().into_owned().blocks)
+    }
+
+    fn from_lazy(value: LazyValue, tag: Tag) -> Self {
+        Self::new(SignedRadixCiphertext::Lazy(value), tag)
+    }
+}
+
+/// Records the operation in the current graph, if lazy evaluation is enabled
+/// on this thread and the server key is a CPU key.
+///
+/// Simulated values are cheap to compute, so they are never recorded.
+///
+/// Returns `None` if the operation is to be computed right away.
+fn record_operation<T: LazyInteger>(
+    op: LazyOp,
+    lhs: &T,
+    rhs: impl FnOnce(&Arc<LazyGraph>, &ServerKey) -> Operand,
+) -> Option<(LazyValue, Tag)> {
+    let graph = CURRENT_GRAPH.with(|graph| graph.borrow().clone())?;
+
+    global_state::with_internal_keys(|key| match key {
+        InternalServerKey::Cpu(cpu_key) => {
+            let operation = Operation {
+                op,
+                lhs: graph.input_node(lhs, cpu_key),
+                rhs: rhs(&graph, cpu_key),
+                is_signed: T::IS_SIGNED,
+                server_key: cpu_key.clone(),
+            };
+            Some((graph.record(operation), cpu_key.tag.clone()))
+        }
+        #[cfg(feature = "gpu")]
+        InternalServerKey::Cuda(_) => None,
+        InternalServerKey::Simulation(_) => None,
+    })
+}
+
+/// See [record_operation], `op` must be an arithmetic or bitwise operation
+pub(in crate::high_level_api) fn record_binary_op<T: LazyInteger>(
+    op: LazyOp,
+    lhs: &T,
+    rhs: &T,
+) -> Option<T> {
+    let (value, tag) = record_operation(op, lhs, |graph, key| {
+        Operand::Value(graph.input_node(rhs, key))
+    })?;
+    Some(T::from_lazy(value, tag))
+}
+
+/// See [record_operation], `op` must be an arithmetic or bitwise operation
+pub(in crate::high_level_api) fn record_scalar_op<T, Scalar>(
+    op: LazyOp,
+    lhs: &T,
+    rhs: Scalar,
+) -> Option<T>
+where
+    T: LazyInteger,
+    Scalar: DecomposableInto<u8>,
+{
+    let (value, tag) = record_operation(op, lhs, |_, _| {
+        Operand::Scalar(truncated_scalar(rhs, T::num_bits()))
+    })?;
+    Some(T::from_lazy(value, tag))
+}
+
+/// See [record_operation], `op` must be a comparison
+pub(in crate::high_level_api) fn record_comparison<T: LazyInteger>(
+    op: LazyOp,
+    lhs: &T,
+    rhs: &T,
+) -> Option<FheBool> {
+    let (value, tag) = record_operation(op, lhs, |graph, key| {
+        Operand::Value(graph.input_node(rhs, key))
+    })?;
+    Some(FheBool::new(InnerBoolean::Lazy(value), tag))
+}
@@ -47,13 +47,21 @@ macro_rules! export_concrete_array_types {
 }
 
 pub use crate::core_crypto::commons::math::random::Seed;
+pub use crate::core_crypto::seeders::MasterSeed;
 pub use crate::integer::server_key::MatchValues;
 use crate::{error, Error};
+pub use async_api::{AsyncServerKey, FheFuture};
 pub use config::{Config, ConfigBuilder};
+pub use context::FheContext;
+pub use errors::TagMismatchError;
 #[cfg(feature = "gpu")]
 pub use global_state::CudaGpuChoice;
-pub use global_state::{set_server_key, unset_server_key, with_server_key_as_context};
+pub use global_state::{
+    set_server_key, set_server_key_with_strict_tags, unset_server_key, with_server_key_as_context,
+};
 
+#[cfg(feature = "pbs-stats")]
+pub use dry_run::{dry_run, DryRun, DryRunReport, OperationCost};
 pub use integers::{
     CompressedFheInt, CompressedFheUint, FheInt, FheUint, IntegerId, SquashedNoiseFheInt,
     SquashedNoiseFheUint,
@@ -62,8 +70,10 @@ pub use integers::{
 pub use keys::CudaServerKey;
 pub use keys::{
     generate_keys, ClientKey, CompactPublicKey, CompressedCompactPublicKey, CompressedPublicKey,
-    CompressedServerKey, KeySwitchingKey, PublicKey, ServerKey,
+    CompressedServerKey, KeyRotationKey, KeySwitchingKey, PublicKey, ServerKey,
+    SimulationServerKey,
 };
+pub use lazy::with_lazy_evaluation;
 use strum::FromRepr;
 
 #[cfg(test)]
@@ -129,13 +139,19 @@ pub use strings::ascii::{EncryptableString, FheAsciiString, FheStringIsEmpty, Fh
 pub use tag::Tag;
 pub use traits::FheId;
 
+mod async_api;
 mod booleans;
 mod compressed_ciphertext_list;
 mod config;
+mod context;
+#[cfg(feature = "pbs-stats")]
+mod dry_run;
 mod errors;
 mod global_state;
 mod integers;
 mod keys;
+mod lazy;
+mod simulation;
 #[cfg(feature = "strings")]
 mod strings;
 mod traits;
@@ -143,7 +159,12 @@ mod utils;
 
 pub mod array;
 pub mod backward_compatibility;
+pub mod circuit;
 mod compact_list;
+pub mod hashing;
+#[cfg(feature = "key-store")]
+pub mod key_store;
+pub mod program;
 mod tag;
 
 #[cfg(feature = "gpu")]
@@ -161,6 +182,8 @@ pub enum Device {
     Cpu,
     #[cfg(feature = "gpu")]
     CudaGpu,
+    /// Clear-value simulation, see [SimulationServerKey]
+    Simulation,
 }
 
 #[derive(FromRepr, Copy, Clone, PartialEq, Eq, Debug)]
@@ -18,5 +18,8 @@ pub use crate::core_crypto::prelude::{CastFrom, CastInto};
 
 pub use crate::high_level_api::array::traits::FheSliceDotProduct;
 
+#[cfg(feature = "key-wrapping")]
+pub use crate::key_wrapping::EncryptedExport;
+
 #[cfg(feature = "strings")]
 pub use crate::high_level_api::strings::traits::*;
@@ -0,0 +1,230 @@
+use super::{for_each_executed, BinaryOp, ComparisonOp, Instruction, Program, UnaryOp, ValueType};
+use crate::high_level_api::backward_compatibility::program::ClearValueVersions;
+use crate::high_level_api::simulation::SimulatedInteger;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use tfhe_versionable::Versionize;
+
+/// A clear value, of one of the types of the registers of a [Program]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Versionize)]
+#[versionize(ClearValueVersions)]
+pub enum ClearValue {
+    Bool(bool),
+    Uint8(u8),
+    Uint16(u16),
+    Uint32(u32),
+    Uint64(u64),
+    Int8(i8),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+}
+
+impl ClearValue {
+    pub fn value_type(&self) -> ValueType {
+        match self {
+            Self::Bool(_) => ValueType::Bool,
+            Self::Uint8(_) => ValueType::Uint8,
+            Self::Uint16(_) => ValueType::Uint16,
+            Self::Uint32(_) => ValueType::Uint32,
+            Self::Uint64(_) => ValueType::Uint64,
+            Self::Int8(_) => ValueType::Int8,
+            Self::Int16(_) => ValueType::Int16,
+            Self::Int32(_) => ValueType::Int32,
+            Self::Int64(_) => ValueType::Int64,
+        }
+    }
+
+    /// Returns the zero (or `false`) value of the type
+    pub fn zero(value_type: ValueType) -> Self {
+        match value_type {
+            ValueType::Bool => Self::Bool(false),
+            ValueType::Uint8 => Self::Uint8(0),
+            ValueType::Uint16 => Self::Uint16(0),
+            ValueType::Uint32 => Self::Uint32(0),
+            ValueType::Uint64 => Self::Uint64(0),
+            ValueType::Int8 => Self::Int8(0),
+            ValueType::Int16 => Self::Int16(0),
+            ValueType::Int32 => Self::Int32(0),
+            ValueType::Int64 => Self::Int64(0),
+        }
+    }
+
+    // Booleans are represented as 1 bit unsigned integers
+    fn to_simulated(self) -> SimulatedInteger {
+        let value_type = self.value_type();
+        let (num_bits, is_signed) = (value_type.num_bits(), value_type.is_signed());
+        match self {
+            Self::Bool(value) => SimulatedInteger::from_bool(value, num_bits, is_signed),
+            Self::Uint8(value) => SimulatedInteger::from_scalar(value, num_bits, is_signed),
+            Self::Uint16(value) => SimulatedInteger::from_scalar(value, num_bits, is_signed),
+            Self::Uint32(value) => SimulatedInteger::from_scalar(value, num_bits, is_signed),
+            Self::Uint64(value) => SimulatedInteger::from_scalar(value, num_bits, is_signed),
+            Self::Int8(value) => SimulatedInteger::from_scalar(value, num_bits, is_signed),
+            Self::Int16(value) => SimulatedInteger::from_scalar(value, num_bits, is_signed),
+            Self::Int32(value) => SimulatedInteger::from_scalar(value, num_bits, is_signed),
+            Self::Int64(value) => SimulatedInteger::from_scalar(value, num_bits, is_signed),
+        }
+    }
+
+    fn from_simulated(value: SimulatedInteger, value_type: ValueType) -> Self {
+        let value = value.cast(value_type.num_bits(), value_type.is_signed());
+        match value_type {
+            ValueType::Bool => Self::Bool(value.to_bool()),
+            ValueType::Uint8 => Self::Uint8(value.to_unsigned()),
+            ValueType::Uint16 => Self::Uint16(value.to_unsigned()),
+            ValueType::Uint32 => Self::Uint32(value.to_unsigned()),
+            ValueType::Uint64 => Self::Uint64(value.to_unsigned()),
+            ValueType::Int8 => Self::Int8(value.to_signed()),
+            ValueType::Int16 => Self::Int16(value.to_signed()),
+            ValueType::Int32 => Self::Int32(value.to_signed()),
+            ValueType::Int64 => Self::Int64(value.to_signed()),
+        }
+    }
+}
+
+macro_rules! impl_from_clear {
+    ($($variant:ident($clear_type:ty)),* $(,)?) => {
+        $(
+            impl From<$clear_type> for ClearValue {
+                fn from(value: $clear_type) -> Self {
+                    Self::$variant(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_clear!(
+    Bool(bool),
+    Uint8(u8),
+    Uint16(u16),
+    Uint32(u32),
+    Uint64(u64),
+    Int8(i8),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+);
+
+impl Program {
+    /// Executes the program on clear values
+    ///
+    /// The results are the same as the ones of [Program::run] on the encryptions of the inputs.
+    pub fn run_clear(&self, inputs: &[ClearValue]) -> crate::Result<Vec<ClearValue>> {
+        self.validate()?;
+        self.check_input_types(inputs.iter().map(ClearValue::value_type))?;
+
+        let mut registers = self
+            .registers
+            .iter()
+            .map(|&value_type| ClearValue::zero(value_type))
+            .collect::<Vec<_>>();
+        for (&register, &input) in self.inputs.iter().zip(inputs) {
+            registers[register] = input;
+        }
+
+        for_each_executed(&self.instructions, &mut |instruction| {
+            let (dst, result) = execute_clear(instruction, &registers);
+            registers[dst] = result;
+        })?;
+
+        Ok(self
+            .outputs
+            .iter()
+            .map(|&register| registers[register])
+            .collect())
+    }
+}
+
+fn execute_clear(instruction: &Instruction, registers: &[ClearValue]) -> (usize, ClearValue) {
+    let value = |register: usize| registers[register].to_simulated();
+    let register_type = |register: usize| registers[register].value_type();
+
+    match *instruction {
+        Instruction::Constant { dst, value } => (dst, value),
+        Instruction::Copy { dst, src } => (dst, registers[src]),
+        Instruction::Unary { op, dst, src } => {
+            let result = match (op, registers[src]) {
+                (UnaryOp::Not, ClearValue::Bool(value)) => ClearValue::Bool(!value),
+                (UnaryOp::Not, _) => {
+                    ClearValue::from_simulated(value(src).bitnot(), register_type(src))
+                }
+                (UnaryOp::Neg, _) => {
+                    ClearValue::from_simulated(value(src).neg(), register_type(src))
+                }
+            };
+            (dst, result)
+        }
+        Instruction::Binary { op, dst, lhs, rhs } => {
+            let (a, b) = (value(lhs), value(rhs));
+            let result = match op {
+                BinaryOp::Add => a.add(&b),
+                BinaryOp::Sub => a.sub(&b),
+                BinaryOp::Mul => a.mul(&b),
+                BinaryOp::Div => a.div(&b),
+                BinaryOp::Rem => a.rem(&b),
+                BinaryOp::Min => a.min(&b),
+                BinaryOp::Max => a.max(&b),
+                BinaryOp::BitAnd => a.bitand(&b),
+                BinaryOp::BitOr => a.bitor(&b),
+                BinaryOp::BitXor => a.bitxor(&b),
+                BinaryOp::Shl => a.shl_encrypted(&b),
+                BinaryOp::Shr => a.shr_encrypted(&b),
+                BinaryOp::RotateLeft => a.rotate_left_encrypted(&b),
+                BinaryOp::RotateRight => a.rotate_right_encrypted(&b),
+            };
+            (dst, ClearValue::from_simulated(result, register_type(lhs)))
+        }
+        Instruction::Compare { op, dst, lhs, rhs } => {
+            let ordering = value(lhs).cmp(&value(rhs));
+            let result = match op {
+                ComparisonOp::Eq => ordering == Ordering::Equal,
+                ComparisonOp::Ne => ordering != Ordering::Equal,
+                ComparisonOp::Lt => ordering == Ordering::Less,
+                ComparisonOp::Le => ordering != Ordering::Greater,
+                ComparisonOp::Gt => ordering == Ordering::Greater,
+                ComparisonOp::Ge => ordering != Ordering::Less,
+            };
+            (dst, ClearValue::Bool(result))
+        }
+        Instruction::Select {
+            dst,
+            condition,
+            if_true,
+            if_false,
+        } => {
+            let result = if registers[condition] == ClearValue::Bool(true) {
+                registers[if_true]
+            } else {
+                registers[if_false]
+            };
+            (dst, result)
+        }
+        Instruction::Cast { dst, src } => {
+            let dst_type = register_type(dst);
+            let result = if dst_type == ValueType::Bool {
+                ClearValue::Bool(value(src).to_bool())
+            } else {
+                ClearValue::from_simulated(value(src), dst_type)
+            };
+            (dst, result)
+        }
+        Instruction::Load {
+            dst,
+            index,
+            ref table,
+        } => {
+            let index = value(index).to_unsigned::<u64>();
+            let result = usize::try_from(index)
+                .ok()
+                .and_then(|index| table.get(index))
+                .map_or_else(
+                    || ClearValue::zero(register_type(dst)),
+                    |&register| registers[register],
+                );
+            (dst, result)
+        }
+        Instruction::Repeat { .. } => unreachable!("loops are unrolled by for_each_executed"),
+    }
+}
@@ -0,0 +1,379 @@
+use super::{
+    for_each_executed, BinaryOp, ClearValue, ComparisonOp, Instruction, Program, UnaryOp, ValueType,
+};
+use crate::high_level_api::context::FheContext;
+use crate::prelude::*;
+use crate::{
+    ClientKey, FheBool, FheInt16, FheInt32, FheInt64, FheInt8, FheUint16, FheUint32, FheUint64,
+    FheUint8,
+};
+
+/// An encrypted value, of one of the types of the registers of a [Program]
+#[derive(Clone)]
+pub enum FheValue {
+    Bool(FheBool),
+    Uint8(FheUint8),
+    Uint16(FheUint16),
+    Uint32(FheUint32),
+    Uint64(FheUint64),
+    Int8(FheInt8),
+    Int16(FheInt16),
+    Int32(FheInt32),
+    Int64(FheInt64),
+}
+
+/// Applies the closure to the integer values, which are all of the same type
+macro_rules! map_integers {
+    (($($value:expr),+), |$($name:ident),+| $body:expr) => {
+        map_integers!(
+            @arms ($($value),+), ($($name),+), $body,
+            [Uint8, Uint16, Uint32, Uint64, Int8, Int16, Int32, Int64]
+        )
+    };
+    (@arms ($($value:expr),+), ($($name:ident),+), $body:expr, [$($variant:ident),*]) => {
+        match ($($value),+) {
+            $(($(FheValue::$variant($name)),+) => FheValue::$variant($body),)*
+            _ => unreachable!("operand types are checked before execution"),
+        }
+    };
+}
+
+/// Same as [map_integers] but the closure returns a [FheBool]
+macro_rules! compare_integers {
+    (($lhs:expr, $rhs:expr), |$a:ident, $b:ident| $body:expr) => {
+        compare_integers!(
+            @arms ($lhs, $rhs), ($a, $b), $body,
+            [Uint8, Uint16, Uint32, Uint64, Int8, Int16, Int32, Int64]
+        )
+    };
+    (@arms ($lhs:expr, $rhs:expr), ($a:ident, $b:ident), $body:expr, [$($variant:ident),*]) => {
+        match ($lhs, $rhs) {
+            $((FheValue::$variant($a), FheValue::$variant($b)) => $body,)*
+            _ => unreachable!("operand types are checked before execution"),
+        }
+    };
+}
+
+/// Applies the closure to an integer and an unsigned integer of the same size
+macro_rules! map_shifts {
+    (($lhs:expr, $rhs:expr), |$a:ident, $b:ident| $body:expr) => {
+        match ($lhs, $rhs) {
+            (FheValue::Uint8($a), FheValue::Uint8($b)) => FheValue::Uint8($body),
+            (FheValue::Uint16($a), FheValue::Uint16($b)) => FheValue::Uint16($body),
+            (FheValue::Uint32($a), FheValue::Uint32($b)) => FheValue::Uint32($body),
+            (FheValue::Uint64($a), FheValue::Uint64($b)) => FheValue::Uint64($body),
+            (FheValue::Int8($a), FheValue::Uint8($b)) => FheValue::Int8($body),
+            (FheValue::Int16($a), FheValue::Uint16($b)) => FheValue::Int16($body),
+            (FheValue::Int32($a), FheValue::Uint32($b)) => FheValue::Int32($body),
+            (FheValue::Int64($a), FheValue::Uint64($b)) => FheValue::Int64($body),
+            _ => unreachable!("operand types are checked before execution"),
+        }
+    };
+}
+
+/// Converts the value to the given integer type
+macro_rules! cast_to {
+    ($value:expr, $variant:ident, $fhe_type:ty) => {
+        match $value {
+            FheValue::Bool(v) => FheValue::$variant(<$fhe_type>::cast_from(v.clone())),
+            FheValue::Uint8(v) => FheValue::$variant(<$fhe_type>::cast_from(v.clone())),
+            FheValue::Uint16(v) => FheValue::$variant(<$fhe_type>::cast_from(v.clone())),
+            FheValue::Uint32(v) => FheValue::$variant(<$fhe_type>::cast_from(v.clone())),
+            FheValue::Uint64(v) => FheValue::$variant(<$fhe_type>::cast_from(v.clone())),
+            FheValue::Int8(v) => FheValue::$variant(<$fhe_type>::cast_from(v.clone())),
+            FheValue::Int16(v) => FheValue::$variant(<$fhe_type>::cast_from(v.clone())),
+            FheValue::Int32(v) => FheValue::$variant(<$fhe_type>::cast_from(v.clone())),
+            FheValue::Int64(v) => FheValue::$variant(<$fhe_type>::cast_from(v.clone())),
+        }
+    };
+}
+
+macro_rules! impl_conversions {
+    ($($variant:ident($fhe_type:ty)),* $(,)?) => {
+        $(
+            impl From<$fhe_type> for FheValue {
+                fn from(value: $fhe_type) -> Self {
+                    Self::$variant(value)
+                }
+            }
+
+            impl TryFrom<FheValue> for $fhe_type {
+                type Error = crate::Error;
+
+                fn try_from(value: FheValue) -> Result<Self, Self::Error> {
+                    match value {
+                        FheValue::$variant(value) => Ok(value),
+                        other => Err(crate::error!(
+                            "Expected a {:?} value, got a {:?} value",
+                            ValueType::$variant,
+                            other.value_type()
+                        )),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_conversions!(
+    Bool(FheBool),
+    Uint8(FheUint8),
+    Uint16(FheUint16),
+    Uint32(FheUint32),
+    Uint64(FheUint64),
+    Int8(FheInt8),
+    Int16(FheInt16),
+    Int32(FheInt32),
+    Int64(FheInt64),
+);
+
+impl FheValue {
+    pub fn value_type(&self) -> ValueType {
+        match self {
+            Self::Bool(_) => ValueType::Bool,
+            Self::Uint8(_) => ValueType::Uint8,
+            Self::Uint16(_) => ValueType::Uint16,
+            Self::Uint32(_) => ValueType::Uint32,
+            Self::Uint64(_) => ValueType::Uint64,
+            Self::Int8(_) => ValueType::Int8,
+            Self::Int16(_) => ValueType::Int16,
+            Self::Int32(_) => ValueType::Int32,
+            Self::Int64(_) => ValueType::Int64,
+        }
+    }
+
+    /// Trivially encrypts the value
+    pub fn encrypt_trivial(value: ClearValue) -> Self {
+        match value {
+            ClearValue::Bool(v) => Self::Bool(FheBool::encrypt_trivial(v)),
+            ClearValue::Uint8(v) => Self::Uint8(FheUint8::encrypt_trivial(v)),
+            ClearValue::Uint16(v) => Self::Uint16(FheUint16::encrypt_trivial(v)),
+            ClearValue::Uint32(v) => Self::Uint32(FheUint32::encrypt_trivial(v)),
+            ClearValue::Uint64(v) => Self::Uint64(FheUint64::encrypt_trivial(v)),
+            ClearValue::Int8(v) => Self::Int8(FheInt8::encrypt_trivial(v)),
+            ClearValue::Int16(v) => Self::Int16(FheInt16::encrypt_trivial(v)),
+            ClearValue::Int32(v) => Self::Int32(FheInt32::encrypt_trivial(v)),
+            ClearValue::Int64(v) => Self::Int64(FheInt64::encrypt_trivial(v)),
+        }
+    }
+
+    fn is_true(&self) -> &FheBool {
+        match self {
+            Self::Bool(value) => value,
+            _ => unreachable!("operand types are checked before execution"),
+        }
+    }
+}
+
+impl FheEncrypt<ClearValue, ClientKey> for FheValue {
+    fn encrypt(value: ClearValue, key: &ClientKey) -> Self {
+        match value {
+            ClearValue::Bool(v) => Self::Bool(FheBool::encrypt(v, key)),
+            ClearValue::Uint8(v) => Self::Uint8(FheUint8::encrypt(v, key)),
+            ClearValue::Uint16(v) => Self::Uint16(FheUint16::encrypt(v, key)),
+            ClearValue::Uint32(v) => Self::Uint32(FheUint32::encrypt(v, key)),
+            ClearValue::Uint64(v) => Self::Uint64(FheUint64::encrypt(v, key)),
+            ClearValue::Int8(v) => Self::Int8(FheInt8::encrypt(v, key)),
+            ClearValue::Int16(v) => Self::Int16(FheInt16::encrypt(v, key)),
+            ClearValue::Int32(v) => Self::Int32(FheInt32::encrypt(v, key)),
+            ClearValue::Int64(v) => Self::Int64(FheInt64::encrypt(v, key)),
+        }
+    }
+}
+
+impl FheDecrypt<ClearValue> for FheValue {
+    fn decrypt(&self, key: &ClientKey) -> ClearValue {
+        match self {
+            Self::Bool(v) => ClearValue::Bool(v.decrypt(key)),
+            Self::Uint8(v) => ClearValue::Uint8(v.decrypt(key)),
+            Self::Uint16(v) => ClearValue::Uint16(v.decrypt(key)),
+            Self::Uint32(v) => ClearValue::Uint32(v.decrypt(key)),
+            Self::Uint64(v) => ClearValue::Uint64(v.decrypt(key)),
+            Self::Int8(v) => ClearValue::Int8(v.decrypt(key)),
+            Self::Int16(v) => ClearValue::Int16(v.decrypt(key)),
+            Self::Int32(v) => ClearValue::Int32(v.decrypt(key)),
+            Self::Int64(v) => ClearValue::Int64(v.decrypt(key)),
+        }
+    }
+}
+
+impl Program {
+    /// Executes the program on encrypted values, using the server key set for the current thread
+    ///
+    /// # Panics
+    ///
+    /// Panics if the server key is not set
+    #[track_caller]
+    pub fn run(&self, inputs: &[FheValue]) -> crate::Result<Vec<FheValue>> {
+        self.validate()?;
+        self.check_input_types(inputs.iter().map(FheValue::value_type))?;
+
+        let context = FheContext::from_current_thread();
+        let mut registers = self
+            .registers
+            .iter()
+            .map(|&value_type| FheValue::encrypt_trivial(ClearValue::zero(value_type)))
+            .collect::<Vec<_>>();
+        for (&register, input) in self.inputs.iter().zip(inputs) {
+            registers[register] = input.clone();
+        }
+
+        for_each_executed(&self.instructions, &mut |instruction| {
+            let (dst, result) = execute(instruction, &registers, &context);
+            registers[dst] = result;
+        })?;
+
+        Ok(self
+            .outputs
+            .iter()
+            .map(|&register| registers[register].clone())
+            .collect())
+    }
+}
+
+fn execute(
+    instruction: &Instruction,
+    registers: &[FheValue],
+    context: &FheContext,
+) -> (usize, FheValue) {
+    match *instruction {
+        Instruction::Constant { dst, value } => (dst, FheValue::encrypt_trivial(value)),
+        Instruction::Copy { dst, src } => (dst, registers[src].clone()),
+        Instruction::Unary { op, dst, src } => {
+            let result = match (op, &registers[src]) {
+                (UnaryOp::Not, FheValue::Bool(value)) => FheValue::Bool(!value),
+                (UnaryOp::Not, value) => map_integers!((value), |a| !a),
+                (UnaryOp::Neg, value) => map_integers!((value), |a| -a),
+            };
+            (dst, result)
+        }
+        Instruction::Binary { op, dst, lhs, rhs } => {
+            let operands = (&registers[lhs], &registers[rhs]);
+            let result = match (op, operands) {
+                (BinaryOp::BitAnd, (FheValue::Bool(a), FheValue::Bool(b))) => FheValue::Bool(a & b),
+                (BinaryOp::BitOr, (FheValue::Bool(a), FheValue::Bool(b))) => FheValue::Bool(a | b),
+                (BinaryOp::BitXor, (FheValue::Bool(a), FheValue::Bool(b))) => FheValue::Bool(a ^ b),
+                (BinaryOp::Add, (lhs, rhs)) => map_integers!((lhs, rhs), |a, b| a + b),
+                (BinaryOp::Sub, (lhs, rhs)) => map_integers!((lhs, rhs), |a, b| a - b),
+                (BinaryOp::Mul, (lhs, rhs)) => map_integers!((lhs, rhs), |a, b| a * b),
+                (BinaryOp::Div, (lhs, rhs)) => map_integers!((lhs, rhs), |a, b| a / b),
+                (BinaryOp::Rem, (lhs, rhs)) => map_integers!((lhs, rhs), |a, b| a % b),
+                (BinaryOp::Min, (lhs, rhs)) => map_integers!((lhs, rhs), |a, b| a.min(b)),
+                (BinaryOp::Max, (lhs, rhs)) => map_integers!((lhs, rhs), |a, b| a.max(b)),
+                (BinaryOp::BitAnd, (lhs, rhs)) => map_integers!((lhs, rhs), |a, b| a & b),
+                (BinaryOp::BitOr, (lhs, rhs)) => map_integers!((lhs, rhs), |a, b| a | b),
+                (BinaryOp::BitXor, (lhs, rhs)) => map_integers!((lhs, rhs), |a, b| a ^ b),
+                (BinaryOp::Shl, (lhs, rhs)) => map_shifts!((lhs, rhs), |a, b| a << b),
+                (BinaryOp::Shr, (lhs, rhs)) => map_shifts!((lhs, rhs), |a, b| a >> b),
+                (BinaryOp::RotateLeft, (lhs, rhs)) => {
+                    map_shifts!((lhs, rhs), |a, b| a.rotate_left(b))
+                }
+                (BinaryOp::RotateRight, (lhs, rhs)) => {
+                    map_shifts!((lhs, rhs), |a, b| a.rotate_right(b))
+                }
+            };
+            (dst, result)
+        }
+        Instruction::Compare { op, dst, lhs, rhs } => {
+            let operands = (&registers[lhs], &registers[rhs]);
+            let result = match (op, operands) {
+                (ComparisonOp::Eq, (FheValue::Bool(a), FheValue::Bool(b))) => a.eq(b),
+                (ComparisonOp::Ne, (FheValue::Bool(a), FheValue::Bool(b))) => a.ne(b),
+                (ComparisonOp::Eq, (lhs, rhs)) => compare_integers!((lhs, rhs), |a, b| a.eq(b)),
+                (ComparisonOp::Ne, (lhs, rhs)) => compare_integers!((lhs, rhs), |a, b| a.ne(b)),
+                (ComparisonOp::Lt, (lhs, rhs)) => compare_integers!((lhs, rhs), |a, b| a.lt(b)),
+                (ComparisonOp::Le, (lhs, rhs)) => compare_integers!((lhs, rhs), |a, b| a.le(b)),
+                (ComparisonOp::Gt, (lhs, rhs)) => compare_integers!((lhs, rhs), |a, b| a.gt(b)),
+                (ComparisonOp::Ge, (lhs, rhs)) => compare_integers!((lhs, rhs), |a, b| a.ge(b)),
+            };
+            (dst, FheValue::Bool(result))
+        }
+        Instruction::Select {
+            dst,
+            condition,
+            if_true,
+            if_false,
+        } => (
+            dst,
+            select(
+                registers[condition].is_true(),
+                &registers[if_true],
+                &registers[if_false],
+            ),
+        ),
+        Instruction::Cast { dst, src } => {
+            let value = &registers[src];
+            let result = match registers[dst].value_type() {
+                ValueType::Bool => match value {
+                    FheValue::Bool(v) => FheValue::Bool(v.clone()),
+                    FheValue::Uint8(v) => FheValue::Bool(v.ne(0u8)),
+                    FheValue::Uint16(v) => FheValue::Bool(v.ne(0u16)),
+                    FheValue::Uint32(v) => FheValue::Bool(v.ne(0u32)),
+                    FheValue::Uint64(v) => FheValue::Bool(v.ne(0u64)),
+                    FheValue::Int8(v) => FheValue::Bool(v.ne(0i8)),
+                    FheValue::Int16(v) => FheValue::Bool(v.ne(0i16)),
+                    FheValue::Int32(v) => FheValue::Bool(v.ne(0i32)),
+                    FheValue::Int64(v) => FheValue::Bool(v.ne(0i64)),
+                },
+                ValueType::Uint8 => cast_to!(value, Uint8, FheUint8),
+                ValueType::Uint16 => cast_to!(value, Uint16, FheUint16),
+                ValueType::Uint32 => cast_to!(value, Uint32, FheUint32),
+                ValueType::Uint64 => cast_to!(value, Uint64, FheUint64),
+                ValueType::Int8 => cast_to!(value, Int8, FheInt8),
+                ValueType::Int16 => cast_to!(value, Int16, FheInt16),
+                ValueType::Int32 => cast_to!(value, Int32, FheInt32),
+                ValueType::Int64 => cast_to!(value, Int64, FheInt64),
+            };
+            (dst, result)
+        }
+        Instruction::Load {
+            dst,
+            index,
+            ref table,
+        } => {
+            let value_type = registers[dst].value_type();
+            (
+                dst,
+                load(&registers[index], table, value_type, registers, context),
+            )
+        }
+        Instruction::Repeat { .. } => unreachable!("loops are unrolled by for_each_executed"),
+    }
+}
+
+fn select(condition: &FheBool, if_true: &FheValue, if_false: &FheValue) -> FheValue {
+    match (if_true, if_false) {
+        (FheValue::Bool(a), FheValue::Bool(b)) => FheValue::Bool(condition.select(a, b)),
+        (lhs, rhs) => map_integers!((lhs, rhs), |a, b| condition.select(a, b)),
+    }
+}
+
+/// Reads `table[index]`, the comparisons of the index with each position of the table are
+/// computed in parallel
+fn load(
+    index: &FheValue,
+    table: &[usize],
+    value_type: ValueType,
+    registers: &[FheValue],
+    context: &FheContext,
+) -> FheValue {
+    let is_index = |position: usize| -> Option<FheBool> {
+        match index {
+            FheValue::Uint8(v) => u8::try_from(position).ok().map(|p| v.eq(p)),
+            FheValue::Uint16(v) => u16::try_from(position).ok().map(|p| v.eq(p)),
+            FheValue::Uint32(v) => u32::try_from(position).ok().map(|p| v.eq(p)),
+            FheValue::Uint64(v) => u64::try_from(position).ok().map(|p| v.eq(p)),
+            _ => unreachable!("operand types are checked before execution"),
+        }
+    };
+    let conditions = context.par_map(0..table.len(), is_index);
+
+    // Out of bounds reads give zero, positions the index type cannot represent are skipped
+    let mut result = FheValue::encrypt_trivial(ClearValue::zero(value_type));
+    for (condition, &register) in conditions.iter().zip(table) {
+        if let Some(condition) = condition {
+            result = select(condition, &registers[register], &result);
+        }
+    }
+    result
+}
@@ -0,0 +1,537 @@
+//! Interpreter for small programs over encrypted values.
+//!
+//! A [Program] works on typed registers, holding [FheBool](crate::FheBool),
+//! [FheUint8](crate::FheUint8) to [FheUint64](crate::FheUint64) or [FheInt8](crate::FheInt8) to
+//! [FheInt64](crate::FheInt64) values, and is a flat list of [Instruction]s. The control flow of a
+//! program never depends on encrypted data: loops repeat their body a clear number of times,
+//! conditional values are computed with [Instruction::Select] and memory is read at encrypted
+//! indices with [Instruction::Load].
+//!
+//! Programs are plain data, they can be created at runtime and serialized. They are executed:
+//! - on encrypted values with [Program::run], using the server key set for the current thread,
+//! - on clear values with [Program::run_clear], which gives the same results as [Program::run],
+//!   including for edge cases like divisions by zero, and can be used to test programs.
+//!
+//! All the registers start at zero (or `false`), the inputs are then copied to their registers.
+//!
+//! # Example
+//!
+//! ```rust
+//! use tfhe::prelude::*;
+//! use tfhe::program::{
+//!     BinaryOp, ClearValue, ComparisonOp, FheValue, Instruction, Program, ValueType,
+//! };
+//! use tfhe::{generate_keys, set_server_key, ConfigBuilder};
+//!
+//! // Counts how many of 4 values are greater than a threshold
+//! let values = [0, 1, 2, 3];
+//! let (threshold, index, count, one, value, increment) = (4, 5, 6, 7, 8, 9);
+//! let is_greater = 10;
+//! let mut registers = vec![ValueType::Uint8; 10];
+//! registers.push(ValueType::Bool);
+//! let program = Program::new(
+//!     registers,
+//!     vec![0, 1, 2, 3, threshold],
+//!     vec![count],
+//!     vec![
+//!         Instruction::Constant {
+//!             dst: one,
+//!             value: ClearValue::Uint8(1),
+//!         },
+//!         Instruction::Repeat {
+//!             count: 4,
+//!             body_len: 5,
+//!         },
+//!         Instruction::Load {
+//!             dst: value,
+//!             index,
+//!             table: values.to_vec(),
+//!         },
+//!         Instruction::Compare {
+//!             op: ComparisonOp::Gt,
+//!             dst: is_greater,
+//!             lhs: value,
+//!             rhs: threshold,
+//!         },
+//!         Instruction::Cast {
+//!             dst: increment,
+//!             src: is_greater,
+//!         },
+//!         Instruction::Binary {
+//!             op: BinaryOp::Add,
+//!             dst: count,
+//!             lhs: count,
+//!             rhs: increment,
+//!         },
+//!         Instruction::Binary {
+//!             op: BinaryOp::Add,
+//!             dst: index,
+//!             lhs: index,
+//!             rhs: one,
+//!         },
+//!     ],
+//! )
+//! .unwrap();
+//!
+//! let (client_key, server_key) = generate_keys(ConfigBuilder::default());
+//! set_server_key(server_key);
+//!
+//! let clear_inputs = [12u8, 200, 47, 99, 50].map(ClearValue::Uint8);
+//! let inputs = clear_inputs
+//!     .iter()
+//!     .map(|&value| FheValue::encrypt(value, &client_key))
+//!     .collect::<Vec<_>>();
+//!
+//! let outputs = program.run(&inputs).unwrap();
+//! let count: ClearValue = outputs[0].decrypt(&client_key);
+//! assert_eq!(count, ClearValue::Uint8(2));
+//! assert_eq!(program.run_clear(&clear_inputs).unwrap(), vec![count]);
+//! ```
+
+mod clear;
+mod fhe;
+
+pub use clear::ClearValue;
+pub use fhe::FheValue;
+
+use crate::high_level_api::backward_compatibility::program::*;
+use crate::named::Named;
+use serde::{Deserialize, Serialize};
+use tfhe_versionable::Versionize;
+
+/// Type of a register
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Versionize)]
+#[versionize(ValueTypeVersions)]
+pub enum ValueType {
+    Bool,
+    Uint8,
+    Uint16,
+    Uint32,
+    Uint64,
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+}
+
+impl ValueType {
+    /// Returns the number of bits of the type, 1 for booleans
+    pub fn num_bits(self) -> u32 {
+        match self {
+            Self::Bool => 1,
+            Self::Uint8 | Self::Int8 => 8,
+            Self::Uint16 | Self::Int16 => 16,
+            Self::Uint32 | Self::Int32 => 32,
+            Self::Uint64 | Self::Int64 => 64,
+        }
+    }
+
+    pub fn is_signed(self) -> bool {
+        matches!(self, Self::Int8 | Self::Int16 | Self::Int32 | Self::Int64)
+    }
+
+    pub fn is_unsigned(self) -> bool {
+        matches!(
+            self,
+            Self::Uint8 | Self::Uint16 | Self::Uint32 | Self::Uint64
+        )
+    }
+
+    pub fn is_integer(self) -> bool {
+        self != Self::Bool
+    }
+}
+
+/// Operations with one operand
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Versionize)]
+#[versionize(UnaryOpVersions)]
+pub enum UnaryOp {
+    /// Bitwise not for integers, logical not for booleans
+    Not,
+    /// Wrapping negation, for integers
+    Neg,
+}
+
+/// Operations with two operands, whose result has the type of the left operand
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Versionize)]
+#[versionize(BinaryOpVersions)]
+pub enum BinaryOp {
+    /// Wrapping addition, for integers
+    Add,
+    /// Wrapping subtraction, for integers
+    Sub,
+    /// Wrapping multiplication, for integers
+    Mul,
+    /// Division, for integers, see [FheUint](crate::FheUint) for the result of a division by 0
+    Div,
+    /// Remainder, for integers
+    Rem,
+    /// For integers
+    Min,
+    /// For integers
+    Max,
+    /// For integers and booleans
+    BitAnd,
+    /// For integers and booleans
+    BitOr,
+    /// For integers and booleans
+    BitXor,
+    /// For integers, the amount is an unsigned integer of the same size, taken modulo the number
+    /// of bits
+    Shl,
+    /// Same as [BinaryOp::Shl], arithmetic shift for signed integers
+    Shr,
+    /// Same as [BinaryOp::Shl]
+    RotateLeft,
+    /// Same as [BinaryOp::Shl]
+    RotateRight,
+}
+
+impl BinaryOp {
+    fn is_shift(self) -> bool {
+        matches!(
+            self,
+            Self::Shl | Self::Shr | Self::RotateLeft | Self::RotateRight
+        )
+    }
+
+    fn is_bitwise(self) -> bool {
+        matches!(self, Self::BitAnd | Self::BitOr | Self::BitXor)
+    }
+}
+
+/// Comparisons, whose result is a boolean
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Versionize)]
+#[versionize(ComparisonOpVersions)]
+pub enum ComparisonOp {
+    /// For integers and booleans
+    Eq,
+    /// For integers and booleans
+    Ne,
+    /// For integers
+    Lt,
+    /// For integers
+    Le,
+    /// For integers
+    Gt,
+    /// For integers
+    Ge,
+}
+
+/// An instruction of a [Program], registers are identified by their index
+///
+/// The destination register of an instruction may also be one of its operands.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Versionize)]
+#[versionize(InstructionVersions)]
+pub enum Instruction {
+    /// `dst = value`
+    Constant { dst: usize, value: ClearValue },
+    /// `dst = src`
+    Copy { dst: usize, src: usize },
+    /// `dst = op(src)`
+    Unary { op: UnaryOp, dst: usize, src: usize },
+    /// `dst = op(lhs, rhs)`
+    Binary {
+        op: BinaryOp,
+        dst: usize,
+        lhs: usize,
+        rhs: usize,
+    },
+    /// `dst = op(lhs, rhs)`, `lhs` and `rhs` have the same type and `dst` is a boolean
+    Compare {
+        op: ComparisonOp,
+        dst: usize,
+        lhs: usize,
+        rhs: usize,
+    },
+    /// `dst = if condition { if_true } else { if_false }`, `condition` is a boolean
+    Select {
+        dst: usize,
+        condition: usize,
+        if_true: usize,
+        if_false: usize,
+    },
+    /// Converts `src` to the type of `dst`
+    ///
+    /// Integers are truncated or extended (with sign extension if `src` is signed), booleans
+    /// are converted to 0 or 1, and integers to `src != 0`.
+    Cast { dst: usize, src: usize },
+    /// `dst = table[index]`, where `index` is an unsigned integer, or 0 if `index` is out of the
+    /// bounds of the table
+    ///
+    /// All the registers of the table have the type of `dst`.
+    Load {
+        dst: usize,
+        index: usize,
+        table: Vec<usize>,
+    },
+    /// Executes the next `body_len` instructions `count` times
+    Repeat { count: u32, body_len: usize },
+}
+
+/// A program over encrypted values, see the [module level documentation](self)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Versionize)]
+#[versionize(ProgramVersions)]
+pub struct Program {
+    registers: Vec<ValueType>,
+    inputs: Vec<usize>,
+    outputs: Vec<usize>,
+    instructions: Vec<Instruction>,
+}
+
+impl Named for Program {
+    const NAME: &'static str = "high_level_api::Program";
+}
+
+impl Program {
+    /// Creates a program, checking that the instructions are well typed
+    ///
+    /// - `registers` are the types of the registers,
+    /// - `inputs` are the registers the inputs are copied to before execution,
+    /// - `outputs` are the registers whose values are returned after execution.
+    pub fn new(
+        registers: Vec<ValueType>,
+        inputs: Vec<usize>,
+        outputs: Vec<usize>,
+        instructions: Vec<Instruction>,
+    ) -> crate::Result<Self> {
+        let program = Self {
+            registers,
+            inputs,
+            outputs,
+            instructions,
+        };
+        program.validate()?;
+        Ok(program)
+    }
+
+    pub fn registers(&self) -> &[ValueType] {
+        &self.registers
+    }
+
+    pub fn inputs(&self) -> &[usize] {
+        &self.inputs
+    }
+
+    pub fn outputs(&self) -> &[usize] {
+        &self.outputs
+    }
+
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    /// Returns the types of the inputs
+    pub fn input_types(&self) -> impl Iterator<Item = ValueType> + '_ {
+        self.inputs.iter().map(|&register| self.registers[register])
+    }
+
+    /// Returns the types of the outputs
+    pub fn output_types(&self) -> impl Iterator<Item = ValueType> + '_ {
+        self.outputs
+            .iter()
+            .map(|&register| self.registers[register])
+    }
+
+    /// Checks that the program is well formed, deserialized programs are checked again before
+    /// being executed
+    fn validate(&self) -> crate::Result<()> {
+        let register_type = |register: usize| {
+            self.registers
+                .get(register)
+                .copied()
+                .ok_or_else(|| crate::error!("Register {register} does not exist"))
+        };
+        for &register in self.inputs.iter().chain(&self.outputs) {
+            register_type(register)?;
+        }
+
+        self.validate_block(0, self.instructions.len(), &register_type)
+    }
+
+    fn validate_block(
+        &self,
+        start: usize,
+        end: usize,
+        register_type: &impl Fn(usize) -> crate::Result<ValueType>,
+    ) -> crate::Result<()> {
+        let mut position = start;
+        while position < end {
+            let instruction = &self.instructions[position];
+            self.validate_instruction(instruction, register_type)
+                .map_err(|err| crate::error!("Invalid instruction {position}: {err}"))?;
+
+            if let Instruction::Repeat { body_len, .. } = *instruction {
+                let body_end = position
+                    .checked_add(1)
+                    .and_then(|body_start| body_start.checked_add(body_len))
+                    .filter(|&body_end| body_end <= end)
+                    .ok_or_else(|| {
+                        crate::error!(
+                            "Invalid instruction {position}: the body of the loop ends after \
+                             the enclosing block"
+                        )
+                    })?;
+                self.validate_block(position + 1, body_end, register_type)?;
+                position = body_end;
+            } else {
+                position += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_instruction(
+        &self,
+        instruction: &Instruction,
+        register_type: &impl Fn(usize) -> crate::Result<ValueType>,
+    ) -> crate::Result<()> {
+        let same_types = |types: &[ValueType]| types.windows(2).all(|pair| pair[0] == pair[1]);
+
+        match *instruction {
+            Instruction::Constant { dst, value } => {
+                let dst_type = register_type(dst)?;
+                if value.value_type() != dst_type {
+                    return Err(crate::error!(
+                        "a {:?} constant cannot be stored in a {dst_type:?} register",
+                        value.value_type()
+                    ));
+                }
+            }
+            Instruction::Copy { dst, src } => {
+                if register_type(dst)? != register_type(src)? {
+                    return Err(crate::error!("registers have different types"));
+                }
+            }
+            Instruction::Unary { op, dst, src } => {
+                let src_type = register_type(src)?;
+                if register_type(dst)? != src_type {
+                    return Err(crate::error!("registers have different types"));
+                }
+                if op == UnaryOp::Neg && !src_type.is_integer() {
+                    return Err(crate::error!("{op:?} requires an integer"));
+                }
+            }
+            Instruction::Binary { op, dst, lhs, rhs } => {
+                let (dst_type, lhs_type, rhs_type) = (
+                    register_type(dst)?,
+                    register_type(lhs)?,
+                    register_type(rhs)?,
+                );
+                if op.is_shift() {
+                    if dst_type != lhs_type
+                        || !lhs_type.is_integer()
+                        || !rhs_type.is_unsigned()
+                        || lhs_type.num_bits() != rhs_type.num_bits()
+                    {
+                        return Err(crate::error!(
+                            "{op:?} requires an integer and an unsigned integer of the same size"
+                        ));
+                    }
+                } else {
+                    if !same_types(&[dst_type, lhs_type, rhs_type]) {
+                        return Err(crate::error!("registers have different types"));
+                    }
+                    if !op.is_bitwise() && !lhs_type.is_integer() {
+                        return Err(crate::error!("{op:?} requires integers"));
+                    }
+                }
+            }
+            Instruction::Compare { op, dst, lhs, rhs } => {
+                let lhs_type = register_type(lhs)?;
+                if register_type(dst)? != ValueType::Bool {
+                    return Err(crate::error!("the result of a comparison is a boolean"));
+                }
+                if lhs_type != register_type(rhs)? {
+                    return Err(crate::error!("registers have different types"));
+                }
+                if !matches!(op, ComparisonOp::Eq | ComparisonOp::Ne) && !lhs_type.is_integer() {
+                    return Err(crate::error!("{op:?} requires integers"));
+                }
+            }
+            Instruction::Select {
+                dst,
+                condition,
+                if_true,
+                if_false,
+            } => {
+                if register_type(condition)? != ValueType::Bool {
+                    return Err(crate::error!("the condition must be a boolean"));
+                }
+                let types = [
+                    register_type(dst)?,
+                    register_type(if_true)?,
+                    register_type(if_false)?,
+                ];
+                if !same_types(&types) {
+                    return Err(crate::error!("registers have different types"));
+                }
+            }
+            Instruction::Cast { dst, src } => {
+                register_type(dst)?;
+                register_type(src)?;
+            }
+            Instruction::Load {
+                dst,
+                index,
+                ref table,
+            } => {
+                if !register_type(index)?.is_unsigned() {
+                    return Err(crate::error!("the index must be an unsigned integer"));
+                }
+                let dst_type = register_type(dst)?;
+                for &register in table {
+                    if register_type(register)? != dst_type {
+                        return Err(crate::error!("registers have different types"));
+                    }
+                }
+            }
+            Instruction::Repeat { .. } => {}
+        }
+        Ok(())
+    }
+
+    fn check_input_types(&self, input_types: impl Iterator<Item = ValueType>) -> crate::Result<()> {
+        let input_types = input_types.collect::<Vec<_>>();
+        let expected = self.input_types().collect::<Vec<_>>();
+        if input_types != expected {
+            return Err(crate::error!(
+                "Expected inputs of types {expected:?}, got {input_types:?}"
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Calls `execute` on each instruction of the block, in execution order
+///
+/// Returns an error if the body of a loop ends after the block.
+fn for_each_executed(
+    instructions: &[Instruction],
+    execute: &mut impl FnMut(&Instruction),
+) -> crate::Result<()> {
+    let mut position = 0;
+    while position < instructions.len() {
+        match instructions[position] {
+            Instruction::Repeat { count, body_len } => {
+                let body = instructions[position + 1..]
+                    .get(..body_len)
+                    .ok_or_else(|| {
+                        crate::error!(
+                            "Invalid instruction {position}: the body of the loop ends after \
+                             the enclosing block"
+                        )
+                    })?;
+                for _ in 0..count {
+                    for_each_executed(body, execute)?;
+                }
+                position += 1 + body_len;
+            }
+            ref instruction => {
+                execute(instruction);
+                position += 1;
+            }
+        }
+    }
+    Ok(())
+}
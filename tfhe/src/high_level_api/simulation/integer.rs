@@ -0,0 +1,545 @@
+use crate::core_crypto::commons::math::random::RandomGenerator;
+use crate::core_crypto::prelude::{CastFrom, DefaultRandomGenerator, UnsignedNumeric};
+use crate::high_level_api::keys::SimulationServerKey;
+use crate::integer::bigint::{I2048, U2048};
+use crate::integer::block_decomposition::{
+    BlockDecomposer, BlockRecomposer, DecomposableInto, RecomposableFrom, RecomposableSignedInteger,
+};
+use crate::Seed;
+use std::cmp::Ordering;
+use std::ops::Range;
+
+/// Clear value of an integer of the simulation backend
+///
+/// The value is stored as the `num_bits` lower bits of a 2048 bits integer (using two's
+/// complement for signed integers), `num_bits` being the number of bits of the radix
+/// ciphertext this value simulates (i.e. number of blocks times the bits of message per block).
+///
+/// All the operations reproduce the result the radix operations of the
+/// [integer](crate::integer) module give, including for edge cases (division by zero, shift
+/// amounts larger than the number of bits, etc).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SimulatedInteger {
+    bits: U2048,
+    num_bits: u32,
+    is_signed: bool,
+}
+
+impl SimulatedInteger {
+    pub(crate) fn new(bits: U2048, num_bits: u32, is_signed: bool) -> Self {
+        assert!(
+            num_bits <= U2048::BITS,
+            "The simulation backend supports integers of at most {} bits",
+            U2048::BITS
+        );
+        Self {
+            bits: bits & Self::mask(num_bits),
+            num_bits,
+            is_signed,
+        }
+    }
+
+    pub(crate) fn zero(num_bits: u32, is_signed: bool) -> Self {
+        Self::new(U2048::ZERO, num_bits, is_signed)
+    }
+
+    pub(crate) fn from_bool(value: bool, num_bits: u32, is_signed: bool) -> Self {
+        Self::new(U2048::from(value), num_bits, is_signed)
+    }
+
+    /// Creates a simulated value from a clear one, signed clear values are sign extended
+    pub(crate) fn from_scalar<T>(value: T, num_bits: u32, is_signed: bool) -> Self
+    where
+        T: DecomposableInto<u64>,
+    {
+        let mut bytes = [0u8; (U2048::BITS / u8::BITS) as usize];
+        let decomposer = BlockDecomposer::with_block_count(value, u8::BITS, bytes.len());
+        for (byte, decomposed) in bytes.iter_mut().zip(decomposer.iter_as::<u64>()) {
+            *byte = decomposed as u8;
+        }
+        let mut bits = U2048::ZERO;
+        bits.copy_from_le_byte_slice(&bytes);
+        Self::new(bits, num_bits, is_signed)
+    }
+
+    /// Clear counterpart of the oblivious pseudo random generation, the value is taken
+    /// uniformly in `[0, 2^random_bits_count[`
+    ///
+    /// For a given seed, the value differs from the one the other backends generate.
+    pub(crate) fn pseudo_random(
+        seed: Seed,
+        random_bits_count: u64,
+        num_bits: u32,
+        is_signed: bool,
+    ) -> Self {
+        let range_log_size = u64::from(num_bits);
+        assert!(
+            random_bits_count <= range_log_size,
+            "The range asked for a random value (=[0, 2^{random_bits_count}[) does not fit in the available range [0, 2^{range_log_size}[",
+        );
+
+        let mut generator = RandomGenerator::<DefaultRandomGenerator>::new(seed);
+        let mut bytes = [0u8; (U2048::BITS / u8::BITS) as usize];
+        for byte in bytes
+            .iter_mut()
+            .take(random_bits_count.div_ceil(u64::from(u8::BITS)) as usize)
+        {
+            *byte = generator.generate_next();
+        }
+        let mut bits = U2048::ZERO;
+        bits.copy_from_le_byte_slice(&bytes);
+        let bits = bits & Self::mask(random_bits_count as u32);
+        Self::new(bits, num_bits, is_signed)
+    }
+
+    /// Creates a simulated value from a clear one, with the same size and signedness as self
+    pub(crate) fn scalar<T>(&self, value: T) -> Self
+    where
+        T: DecomposableInto<u64>,
+    {
+        Self::from_scalar(value, self.num_bits, self.is_signed)
+    }
+
+    /// Decrypts a ciphertext encrypted with the client key of the simulation key
+    pub(crate) fn from_radix(
+        ct: &crate::integer::RadixCiphertext,
+        key: &SimulationServerKey,
+    ) -> Self {
+        let bits: U2048 = key.client_key.key.key.decrypt_radix(ct);
+        Self::new(bits, key.num_bits(ct.blocks.len()), false)
+    }
+
+    /// Decrypts a ciphertext encrypted with the client key of the simulation key
+    pub(crate) fn from_signed_radix(
+        ct: &crate::integer::SignedRadixCiphertext,
+        key: &SimulationServerKey,
+    ) -> Self {
+        let value: I2048 = key.client_key.key.key.decrypt_signed_radix(ct);
+        Self::new(U2048::cast_from(value), key.num_bits(ct.blocks.len()), true)
+    }
+
+    /// Encrypts the value with the client key of the simulation key
+    pub(crate) fn to_radix(self, key: &SimulationServerKey) -> crate::integer::RadixCiphertext {
+        key.client_key
+            .key
+            .key
+            .encrypt_radix(self.bits, self.num_blocks(key))
+    }
+
+    /// Encrypts the value with the client key of the simulation key
+    pub(crate) fn to_signed_radix(
+        self,
+        key: &SimulationServerKey,
+    ) -> crate::integer::SignedRadixCiphertext {
+        crate::integer::SignedRadixCiphertext::from(self.to_radix(key).blocks)
+    }
+
+    pub(crate) fn to_unsigned<T>(self) -> T
+    where
+        T: RecomposableFrom<u64> + UnsignedNumeric,
+    {
+        BlockRecomposer::recompose_unsigned(self.le_bytes(), u8::BITS)
+    }
+
+    pub(crate) fn to_signed<T>(self) -> T
+    where
+        T: RecomposableSignedInteger,
+    {
+        BlockRecomposer::recompose_signed_with_size(self.le_bytes(), u8::BITS, self.num_bits)
+    }
+
+    fn le_bytes(&self) -> impl Iterator<Item = u64> {
+        let mut bytes = [0u8; (U2048::BITS / u8::BITS) as usize];
+        self.bits.copy_to_le_byte_slice(&mut bytes);
+        bytes.into_iter().map(u64::from)
+    }
+
+    pub(crate) fn num_bits(&self) -> u32 {
+        self.num_bits
+    }
+
+    pub(crate) fn num_blocks(&self, key: &SimulationServerKey) -> usize {
+        (self.num_bits / key.message_modulus().0.ilog2()) as usize
+    }
+
+    pub(crate) fn is_signed(&self) -> bool {
+        self.is_signed
+    }
+
+    pub(crate) fn is_zero(&self) -> bool {
+        self.bits == U2048::ZERO
+    }
+
+    pub(crate) fn is_negative(&self) -> bool {
+        self.is_signed && self.sign_bit()
+    }
+
+    fn mask(num_bits: u32) -> U2048 {
+        if num_bits >= U2048::BITS {
+            U2048::MAX
+        } else {
+            (U2048::ONE << num_bits) - U2048::ONE
+        }
+    }
+
+    fn sign_bit(&self) -> bool {
+        self.num_bits != 0 && self.bit(self.num_bits - 1)
+    }
+
+    fn bit(&self, index: u32) -> bool {
+        (self.bits >> index) & U2048::ONE == U2048::ONE
+    }
+
+    fn with_bits(&self, bits: U2048) -> Self {
+        Self::new(bits, self.num_bits, self.is_signed)
+    }
+
+    fn all_ones(&self) -> Self {
+        self.with_bits(U2048::MAX)
+    }
+
+    /// Interprets the value in `num_bits` (with sign extension if signed)
+    pub(crate) fn cast(self, num_bits: u32, is_signed: bool) -> Self {
+        let bits = if self.is_negative() {
+            self.bits | !Self::mask(self.num_bits)
+        } else {
+            self.bits
+        };
+        Self::new(bits, num_bits, is_signed)
+    }
+
+    pub(crate) fn is_even(&self) -> bool {
+        !self.bit(0)
+    }
+
+    pub(crate) fn to_bool(self) -> bool {
+        !self.is_zero()
+    }
+
+    pub(crate) fn add(&self, rhs: &Self) -> Self {
+        self.with_bits(self.bits + rhs.bits)
+    }
+
+    pub(crate) fn sub(&self, rhs: &Self) -> Self {
+        self.with_bits(self.bits.wrapping_sub(rhs.bits))
+    }
+
+    pub(crate) fn mul(&self, rhs: &Self) -> Self {
+        self.with_bits(self.bits * rhs.bits)
+    }
+
+    pub(crate) fn neg(&self) -> Self {
+        self.with_bits(U2048::ZERO.wrapping_sub(self.bits))
+    }
+
+    pub(crate) fn bitand(&self, rhs: &Self) -> Self {
+        self.with_bits(self.bits & rhs.bits)
+    }
+
+    pub(crate) fn bitor(&self, rhs: &Self) -> Self {
+        self.with_bits(self.bits | rhs.bits)
+    }
+
+    pub(crate) fn bitxor(&self, rhs: &Self) -> Self {
+        self.with_bits(self.bits ^ rhs.bits)
+    }
+
+    pub(crate) fn bitnot(&self) -> Self {
+        self.with_bits(!self.bits)
+    }
+
+    pub(crate) fn abs(&self) -> Self {
+        if self.is_negative() {
+            self.neg()
+        } else {
+            *self
+        }
+    }
+
+    /// Returns the result and whether the operation overflowed
+    pub(crate) fn overflowing_add(&self, rhs: &Self) -> (Self, bool) {
+        let result = self.add(rhs);
+        let overflowed = if self.is_signed {
+            self.sign_bit() == rhs.sign_bit() && result.sign_bit() != self.sign_bit()
+        } else {
+            result.bits < self.bits
+        };
+        (result, overflowed)
+    }
+
+    /// Returns the result and whether the operation overflowed
+    pub(crate) fn overflowing_sub(&self, rhs: &Self) -> (Self, bool) {
+        let result = self.sub(rhs);
+        let overflowed = if self.is_signed {
+            self.sign_bit() != rhs.sign_bit() && result.sign_bit() != self.sign_bit()
+        } else {
+            rhs.bits > self.bits
+        };
+        (result, overflowed)
+    }
+
+    /// Returns the result and whether the operation overflowed
+    pub(crate) fn overflowing_mul(&self, rhs: &Self) -> (Self, bool) {
+        let result = self.mul(rhs);
+        if !self.is_signed {
+            let overflowed = !self.is_zero() && result.bits / self.bits != rhs.bits;
+            return (result, overflowed);
+        }
+
+        // Work on the magnitudes, |MIN| is representable when seen as unsigned
+        let lhs_abs = self.abs().cast(self.num_bits, false);
+        let rhs_abs = rhs.abs().cast(self.num_bits, false);
+        let (magnitude, magnitude_overflowed) = lhs_abs.overflowing_mul(&rhs_abs);
+
+        let max_magnitude = if self.is_negative() != rhs.is_negative() {
+            // |MIN|
+            U2048::ONE << (self.num_bits - 1)
+        } else {
+            // MAX
+            (U2048::ONE << (self.num_bits - 1)) - U2048::ONE
+        };
+        let overflowed = magnitude_overflowed || magnitude.bits > max_magnitude;
+        (result, overflowed)
+    }
+
+    /// Computes the quotient and the remainder
+    ///
+    /// A division by zero gives a quotient with all bits set for unsigned integers (-1 or 1
+    /// for signed integers depending on the sign of the numerator), and the numerator as
+    /// remainder.
+    pub(crate) fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        if !self.is_signed {
+            if rhs.is_zero() {
+                return (self.all_ones(), *self);
+            }
+            return (
+                self.with_bits(self.bits / rhs.bits),
+                self.with_bits(self.bits % rhs.bits),
+            );
+        }
+
+        // Signed division is done on the absolute values
+        let numerator = self.abs().cast(self.num_bits, false);
+        let divisor = rhs.abs().cast(self.num_bits, false);
+        let (quotient, remainder) = numerator.div_rem(&divisor);
+        let quotient = quotient.cast(self.num_bits, true);
+        let remainder = remainder.cast(self.num_bits, true);
+
+        let quotient = if self.is_negative() != rhs.is_negative() {
+            quotient.neg()
+        } else {
+            quotient
+        };
+        let remainder = if self.is_negative() {
+            remainder.neg()
+        } else {
+            remainder
+        };
+        (quotient, remainder)
+    }
+
+    /// Division by a clear value, which, like for the radix implementation, must not be zero
+    pub(crate) fn scalar_div_rem<T>(&self, rhs: T) -> (Self, Self)
+    where
+        T: DecomposableInto<u64>,
+    {
+        let rhs = self.scalar(rhs);
+        assert!(!rhs.is_zero(), "attempt to divide by 0");
+        self.div_rem(&rhs)
+    }
+
+    pub(crate) fn div(&self, rhs: &Self) -> Self {
+        self.div_rem(rhs).0
+    }
+
+    pub(crate) fn rem(&self, rhs: &Self) -> Self {
+        self.div_rem(rhs).1
+    }
+
+    /// Returns the shift/rotation amount encrypted by `amount`, as computed by the radix
+    /// barrel shifter
+    ///
+    /// Only the bits needed to represent a shift by `num_bits - 1` are looked at, so for a
+    /// number of bits that is not a power of two, the amount may still be greater or equal
+    /// to `num_bits`.
+    fn encrypted_amount(&self, amount: &Self) -> u32 {
+        let mut num_bits_that_tell_shift = self.num_bits.ilog2();
+        if !self.num_bits.is_power_of_two() {
+            num_bits_that_tell_shift += 1;
+        }
+        let masked = amount.bits & Self::mask(num_bits_that_tell_shift);
+        u32::cast_from(masked)
+    }
+
+    pub(crate) fn shl(&self, amount: u32) -> Self {
+        if amount >= self.num_bits {
+            return self.with_bits(U2048::ZERO);
+        }
+        self.with_bits(self.bits << amount)
+    }
+
+    /// Logical shift for unsigned integers, arithmetic shift for signed integers
+    pub(crate) fn shr(&self, amount: u32) -> Self {
+        let fill = if self.is_negative() {
+            self.all_ones()
+        } else {
+            self.with_bits(U2048::ZERO)
+        };
+        if amount >= self.num_bits {
+            return fill;
+        }
+        let shifted = self.bits >> amount;
+        let fill_bits = fill.bits & !(Self::mask(self.num_bits) >> amount);
+        self.with_bits(shifted | fill_bits)
+    }
+
+    pub(crate) fn rotate_left(&self, amount: u32) -> Self {
+        let amount = amount % self.num_bits;
+        if amount == 0 {
+            return *self;
+        }
+        self.with_bits((self.bits << amount) | (self.bits >> (self.num_bits - amount)))
+    }
+
+    pub(crate) fn rotate_right(&self, amount: u32) -> Self {
+        let amount = amount % self.num_bits;
+        self.rotate_left(self.num_bits - amount)
+    }
+
+    pub(crate) fn shl_encrypted(&self, amount: &Self) -> Self {
+        self.shl(self.encrypted_amount(amount))
+    }
+
+    pub(crate) fn shr_encrypted(&self, amount: &Self) -> Self {
+        self.shr(self.encrypted_amount(amount))
+    }
+
+    pub(crate) fn rotate_left_encrypted(&self, amount: &Self) -> Self {
+        self.rotate_left(self.encrypted_amount(amount))
+    }
+
+    pub(crate) fn rotate_right_encrypted(&self, amount: &Self) -> Self {
+        self.rotate_right(self.encrypted_amount(amount))
+    }
+
+    /// Shift/rotation by a clear amount, which is taken modulo the number of bits
+    pub(crate) fn scalar_amount<T>(&self, amount: T) -> u32
+    where
+        T: DecomposableInto<u64>,
+    {
+        let amount = Self::from_scalar(amount, U2048::BITS, false);
+        u32::cast_from(amount.bits % U2048::from(self.num_bits))
+    }
+
+    pub(crate) fn cmp(&self, rhs: &Self) -> Ordering {
+        if self.is_signed {
+            // Flipping the sign bits maps the signed order onto the unsigned order
+            let sign = U2048::ONE << (self.num_bits - 1);
+            (self.bits ^ sign).cmp(&(rhs.bits ^ sign))
+        } else {
+            self.bits.cmp(&rhs.bits)
+        }
+    }
+
+    /// Compares with a clear value, the clear value is not truncated to the number of bits of
+    /// self, so out of range values compare as they would with integers of unbounded size
+    pub(crate) fn cmp_scalar<T>(&self, scalar: T) -> Ordering
+    where
+        T: DecomposableInto<u64>,
+    {
+        let scalar = Self::from_scalar(scalar, U2048::BITS, self.is_signed);
+        self.cast(U2048::BITS, self.is_signed).cmp(&scalar)
+    }
+
+    pub(crate) fn min(&self, rhs: &Self) -> Self {
+        if self.cmp(rhs) == Ordering::Greater {
+            *rhs
+        } else {
+            *self
+        }
+    }
+
+    pub(crate) fn max(&self, rhs: &Self) -> Self {
+        if self.cmp(rhs) == Ordering::Less {
+            *rhs
+        } else {
+            *self
+        }
+    }
+
+    pub(crate) fn count_ones(&self) -> u32 {
+        self.bits.0.iter().map(|word| word.count_ones()).sum()
+    }
+
+    pub(crate) fn count_zeros(&self) -> u32 {
+        self.num_bits - self.count_ones()
+    }
+
+    pub(crate) fn leading_zeros(&self) -> u32 {
+        let unused_bits = U2048::BITS - self.num_bits;
+        self.bits.leading_zeros() - unused_bits
+    }
+
+    pub(crate) fn leading_ones(&self) -> u32 {
+        self.bitnot().leading_zeros()
+    }
+
+    pub(crate) fn trailing_zeros(&self) -> u32 {
+        let mut count = 0;
+        for word in self.bits.0 {
+            count += word.trailing_zeros();
+            if word != 0 {
+                break;
+            }
+        }
+        count.min(self.num_bits)
+    }
+
+    pub(crate) fn trailing_ones(&self) -> u32 {
+        self.bitnot().trailing_zeros()
+    }
+
+    /// Base 2 logarithm, with the value the radix implementation gives for inputs <= 0
+    ///
+    /// The radix implementation computes `(num_bits - 1) - leading_zeros` on a signed counter
+    /// just large enough for the result, so for a zero input the result is -1 in the counter
+    /// seen as an unsigned value.
+    pub(crate) fn ilog2(&self, key: &SimulationServerKey) -> u32 {
+        let leading_zeros = if self.is_negative() {
+            0
+        } else {
+            self.leading_zeros()
+        };
+        if leading_zeros < self.num_bits {
+            return self.num_bits - 1 - leading_zeros;
+        }
+
+        let bits_per_block = key.message_modulus().0.ilog2();
+        let counter_num_blocks = ((self.num_bits - 1).ilog2() + 2).div_ceil(bits_per_block);
+        let counter_num_bits = counter_num_blocks * bits_per_block;
+        u32::MAX >> (u32::BITS - counter_num_bits)
+    }
+
+    /// Returns the base 2 logarithm, and whether the value is > 0
+    pub(crate) fn checked_ilog2(&self, key: &SimulationServerKey) -> (u32, bool) {
+        let is_ok = !self.is_zero() && !self.is_negative();
+        (self.ilog2(key), is_ok)
+    }
+
+    /// Extracts the bits in `range`, the range must already be validated
+    pub(crate) fn bitslice(&self, range: Range<usize>) -> Self {
+        let width = (range.end - range.start) as u32;
+        let sliced = (self.bits >> range.start as u32) & Self::mask(width);
+        self.with_bits(sliced)
+    }
+
+    pub(crate) fn reverse_bits(&self) -> Self {
+        let mut reversed = U2048::ZERO;
+        for i in 0..self.num_bits {
+            if self.bit(i) {
+                reversed = reversed | (U2048::ONE << (self.num_bits - 1 - i));
+            }
+        }
+        self.with_bits(reversed)
+    }
+}
@@ -0,0 +1,6 @@
+//! Clear-value backend of the high level API
+//!
+//! See [SimulationServerKey](crate::SimulationServerKey).
+mod integer;
+
+pub(crate) use integer::SimulatedInteger;
@@ -1,4 +1,4 @@
-use crate::high_level_api::global_state::with_internal_keys;
+use crate::high_level_api::global_state::{check_tags, with_internal_keys};
 use crate::high_level_api::keys::InternalServerKey;
 use crate::high_level_api::strings::ascii::FheAsciiString;
 use crate::prelude::{FheEq, FheEqIgnoreCase, FheOrd};
@@ -7,6 +7,7 @@ use crate::FheBool;
 
 impl FheEq<&Self> for FheAsciiString {
     fn eq(&self, other: &Self) -> FheBool {
+        check_tags([&self.tag, &other.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key
@@ -18,10 +19,14 @@ impl FheEq<&Self> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings eq");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings eq");
+            }
         })
     }
 
     fn ne(&self, other: &Self) -> FheBool {
+        check_tags([&self.tag, &other.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key
@@ -33,12 +38,16 @@ impl FheEq<&Self> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings ne");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings ne");
+            }
         })
     }
 }
 
 impl FheEq<&ClearString> for FheAsciiString {
     fn eq(&self, other: &ClearString) -> FheBool {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key.string_key().eq(&self.inner.on_cpu(), other.into());
@@ -48,10 +57,14 @@ impl FheEq<&ClearString> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings eq");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings eq");
+            }
         })
     }
 
     fn ne(&self, other: &ClearString) -> FheBool {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key.string_key().ne(&self.inner.on_cpu(), other.into());
@@ -61,12 +74,16 @@ impl FheEq<&ClearString> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings ne");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings ne");
+            }
         })
     }
 }
 
 impl FheOrd<&Self> for FheAsciiString {
     fn lt(&self, other: &Self) -> FheBool {
+        check_tags([&self.tag, &other.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key
@@ -78,10 +95,14 @@ impl FheOrd<&Self> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings lt");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings lt");
+            }
         })
     }
 
     fn le(&self, other: &Self) -> FheBool {
+        check_tags([&self.tag, &other.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key
@@ -93,10 +114,14 @@ impl FheOrd<&Self> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings le");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings le");
+            }
         })
     }
 
     fn gt(&self, other: &Self) -> FheBool {
+        check_tags([&self.tag, &other.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key
@@ -108,10 +133,14 @@ impl FheOrd<&Self> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings gt");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings gt");
+            }
         })
     }
 
     fn ge(&self, other: &Self) -> FheBool {
+        check_tags([&self.tag, &other.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key
@@ -123,12 +152,16 @@ impl FheOrd<&Self> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings ge");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings ge");
+            }
         })
     }
 }
 
 impl FheOrd<&ClearString> for FheAsciiString {
     fn lt(&self, other: &ClearString) -> FheBool {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key.string_key().lt(&self.inner.on_cpu(), other.into());
@@ -138,10 +171,14 @@ impl FheOrd<&ClearString> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings lt");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings lt");
+            }
         })
     }
 
     fn le(&self, other: &ClearString) -> FheBool {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key.string_key().le(&self.inner.on_cpu(), other.into());
@@ -151,10 +188,14 @@ impl FheOrd<&ClearString> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings le");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings le");
+            }
         })
     }
 
     fn gt(&self, other: &ClearString) -> FheBool {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key.string_key().gt(&self.inner.on_cpu(), other.into());
@@ -164,10 +205,14 @@ impl FheOrd<&ClearString> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings gt");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings gt");
+            }
         })
     }
 
     fn ge(&self, other: &ClearString) -> FheBool {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key.string_key().ge(&self.inner.on_cpu(), other.into());
@@ -177,6 +222,9 @@ impl FheOrd<&ClearString> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings ge");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings ge");
+            }
         })
     }
 }
@@ -205,6 +253,7 @@ impl FheEqIgnoreCase for FheAsciiString {
     /// assert!(is_eq.decrypt(&client_key));
     /// ```
     fn eq_ignore_case(&self, rhs: &Self) -> FheBool {
+        check_tags([&self.tag, &rhs.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key
@@ -216,6 +265,9 @@ impl FheEqIgnoreCase for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings eq_ignore_case");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings eq_ignore_case");
+            }
         })
     }
 }
@@ -244,6 +296,7 @@ impl FheEqIgnoreCase<ClearString> for FheAsciiString {
     /// assert!(is_eq.decrypt(&client_key));
     /// ```
     fn eq_ignore_case(&self, rhs: &ClearString) -> FheBool {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key
@@ -255,6 +308,9 @@ impl FheEqIgnoreCase<ClearString> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings eq_ignore_case");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings eq_ignore_case");
+            }
         })
     }
 }
@@ -1,4 +1,4 @@
-use crate::high_level_api::global_state::with_internal_keys;
+use crate::high_level_api::global_state::{check_tags, with_internal_keys};
 use crate::high_level_api::keys::InternalServerKey;
 use crate::high_level_api::strings::ascii::FheAsciiString;
 use crate::high_level_api::strings::traits::FheStringMatching;
@@ -29,6 +29,7 @@ impl FheStringMatching<&Self> for FheAsciiString {
     /// assert!(found.decrypt(&client_key));
     /// ```
     fn contains(&self, other: &Self) -> FheBool {
+        check_tags([&self.tag, &other.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key
@@ -40,6 +41,9 @@ impl FheStringMatching<&Self> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings contains");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings contains");
+            }
         })
     }
 
@@ -66,6 +70,7 @@ impl FheStringMatching<&Self> for FheAsciiString {
     /// assert!(!found.decrypt(&client_key));
     /// ```
     fn starts_with(&self, other: &Self) -> FheBool {
+        check_tags([&self.tag, &other.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key
@@ -77,6 +82,9 @@ impl FheStringMatching<&Self> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings starts_with");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings starts_with");
+            }
         })
     }
 
@@ -103,6 +111,7 @@ impl FheStringMatching<&Self> for FheAsciiString {
     /// assert!(found.decrypt(&client_key));
     /// ```
     fn ends_with(&self, other: &Self) -> FheBool {
+        check_tags([&self.tag, &other.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key
@@ -114,12 +123,16 @@ impl FheStringMatching<&Self> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings ends_with");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings ends_with");
+            }
         })
     }
 }
 
 impl FheStringMatching<&ClearString> for FheAsciiString {
     fn contains(&self, other: &ClearString) -> FheBool {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key
@@ -131,10 +144,14 @@ impl FheStringMatching<&ClearString> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings contains");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings contains");
+            }
         })
     }
 
     fn starts_with(&self, other: &ClearString) -> FheBool {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key
@@ -146,10 +163,14 @@ impl FheStringMatching<&ClearString> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings starts_with");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings starts_with");
+            }
         })
     }
 
     fn ends_with(&self, other: &ClearString) -> FheBool {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key
@@ -161,6 +182,9 @@ impl FheStringMatching<&ClearString> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings ends_with");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings ends_with");
+            }
         })
     }
 }
@@ -1,4 +1,4 @@
-use crate::high_level_api::global_state::with_internal_keys;
+use crate::high_level_api::global_state::{check_tags, with_internal_keys};
 use crate::high_level_api::keys::InternalServerKey;
 use crate::high_level_api::strings::ascii::FheAsciiString;
 use crate::high_level_api::strings::traits::FheStringFind;
@@ -32,6 +32,7 @@ impl FheStringFind<&Self> for FheAsciiString {
     /// assert_eq!(pos, 1);
     /// ```
     fn find(&self, pat: &Self) -> (FheUint32, FheBool) {
+        check_tags([&self.tag, &pat.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let (inner, block) = cpu_key
@@ -46,6 +47,9 @@ impl FheStringFind<&Self> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings find");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings find");
+            }
         })
     }
 
@@ -75,6 +79,7 @@ impl FheStringFind<&Self> for FheAsciiString {
     /// assert_eq!(pos, 11);
     /// ```
     fn rfind(&self, pat: &Self) -> (FheUint32, FheBool) {
+        check_tags([&self.tag, &pat.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let (inner, block) = cpu_key
@@ -89,6 +94,9 @@ impl FheStringFind<&Self> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings rfind");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings rfind");
+            }
         })
     }
 }
@@ -120,6 +128,7 @@ impl FheStringFind<&ClearString> for FheAsciiString {
     /// assert_eq!(pos, 1);
     /// ```
     fn find(&self, pat: &ClearString) -> (FheUint32, FheBool) {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let (inner, block) = cpu_key.string_key().find(&self.inner.on_cpu(), pat.into());
@@ -132,6 +141,9 @@ impl FheStringFind<&ClearString> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings find");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings find");
+            }
         })
     }
 
@@ -161,6 +173,7 @@ impl FheStringFind<&ClearString> for FheAsciiString {
     /// assert_eq!(pos, 11);
     /// ```
     fn rfind(&self, pat: &ClearString) -> (FheUint32, FheBool) {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let (inner, block) = cpu_key.string_key().rfind(&self.inner.on_cpu(), pat.into());
@@ -173,6 +186,9 @@ impl FheStringFind<&ClearString> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings rfind");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings rfind");
+            }
         })
     }
 }
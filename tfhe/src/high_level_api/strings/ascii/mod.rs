@@ -7,7 +7,7 @@ mod strip;
 mod trim;
 
 pub use crate::high_level_api::backward_compatibility::strings::FheAsciiStringVersions;
-use crate::high_level_api::details::MaybeCloned;
+use crate::high_level_api::details::{cpu_data_versioned_schema, MaybeCloned};
 use crate::high_level_api::errors::UninitializedServerKey;
 use crate::high_level_api::global_state;
 use crate::high_level_api::keys::InternalServerKey;
@@ -19,6 +19,7 @@ use crate::strings::ciphertext::FheString;
 use crate::{ClientKey, HlExpandable, Tag};
 pub use no_pattern::{FheStringIsEmpty, FheStringLen};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tfhe_versionable::schema::{DataSchema, SchemaRegistry};
 use tfhe_versionable::{Unversionize, UnversionizeError, Versionize, VersionizeOwned};
 
 pub enum EncryptableString<'a> {
@@ -110,6 +111,12 @@ impl VersionizeOwned for AsciiDevice {
             cpu_data.into_owned().versionize_owned(),
         ))
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        cpu_data_versioned_schema::<Self, Self::VersionedOwned, AsciiDeviceVersionOwned, FheString>(
+            registry,
+        )
+    }
 }
 
 impl Unversionize for AsciiDevice {
@@ -377,6 +384,9 @@ impl<'a> FheTryTrivialEncrypt<EncryptableString<'a>> for FheAsciiString {
             }
             #[cfg(feature = "gpu")]
             Some(InternalServerKey::Cuda(_)) => Err(crate::error!("CUDA does not support string")),
+            Some(InternalServerKey::Simulation(_)) => Err(crate::error!(
+                "The simulation backend does not support string"
+            )),
             None => Err(UninitializedServerKey.into()),
         })
     }
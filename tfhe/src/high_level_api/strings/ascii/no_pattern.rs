@@ -1,4 +1,4 @@
-use crate::high_level_api::global_state::with_internal_keys;
+use crate::high_level_api::global_state::{check_tags, with_internal_keys};
 use crate::high_level_api::integers::FheUint16;
 use crate::high_level_api::keys::InternalServerKey;
 use crate::high_level_api::strings::ascii::FheAsciiString;
@@ -93,6 +93,7 @@ impl FheAsciiString {
     /// }
     /// ```
     pub fn len(&self) -> FheStringLen {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let mut len = cpu_key.string_key().len(&self.inner.on_cpu()).into();
@@ -105,6 +106,9 @@ impl FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings len");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings len");
+            }
         })
     }
 
@@ -138,6 +142,7 @@ impl FheAsciiString {
     /// }
     /// ```
     pub fn is_empty(&self) -> FheStringIsEmpty {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let mut result = cpu_key.string_key().is_empty(&self.inner.on_cpu()).into();
@@ -150,6 +155,9 @@ impl FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings len");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings len");
+            }
         })
     }
 
@@ -171,6 +179,7 @@ impl FheAsciiString {
     /// assert_eq!(&dec, "tfhe-rs");
     /// ```
     pub fn to_lowercase(&self) -> Self {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key.string_key().to_lowercase(&self.inner.on_cpu());
@@ -180,6 +189,9 @@ impl FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings to_lowercase");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings to_lowercase");
+            }
         })
     }
 
@@ -201,6 +213,7 @@ impl FheAsciiString {
     /// assert_eq!(&dec, "TFHE-RS");
     /// ```
     pub fn to_uppercase(&self) -> Self {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key.string_key().to_uppercase(&self.inner.on_cpu());
@@ -210,6 +223,9 @@ impl FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings to_uppercase");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings to_uppercase");
+            }
         })
     }
 
@@ -235,6 +251,7 @@ impl FheAsciiString {
     /// assert_eq!(&dec, "tfhe-rs");
     /// ```
     pub fn concat(&self, other: &Self) -> Self {
+        check_tags([&self.tag, &other.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key
@@ -246,6 +263,9 @@ impl FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings concatenating");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings concatenating");
+            }
         })
     }
 }
@@ -293,6 +313,7 @@ impl FheStringRepeat<u16> for FheAsciiString {
     /// assert_eq!(&dec, "tfhe tfhe tfhe ");
     /// ```
     fn repeat(&self, count: u16) -> Self {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key
@@ -304,6 +325,9 @@ impl FheStringRepeat<u16> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings repeat");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings repeat");
+            }
         })
     }
 }
@@ -339,6 +363,7 @@ impl FheStringRepeat<(FheUint16, u16)> for FheAsciiString {
     /// assert_eq!(&dec, &expected);
     /// ```
     fn repeat(&self, (count, bound): (FheUint16, u16)) -> Self {
+        check_tags([&self.tag, &count.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key.string_key().repeat(
@@ -351,6 +376,9 @@ impl FheStringRepeat<(FheUint16, u16)> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings repeat");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings repeat");
+            }
         })
     }
 }
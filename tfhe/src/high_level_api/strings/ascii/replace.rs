@@ -1,4 +1,4 @@
-use crate::high_level_api::global_state::with_internal_keys;
+use crate::high_level_api::global_state::{check_tags, with_internal_keys};
 use crate::high_level_api::keys::InternalServerKey;
 use crate::high_level_api::strings::ascii::FheAsciiString;
 use crate::high_level_api::strings::traits::FheStringReplace;
@@ -32,6 +32,7 @@ impl FheStringReplace<&Self> for FheAsciiString {
     /// assert_eq!(&dec, "tcookie is an cookie scheme");
     /// ```
     fn replace(&self, from: &Self, to: &Self) -> Self {
+        check_tags([&self.tag, &from.tag, &to.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key.string_key().replace(
@@ -45,6 +46,9 @@ impl FheStringReplace<&Self> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings replace");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings replace");
+            }
         })
     }
 }
@@ -74,6 +78,7 @@ impl FheStringReplace<&ClearString> for FheAsciiString {
     /// assert_eq!(&dec, "tcookie is an cookie scheme");
     /// ```
     fn replace(&self, from: &ClearString, to: &Self) -> Self {
+        check_tags([&self.tag, &to.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key.string_key().replace(
@@ -87,6 +92,9 @@ impl FheStringReplace<&ClearString> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings replace");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings replace");
+            }
         })
     }
 }
@@ -111,6 +119,7 @@ impl FheStringReplaceN<&Self, u32> for FheAsciiString {
 
 impl FheStringReplaceN<&Self, u16> for FheAsciiString {
     fn replacen(&self, from: &Self, to: &Self, count: u16) -> Self {
+        check_tags([&self.tag, &from.tag, &to.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key.string_key().replacen(
@@ -125,12 +134,16 @@ impl FheStringReplaceN<&Self, u16> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings replacen");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings replacen");
+            }
         })
     }
 }
 
 impl FheStringReplaceN<&Self, (FheUint16, u16)> for FheAsciiString {
     fn replacen(&self, from: &Self, to: &Self, (count, max): (FheUint16, u16)) -> Self {
+        check_tags([&self.tag, &from.tag, &to.tag, &count.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key.string_key().replacen(
@@ -145,6 +158,9 @@ impl FheStringReplaceN<&Self, (FheUint16, u16)> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings replacen");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings replacen");
+            }
         })
     }
 }
@@ -169,6 +185,7 @@ impl FheStringReplaceN<&ClearString, u32> for FheAsciiString {
 
 impl FheStringReplaceN<&ClearString, u16> for FheAsciiString {
     fn replacen(&self, from: &ClearString, to: &Self, count: u16) -> Self {
+        check_tags([&self.tag, &to.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key.string_key().replacen(
@@ -183,12 +200,16 @@ impl FheStringReplaceN<&ClearString, u16> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings replacen");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings replacen");
+            }
         })
     }
 }
 
 impl FheStringReplaceN<&ClearString, (FheUint16, u16)> for FheAsciiString {
     fn replacen(&self, from: &ClearString, to: &Self, (count, max): (FheUint16, u16)) -> Self {
+        check_tags([&self.tag, &to.tag, &count.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key.string_key().replacen(
@@ -203,6 +224,9 @@ impl FheStringReplaceN<&ClearString, (FheUint16, u16)> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strings replacen");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strings replacen");
+            }
         })
     }
 }
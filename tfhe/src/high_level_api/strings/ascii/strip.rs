@@ -1,4 +1,4 @@
-use crate::high_level_api::global_state::with_internal_keys;
+use crate::high_level_api::global_state::{check_tags, with_internal_keys};
 use crate::high_level_api::keys::InternalServerKey;
 use crate::high_level_api::strings::ascii::FheAsciiString;
 use crate::high_level_api::strings::traits::FheStringStrip;
@@ -35,6 +35,7 @@ impl FheStringStrip<&Self> for FheAsciiString {
     /// assert_eq!(&dec, "-rs");
     /// ```
     fn strip_prefix<'a>(&self, pat: &Self) -> (Self, FheBool) {
+        check_tags([&self.tag, &pat.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let (inner, block) = cpu_key
@@ -49,6 +50,9 @@ impl FheStringStrip<&Self> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strip_prefix");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strip_prefix");
+            }
         })
     }
 
@@ -81,6 +85,7 @@ impl FheStringStrip<&Self> for FheAsciiString {
     /// assert_eq!(&dec, "tfhe-rs");
     /// ```
     fn strip_suffix<'a>(&self, pat: &Self) -> (Self, FheBool) {
+        check_tags([&self.tag, &pat.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let (inner, block) = cpu_key
@@ -95,6 +100,9 @@ impl FheStringStrip<&Self> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strip_suffix");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strip_suffix");
+            }
         })
     }
 }
@@ -129,6 +137,7 @@ impl FheStringStrip<&ClearString> for FheAsciiString {
     /// assert_eq!(&dec, "-rs");
     /// ```
     fn strip_prefix<'a>(&self, pat: &ClearString) -> (Self, FheBool) {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let (inner, block) = cpu_key
@@ -143,6 +152,9 @@ impl FheStringStrip<&ClearString> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strip_prefix");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strip_prefix");
+            }
         })
     }
 
@@ -175,6 +187,7 @@ impl FheStringStrip<&ClearString> for FheAsciiString {
     /// assert_eq!(&dec, "tfhe-rs");
     /// ```
     fn strip_suffix<'a>(&self, pat: &ClearString) -> (Self, FheBool) {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let (inner, block) = cpu_key
@@ -189,6 +202,9 @@ impl FheStringStrip<&ClearString> for FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support strip_suffix");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support strip_suffix");
+            }
         })
     }
 }
@@ -1,4 +1,4 @@
-use crate::high_level_api::global_state::with_internal_keys;
+use crate::high_level_api::global_state::{check_tags, with_internal_keys};
 use crate::high_level_api::keys::InternalServerKey;
 use crate::high_level_api::strings::ascii::FheAsciiString;
 
@@ -23,6 +23,7 @@ impl FheAsciiString {
     /// assert_eq!(&dec, "tfhe-rs   ");
     /// ```
     pub fn trim_start(&self) -> Self {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key.string_key().trim_start(&self.inner.on_cpu());
@@ -32,6 +33,9 @@ impl FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support trim_start");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support trim_start");
+            }
         })
     }
 
@@ -55,6 +59,7 @@ impl FheAsciiString {
     /// assert_eq!(&dec, "   tfhe-rs");
     /// ```
     pub fn trim_end(&self) -> Self {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key.string_key().trim_end(&self.inner.on_cpu());
@@ -64,6 +69,9 @@ impl FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support trim_end");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support trim_end");
+            }
         })
     }
 
@@ -87,6 +95,7 @@ impl FheAsciiString {
     /// assert_eq!(&dec, "tfhe-rs");
     /// ```
     pub fn trim(&self) -> Self {
+        check_tags([&self.tag]);
         with_internal_keys(|keys| match keys {
             InternalServerKey::Cpu(cpu_key) => {
                 let inner = cpu_key.string_key().trim(&self.inner.on_cpu());
@@ -96,6 +105,9 @@ impl FheAsciiString {
             InternalServerKey::Cuda(_) => {
                 panic!("gpu does not support trim");
             }
+            InternalServerKey::Simulation(_) => {
+                panic!("the simulation backend does not support trim");
+            }
         })
     }
 }
@@ -1,4 +1,5 @@
 use crate::high_level_api::backward_compatibility::tag::TagVersions;
+use tfhe_versionable::schema::{DataSchema, SchemaRegistry};
 use tfhe_versionable::{Unversionize, UnversionizeError, Versionize, VersionizeOwned};
 
 const STACK_ARRAY_SIZE: usize = std::mem::size_of::<Vec<u8>>() - 1;
@@ -247,6 +248,10 @@ impl VersionizeOwned for SmallVec {
             Self::Heap(vec) => vec,
         }
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        Self::VersionedOwned::versioned_schema(registry)
+    }
 }
 
 impl Unversionize for SmallVec {
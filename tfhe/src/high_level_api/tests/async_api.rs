@@ -0,0 +1,94 @@
+use crate::prelude::*;
+use crate::{
+    unset_server_key, AsyncServerKey, ClientKey, ConfigBuilder, FheBool, FheUint16, ServerKey,
+    SimulationServerKey,
+};
+use futures::executor::block_on;
+use rand::random;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+
+#[test]
+fn test_async_integer_and_boolean_ops() {
+    let config = ConfigBuilder::default().build();
+    let cks = ClientKey::generate(config);
+    let async_key = AsyncServerKey::with_num_threads(ServerKey::new(&cks), 2).unwrap();
+    assert_eq!(async_key.num_threads(), 2);
+
+    // The calling thread does not need a key
+    unset_server_key();
+
+    let clear_a = random::<u16>();
+    let clear_b = random::<u16>();
+    let a = FheUint16::encrypt(clear_a, &cks);
+    let b = FheUint16::encrypt(clear_b, &cks);
+
+    let (sum, is_lower, max) = block_on(async {
+        // Start all operations before awaiting any of them
+        let sum = async_key.add(&a, &b);
+        let is_lower = async_key.lt(&a, &b);
+        let max = async_key.max(&a, &b);
+        (sum.await, is_lower.await, max.await)
+    });
+
+    let decrypted: u16 = sum.decrypt(&cks);
+    assert_eq!(decrypted, clear_a.wrapping_add(clear_b));
+    assert_eq!(is_lower.decrypt(&cks), clear_a < clear_b);
+    let decrypted: u16 = max.decrypt(&cks);
+    assert_eq!(decrypted, clear_a.max(clear_b));
+
+    let selected = block_on(async_key.if_then_else(&is_lower, &a, &b));
+    let decrypted: u16 = selected.decrypt(&cks);
+    assert_eq!(decrypted, if clear_a < clear_b { clear_a } else { clear_b });
+
+    let t = FheBool::encrypt(true, &cks);
+    let f = FheBool::encrypt(false, &cks);
+    let result = block_on(async {
+        let xor = async_key.bitxor(&t, &f).await;
+        async_key.not(&xor).await
+    });
+    assert!(!result.decrypt(&cks));
+}
+
+#[test]
+fn test_async_cancellation() {
+    let config = ConfigBuilder::default().build();
+    let cks = ClientKey::generate(config);
+    let async_key = AsyncServerKey::with_num_threads(SimulationServerKey::new(&cks), 1).unwrap();
+
+    // Block the only thread of the pool
+    let (sender, receiver) = mpsc::channel::<()>();
+    let blocking = async_key.spawn(move || receiver.recv().unwrap());
+
+    let ran = Arc::new(AtomicBool::new(false));
+    let cancelled = {
+        let ran = Arc::clone(&ran);
+        async_key.spawn(move || ran.store(true, Ordering::SeqCst))
+    };
+    assert!(!cancelled.is_finished());
+    drop(cancelled);
+
+    sender.send(()).unwrap();
+    block_on(blocking);
+
+    // Jobs run in order on a single thread, so the cancelled one would have run by now
+    let a = FheUint16::encrypt(3u16, &cks);
+    let product = block_on(async_key.mul(&a, &a));
+    let decrypted: u16 = product.decrypt(&cks);
+    assert_eq!(decrypted, 9);
+    assert!(!ran.load(Ordering::SeqCst));
+}
+
+#[test]
+fn test_async_panic_is_propagated() {
+    let config = ConfigBuilder::default().build();
+    let cks = ClientKey::generate(config);
+    let async_key = AsyncServerKey::with_num_threads(SimulationServerKey::new(&cks), 1).unwrap();
+
+    let future = async_key.spawn(|| -> u32 { panic!("expected panic") });
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| block_on(future)));
+    assert!(result.is_err());
+
+    // The pool is still usable
+    assert_eq!(block_on(async_key.spawn(|| 42u32)), 42);
+}
@@ -0,0 +1,412 @@
+use crate::circuit::{BinaryGate, Circuit, Gate};
+use crate::prelude::*;
+use crate::shortint::parameters::PARAM_MESSAGE_2_CARRY_2_KS_PBS;
+use crate::{generate_keys, set_server_key, ConfigBuilder, FheBool};
+use rand::{random, thread_rng, Rng};
+
+/// Bristol Fashion ripple carry adder of two `num_bits` values, outputs the sum and the carry
+fn adder_circuit(num_bits: usize) -> String {
+    let mut gates = Vec::new();
+    let mut next_wire = 2 * num_bits;
+    let mut new_wire = || {
+        next_wire += 1;
+        next_wire - 1
+    };
+
+    let mut carry = new_wire();
+    gates.push(format!("1 1 0 {carry} EQ"));
+    let mut sums = Vec::new();
+    for i in 0..num_bits {
+        let (a, b) = (i, num_bits + i);
+        let [t, not_t, not_sum, sum, generate, propagate, carry_out] =
+            std::array::from_fn(|_| new_wire());
+        gates.push(format!("2 1 {a} {b} {t} XOR"));
+        gates.push(format!("1 1 {t} {not_t} INV"));
+        gates.push(format!("2 1 {not_t} {carry} {not_sum} XOR"));
+        gates.push(format!("1 1 {not_sum} {sum} INV"));
+        gates.push(format!(
+            "4 2 {a} {t} {b} {carry} {generate} {propagate} MAND"
+        ));
+        gates.push(format!("2 1 {generate} {propagate} {carry_out} XOR"));
+        sums.push(sum);
+        carry = carry_out;
+    }
+    for wire in sums.into_iter().chain([carry]) {
+        gates.push(format!("1 1 {wire} {} EQW", new_wire()));
+    }
+
+    format!(
+        "{} {}\n2 {num_bits} {num_bits}\n2 {num_bits} 1\n\n{}\n",
+        gates.len(),
+        next_wire,
+        gates.join("\n")
+    )
+}
+
+fn to_bits(value: u64, num_bits: usize) -> Vec<bool> {
+    (0..num_bits).map(|i| (value >> i) & 1 == 1).collect()
+}
+
+fn from_bits(bits: &[bool]) -> u64 {
+    bits.iter()
+        .enumerate()
+        .map(|(i, &bit)| u64::from(bit) << i)
+        .sum()
+}
+
+/// Random circuit with a single input of `num_inputs` bits and `num_outputs` output bits
+fn random_circuit(num_inputs: usize, num_gates: usize, num_outputs: usize) -> Circuit {
+    let mut rng = thread_rng();
+    let ops = [
+        BinaryGate::And,
+        BinaryGate::Or,
+        BinaryGate::Xor,
+        BinaryGate::Nand,
+        BinaryGate::Nor,
+        BinaryGate::Xnor,
+    ];
+
+    let gates = (num_inputs..num_inputs + num_gates)
+        .map(|output| match rng.gen_range(0..8) {
+            0 => Gate::Inv {
+                input: rng.gen_range(0..output),
+                output,
+            },
+            1 => Gate::Constant {
+                value: rng.gen(),
+                output,
+            },
+            _ => Gate::Binary {
+                op: ops[rng.gen_range(0..ops.len())],
+                lhs: rng.gen_range(0..output),
+                rhs: rng.gen_range(0..output),
+                output,
+            },
+        })
+        .collect();
+
+    Circuit::new(
+        num_inputs + num_gates,
+        vec![num_inputs],
+        vec![num_outputs],
+        gates,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_circuit_parsing() {
+    let circuit = Circuit::from_bristol_fashion(&adder_circuit(8)).unwrap();
+    assert_eq!(circuit.input_sizes(), &[8, 8]);
+    assert_eq!(circuit.output_sizes(), &[8, 1]);
+    // Each MAND is split in 2 AND gates
+    assert_eq!(circuit.gates().len(), 1 + 8 * 7 + 9);
+    assert!(circuit.depth() > 8);
+
+    // Old Bristol format, (a0 ^ b0, !(a1 & b1))
+    let circuit = Circuit::from_bristol(
+        "3 7
+        2 2 2
+
+        2 1 0 2 5 XOR
+        2 1 1 3 4 AND
+        1 1 4 6 INV",
+    )
+    .unwrap();
+    assert_eq!(circuit.input_sizes(), &[2, 2]);
+    for (a, b) in [(0, 0), (1, 2), (3, 3), (2, 1)] {
+        let outputs = circuit
+            .evaluate_clear(&[to_bits(a, 2), to_bits(b, 2)])
+            .unwrap();
+        let expected = vec![(a ^ b) & 1 == 1, (a & b) & 2 == 0];
+        assert_eq!(outputs, vec![expected]);
+    }
+
+    let invalid_netlists = [
+        // Wrong number of gates
+        "2 3\n1 1\n1 1\n\n1 1 0 1 INV",
+        // Wire read before being assigned
+        "1 3\n1 1\n1 1\n\n2 1 0 1 2 AND",
+        // Wire assigned twice
+        "2 3\n1 1\n1 1\n\n1 1 0 2 INV\n1 1 0 2 INV",
+        // More wires than the inputs and gates can assign
+        "1 18446744073709551615\n1 1\n1 1\n\n1 1 0 1 INV",
+        // Wire out of bounds
+        "1 3\n1 1\n1 1\n\n1 1 0 3 INV",
+        // Unknown gate
+        "1 3\n2 1 1\n1 1\n\n2 1 0 1 2 ADD",
+        // Wrong number of wires for the gate
+        "1 3\n2 1 1\n1 1\n\n2 1 0 1 2 INV",
+        // Invalid constant
+        "1 2\n1 1\n1 1\n\n1 1 2 1 EQ",
+        // Invalid header
+        "1 3\n2 1\n1 1\n\n2 1 0 1 2 XOR",
+    ];
+    for netlist in invalid_netlists {
+        assert!(
+            Circuit::from_bristol_fashion(netlist).is_err(),
+            "{netlist} should not be accepted"
+        );
+    }
+
+    let circuit = Circuit::from_bristol_fashion(&adder_circuit(4)).unwrap();
+    assert!(circuit.evaluate_clear(&[to_bits(1, 4)]).is_err());
+    assert!(circuit
+        .evaluate_clear(&[to_bits(1, 4), to_bits(1, 3)])
+        .is_err());
+}
+
+#[test]
+fn test_circuit_clear_evaluation() {
+    let num_bits = 16;
+    let circuit = Circuit::from_bristol_fashion(&adder_circuit(num_bits)).unwrap();
+
+    for _ in 0..10 {
+        let (a, b) = (random::<u16>(), random::<u16>());
+        let outputs = circuit
+            .evaluate_clear(&[
+                to_bits(u64::from(a), num_bits),
+                to_bits(u64::from(b), num_bits),
+            ])
+            .unwrap();
+        let (sum, carry) = a.overflowing_add(b);
+        assert_eq!(from_bits(&outputs[0]), u64::from(sum));
+        assert_eq!(outputs[1], vec![carry]);
+    }
+}
+
+#[test]
+fn test_circuit_fhe_bool_evaluation() {
+    let num_bits = 4;
+    let circuit = Circuit::from_bristol_fashion(&adder_circuit(num_bits)).unwrap();
+
+    let config = ConfigBuilder::default().build();
+    let (cks, sks) = generate_keys(config);
+    set_server_key(sks);
+
+    let (a, b) = (random::<u64>() % 16, random::<u64>() % 16);
+    let inputs = [to_bits(a, num_bits), to_bits(b, num_bits)]
+        .iter()
+        .map(|bits| {
+            bits.iter()
+                .map(|&bit| FheBool::encrypt(bit, &cks))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let outputs = circuit.evaluate(&inputs).unwrap();
+    let decrypted = outputs
+        .iter()
+        .map(|bits| bits.iter().map(|bit| bit.decrypt(&cks)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(from_bits(&decrypted[0]), (a + b) % 16);
+    assert_eq!(decrypted[1], vec![a + b >= 16]);
+}
+
+#[test]
+fn test_circuit_fused_evaluation() {
+    let (cks, sks) = crate::shortint::gen_keys(PARAM_MESSAGE_2_CARRY_2_KS_PBS);
+
+    let num_bits = 4;
+    let circuit = Circuit::from_bristol_fashion(&adder_circuit(num_bits)).unwrap();
+    let fused = circuit.fuse(&sks).unwrap();
+    let num_binary_gates = circuit
+        .gates()
+        .iter()
+        .filter(|gate| matches!(gate, Gate::Binary { .. }))
+        .count();
+    assert!(fused.num_lookup_tables() < num_binary_gates);
+    assert!(fused.depth() <= circuit.depth());
+
+    let (a, b) = (random::<u64>() % 16, random::<u64>() % 16);
+    let inputs = [to_bits(a, num_bits), to_bits(b, num_bits)]
+        .iter()
+        .map(|bits| {
+            bits.iter()
+                .map(|&bit| cks.encrypt(u64::from(bit)))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let outputs = fused.evaluate(&sks, &inputs).unwrap();
+    let decrypted = outputs
+        .iter()
+        .map(|bits| {
+            bits.iter()
+                .map(|bit| cks.decrypt(bit) == 1)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(from_bits(&decrypted[0]), (a + b) % 16);
+    assert_eq!(decrypted[1], vec![a + b >= 16]);
+
+    for _ in 0..3 {
+        let circuit = random_circuit(6, 30, 8);
+        let fused = circuit.fuse(&sks).unwrap();
+        assert!(fused.num_lookup_tables() <= circuit.gates().len());
+
+        let clear_inputs = vec![to_bits(random::<u64>() % 64, 6)];
+        let inputs = vec![clear_inputs[0]
+            .iter()
+            .map(|&bit| cks.encrypt(u64::from(bit)))
+            .collect::<Vec<_>>()];
+
+        let expected = circuit.evaluate_clear(&clear_inputs).unwrap();
+        let outputs = fused.evaluate(&sks, &inputs).unwrap();
+        let decrypted = outputs[0]
+            .iter()
+            .map(|bit| cks.decrypt(bit) == 1)
+            .collect::<Vec<_>>();
+        assert_eq!(decrypted, expected[0]);
+    }
+}
+
+/// Bits of `bytes` in the order of the published circuits: the bytes form a big endian
+/// integer, and the first wire holds its least significant bit
+fn bytes_to_bits(bytes: &[u8]) -> Vec<bool> {
+    bytes
+        .iter()
+        .rev()
+        .flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1 == 1))
+        .collect()
+}
+
+fn bits_to_bytes(bits: &[bool]) -> Vec<u8> {
+    let mut bytes = bits
+        .chunks(8)
+        .map(|bits| {
+            bits.iter()
+                .rev()
+                .fold(0, |byte, &bit| (byte << 1) | u8::from(bit))
+        })
+        .collect::<Vec<_>>();
+    bytes.reverse();
+    bytes
+}
+
+fn hex_to_bytes(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// Reads one of the circuits published at <https://nigelsmart.github.io/MPC-Circuits/>, which
+/// must be downloaded in the directory given by the `TFHE_BRISTOL_CIRCUITS_DIR` environment
+/// variable
+fn published_circuit(file_name: &str) -> Circuit {
+    let directory = std::env::var("TFHE_BRISTOL_CIRCUITS_DIR")
+        .expect("TFHE_BRISTOL_CIRCUITS_DIR must be set to run this test");
+    let netlist =
+        std::fs::read_to_string(std::path::Path::new(&directory).join(file_name)).unwrap();
+    Circuit::from_bristol_fashion(&netlist).unwrap()
+}
+
+/// Evaluates `circuit` on `inputs` in clear, and checks the result is `expected`
+fn clear_known_answer_test_case(circuit: &Circuit, inputs: &[Vec<u8>], expected: &[u8]) {
+    let clear_inputs = inputs
+        .iter()
+        .map(|bytes| bytes_to_bits(bytes))
+        .collect::<Vec<_>>();
+
+    let outputs = circuit.evaluate_clear(&clear_inputs).unwrap();
+    assert_eq!(bits_to_bytes(&outputs[0]), expected);
+}
+
+/// Evaluates `circuit` on `inputs`, with [FheBool]s and with fused lookup tables, and checks
+/// that both give the `expected` output
+fn encrypted_known_answer_test_case(circuit: &Circuit, inputs: &[Vec<u8>], expected: &[u8]) {
+    let clear_inputs = inputs
+        .iter()
+        .map(|bytes| bytes_to_bits(bytes))
+        .collect::<Vec<_>>();
+
+    let config = ConfigBuilder::default().build();
+    let (cks, sks) = generate_keys(config);
+    set_server_key(sks);
+    let inputs = clear_inputs
+        .iter()
+        .map(|bits| {
+            bits.iter()
+                .map(|&bit| FheBool::encrypt(bit, &cks))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let outputs = circuit.evaluate(&inputs).unwrap();
+    let decrypted = outputs[0]
+        .iter()
+        .map(|bit| bit.decrypt(&cks))
+        .collect::<Vec<_>>();
+    assert_eq!(bits_to_bytes(&decrypted), expected);
+
+    let (cks, sks) = crate::shortint::gen_keys(PARAM_MESSAGE_2_CARRY_2_KS_PBS);
+    let fused = circuit.fuse(&sks).unwrap();
+    let inputs = clear_inputs
+        .iter()
+        .map(|bits| {
+            bits.iter()
+                .map(|&bit| cks.encrypt(u64::from(bit)))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let outputs = fused.evaluate(&sks, &inputs).unwrap();
+    let decrypted = outputs[0]
+        .iter()
+        .map(|bit| cks.decrypt(bit) == 1)
+        .collect::<Vec<_>>();
+    assert_eq!(bits_to_bytes(&decrypted), expected);
+}
+
+/// Key, plaintext and ciphertext of the AES-128 test vector of NIST SP 800-38A, F.1.1
+fn aes_128_test_vector() -> (Vec<Vec<u8>>, Vec<u8>) {
+    (
+        vec![
+            hex_to_bytes("2b7e151628aed2a6abf7158809cf4f3c"),
+            hex_to_bytes("6bc1bee22e409f96e93d7e117393172a"),
+        ],
+        hex_to_bytes("3ad77bb40d7a3660a89ecaf32466ef97"),
+    )
+}
+
+/// Block, chaining state and output of the SHA-256 compression of the padded message "abc",
+/// from the initial hash value
+fn sha_256_test_vector() -> (Vec<Vec<u8>>, Vec<u8>) {
+    let mut block = b"abc".to_vec();
+    block.push(0x80);
+    block.resize(56, 0);
+    block.extend_from_slice(&(8 * 3u64).to_be_bytes());
+    let initial_hash_value =
+        hex_to_bytes("6a09e667bb67ae853c6ef372a54ff53a510e527f9b05688c1f83d9ab5be0cd19");
+
+    (
+        vec![block, initial_hash_value],
+        hex_to_bytes("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+    )
+}
+
+#[test]
+#[ignore = "requires the published Bristol Fashion circuits, see TFHE_BRISTOL_CIRCUITS_DIR"]
+fn test_circuit_published_aes_128_clear() {
+    let (inputs, expected) = aes_128_test_vector();
+    clear_known_answer_test_case(&published_circuit("aes_128.txt"), &inputs, &expected);
+}
+
+#[test]
+#[ignore = "requires the published Bristol Fashion circuits, see TFHE_BRISTOL_CIRCUITS_DIR"]
+fn test_circuit_published_aes_128() {
+    let (inputs, expected) = aes_128_test_vector();
+    encrypted_known_answer_test_case(&published_circuit("aes_128.txt"), &inputs, &expected);
+}
+
+#[test]
+#[ignore = "requires the published Bristol Fashion circuits, see TFHE_BRISTOL_CIRCUITS_DIR"]
+fn test_circuit_published_sha_256_clear() {
+    let (inputs, expected) = sha_256_test_vector();
+    clear_known_answer_test_case(&published_circuit("sha256.txt"), &inputs, &expected);
+}
+
+#[test]
+#[ignore = "requires the published Bristol Fashion circuits, see TFHE_BRISTOL_CIRCUITS_DIR"]
+fn test_circuit_published_sha_256() {
+    let (inputs, expected) = sha_256_test_vector();
+    encrypted_known_answer_test_case(&published_circuit("sha256.txt"), &inputs, &expected);
+}
@@ -0,0 +1,97 @@
+use crate::prelude::*;
+use crate::{
+    set_server_key, unset_server_key, ClientKey, ConfigBuilder, CpuFheUint8Array, Device, FheBool,
+    FheContext, FheUint8, ServerKey, SimulationServerKey,
+};
+use rand::random;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[test]
+fn test_context_without_thread_local_key() {
+    let config = ConfigBuilder::default().build();
+    let cks = ClientKey::generate(config);
+    let context = FheContext::new(ServerKey::new(&cks));
+
+    unset_server_key();
+
+    let clear_a = random::<u8>();
+    let clear_b = random::<u8>();
+    let a = FheUint8::encrypt(clear_a, &cks);
+    let b = FheUint8::encrypt(clear_b, &cks);
+
+    let decrypted: u8 = context.add(&a, &b).decrypt(&cks);
+    assert_eq!(decrypted, clear_a.wrapping_add(clear_b));
+
+    let decrypted: u8 = context.mul(&a, 3u8).decrypt(&cks);
+    assert_eq!(decrypted, clear_a.wrapping_mul(3));
+
+    let decrypted = context.ge(&a, &b).decrypt(&cks);
+    assert_eq!(decrypted, clear_a >= clear_b);
+
+    let decrypted: u8 = context.min(&a, &b).decrypt(&cks);
+    assert_eq!(decrypted, clear_a.min(clear_b));
+
+    let (result, overflowed) = context.overflowing_add(&a, &b);
+    let decrypted: u8 = result.decrypt(&cks);
+    assert_eq!(
+        (decrypted, overflowed.decrypt(&cks)),
+        clear_a.overflowing_add(clear_b)
+    );
+
+    let cond = FheBool::encrypt(clear_a < clear_b, &cks);
+    let decrypted = context.not(&cond).decrypt(&cks);
+    assert_eq!(decrypted, clear_a >= clear_b);
+    let decrypted: u8 = context.if_then_else(&cond, &a, &b).decrypt(&cks);
+    assert_eq!(decrypted, clear_a.min(clear_b));
+
+    let clear_lhs = [clear_a, 1, 2];
+    let clear_rhs = [clear_b, 3, 4];
+    let lhs = CpuFheUint8Array::try_encrypt(clear_lhs.as_slice(), &cks).unwrap();
+    let rhs = CpuFheUint8Array::try_encrypt(clear_rhs.as_slice(), &cks).unwrap();
+    let decrypted: Vec<u8> = context.bitxor(&lhs, &rhs).decrypt(&cks);
+    let expected = clear_lhs
+        .iter()
+        .zip(clear_rhs.iter())
+        .map(|(l, r)| l ^ r)
+        .collect::<Vec<_>>();
+    assert_eq!(decrypted, expected);
+
+    let clears = (0..8).map(|_| random::<u8>()).collect::<Vec<_>>();
+    let encrypted = clears
+        .iter()
+        .map(|&clear| FheUint8::encrypt(clear, &cks))
+        .collect::<Vec<_>>();
+    let results = context.par_map(&encrypted, |value| value + &a);
+    for (result, clear) in results.iter().zip(clears) {
+        let decrypted: u8 = result.decrypt(&cks);
+        assert_eq!(decrypted, clear.wrapping_add(clear_a));
+    }
+
+    // Nothing leaked in the thread local state
+    let result = catch_unwind(AssertUnwindSafe(|| &a + &b));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_context_restores_thread_local_key() {
+    let config = ConfigBuilder::default().build();
+    let cks = ClientKey::generate(config);
+    let context = FheContext::new(SimulationServerKey::new(&cks));
+    set_server_key(ServerKey::new(&cks));
+
+    let a = FheUint8::encrypt(7u8, &cks);
+
+    let simulated = context.run(|| &a + 1u8);
+    assert_eq!(simulated.current_device(), Device::Simulation);
+
+    // A panic in the context does not leave its key behind
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        context.run(|| panic!("expected panic"))
+    }));
+    assert!(result.is_err());
+
+    let computed = &a + 1u8;
+    assert_eq!(computed.current_device(), Device::Cpu);
+    let decrypted: u8 = computed.decrypt(&cks);
+    assert_eq!(decrypted, 8);
+}
@@ -0,0 +1,136 @@
+use crate::prelude::*;
+use crate::shortint::parameters::test_params::TEST_PARAM_MULTI_BIT_GROUP_2_MESSAGE_2_CARRY_2_KS_PBS_GAUSSIAN_2M64;
+use crate::{
+    dry_run, set_server_key, ClientKey, ConfigBuilder, FheBool, FheInt16, FheUint32, FheUint8,
+    ServerKey, Stats,
+};
+use rand::random;
+
+fn compute(
+    a: &FheUint32,
+    b: &FheUint32,
+    c: &FheInt16,
+    cond: &FheBool,
+) -> (FheUint32, FheBool, FheInt16) {
+    let sum = a + b;
+    let product = a * 7u32;
+    let is_lower = a.lt(b);
+    let selected = cond.select(&sum, &product);
+    let negated = -c + 3i16;
+    (selected, is_lower & cond, negated.max(c))
+}
+
+#[test]
+fn test_dry_run_counts_match_real_computation() {
+    let config = ConfigBuilder::default().build();
+
+    let cks = ClientKey::generate(config);
+    let sks = ServerKey::new(&cks);
+    let a = FheUint32::encrypt(random::<u32>(), &cks);
+    let b = FheUint32::encrypt(random::<u32>(), &cks);
+    let c = FheInt16::encrypt(random::<i16>(), &cks);
+    let cond = FheBool::encrypt(random::<bool>(), &cks);
+
+    let pool = rayon::ThreadPoolBuilder::new().build().unwrap();
+    let scope = Stats::scope();
+    scope.install(&pool, || {
+        set_server_key(sks);
+        let _ = compute(&a, &b, &c, &cond);
+    });
+    let expected = scope.stats();
+    assert!(expected.pbs > 0);
+
+    let (_, report) = dry_run(config, |run| {
+        let a = run.unsigned();
+        let b = run.unsigned();
+        let c = run.signed();
+        let cond = run.boolean();
+
+        run.measure("compute", || {
+            let _ = compute(&a, &b, &c, &cond);
+        });
+        run.measure("add", || &a + &b);
+        run.measure("add", || &a + &b);
+    });
+
+    let computation = report.operation("compute").unwrap();
+    assert_eq!(computation.count, 1);
+    assert_eq!(computation.stats, expected);
+
+    let add = report.operation("add").unwrap();
+    assert_eq!(add.count, 2);
+    assert_eq!(report.total(), computation.stats + add.stats);
+    assert!(report.classic_pbs_count() > 0);
+    assert!(report.classic_pbs_count() <= report.pbs_count());
+    assert_eq!(report.multi_bit_pbs_count(), 0);
+    assert!(report.pbs_depth() > 0);
+    assert!(report.pbs_depth() < report.pbs_count());
+
+    let pbs_latency = std::time::Duration::from_millis(10);
+    let keyswitch_latency = std::time::Duration::from_millis(1);
+    assert_eq!(
+        report.estimated_latency(pbs_latency, keyswitch_latency),
+        computation.estimated_latency(pbs_latency, keyswitch_latency)
+            + add.estimated_latency(pbs_latency, keyswitch_latency)
+    );
+}
+
+#[test]
+fn test_dry_run_multi_bit() {
+    let config = ConfigBuilder::with_custom_parameters(
+        TEST_PARAM_MULTI_BIT_GROUP_2_MESSAGE_2_CARRY_2_KS_PBS_GAUSSIAN_2M64,
+    )
+    .build();
+
+    let (_, report) = dry_run(config, |run| {
+        let a: FheUint8 = run.unsigned();
+        let b: FheUint8 = run.unsigned();
+        &a * &b
+    });
+
+    assert!(report.pbs_count() > 0);
+    assert!(report.multi_bit_pbs_count() > 0);
+    assert!(report.multi_bit_pbs_count() <= report.pbs_count());
+    assert_eq!(report.classic_pbs_count(), 0);
+    assert!(report.operations().next().is_none());
+}
+
+#[test]
+fn test_dry_run_pbs_depth() {
+    let config = ConfigBuilder::default().build();
+
+    let (_, report) = dry_run(config, |run| {
+        let a: FheUint8 = run.unsigned();
+        let b: FheUint8 = run.unsigned();
+
+        let sum = run.measure("first", || &a + &b);
+        // Does not depend on the first sum, it can run alongside it
+        let _ = run.measure("independent", || &b + &a);
+        run.measure("chained", || &sum + &a)
+    });
+
+    let first = report.operation("first").unwrap().stats;
+    let independent = report.operation("independent").unwrap().stats;
+    let chained = report.operation("chained").unwrap().stats;
+
+    assert!(first.pbs_depth > 0);
+    assert!(first.pbs_depth <= first.pbs);
+    assert_eq!(independent.pbs, first.pbs);
+    assert_eq!(independent.pbs_depth, 0);
+    assert!(chained.pbs_depth > 0);
+    assert_eq!(report.pbs_depth(), first.pbs_depth + chained.pbs_depth);
+
+    let pbs_latency = std::time::Duration::from_millis(10);
+    let keyswitch_latency = std::time::Duration::from_millis(1);
+    assert_eq!(
+        report.estimated_latency(pbs_latency, keyswitch_latency),
+        (pbs_latency + keyswitch_latency) * report.pbs_depth() as u32
+    );
+    assert_eq!(
+        report
+            .operation("independent")
+            .unwrap()
+            .estimated_latency(pbs_latency, keyswitch_latency),
+        std::time::Duration::ZERO
+    );
+}
@@ -0,0 +1,234 @@
+use crate::hashing::{
+    keccak_f1600, sha256, sha256_with_encrypted_len, sha256_words, sha3_256,
+    sha3_256_with_encrypted_len,
+};
+use crate::prelude::*;
+use crate::{
+    set_server_key, ClientKey, ConfigBuilder, FheUint16, FheUint32, FheUint64, FheUint8,
+    SimulationServerKey,
+};
+use rand::{random, thread_rng, Rng};
+use sha3::{Digest, Sha3_256};
+
+/// Clear SHA-256, checked against the FIPS 180-2 examples
+fn clear_sha256(message: &[u8]) -> [u8; 32] {
+    const K: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4,
+        0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe,
+        0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f,
+        0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7,
+        0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc,
+        0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
+        0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116,
+        0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+        0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7,
+        0xc67178f2,
+    ];
+    let mut state: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+
+    let mut padded = message.to_vec();
+    padded.push(0x80);
+    while padded.len() % 64 != 56 {
+        padded.push(0);
+    }
+    padded.extend(((message.len() as u64) * 8).to_be_bytes());
+
+    for block in padded.chunks_exact(64) {
+        let mut w = block
+            .chunks_exact(4)
+            .map(|bytes| u32::from_be_bytes(bytes.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w.push(
+                w[i - 16]
+                    .wrapping_add(s0)
+                    .wrapping_add(w[i - 7])
+                    .wrapping_add(s1),
+            );
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let choice = (e & f) ^ (!e & g);
+            let t1 = h
+                .wrapping_add(s1)
+                .wrapping_add(choice)
+                .wrapping_add(K[i])
+                .wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let majority = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(majority);
+            (h, g, f, e, d, c, b, a) = (g, f, e, d.wrapping_add(t1), c, b, a, t1.wrapping_add(t2));
+        }
+        for (value, working) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *value = value.wrapping_add(working);
+        }
+    }
+
+    let mut digest = [0u8; 32];
+    for (bytes, word) in digest.chunks_exact_mut(4).zip(state) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+fn clear_sha3_256(message: &[u8]) -> [u8; 32] {
+    Sha3_256::digest(message).into()
+}
+
+fn setup_simulation() -> ClientKey {
+    let client_key = ClientKey::generate(ConfigBuilder::default().build());
+    set_server_key(SimulationServerKey::new(&client_key));
+    client_key
+}
+
+fn encrypt_bytes(message: &[u8], cks: &ClientKey) -> Vec<FheUint8> {
+    message
+        .iter()
+        .map(|&byte| FheUint8::encrypt(byte, cks))
+        .collect()
+}
+
+fn decrypt_bytes(digest: &[FheUint8], cks: &ClientKey) -> [u8; 32] {
+    let bytes = digest
+        .iter()
+        .map(|byte| byte.decrypt(cks))
+        .collect::<Vec<u8>>();
+    bytes.try_into().unwrap()
+}
+
+fn random_message(max_len: usize) -> Vec<u8> {
+    let len = thread_rng().gen_range(0..=max_len);
+    (0..len).map(|_| random()).collect()
+}
+
+#[test]
+fn test_clear_references() {
+    let hex = |digest: [u8; 32]| hex::encode(digest);
+
+    assert_eq!(
+        hex(clear_sha256(b"")),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    assert_eq!(
+        hex(clear_sha256(b"abc")),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(
+        hex(clear_sha256(
+            b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+        )),
+        "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+    );
+    assert_eq!(
+        hex(clear_sha3_256(b"")),
+        "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"
+    );
+    assert_eq!(
+        hex(clear_sha3_256(b"abc")),
+        "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+    );
+}
+
+#[test]
+fn test_sha256() {
+    let cks = setup_simulation();
+
+    // Lengths around the block boundaries
+    let lengths = [0, 3, 55, 56, 63, 64, 119, 120, 150];
+    for len in lengths {
+        let message = (0..len).map(|_| random()).collect::<Vec<u8>>();
+        let digest = sha256(&encrypt_bytes(&message, &cks));
+        assert_eq!(
+            decrypt_bytes(&digest, &cks),
+            clear_sha256(&message),
+            "length {len}"
+        );
+    }
+
+    let words = (0..20).map(|_| random()).collect::<Vec<u32>>();
+    let encrypted_words = words
+        .iter()
+        .map(|&word| FheUint32::encrypt(word, &cks))
+        .collect::<Vec<_>>();
+    let digest = sha256_words(&encrypted_words)
+        .iter()
+        .flat_map(|word| {
+            let word: u32 = word.decrypt(&cks);
+            word.to_be_bytes()
+        })
+        .collect::<Vec<_>>();
+    let message = words
+        .iter()
+        .flat_map(|word| word.to_be_bytes())
+        .collect::<Vec<_>>();
+    assert_eq!(digest, clear_sha256(&message));
+}
+
+#[test]
+fn test_sha3_256() {
+    let cks = setup_simulation();
+
+    let lengths = [0, 3, 135, 136, 137, 300];
+    for len in lengths {
+        let message = (0..len).map(|_| random()).collect::<Vec<u8>>();
+        let digest = sha3_256(&encrypt_bytes(&message, &cks));
+        assert_eq!(
+            decrypt_bytes(&digest, &cks),
+            clear_sha3_256(&message),
+            "length {len}"
+        );
+    }
+
+    // Keccak-f[1600] on the zero state, from the Keccak team test vectors
+    let mut state = std::array::from_fn(|_| FheUint64::encrypt(0u64, &cks));
+    keccak_f1600(&mut state);
+    let first_lane: u64 = state[0].decrypt(&cks);
+    assert_eq!(first_lane, 0xf1258f7940e1dde7);
+}
+
+#[test]
+fn test_hashing_with_encrypted_len() {
+    let cks = setup_simulation();
+
+    for (max_len, lengths) in [(70, vec![0, 5, 55, 56, 64, 70]), (140, vec![0, 135, 136])] {
+        let message = random_message(max_len);
+        let mut buffer = message.clone();
+        buffer.resize(max_len, 0);
+        let encrypted = encrypt_bytes(&buffer, &cks);
+
+        for len in lengths.into_iter().chain([message.len()]) {
+            let prefix = &buffer[..len];
+            let encrypted_len = FheUint16::encrypt(len as u16, &cks);
+
+            let digest = sha256_with_encrypted_len(&encrypted, &encrypted_len);
+            assert_eq!(decrypt_bytes(&digest, &cks), clear_sha256(prefix));
+            let digest = sha3_256_with_encrypted_len(&encrypted, &encrypted_len);
+            assert_eq!(decrypt_bytes(&digest, &cks), clear_sha3_256(prefix));
+        }
+    }
+}
+
+#[cfg(feature = "strings")]
+#[test]
+fn test_hashing_ascii_strings() {
+    use crate::hashing::{sha256_ascii, sha3_256_ascii};
+    use crate::{generate_keys, FheAsciiString};
+
+    let (cks, sks) = generate_keys(ConfigBuilder::default());
+    set_server_key(sks);
+
+    let string = FheAsciiString::try_encrypt_with_padding("tfhe", 2, &cks).unwrap();
+    let digest = sha256_ascii(&string);
+    assert_eq!(decrypt_bytes(&digest, &cks), clear_sha256(b"tfhe"));
+
+    let string = FheAsciiString::try_encrypt("tfhe-rs", &cks).unwrap();
+    let digest = sha3_256_ascii(&string);
+    assert_eq!(decrypt_bytes(&digest, &cks), clear_sha3_256(b"tfhe-rs"));
+}
@@ -0,0 +1,123 @@
+use crate::prelude::*;
+use crate::shortint::parameters::{
+    COMP_PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128,
+    PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128,
+};
+use crate::{
+    set_server_key, ClientKey, CompressedCiphertextList, CompressedCiphertextListBuilder,
+    ConfigBuilder, FheBool, FheInt16, FheUint32, KeyRotationKey, ServerKey,
+};
+use rand::random;
+
+#[test]
+fn test_key_rotation() {
+    let config =
+        ConfigBuilder::with_custom_parameters(PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128)
+            .enable_compression(COMP_PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128)
+            .build();
+
+    let mut old_cks = ClientKey::generate(config);
+    old_cks.tag_mut().set_u64(random());
+    let old_sks = ServerKey::new(&old_cks);
+
+    let mut new_cks = ClientKey::generate(config);
+    new_cks.tag_mut().set_u64(random());
+    let new_sks = ServerKey::new(&new_cks);
+
+    let rotation_key = KeyRotationKey::new(&old_cks, (&new_cks, &new_sks)).unwrap();
+    assert!(rotation_key.supports_compressed_lists());
+    assert_eq!(rotation_key.tag_in(), old_cks.tag());
+    assert_eq!(rotation_key.tag_out(), new_cks.tag());
+
+    let clear_a = random::<u32>();
+    let clear_b = random::<i16>();
+    let clear_c = random::<bool>();
+
+    let a = FheUint32::encrypt(clear_a, &old_cks);
+    let b = FheInt16::encrypt(clear_b, &old_cks);
+    let c = FheBool::encrypt(clear_c, &old_cks);
+
+    // Single ciphertexts
+    {
+        let rotated_a = rotation_key.keyswitch(&a);
+        let rotated_b = rotation_key.keyswitch(&b);
+        let rotated_c = rotation_key.keyswitch(&c);
+
+        assert_eq!(rotated_a.tag(), new_cks.tag());
+        assert_eq!(rotated_b.tag(), new_cks.tag());
+        assert_eq!(rotated_c.tag(), new_cks.tag());
+
+        let da: u32 = rotated_a.decrypt(&new_cks);
+        let db: i16 = rotated_b.decrypt(&new_cks);
+        let dc: bool = rotated_c.decrypt(&new_cks);
+        assert_eq!((da, db, dc), (clear_a, clear_b, clear_c));
+
+        // Rotated ciphertexts can be used with the new server key
+        set_server_key(new_sks.clone());
+        let sum = &rotated_a + &FheUint32::encrypt(1u32, &new_cks);
+        let d: u32 = sum.decrypt(&new_cks);
+        assert_eq!(d, clear_a.wrapping_add(1));
+    }
+
+    // Whole compressed lists
+    {
+        set_server_key(old_sks);
+        let list = CompressedCiphertextListBuilder::new()
+            .push(a)
+            .push(b)
+            .push(c)
+            .build()
+            .unwrap();
+
+        let rotated = rotation_key.keyswitch(&list);
+        assert_eq!(rotated.tag(), new_cks.tag());
+        assert_eq!(rotated.len(), 3);
+
+        let rotated: CompressedCiphertextList =
+            bincode::deserialize(&bincode::serialize(&rotated).unwrap()).unwrap();
+
+        set_server_key(new_sks);
+        let a: FheUint32 = rotated.get(0).unwrap().unwrap();
+        let b: FheInt16 = rotated.get(1).unwrap().unwrap();
+        let c: FheBool = rotated.get(2).unwrap().unwrap();
+
+        let da: u32 = a.decrypt(&new_cks);
+        let db: i16 = b.decrypt(&new_cks);
+        let dc: bool = c.decrypt(&new_cks);
+        assert_eq!((da, db, dc), (clear_a, clear_b, clear_c));
+    }
+
+    #[cfg(feature = "strings")]
+    {
+        let string = crate::FheAsciiString::try_encrypt("rotation", &old_cks).unwrap();
+        let rotated = rotation_key.keyswitch(&string);
+        assert_eq!(rotated.tag(), new_cks.tag());
+
+        let decrypted: String = rotated.decrypt(&new_cks);
+        assert_eq!(decrypted, "rotation");
+    }
+}
+
+#[test]
+fn test_key_rotation_without_compression() {
+    let config = ConfigBuilder::default().build();
+    let old_cks = ClientKey::generate(config);
+    let new_cks = ClientKey::generate(config);
+    let new_sks = ServerKey::new(&new_cks);
+
+    let rotation_key = KeyRotationKey::new(&old_cks, (&new_cks, &new_sks)).unwrap();
+    assert!(!rotation_key.supports_compressed_lists());
+
+    let compression_config =
+        ConfigBuilder::with_custom_parameters(PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128)
+            .enable_compression(COMP_PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128)
+            .build();
+    let cks = ClientKey::generate(compression_config);
+    set_server_key(ServerKey::new(&cks));
+    let list = CompressedCiphertextListBuilder::new()
+        .push(FheBool::encrypt(true, &cks))
+        .build()
+        .unwrap();
+
+    assert!(rotation_key.try_rotate_compressed_list(&list).is_err());
+}
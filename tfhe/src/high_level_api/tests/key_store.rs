@@ -0,0 +1,188 @@
+use std::io::Read;
+
+use crate::high_level_api::key_store::{
+    FileSystemBackend, KeyEncryptionKey, KeyKind, KeyStore, KeyStoreBackend, KeyStoreError,
+    MemoryBackend,
+};
+use crate::prelude::*;
+use crate::{
+    ClientKey, CompactPublicKey, CompressedServerKey, ConfigBuilder, FheUint8, ServerKey, Tag,
+};
+
+fn tagged_client_key(tag_value: u64) -> ClientKey {
+    let mut cks = ClientKey::generate(ConfigBuilder::default());
+    cks.tag_mut().set_u64(tag_value);
+    cks
+}
+
+fn check_store_and_load<B: KeyStoreBackend>(store: &KeyStore<B>) {
+    let cks = tagged_client_key(1);
+    let csks = CompressedServerKey::new(&cks);
+    let cpk = CompactPublicKey::new(&cks);
+
+    assert!(store.load::<ClientKey>(cks.tag()).unwrap().is_none());
+
+    store.store_tagged(&cks).unwrap();
+    store.store_tagged(&csks).unwrap();
+    store.store_tagged(&cpk).unwrap();
+
+    let loaded_cks: ClientKey = store.load(cks.tag()).unwrap().unwrap();
+    let loaded_csks: CompressedServerKey = store.load(cks.tag()).unwrap().unwrap();
+    let loaded_cpk: CompactPublicKey = store.load(cks.tag()).unwrap().unwrap();
+    assert_eq!(loaded_cks.tag(), cks.tag());
+
+    // The loaded keys work together
+    crate::set_server_key(loaded_csks.decompress());
+    let list = crate::CompactCiphertextList::builder(&loaded_cpk)
+        .push(12u8)
+        .build();
+    let expander = list.expand().unwrap();
+    let a: FheUint8 = expander.get(0).unwrap().unwrap();
+    let b = FheUint8::encrypt(30u8, &cks);
+    let c = a + b;
+    let decrypted: u8 = c.decrypt(&loaded_cks);
+    assert_eq!(decrypted, 42);
+
+    let mut entries = store.entries().unwrap();
+    entries.sort_by_key(|metadata| metadata.kind().name());
+    let kinds: Vec<_> = entries.iter().map(|metadata| metadata.kind()).collect();
+    assert_eq!(
+        kinds,
+        [
+            KeyKind::ClientKey,
+            KeyKind::CompactPublicKey,
+            KeyKind::CompressedServerKey
+        ]
+    );
+    assert!(entries.iter().all(|metadata| metadata.tag() == cks.tag()));
+
+    // Storing with the same tag replaces the key
+    let other_cks = ClientKey::generate(ConfigBuilder::default());
+    store.store(cks.tag(), &other_cks).unwrap();
+    let loaded: ClientKey = store.load(cks.tag()).unwrap().unwrap();
+    assert!(loaded.tag().is_empty());
+    assert_eq!(store.entries().unwrap().len(), 3);
+
+    assert!(store.remove(KeyKind::ClientKey, cks.tag()).unwrap());
+    assert!(!store.remove(KeyKind::ClientKey, cks.tag()).unwrap());
+    assert!(!store.contains(KeyKind::ClientKey, cks.tag()).unwrap());
+    assert!(store
+        .contains(KeyKind::CompactPublicKey, cks.tag())
+        .unwrap());
+}
+
+#[test]
+fn test_key_store_memory() {
+    let store = KeyStore::new(MemoryBackend::new());
+    check_store_and_load(&store);
+
+    let store =
+        KeyStore::new(MemoryBackend::new()).with_encryption_key(KeyEncryptionKey::generate());
+    check_store_and_load(&store);
+}
+
+#[test]
+fn test_key_store_file_system() {
+    let directory = std::env::temp_dir().join(format!("tfhe_key_store_{}", std::process::id()));
+
+    let store = KeyStore::new(FileSystemBackend::new(&directory).unwrap())
+        .with_encryption_key(KeyEncryptionKey::generate());
+    check_store_and_load(&store);
+
+    // Keys are visible from another store on the same directory
+    let other_store = KeyStore::new(FileSystemBackend::new(&directory).unwrap());
+    assert_eq!(other_store.entries().unwrap().len(), 2);
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_key_store_encryption() {
+    let store =
+        KeyStore::new(MemoryBackend::new()).with_encryption_key(KeyEncryptionKey::generate());
+
+    let cks = tagged_client_key(2);
+    let sks = ServerKey::new(&cks);
+    store.store_tagged(&cks).unwrap();
+    store.store_tagged(&sks).unwrap();
+
+    let metadata = store
+        .metadata(KeyKind::ClientKey, cks.tag())
+        .unwrap()
+        .unwrap();
+    assert!(metadata.is_encrypted());
+    assert_eq!(metadata.tfhe_version(), env!("CARGO_PKG_VERSION"));
+
+    // Public material is never encrypted
+    let metadata = store
+        .metadata(KeyKind::ServerKey, cks.tag())
+        .unwrap()
+        .unwrap();
+    assert!(!metadata.is_encrypted());
+}
+
+#[test]
+fn test_key_store_errors() {
+    let kek = KeyEncryptionKey::generate();
+    let directory =
+        std::env::temp_dir().join(format!("tfhe_key_store_errors_{}", std::process::id()));
+
+    let cks = tagged_client_key(3);
+    KeyStore::new(FileSystemBackend::new(&directory).unwrap())
+        .with_encryption_key(kek.clone())
+        .store_tagged(&cks)
+        .unwrap();
+
+    let store = KeyStore::new(FileSystemBackend::new(&directory).unwrap());
+    assert!(matches!(
+        store.load::<ClientKey>(cks.tag()),
+        Err(KeyStoreError::MissingEncryptionKey)
+    ));
+
+    let store = store.with_encryption_key(KeyEncryptionKey::generate());
+    assert!(matches!(
+        store.load::<ClientKey>(cks.tag()),
+        Err(KeyStoreError::DecryptionFailed)
+    ));
+
+    let store = KeyStore::new(FileSystemBackend::new(&directory).unwrap()).with_encryption_key(kek);
+    assert!(store.load::<ClientKey>(cks.tag()).unwrap().is_some());
+
+    // Keys stored with another tag are not found
+    let mut other_tag = Tag::default();
+    other_tag.set_u64(4);
+    assert!(store.load::<ClientKey>(&other_tag).unwrap().is_none());
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_key_store_fresh_nonces() {
+    let store =
+        KeyStore::new(MemoryBackend::new()).with_encryption_key(KeyEncryptionKey::generate());
+    let cks = tagged_client_key(5);
+
+    let read_record = || {
+        let names = store.backend().list().unwrap();
+        assert_eq!(names.len(), 1);
+        let mut record = Vec::new();
+        store
+            .backend()
+            .read(&names[0])
+            .unwrap()
+            .unwrap()
+            .read_to_end(&mut record)
+            .unwrap();
+        record
+    };
+
+    // Storing the same key twice with the same encryption key must not reuse the nonce
+    store.store_tagged(&cks).unwrap();
+    let first = read_record();
+    store.store_tagged(&cks).unwrap();
+    let second = read_record();
+    assert_ne!(first, second);
+
+    let loaded: ClientKey = store.load(cks.tag()).unwrap().unwrap();
+    assert_eq!(loaded.tag(), cks.tag());
+}
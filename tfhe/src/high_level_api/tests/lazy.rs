@@ -0,0 +1,271 @@
+use crate::prelude::*;
+use crate::shortint::parameters::{
+    COMP_PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128,
+    PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128,
+};
+use crate::{
+    set_server_key, with_lazy_evaluation, ClientKey, CompressedCiphertextListBuilder,
+    ConfigBuilder, FheInt32, FheUint32, ServerKey,
+};
+use rand::random;
+
+fn setup_default_cpu() -> ClientKey {
+    let config = ConfigBuilder::default().build();
+    let client_key = ClientKey::generate(config);
+    set_server_key(ServerKey::new(&client_key));
+    client_key
+}
+
+#[test]
+fn test_lazy_evaluation() {
+    let cks = setup_default_cpu();
+
+    let clear_a = random::<u32>();
+    let clear_b = random::<u32>();
+    let clear_c = random::<u32>();
+
+    let a = FheUint32::encrypt(clear_a, &cks);
+    let b = FheUint32::encrypt(clear_b, &cks);
+    let c = FheUint32::encrypt(clear_c, &cks);
+
+    let (sum, mixed, product, mut accumulated) = with_lazy_evaluation(|| {
+        // Long chain of additions and subtractions, carries have to be
+        // propagated at some point in the chain
+        let mut sum = &a + &b;
+        for _ in 0..10 {
+            sum = &sum + &c - &b + &a;
+        }
+
+        let mixed = ((&a ^ &b) & (&b | &c)) ^ (&sum & &a);
+        let product = &a * &b + &c * &a;
+
+        let mut accumulated = a.clone();
+        accumulated += &b;
+        accumulated ^= &c;
+        accumulated *= &b;
+        accumulated -= &a;
+        accumulated &= &sum;
+        accumulated |= &c;
+
+        (sum, mixed, product, accumulated)
+    });
+
+    let mut expected_sum = clear_a.wrapping_add(clear_b);
+    for _ in 0..10 {
+        expected_sum = expected_sum
+            .wrapping_add(clear_c)
+            .wrapping_sub(clear_b)
+            .wrapping_add(clear_a);
+    }
+    let expected_mixed = ((clear_a ^ clear_b) & (clear_b | clear_c)) ^ (expected_sum & clear_a);
+    let expected_product = clear_a
+        .wrapping_mul(clear_b)
+        .wrapping_add(clear_c.wrapping_mul(clear_a));
+    let expected_accumulated = (((clear_a.wrapping_add(clear_b) ^ clear_c).wrapping_mul(clear_b))
+        .wrapping_sub(clear_a)
+        & expected_sum)
+        | clear_c;
+
+    accumulated.eval();
+    let decrypted: u32 = accumulated.decrypt(&cks);
+    assert_eq!(decrypted, expected_accumulated);
+
+    let decrypted: u32 = sum.decrypt(&cks);
+    assert_eq!(decrypted, expected_sum);
+
+    let decrypted: u32 = mixed.decrypt(&cks);
+    assert_eq!(decrypted, expected_mixed);
+
+    // Lazy values can be used with operations that are not recorded
+    let is_eq = product.eq(&(&a * &b + &c * &a));
+    assert!(is_eq.decrypt(&cks));
+
+    // And serialized
+    let product: FheUint32 = bincode::deserialize(&bincode::serialize(&product).unwrap()).unwrap();
+    let decrypted: u32 = product.decrypt(&cks);
+    assert_eq!(decrypted, expected_product);
+}
+
+#[test]
+fn test_lazy_evaluation_scalar_and_comparisons() {
+    let cks = setup_default_cpu();
+
+    let clear_a = random::<u32>();
+    let clear_b = random::<u32>();
+    let clear_c = random::<u32>();
+
+    let a = FheUint32::encrypt(clear_a, &cks);
+    let b = FheUint32::encrypt(clear_b, &cks);
+
+    let (scalar_chain, mut accumulated, is_eq, is_ne, is_lt, is_ge) = with_lazy_evaluation(|| {
+        // Dropped before the evaluation, but still computed as the other values need it
+        let intermediate = &a * clear_c - &b;
+        let scalar_chain = ((&intermediate + clear_c) ^ clear_a) & (u32::MAX - 1);
+
+        let mut accumulated = b.clone();
+        accumulated += clear_a;
+        accumulated -= clear_c;
+        accumulated *= 3u32;
+        accumulated |= clear_b;
+        accumulated ^= clear_c;
+        accumulated &= clear_a;
+
+        // Not used at all, so never computed
+        let _unused = &intermediate * &a;
+
+        let sum = &a + &b;
+        (
+            scalar_chain,
+            accumulated,
+            sum.eq(&sum),
+            sum.ne(&a),
+            intermediate.lt(&a),
+            intermediate.ge(&b),
+        )
+    });
+
+    let intermediate = clear_a.wrapping_mul(clear_c).wrapping_sub(clear_b);
+    let expected_scalar_chain = (intermediate.wrapping_add(clear_c) ^ clear_a) & (u32::MAX - 1);
+    let expected_accumulated =
+        (((clear_b.wrapping_add(clear_a).wrapping_sub(clear_c)).wrapping_mul(3) | clear_b)
+            ^ clear_c)
+            & clear_a;
+
+    let decrypted: u32 = scalar_chain.decrypt(&cks);
+    assert_eq!(decrypted, expected_scalar_chain);
+
+    accumulated.eval();
+    let decrypted: u32 = accumulated.decrypt(&cks);
+    assert_eq!(decrypted, expected_accumulated);
+
+    assert!(is_eq.decrypt(&cks));
+    assert_eq!(
+        is_ne.decrypt(&cks),
+        clear_a.wrapping_add(clear_b) != clear_a
+    );
+    assert_eq!(is_lt.decrypt(&cks), intermediate < clear_a);
+    assert_eq!(is_ge.decrypt(&cks), intermediate >= clear_b);
+}
+
+#[test]
+fn test_lazy_evaluation_signed() {
+    let cks = setup_default_cpu();
+
+    let clear_a = random::<i32>();
+    let clear_b = random::<i32>();
+    let clear_c = random::<i32>();
+
+    let a = FheInt32::encrypt(clear_a, &cks);
+    let b = FheInt32::encrypt(clear_b, &cks);
+
+    let (result, mut accumulated, is_lt, is_le, is_gt) = with_lazy_evaluation(|| {
+        let result = ((&a - &b) * &a + clear_c) ^ (&b | -5i32);
+
+        let mut accumulated = a.clone();
+        accumulated -= -7i32;
+        accumulated *= &b;
+        accumulated += &a;
+        accumulated &= clear_c;
+        accumulated ^= &b;
+
+        (result, accumulated, a.lt(&b), b.le(&a), (&a - &b).gt(&a))
+    });
+
+    let expected_result = (clear_a
+        .wrapping_sub(clear_b)
+        .wrapping_mul(clear_a)
+        .wrapping_add(clear_c))
+        ^ (clear_b | -5);
+    let expected_accumulated = ((clear_a
+        .wrapping_add(7)
+        .wrapping_mul(clear_b)
+        .wrapping_add(clear_a))
+        & clear_c)
+        ^ clear_b;
+
+    // The values are evaluated from another thread
+    let (decrypted_result, is_gt) = std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                let decrypted: i32 = result.decrypt(&cks);
+                (decrypted, is_gt.decrypt(&cks))
+            })
+            .join()
+            .unwrap()
+    });
+    assert_eq!(decrypted_result, expected_result);
+    assert_eq!(is_gt, clear_a.wrapping_sub(clear_b) > clear_a);
+
+    accumulated.eval();
+    let decrypted: i32 = accumulated.decrypt(&cks);
+    assert_eq!(decrypted, expected_accumulated);
+
+    assert_eq!(is_lt.decrypt(&cks), clear_a < clear_b);
+    assert_eq!(is_le.decrypt(&cks), clear_b <= clear_a);
+}
+
+#[test]
+fn test_lazy_evaluation_across_graphs() {
+    let cks = setup_default_cpu();
+
+    let clear_a = random::<u32>();
+    let clear_b = random::<u32>();
+
+    let a = FheUint32::encrypt(clear_a, &cks);
+    let b = FheUint32::encrypt(clear_b, &cks);
+
+    let first = with_lazy_evaluation(|| &a + &b);
+    // The value of the first graph is used in a second one
+    let second = with_lazy_evaluation(|| {
+        let nested = with_lazy_evaluation(|| &first & &a);
+        &nested + &first
+    });
+
+    let decrypted: u32 = second.decrypt(&cks);
+    let expected_first = clear_a.wrapping_add(clear_b);
+    assert_eq!(
+        decrypted,
+        (expected_first & clear_a).wrapping_add(expected_first)
+    );
+
+    let decrypted: u32 = first.decrypt(&cks);
+    assert_eq!(decrypted, expected_first);
+
+    // Outside of the scope, operations are computed right away
+    let mut eager = &a + &b;
+    eager.eval();
+    let decrypted: u32 = eager.decrypt(&cks);
+    assert_eq!(decrypted, expected_first);
+}
+
+#[test]
+fn test_lazy_evaluation_compression() {
+    let config =
+        ConfigBuilder::with_custom_parameters(PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128)
+            .enable_compression(COMP_PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128)
+            .build();
+    let cks = ClientKey::generate(config);
+    set_server_key(ServerKey::new(&cks));
+
+    let clear_a = random::<u32>();
+    let clear_b = random::<u32>();
+
+    let a = FheUint32::encrypt(clear_a, &cks);
+    let b = FheUint32::encrypt(clear_b, &cks);
+
+    let (sum, xor) = with_lazy_evaluation(|| (&a + &b, &a ^ &b));
+
+    let list = CompressedCiphertextListBuilder::new()
+        .push(sum)
+        .push(xor)
+        .build()
+        .unwrap();
+
+    let sum: FheUint32 = list.get(0).unwrap().unwrap();
+    let xor: FheUint32 = list.get(1).unwrap().unwrap();
+
+    let decrypted: u32 = sum.decrypt(&cks);
+    assert_eq!(decrypted, clear_a.wrapping_add(clear_b));
+    let decrypted: u32 = xor.decrypt(&cks);
+    assert_eq!(decrypted, clear_a ^ clear_b);
+}
@@ -1,6 +1,18 @@
+mod async_api;
+mod circuit;
+mod context;
+#[cfg(feature = "pbs-stats")]
+mod dry_run;
 #[cfg(feature = "gpu")]
 mod gpu_selection;
+mod hashing;
+mod key_rotation;
+#[cfg(feature = "key-store")]
+mod key_store;
+mod lazy;
 mod noise_squashing;
+mod program;
+mod simulation;
 mod tags_on_entities;
 
 use crate::high_level_api::prelude::*;
@@ -142,6 +154,46 @@ fn test_with_seed() {
     assert_ne!(&cks1_serialized, &cks4_serialized);
 }
 
+#[test]
+fn test_with_master_seed() {
+    use crate::shortint::parameters::{
+        COMP_PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128,
+        PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128,
+    };
+    use crate::MasterSeed;
+
+    let config =
+        ConfigBuilder::with_custom_parameters(PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128)
+            .enable_compression(COMP_PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128)
+            .build();
+
+    let master_seed = MasterSeed::generate();
+    let generate = |tenant: &str| {
+        let tenant_seed = master_seed.derive_child(tenant);
+        let cks = ClientKey::generate_with_seed(config, tenant_seed.derive("client_key"));
+        let csks = CompressedServerKey::new_with_seed(&cks, tenant_seed.derive("server_key"));
+        let pk = CompactPublicKey::new_with_seed(&cks, tenant_seed.derive("public_key"));
+
+        (
+            bincode::serialize(&cks).unwrap(),
+            bincode::serialize(&csks).unwrap(),
+            bincode::serialize(&pk).unwrap(),
+        )
+    };
+
+    // All the keys, including the compression keys, are reproducible
+    let (cks1, csks1, pk1) = generate("tenant-1");
+    let (cks2, csks2, pk2) = generate("tenant-1");
+    assert_eq!(cks1, cks2);
+    assert_eq!(csks1, csks2);
+    assert_eq!(pk1, pk2);
+
+    let (cks3, csks3, pk3) = generate("tenant-2");
+    assert_ne!(cks1, cks3);
+    assert_ne!(csks1, csks3);
+    assert_ne!(pk1, pk3);
+}
+
 #[test]
 fn test_with_context() {
     let config = ConfigBuilder::default().build();
@@ -0,0 +1,389 @@
+use crate::prelude::*;
+use crate::program::{
+    BinaryOp, ClearValue, ComparisonOp, FheValue, Instruction, Program, UnaryOp, ValueType,
+};
+use crate::safe_serialization::{safe_deserialize, safe_serialize};
+use crate::{generate_keys, set_server_key, ClientKey, ConfigBuilder, SimulationServerKey};
+use rand::{random, thread_rng, Rng};
+
+// Registers of the random programs
+const UNSIGNED: [usize; 3] = [0, 1, 2];
+const SIGNED: [usize; 3] = [3, 4, 5];
+const BOOLEANS: [usize; 2] = [6, 7];
+
+fn random_program_registers() -> Vec<ValueType> {
+    let mut registers = vec![ValueType::Uint8; UNSIGNED.len()];
+    registers.extend([ValueType::Int8; SIGNED.len()]);
+    registers.extend([ValueType::Bool; BOOLEANS.len()]);
+    registers
+}
+
+fn random_instruction(rng: &mut impl Rng) -> Instruction {
+    let mut pick = |registers: &[usize]| registers[rng.gen_range(0..registers.len())];
+    let integers = if random() { UNSIGNED } else { SIGNED };
+    let (dst, lhs, rhs) = (pick(&integers), pick(&integers), pick(&integers));
+    let (condition, boolean) = (pick(&BOOLEANS), pick(&BOOLEANS));
+    let amount = pick(&UNSIGNED);
+    let any = pick(&[UNSIGNED, SIGNED].concat());
+
+    let binary_ops = [
+        BinaryOp::Add,
+        BinaryOp::Sub,
+        BinaryOp::Mul,
+        BinaryOp::Div,
+        BinaryOp::Rem,
+        BinaryOp::Min,
+        BinaryOp::Max,
+        BinaryOp::BitAnd,
+        BinaryOp::BitOr,
+        BinaryOp::BitXor,
+    ];
+    let shift_ops = [
+        BinaryOp::Shl,
+        BinaryOp::Shr,
+        BinaryOp::RotateLeft,
+        BinaryOp::RotateRight,
+    ];
+    let comparison_ops = [
+        ComparisonOp::Eq,
+        ComparisonOp::Ne,
+        ComparisonOp::Lt,
+        ComparisonOp::Le,
+        ComparisonOp::Gt,
+        ComparisonOp::Ge,
+    ];
+
+    match rng.gen_range(0..10) {
+        0 => Instruction::Constant {
+            dst,
+            value: if integers == UNSIGNED {
+                ClearValue::Uint8(rng.gen())
+            } else {
+                ClearValue::Int8(rng.gen())
+            },
+        },
+        1 => Instruction::Unary {
+            op: if random() { UnaryOp::Not } else { UnaryOp::Neg },
+            dst,
+            src: lhs,
+        },
+        2 | 3 => Instruction::Binary {
+            op: binary_ops[rng.gen_range(0..binary_ops.len())],
+            dst,
+            lhs,
+            rhs,
+        },
+        4 => Instruction::Binary {
+            op: shift_ops[rng.gen_range(0..shift_ops.len())],
+            dst,
+            lhs: dst,
+            rhs: amount,
+        },
+        5 => Instruction::Compare {
+            op: comparison_ops[rng.gen_range(0..comparison_ops.len())],
+            dst: condition,
+            lhs,
+            rhs,
+        },
+        6 => Instruction::Select {
+            dst,
+            condition,
+            if_true: lhs,
+            if_false: rhs,
+        },
+        7 => Instruction::Cast {
+            dst: if random() { any } else { boolean },
+            src: if random() { any } else { boolean },
+        },
+        8 => Instruction::Load {
+            dst,
+            index: amount,
+            table: vec![lhs, rhs, dst],
+        },
+        _ => Instruction::Binary {
+            op: BinaryOp::BitXor,
+            dst: boolean,
+            lhs: boolean,
+            rhs: condition,
+        },
+    }
+}
+
+fn random_program(num_instructions: usize) -> Program {
+    let mut rng = thread_rng();
+    let mut instructions = Vec::new();
+    while instructions.len() < num_instructions {
+        if rng.gen_range(0..8) == 0 {
+            let body_len = rng.gen_range(1..4);
+            instructions.push(Instruction::Repeat {
+                count: rng.gen_range(0..3),
+                body_len,
+            });
+            instructions.extend((0..body_len).map(|_| random_instruction(&mut rng)));
+        } else {
+            instructions.push(random_instruction(&mut rng));
+        }
+    }
+
+    let registers = random_program_registers();
+    let outputs = (0..registers.len()).collect();
+    Program::new(
+        registers,
+        vec![UNSIGNED[0], SIGNED[0], BOOLEANS[0]],
+        outputs,
+        instructions,
+    )
+    .unwrap()
+}
+
+fn run_and_decrypt(program: &Program, inputs: &[ClearValue], cks: &ClientKey) -> Vec<ClearValue> {
+    let encrypted_inputs = inputs
+        .iter()
+        .map(|&input| FheValue::encrypt(input, cks))
+        .collect::<Vec<_>>();
+    let outputs = program.run(&encrypted_inputs).unwrap();
+    assert!(outputs
+        .iter()
+        .map(FheValue::value_type)
+        .eq(program.output_types()));
+    outputs.iter().map(|output| output.decrypt(cks)).collect()
+}
+
+#[test]
+fn test_program_matches_clear_execution() {
+    let config = ConfigBuilder::default().build();
+    let cks = ClientKey::generate(config);
+    set_server_key(SimulationServerKey::new(&cks));
+
+    for _ in 0..20 {
+        let program = random_program(40);
+        let inputs = [
+            ClearValue::Uint8(random()),
+            ClearValue::Int8(random()),
+            ClearValue::Bool(random()),
+        ];
+        let expected = program.run_clear(&inputs).unwrap();
+        let outputs = run_and_decrypt(&program, &inputs, &cks);
+        assert_eq!(outputs, expected, "{program:?}");
+    }
+}
+
+#[test]
+fn test_program_cpu() {
+    // Computes (a / b, a << b, [a, a / b, a << b][b], a as a 16 bits signed value)
+    let registers = vec![
+        ValueType::Uint8,
+        ValueType::Uint8,
+        ValueType::Uint8,
+        ValueType::Uint8,
+        ValueType::Uint8,
+        ValueType::Int16,
+    ];
+    let program = Program::new(
+        registers,
+        vec![0, 1],
+        vec![2, 3, 4, 5],
+        vec![
+            Instruction::Binary {
+                op: BinaryOp::Div,
+                dst: 2,
+                lhs: 0,
+                rhs: 1,
+            },
+            Instruction::Binary {
+                op: BinaryOp::Shl,
+                dst: 3,
+                lhs: 0,
+                rhs: 1,
+            },
+            Instruction::Load {
+                dst: 4,
+                index: 1,
+                table: vec![0, 2, 3],
+            },
+            Instruction::Cast { dst: 5, src: 0 },
+        ],
+    )
+    .unwrap();
+
+    let (cks, sks) = generate_keys(ConfigBuilder::default());
+    set_server_key(sks);
+
+    for b in [0u8, 2, 5] {
+        let inputs = [ClearValue::Uint8(random()), ClearValue::Uint8(b)];
+        let expected = program.run_clear(&inputs).unwrap();
+        assert_eq!(run_and_decrypt(&program, &inputs, &cks), expected);
+    }
+
+    // Division by zero and out of bounds loads
+    let inputs = [ClearValue::Uint8(200), ClearValue::Uint8(0)];
+    assert_eq!(
+        program.run_clear(&inputs).unwrap(),
+        vec![
+            ClearValue::Uint8(u8::MAX),
+            ClearValue::Uint8(200),
+            ClearValue::Uint8(200),
+            ClearValue::Int16(200),
+        ]
+    );
+    let inputs = [ClearValue::Uint8(200), ClearValue::Uint8(5)];
+    assert_eq!(program.run_clear(&inputs).unwrap()[2], ClearValue::Uint8(0));
+}
+
+#[test]
+fn test_program_validation() {
+    let registers = vec![ValueType::Uint8, ValueType::Int8, ValueType::Bool];
+    let invalid_programs = [
+        // Register out of bounds
+        (vec![0], vec![3], vec![Instruction::Copy { dst: 0, src: 0 }]),
+        // Different types
+        (
+            vec![0],
+            vec![0],
+            vec![Instruction::Binary {
+                op: BinaryOp::Add,
+                dst: 0,
+                lhs: 0,
+                rhs: 1,
+            }],
+        ),
+        // Arithmetic on booleans
+        (
+            vec![2],
+            vec![2],
+            vec![Instruction::Binary {
+                op: BinaryOp::Add,
+                dst: 2,
+                lhs: 2,
+                rhs: 2,
+            }],
+        ),
+        // Signed shift amount
+        (
+            vec![0],
+            vec![0],
+            vec![Instruction::Binary {
+                op: BinaryOp::Shl,
+                dst: 0,
+                lhs: 0,
+                rhs: 1,
+            }],
+        ),
+        // Non boolean comparison result
+        (
+            vec![0],
+            vec![0],
+            vec![Instruction::Compare {
+                op: ComparisonOp::Eq,
+                dst: 0,
+                lhs: 0,
+                rhs: 0,
+            }],
+        ),
+        // Non boolean condition
+        (
+            vec![0],
+            vec![0],
+            vec![Instruction::Select {
+                dst: 0,
+                condition: 0,
+                if_true: 0,
+                if_false: 0,
+            }],
+        ),
+        // Signed index
+        (
+            vec![0],
+            vec![0],
+            vec![Instruction::Load {
+                dst: 0,
+                index: 1,
+                table: vec![0],
+            }],
+        ),
+        // Loop body out of the program
+        (
+            vec![0],
+            vec![0],
+            vec![Instruction::Repeat {
+                count: 2,
+                body_len: 1,
+            }],
+        ),
+        // Loop body length overflowing the instruction position
+        (
+            vec![0],
+            vec![0],
+            vec![
+                Instruction::Repeat {
+                    count: 2,
+                    body_len: 1,
+                },
+                Instruction::Repeat {
+                    count: 2,
+                    body_len: usize::MAX,
+                },
+            ],
+        ),
+    ];
+    for (inputs, outputs, instructions) in invalid_programs {
+        assert!(
+            Program::new(registers.clone(), inputs, outputs, instructions.clone()).is_err(),
+            "{instructions:?} should not be accepted"
+        );
+    }
+
+    let program = Program::new(registers, vec![0, 2], vec![1], vec![]).unwrap();
+    assert!(program.run_clear(&[ClearValue::Uint8(1)]).is_err());
+    assert!(program
+        .run_clear(&[ClearValue::Int8(1), ClearValue::Bool(true)])
+        .is_err());
+    assert_eq!(
+        program
+            .run_clear(&[ClearValue::Uint8(1), ClearValue::Bool(true)])
+            .unwrap(),
+        vec![ClearValue::Int8(0)]
+    );
+}
+
+#[test]
+fn test_program_serialization() {
+    let program = random_program(30);
+
+    let mut buffer = vec![];
+    safe_serialize(&program, &mut buffer, 1 << 20).unwrap();
+    let deserialized: Program = safe_deserialize(buffer.as_slice(), 1 << 20).unwrap();
+    assert_eq!(deserialized, program);
+}
+
+#[test]
+fn test_program_malicious_loop_body_len() {
+    // Same layout as `Program`, used to build a program that skips validation
+    #[derive(serde::Serialize)]
+    struct UncheckedProgram {
+        registers: Vec<ValueType>,
+        inputs: Vec<usize>,
+        outputs: Vec<usize>,
+        instructions: Vec<Instruction>,
+    }
+
+    for body_len in [usize::MAX, usize::MAX - 1, 2] {
+        let unchecked = UncheckedProgram {
+            registers: vec![ValueType::Uint8],
+            inputs: vec![0],
+            outputs: vec![0],
+            instructions: vec![
+                Instruction::Repeat {
+                    count: 2,
+                    body_len: 1,
+                },
+                Instruction::Repeat { count: 2, body_len },
+            ],
+        };
+        let program: Program =
+            bincode::deserialize(&bincode::serialize(&unchecked).unwrap()).unwrap();
+
+        // The program is checked again before being executed
+        assert!(program.run_clear(&[ClearValue::Uint8(1)]).is_err());
+    }
+}
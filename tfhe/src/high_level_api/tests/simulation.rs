@@ -0,0 +1,174 @@
+use crate::prelude::*;
+use crate::{
+    set_server_key, ClientKey, ConfigBuilder, Device, FheBool, FheInt16, FheUint16, FheUint32,
+    FheUint8, ServerKey, SimulationServerKey,
+};
+use rand::random;
+
+fn setup_simulation() -> ClientKey {
+    let config = ConfigBuilder::default().build();
+    let client_key = ClientKey::generate(config);
+    set_server_key(SimulationServerKey::new(&client_key));
+    client_key
+}
+
+#[test]
+fn test_simulation_unsigned_ops() {
+    let cks = setup_simulation();
+
+    let clear_a = random::<u32>();
+    let clear_b = random::<u32>();
+
+    let a = FheUint32::encrypt(clear_a, &cks);
+    let b = FheUint32::encrypt(clear_b, &cks);
+
+    let c = &a + &b;
+    assert_eq!(c.current_device(), Device::Simulation);
+    let decrypted: u32 = c.decrypt(&cks);
+    assert_eq!(decrypted, clear_a.wrapping_add(clear_b));
+
+    let decrypted: u32 = (&a - &b).decrypt(&cks);
+    assert_eq!(decrypted, clear_a.wrapping_sub(clear_b));
+
+    let decrypted: u32 = (&a * &b).decrypt(&cks);
+    assert_eq!(decrypted, clear_a.wrapping_mul(clear_b));
+
+    let decrypted: u32 = ((&a & &b) | (&a ^ &b)).decrypt(&cks);
+    assert_eq!(decrypted, (clear_a & clear_b) | (clear_a ^ clear_b));
+
+    let decrypted: u32 = (&a / &b).decrypt(&cks);
+    assert_eq!(decrypted, clear_a / clear_b);
+
+    let decrypted: u32 = (&a % &b).decrypt(&cks);
+    assert_eq!(decrypted, clear_a % clear_b);
+
+    let decrypted: u32 = (&a << &b).decrypt(&cks);
+    assert_eq!(decrypted, clear_a.wrapping_shl(clear_b));
+
+    let decrypted: u32 = (&a >> &b).decrypt(&cks);
+    assert_eq!(decrypted, clear_a.wrapping_shr(clear_b));
+
+    let decrypted: u32 = (&a).rotate_left(&b).decrypt(&cks);
+    assert_eq!(decrypted, clear_a.rotate_left(clear_b));
+
+    let decrypted: u32 = a.min(&b).decrypt(&cks);
+    assert_eq!(decrypted, clear_a.min(clear_b));
+
+    let decrypted: bool = a.gt(&b).decrypt(&cks);
+    assert_eq!(decrypted, clear_a > clear_b);
+
+    let decrypted: bool = a.eq(clear_a).decrypt(&cks);
+    assert!(decrypted);
+
+    let decrypted: u32 = (&a + 1u32).decrypt(&cks);
+    assert_eq!(decrypted, clear_a.wrapping_add(1));
+
+    let (result, overflowed) = (&a).overflowing_add(&b);
+    let decrypted: u32 = result.decrypt(&cks);
+    assert_eq!(
+        (decrypted, overflowed.decrypt(&cks)),
+        clear_a.overflowing_add(clear_b)
+    );
+
+    let decrypted: u32 = a.leading_zeros().decrypt(&cks);
+    assert_eq!(decrypted, clear_a.leading_zeros());
+
+    let cond = FheBool::encrypt(clear_a > clear_b, &cks);
+    let decrypted: u32 = cond.select(&a, &b).decrypt(&cks);
+    assert_eq!(decrypted, clear_a.max(clear_b));
+
+    let casted: FheUint8 = a.clone().cast_into();
+    let decrypted: u8 = casted.decrypt(&cks);
+    assert_eq!(decrypted, clear_a as u8);
+}
+
+#[test]
+fn test_simulation_signed_ops() {
+    let cks = setup_simulation();
+
+    let clear_a = random::<i16>();
+    let clear_b = random::<i16>();
+
+    let a = FheInt16::encrypt(clear_a, &cks);
+    let b = FheInt16::encrypt(clear_b, &cks);
+
+    let decrypted: i16 = (&a + &b).decrypt(&cks);
+    assert_eq!(decrypted, clear_a.wrapping_add(clear_b));
+
+    let decrypted: i16 = (&a * &b).decrypt(&cks);
+    assert_eq!(decrypted, clear_a.wrapping_mul(clear_b));
+
+    if clear_b != 0 {
+        let decrypted: i16 = (&a / &b).decrypt(&cks);
+        assert_eq!(decrypted, clear_a.wrapping_div(clear_b));
+
+        let decrypted: i16 = (&a % &b).decrypt(&cks);
+        assert_eq!(decrypted, clear_a.wrapping_rem(clear_b));
+    }
+
+    let decrypted: i16 = (&a >> 3u16).decrypt(&cks);
+    assert_eq!(decrypted, clear_a >> 3);
+
+    let decrypted: i16 = (-&a).decrypt(&cks);
+    assert_eq!(decrypted, clear_a.wrapping_neg());
+
+    let decrypted: i16 = a.abs().decrypt(&cks);
+    assert_eq!(decrypted, clear_a.wrapping_abs());
+
+    let decrypted: bool = a.lt(&b).decrypt(&cks);
+    assert_eq!(decrypted, clear_a < clear_b);
+
+    let decrypted: bool = a.le(-1i16).decrypt(&cks);
+    assert_eq!(decrypted, clear_a <= -1);
+
+    let casted: FheUint16 = a.cast_into();
+    let decrypted: u16 = casted.decrypt(&cks);
+    assert_eq!(decrypted, clear_a as u16);
+}
+
+#[test]
+fn test_simulation_booleans() {
+    let cks = setup_simulation();
+
+    for (clear_a, clear_b) in [(false, false), (false, true), (true, false), (true, true)] {
+        let a = FheBool::encrypt(clear_a, &cks);
+        let b = FheBool::encrypt(clear_b, &cks);
+
+        let decrypted = (&a & &b).decrypt(&cks);
+        assert_eq!(decrypted, clear_a & clear_b);
+
+        let decrypted = (&a | &b).decrypt(&cks);
+        assert_eq!(decrypted, clear_a | clear_b);
+
+        let decrypted = (&a ^ &b).decrypt(&cks);
+        assert_eq!(decrypted, clear_a ^ clear_b);
+
+        let decrypted = (!&a).decrypt(&cks);
+        assert_eq!(decrypted, !clear_a);
+    }
+}
+
+#[test]
+fn test_simulation_matches_cpu_edge_cases() {
+    let config = ConfigBuilder::default().build();
+    let cks = ClientKey::generate(config);
+    let cpu_key = ServerKey::new(&cks);
+    let sim_key = SimulationServerKey::new(&cks);
+
+    let clear_a = random::<u8>();
+    let a = FheUint8::encrypt(clear_a, &cks);
+    let zero = FheUint8::encrypt(0u8, &cks);
+
+    let compute = |a: &FheUint8, zero: &FheUint8| -> (u8, u8) {
+        let (q, r) = a.div_rem(zero);
+        (q.decrypt(&cks), r.decrypt(&cks))
+    };
+
+    set_server_key(cpu_key);
+    let expected = compute(&a, &zero);
+
+    set_server_key(sim_key);
+    let simulated = compute(&a, &zero);
+
+    assert_eq!(simulated, expected);
+}
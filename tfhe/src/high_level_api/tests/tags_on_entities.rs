@@ -394,3 +394,86 @@ fn test_tag_propagation(
         }
     }
 }
+
+#[test]
+fn test_strict_tag_checks() {
+    use crate::{set_server_key_with_strict_tags, ErrorKind, FheUint8, MatchValues};
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut cks = ClientKey::generate(ConfigBuilder::default());
+    cks.tag_mut().set_u64(random());
+    let sks = ServerKey::new(&cks);
+
+    let a = FheUint8::encrypt(3u8, &cks);
+    let mut b = FheUint8::encrypt(4u8, &cks);
+    let mut c = FheBool::encrypt(true, &cks);
+    // Ciphertexts coming from another key set
+    b.tag_mut().set_u64(cks.tag().as_u64().wrapping_add(1));
+    c.tag_mut().set_u64(cks.tag().as_u64().wrapping_add(1));
+
+    set_server_key_with_strict_tags(sks.clone());
+
+    let d = &a + &a;
+    let decrypted: u8 = d.decrypt(&cks);
+    assert_eq!(decrypted, 6);
+
+    assert!(catch_unwind(AssertUnwindSafe(|| &a + &b)).is_err());
+    assert!(catch_unwind(AssertUnwindSafe(|| &b + 1u8)).is_err());
+    assert!(catch_unwind(AssertUnwindSafe(|| a.eq(&b))).is_err());
+    assert!(catch_unwind(AssertUnwindSafe(|| c.if_then_else(&a, &a))).is_err());
+    assert!(catch_unwind(AssertUnwindSafe(|| [&a, &b].into_iter().sum::<FheUint8>())).is_err());
+
+    let check_mismatch = |result: crate::Result<_>, found: &crate::Tag| {
+        let Err(err) = result else {
+            panic!("Expected a tag mismatch error");
+        };
+        match err.kind() {
+            ErrorKind::TagMismatch(mismatch) => {
+                assert_eq!(mismatch.expected(), cks.tag());
+                assert_eq!(mismatch.found(), found);
+            }
+            kind => panic!("Unexpected error kind {kind:?}"),
+        }
+    };
+
+    let match_values = MatchValues::new(vec![(3u8, 1u8)]).unwrap();
+    check_mismatch(
+        b.match_value::<u8, crate::FheUint8Id>(&match_values)
+            .map(|_| ()),
+        b.tag(),
+    );
+    check_mismatch((&b).bitslice(0..4u32).map(|_| ()), b.tag());
+    check_mismatch(c.squash_noise().map(|_| ()), c.tag());
+    check_mismatch(
+        CompressedCiphertextListBuilder::new()
+            .push(a.clone())
+            .push(c.clone())
+            .build()
+            .map(|_| ()),
+        c.tag(),
+    );
+
+    #[cfg(feature = "strings")]
+    {
+        let s1 = crate::FheAsciiString::try_encrypt("tfhe", &cks).unwrap();
+        let mut s2 = crate::FheAsciiString::try_encrypt("rs", &cks).unwrap();
+        s2.tag_mut().set_u64(cks.tag().as_u64().wrapping_add(1));
+        let _ = s1.concat(&s1);
+        check_mismatch(
+            CompressedCiphertextListBuilder::new()
+                .push(s1.clone())
+                .push(s2.clone())
+                .build()
+                .map(|_| ()),
+            s2.tag(),
+        );
+        assert!(catch_unwind(AssertUnwindSafe(|| s1.concat(&s2))).is_err());
+    }
+
+    // Regular mode does not check tags
+    set_server_key(sks);
+    let d = &a + &b;
+    let decrypted: u8 = d.decrypt(&cks);
+    assert_eq!(decrypted, 7);
+    assert_eq!(d.tag(), cks.tag());
+}
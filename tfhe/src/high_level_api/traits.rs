@@ -1,6 +1,5 @@
 use std::ops::RangeBounds;
 
-use crate::error::InvalidRangeError;
 use crate::high_level_api::ClientKey;
 use crate::{FheBool, Tag};
 
@@ -183,7 +182,7 @@ pub trait OverflowingMul<Rhs> {
 pub trait BitSlice<Bounds> {
     type Output;
 
-    fn bitslice<R>(self, range: R) -> Result<Self::Output, InvalidRangeError>
+    fn bitslice<R>(self, range: R) -> crate::Result<Self::Output>
     where
         R: RangeBounds<Bounds>;
 }
@@ -22,6 +22,7 @@ use crate::integer::ciphertext::{CompressedCrtCiphertext, CrtCiphertext};
 use crate::integer::client_key::utils::i_crt;
 use crate::integer::compression_keys::{CompressionKey, CompressionPrivateKeys, DecompressionKey};
 use crate::integer::encryption::{encrypt_crt, encrypt_words_radix_impl};
+use crate::named::Named;
 use crate::shortint::ciphertext::Degree;
 use crate::shortint::parameters::{CompressionParameters, MessageModulus};
 use crate::shortint::{
@@ -46,6 +47,10 @@ pub struct ClientKey {
     pub(crate) key: ShortintClientKey,
 }
 
+impl Named for ClientKey {
+    const NAME: &'static str = "integer::ClientKey";
+}
+
 impl From<ShortintClientKey> for ClientKey {
     fn from(key: ShortintClientKey) -> Self {
         Self { key }
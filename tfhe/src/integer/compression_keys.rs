@@ -1,7 +1,19 @@
 use super::ClientKey;
 use crate::conformance::ParameterSetConformant;
+use crate::core_crypto::prelude::{
+    CiphertextModulusLog, LweCiphertextCount, LwePackingKeyswitchKeyOwned,
+    SeededLweBootstrapKeyOwned, SeededLwePackingKeyswitchKeyOwned,
+};
 use crate::integer::backward_compatibility::list_compression::*;
 use crate::named::Named;
+use crate::shortint::server_key::{
+    ShortintBootstrappingKey, ShortintBootstrappingKeyStreamingMetadata,
+};
+use crate::streaming_serialization::{
+    LwePackingKeyswitchKeyStreamingMetadata, SeededLweBootstrapKeyStreamingMetadata,
+    SeededLwePackingKeyswitchKeyStreamingMetadata, StreamingDataReader, StreamingDataWriter,
+    StreamingError, StreamingSerializable,
+};
 use serde::{Deserialize, Serialize};
 use tfhe_versionable::Versionize;
 
@@ -193,3 +205,237 @@ impl ParameterSetConformant for CompressedDecompressionKey {
         key.is_conformant(parameter_set)
     }
 }
+
+/// Parameters of a [`CompressionKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct CompressionKeyStreamingMetadata {
+    pub packing_key_switching_key: LwePackingKeyswitchKeyStreamingMetadata<u64>,
+    pub lwe_per_glwe: LweCiphertextCount,
+    pub storage_log_modulus: CiphertextModulusLog,
+}
+
+impl StreamingSerializable for CompressionKey {
+    type Element = u64;
+    type Metadata = CompressionKeyStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        let crate::shortint::list_compression::CompressionKey {
+            packing_key_switching_key,
+            lwe_per_glwe,
+            storage_log_modulus,
+        } = &self.key;
+
+        CompressionKeyStreamingMetadata {
+            packing_key_switching_key: packing_key_switching_key.streaming_metadata(),
+            lwe_per_glwe: *lwe_per_glwe,
+            storage_log_modulus: *storage_log_modulus,
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        LwePackingKeyswitchKeyOwned::streaming_data_len(&metadata.packing_key_switching_key)
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        self.key
+            .packing_key_switching_key
+            .write_streaming_data(writer)
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        let CompressionKeyStreamingMetadata {
+            packing_key_switching_key,
+            lwe_per_glwe,
+            storage_log_modulus,
+        } = metadata;
+
+        Ok(Self {
+            key: crate::shortint::list_compression::CompressionKey {
+                packing_key_switching_key: LwePackingKeyswitchKeyOwned::read_streaming_data(
+                    packing_key_switching_key,
+                    reader,
+                )?,
+                lwe_per_glwe,
+                storage_log_modulus,
+            },
+        })
+    }
+}
+
+/// Parameters of a [`CompressedCompressionKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct CompressedCompressionKeyStreamingMetadata {
+    pub packing_key_switching_key: SeededLwePackingKeyswitchKeyStreamingMetadata<u64>,
+    pub lwe_per_glwe: LweCiphertextCount,
+    pub storage_log_modulus: CiphertextModulusLog,
+}
+
+impl StreamingSerializable for CompressedCompressionKey {
+    type Element = u64;
+    type Metadata = CompressedCompressionKeyStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        let crate::shortint::list_compression::CompressedCompressionKey {
+            packing_key_switching_key,
+            lwe_per_glwe,
+            storage_log_modulus,
+        } = &self.key;
+
+        CompressedCompressionKeyStreamingMetadata {
+            packing_key_switching_key: packing_key_switching_key.streaming_metadata(),
+            lwe_per_glwe: *lwe_per_glwe,
+            storage_log_modulus: *storage_log_modulus,
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        SeededLwePackingKeyswitchKeyOwned::streaming_data_len(&metadata.packing_key_switching_key)
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        self.key
+            .packing_key_switching_key
+            .write_streaming_data(writer)
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        let CompressedCompressionKeyStreamingMetadata {
+            packing_key_switching_key,
+            lwe_per_glwe,
+            storage_log_modulus,
+        } = metadata;
+
+        Ok(Self {
+            key: crate::shortint::list_compression::CompressedCompressionKey {
+                packing_key_switching_key: SeededLwePackingKeyswitchKeyOwned::read_streaming_data(
+                    packing_key_switching_key,
+                    reader,
+                )?,
+                lwe_per_glwe,
+                storage_log_modulus,
+            },
+        })
+    }
+}
+
+/// Parameters of a [`DecompressionKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct DecompressionKeyStreamingMetadata {
+    pub blind_rotate_key: ShortintBootstrappingKeyStreamingMetadata,
+    pub lwe_per_glwe: LweCiphertextCount,
+}
+
+impl StreamingSerializable for DecompressionKey {
+    type Element = u8;
+    type Metadata = DecompressionKeyStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        let crate::shortint::list_compression::DecompressionKey {
+            blind_rotate_key,
+            lwe_per_glwe,
+        } = &self.key;
+
+        DecompressionKeyStreamingMetadata {
+            blind_rotate_key: blind_rotate_key.streaming_metadata(),
+            lwe_per_glwe: *lwe_per_glwe,
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        ShortintBootstrappingKey::streaming_data_len(&metadata.blind_rotate_key)
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        self.key.blind_rotate_key.write_streaming_data(writer)
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        let DecompressionKeyStreamingMetadata {
+            blind_rotate_key,
+            lwe_per_glwe,
+        } = metadata;
+
+        Ok(Self {
+            key: crate::shortint::list_compression::DecompressionKey {
+                blind_rotate_key: ShortintBootstrappingKey::read_streaming_data(
+                    blind_rotate_key,
+                    reader,
+                )?,
+                lwe_per_glwe,
+            },
+        })
+    }
+}
+
+/// Parameters of a [`CompressedDecompressionKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct CompressedDecompressionKeyStreamingMetadata {
+    pub blind_rotate_key: SeededLweBootstrapKeyStreamingMetadata<u64>,
+    pub lwe_per_glwe: LweCiphertextCount,
+}
+
+impl StreamingSerializable for CompressedDecompressionKey {
+    type Element = u64;
+    type Metadata = CompressedDecompressionKeyStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        let crate::shortint::list_compression::CompressedDecompressionKey {
+            blind_rotate_key,
+            lwe_per_glwe,
+        } = &self.key;
+
+        CompressedDecompressionKeyStreamingMetadata {
+            blind_rotate_key: blind_rotate_key.streaming_metadata(),
+            lwe_per_glwe: *lwe_per_glwe,
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        SeededLweBootstrapKeyOwned::streaming_data_len(&metadata.blind_rotate_key)
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        self.key.blind_rotate_key.write_streaming_data(writer)
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        let CompressedDecompressionKeyStreamingMetadata {
+            blind_rotate_key,
+            lwe_per_glwe,
+        } = metadata;
+
+        Ok(Self {
+            key: crate::shortint::list_compression::CompressedDecompressionKey {
+                blind_rotate_key: SeededLweBootstrapKeyOwned::read_streaming_data(
+                    blind_rotate_key,
+                    reader,
+                )?,
+                lwe_per_glwe,
+            },
+        })
+    }
+}
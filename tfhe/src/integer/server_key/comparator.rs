@@ -187,6 +187,7 @@ impl<'a> Comparator<'a> {
             lhs.noise_level() + rhs.noise_level(),
             self.server_key.key.max_noise_level,
         );
+        lhs.merge_pbs_depth(rhs);
         self.server_key
             .key
             .apply_lookup_table_assign(lhs, &self.sign_lut);
@@ -22,6 +22,25 @@ pub use radix_parallel::{MatchValues, MiniUnsignedInteger, Reciprocable};
 use serde::{Deserialize, Serialize};
 use tfhe_versionable::Versionize;
 
+/// Enters a `tracing` span recording the operation name and bit width, which is exited at the end
+/// of the enclosing block.
+///
+/// Expands to nothing when the `tracing` feature is disabled, in which case the arguments are not
+/// evaluated.
+macro_rules! operation_span {
+    ($operation:expr, $num_bits:expr) => {
+        #[cfg(feature = "tracing")]
+        let _operation_span = ::tracing::info_span!(
+            "operation",
+            operation = $operation,
+            num_bits = $num_bits as u64
+        )
+        .entered();
+    };
+}
+
+pub(crate) use operation_span;
+
 /// A structure containing the server public key.
 ///
 /// The server key is generated by the client and is meant to be published: the client
@@ -237,6 +256,15 @@ impl ServerKey {
 
         max_sum_to_full_carry.min(self.key.max_noise_level.get()) as usize
     }
+
+    /// Returns the number of message bits of the given radix ciphertext
+    #[cfg(feature = "tracing")]
+    pub(crate) fn num_bits_of<T>(&self, ct: &T) -> usize
+    where
+        T: crate::integer::IntegerCiphertext,
+    {
+        ct.blocks().len() * self.message_modulus().0.ilog2() as usize
+    }
 }
 
 impl AsRef<crate::shortint::ServerKey> for ServerKey {
@@ -1,4 +1,5 @@
 use crate::integer::ciphertext::IntegerRadixCiphertext;
+use crate::integer::server_key::operation_span;
 use crate::integer::ServerKey;
 
 impl ServerKey {
@@ -37,6 +38,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("abs", self.num_bits_of(ct));
         if ct.block_carries_are_empty() {
             self.unchecked_abs_parallelized(ct)
         } else {
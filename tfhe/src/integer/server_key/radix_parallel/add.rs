@@ -1,5 +1,6 @@
 use crate::core_crypto::commons::numeric::UnsignedInteger;
 use crate::integer::ciphertext::IntegerRadixCiphertext;
+use crate::integer::server_key::operation_span;
 use crate::integer::{BooleanBlock, RadixCiphertext, ServerKey, SignedRadixCiphertext};
 use crate::shortint::ciphertext::Degree;
 use crate::shortint::Ciphertext;
@@ -222,6 +223,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("add", self.num_bits_of(ct_left));
         let mut ct_res = ct_left.clone();
         self.add_assign_parallelized(&mut ct_res, ct_right);
         ct_res
@@ -291,6 +293,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("overflowing_add", self.num_bits_of(ct_left));
         let mut ct_res = ct_left.clone();
         let overflowed = self.overflowing_add_assign_parallelized(&mut ct_res, ct_right);
         (ct_res, overflowed)
@@ -1,4 +1,5 @@
 use crate::integer::ciphertext::IntegerRadixCiphertext;
+use crate::integer::server_key::operation_span;
 use crate::integer::ServerKey;
 use rayon::prelude::*;
 
@@ -130,6 +131,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("bitand", self.num_bits_of(ct_left));
         let mut ct_res = ct_left.clone();
         self.bitand_assign_parallelized(&mut ct_res, ct_right);
         ct_res
@@ -297,6 +299,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("bitor", self.num_bits_of(ct_left));
         let mut ct_res = ct_left.clone();
         self.bitor_assign_parallelized(&mut ct_res, ct_right);
         ct_res
@@ -464,6 +467,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("bitxor", self.num_bits_of(ct_left));
         let mut ct_res = ct_left.clone();
         self.bitxor_assign_parallelized(&mut ct_res, ct_right);
         ct_res
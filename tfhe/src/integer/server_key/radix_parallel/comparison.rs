@@ -3,6 +3,7 @@ use crate::core_crypto::prelude::UnsignedInteger;
 use crate::integer::ciphertext::boolean_value::BooleanBlock;
 use crate::integer::ciphertext::IntegerRadixCiphertext;
 use crate::integer::prelude::ServerKeyDefaultCMux;
+use crate::integer::server_key::operation_span;
 use crate::shortint::{Ciphertext, MessageModulus};
 use rayon::prelude::*;
 
@@ -807,6 +808,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("eq", self.num_bits_of(lhs));
         let mut tmp_lhs;
         let mut tmp_rhs;
         let (lhs, rhs) = match (lhs.block_carries_are_empty(), rhs.block_carries_are_empty()) {
@@ -839,6 +841,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("ne", self.num_bits_of(lhs));
         let mut tmp_lhs;
         let mut tmp_rhs;
         let (lhs, rhs) = match (lhs.block_carries_are_empty(), rhs.block_carries_are_empty()) {
@@ -871,6 +874,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("gt", self.num_bits_of(lhs));
         let mut tmp_lhs;
         let mut tmp_rhs;
         let (lhs, rhs) = match (lhs.block_carries_are_empty(), rhs.block_carries_are_empty()) {
@@ -903,6 +907,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("ge", self.num_bits_of(lhs));
         let mut tmp_lhs;
         let mut tmp_rhs;
         let (lhs, rhs) = match (lhs.block_carries_are_empty(), rhs.block_carries_are_empty()) {
@@ -935,6 +940,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("lt", self.num_bits_of(lhs));
         let mut tmp_lhs;
         let mut tmp_rhs;
         let (lhs, rhs) = match (lhs.block_carries_are_empty(), rhs.block_carries_are_empty()) {
@@ -967,6 +973,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("le", self.num_bits_of(lhs));
         let mut tmp_lhs;
         let mut tmp_rhs;
         let (lhs, rhs) = match (lhs.block_carries_are_empty(), rhs.block_carries_are_empty()) {
@@ -999,6 +1006,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("max", self.num_bits_of(lhs));
         let mut tmp_lhs;
         let mut tmp_rhs;
 
@@ -1032,6 +1040,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("min", self.num_bits_of(lhs));
         let mut tmp_lhs;
         let mut tmp_rhs;
 
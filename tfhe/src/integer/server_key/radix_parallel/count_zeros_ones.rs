@@ -3,6 +3,7 @@ use crate::integer::server_key::num_bits_to_represent_unsigned_value;
 use crate::integer::{IntegerRadixCiphertext, RadixCiphertext, SignedRadixCiphertext};
 use crate::shortint::ciphertext::Degree;
 
+use crate::integer::server_key::operation_span;
 use rayon::prelude::*;
 
 #[derive(Copy, Clone, PartialEq, Eq)]
@@ -97,6 +98,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("count_ones", self.num_bits_of(ct));
         self.count_bits_parallelized(ct, BitCountKind::One)
     }
 
@@ -108,6 +110,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("count_zeros", self.num_bits_of(ct));
         self.count_bits_parallelized(ct, BitCountKind::Zero)
     }
 
@@ -1,5 +1,6 @@
 use crate::integer::ciphertext::{IntegerRadixCiphertext, RadixCiphertext, SignedRadixCiphertext};
 use crate::integer::server_key::comparator::ZeroComparisonType;
+use crate::integer::server_key::operation_span;
 use crate::integer::{BooleanBlock, IntegerCiphertext, ServerKey};
 use crate::shortint::MessageModulus;
 use rayon::prelude::*;
@@ -610,6 +611,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("div_rem", self.num_bits_of(numerator));
         let mut tmp_numerator;
         let mut tmp_divisor;
 
@@ -764,6 +766,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("div", self.num_bits_of(numerator));
         let (q, _r) = self.div_rem_parallelized(numerator, divisor);
         q
     }
@@ -871,6 +874,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("rem", self.num_bits_of(numerator));
         let (_q, r) = self.div_rem_parallelized(numerator, divisor);
         r
     }
@@ -915,6 +919,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("checked_div_rem", self.num_bits_of(numerator));
         let ((q, r), div_by_0) = rayon::join(
             || self.div_rem_parallelized(numerator, divisor),
             || self.are_all_blocks_zero(divisor.blocks()),
@@ -960,6 +965,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("checked_div", self.num_bits_of(numerator));
         let (q, div_by_0) = rayon::join(
             || self.div_parallelized(numerator, divisor),
             || self.are_all_blocks_zero(divisor.blocks()),
@@ -1005,6 +1011,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("checked_rem", self.num_bits_of(numerator));
         let (r, rem_by_0) = rayon::join(
             || self.rem_parallelized(numerator, divisor),
             || self.are_all_blocks_zero(divisor.blocks()),
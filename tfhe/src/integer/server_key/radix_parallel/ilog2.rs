@@ -1,3 +1,4 @@
+use crate::integer::server_key::operation_span;
 use crate::integer::{
     BooleanBlock, IntegerCiphertext, IntegerRadixCiphertext, RadixCiphertext, ServerKey,
     SignedRadixCiphertext,
@@ -462,6 +463,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("trailing_zeros", self.num_bits_of(ct));
         let mut tmp;
         let ct = if ct.block_carries_are_empty() {
             ct
@@ -506,6 +508,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("trailing_ones", self.num_bits_of(ct));
         let mut tmp;
         let ct = if ct.block_carries_are_empty() {
             ct
@@ -550,6 +553,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("leading_zeros", self.num_bits_of(ct));
         let mut tmp;
         let ct = if ct.block_carries_are_empty() {
             ct
@@ -594,6 +598,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("leading_ones", self.num_bits_of(ct));
         let mut tmp;
         let ct = if ct.block_carries_are_empty() {
             ct
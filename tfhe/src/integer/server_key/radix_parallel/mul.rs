@@ -1,4 +1,5 @@
 use crate::integer::ciphertext::IntegerRadixCiphertext;
+use crate::integer::server_key::operation_span;
 use crate::integer::{BooleanBlock, RadixCiphertext, ServerKey, SignedRadixCiphertext, I256};
 use crate::shortint::ciphertext::{Degree, NoiseLevel};
 use rayon::prelude::*;
@@ -600,6 +601,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("mul", self.num_bits_of(ct1));
         let mut ct_res = ct1.clone();
         self.mul_assign_parallelized(&mut ct_res, ct2);
         ct_res
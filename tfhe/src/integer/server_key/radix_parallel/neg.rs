@@ -1,4 +1,5 @@
 use crate::integer::ciphertext::IntegerRadixCiphertext;
+use crate::integer::server_key::operation_span;
 use crate::integer::ServerKey;
 
 impl ServerKey {
@@ -78,6 +79,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("neg", self.num_bits_of(ctxt));
         let mut tmp_ctxt;
 
         let ct = if ctxt.block_carries_are_empty() {
@@ -1,5 +1,6 @@
 use super::ServerKey;
 use crate::integer::ciphertext::IntegerRadixCiphertext;
+use crate::integer::server_key::operation_span;
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 
 impl ServerKey {
@@ -31,6 +32,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("reverse_bits", self.num_bits_of(ct));
         let message_modulus = self.message_modulus().0;
 
         let mut clean_ct;
@@ -1,5 +1,6 @@
 use super::shift::BarrelShifterOperation;
 use crate::integer::ciphertext::{IntegerRadixCiphertext, RadixCiphertext};
+use crate::integer::server_key::operation_span;
 use crate::integer::ServerKey;
 
 impl ServerKey {
@@ -127,6 +128,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("rotate_right", self.num_bits_of(ct));
         let mut ct_res = ct.clone();
         self.rotate_right_assign_parallelized(&mut ct_res, rotate);
         ct_res
@@ -256,6 +258,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("rotate_left", self.num_bits_of(ct));
         let mut ct_res = ct.clone();
         self.rotate_left_assign_parallelized(&mut ct_res, rotate);
         ct_res
@@ -4,6 +4,7 @@ use crate::integer::ciphertext::IntegerRadixCiphertext;
 use crate::integer::{BooleanBlock, RadixCiphertext, ServerKey, SignedRadixCiphertext};
 use crate::shortint::Ciphertext;
 
+use crate::integer::server_key::operation_span;
 use rayon::prelude::*;
 
 impl ServerKey {
@@ -62,6 +63,7 @@ impl ServerKey {
         T: IntegerRadixCiphertext,
         Scalar: DecomposableInto<u8>,
     {
+        operation_span!("overflowing_scalar_add", self.num_bits_of(lhs));
         let mut result = lhs.clone();
         let overflowed = self.overflowing_scalar_add_assign_parallelized(&mut result, scalar);
         (result, overflowed)
@@ -227,6 +229,7 @@ impl ServerKey {
         Scalar: DecomposableInto<u8>,
         T: IntegerRadixCiphertext,
     {
+        operation_span!("scalar_add", self.num_bits_of(ct));
         let mut ct_res = ct.clone();
         self.scalar_add_assign_parallelized(&mut ct_res, scalar);
         ct_res
@@ -1,5 +1,6 @@
 use crate::integer::block_decomposition::{BlockDecomposer, DecomposableInto};
 use crate::integer::ciphertext::{IntegerRadixCiphertext, RadixCiphertext};
+use crate::integer::server_key::operation_span;
 use crate::integer::ServerKey;
 use rayon::prelude::*;
 
@@ -96,6 +97,7 @@ impl ServerKey {
         T: IntegerRadixCiphertext,
         Scalar: DecomposableInto<u8>,
     {
+        operation_span!("scalar_bitand", self.num_bits_of(lhs));
         let mut result = lhs.clone();
         self.scalar_bitand_assign_parallelized(&mut result, rhs);
         result
@@ -221,6 +223,7 @@ impl ServerKey {
         T: IntegerRadixCiphertext,
         Scalar: DecomposableInto<u8>,
     {
+        operation_span!("scalar_bitor", self.num_bits_of(lhs));
         let mut result = lhs.clone();
         self.scalar_bitor_assign_parallelized(&mut result, rhs);
         result
@@ -349,6 +352,7 @@ impl ServerKey {
         T: IntegerRadixCiphertext,
         Scalar: DecomposableInto<u8>,
     {
+        operation_span!("scalar_bitxor", self.num_bits_of(lhs));
         let mut result = lhs.clone();
         self.scalar_bitxor_assign_parallelized(&mut result, rhs);
         result
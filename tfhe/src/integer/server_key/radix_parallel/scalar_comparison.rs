@@ -4,6 +4,7 @@ use crate::integer::block_decomposition::{BlockDecomposer, DecomposableInto};
 use crate::integer::ciphertext::boolean_value::BooleanBlock;
 use crate::integer::ciphertext::IntegerRadixCiphertext;
 use crate::integer::server_key::comparator::ZeroComparisonType;
+use crate::integer::server_key::operation_span;
 use crate::integer::server_key::radix_parallel::comparison::{
     is_x_less_than_y_given_input_borrow, ComparisonKind, PreparedSignedCheck,
 };
@@ -594,6 +595,7 @@ impl ServerKey {
         T: IntegerRadixCiphertext,
         Scalar: DecomposableInto<u64>,
     {
+        operation_span!("scalar_eq", self.num_bits_of(lhs));
         let mut tmp_lhs;
         let lhs = if lhs.block_carries_are_empty() {
             lhs
@@ -621,6 +623,7 @@ impl ServerKey {
         T: IntegerRadixCiphertext,
         Scalar: DecomposableInto<u64>,
     {
+        operation_span!("scalar_ne", self.num_bits_of(lhs));
         let mut tmp_lhs;
         let lhs = if lhs.block_carries_are_empty() {
             lhs
@@ -1208,6 +1211,7 @@ impl ServerKey {
         T: IntegerRadixCiphertext,
         Scalar: DecomposableInto<u64>,
     {
+        operation_span!("scalar_gt", self.num_bits_of(lhs));
         let mut tmp_lhs;
         let lhs = if lhs.block_carries_are_empty() {
             lhs
@@ -1224,6 +1228,7 @@ impl ServerKey {
         T: IntegerRadixCiphertext,
         Scalar: DecomposableInto<u64>,
     {
+        operation_span!("scalar_ge", self.num_bits_of(lhs));
         let mut tmp_lhs;
         let lhs = if lhs.block_carries_are_empty() {
             lhs
@@ -1240,6 +1245,7 @@ impl ServerKey {
         T: IntegerRadixCiphertext,
         Scalar: DecomposableInto<u64>,
     {
+        operation_span!("scalar_lt", self.num_bits_of(lhs));
         let mut tmp_lhs;
         let lhs = if lhs.block_carries_are_empty() {
             lhs
@@ -1256,6 +1262,7 @@ impl ServerKey {
         T: IntegerRadixCiphertext,
         Scalar: DecomposableInto<u64>,
     {
+        operation_span!("scalar_le", self.num_bits_of(lhs));
         let mut tmp_lhs;
         let lhs = if lhs.block_carries_are_empty() {
             lhs
@@ -1272,6 +1279,7 @@ impl ServerKey {
         T: IntegerRadixCiphertext,
         Scalar: DecomposableInto<u64>,
     {
+        operation_span!("scalar_max", self.num_bits_of(lhs));
         let mut tmp_lhs;
         let lhs = if lhs.block_carries_are_empty() {
             lhs
@@ -1288,6 +1296,7 @@ impl ServerKey {
         T: IntegerRadixCiphertext,
         Scalar: DecomposableInto<u64>,
     {
+        operation_span!("scalar_min", self.num_bits_of(lhs));
         let mut tmp_lhs;
         let lhs = if lhs.block_carries_are_empty() {
             lhs
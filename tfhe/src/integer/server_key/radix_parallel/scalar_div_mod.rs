@@ -14,6 +14,7 @@ use crate::core_crypto::prelude::{CastFrom, CastInto, Numeric, SignedNumeric, Un
 use crate::integer::bigint::{StaticUnsignedBigInt, I1024, I2048, I4096, U1024, U2048, U4096};
 use crate::integer::block_decomposition::DecomposableInto;
 use crate::integer::ciphertext::{RadixCiphertext, SignedRadixCiphertext};
+use crate::integer::server_key::operation_span;
 use crate::integer::server_key::radix::scalar_mul::ScalarMultiplier;
 use crate::integer::{IntegerCiphertext, ServerKey, I256, I512, U256, U512};
 use std::ops::{Add, AddAssign, BitAnd, Div, Mul, Neg, Shl, Shr, Sub};
@@ -628,6 +629,7 @@ impl ServerKey {
         T: SignedReciprocable + ScalarMultiplier,
         <<T as SignedReciprocable>::Unsigned as Reciprocable>::DoublePrecision: Send,
     {
+        operation_span!("signed_scalar_div_rem", self.num_bits_of(numerator));
         if numerator.block_carries_are_empty() {
             self.unchecked_signed_scalar_div_rem_parallelized(numerator, divisor)
         } else {
@@ -676,6 +678,7 @@ impl ServerKey {
         T: SignedReciprocable,
         <<T as SignedReciprocable>::Unsigned as Reciprocable>::DoublePrecision: Send,
     {
+        operation_span!("signed_scalar_div", self.num_bits_of(numerator));
         let mut result = numerator.clone();
         self.signed_scalar_div_assign_parallelized(&mut result, divisor);
         result
@@ -697,6 +700,7 @@ impl ServerKey {
         T: SignedReciprocable + ScalarMultiplier,
         <<T as SignedReciprocable>::Unsigned as Reciprocable>::DoublePrecision: Send,
     {
+        operation_span!("signed_scalar_rem", self.num_bits_of(numerator));
         let mut result = numerator.clone();
         self.signed_scalar_rem_assign_parallelized(&mut result, divisor);
         result
@@ -1023,6 +1027,7 @@ impl ServerKey {
     where
         T: Reciprocable + DecomposableInto<u8>,
     {
+        operation_span!("scalar_div", self.num_bits_of(numerator));
         let mut result = numerator.clone();
         self.scalar_div_assign_parallelized(&mut result, divisor);
         result
@@ -1068,6 +1073,7 @@ impl ServerKey {
     where
         T: Reciprocable + ScalarMultiplier + DecomposableInto<u8>,
     {
+        operation_span!("scalar_rem", self.num_bits_of(numerator));
         let mut result = numerator.clone();
         self.scalar_rem_assign_parallelized(&mut result, divisor);
         result
@@ -1110,6 +1116,7 @@ impl ServerKey {
     where
         T: Reciprocable + ScalarMultiplier + DecomposableInto<u8>,
     {
+        operation_span!("scalar_div_rem", self.num_bits_of(numerator));
         if numerator.block_carries_are_empty() {
             self.unchecked_scalar_div_rem_parallelized(numerator, divisor)
         } else {
@@ -1,5 +1,6 @@
 use crate::integer::block_decomposition::{BlockDecomposer, DecomposableInto};
 use crate::integer::ciphertext::IntegerRadixCiphertext;
+use crate::integer::server_key::operation_span;
 use crate::integer::server_key::radix::scalar_mul::ScalarMultiplier;
 use crate::integer::ServerKey;
 use rayon::prelude::*;
@@ -173,6 +174,7 @@ impl ServerKey {
         T: IntegerRadixCiphertext,
         Scalar: ScalarMultiplier + DecomposableInto<u8>,
     {
+        operation_span!("scalar_mul", self.num_bits_of(ct));
         let mut ct_res = ct.clone();
         self.scalar_mul_assign_parallelized(&mut ct_res, scalar);
         ct_res
@@ -1,5 +1,6 @@
 use crate::core_crypto::prelude::CastFrom;
 use crate::integer::ciphertext::IntegerRadixCiphertext;
+use crate::integer::server_key::operation_span;
 use crate::integer::ServerKey;
 use rayon::prelude::*;
 
@@ -132,6 +133,7 @@ impl ServerKey {
         T: IntegerRadixCiphertext,
         u64: CastFrom<Scalar>,
     {
+        operation_span!("scalar_rotate_right", self.num_bits_of(ct_right));
         let mut result = ct_right.clone();
         self.scalar_rotate_right_assign_parallelized(&mut result, n);
         result
@@ -463,6 +465,7 @@ impl ServerKey {
         T: IntegerRadixCiphertext,
         u64: CastFrom<Scalar>,
     {
+        operation_span!("scalar_rotate_left", self.num_bits_of(ct_left));
         let mut result = ct_left.clone();
         self.scalar_rotate_left_assign_parallelized(&mut result, n);
         result
@@ -1,6 +1,7 @@
 use crate::core_crypto::commons::utils::izip;
 use crate::core_crypto::prelude::CastFrom;
 use crate::integer::ciphertext::IntegerRadixCiphertext;
+use crate::integer::server_key::operation_span;
 use crate::integer::ServerKey;
 use rayon::prelude::*;
 
@@ -397,6 +398,7 @@ impl ServerKey {
         T: IntegerRadixCiphertext,
         u64: CastFrom<Scalar>,
     {
+        operation_span!("scalar_right_shift", self.num_bits_of(ct));
         let mut result = ct.clone();
         self.scalar_right_shift_assign_parallelized(&mut result, shift);
         result
@@ -671,6 +673,7 @@ impl ServerKey {
         T: IntegerRadixCiphertext,
         u64: CastFrom<Scalar>,
     {
+        operation_span!("scalar_left_shift", self.num_bits_of(ct_left));
         let mut result = ct_left.clone();
         self.scalar_left_shift_assign_parallelized(&mut result, shift);
         result
@@ -1,6 +1,7 @@
 use crate::core_crypto::prelude::{Cleartext, SignedNumeric, UnsignedNumeric};
 use crate::integer::block_decomposition::{BlockDecomposer, DecomposableInto};
 use crate::integer::ciphertext::IntegerRadixCiphertext;
+use crate::integer::server_key::operation_span;
 use crate::integer::server_key::radix::neg::NegatedDegreeIter;
 use crate::integer::server_key::radix::scalar_sub::TwosComplementNegation;
 use crate::integer::{BooleanBlock, CheckError, RadixCiphertext, ServerKey, SignedRadixCiphertext};
@@ -94,6 +95,7 @@ impl ServerKey {
         T: IntegerRadixCiphertext,
         Scalar: TwosComplementNegation + DecomposableInto<u8>,
     {
+        operation_span!("scalar_sub", self.num_bits_of(ct));
         let mut ct_res = ct.clone();
         self.scalar_sub_assign_parallelized(&mut ct_res, scalar);
         ct_res
@@ -176,6 +178,7 @@ impl ServerKey {
         Scalar: DecomposableInto<u8>,
         T: IntegerRadixCiphertext,
     {
+        operation_span!("left_scalar_sub", self.num_bits_of(rhs));
         if rhs.block_carries_are_empty() {
             // a - b <=> a + (-b) <=> a + (!b + 1) <=> !b + a + 1
             let mut flipped_ct = self.bitnot(rhs);
@@ -271,6 +274,7 @@ impl ServerKey {
                 lhs_b.noise_level() + borrow.noise_level(),
                 self.key.max_noise_level,
             );
+            lhs_b.merge_pbs_depth(&borrow);
 
             borrow.clone_from(lhs_b);
 
@@ -401,6 +405,7 @@ impl ServerKey {
                             block.noise_level() + simulator.noise_level(),
                             self.key.max_noise_level,
                         );
+                        block.merge_pbs_depth(simulator);
                         self.key.unchecked_scalar_add_assign(block, 1);
                     }
                 });
@@ -434,6 +439,7 @@ impl ServerKey {
                             block.noise_level() + borrow.noise_level(),
                             self.key.max_noise_level,
                         );
+                        block.merge_pbs_depth(borrow);
 
                         let lut = if i % 2 == 0 {
                             &extract_message_low_block_mut
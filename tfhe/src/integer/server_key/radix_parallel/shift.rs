@@ -1,4 +1,5 @@
 use crate::integer::ciphertext::{IntegerRadixCiphertext, RadixCiphertext};
+use crate::integer::server_key::operation_span;
 use crate::integer::server_key::radix_parallel::bit_extractor::BitExtractor;
 use crate::integer::ServerKey;
 use rayon::prelude::*;
@@ -154,6 +155,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("right_shift", self.num_bits_of(ct));
         let mut ct_res = ct.clone();
         self.right_shift_assign_parallelized(&mut ct_res, shift);
         ct_res
@@ -302,6 +304,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("left_shift", self.num_bits_of(ct));
         let mut ct_res = ct.clone();
         self.left_shift_assign_parallelized(&mut ct_res, shift);
         ct_res
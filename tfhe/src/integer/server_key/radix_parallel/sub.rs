@@ -1,4 +1,5 @@
 use crate::integer::ciphertext::IntegerRadixCiphertext;
+use crate::integer::server_key::operation_span;
 use crate::integer::server_key::radix_parallel::add::CarryPropagationAlgorithm;
 use crate::integer::server_key::radix_parallel::OutputFlag;
 use crate::integer::{
@@ -145,6 +146,7 @@ impl ServerKey {
     where
         T: IntegerRadixCiphertext,
     {
+        operation_span!("sub", self.num_bits_of(ctxt_left));
         let mut ct_res = ctxt_left.clone();
         self.sub_assign_parallelized(&mut ct_res, ctxt_right);
         ct_res
@@ -427,6 +429,7 @@ impl ServerKey {
                         block.noise_level() + simulator.noise_level(),
                         self.key.max_noise_level,
                     );
+                    block.merge_pbs_depth(simulator);
                     self.key.unchecked_scalar_add_assign(block, 1);
                 });
 
@@ -459,6 +462,7 @@ impl ServerKey {
                         block.noise_level() + borrow.noise_level(),
                         self.key.max_noise_level,
                     );
+                    block.merge_pbs_depth(borrow);
 
                     self.key
                         .apply_lookup_table_assign(block, &message_extract_lut)
@@ -648,6 +652,7 @@ impl ServerKey {
                 lhs_block.noise_level() + borrow.noise_level(),
                 self.key.max_noise_level,
             );
+            lhs_block.merge_pbs_depth(&borrow);
             let (msg, new_borrow) = rayon::join(
                 || self.key.message_extract(lhs_block),
                 || self.key.apply_lookup_table(lhs_block, &compute_borrow_lut),
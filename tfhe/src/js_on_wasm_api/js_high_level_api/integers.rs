@@ -221,6 +221,17 @@ macro_rules! create_wrapper_type_non_native_type (
                 Ok(buffer)
             }
 
+            #[wasm_bindgen]
+            pub fn safe_serialize_with_compression(&self, serialized_size_limit: u64) -> Result<Vec<u8>, JsError> {
+                let mut buffer = vec![];
+                catch_panic_result(|| crate::safe_serialization::SerializationConfig::new(serialized_size_limit)
+                    .enable_compression(crate::safe_serialization::CompressionCodec::Lz4)
+                    .serialize_into(&self.0, &mut buffer)
+                    .map_err(into_js_error))?;
+
+                Ok(buffer)
+            }
+
             #[wasm_bindgen]
             pub fn safe_deserialize(buffer: &[u8], serialized_size_limit: u64) -> Result<$type_name, JsError> {
                 catch_panic_result(|| {
@@ -285,6 +296,17 @@ macro_rules! create_wrapper_type_non_native_type (
                 Ok(buffer)
             }
 
+            #[wasm_bindgen]
+            pub fn safe_serialize_with_compression(&self, serialized_size_limit: u64) -> Result<Vec<u8>, JsError> {
+                let mut buffer = vec![];
+                catch_panic_result(|| crate::safe_serialization::SerializationConfig::new(serialized_size_limit)
+                    .enable_compression(crate::safe_serialization::CompressionCodec::Lz4)
+                    .serialize_into(&self.0, &mut buffer)
+                    .map_err(into_js_error))?;
+
+                Ok(buffer)
+            }
+
             #[wasm_bindgen]
             pub fn safe_deserialize(buffer: &[u8], serialized_size_limit: u64) -> Result<$compressed_type_name, JsError> {
                 catch_panic_result(|| {
@@ -739,6 +761,17 @@ macro_rules! create_wrapper_type_that_has_native_type (
                 Ok(buffer)
             }
 
+            #[wasm_bindgen]
+            pub fn safe_serialize_with_compression(&self, serialized_size_limit: u64) -> Result<Vec<u8>, JsError> {
+                let mut buffer = vec![];
+                catch_panic_result(|| crate::safe_serialization::SerializationConfig::new(serialized_size_limit)
+                    .enable_compression(crate::safe_serialization::CompressionCodec::Lz4)
+                    .serialize_into(&self.0, &mut buffer)
+                    .map_err(into_js_error))?;
+
+                Ok(buffer)
+            }
+
             #[wasm_bindgen]
             pub fn safe_deserialize(buffer: &[u8], serialized_size_limit: u64) -> Result<$type_name, JsError> {
                 catch_panic_result(|| {
@@ -800,6 +833,17 @@ macro_rules! create_wrapper_type_that_has_native_type (
                 Ok(buffer)
             }
 
+            #[wasm_bindgen]
+            pub fn safe_serialize_with_compression(&self, serialized_size_limit: u64) -> Result<Vec<u8>, JsError> {
+                let mut buffer = vec![];
+                catch_panic_result(|| crate::safe_serialization::SerializationConfig::new(serialized_size_limit)
+                    .enable_compression(crate::safe_serialization::CompressionCodec::Lz4)
+                    .serialize_into(&self.0, &mut buffer)
+                    .map_err(into_js_error))?;
+
+                Ok(buffer)
+            }
+
             #[wasm_bindgen]
             pub fn safe_deserialize(buffer: &[u8], serialized_size_limit: u64) -> Result<$compressed_type_name, JsError> {
                 catch_panic_result(|| {
@@ -1093,6 +1137,22 @@ impl CompactCiphertextList {
         Ok(buffer)
     }
 
+    #[wasm_bindgen]
+    pub fn safe_serialize_with_compression(
+        &self,
+        serialized_size_limit: u64,
+    ) -> Result<Vec<u8>, JsError> {
+        let mut buffer = vec![];
+        catch_panic_result(|| {
+            crate::safe_serialization::SerializationConfig::new(serialized_size_limit)
+                .enable_compression(crate::safe_serialization::CompressionCodec::Lz4)
+                .serialize_into(&self.0, &mut buffer)
+                .map_err(into_js_error)
+        })?;
+
+        Ok(buffer)
+    }
+
     #[wasm_bindgen]
     pub fn safe_deserialize(
         buffer: &[u8],
@@ -1189,6 +1249,22 @@ impl ProvenCompactCiphertextList {
         Ok(buffer)
     }
 
+    #[wasm_bindgen]
+    pub fn safe_serialize_with_compression(
+        &self,
+        serialized_size_limit: u64,
+    ) -> Result<Vec<u8>, JsError> {
+        let mut buffer = vec![];
+        catch_panic_result(|| {
+            crate::safe_serialization::SerializationConfig::new(serialized_size_limit)
+                .enable_compression(crate::safe_serialization::CompressionCodec::Lz4)
+                .serialize_into(&self.0, &mut buffer)
+                .map_err(into_js_error)
+        })?;
+
+        Ok(buffer)
+    }
+
     #[wasm_bindgen]
     pub fn safe_deserialize(
         buffer: &[u8],
@@ -58,6 +58,22 @@ impl TfheClientKey {
         Ok(buffer)
     }
 
+    #[wasm_bindgen]
+    pub fn safe_serialize_with_compression(
+        &self,
+        serialized_size_limit: u64,
+    ) -> Result<Vec<u8>, JsError> {
+        let mut buffer = vec![];
+        catch_panic_result(|| {
+            crate::safe_serialization::SerializationConfig::new(serialized_size_limit)
+                .enable_compression(crate::safe_serialization::CompressionCodec::Lz4)
+                .serialize_into(&self.0, &mut buffer)
+                .map_err(into_js_error)
+        })?;
+
+        Ok(buffer)
+    }
+
     #[wasm_bindgen]
     pub fn safe_deserialize(
         buffer: &[u8],
@@ -110,6 +126,22 @@ impl TfheCompressedServerKey {
         Ok(buffer)
     }
 
+    #[wasm_bindgen]
+    pub fn safe_serialize_with_compression(
+        &self,
+        serialized_size_limit: u64,
+    ) -> Result<Vec<u8>, JsError> {
+        let mut buffer = vec![];
+        catch_panic_result(|| {
+            crate::safe_serialization::SerializationConfig::new(serialized_size_limit)
+                .enable_compression(crate::safe_serialization::CompressionCodec::Lz4)
+                .serialize_into(&self.0, &mut buffer)
+                .map_err(into_js_error)
+        })?;
+
+        Ok(buffer)
+    }
+
     #[wasm_bindgen]
     pub fn safe_deserialize(
         buffer: &[u8],
@@ -189,6 +221,22 @@ impl TfhePublicKey {
         Ok(buffer)
     }
 
+    #[wasm_bindgen]
+    pub fn safe_serialize_with_compression(
+        &self,
+        serialized_size_limit: u64,
+    ) -> Result<Vec<u8>, JsError> {
+        let mut buffer = vec![];
+        catch_panic_result(|| {
+            crate::safe_serialization::SerializationConfig::new(serialized_size_limit)
+                .enable_compression(crate::safe_serialization::CompressionCodec::Lz4)
+                .serialize_into(&self.0, &mut buffer)
+                .map_err(into_js_error)
+        })?;
+
+        Ok(buffer)
+    }
+
     #[wasm_bindgen]
     pub fn safe_deserialize(
         buffer: &[u8],
@@ -245,6 +293,22 @@ impl TfheCompressedPublicKey {
         Ok(buffer)
     }
 
+    #[wasm_bindgen]
+    pub fn safe_serialize_with_compression(
+        &self,
+        serialized_size_limit: u64,
+    ) -> Result<Vec<u8>, JsError> {
+        let mut buffer = vec![];
+        catch_panic_result(|| {
+            crate::safe_serialization::SerializationConfig::new(serialized_size_limit)
+                .enable_compression(crate::safe_serialization::CompressionCodec::Lz4)
+                .serialize_into(&self.0, &mut buffer)
+                .map_err(into_js_error)
+        })?;
+
+        Ok(buffer)
+    }
+
     #[wasm_bindgen]
     pub fn safe_deserialize(
         buffer: &[u8],
@@ -296,6 +360,22 @@ impl TfheCompactPublicKey {
         Ok(buffer)
     }
 
+    #[wasm_bindgen]
+    pub fn safe_serialize_with_compression(
+        &self,
+        serialized_size_limit: u64,
+    ) -> Result<Vec<u8>, JsError> {
+        let mut buffer = vec![];
+        catch_panic_result(|| {
+            crate::safe_serialization::SerializationConfig::new(serialized_size_limit)
+                .enable_compression(crate::safe_serialization::CompressionCodec::Lz4)
+                .serialize_into(&self.0, &mut buffer)
+                .map_err(into_js_error)
+        })?;
+
+        Ok(buffer)
+    }
+
     #[wasm_bindgen]
     pub fn safe_deserialize(
         buffer: &[u8],
@@ -366,6 +446,22 @@ impl TfheCompressedCompactPublicKey {
         Ok(buffer)
     }
 
+    #[wasm_bindgen]
+    pub fn safe_serialize_with_compression(
+        &self,
+        serialized_size_limit: u64,
+    ) -> Result<Vec<u8>, JsError> {
+        let mut buffer = vec![];
+        catch_panic_result(|| {
+            crate::safe_serialization::SerializationConfig::new(serialized_size_limit)
+                .enable_compression(crate::safe_serialization::CompressionCodec::Lz4)
+                .serialize_into(&self.0, &mut buffer)
+                .map_err(into_js_error)
+        })?;
+
+        Ok(buffer)
+    }
+
     #[wasm_bindgen]
     pub fn safe_deserialize(
         buffer: &[u8],
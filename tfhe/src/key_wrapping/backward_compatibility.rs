@@ -0,0 +1,13 @@
+use tfhe_versionable::VersionsDispatch;
+
+use super::{KeyDerivation, WrappedKey};
+
+#[derive(VersionsDispatch)]
+pub enum KeyDerivationVersions {
+    V0(KeyDerivation),
+}
+
+#[derive(VersionsDispatch)]
+pub enum WrappedKeyVersions {
+    V0(WrappedKey),
+}
@@ -0,0 +1,626 @@
+//! Encryption of secret keys at rest.
+//!
+//! Secret keys (such as the [`ClientKey`](crate::ClientKey)) hold their secret material in the
+//! clear when they are serialized. This module allows to export them encrypted with
+//! XChaCha20-Poly1305, using either a random [`KeyEncryptionKey`] or a key derived from a password
+//! with Argon2id.
+//!
+//! The encrypted key is stored in a [`WrappedKey`], that is versioned and can be serialized with
+//! the [`safe_serialization`](crate::safe_serialization) module. The [`EncryptedExport`] trait
+//! provides shortcuts to directly export and import the keys.
+//!
+//! # Example
+//!
+//! ```rust
+//! use tfhe::key_wrapping::{Argon2Params, EncryptedExport, WrappingKey};
+//! use tfhe::{ClientKey, ConfigBuilder};
+//!
+//! let cks = ClientKey::generate(ConfigBuilder::default());
+//!
+//! // Low cost parameters to keep the example fast, the defaults should be preferred
+//! let params = Argon2Params::new(1024, 1, 1);
+//! let key = WrappingKey::password_with_params("correct horse battery staple", params);
+//!
+//! let mut buffer = Vec::new();
+//! cks.export_encrypted(&mut buffer, key, 1 << 30).unwrap();
+//!
+//! let _imported = ClientKey::import_encrypted(buffer.as_slice(), key, 1 << 30).unwrap();
+//!
+//! // The wrong password is rejected
+//! let wrong_key = WrappingKey::password("Tr0ub4dor&3");
+//! assert!(ClientKey::import_encrypted(buffer.as_slice(), wrong_key, 1 << 30).is_err());
+//! ```
+
+mod backward_compatibility;
+
+use std::fmt::{Debug, Display};
+use std::io::{Read, Write};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand_core::{OsRng, RngCore};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tfhe_versionable::{Unversionize, Versionize};
+
+use crate::named::Named;
+use crate::safe_serialization::{
+    DeserializationConfig, SafeDeserializationError, SerializationConfig,
+};
+use backward_compatibility::{KeyDerivationVersions, WrappedKeyVersions};
+
+/// Size in bytes of a [`KeyEncryptionKey`]
+pub const KEY_ENCRYPTION_KEY_SIZE: usize = 32;
+
+/// Size in bytes of the XChaCha20-Poly1305 nonces
+pub(crate) const NONCE_SIZE: usize = 24;
+
+/// Size in bytes of the salt used to derive keys from passwords
+const SALT_SIZE: usize = 16;
+
+/// Returns bytes drawn from the operating system's random number generator.
+///
+/// Nonces, salts and keys must never come from [`crate::core_crypto::seeders::new_seeder`]:
+/// it returns the registered custom seeder if any, which may be deterministic.
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
+/// Symmetric key used to encrypt secret keys at rest.
+///
+/// Keys are encrypted with XChaCha20-Poly1305.
+#[derive(Clone)]
+pub struct KeyEncryptionKey([u8; KEY_ENCRYPTION_KEY_SIZE]);
+
+impl KeyEncryptionKey {
+    /// Creates a key from raw bytes
+    pub fn new(bytes: [u8; KEY_ENCRYPTION_KEY_SIZE]) -> Self {
+        Self(bytes)
+    }
+
+    /// Generates a new random key from the operating system's random number generator
+    pub fn generate() -> Self {
+        Self(random_bytes())
+    }
+
+    /// Returns the raw bytes of the key
+    pub fn as_bytes(&self) -> &[u8; KEY_ENCRYPTION_KEY_SIZE] {
+        &self.0
+    }
+
+    /// Encrypts `plaintext` and authenticates it alongside `associated_data`, returns the random
+    /// nonce that was used and the ciphertext
+    pub(crate) fn encrypt(
+        &self,
+        plaintext: &[u8],
+        associated_data: &[u8],
+    ) -> Option<([u8; NONCE_SIZE], Vec<u8>)> {
+        let nonce = random_bytes::<NONCE_SIZE>();
+        let ciphertext = self
+            .cipher()
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad: associated_data,
+                },
+            )
+            .ok()?;
+        Some((nonce, ciphertext))
+    }
+
+    /// Decrypts data produced by [`Self::encrypt`], returns `None` if the ciphertext or the
+    /// associated data have been modified or if they were encrypted with another key
+    pub(crate) fn decrypt(
+        &self,
+        nonce: &[u8; NONCE_SIZE],
+        ciphertext: &[u8],
+        associated_data: &[u8],
+    ) -> Option<Vec<u8>> {
+        self.cipher()
+            .decrypt(
+                XNonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: associated_data,
+                },
+            )
+            .ok()
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(&self.0.into())
+    }
+}
+
+// The key should not end up in logs
+impl Debug for KeyEncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("KeyEncryptionKey").finish_non_exhaustive()
+    }
+}
+
+impl Drop for KeyEncryptionKey {
+    fn drop(&mut self) {
+        self.0.fill(0);
+    }
+}
+
+/// Cost parameters of the Argon2id key derivation function
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Argon2Params {
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+}
+
+impl Default for Argon2Params {
+    /// The default parameters follow the recommendations of OWASP: 19MiB of memory, 2 iterations
+    /// and 1 degree of parallelism
+    fn default() -> Self {
+        Self {
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl Argon2Params {
+    /// Creates parameters with the given memory size in KiB, number of iterations and degree of
+    /// parallelism
+    pub fn new(memory_kib: u32, iterations: u32, parallelism: u32) -> Self {
+        Self {
+            memory_kib,
+            iterations,
+            parallelism,
+        }
+    }
+}
+
+/// The secret used to encrypt or decrypt a [`WrappedKey`]
+#[derive(Copy, Clone)]
+pub enum WrappingKey<'a> {
+    /// A key derived from a password with Argon2id. The parameters are only used when exporting
+    /// a key, when importing the ones that were used for the export are read from the
+    /// [`WrappedKey`].
+    Password {
+        password: &'a str,
+        params: Argon2Params,
+    },
+    /// A random key that is directly used for encryption
+    EncryptionKey(&'a KeyEncryptionKey),
+}
+
+impl<'a> WrappingKey<'a> {
+    /// Uses a password with the default [`Argon2Params`]
+    pub fn password(password: &'a str) -> Self {
+        Self::password_with_params(password, Argon2Params::default())
+    }
+
+    /// Uses a password with custom [`Argon2Params`]
+    pub fn password_with_params(password: &'a str, params: Argon2Params) -> Self {
+        Self::Password { password, params }
+    }
+}
+
+impl<'a> From<&'a KeyEncryptionKey> for WrappingKey<'a> {
+    fn from(value: &'a KeyEncryptionKey) -> Self {
+        Self::EncryptionKey(value)
+    }
+}
+
+// The password should not end up in logs
+impl Debug for WrappingKey<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Password { params, .. } => f
+                .debug_struct("Password")
+                .field("params", params)
+                .finish_non_exhaustive(),
+            Self::EncryptionKey(key) => f.debug_tuple("EncryptionKey").field(key).finish(),
+        }
+    }
+}
+
+/// How the encryption key of a [`WrappedKey`] is obtained
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Versionize)]
+#[versionize(KeyDerivationVersions)]
+pub enum KeyDerivation {
+    /// A [`KeyEncryptionKey`] is used as is
+    None,
+    /// The key is derived from a password
+    Argon2id {
+        salt: Vec<u8>,
+        memory_kib: u32,
+        iterations: u32,
+        parallelism: u32,
+    },
+}
+
+impl KeyDerivation {
+    fn derive_key(&self, key: WrappingKey<'_>) -> Result<KeyEncryptionKey, KeyWrappingError> {
+        match (self, key) {
+            (Self::None, WrappingKey::EncryptionKey(key)) => Ok(key.clone()),
+            (
+                Self::Argon2id {
+                    salt,
+                    memory_kib,
+                    iterations,
+                    parallelism,
+                },
+                WrappingKey::Password { password, .. },
+            ) => {
+                let params = Params::new(
+                    *memory_kib,
+                    *iterations,
+                    *parallelism,
+                    Some(KEY_ENCRYPTION_KEY_SIZE),
+                )
+                .map_err(|err| KeyWrappingError::KeyDerivation(err.to_string()))?;
+
+                let mut bytes = [0u8; KEY_ENCRYPTION_KEY_SIZE];
+                Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+                    .hash_password_into(password.as_bytes(), salt, &mut bytes)
+                    .map_err(|err| KeyWrappingError::KeyDerivation(err.to_string()))?;
+
+                let key = KeyEncryptionKey(bytes);
+                bytes.fill(0);
+                Ok(key)
+            }
+            (Self::None, WrappingKey::Password { .. }) => Err(KeyWrappingError::WrongKeyKind(
+                "the key was encrypted with a KeyEncryptionKey but a password was provided"
+                    .to_string(),
+            )),
+            (Self::Argon2id { .. }, WrappingKey::EncryptionKey(_)) => {
+                Err(KeyWrappingError::WrongKeyKind(
+                    "the key was encrypted with a password but a KeyEncryptionKey was provided"
+                        .to_string(),
+                ))
+            }
+        }
+    }
+}
+
+/// Errors that can occur when wrapping or unwrapping a key
+#[derive(Debug)]
+pub enum KeyWrappingError {
+    /// The key could not be serialized
+    Serialization(bincode::Error),
+    /// The wrapped key could not be deserialized
+    Deserialization(SafeDeserializationError),
+    /// The wrapped key holds another type of object
+    ObjectMismatch { expected: String, found: String },
+    /// The kind of secret provided does not match the one used to encrypt the key
+    WrongKeyKind(String),
+    /// The encryption key could not be derived from the password
+    KeyDerivation(String),
+    /// The wrapped key could not be decrypted, either because the data has been corrupted or
+    /// because it was encrypted with another key
+    DecryptionFailed,
+}
+
+impl Display for KeyWrappingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Serialization(err) => write!(f, "Failed to serialize key: {err}"),
+            Self::Deserialization(err) => write!(f, "Failed to deserialize key: {err}"),
+            Self::ObjectMismatch { expected, found } => {
+                write!(f, "Expected a wrapped {expected}, found a wrapped {found}")
+            }
+            Self::WrongKeyKind(msg) => write!(f, "Wrong kind of wrapping key: {msg}"),
+            Self::KeyDerivation(msg) => write!(f, "Failed to derive the encryption key: {msg}"),
+            Self::DecryptionFailed => write!(f, "Failed to decrypt the wrapped key"),
+        }
+    }
+}
+
+impl std::error::Error for KeyWrappingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Serialization(err) => Some(err),
+            Self::Deserialization(err) => Some(err),
+            Self::ObjectMismatch { .. }
+            | Self::WrongKeyKind(_)
+            | Self::KeyDerivation(_)
+            | Self::DecryptionFailed => None,
+        }
+    }
+}
+
+impl From<bincode::Error> for KeyWrappingError {
+    fn from(value: bincode::Error) -> Self {
+        Self::Serialization(value)
+    }
+}
+
+impl From<SafeDeserializationError> for KeyWrappingError {
+    fn from(value: SafeDeserializationError) -> Self {
+        Self::Deserialization(value)
+    }
+}
+
+/// A secret key encrypted with a [`WrappingKey`].
+///
+/// The object is serialized with [`safe_serialization`](crate::safe_serialization) before being
+/// encrypted, so it can be upgraded when it is unwrapped by a later version of *TFHE-rs*. The
+/// name of the object and the parameters of the key derivation are authenticated alongside the
+/// encrypted data.
+#[derive(Clone, Debug, Serialize, Deserialize, Versionize)]
+#[versionize(WrappedKeyVersions)]
+pub struct WrappedKey {
+    object_name: String,
+    derivation: KeyDerivation,
+    nonce: Vec<u8>,
+    ciphertext: Vec<u8>,
+}
+
+impl Named for WrappedKey {
+    const NAME: &'static str = "key_wrapping::WrappedKey";
+}
+
+impl WrappedKey {
+    /// Serializes then encrypts `object` with the given key
+    pub fn wrap<T: Serialize + Versionize + Named>(
+        object: &T,
+        key: WrappingKey<'_>,
+    ) -> Result<Self, KeyWrappingError> {
+        let derivation = match key {
+            WrappingKey::Password { params, .. } => KeyDerivation::Argon2id {
+                salt: random_bytes::<SALT_SIZE>().to_vec(),
+                memory_kib: params.memory_kib,
+                iterations: params.iterations,
+                parallelism: params.parallelism,
+            },
+            WrappingKey::EncryptionKey(_) => KeyDerivation::None,
+        };
+        let encryption_key = derivation.derive_key(key)?;
+
+        let mut serialized = Vec::new();
+        SerializationConfig::new_with_unlimited_size().serialize_into(object, &mut serialized)?;
+
+        let associated_data = Self::associated_data(T::NAME, &derivation)?;
+        let encrypted = encryption_key.encrypt(&serialized, &associated_data);
+        serialized.fill(0);
+
+        let (nonce, ciphertext) = encrypted.ok_or_else(|| {
+            KeyWrappingError::Serialization(Box::new(bincode::ErrorKind::Custom(
+                "failed to encrypt the key".to_string(),
+            )))
+        })?;
+
+        Ok(Self {
+            object_name: T::NAME.to_string(),
+            derivation,
+            nonce: nonce.to_vec(),
+            ciphertext,
+        })
+    }
+
+    /// Decrypts then deserializes the wrapped object.
+    ///
+    /// Objects larger than `serialized_size_limit` bytes are rejected.
+    pub fn unwrap<T: DeserializeOwned + Unversionize + Named>(
+        &self,
+        key: WrappingKey<'_>,
+        serialized_size_limit: u64,
+    ) -> Result<T, KeyWrappingError> {
+        if self.object_name != T::NAME
+            && !T::BACKWARD_COMPATIBILITY_ALIASES.contains(&self.object_name.as_str())
+        {
+            return Err(KeyWrappingError::ObjectMismatch {
+                expected: T::NAME.to_string(),
+                found: self.object_name.clone(),
+            });
+        }
+
+        let nonce: &[u8; NONCE_SIZE] = self
+            .nonce
+            .as_slice()
+            .try_into()
+            .map_err(|_| KeyWrappingError::DecryptionFailed)?;
+
+        let encryption_key = self.derivation.derive_key(key)?;
+        let associated_data = Self::associated_data(&self.object_name, &self.derivation)?;
+        let mut serialized = encryption_key
+            .decrypt(nonce, &self.ciphertext, &associated_data)
+            .ok_or(KeyWrappingError::DecryptionFailed)?;
+
+        let object = DeserializationConfig::new(serialized_size_limit)
+            .disable_conformance()
+            .deserialize_from_typed(serialized.as_slice());
+        serialized.fill(0);
+
+        Ok(object?)
+    }
+
+    /// Returns the name of the wrapped object
+    pub fn object_name(&self) -> &str {
+        &self.object_name
+    }
+
+    /// Returns how the encryption key is obtained
+    pub fn derivation(&self) -> &KeyDerivation {
+        &self.derivation
+    }
+
+    /// Returns true if the object was encrypted with a password
+    pub fn is_password_protected(&self) -> bool {
+        matches!(self.derivation, KeyDerivation::Argon2id { .. })
+    }
+
+    /// The data authenticated alongside the encrypted object. This prevents swapping the
+    /// derivation parameters or passing the object as another type.
+    fn associated_data(
+        object_name: &str,
+        derivation: &KeyDerivation,
+    ) -> Result<Vec<u8>, KeyWrappingError> {
+        Ok(bincode::serialize(&(Self::NAME, object_name, derivation))?)
+    }
+}
+
+/// Export and import of secret keys encrypted at rest.
+///
+/// This is a shortcut to [`WrappedKey`] serialized with the default configuration of the
+/// [`safe_serialization`](crate::safe_serialization) module.
+pub trait EncryptedExport:
+    Serialize + DeserializeOwned + Versionize + Unversionize + Named + Sized
+{
+    /// Encrypts the key with `key` and writes it to `writer`.
+    ///
+    /// This fails if the serialized object is larger than `serialized_size_limit` bytes.
+    fn export_encrypted(
+        &self,
+        writer: impl Write,
+        key: WrappingKey<'_>,
+        serialized_size_limit: u64,
+    ) -> Result<(), KeyWrappingError> {
+        let wrapped = WrappedKey::wrap(self, key)?;
+        SerializationConfig::new(serialized_size_limit).serialize_into(&wrapped, writer)?;
+        Ok(())
+    }
+
+    /// Reads a key written by [`Self::export_encrypted`] and decrypts it with `key`.
+    ///
+    /// This fails if the serialized object is larger than `serialized_size_limit` bytes.
+    fn import_encrypted(
+        reader: impl Read,
+        key: WrappingKey<'_>,
+        serialized_size_limit: u64,
+    ) -> Result<Self, KeyWrappingError> {
+        let wrapped: WrappedKey = DeserializationConfig::new(serialized_size_limit)
+            .disable_conformance()
+            .deserialize_from_typed(reader)?;
+        wrapped.unwrap(key, serialized_size_limit)
+    }
+}
+
+#[cfg(feature = "shortint")]
+impl EncryptedExport for crate::shortint::ClientKey {}
+
+#[cfg(feature = "integer")]
+impl EncryptedExport for crate::integer::ClientKey {}
+
+#[cfg(feature = "integer")]
+impl EncryptedExport for crate::integer::compression_keys::CompressionPrivateKeys {}
+
+#[cfg(feature = "integer")]
+impl EncryptedExport for crate::integer::noise_squashing::NoiseSquashingPrivateKey {}
+
+#[cfg(feature = "integer")]
+impl EncryptedExport for crate::ClientKey {}
+
+#[cfg(all(test, feature = "integer"))]
+mod test {
+    use super::*;
+    use crate::shortint::parameters::PARAM_MESSAGE_2_CARRY_2_KS_PBS;
+
+    fn test_params() -> Argon2Params {
+        Argon2Params::new(1024, 1, 1)
+    }
+
+    #[test]
+    fn wrap_unwrap_with_encryption_key() {
+        let cks = crate::shortint::ClientKey::new(PARAM_MESSAGE_2_CARRY_2_KS_PBS);
+        let kek = KeyEncryptionKey::generate();
+
+        let mut buffer = Vec::new();
+        cks.export_encrypted(&mut buffer, (&kek).into(), 1 << 30)
+            .unwrap();
+
+        let imported =
+            crate::shortint::ClientKey::import_encrypted(buffer.as_slice(), (&kek).into(), 1 << 30)
+                .unwrap();
+        assert_eq!(imported, cks);
+
+        let other_kek = KeyEncryptionKey::generate();
+        assert!(matches!(
+            crate::shortint::ClientKey::import_encrypted(
+                buffer.as_slice(),
+                (&other_kek).into(),
+                1 << 30
+            ),
+            Err(KeyWrappingError::DecryptionFailed)
+        ));
+
+        // The kind of secret is checked before decryption
+        assert!(matches!(
+            crate::shortint::ClientKey::import_encrypted(
+                buffer.as_slice(),
+                WrappingKey::password("password"),
+                1 << 30
+            ),
+            Err(KeyWrappingError::WrongKeyKind(_))
+        ));
+    }
+
+    #[test]
+    fn wrap_unwrap_with_password() {
+        let cks = crate::integer::ClientKey::new(PARAM_MESSAGE_2_CARRY_2_KS_PBS);
+        let key = WrappingKey::password_with_params("password", test_params());
+
+        let wrapped = WrappedKey::wrap(&cks, key).unwrap();
+        assert!(wrapped.is_password_protected());
+        assert_eq!(wrapped.object_name(), "integer::ClientKey");
+
+        let unwrapped: crate::integer::ClientKey = wrapped.unwrap(key, 1 << 30).unwrap();
+        assert_eq!(unwrapped, cks);
+
+        // The parameters used for the import are the ones of the wrapped key
+        let other_params = WrappingKey::password_with_params("password", Argon2Params::default());
+        assert!(wrapped
+            .unwrap::<crate::integer::ClientKey>(other_params, 1 << 30)
+            .is_ok());
+
+        let wrong_password = WrappingKey::password_with_params("passw0rd", test_params());
+        assert!(matches!(
+            wrapped.unwrap::<crate::integer::ClientKey>(wrong_password, 1 << 30),
+            Err(KeyWrappingError::DecryptionFailed)
+        ));
+
+        assert!(matches!(
+            wrapped.unwrap::<crate::shortint::ClientKey>(key, 1 << 30),
+            Err(KeyWrappingError::ObjectMismatch { .. })
+        ));
+
+        // The derivation parameters are authenticated
+        let mut tampered = wrapped.clone();
+        if let KeyDerivation::Argon2id { iterations, .. } = &mut tampered.derivation {
+            *iterations += 1;
+        }
+        assert!(matches!(
+            tampered.unwrap::<crate::integer::ClientKey>(key, 1 << 30),
+            Err(KeyWrappingError::DecryptionFailed)
+        ));
+
+        // The size limit applies to the unwrapped object
+        assert!(matches!(
+            wrapped.unwrap::<crate::integer::ClientKey>(key, 16),
+            Err(KeyWrappingError::Deserialization(_))
+        ));
+    }
+
+    #[test]
+    fn wrap_unwrap_hl_keys() {
+        use crate::prelude::*;
+        use crate::{ClientKey, ConfigBuilder, FheUint8};
+
+        let mut cks = ClientKey::generate(ConfigBuilder::default());
+        cks.tag_mut().set_u64(7);
+        let kek = KeyEncryptionKey::generate();
+
+        let mut buffer = Vec::new();
+        cks.export_encrypted(&mut buffer, (&kek).into(), 1 << 30)
+            .unwrap();
+        let imported =
+            ClientKey::import_encrypted(buffer.as_slice(), (&kek).into(), 1 << 30).unwrap();
+        assert_eq!(imported.tag(), cks.tag());
+
+        let ct = FheUint8::encrypt(42u8, &cks);
+        let decrypted: u8 = ct.decrypt(&imported);
+        assert_eq!(decrypted, 42);
+    }
+}
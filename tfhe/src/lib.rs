@@ -144,6 +144,11 @@ pub mod keycache;
 
 pub mod safe_serialization;
 
+#[cfg(feature = "key-wrapping")]
+pub mod key_wrapping;
+
+pub mod streaming_serialization;
+
 pub mod conformance;
 
 pub mod named;
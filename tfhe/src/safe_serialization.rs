@@ -6,18 +6,27 @@
 
 use std::borrow::Cow;
 use std::fmt::Display;
+use std::io::{Read, Write};
 
 use crate::conformance::ParameterSetConformant;
 use crate::named::Named;
 use bincode::Options;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use tfhe_versionable::{Unversionize, Versionize};
+#[cfg(feature = "integrity-check")]
+use sha3::{Digest, Sha3_256};
+use tfhe_versionable::{Unversionize, Versionize, VersionizeOwned};
 
 /// This is the global version of the serialization scheme that is used. This should be updated when
 /// the SerializationHeader is updated.
 const SERIALIZATION_VERSION: &str = "0.5";
 
+/// Version of the serialization scheme used when the object has an integrity trailer or is
+/// compressed. In this version, the header is followed by a [`HeaderExtension`] that flags the
+/// optional features used for this object. Objects serialized without any of these features still
+/// use [`SERIALIZATION_VERSION`] so that they can be loaded by previous versions of *TFHE-rs*.
+const SERIALIZATION_VERSION_WITH_EXTENSION: &str = "0.6";
+
 /// This is the version of the versioning scheme used to add backward compatibibility on tfhe-rs
 /// types. Similar to SERIALIZATION_VERSION, this number should be increased when the versioning
 /// scheme is upgraded.
@@ -31,6 +40,55 @@ const CRATE_VERSION: &str = concat!(
     env!("CARGO_PKG_VERSION_MINOR")
 );
 
+/// Size in bytes of the integrity trailer appended after the serialized object
+const INTEGRITY_TRAILER_SIZE: usize = 32;
+
+/// Size in bytes of the blocks processed by SHA3-256, used to pad the HMAC keys
+const SHA3_256_BLOCK_SIZE: usize = 136;
+
+/// Message of the errors returned when an integrity trailer is used without the
+/// `integrity-check` feature
+#[cfg(not(feature = "integrity-check"))]
+const MISSING_INTEGRITY_FEATURE_MESSAGE: &str =
+    "Integrity checks require the \"integrity-check\" feature of TFHE-rs";
+
+/// Size in bytes of the length of the compressed data, written before the compressed data
+const COMPRESSED_SIZE_PREFIX_SIZE: u64 = std::mem::size_of::<u64>() as u64;
+
+/// Error returned when an object can't be deserialized by a [`DeserializationConfig`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SafeDeserializationError {
+    /// The serialization header does not match the requested type or is not compatible with this
+    /// version of *TFHE-rs*
+    InvalidHeader(String),
+    /// The serialized data could not be decoded
+    Decoding(String),
+    /// The deserialized object is not conformant with the given parameter set
+    NonConformant(String),
+    /// The integrity trailer is missing or does not match the serialized data. This happens if the
+    /// data has been truncated or corrupted, or if it was authenticated with another key.
+    IntegrityCheckFailed(String),
+}
+
+impl Display for SafeDeserializationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidHeader(msg) | Self::Decoding(msg) | Self::NonConformant(msg) => {
+                write!(f, "{msg}")
+            }
+            Self::IntegrityCheckFailed(msg) => write!(f, "Integrity check failed: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for SafeDeserializationError {}
+
+impl From<SafeDeserializationError> for String {
+    fn from(value: SafeDeserializationError) -> Self {
+        value.to_string()
+    }
+}
+
 /// Tells if this serialized object is versioned or not
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
 enum SerializationVersioningMode {
@@ -69,6 +127,257 @@ impl SerializationVersioningMode {
     }
 }
 
+/// Algorithm used to compute the integrity trailer of a serialized object
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq)]
+enum IntegrityAlgorithm {
+    /// SHA3-256 digest of the header and the serialized object
+    Sha3_256,
+    /// HMAC-SHA3-256 of the header and the serialized object, keyed by a secret
+    HmacSha3_256,
+}
+
+impl Display for IntegrityAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sha3_256 => write!(f, "SHA3-256"),
+            Self::HmacSha3_256 => write!(f, "HMAC-SHA3-256"),
+        }
+    }
+}
+
+/// Codec used to compress serialized objects, see [`SerializationConfig::enable_compression`].
+///
+/// The codecs are only available if *TFHE-rs* is built with the corresponding cargo feature.
+/// Compressed objects can be deserialized without any specific configuration, as the codec is
+/// recorded in the serialization header.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CompressionCodec {
+    /// LZ4 frame format, requires the `lz4` feature. This codec is fast, which makes it usable for
+    /// large keys.
+    Lz4,
+}
+
+impl Display for CompressionCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Lz4 => write!(f, "lz4"),
+        }
+    }
+}
+
+impl CompressionCodec {
+    /// Returns the compressed bytes of the data written by `write_data`
+    fn compress(
+        self,
+        write_data: impl FnOnce(&mut dyn Write) -> bincode::Result<()>,
+    ) -> bincode::Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => {
+                let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+                write_data(&mut encoder)?;
+                encoder
+                    .finish()
+                    .map_err(|err| bincode::ErrorKind::Io(err.into()).into())
+            }
+            #[cfg(not(feature = "lz4"))]
+            Self::Lz4 => {
+                let _ = write_data;
+                Err(bincode::ErrorKind::Custom(self.missing_feature_message()).into())
+            }
+        }
+    }
+
+    /// Returns a reader over the decompressed content of `compressed`
+    fn decompressed_reader<'a>(
+        self,
+        compressed: &'a [u8],
+    ) -> Result<Box<dyn Read + 'a>, SafeDeserializationError> {
+        match self {
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => Ok(Box::new(lz4_flex::frame::FrameDecoder::new(compressed))),
+            #[cfg(not(feature = "lz4"))]
+            Self::Lz4 => {
+                let _ = compressed;
+                Err(SafeDeserializationError::Decoding(
+                    self.missing_feature_message(),
+                ))
+            }
+        }
+    }
+
+    #[cfg(not(feature = "lz4"))]
+    fn missing_feature_message(self) -> String {
+        format!("Compression with {self} requires the \"{self}\" feature of TFHE-rs")
+    }
+}
+
+/// Flags the optional features used by a serialized object, written after the header for objects
+/// serialized with [`SERIALIZATION_VERSION_WITH_EXTENSION`]
+#[derive(Serialize, Deserialize, Copy, Clone, Default)]
+struct HeaderExtension {
+    integrity_algorithm: Option<IntegrityAlgorithm>,
+    compression: Option<CompressionCodec>,
+}
+
+/// Secret key used to authenticate serialized objects with HMAC-SHA3-256.
+///
+/// The key is stored padded to the block size of SHA3-256, as done by the HMAC construction.
+#[derive(Copy, Clone)]
+#[cfg_attr(not(feature = "integrity-check"), allow(dead_code))]
+struct IntegrityKey([u8; SHA3_256_BLOCK_SIZE]);
+
+impl IntegrityKey {
+    fn new(key: &[u8]) -> Self {
+        let mut padded_key = [0u8; SHA3_256_BLOCK_SIZE];
+
+        if key.len() > SHA3_256_BLOCK_SIZE {
+            // Without the feature the key is never used, as computing the trailer fails
+            #[cfg(feature = "integrity-check")]
+            padded_key[..INTEGRITY_TRAILER_SIZE].copy_from_slice(&Sha3_256::digest(key));
+        } else {
+            padded_key[..key.len()].copy_from_slice(key);
+        }
+
+        Self(padded_key)
+    }
+
+    #[cfg(feature = "integrity-check")]
+    fn xor_pad(&self, pad: u8) -> [u8; SHA3_256_BLOCK_SIZE] {
+        self.0.map(|byte| byte ^ pad)
+    }
+}
+
+/// Integrity check performed on a serialized object
+#[derive(Copy, Clone)]
+#[cfg_attr(not(feature = "integrity-check"), allow(dead_code))]
+enum IntegrityCheck {
+    Digest,
+    Hmac(IntegrityKey),
+}
+
+impl IntegrityCheck {
+    fn algorithm(&self) -> IntegrityAlgorithm {
+        match self {
+            Self::Digest => IntegrityAlgorithm::Sha3_256,
+            Self::Hmac(_) => IntegrityAlgorithm::HmacSha3_256,
+        }
+    }
+
+    /// Returns a hasher computing the trailer, or an error if *TFHE-rs* is built without the
+    /// `integrity-check` feature
+    #[cfg(feature = "integrity-check")]
+    fn hasher(&self) -> Result<IntegrityHasher, String> {
+        match self {
+            Self::Digest => Ok(IntegrityHasher::Digest(Sha3_256::new())),
+            Self::Hmac(key) => {
+                let mut inner = Sha3_256::new();
+                inner.update(key.xor_pad(0x36));
+                Ok(IntegrityHasher::Hmac { inner, key: *key })
+            }
+        }
+    }
+
+    #[cfg(not(feature = "integrity-check"))]
+    fn hasher(&self) -> Result<IntegrityHasher, String> {
+        Err(MISSING_INTEGRITY_FEATURE_MESSAGE.to_string())
+    }
+}
+
+/// Incremental computation of an integrity trailer
+#[cfg_attr(not(feature = "integrity-check"), allow(dead_code))]
+enum IntegrityHasher {
+    #[cfg(feature = "integrity-check")]
+    Digest(Sha3_256),
+    #[cfg(feature = "integrity-check")]
+    Hmac { inner: Sha3_256, key: IntegrityKey },
+    /// Never constructed, the hasher can't be created without the `integrity-check` feature
+    #[cfg(not(feature = "integrity-check"))]
+    Unavailable(std::convert::Infallible),
+}
+
+impl IntegrityHasher {
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            #[cfg(feature = "integrity-check")]
+            Self::Digest(hasher) | Self::Hmac { inner: hasher, .. } => hasher.update(data),
+            #[cfg(not(feature = "integrity-check"))]
+            Self::Unavailable(never) => {
+                let _ = data;
+                match *never {}
+            }
+        }
+    }
+
+    fn finalize(self) -> [u8; INTEGRITY_TRAILER_SIZE] {
+        match self {
+            #[cfg(feature = "integrity-check")]
+            Self::Digest(hasher) => hasher.finalize().into(),
+            #[cfg(feature = "integrity-check")]
+            Self::Hmac { inner, key } => {
+                let mut outer = Sha3_256::new();
+                outer.update(key.xor_pad(0x5c));
+                outer.update(inner.finalize());
+                outer.finalize().into()
+            }
+            #[cfg(not(feature = "integrity-check"))]
+            Self::Unavailable(never) => match never {},
+        }
+    }
+}
+
+/// Writer that feeds the written bytes to an [`IntegrityHasher`]
+struct IntegrityWriter<W> {
+    inner: W,
+    hasher: IntegrityHasher,
+}
+
+impl<W: Write> Write for IntegrityWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Reader that feeds the read bytes to an [`IntegrityHasher`]
+struct IntegrityReader<R> {
+    inner: R,
+    hasher: IntegrityHasher,
+}
+
+impl<R: Read> Read for IntegrityReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.hasher.update(&buf[..read]);
+        Ok(read)
+    }
+}
+
+/// Reader that keeps a copy of the read bytes. This is used to include the header in the
+/// integrity check, since the algorithm is only known once the header has been read.
+pub(crate) struct RecordingReader<R> {
+    pub(crate) inner: R,
+    pub(crate) recorded: Vec<u8>,
+}
+
+impl<R: Read> Read for RecordingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.recorded.extend_from_slice(&buf[..read]);
+        Ok(read)
+    }
+}
+
+/// Compares two trailers without leaking the position of the first difference
+fn trailers_eq(lhs: &[u8; INTEGRITY_TRAILER_SIZE], rhs: &[u8; INTEGRITY_TRAILER_SIZE]) -> bool {
+    lhs.iter().zip(rhs).fold(0, |acc, (l, r)| acc | (l ^ r)) == 0
+}
+
 /// Header with global metadata about the serialized object. This help checking that we are not
 /// deserializing data that we can't handle.
 #[derive(Serialize, Deserialize)]
@@ -139,11 +448,14 @@ Please use the versioned serialization mode for backward compatibility.",
 }
 
 /// A configuration used to Serialize *TFHE-rs* objects. This configuration decides
-/// if the object will be versioned and holds the max byte size of the written data.
+/// if the object will be versioned, compressed, if an integrity trailer is appended and holds the
+/// max byte size of the written data.
 #[derive(Clone)]
 pub struct SerializationConfig {
     versioned: SerializationVersioningMode,
     serialized_size_limit: Option<u64>,
+    integrity_check: Option<IntegrityCheck>,
+    compression: Option<CompressionCodec>,
 }
 
 impl SerializationConfig {
@@ -155,6 +467,8 @@ impl SerializationConfig {
         Self {
             versioned: SerializationVersioningMode::versioned(),
             serialized_size_limit: Some(serialized_size_limit),
+            integrity_check: None,
+            compression: None,
         }
     }
 
@@ -163,6 +477,8 @@ impl SerializationConfig {
         Self {
             versioned: SerializationVersioningMode::versioned(),
             serialized_size_limit: None,
+            integrity_check: None,
+            compression: None,
         }
     }
 
@@ -190,15 +506,111 @@ impl SerializationConfig {
         }
     }
 
+    /// Appends a SHA3-256 digest of the header and the object after the serialized object. This
+    /// allows [`DeserializationConfig::deserialize_from`] to detect truncated or corrupted data.
+    ///
+    /// Objects serialized with an integrity trailer can't be loaded by versions of *TFHE-rs* that
+    /// don't support it.
+    ///
+    /// Serialization and deserialization return an error if *TFHE-rs* is built without the
+    /// `integrity-check` feature.
+    pub fn enable_integrity_check(self) -> Self {
+        Self {
+            integrity_check: Some(IntegrityCheck::Digest),
+            ..self
+        }
+    }
+
+    /// Appends an HMAC-SHA3-256 of the header and the object, keyed by `key`, after the serialized
+    /// object. On top of detecting corrupted data, this prevents anyone who does not know the key
+    /// from modifying it. The same key must be given to
+    /// [`DeserializationConfig::with_integrity_key`] to load the object.
+    ///
+    /// This also requires the `integrity-check` feature.
+    pub fn enable_authenticated_integrity_check(self, key: &[u8]) -> Self {
+        Self {
+            integrity_check: Some(IntegrityCheck::Hmac(IntegrityKey::new(key))),
+            ..self
+        }
+    }
+
+    /// Disables the integrity trailer of serialized objects
+    pub fn disable_integrity_check(self) -> Self {
+        Self {
+            integrity_check: None,
+            ..self
+        }
+    }
+
+    /// Compresses the serialized object with the given codec. The size limit applies both to the
+    /// compressed and to the uncompressed object.
+    ///
+    /// The codec is recorded in the header, so the object is decompressed transparently by
+    /// [`DeserializationConfig::deserialize_from`]. Compressed objects can't be loaded by versions
+    /// of *TFHE-rs* that don't support compression.
+    ///
+    /// Serialization and deserialization return an error if *TFHE-rs* is built without the feature
+    /// of the codec.
+    pub fn enable_compression(self, codec: CompressionCodec) -> Self {
+        Self {
+            compression: Some(codec),
+            ..self
+        }
+    }
+
+    /// Disables the compression of serialized objects
+    pub fn disable_compression(self) -> Self {
+        Self {
+            compression: None,
+            ..self
+        }
+    }
+
     /// Create a serialization header based on the current config
     fn create_header<T: Named>(&self) -> SerializationHeader {
-        match self.versioned {
+        let mut header = match self.versioned {
             SerializationVersioningMode::Versioned { .. } => {
                 SerializationHeader::new_versioned::<T>()
             }
             SerializationVersioningMode::Unversioned { .. } => {
                 SerializationHeader::new_unversioned::<T>()
             }
+        };
+
+        if self.compression.is_some() || self.integrity_check.is_some() {
+            header.header_version = Cow::Borrowed(SERIALIZATION_VERSION_WITH_EXTENSION);
+        }
+
+        header
+    }
+
+    /// Serializes the header and the data that follows it, depending on its version
+    fn serialize_header<T: Named>(&self) -> bincode::Result<Vec<u8>> {
+        let options = bincode::DefaultOptions::new().with_fixint_encoding();
+
+        let header = self.create_header::<T>();
+
+        let mut header_bytes = options.serialize(&header)?;
+
+        if header.header_version == SERIALIZATION_VERSION_WITH_EXTENSION {
+            let extension = HeaderExtension {
+                integrity_algorithm: self
+                    .integrity_check
+                    .map(|integrity_check| integrity_check.algorithm()),
+                compression: self.compression,
+            };
+            options.serialize_into(&mut header_bytes, &extension)?;
+        }
+
+        Ok(header_bytes)
+    }
+
+    /// Size of the integrity trailer written after the object
+    fn trailer_size(&self) -> u64 {
+        if self.integrity_check.is_some() {
+            INTEGRITY_TRAILER_SIZE as u64
+        } else {
+            0
         }
     }
 
@@ -206,24 +618,40 @@ impl SerializationConfig {
     ///
     /// The size is returned as a u64 to handle the serialization of large buffers under 32b
     /// architectures.
+    ///
+    /// If compression is enabled, the object has to be compressed to know its size.
     pub fn serialized_size<T: Serialize + Versionize + Named>(
         &self,
         object: &T,
     ) -> bincode::Result<u64> {
-        let options = bincode::DefaultOptions::new().with_fixint_encoding();
-
-        let header = self.create_header::<T>();
-
-        let header_size = options.serialized_size(&header)?;
-
-        let data_size = match self.versioned {
+        match self.versioned {
             SerializationVersioningMode::Versioned { .. } => {
-                options.serialized_size(&object.versionize())?
+                self.serialized_data_size::<T>(&object.versionize())
             }
-            SerializationVersioningMode::Unversioned { .. } => options.serialized_size(&object)?,
-        };
+            SerializationVersioningMode::Unversioned { .. } => {
+                self.serialized_data_size::<T>(object)
+            }
+        }
+    }
 
-        Ok(header_size + data_size)
+    /// Returns the size the object would take if serialized using
+    /// [`Self::serialize_into_version`]
+    pub fn serialized_size_of_version<T: Serialize + Versionize + VersionizeOwned + Named>(
+        &self,
+        object: &T,
+        version: usize,
+    ) -> bincode::Result<u64> {
+        match self.versioned {
+            SerializationVersioningMode::Versioned { .. } => {
+                match downgrade_object(object, version)? {
+                    Some(downgraded) => self.serialized_data_size::<T>(&downgraded),
+                    None => self.serialized_data_size::<T>(&object.versionize()),
+                }
+            }
+            SerializationVersioningMode::Unversioned { .. } => {
+                self.serialized_data_size::<T>(object)
+            }
+        }
     }
 
     /// Serializes an object into a [writer](std::io::Write), based on the current config.
@@ -231,47 +659,165 @@ impl SerializationConfig {
     pub fn serialize_into<T: Serialize + Versionize + Named>(
         self,
         object: &T,
-        mut writer: impl std::io::Write,
+        writer: impl std::io::Write,
     ) -> bincode::Result<()> {
-        let options = bincode::DefaultOptions::new()
-            .with_fixint_encoding()
-            .with_limit(0); // Force to explicitly set the limit for each serialization
+        match self.versioned {
+            SerializationVersioningMode::Versioned { .. } => {
+                self.serialize_data_with_trailer_into::<T>(&object.versionize(), writer)
+            }
+            SerializationVersioningMode::Unversioned { .. } => {
+                self.serialize_data_with_trailer_into::<T>(object, writer)
+            }
+        }
+    }
 
-        let header = self.create_header::<T>();
-        let header_size = options.with_no_limit().serialized_size(&header)?;
+    /// Same as [`Self::serialize_into`], but serializes the object as the version at index
+    /// `version` of its dispatch enum instead of the current one. This allows older versions of
+    /// *TFHE-rs* to load the object.
+    ///
+    /// Serialization returns an error if the type does not support being downgraded, or if the
+    /// object holds data that can't be represented in the target version. The version is ignored
+    /// if versioning is disabled. The target version should be readable by the consumer, which
+    /// also means that the integrity check and compression should not be enabled for versions of
+    /// *TFHE-rs* that don't support them.
+    pub fn serialize_into_version<T: Serialize + Versionize + VersionizeOwned + Named>(
+        self,
+        object: &T,
+        version: usize,
+        writer: impl std::io::Write,
+    ) -> bincode::Result<()> {
+        match self.versioned {
+            SerializationVersioningMode::Versioned { .. } => {
+                match downgrade_object(object, version)? {
+                    Some(downgraded) => {
+                        self.serialize_data_with_trailer_into::<T>(&downgraded, writer)
+                    }
+                    None => {
+                        self.serialize_data_with_trailer_into::<T>(&object.versionize(), writer)
+                    }
+                }
+            }
+            SerializationVersioningMode::Unversioned { .. } => {
+                self.serialize_data_with_trailer_into::<T>(object, writer)
+            }
+        }
+    }
 
-        if let Some(size_limit) = self.serialized_size_limit {
-            options
-                .with_limit(size_limit)
-                .serialize_into(&mut writer, &header)?;
+    /// Returns the size of the header, the serialized `data` of an object of type `T` and the
+    /// integrity trailer
+    ///
+    /// If compression is enabled, the data has to be compressed to know its size.
+    fn serialized_data_size<T: Named>(&self, data: &impl Serialize) -> bincode::Result<u64> {
+        let options = bincode::DefaultOptions::new().with_fixint_encoding();
 
-            let options = options.with_limit(size_limit - header_size);
+        let header_size = self.serialize_header::<T>()?.len() as u64;
 
-            match self.versioned {
-                SerializationVersioningMode::Versioned { .. } => {
-                    options.serialize_into(&mut writer, &object.versionize())?
-                }
-                SerializationVersioningMode::Unversioned { .. } => {
-                    options.serialize_into(&mut writer, &object)?
-                }
-            }
+        let data_size = if let Some(codec) = self.compression {
+            let compressed =
+                codec.compress(|writer| self.serialize_data_into(data, writer, None))?;
+            COMPRESSED_SIZE_PREFIX_SIZE + compressed.len() as u64
         } else {
-            let options = options.with_no_limit();
+            options.serialized_size(data)?
+        };
 
-            options.serialize_into(&mut writer, &header)?;
+        Ok(header_size + data_size + self.trailer_size())
+    }
 
-            match self.versioned {
-                SerializationVersioningMode::Versioned { .. } => {
-                    options.serialize_into(&mut writer, &object.versionize())?
-                }
-                SerializationVersioningMode::Unversioned { .. } => {
-                    options.serialize_into(&mut writer, &object)?
-                }
+    /// Serializes the header, the `data` of an object of type `T` and the integrity trailer
+    fn serialize_data_with_trailer_into<T: Named>(
+        &self,
+        data: &impl Serialize,
+        mut writer: impl std::io::Write,
+    ) -> bincode::Result<()> {
+        let Some(integrity_check) = self.integrity_check else {
+            return self.serialize_object_into::<T>(data, writer);
+        };
+
+        let mut integrity_writer = IntegrityWriter {
+            inner: &mut writer,
+            hasher: integrity_check
+                .hasher()
+                .map_err(bincode::ErrorKind::Custom)?,
+        };
+
+        self.serialize_object_into::<T>(data, &mut integrity_writer)?;
+
+        let trailer = integrity_writer.hasher.finalize();
+        writer.write_all(&trailer)?;
+
+        Ok(())
+    }
+
+    /// Serializes the header and the `data` of an object of type `T`, without the integrity
+    /// trailer
+    fn serialize_object_into<T: Named>(
+        &self,
+        data: &impl Serialize,
+        mut writer: impl std::io::Write,
+    ) -> bincode::Result<()> {
+        let header_bytes = self.serialize_header::<T>()?;
+
+        // Keep room for the header and the integrity trailer, which are written outside of the
+        // size limited bincode serializer
+        let data_size_limit = self
+            .serialized_size_limit
+            .map(|size_limit| {
+                size_limit
+                    .checked_sub(header_bytes.len() as u64 + self.trailer_size())
+                    .ok_or(bincode::ErrorKind::SizeLimit)
+            })
+            .transpose()?;
+
+        writer.write_all(&header_bytes)?;
+
+        let Some(codec) = self.compression else {
+            return self.serialize_data_into(data, writer, data_size_limit);
+        };
+
+        let compressed =
+            codec.compress(|writer| self.serialize_data_into(data, writer, data_size_limit))?;
+        let compressed_size = compressed.len() as u64;
+
+        if let Some(size_limit) = data_size_limit {
+            if COMPRESSED_SIZE_PREFIX_SIZE + compressed_size > size_limit {
+                return Err(bincode::ErrorKind::SizeLimit.into());
             }
         }
 
+        writer.write_all(&compressed_size.to_le_bytes())?;
+        writer.write_all(&compressed)?;
+
         Ok(())
     }
+
+    /// Serializes the data of the object, without header
+    fn serialize_data_into(
+        &self,
+        data: &impl Serialize,
+        writer: impl std::io::Write,
+        size_limit: Option<u64>,
+    ) -> bincode::Result<()> {
+        let options = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .with_limit(0); // Force to explicitly set the limit for each serialization
+
+        if let Some(size_limit) = size_limit {
+            options.with_limit(size_limit).serialize_into(writer, data)
+        } else {
+            options.with_no_limit().serialize_into(writer, data)
+        }
+    }
+}
+
+/// Versions the object as the version at index `version` of its dispatch enum, `None` if this is
+/// its current version
+fn downgrade_object<T: VersionizeOwned>(
+    object: &T,
+    version: usize,
+) -> bincode::Result<Option<T::VersionedOwned>> {
+    object
+        .versionize_downgraded(version)
+        .map_err(|err| bincode::ErrorKind::Custom(err.to_string()).into())
 }
 
 /// A configuration used to Serialize *TFHE-rs* objects. This configuration decides
@@ -280,6 +826,8 @@ impl SerializationConfig {
 pub struct DeserializationConfig {
     serialized_size_limit: Option<u64>,
     validate_header: bool,
+    require_integrity_check: bool,
+    integrity_key: Option<IntegrityKey>,
 }
 
 /// A configuration used to Serialize *TFHE-rs* objects. This is similar to
@@ -290,80 +838,240 @@ pub struct DeserializationConfig {
 pub struct NonConformantDeserializationConfig {
     serialized_size_limit: Option<u64>,
     validate_header: bool,
+    require_integrity_check: bool,
+    integrity_key: Option<IntegrityKey>,
 }
 
 impl NonConformantDeserializationConfig {
-    /// Deserialize a header using the current config
+    /// Deserialize a header using the current config. The optional features used by the object,
+    /// such as the algorithm of its integrity trailer, are also returned.
     fn deserialize_header(
         &self,
         reader: &mut impl std::io::Read,
-    ) -> Result<SerializationHeader, String> {
+    ) -> Result<(SerializationHeader, HeaderExtension), SafeDeserializationError> {
         let options = bincode::DefaultOptions::new()
             .with_fixint_encoding()
             .with_limit(0);
 
-        if let Some(size_limit) = self.serialized_size_limit {
+        let header: bincode::Result<SerializationHeader> =
+            if let Some(size_limit) = self.serialized_size_limit {
+                options
+                    .with_limit(size_limit)
+                    .deserialize_from(&mut *reader)
+            } else {
+                options.with_no_limit().deserialize_from(&mut *reader)
+            };
+        let header = header.map_err(|err| SafeDeserializationError::Decoding(err.to_string()))?;
+
+        // The extensions only hold fieldless enums, which have a fixed size encoding
+        let extension = if header.header_version == SERIALIZATION_VERSION_WITH_EXTENSION {
             options
-                .with_limit(size_limit)
+                .with_no_limit()
                 .deserialize_from(reader)
-                .map_err(|err| err.to_string())
+                .map_err(|err| SafeDeserializationError::Decoding(err.to_string()))?
         } else {
+            HeaderExtension::default()
+        };
+
+        Ok((header, extension))
+    }
+
+    /// Returns the check to perform on an object whose header announces `integrity_algorithm`
+    fn integrity_check(
+        &self,
+        integrity_algorithm: Option<IntegrityAlgorithm>,
+    ) -> Result<Option<IntegrityCheck>, SafeDeserializationError> {
+        match (integrity_algorithm, self.integrity_key) {
+            (None, None) if !self.require_integrity_check => Ok(None),
+            (None, _) => Err(SafeDeserializationError::IntegrityCheckFailed(
+                "The serialized object has no integrity trailer".to_string(),
+            )),
+            (Some(IntegrityAlgorithm::Sha3_256), None) => Ok(Some(IntegrityCheck::Digest)),
+            (Some(IntegrityAlgorithm::Sha3_256), Some(_)) => {
+                Err(SafeDeserializationError::IntegrityCheckFailed(format!(
+                    "The serialized object is not authenticated, expected an {} trailer",
+                    IntegrityAlgorithm::HmacSha3_256
+                )))
+            }
+            (Some(IntegrityAlgorithm::HmacSha3_256), Some(key)) => {
+                Ok(Some(IntegrityCheck::Hmac(key)))
+            }
+            (Some(IntegrityAlgorithm::HmacSha3_256), None) => {
+                Err(SafeDeserializationError::IntegrityCheckFailed(format!(
+                    "The serialized object is authenticated with {}, but no integrity key was \
+provided",
+                    IntegrityAlgorithm::HmacSha3_256
+                )))
+            }
+        }
+    }
+
+    /// Decodes the serialized object that follows the header with the current size limit,
+    /// decompressing it first if needed. `reserved_size` is the number of bytes of the limit
+    /// already used by the header and the trailer.
+    fn decode<D: DeserializeOwned>(
+        &self,
+        mut reader: impl std::io::Read,
+        reserved_size: u64,
+        compression: Option<CompressionCodec>,
+    ) -> Result<D, SafeDeserializationError> {
+        let Some(codec) = compression else {
+            return self.decode_data(reader, reserved_size);
+        };
+
+        let mut compressed_size = [0u8; COMPRESSED_SIZE_PREFIX_SIZE as usize];
+        reader
+            .read_exact(&mut compressed_size)
+            .map_err(|err| SafeDeserializationError::Decoding(err.to_string()))?;
+        let compressed_size = u64::from_le_bytes(compressed_size);
+
+        if let Some(size_limit) = self.serialized_size_limit {
+            let available_size =
+                size_limit.saturating_sub(reserved_size + COMPRESSED_SIZE_PREFIX_SIZE);
+
+            if compressed_size > available_size {
+                return Err(SafeDeserializationError::Decoding(format!(
+                    "The compressed object size ({compressed_size} bytes) exceeds the size limit"
+                )));
+            }
+        }
+
+        // The buffer grows with the data that is actually read, so a wrong size does not cause a
+        // large allocation
+        let mut compressed = Vec::new();
+        reader
+            .by_ref()
+            .take(compressed_size)
+            .read_to_end(&mut compressed)
+            .map_err(|err| SafeDeserializationError::Decoding(err.to_string()))?;
+
+        if compressed.len() as u64 != compressed_size {
+            return Err(SafeDeserializationError::Decoding(format!(
+                "Unexpected end of the compressed object, expected {compressed_size} bytes, got {}",
+                compressed.len()
+            )));
+        }
+
+        let decompressed = codec.decompressed_reader(&compressed)?;
+
+        // The size limit also applies to the decompressed data, to prevent decompression bombs
+        self.decode_data(decompressed, reserved_size)
+    }
+
+    /// Decodes the uncompressed serialized object with the current size limit
+    fn decode_data<D: DeserializeOwned>(
+        &self,
+        reader: impl std::io::Read,
+        reserved_size: u64,
+    ) -> Result<D, SafeDeserializationError> {
+        let options = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .with_limit(0); // Force to explicitly set the limit for each deserialization
+
+        let deserialized = if let Some(size_limit) = self.serialized_size_limit {
             options
-                .with_no_limit()
+                .with_limit(size_limit.saturating_sub(reserved_size))
                 .deserialize_from(reader)
-                .map_err(|err| err.to_string())
+        } else {
+            options.with_no_limit().deserialize_from(reader)
+        };
+
+        deserialized.map_err(|err| SafeDeserializationError::Decoding(err.to_string()))
+    }
+
+    /// Decodes the serialized object and checks its integrity trailer if needed. `header_bytes`
+    /// are the raw bytes of the header, which are covered by the trailer.
+    fn decode_checked<D: DeserializeOwned>(
+        &self,
+        mut reader: impl std::io::Read,
+        header_bytes: &[u8],
+        extension: HeaderExtension,
+    ) -> Result<D, SafeDeserializationError> {
+        let header_size = header_bytes.len() as u64;
+
+        let Some(integrity_check) = self.integrity_check(extension.integrity_algorithm)? else {
+            return self.decode(reader, header_size, extension.compression);
+        };
+
+        let mut hasher = integrity_check
+            .hasher()
+            .map_err(SafeDeserializationError::Decoding)?;
+        hasher.update(header_bytes);
+
+        let mut integrity_reader = IntegrityReader {
+            inner: &mut reader,
+            hasher,
+        };
+
+        let deserialized = self.decode(
+            &mut integrity_reader,
+            header_size + INTEGRITY_TRAILER_SIZE as u64,
+            extension.compression,
+        )?;
+
+        let expected_trailer = integrity_reader.hasher.finalize();
+
+        let mut trailer = [0u8; INTEGRITY_TRAILER_SIZE];
+        reader.read_exact(&mut trailer).map_err(|err| {
+            SafeDeserializationError::IntegrityCheckFailed(format!(
+                "Could not read the integrity trailer: {err}"
+            ))
+        })?;
+
+        if !trailers_eq(&trailer, &expected_trailer) {
+            return Err(SafeDeserializationError::IntegrityCheckFailed(format!(
+                "The {} of the serialized data does not match its trailer",
+                integrity_check.algorithm()
+            )));
         }
+
+        Ok(deserialized)
     }
 
     /// Deserializes an object serialized by [`SerializationConfig::serialize_into`] from a
     /// [reader](std::io::Read). Performs various sanity checks based on the deserialization config,
     /// but skips conformance checks.
+    ///
+    /// See [`Self::deserialize_from_typed`] to get the kind of error that occurred.
     pub fn deserialize_from<T: DeserializeOwned + Unversionize + Named>(
         self,
-        mut reader: impl std::io::Read,
+        reader: impl std::io::Read,
     ) -> Result<T, String> {
-        let options = bincode::DefaultOptions::new()
-            .with_fixint_encoding()
-            .with_limit(0); // Force to explicitly set the limit for each deserialization
+        self.deserialize_from_typed(reader).map_err(String::from)
+    }
+
+    /// Same as [`Self::deserialize_from`], but returns a [`SafeDeserializationError`]
+    pub fn deserialize_from_typed<T: DeserializeOwned + Unversionize + Named>(
+        self,
+        reader: impl std::io::Read,
+    ) -> Result<T, SafeDeserializationError> {
+        let mut header_reader = RecordingReader {
+            inner: reader,
+            recorded: Vec::new(),
+        };
 
-        let deserialized_header: SerializationHeader = self.deserialize_header(&mut reader)?;
+        let (deserialized_header, extension) = self.deserialize_header(&mut header_reader)?;
 
-        let header_size = options
-            .with_no_limit()
-            .serialized_size(&deserialized_header)
-            .map_err(|err| err.to_string())?;
+        let RecordingReader {
+            inner: mut reader,
+            recorded: header_bytes,
+        } = header_reader;
 
         if self.validate_header {
-            deserialized_header.validate::<T>()?;
+            deserialized_header
+                .validate::<T>()
+                .map_err(SafeDeserializationError::InvalidHeader)?;
         }
 
-        if let Some(size_limit) = self.serialized_size_limit {
-            let options = options.with_limit(size_limit - header_size);
-            match deserialized_header.versioning_mode {
-                SerializationVersioningMode::Versioned { .. } => {
-                    let deser_versioned = options
-                        .deserialize_from(&mut reader)
-                        .map_err(|err| err.to_string())?;
-
-                    T::unversionize(deser_versioned).map_err(|e| e.to_string())
-                }
-                SerializationVersioningMode::Unversioned { .. } => options
-                    .deserialize_from(&mut reader)
-                    .map_err(|err| err.to_string()),
+        match deserialized_header.versioning_mode {
+            SerializationVersioningMode::Versioned { .. } => {
+                let deser_versioned = self.decode_checked(&mut reader, &header_bytes, extension)?;
+
+                T::unversionize(deser_versioned)
+                    .map_err(|e| SafeDeserializationError::Decoding(e.to_string()))
             }
-        } else {
-            let options = options.with_no_limit();
-            match deserialized_header.versioning_mode {
-                SerializationVersioningMode::Versioned { .. } => {
-                    let deser_versioned = options
-                        .deserialize_from(&mut reader)
-                        .map_err(|err| err.to_string())?;
-
-                    T::unversionize(deser_versioned).map_err(|e| e.to_string())
-                }
-                SerializationVersioningMode::Unversioned { .. } => options
-                    .deserialize_from(&mut reader)
-                    .map_err(|err| err.to_string()),
+            SerializationVersioningMode::Unversioned { .. } => {
+                self.decode_checked(&mut reader, &header_bytes, extension)
             }
         }
     }
@@ -373,6 +1081,8 @@ impl NonConformantDeserializationConfig {
         DeserializationConfig {
             serialized_size_limit: self.serialized_size_limit,
             validate_header: self.validate_header,
+            require_integrity_check: self.require_integrity_check,
+            integrity_key: self.integrity_key,
         }
     }
 }
@@ -388,10 +1098,14 @@ impl DeserializationConfig {
     /// It will also check that the object is conformant with the parameter set given in
     /// `conformance_params`. Finally, it will check the compatibility of the loaded data with
     /// the current *TFHE-rs* version.
+    ///
+    /// If the object has an integrity trailer, it is checked as well.
     pub fn new(serialized_size_limit: u64) -> Self {
         Self {
             serialized_size_limit: Some(serialized_size_limit),
             validate_header: true,
+            require_integrity_check: false,
+            integrity_key: None,
         }
     }
 
@@ -400,6 +1114,8 @@ impl DeserializationConfig {
         Self {
             serialized_size_limit: None,
             validate_header: true,
+            require_integrity_check: false,
+            integrity_key: None,
         }
     }
 
@@ -429,27 +1145,63 @@ impl DeserializationConfig {
         }
     }
 
+    /// Rejects objects that were serialized without an integrity trailer (see
+    /// [`SerializationConfig::enable_integrity_check`]). By default, the trailer is only checked
+    /// if it is present.
+    pub fn require_integrity_check(self) -> Self {
+        Self {
+            require_integrity_check: true,
+            ..self
+        }
+    }
+
+    /// Sets the secret key used to check objects serialized with
+    /// [`SerializationConfig::enable_authenticated_integrity_check`]. Once a key is set, objects
+    /// that are not authenticated with it are rejected.
+    pub fn with_integrity_key(self, key: &[u8]) -> Self {
+        Self {
+            integrity_key: Some(IntegrityKey::new(key)),
+            ..self
+        }
+    }
+
     /// Disables the conformance check on an existing config.
     pub fn disable_conformance(self) -> NonConformantDeserializationConfig {
         NonConformantDeserializationConfig {
             serialized_size_limit: self.serialized_size_limit,
             validate_header: self.validate_header,
+            require_integrity_check: self.require_integrity_check,
+            integrity_key: self.integrity_key,
         }
     }
 
     /// Deserializes an object serialized by [`SerializationConfig::serialize_into`] from a
     /// [reader](std::io::Read). Performs various sanity checks based on the deserialization config.
+    ///
+    /// See [`Self::deserialize_from_typed`] to get the kind of error that occurred.
     pub fn deserialize_from<T: DeserializeOwned + Unversionize + Named + ParameterSetConformant>(
         self,
         reader: impl std::io::Read,
         parameter_set: &T::ParameterSet,
     ) -> Result<T, String> {
-        let deser: T = self.disable_conformance().deserialize_from(reader)?;
+        self.deserialize_from_typed(reader, parameter_set)
+            .map_err(String::from)
+    }
+
+    /// Same as [`Self::deserialize_from`], but returns a [`SafeDeserializationError`]
+    pub fn deserialize_from_typed<
+        T: DeserializeOwned + Unversionize + Named + ParameterSetConformant,
+    >(
+        self,
+        reader: impl std::io::Read,
+        parameter_set: &T::ParameterSet,
+    ) -> Result<T, SafeDeserializationError> {
+        let deser: T = self.disable_conformance().deserialize_from_typed(reader)?;
         if !deser.is_conformant(parameter_set) {
-            return Err(format!(
+            return Err(SafeDeserializationError::NonConformant(format!(
                 "Deserialized object of type {} not conformant with given parameter set",
                 T::NAME
-            ));
+            )));
         }
 
         Ok(deser)
@@ -483,6 +1235,16 @@ pub fn safe_deserialize<T: DeserializeOwned + Unversionize + Named>(
         .deserialize_from(reader)
 }
 
+/// Same as [`safe_deserialize`], but returns a [`SafeDeserializationError`]
+pub fn safe_deserialize_typed<T: DeserializeOwned + Unversionize + Named>(
+    reader: impl std::io::Read,
+    serialized_size_limit: u64,
+) -> Result<T, SafeDeserializationError> {
+    DeserializationConfig::new(serialized_size_limit)
+        .disable_conformance()
+        .deserialize_from_typed(reader)
+}
+
 /// Serialize an object with the default configuration and conformance checks (with size limit,
 /// header check and versioning). This is an alias for
 /// `DeserializationConfig::new(serialized_size_limit).deserialize_from`
@@ -496,6 +1258,17 @@ pub fn safe_deserialize_conformant<
     DeserializationConfig::new(serialized_size_limit).deserialize_from(reader, parameter_set)
 }
 
+/// Same as [`safe_deserialize_conformant`], but returns a [`SafeDeserializationError`]
+pub fn safe_deserialize_conformant_typed<
+    T: DeserializeOwned + Unversionize + Named + ParameterSetConformant,
+>(
+    reader: impl std::io::Read,
+    serialized_size_limit: u64,
+    parameter_set: &T::ParameterSet,
+) -> Result<T, SafeDeserializationError> {
+    DeserializationConfig::new(serialized_size_limit).deserialize_from_typed(reader, parameter_set)
+}
+
 #[cfg(all(test, feature = "shortint"))]
 mod test_shortint {
     use tfhe_versionable::Versionize;
@@ -674,6 +1447,218 @@ mod test_shortint {
 
         assert!(safe_deserialize::<Baz>(foo_ser.as_slice(), 0x1000).is_err());
     }
+
+    #[cfg(feature = "integrity-check")]
+    #[test]
+    fn safe_deserialization_integrity_check() {
+        let (ck, _sk) = gen_keys(TEST_PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128);
+        let conformance_params =
+            TEST_PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128.to_shortint_conformance_param();
+
+        let msg = 2_u64;
+
+        let ct = ck.encrypt(msg);
+
+        let mut buffer = vec![];
+
+        let config = SerializationConfig::new(1 << 20).enable_integrity_check();
+
+        let size = config.serialized_size(&ct).unwrap();
+        config.serialize_into(&ct, &mut buffer).unwrap();
+
+        assert_eq!(size as usize, buffer.len());
+
+        let ct2 = DeserializationConfig::new(size)
+            .require_integrity_check()
+            .deserialize_from::<Ciphertext>(buffer.as_slice(), &conformance_params)
+            .unwrap();
+
+        let dec = ck.decrypt(&ct2);
+        assert_eq!(msg, dec);
+
+        // Flip a bit in the ciphertext data
+        let mut corrupted = buffer.clone();
+        corrupted[buffer.len() / 2] ^= 1;
+
+        assert!(matches!(
+            DeserializationConfig::new(size)
+                .deserialize_from_typed::<Ciphertext>(corrupted.as_slice(), &conformance_params),
+            Err(SafeDeserializationError::IntegrityCheckFailed(_))
+        ));
+
+        // Remove part of the trailer
+        let truncated = &buffer[..buffer.len() - 1];
+
+        assert!(matches!(
+            DeserializationConfig::new(size)
+                .deserialize_from_typed::<Ciphertext>(truncated, &conformance_params),
+            Err(SafeDeserializationError::IntegrityCheckFailed(_))
+        ));
+
+        // Objects without trailer are only accepted if the check is not required
+        let mut unchecked_buffer = vec![];
+        SerializationConfig::new(1 << 20)
+            .serialize_into(&ct, &mut unchecked_buffer)
+            .unwrap();
+
+        assert!(DeserializationConfig::new(1 << 20)
+            .deserialize_from::<Ciphertext>(unchecked_buffer.as_slice(), &conformance_params)
+            .is_ok());
+
+        assert!(matches!(
+            DeserializationConfig::new(1 << 20)
+                .require_integrity_check()
+                .deserialize_from_typed::<Ciphertext>(
+                    unchecked_buffer.as_slice(),
+                    &conformance_params
+                ),
+            Err(SafeDeserializationError::IntegrityCheckFailed(_))
+        ));
+    }
+
+    #[cfg(feature = "integrity-check")]
+    #[test]
+    fn safe_deserialization_authenticated_integrity_check() {
+        let (ck, _sk) = gen_keys(TEST_PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128);
+        let conformance_params =
+            TEST_PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128.to_shortint_conformance_param();
+
+        let msg = 2_u64;
+
+        let ct = ck.encrypt(msg);
+
+        let key = b"integrity key";
+
+        let mut buffer = vec![];
+
+        let config = SerializationConfig::new(1 << 20)
+            .disable_versioning()
+            .enable_authenticated_integrity_check(key);
+
+        let size = config.serialized_size(&ct).unwrap();
+        config.serialize_into(&ct, &mut buffer).unwrap();
+
+        assert_eq!(size as usize, buffer.len());
+
+        let ct2 = DeserializationConfig::new(size)
+            .with_integrity_key(key)
+            .deserialize_from::<Ciphertext>(buffer.as_slice(), &conformance_params)
+            .unwrap();
+
+        let dec = ck.decrypt(&ct2);
+        assert_eq!(msg, dec);
+
+        assert!(matches!(
+            DeserializationConfig::new(size)
+                .with_integrity_key(b"another key")
+                .deserialize_from_typed::<Ciphertext>(buffer.as_slice(), &conformance_params),
+            Err(SafeDeserializationError::IntegrityCheckFailed(_))
+        ));
+
+        assert!(matches!(
+            DeserializationConfig::new(size)
+                .deserialize_from_typed::<Ciphertext>(buffer.as_slice(), &conformance_params),
+            Err(SafeDeserializationError::IntegrityCheckFailed(_))
+        ));
+
+        // A plain digest can be recomputed by anyone, so it is rejected when a key is expected
+        let mut digest_buffer = vec![];
+        SerializationConfig::new(1 << 20)
+            .enable_integrity_check()
+            .serialize_into(&ct, &mut digest_buffer)
+            .unwrap();
+
+        assert!(matches!(
+            DeserializationConfig::new(1 << 20)
+                .with_integrity_key(key)
+                .deserialize_from_typed::<Ciphertext>(
+                    digest_buffer.as_slice(),
+                    &conformance_params
+                ),
+            Err(SafeDeserializationError::IntegrityCheckFailed(_))
+        ));
+    }
+
+    #[cfg(not(feature = "integrity-check"))]
+    #[test]
+    fn safe_serialization_integrity_check_without_feature() {
+        let (ck, _sk) = gen_keys(TEST_PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128);
+
+        let ct = ck.encrypt(2);
+
+        assert!(SerializationConfig::new(1 << 20)
+            .enable_integrity_check()
+            .serialize_into(&ct, &mut vec![])
+            .is_err());
+    }
+
+    #[cfg(all(feature = "lz4", feature = "integrity-check"))]
+    #[test]
+    fn safe_deserialization_compression() {
+        let (ck, sk) = gen_keys(TEST_PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128);
+        let conformance_params =
+            TEST_PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128.to_shortint_conformance_param();
+
+        let msg = 2_u64;
+
+        let ct = ck.encrypt(msg);
+
+        for config in [
+            SerializationConfig::new(1 << 20).enable_compression(CompressionCodec::Lz4),
+            SerializationConfig::new(1 << 20)
+                .disable_versioning()
+                .enable_compression(CompressionCodec::Lz4)
+                .enable_integrity_check(),
+        ] {
+            let mut buffer = vec![];
+
+            let size = config.serialized_size(&ct).unwrap();
+            config.clone().serialize_into(&ct, &mut buffer).unwrap();
+
+            assert_eq!(size as usize, buffer.len());
+
+            let ct2 = DeserializationConfig::new(size)
+                .deserialize_from::<Ciphertext>(buffer.as_slice(), &conformance_params)
+                .unwrap();
+
+            let dec = ck.decrypt(&ct2);
+            assert_eq!(msg, dec);
+
+            // Truncated compressed data
+            assert!(DeserializationConfig::new(size)
+                .deserialize_from::<Ciphertext>(
+                    &buffer[..buffer.len() - INTEGRITY_TRAILER_SIZE - 1],
+                    &conformance_params
+                )
+                .is_err());
+        }
+
+        // The server key has some redundancy, so it should be smaller once compressed
+        let mut buffer = vec![];
+        let mut compressed_buffer = vec![];
+
+        safe_serialize(&sk, &mut buffer, 1 << 30).unwrap();
+        SerializationConfig::new(1 << 30)
+            .enable_compression(CompressionCodec::Lz4)
+            .serialize_into(&sk, &mut compressed_buffer)
+            .unwrap();
+
+        assert!(compressed_buffer.len() < buffer.len());
+
+        // The size limit also applies to the decompressed object
+        let compressed_size = compressed_buffer.len() as u64;
+        assert!(compressed_size < buffer.len() as u64 - 1);
+
+        assert!(safe_deserialize::<crate::shortint::ServerKey>(
+            compressed_buffer.as_slice(),
+            buffer.len() as u64 - 1
+        )
+        .is_err());
+
+        // Keep some room for the header extension
+        let _sk2: crate::shortint::ServerKey =
+            safe_deserialize(compressed_buffer.as_slice(), buffer.len() as u64 + 64).unwrap();
+    }
 }
 
 #[cfg(all(test, feature = "integer"))]
@@ -856,4 +1841,67 @@ mod test_integer {
 
         assert_eq!(&msg[..], &dec);
     }
+
+    #[test]
+    fn safe_serialization_target_version() {
+        let (client_key, _sks) = generate_keys(ConfigBuilder::default().build());
+
+        let msg = 27u8;
+        let ct = FheUint8::encrypt(msg, &client_key);
+
+        let mut buffer = vec![];
+        let mut downgraded_buffer = vec![];
+
+        let config = SerializationConfig::new(1 << 20);
+        config.clone().serialize_into(&ct, &mut buffer).unwrap();
+
+        // The current version is also a valid target
+        let mut current_buffer = vec![];
+        config
+            .clone()
+            .serialize_into_version(&ct, 1, &mut current_buffer)
+            .unwrap();
+        assert_eq!(buffer, current_buffer);
+
+        let size = config.serialized_size_of_version(&ct, 0).unwrap();
+        config
+            .clone()
+            .serialize_into_version(&ct, 0, &mut downgraded_buffer)
+            .unwrap();
+
+        assert_eq!(size as usize, downgraded_buffer.len());
+        assert_ne!(buffer, downgraded_buffer);
+
+        // Downgraded data is upgraded on load
+        let ct2: FheUint8 = DeserializationConfig::new(1 << 20)
+            .disable_conformance()
+            .deserialize_from(downgraded_buffer.as_slice())
+            .unwrap();
+        let dec: u8 = ct2.decrypt(&client_key);
+        assert_eq!(msg, dec);
+
+        // Keys can also be downgraded
+        let mut key_buffer = vec![];
+        config
+            .clone()
+            .serialize_into_version(&client_key, 0, &mut key_buffer)
+            .unwrap();
+        let client_key2: crate::ClientKey = DeserializationConfig::new(1 << 20)
+            .disable_conformance()
+            .deserialize_from(key_buffer.as_slice())
+            .unwrap();
+        let dec: u8 = ct.decrypt(&client_key2);
+        assert_eq!(msg, dec);
+
+        // The tag cannot be represented in the first version
+        let mut tagged_ct = ct.clone();
+        tagged_ct.tag_mut().set_u64(42);
+        assert!(config
+            .clone()
+            .serialize_into_version(&tagged_ct, 0, &mut vec![])
+            .is_err());
+
+        // Unknown version
+        assert!(config.serialize_into_version(&ct, 2, &mut vec![]).is_err());
+    }
 }
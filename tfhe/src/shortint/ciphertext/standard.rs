@@ -11,7 +11,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 use tfhe_versionable::Versionize;
 
-#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Versionize)]
+#[derive(Debug, Serialize, Deserialize, Versionize)]
 #[versionize(CiphertextVersions)]
 #[must_use]
 pub struct Ciphertext {
@@ -23,6 +23,9 @@ pub struct Ciphertext {
     pub message_modulus: MessageModulus,
     pub carry_modulus: CarryModulus,
     pub pbs_order: PBSOrder,
+    // Only used to measure the cost of computations, it is not part of the serialized data
+    #[serde(skip)]
+    pbs_depth: u64,
 }
 
 impl crate::named::Named for Ciphertext {
@@ -40,6 +43,7 @@ impl ParameterSetConformant for Ciphertext {
             message_modulus,
             carry_modulus,
             pbs_order,
+            pbs_depth: _,
         } = self;
 
         ct.is_conformant(&param.ct_params)
@@ -51,6 +55,31 @@ impl ParameterSetConformant for Ciphertext {
     }
 }
 
+// The PBS depth is not part of the value of the ciphertext: two algorithms computing the same
+// ciphertext with a different number of sequential PBS give equal ciphertexts.
+impl PartialEq for Ciphertext {
+    fn eq(&self, other: &Self) -> bool {
+        let Self {
+            ct,
+            degree,
+            noise_level,
+            message_modulus,
+            carry_modulus,
+            pbs_order,
+            pbs_depth: _,
+        } = self;
+
+        *ct == other.ct
+            && *degree == other.degree
+            && *noise_level == other.noise_level
+            && *message_modulus == other.message_modulus
+            && *carry_modulus == other.carry_modulus
+            && *pbs_order == other.pbs_order
+    }
+}
+
+impl Eq for Ciphertext {}
+
 // Use destructuring to also have a compile error
 // if ever a new member is added to Ciphertext
 // and is not handled here.
@@ -65,6 +94,7 @@ impl Clone for Ciphertext {
             carry_modulus: src_carry_modulus,
             pbs_order: src_pbs_order,
             noise_level: src_noise_level,
+            pbs_depth: src_pbs_depth,
         } = self;
 
         Self {
@@ -74,6 +104,7 @@ impl Clone for Ciphertext {
             carry_modulus: *src_carry_modulus,
             pbs_order: *src_pbs_order,
             noise_level: *src_noise_level,
+            pbs_depth: *src_pbs_depth,
         }
     }
 
@@ -85,6 +116,7 @@ impl Clone for Ciphertext {
             carry_modulus: dst_carry_modulus,
             pbs_order: dst_pbs_order,
             noise_level: dst_noise_level,
+            pbs_depth: dst_pbs_depth,
         } = self;
 
         let Self {
@@ -94,6 +126,7 @@ impl Clone for Ciphertext {
             carry_modulus: src_carry_modulus,
             pbs_order: src_pbs_order,
             noise_level: src_noise_level,
+            pbs_depth: src_pbs_depth,
         } = source;
 
         if dst_ct.ciphertext_modulus() != src_ct.ciphertext_modulus()
@@ -108,6 +141,7 @@ impl Clone for Ciphertext {
         *dst_carry_modulus = *src_carry_modulus;
         *dst_pbs_order = *src_pbs_order;
         *dst_noise_level = *src_noise_level;
+        *dst_pbs_depth = *src_pbs_depth;
     }
 }
 
@@ -127,6 +161,7 @@ impl Ciphertext {
             message_modulus,
             carry_modulus,
             pbs_order,
+            pbs_depth: 0,
         }
     }
     pub fn carry_is_empty(&self) -> bool {
@@ -152,6 +187,26 @@ impl Ciphertext {
         self.noise_level = noise_level;
     }
 
+    /// Number of programmable bootstrappings that had to be done one after the other to compute
+    /// this ciphertext.
+    ///
+    /// Freshly encrypted and trivial ciphertexts have a depth of 0, a bootstrapping outputs
+    /// ciphertexts one level deeper than its input and the other operations keep the depth of
+    /// their deepest input. The depth is not serialized, a deserialized ciphertext has a depth
+    /// of 0.
+    pub fn pbs_depth(&self) -> u64 {
+        self.pbs_depth
+    }
+
+    pub(crate) fn set_pbs_depth(&mut self, pbs_depth: u64) {
+        self.pbs_depth = pbs_depth;
+    }
+
+    /// Keeps the deepest of the depths of `self` and `other`, for operations combining both
+    pub(crate) fn merge_pbs_depth(&mut self, other: &Self) {
+        self.pbs_depth = self.pbs_depth.max(other.pbs_depth);
+    }
+
     /// Decrypts a trivial ciphertext
     ///
     /// Trivial ciphertexts are ciphertexts which are not encrypted
@@ -295,6 +350,7 @@ mod tests {
             carry_modulus: CarryModulus(1),
             pbs_order: PBSOrder::KeyswitchBootstrap,
             noise_level: NoiseLevel::NOMINAL,
+            pbs_depth: 0,
         };
 
         let c2 = Ciphertext {
@@ -307,6 +363,7 @@ mod tests {
             carry_modulus: CarryModulus(2),
             pbs_order: PBSOrder::BootstrapKeyswitch,
             noise_level: NoiseLevel::NOMINAL,
+            pbs_depth: 0,
         };
 
         assert_ne!(c1, c2);
@@ -327,6 +384,7 @@ mod tests {
             carry_modulus: CarryModulus(1),
             pbs_order: PBSOrder::KeyswitchBootstrap,
             noise_level: NoiseLevel::NOMINAL,
+            pbs_depth: 0,
         };
 
         let c2 = Ciphertext {
@@ -339,6 +397,7 @@ mod tests {
             carry_modulus: CarryModulus(2),
             pbs_order: PBSOrder::BootstrapKeyswitch,
             noise_level: NoiseLevel::NOMINAL,
+            pbs_depth: 0,
         };
 
         assert_ne!(c1, c2);
@@ -359,6 +418,7 @@ mod tests {
             carry_modulus: CarryModulus(1),
             pbs_order: PBSOrder::KeyswitchBootstrap,
             noise_level: NoiseLevel::NOMINAL,
+            pbs_depth: 0,
         };
 
         let c2 = Ciphertext {
@@ -371,6 +431,7 @@ mod tests {
             carry_modulus: CarryModulus(2),
             pbs_order: PBSOrder::BootstrapKeyswitch,
             noise_level: NoiseLevel::NOMINAL,
+            pbs_depth: 0,
         };
 
         assert_ne!(c1, c2);
@@ -9,6 +9,7 @@ use crate::core_crypto::prelude::{
     allocate_and_generate_new_binary_glwe_secret_key,
     allocate_and_generate_new_binary_lwe_secret_key, decrypt_lwe_ciphertext,
 };
+use crate::named::Named;
 use crate::shortint::backward_compatibility::client_key::ClientKeyVersions;
 use crate::shortint::ciphertext::{Ciphertext, CompressedCiphertext};
 use crate::shortint::engine::ShortintEngine;
@@ -37,6 +38,10 @@ pub struct ClientKey {
     pub parameters: ShortintParameterSet,
 }
 
+impl Named for ClientKey {
+    const NAME: &'static str = "shortint::ClientKey";
+}
+
 impl<'cks> From<&'cks ClientKey> for SecretEncryptionKeyView<'cks> {
     fn from(value: &'cks ClientKey) -> Self {
         Self {
@@ -330,6 +330,58 @@ impl ShortintEngine {
         LOCAL_ENGINE.with(|engine_cell| func(&mut engine_cell.borrow_mut()))
     }
 
+    /// Runs `func` with `engine` temporarily replacing the `thread_local` engine of the current
+    /// thread.
+    ///
+    /// Keys generated by `func` on the current thread draw their randomness from `engine`, so
+    /// using an engine created by [`ShortintEngine::new_from_seeder`] with a deterministic seeder
+    /// makes the generation reproducible. The previous engine is restored afterwards, even if
+    /// `func` panics.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::core_crypto::prelude::*;
+    /// use tfhe::shortint::engine::ShortintEngine;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2_KS_PBS;
+    /// use tfhe::shortint::{ClientKey, CompressedPublicKey};
+    ///
+    /// let master_seed = MasterSeed::new([7u8; 32]);
+    ///
+    /// let generate = || {
+    ///     let mut engine = ShortintEngine::new_from_seeder(&mut master_seed.derive_seeder("keys"));
+    ///     ShortintEngine::with_scoped_thread_local(&mut engine, || {
+    ///         let cks = ClientKey::new(PARAM_MESSAGE_2_CARRY_2_KS_PBS);
+    ///         let pk = CompressedPublicKey::new(&cks);
+    ///         (cks, pk)
+    ///     })
+    /// };
+    ///
+    /// let (cks_1, pk_1) = generate();
+    /// let (cks_2, pk_2) = generate();
+    /// assert_eq!(cks_1, cks_2);
+    /// assert_eq!(pk_1, pk_2);
+    /// ```
+    pub fn with_scoped_thread_local<F, R>(engine: &mut Self, func: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        /// Swaps the engines back when dropped
+        struct ScopeGuard<'a>(&'a mut ShortintEngine);
+
+        impl Drop for ScopeGuard<'_> {
+            fn drop(&mut self) {
+                LOCAL_ENGINE
+                    .with(|engine_cell| std::mem::swap(&mut *engine_cell.borrow_mut(), self.0));
+            }
+        }
+
+        LOCAL_ENGINE.with(|engine_cell| std::mem::swap(&mut *engine_cell.borrow_mut(), engine));
+        let _guard = ScopeGuard(engine);
+
+        func()
+    }
+
     /// Create a new shortint engine
     ///
     /// Creating a `ShortintEngine` should not be needed, as each
@@ -15,6 +15,10 @@ use crate::shortint::parameters::{
 };
 use crate::shortint::server_key::apply_programmable_bootstrap;
 use crate::shortint::{Ciphertext, ClientKey, CompressedServerKey, MaxNoiseLevel, ServerKey};
+use crate::streaming_serialization::{
+    LweKeyswitchKeyStreamingMetadata, SeededLweKeyswitchKeyStreamingMetadata, StreamingDataReader,
+    StreamingDataWriter, StreamingError, StreamingSerializable,
+};
 use core::cmp::Ordering;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -552,6 +556,8 @@ impl<'keys> KeySwitchingKeyView<'keys> {
         };
 
         // The keyswitch
+        #[cfg(feature = "pbs-stats")]
+        crate::shortint::server_key::pbs_stats::record_keyswitch(1);
         keyswitch_lwe_ciphertext(
             self.key_switching_key_material.key_switching_key,
             &pre_processed.ct,
@@ -585,6 +591,8 @@ impl<'keys> KeySwitchingKeyView<'keys> {
                             self.dest_server_key.max_noise_level,
                         );
 
+                        #[cfg(feature = "pbs-stats")]
+                        crate::shortint::server_key::pbs_stats::record_keyswitch(1);
                         keyswitch_lwe_ciphertext(
                             &self.dest_server_key.key_switching_key,
                             &wrong_key_ct.ct,
@@ -1054,3 +1062,95 @@ impl ParameterSetConformant for CompressedKeySwitchingKeyMaterial {
             && *destination_key == parameter_set.destination_key
     }
 }
+
+/// Parameters of a [`KeySwitchingKeyMaterial`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct KeySwitchingKeyMaterialStreamingMetadata {
+    pub key_switching_key: LweKeyswitchKeyStreamingMetadata<u64>,
+    pub cast_rshift: i8,
+    pub destination_key: EncryptionKeyChoice,
+}
+
+impl StreamingSerializable for KeySwitchingKeyMaterial {
+    type Element = u64;
+    type Metadata = KeySwitchingKeyMaterialStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        KeySwitchingKeyMaterialStreamingMetadata {
+            key_switching_key: self.key_switching_key.streaming_metadata(),
+            cast_rshift: self.cast_rshift,
+            destination_key: self.destination_key,
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        LweKeyswitchKeyOwned::streaming_data_len(&metadata.key_switching_key)
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        self.key_switching_key.write_streaming_data(writer)
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        Ok(Self {
+            key_switching_key: LweKeyswitchKeyOwned::read_streaming_data(
+                metadata.key_switching_key,
+                reader,
+            )?,
+            cast_rshift: metadata.cast_rshift,
+            destination_key: metadata.destination_key,
+        })
+    }
+}
+
+/// Parameters of a [`CompressedKeySwitchingKeyMaterial`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct CompressedKeySwitchingKeyMaterialStreamingMetadata {
+    pub key_switching_key: SeededLweKeyswitchKeyStreamingMetadata<u64>,
+    pub cast_rshift: i8,
+    pub destination_key: EncryptionKeyChoice,
+}
+
+impl StreamingSerializable for CompressedKeySwitchingKeyMaterial {
+    type Element = u64;
+    type Metadata = CompressedKeySwitchingKeyMaterialStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        CompressedKeySwitchingKeyMaterialStreamingMetadata {
+            key_switching_key: self.key_switching_key.streaming_metadata(),
+            cast_rshift: self.cast_rshift,
+            destination_key: self.destination_key,
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        SeededLweKeyswitchKeyOwned::streaming_data_len(&metadata.key_switching_key)
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        self.key_switching_key.write_streaming_data(writer)
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        Ok(Self {
+            key_switching_key: SeededLweKeyswitchKeyOwned::read_streaming_data(
+                metadata.key_switching_key,
+                reader,
+            )?,
+            cast_rshift: metadata.cast_rshift,
+            destination_key: metadata.destination_key,
+        })
+    }
+}
@@ -21,6 +21,9 @@ impl CompressionKey {
         &self,
         ciphertexts: &[Ciphertext],
     ) -> CompressedCiphertextList {
+        #[cfg(feature = "pbs-stats")]
+        crate::shortint::server_key::pbs_stats::record_compression();
+
         let count = CiphertextCount(ciphertexts.len());
 
         let lwe_pksk = &self.packing_key_switching_key;
@@ -103,6 +106,8 @@ impl CompressionKey {
                 let mut out =
                     GlweCiphertext::new(0, glwe_size, polynomial_size, ciphertext_modulus);
 
+                #[cfg(feature = "pbs-stats")]
+                crate::shortint::server_key::pbs_stats::record_packing_keyswitch(1);
                 par_keyswitch_lwe_ciphertext_list_and_pack_in_glwe_ciphertext(
                     lwe_pksk, &list, &mut out,
                 );
@@ -149,6 +154,9 @@ impl DecompressionKey {
             )));
         }
 
+        #[cfg(feature = "pbs-stats")]
+        crate::shortint::server_key::pbs_stats::record_decompression();
+
         let encryption_cleartext_modulus = packed.message_modulus.0 * packed.carry_modulus.0;
         // We multiply by message_modulus during compression so the actual modulus for the
         // compression is smaller
@@ -9,7 +9,14 @@ use crate::shortint::client_key::ClientKey;
 use crate::shortint::engine::ShortintEngine;
 use crate::shortint::parameters::{CarryModulus, CoreCiphertextModulus, MessageModulus};
 use crate::shortint::server_key::{
-    CompressedModulusSwitchNoiseReductionKey, ModulusSwitchNoiseReductionKeyConformanceParams,
+    CompressedModulusSwitchNoiseReductionKey,
+    CompressedModulusSwitchNoiseReductionKeyStreamingMetadata,
+    ModulusSwitchNoiseReductionKeyConformanceParams,
+};
+use crate::streaming_serialization::{
+    check_ciphertext_modulus, checked_data_size, streaming_data_size,
+    SeededLweBootstrapKeyStreamingMetadata, StreamingDataReader, StreamingDataWriter,
+    StreamingError, StreamingSerializable,
 };
 use serde::{Deserialize, Serialize};
 use tfhe_versionable::Versionize;
@@ -197,3 +204,79 @@ impl ParameterSetConformant for CompressedNoiseSquashingKey {
             && *carry_modulus == *expected_carry_modulus
     }
 }
+
+/// Parameters of a [`CompressedNoiseSquashingKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct CompressedNoiseSquashingKeyStreamingMetadata {
+    pub bootstrapping_key: SeededLweBootstrapKeyStreamingMetadata<u128>,
+    pub modulus_switch_noise_reduction_key:
+        Option<CompressedModulusSwitchNoiseReductionKeyStreamingMetadata>,
+    pub message_modulus: MessageModulus,
+    pub carry_modulus: CarryModulus,
+    pub output_ciphertext_modulus: CoreCiphertextModulus<u128>,
+}
+
+impl StreamingSerializable for CompressedNoiseSquashingKey {
+    type Element = u8;
+    type Metadata = CompressedNoiseSquashingKeyStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        CompressedNoiseSquashingKeyStreamingMetadata {
+            bootstrapping_key: self.bootstrapping_key.streaming_metadata(),
+            modulus_switch_noise_reduction_key: self
+                .modulus_switch_noise_reduction_key
+                .as_ref()
+                .map(StreamingSerializable::streaming_metadata),
+            message_modulus: self.message_modulus,
+            carry_modulus: self.carry_modulus,
+            output_ciphertext_modulus: self.output_ciphertext_modulus,
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        check_ciphertext_modulus(metadata.output_ciphertext_modulus, false)?;
+
+        checked_data_size(&[
+            streaming_data_size::<SeededLweBootstrapKeyOwned<u128>>(&metadata.bootstrapping_key)?,
+            metadata
+                .modulus_switch_noise_reduction_key
+                .as_ref()
+                .map_or(
+                    Ok(0),
+                    streaming_data_size::<CompressedModulusSwitchNoiseReductionKey>,
+                )?,
+        ])
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        self.bootstrapping_key.write_streaming_data(writer)?;
+        if let Some(key) = &self.modulus_switch_noise_reduction_key {
+            key.write_streaming_data(writer)?;
+        }
+        Ok(())
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        Ok(Self {
+            bootstrapping_key: SeededLweBootstrapKeyOwned::read_streaming_data(
+                metadata.bootstrapping_key,
+                reader,
+            )?,
+            modulus_switch_noise_reduction_key: metadata
+                .modulus_switch_noise_reduction_key
+                .map(|key| {
+                    CompressedModulusSwitchNoiseReductionKey::read_streaming_data(key, reader)
+                })
+                .transpose()?,
+            message_modulus: metadata.message_modulus,
+            carry_modulus: metadata.carry_modulus,
+            output_ciphertext_modulus: metadata.output_ciphertext_modulus,
+        })
+    }
+}
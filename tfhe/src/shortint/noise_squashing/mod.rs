@@ -4,6 +4,10 @@ mod server_key;
 #[cfg(test)]
 pub mod tests;
 
-pub use compressed_server_key::CompressedNoiseSquashingKey;
+pub use compressed_server_key::{
+    CompressedNoiseSquashingKey, CompressedNoiseSquashingKeyStreamingMetadata,
+};
 pub use private_key::NoiseSquashingPrivateKey;
-pub use server_key::{NoiseSquashingKey, NoiseSquashingKeyConformanceParams};
+pub use server_key::{
+    NoiseSquashingKey, NoiseSquashingKeyConformanceParams, NoiseSquashingKeyStreamingMetadata,
+};
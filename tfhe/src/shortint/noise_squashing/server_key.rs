@@ -22,7 +22,13 @@ use crate::shortint::parameters::{
     PBSOrder, PBSParameters,
 };
 use crate::shortint::server_key::{
-    ModulusSwitchNoiseReductionKey, ModulusSwitchNoiseReductionKeyConformanceParams, ServerKey,
+    ModulusSwitchNoiseReductionKey, ModulusSwitchNoiseReductionKeyConformanceParams,
+    ModulusSwitchNoiseReductionKeyStreamingMetadata, ServerKey,
+};
+use crate::streaming_serialization::{
+    check_ciphertext_modulus, checked_data_size, streaming_data_size,
+    FourierLweBootstrapKeyStreamingMetadata, StreamingDataReader, StreamingDataWriter,
+    StreamingError, StreamingSerializable,
 };
 use serde::{Deserialize, Serialize};
 use tfhe_versionable::Versionize;
@@ -221,6 +227,9 @@ impl NoiseSquashingKey {
         ciphertext: &Ciphertext,
         src_server_key: &ServerKey,
     ) -> SquashedNoiseCiphertext {
+        #[cfg(feature = "pbs-stats")]
+        crate::shortint::server_key::pbs_stats::record_noise_squashing(1);
+
         let mut lwe_before_noise_squashing = match src_server_key.pbs_order {
             // Under the big key, first need to keyswitch
             PBSOrder::KeyswitchBootstrap => {
@@ -230,6 +239,8 @@ impl NoiseSquashingKey {
                     src_server_key.key_switching_key.ciphertext_modulus(),
                 );
 
+                #[cfg(feature = "pbs-stats")]
+                crate::shortint::server_key::pbs_stats::record_keyswitch(1);
                 keyswitch_lwe_ciphertext(
                     &src_server_key.key_switching_key,
                     &ciphertext.ct,
@@ -426,3 +437,73 @@ impl ParameterSetConformant for NoiseSquashingKey {
             && *carry_modulus == *expected_carry_modulus
     }
 }
+
+/// Parameters of a [`NoiseSquashingKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct NoiseSquashingKeyStreamingMetadata {
+    pub bootstrapping_key: FourierLweBootstrapKeyStreamingMetadata,
+    pub modulus_switch_noise_reduction_key: Option<ModulusSwitchNoiseReductionKeyStreamingMetadata>,
+    pub message_modulus: MessageModulus,
+    pub carry_modulus: CarryModulus,
+    pub output_ciphertext_modulus: CoreCiphertextModulus<u128>,
+}
+
+impl StreamingSerializable for NoiseSquashingKey {
+    type Element = u8;
+    type Metadata = NoiseSquashingKeyStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        NoiseSquashingKeyStreamingMetadata {
+            bootstrapping_key: self.bootstrapping_key.streaming_metadata(),
+            modulus_switch_noise_reduction_key: self
+                .modulus_switch_noise_reduction_key
+                .as_ref()
+                .map(StreamingSerializable::streaming_metadata),
+            message_modulus: self.message_modulus,
+            carry_modulus: self.carry_modulus,
+            output_ciphertext_modulus: self.output_ciphertext_modulus,
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        check_ciphertext_modulus(metadata.output_ciphertext_modulus, false)?;
+
+        checked_data_size(&[
+            streaming_data_size::<Fourier128LweBootstrapKeyOwned>(&metadata.bootstrapping_key)?,
+            metadata
+                .modulus_switch_noise_reduction_key
+                .as_ref()
+                .map_or(Ok(0), streaming_data_size::<ModulusSwitchNoiseReductionKey>)?,
+        ])
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        self.bootstrapping_key.write_streaming_data(writer)?;
+        if let Some(key) = &self.modulus_switch_noise_reduction_key {
+            key.write_streaming_data(writer)?;
+        }
+        Ok(())
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        Ok(Self {
+            bootstrapping_key: Fourier128LweBootstrapKeyOwned::read_streaming_data(
+                metadata.bootstrapping_key,
+                reader,
+            )?,
+            modulus_switch_noise_reduction_key: metadata
+                .modulus_switch_noise_reduction_key
+                .map(|key| ModulusSwitchNoiseReductionKey::read_streaming_data(key, reader))
+                .transpose()?,
+            message_modulus: metadata.message_modulus,
+            carry_modulus: metadata.carry_modulus,
+            output_ciphertext_modulus: metadata.output_ciphertext_modulus,
+        })
+    }
+}
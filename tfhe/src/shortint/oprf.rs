@@ -164,6 +164,8 @@ impl ServerKey {
             PBSOrder::BootstrapKeyswitch => {
                 let mut ct_ksed = LweCiphertext::new(0, in_lwe_size, self.ciphertext_modulus);
 
+                #[cfg(feature = "pbs-stats")]
+                crate::shortint::server_key::pbs_stats::record_keyswitch(1);
                 keyswitch_lwe_ciphertext(&self.key_switching_key, &ct, &mut ct_ksed);
 
                 ct_ksed
@@ -389,4 +389,5 @@ pub(crate) fn unchecked_add_assign(
         ct_left.noise_level() + ct_right.noise_level(),
         max_noise_level,
     );
+    ct_left.merge_pbs_depth(ct_right);
 }
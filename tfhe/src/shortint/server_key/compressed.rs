@@ -1,7 +1,8 @@
 //! Module with the definition of the CompressedServerKey.
 
 use super::{
-    CompressedModulusSwitchNoiseReductionKey, MaxDegree,
+    CompressedModulusSwitchNoiseReductionKey,
+    CompressedModulusSwitchNoiseReductionKeyStreamingMetadata, MaxDegree,
     ModulusSwitchNoiseReductionKeyConformanceParams, PBSConformanceParams,
     PbsTypeConformanceParams,
 };
@@ -16,6 +17,12 @@ use crate::shortint::engine::ShortintEngine;
 use crate::shortint::parameters::{CarryModulus, CiphertextModulus, MessageModulus};
 use crate::shortint::server_key::ShortintBootstrappingKey;
 use crate::shortint::{ClientKey, PBSParameters, ServerKey};
+use crate::streaming_serialization::{
+    check_ciphertext_modulus, checked_data_size, streaming_data_size,
+    SeededLweBootstrapKeyStreamingMetadata, SeededLweKeyswitchKeyStreamingMetadata,
+    SeededLweMultiBitBootstrapKeyStreamingMetadata, StreamingDataReader, StreamingDataWriter,
+    StreamingError, StreamingSerializable,
+};
 use serde::{Deserialize, Serialize};
 use tfhe_versionable::Versionize;
 
@@ -479,3 +486,178 @@ impl ParameterSetConformant for CompressedServerKey {
             && *ciphertext_modulus == parameter_set.ciphertext_modulus()
     }
 }
+
+/// Parameters of a [`ShortintCompressedBootstrappingKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub enum ShortintCompressedBootstrappingKeyStreamingMetadata {
+    Classic {
+        bsk: SeededLweBootstrapKeyStreamingMetadata<u64>,
+        modulus_switch_noise_reduction_key:
+            Option<CompressedModulusSwitchNoiseReductionKeyStreamingMetadata>,
+    },
+    MultiBit {
+        seeded_bsk: SeededLweMultiBitBootstrapKeyStreamingMetadata<u64>,
+        deterministic_execution: bool,
+    },
+}
+
+impl StreamingSerializable for ShortintCompressedBootstrappingKey {
+    type Element = u8;
+    type Metadata = ShortintCompressedBootstrappingKeyStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        match self {
+            Self::Classic {
+                bsk,
+                modulus_switch_noise_reduction_key,
+            } => ShortintCompressedBootstrappingKeyStreamingMetadata::Classic {
+                bsk: bsk.streaming_metadata(),
+                modulus_switch_noise_reduction_key: modulus_switch_noise_reduction_key
+                    .as_ref()
+                    .map(StreamingSerializable::streaming_metadata),
+            },
+            Self::MultiBit {
+                seeded_bsk,
+                deterministic_execution,
+            } => ShortintCompressedBootstrappingKeyStreamingMetadata::MultiBit {
+                seeded_bsk: seeded_bsk.streaming_metadata(),
+                deterministic_execution: *deterministic_execution,
+            },
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        match metadata {
+            ShortintCompressedBootstrappingKeyStreamingMetadata::Classic {
+                bsk,
+                modulus_switch_noise_reduction_key,
+            } => checked_data_size(&[
+                streaming_data_size::<SeededLweBootstrapKeyOwned<u64>>(bsk)?,
+                modulus_switch_noise_reduction_key.as_ref().map_or(
+                    Ok(0),
+                    streaming_data_size::<CompressedModulusSwitchNoiseReductionKey>,
+                )?,
+            ]),
+            ShortintCompressedBootstrappingKeyStreamingMetadata::MultiBit {
+                seeded_bsk, ..
+            } => streaming_data_size::<SeededLweMultiBitBootstrapKeyOwned<u64>>(seeded_bsk),
+        }
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        match self {
+            Self::Classic {
+                bsk,
+                modulus_switch_noise_reduction_key,
+            } => {
+                bsk.write_streaming_data(writer)?;
+                if let Some(key) = modulus_switch_noise_reduction_key {
+                    key.write_streaming_data(writer)?;
+                }
+                Ok(())
+            }
+            Self::MultiBit { seeded_bsk, .. } => seeded_bsk.write_streaming_data(writer),
+        }
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        match metadata {
+            ShortintCompressedBootstrappingKeyStreamingMetadata::Classic {
+                bsk,
+                modulus_switch_noise_reduction_key,
+            } => Ok(Self::Classic {
+                bsk: SeededLweBootstrapKeyOwned::read_streaming_data(bsk, reader)?,
+                modulus_switch_noise_reduction_key: modulus_switch_noise_reduction_key
+                    .map(|key| {
+                        CompressedModulusSwitchNoiseReductionKey::read_streaming_data(key, reader)
+                    })
+                    .transpose()?,
+            }),
+            ShortintCompressedBootstrappingKeyStreamingMetadata::MultiBit {
+                seeded_bsk,
+                deterministic_execution,
+            } => Ok(Self::MultiBit {
+                seeded_bsk: SeededLweMultiBitBootstrapKeyOwned::read_streaming_data(
+                    seeded_bsk, reader,
+                )?,
+                deterministic_execution,
+            }),
+        }
+    }
+}
+
+/// Parameters of a [`CompressedServerKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct CompressedServerKeyStreamingMetadata {
+    pub key_switching_key: SeededLweKeyswitchKeyStreamingMetadata<u64>,
+    pub bootstrapping_key: ShortintCompressedBootstrappingKeyStreamingMetadata,
+    pub message_modulus: MessageModulus,
+    pub carry_modulus: CarryModulus,
+    pub max_degree: MaxDegree,
+    pub max_noise_level: MaxNoiseLevel,
+    pub ciphertext_modulus: CiphertextModulus,
+    pub pbs_order: PBSOrder,
+}
+
+impl StreamingSerializable for CompressedServerKey {
+    type Element = u8;
+    type Metadata = CompressedServerKeyStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        CompressedServerKeyStreamingMetadata {
+            key_switching_key: self.key_switching_key.streaming_metadata(),
+            bootstrapping_key: self.bootstrapping_key.streaming_metadata(),
+            message_modulus: self.message_modulus,
+            carry_modulus: self.carry_modulus,
+            max_degree: self.max_degree,
+            max_noise_level: self.max_noise_level,
+            ciphertext_modulus: self.ciphertext_modulus,
+            pbs_order: self.pbs_order,
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        check_ciphertext_modulus(metadata.ciphertext_modulus, false)?;
+
+        checked_data_size(&[
+            streaming_data_size::<SeededLweKeyswitchKeyOwned<u64>>(&metadata.key_switching_key)?,
+            ShortintCompressedBootstrappingKey::streaming_data_len(&metadata.bootstrapping_key)?,
+        ])
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        self.key_switching_key.write_streaming_data(writer)?;
+        self.bootstrapping_key.write_streaming_data(writer)
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        Ok(Self {
+            key_switching_key: SeededLweKeyswitchKeyOwned::read_streaming_data(
+                metadata.key_switching_key,
+                reader,
+            )?,
+            bootstrapping_key: ShortintCompressedBootstrappingKey::read_streaming_data(
+                metadata.bootstrapping_key,
+                reader,
+            )?,
+            message_modulus: metadata.message_modulus,
+            carry_modulus: metadata.carry_modulus,
+            max_degree: metadata.max_degree,
+            max_noise_level: metadata.max_noise_level,
+            ciphertext_modulus: metadata.ciphertext_modulus,
+            pbs_order: metadata.pbs_order,
+        })
+    }
+}
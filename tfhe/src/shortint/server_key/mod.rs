@@ -52,25 +52,19 @@ use crate::shortint::parameters::{
     CarryModulus, CiphertextConformanceParams, CiphertextModulus, MessageModulus,
 };
 use crate::shortint::{EncryptionKeyChoice, PBSOrder, PaddingBit, ShortintEncoding};
+use crate::streaming_serialization::{
+    check_ciphertext_modulus, checked_data_size, streaming_data_size,
+    FourierLweBootstrapKeyStreamingMetadata, FourierLweMultiBitBootstrapKeyStreamingMetadata,
+    LweKeyswitchKeyStreamingMetadata, StreamingDataReader, StreamingDataWriter, StreamingError,
+    StreamingSerializable,
+};
 use aligned_vec::ABox;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Display, Formatter};
 use tfhe_versionable::Versionize;
 
 #[cfg(feature = "pbs-stats")]
-pub mod pbs_stats {
-    use std::sync::atomic::AtomicU64;
-    pub use std::sync::atomic::Ordering;
-    pub static PBS_COUNT: AtomicU64 = AtomicU64::new(0);
-
-    pub fn get_pbs_count() -> u64 {
-        PBS_COUNT.load(Ordering::Relaxed)
-    }
-
-    pub fn reset_pbs_count() {
-        PBS_COUNT.store(0, Ordering::Relaxed);
-    }
-}
+pub mod pbs_stats;
 #[cfg(feature = "pbs-stats")]
 pub use pbs_stats::*;
 
@@ -591,6 +585,68 @@ impl ServerKey {
         }
     }
 
+    /// Creates a server key for the given parameters whose key material is zeroed.
+    ///
+    /// The key has the dimensions of a real key, so that the ciphertexts and lookup tables it
+    /// creates have the right sizes, but it can only be used in a dry run scope (see
+    /// [`pbs_stats`]), where bootstrappings and keyswitches are not computed.
+    #[cfg(feature = "pbs-stats")]
+    pub(crate) fn new_dry_run(parameters: PBSParameters) -> Self {
+        let glwe_size = parameters.glwe_dimension().to_glwe_size();
+        let polynomial_size = parameters.polynomial_size();
+
+        let bootstrapping_key = match parameters {
+            PBSParameters::PBS(pbs_params) => ShortintBootstrappingKey::Classic {
+                bsk: FourierLweBootstrapKey::new(
+                    pbs_params.lwe_dimension,
+                    glwe_size,
+                    polynomial_size,
+                    pbs_params.pbs_base_log,
+                    pbs_params.pbs_level,
+                ),
+                modulus_switch_noise_reduction_key: None,
+            },
+            PBSParameters::MultiBitPBS(pbs_params) => ShortintBootstrappingKey::MultiBit {
+                fourier_bsk: FourierLweMultiBitBootstrapKey::new(
+                    pbs_params.lwe_dimension,
+                    glwe_size,
+                    polynomial_size,
+                    pbs_params.pbs_base_log,
+                    pbs_params.pbs_level,
+                    pbs_params.grouping_factor,
+                ),
+                // The bootstrappings are never computed
+                thread_count: ThreadCount(1),
+                deterministic_execution: pbs_params.deterministic_execution,
+            },
+        };
+
+        let key_switching_key = LweKeyswitchKey::new(
+            0u64,
+            parameters.ks_base_log(),
+            parameters.ks_level(),
+            parameters
+                .glwe_dimension()
+                .to_equivalent_lwe_dimension(polynomial_size),
+            parameters.lwe_dimension(),
+            parameters.ciphertext_modulus(),
+        );
+
+        Self::from_raw_parts(
+            key_switching_key,
+            bootstrapping_key,
+            parameters.message_modulus(),
+            parameters.carry_modulus(),
+            MaxDegree::from_msg_carry_modulus(
+                parameters.message_modulus(),
+                parameters.carry_modulus(),
+            ),
+            parameters.max_noise_level(),
+            parameters.ciphertext_modulus(),
+            parameters.encryption_key_choice().into(),
+        )
+    }
+
     pub fn conformance_params(&self) -> CiphertextConformanceParams {
         let lwe_dim = self.ciphertext_lwe_dimension();
 
@@ -818,10 +874,22 @@ impl ServerKey {
             return;
         }
 
+        #[cfg(feature = "pbs-stats")]
+        if is_dry_run() {
+            record_keyswitch(1);
+            record_bootstrap(&self.bootstrapping_key);
+            ct.degree = acc.degree;
+            ct.set_noise_level(NoiseLevel::NOMINAL, self.max_noise_level);
+            increase_pbs_depth(ct);
+            return;
+        }
+
         ShortintEngine::with_thread_local_mut(|engine| {
             let (mut ciphertext_buffers, buffers) = engine.get_buffers(self);
             match self.pbs_order {
                 PBSOrder::KeyswitchBootstrap => {
+                    #[cfg(feature = "pbs-stats")]
+                    record_keyswitch(1);
                     keyswitch_lwe_ciphertext(
                         &self.key_switching_key,
                         &ct.ct,
@@ -845,6 +913,8 @@ impl ServerKey {
                         buffers,
                     );
 
+                    #[cfg(feature = "pbs-stats")]
+                    record_keyswitch(1);
                     keyswitch_lwe_ciphertext(
                         &self.key_switching_key,
                         &ciphertext_buffers.buffer_lwe_after_pbs,
@@ -856,6 +926,7 @@ impl ServerKey {
 
         ct.degree = acc.degree;
         ct.set_noise_level(NoiseLevel::NOMINAL, self.max_noise_level);
+        increase_pbs_depth(ct);
     }
 
     /// Compute a keyswitch and programmable bootstrap applying several functions on an input
@@ -1186,7 +1257,7 @@ impl ServerKey {
         #[cfg(feature = "pbs-stats")]
         // We want to count trivial PBS in simulator mode
         // In the non trivial case, this increment is done in the `apply_blind_rotate` function
-        let _ = PBS_COUNT.fetch_add(1, Ordering::Relaxed);
+        record_pbs();
 
         assert_eq!(ct.noise_level(), NoiseLevel::ZERO);
         let modulus_sup = self.message_modulus.0 * self.carry_modulus.0;
@@ -1211,7 +1282,10 @@ impl ServerKey {
 
     fn trivial_pbs_many_lut(&self, ct: &Ciphertext, lut: &ManyLookupTableOwned) -> Vec<Ciphertext> {
         #[cfg(feature = "pbs-stats")]
-        let _ = PBS_COUNT.fetch_add(1, Ordering::Relaxed);
+        {
+            record_pbs();
+            record_many_lut_pbs();
+        }
 
         assert_eq!(ct.noise_level(), NoiseLevel::ZERO);
         let modulus_sup = self.message_modulus.0 * self.carry_modulus.0;
@@ -1267,6 +1341,25 @@ impl ServerKey {
         outputs
     }
 
+    /// Records a many-LUT PBS without computing it, the outputs have the degree, noise level and
+    /// PBS depth the PBS would give them
+    #[cfg(feature = "pbs-stats")]
+    fn dry_run_pbs_many_lut(&self, ct: &Ciphertext, lut: &ManyLookupTableOwned) -> Vec<Ciphertext> {
+        record_bootstrap(&self.bootstrapping_key);
+        record_many_lut_pbs();
+
+        lut.per_function_output_degree
+            .iter()
+            .map(|output_degree| {
+                let mut output = ct.clone();
+                output.degree = *output_degree;
+                output.set_noise_level(NoiseLevel::NOMINAL, self.max_noise_level);
+                increase_pbs_depth(&mut output);
+                output
+            })
+            .collect()
+    }
+
     pub(crate) fn keyswitch_programmable_bootstrap_many_lut(
         &self,
         ct: &Ciphertext,
@@ -1276,6 +1369,16 @@ impl ServerKey {
             return self.trivial_pbs_many_lut(ct, lut);
         }
 
+        #[cfg(feature = "pbs-stats")]
+        if is_dry_run() {
+            record_keyswitch(1);
+            return self.dry_run_pbs_many_lut(ct, lut);
+        }
+
+        #[cfg(feature = "pbs-stats")]
+        // The PBS itself is counted in the `apply_blind_rotate` function
+        record_many_lut_pbs();
+
         let mut acc = lut.acc.clone();
 
         ShortintEngine::with_thread_local_mut(|engine| {
@@ -1283,6 +1386,8 @@ impl ServerKey {
             let (mut ciphertext_buffers, buffers) = engine.get_buffers(self);
 
             // Compute a key switch
+            #[cfg(feature = "pbs-stats")]
+            record_keyswitch(1);
             keyswitch_lwe_ciphertext(
                 &self.key_switching_key,
                 &ct.ct,
@@ -1313,6 +1418,7 @@ impl ServerKey {
 
             output_shortint_ct.degree = *output_degree;
             output_shortint_ct.set_noise_level(NoiseLevel::NOMINAL, self.max_noise_level);
+            increase_pbs_depth(&mut output_shortint_ct);
             outputs.push(output_shortint_ct);
         }
 
@@ -1328,6 +1434,16 @@ impl ServerKey {
             return self.trivial_pbs_many_lut(ct, lut);
         }
 
+        #[cfg(feature = "pbs-stats")]
+        if is_dry_run() {
+            record_keyswitch(lut.function_count() as u64);
+            return self.dry_run_pbs_many_lut(ct, lut);
+        }
+
+        #[cfg(feature = "pbs-stats")]
+        // The PBS itself is counted in the `apply_blind_rotate` function
+        record_many_lut_pbs();
+
         let mut acc = lut.acc.clone();
 
         ShortintEngine::with_thread_local_mut(|engine| {
@@ -1356,6 +1472,8 @@ impl ServerKey {
             let mut output_shortint_ct = ct.clone();
 
             // Compute a key switch
+            #[cfg(feature = "pbs-stats")]
+            record_keyswitch(1);
             keyswitch_lwe_ciphertext(
                 &self.key_switching_key,
                 &tmp_lwe_ciphertext,
@@ -1364,6 +1482,7 @@ impl ServerKey {
 
             output_shortint_ct.degree = *output_degree;
             output_shortint_ct.set_noise_level(NoiseLevel::NOMINAL, self.max_noise_level);
+            increase_pbs_depth(&mut output_shortint_ct);
             outputs.push(output_shortint_ct);
         }
 
@@ -1506,6 +1625,24 @@ where
     input
 }
 
+/// Records a programmable bootstrapping computed with `bootstrapping_key`
+#[cfg(feature = "pbs-stats")]
+fn record_bootstrap(bootstrapping_key: &ShortintBootstrappingKey) {
+    record_pbs();
+    match bootstrapping_key {
+        ShortintBootstrappingKey::Classic { .. } => record_classic_pbs(),
+        ShortintBootstrappingKey::MultiBit { .. } => record_multi_bit_pbs(),
+    }
+}
+
+/// Updates the depth of a ciphertext which has just been bootstrapped
+fn increase_pbs_depth(ct: &mut Ciphertext) {
+    ct.set_pbs_depth(ct.pbs_depth() + 1);
+
+    #[cfg(feature = "pbs-stats")]
+    record_pbs_depth(ct.pbs_depth());
+}
+
 pub(crate) fn apply_blind_rotate_no_ms_noise_reduction<InputCont, OutputCont>(
     bootstrapping_key: &ShortintBootstrappingKey,
     in_buffer: &LweCiphertext<InputCont>,
@@ -1516,7 +1653,7 @@ pub(crate) fn apply_blind_rotate_no_ms_noise_reduction<InputCont, OutputCont>(
     OutputCont: ContainerMut<Element = u64>,
 {
     #[cfg(feature = "pbs-stats")]
-    let _ = PBS_COUNT.fetch_add(1, Ordering::Relaxed);
+    record_bootstrap(bootstrapping_key);
 
     match bootstrapping_key {
         ShortintBootstrappingKey::Classic {
@@ -1792,3 +1929,183 @@ impl ParameterSetConformant for ServerKey {
             && *ciphertext_modulus == parameter_set.ciphertext_modulus()
     }
 }
+
+/// Parameters of a [`ShortintBootstrappingKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub enum ShortintBootstrappingKeyStreamingMetadata {
+    Classic {
+        bsk: FourierLweBootstrapKeyStreamingMetadata,
+        modulus_switch_noise_reduction_key: Option<ModulusSwitchNoiseReductionKeyStreamingMetadata>,
+    },
+    MultiBit {
+        fourier_bsk: FourierLweMultiBitBootstrapKeyStreamingMetadata,
+        deterministic_execution: bool,
+    },
+}
+
+impl StreamingSerializable for ShortintBootstrappingKey {
+    type Element = u8;
+    type Metadata = ShortintBootstrappingKeyStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        match self {
+            Self::Classic {
+                bsk,
+                modulus_switch_noise_reduction_key,
+            } => ShortintBootstrappingKeyStreamingMetadata::Classic {
+                bsk: bsk.streaming_metadata(),
+                modulus_switch_noise_reduction_key: modulus_switch_noise_reduction_key
+                    .as_ref()
+                    .map(StreamingSerializable::streaming_metadata),
+            },
+            Self::MultiBit {
+                fourier_bsk,
+                thread_count: _,
+                deterministic_execution,
+            } => ShortintBootstrappingKeyStreamingMetadata::MultiBit {
+                fourier_bsk: fourier_bsk.streaming_metadata(),
+                deterministic_execution: *deterministic_execution,
+            },
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        match metadata {
+            ShortintBootstrappingKeyStreamingMetadata::Classic {
+                bsk,
+                modulus_switch_noise_reduction_key,
+            } => checked_data_size(&[
+                streaming_data_size::<FourierLweBootstrapKeyOwned>(bsk)?,
+                modulus_switch_noise_reduction_key
+                    .as_ref()
+                    .map_or(Ok(0), streaming_data_size::<ModulusSwitchNoiseReductionKey>)?,
+            ]),
+            ShortintBootstrappingKeyStreamingMetadata::MultiBit { fourier_bsk, .. } => {
+                streaming_data_size::<FourierLweMultiBitBootstrapKeyOwned>(fourier_bsk)
+            }
+        }
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        match self {
+            Self::Classic {
+                bsk,
+                modulus_switch_noise_reduction_key,
+            } => {
+                bsk.write_streaming_data(writer)?;
+                if let Some(key) = modulus_switch_noise_reduction_key {
+                    key.write_streaming_data(writer)?;
+                }
+                Ok(())
+            }
+            Self::MultiBit { fourier_bsk, .. } => fourier_bsk.write_streaming_data(writer),
+        }
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        match metadata {
+            ShortintBootstrappingKeyStreamingMetadata::Classic {
+                bsk,
+                modulus_switch_noise_reduction_key,
+            } => Ok(Self::Classic {
+                bsk: FourierLweBootstrapKeyOwned::read_streaming_data(bsk, reader)?,
+                modulus_switch_noise_reduction_key: modulus_switch_noise_reduction_key
+                    .map(|key| ModulusSwitchNoiseReductionKey::read_streaming_data(key, reader))
+                    .transpose()?,
+            }),
+            ShortintBootstrappingKeyStreamingMetadata::MultiBit {
+                fourier_bsk,
+                deterministic_execution,
+            } => {
+                let mut key = Self::MultiBit {
+                    fourier_bsk: FourierLweMultiBitBootstrapKeyOwned::read_streaming_data(
+                        fourier_bsk,
+                        reader,
+                    )?,
+                    // The thread count is not serialized, it depends on the current machine
+                    thread_count: ThreadCount(1),
+                    deterministic_execution,
+                };
+                key.recompute_thread_count();
+
+                Ok(key)
+            }
+        }
+    }
+}
+
+/// Parameters of a [`ServerKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct ServerKeyStreamingMetadata {
+    pub key_switching_key: LweKeyswitchKeyStreamingMetadata<u64>,
+    pub bootstrapping_key: ShortintBootstrappingKeyStreamingMetadata,
+    pub message_modulus: MessageModulus,
+    pub carry_modulus: CarryModulus,
+    pub max_degree: MaxDegree,
+    pub max_noise_level: MaxNoiseLevel,
+    pub ciphertext_modulus: CiphertextModulus,
+    pub pbs_order: PBSOrder,
+}
+
+impl StreamingSerializable for ServerKey {
+    type Element = u8;
+    type Metadata = ServerKeyStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        ServerKeyStreamingMetadata {
+            key_switching_key: self.key_switching_key.streaming_metadata(),
+            bootstrapping_key: self.bootstrapping_key.streaming_metadata(),
+            message_modulus: self.message_modulus,
+            carry_modulus: self.carry_modulus,
+            max_degree: self.max_degree,
+            max_noise_level: self.max_noise_level,
+            ciphertext_modulus: self.ciphertext_modulus,
+            pbs_order: self.pbs_order,
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        check_ciphertext_modulus(metadata.ciphertext_modulus, false)?;
+
+        checked_data_size(&[
+            streaming_data_size::<LweKeyswitchKeyOwned<u64>>(&metadata.key_switching_key)?,
+            ShortintBootstrappingKey::streaming_data_len(&metadata.bootstrapping_key)?,
+        ])
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        self.key_switching_key.write_streaming_data(writer)?;
+        self.bootstrapping_key.write_streaming_data(writer)
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        Ok(Self {
+            key_switching_key: LweKeyswitchKeyOwned::read_streaming_data(
+                metadata.key_switching_key,
+                reader,
+            )?,
+            bootstrapping_key: ShortintBootstrappingKey::read_streaming_data(
+                metadata.bootstrapping_key,
+                reader,
+            )?,
+            message_modulus: metadata.message_modulus,
+            carry_modulus: metadata.carry_modulus,
+            max_degree: metadata.max_degree,
+            max_noise_level: metadata.max_noise_level,
+            ciphertext_modulus: metadata.ciphertext_modulus,
+            pbs_order: metadata.pbs_order,
+        })
+    }
+}
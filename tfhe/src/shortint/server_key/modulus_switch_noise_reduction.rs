@@ -13,6 +13,10 @@ use crate::shortint::backward_compatibility::server_key::modulus_switch_noise_re
 use crate::shortint::engine::ShortintEngine;
 use crate::shortint::parameters::ModulusSwitchNoiseReductionParams;
 use crate::shortint::CiphertextModulus;
+use crate::streaming_serialization::{
+    LweCiphertextListStreamingMetadata, SeededLweCiphertextListStreamingMetadata,
+    StreamingDataReader, StreamingDataWriter, StreamingError, StreamingSerializable,
+};
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 use tfhe_versionable::Versionize;
@@ -263,3 +267,101 @@ impl CompressedModulusSwitchNoiseReductionKey {
         }
     }
 }
+
+/// Parameters of a [`ModulusSwitchNoiseReductionKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct ModulusSwitchNoiseReductionKeyStreamingMetadata {
+    pub modulus_switch_zeros: LweCiphertextListStreamingMetadata<u64>,
+    pub ms_bound: NoiseEstimationMeasureBound,
+    pub ms_r_sigma_factor: RSigmaFactor,
+    pub ms_input_variance: Variance,
+}
+
+impl StreamingSerializable for ModulusSwitchNoiseReductionKey {
+    type Element = u64;
+    type Metadata = ModulusSwitchNoiseReductionKeyStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        ModulusSwitchNoiseReductionKeyStreamingMetadata {
+            modulus_switch_zeros: self.modulus_switch_zeros.streaming_metadata(),
+            ms_bound: self.ms_bound,
+            ms_r_sigma_factor: self.ms_r_sigma_factor,
+            ms_input_variance: self.ms_input_variance,
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        LweCiphertextListOwned::streaming_data_len(&metadata.modulus_switch_zeros)
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        self.modulus_switch_zeros.write_streaming_data(writer)
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        Ok(Self {
+            modulus_switch_zeros: LweCiphertextListOwned::read_streaming_data(
+                metadata.modulus_switch_zeros,
+                reader,
+            )?,
+            ms_bound: metadata.ms_bound,
+            ms_r_sigma_factor: metadata.ms_r_sigma_factor,
+            ms_input_variance: metadata.ms_input_variance,
+        })
+    }
+}
+
+/// Parameters of a [`CompressedModulusSwitchNoiseReductionKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct CompressedModulusSwitchNoiseReductionKeyStreamingMetadata {
+    pub modulus_switch_zeros: SeededLweCiphertextListStreamingMetadata<u64>,
+    pub ms_bound: NoiseEstimationMeasureBound,
+    pub ms_r_sigma_factor: RSigmaFactor,
+    pub ms_input_variance: Variance,
+}
+
+impl StreamingSerializable for CompressedModulusSwitchNoiseReductionKey {
+    type Element = u64;
+    type Metadata = CompressedModulusSwitchNoiseReductionKeyStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        CompressedModulusSwitchNoiseReductionKeyStreamingMetadata {
+            modulus_switch_zeros: self.modulus_switch_zeros.streaming_metadata(),
+            ms_bound: self.ms_bound,
+            ms_r_sigma_factor: self.ms_r_sigma_factor,
+            ms_input_variance: self.ms_input_variance,
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        SeededLweCiphertextListOwned::streaming_data_len(&metadata.modulus_switch_zeros)
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        self.modulus_switch_zeros.write_streaming_data(writer)
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        Ok(Self {
+            modulus_switch_zeros: SeededLweCiphertextListOwned::read_streaming_data(
+                metadata.modulus_switch_zeros,
+                reader,
+            )?,
+            ms_bound: metadata.ms_bound,
+            ms_r_sigma_factor: metadata.ms_r_sigma_factor,
+            ms_input_variance: metadata.ms_input_variance,
+        })
+    }
+}
@@ -27,6 +27,8 @@ impl ServerKey {
                 let (mut ciphertext_buffers, _) = engine.get_buffers(self);
                 match self.pbs_order {
                     PBSOrder::KeyswitchBootstrap => {
+                        #[cfg(feature = "pbs-stats")]
+                        crate::shortint::server_key::pbs_stats::record_keyswitch(1);
                         keyswitch_lwe_ciphertext(
                             &self.key_switching_key,
                             &ct.ct,
@@ -208,6 +210,8 @@ impl ServerKey {
                     .as_mut()
                     .copy_from_slice(ciphertext_buffers.buffer_lwe_after_pbs.into_container()),
                 PBSOrder::BootstrapKeyswitch => {
+                    #[cfg(feature = "pbs-stats")]
+                    crate::shortint::server_key::pbs_stats::record_keyswitch(1);
                     keyswitch_lwe_ciphertext(
                         &self.key_switching_key,
                         &ciphertext_buffers.buffer_lwe_after_pbs,
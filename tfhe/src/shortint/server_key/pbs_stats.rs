@@ -0,0 +1,325 @@
+//! Statistics on the expensive operations executed by the server key.
+//!
+//! Two kinds of counters are available:
+//!
+//! - [`PBS_COUNT`], a process-wide counter of programmable bootstrappings, see [`get_pbs_count`]
+//!   and [`reset_pbs_count`];
+//! - scoped counters created with [`Stats::scope`], which only record the operations executed while
+//!   the returned [`StatsScope`] is alive, and which count more kinds of operations.
+//!
+//! Scoped counters are attached to threads: operations executed on the thread which created the
+//! scope are recorded. As `integer` and high-level operations run most of their bootstraps on
+//! rayon worker threads, [`StatsScope::install`] runs a closure in a dedicated rayon thread pool
+//! whose worker threads all record into the scope, so that concurrent computations executed in
+//! different pools can be attributed separately.
+//!
+//! A scope can also be a dry run scope, in which the server key records the programmable
+//! bootstrappings and keyswitches it would do but skips them: the ciphertexts get the degree, noise
+//! level and PBS depth the real operation would give them, their content is left untouched. This
+//! is what the high-level `dry_run` uses to count the operations of the real algorithms without
+//! keys.
+
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::sync::atomic::AtomicU64;
+pub use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+pub static PBS_COUNT: AtomicU64 = AtomicU64::new(0);
+
+pub fn get_pbs_count() -> u64 {
+    PBS_COUNT.load(Ordering::Relaxed)
+}
+
+pub fn reset_pbs_count() {
+    PBS_COUNT.store(0, Ordering::Relaxed);
+}
+
+/// Snapshot of the operations recorded by a [`StatsScope`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Stats {
+    /// Number of programmable bootstrappings, including the many-LUT ones
+    pub pbs: u64,
+    /// Number of programmable bootstrappings evaluating several lookup tables at once
+    pub many_lut_pbs: u64,
+    /// Number of programmable bootstrappings computed with a classic bootstrapping key
+    ///
+    /// Together with [`Self::multi_bit_pbs`] this does not include the bootstrappings of trivial
+    /// ciphertexts, which are computed in clear.
+    pub classic_pbs: u64,
+    /// Number of programmable bootstrappings computed with a multi-bit bootstrapping key
+    pub multi_bit_pbs: u64,
+    /// Number of programmable bootstrappings that had to be done one after the other
+    ///
+    /// Each ciphertext carries the number of sequential bootstrappings it went through (see
+    /// [`Ciphertext::pbs_depth`](crate::shortint::Ciphertext::pbs_depth)), a scope records the
+    /// deepest ciphertext a bootstrapping produced. The difference of two snapshots is how much
+    /// the operations done in between deepened the computation, so depths add up when
+    /// operations are chained.
+    pub pbs_depth: u64,
+    /// Number of LWE keyswitches
+    pub keyswitch: u64,
+    /// Number of LWE to GLWE packing keyswitches
+    pub packing_keyswitch: u64,
+    /// Number of ciphertext lists compressed
+    pub compression: u64,
+    /// Number of ciphertexts extracted from a compressed list
+    pub decompression: u64,
+    /// Number of ciphertexts noise squashed
+    pub noise_squashing: u64,
+}
+
+impl Stats {
+    /// Starts recording the operations executed on the current thread.
+    ///
+    /// Recording stops when the returned [`StatsScope`] is dropped. Scopes can be nested, an
+    /// operation is then recorded in all the scopes alive on the thread.
+    pub fn scope() -> StatsScope {
+        let scope = SharedStatsScope {
+            counters: Arc::new(Counters::default()),
+        };
+        scope.enter()
+    }
+}
+
+impl std::ops::Add for Stats {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            pbs: self.pbs + rhs.pbs,
+            many_lut_pbs: self.many_lut_pbs + rhs.many_lut_pbs,
+            classic_pbs: self.classic_pbs + rhs.classic_pbs,
+            multi_bit_pbs: self.multi_bit_pbs + rhs.multi_bit_pbs,
+            pbs_depth: self.pbs_depth + rhs.pbs_depth,
+            keyswitch: self.keyswitch + rhs.keyswitch,
+            packing_keyswitch: self.packing_keyswitch + rhs.packing_keyswitch,
+            compression: self.compression + rhs.compression,
+            decompression: self.decompression + rhs.decompression,
+            noise_squashing: self.noise_squashing + rhs.noise_squashing,
+        }
+    }
+}
+
+/// Operations recorded between two snapshots of the same scope
+impl std::ops::Sub for Stats {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            pbs: self.pbs - rhs.pbs,
+            many_lut_pbs: self.many_lut_pbs - rhs.many_lut_pbs,
+            classic_pbs: self.classic_pbs - rhs.classic_pbs,
+            multi_bit_pbs: self.multi_bit_pbs - rhs.multi_bit_pbs,
+            // Operations working on shallower ciphertexts than the deepest one do not make
+            // the computation deeper
+            pbs_depth: self.pbs_depth.saturating_sub(rhs.pbs_depth),
+            keyswitch: self.keyswitch - rhs.keyswitch,
+            packing_keyswitch: self.packing_keyswitch - rhs.packing_keyswitch,
+            compression: self.compression - rhs.compression,
+            decompression: self.decompression - rhs.decompression,
+            noise_squashing: self.noise_squashing - rhs.noise_squashing,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Counters {
+    dry_run: bool,
+    pbs: AtomicU64,
+    many_lut_pbs: AtomicU64,
+    classic_pbs: AtomicU64,
+    multi_bit_pbs: AtomicU64,
+    pbs_depth: AtomicU64,
+    keyswitch: AtomicU64,
+    packing_keyswitch: AtomicU64,
+    compression: AtomicU64,
+    decompression: AtomicU64,
+    noise_squashing: AtomicU64,
+}
+
+impl Counters {
+    fn snapshot(&self) -> Stats {
+        Stats {
+            pbs: self.pbs.load(Ordering::Relaxed),
+            many_lut_pbs: self.many_lut_pbs.load(Ordering::Relaxed),
+            classic_pbs: self.classic_pbs.load(Ordering::Relaxed),
+            multi_bit_pbs: self.multi_bit_pbs.load(Ordering::Relaxed),
+            pbs_depth: self.pbs_depth.load(Ordering::Relaxed),
+            keyswitch: self.keyswitch.load(Ordering::Relaxed),
+            packing_keyswitch: self.packing_keyswitch.load(Ordering::Relaxed),
+            compression: self.compression.load(Ordering::Relaxed),
+            decompression: self.decompression.load(Ordering::Relaxed),
+            noise_squashing: self.noise_squashing.load(Ordering::Relaxed),
+        }
+    }
+}
+
+thread_local! {
+    static ACTIVE_SCOPES: RefCell<Vec<Arc<Counters>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Guard recording the operations executed on the thread it was created on.
+///
+/// Created by [`Stats::scope`] or [`SharedStatsScope::enter`].
+pub struct StatsScope {
+    shared: SharedStatsScope,
+    // The guard unregisters itself from the thread it was created on
+    _not_send: PhantomData<*const ()>,
+}
+
+impl StatsScope {
+    /// Returns the operations recorded so far
+    pub fn stats(&self) -> Stats {
+        self.shared.stats()
+    }
+
+    /// Returns a handle that can be sent to other threads to record into this scope
+    pub fn shared(&self) -> SharedStatsScope {
+        self.shared.clone()
+    }
+
+    /// Runs `op` in `pool`, recording the operations executed by all the threads of the pool.
+    ///
+    /// The pool should be dedicated to the computation being measured, operations executed
+    /// concurrently in the same pool by other callers would also be recorded.
+    pub fn install<OP, R>(&self, pool: &rayon::ThreadPool, op: OP) -> R
+    where
+        OP: FnOnce() -> R + Send,
+        R: Send,
+    {
+        self.shared.install(pool, op)
+    }
+}
+
+impl Drop for StatsScope {
+    fn drop(&mut self) {
+        self.shared.exit();
+    }
+}
+
+/// Thread-safe handle on the counters of a [`StatsScope`].
+#[derive(Clone)]
+pub struct SharedStatsScope {
+    counters: Arc<Counters>,
+}
+
+impl SharedStatsScope {
+    /// Creates a scope in which the bootstrappings and keyswitches are recorded but not computed
+    pub(crate) fn new_dry_run() -> Self {
+        Self {
+            counters: Arc::new(Counters {
+                dry_run: true,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Returns the operations recorded so far
+    pub fn stats(&self) -> Stats {
+        self.counters.snapshot()
+    }
+
+    /// Starts recording the operations executed on the current thread into this scope
+    pub fn enter(&self) -> StatsScope {
+        ACTIVE_SCOPES.with_borrow_mut(|scopes| scopes.push(Arc::clone(&self.counters)));
+        StatsScope {
+            shared: self.clone(),
+            _not_send: PhantomData,
+        }
+    }
+
+    /// See [`StatsScope::install`]
+    pub fn install<OP, R>(&self, pool: &rayon::ThreadPool, op: OP) -> R
+    where
+        OP: FnOnce() -> R + Send,
+        R: Send,
+    {
+        struct ExitOnDrop<'a> {
+            scope: &'a SharedStatsScope,
+            pool: &'a rayon::ThreadPool,
+        }
+
+        impl Drop for ExitOnDrop<'_> {
+            fn drop(&mut self) {
+                self.pool.broadcast(|_| self.scope.exit());
+            }
+        }
+
+        pool.broadcast(|_| {
+            ACTIVE_SCOPES.with_borrow_mut(|scopes| scopes.push(Arc::clone(&self.counters)));
+        });
+        let _guard = ExitOnDrop { scope: self, pool };
+        pool.install(op)
+    }
+
+    fn exit(&self) {
+        ACTIVE_SCOPES.with_borrow_mut(|scopes| {
+            if let Some(index) = scopes
+                .iter()
+                .rposition(|counters| Arc::ptr_eq(counters, &self.counters))
+            {
+                scopes.remove(index);
+            }
+        });
+    }
+}
+
+fn record(counter: impl Fn(&Counters) -> &AtomicU64, count: u64) {
+    ACTIVE_SCOPES.with_borrow(|scopes| {
+        for counters in scopes {
+            let _ = counter(counters).fetch_add(count, Ordering::Relaxed);
+        }
+    });
+}
+
+/// Returns whether a dry run scope is alive on the current thread
+pub(crate) fn is_dry_run() -> bool {
+    ACTIVE_SCOPES.with_borrow(|scopes| scopes.iter().any(|counters| counters.dry_run))
+}
+
+pub(crate) fn record_pbs() {
+    let _ = PBS_COUNT.fetch_add(1, Ordering::Relaxed);
+    record(|c| &c.pbs, 1);
+}
+
+pub(crate) fn record_many_lut_pbs() {
+    record(|c| &c.many_lut_pbs, 1);
+}
+
+pub(crate) fn record_classic_pbs() {
+    record(|c| &c.classic_pbs, 1);
+}
+
+pub(crate) fn record_multi_bit_pbs() {
+    record(|c| &c.multi_bit_pbs, 1);
+}
+
+/// Records that a bootstrapping produced a ciphertext of depth `pbs_depth`
+pub(crate) fn record_pbs_depth(pbs_depth: u64) {
+    ACTIVE_SCOPES.with_borrow(|scopes| {
+        for counters in scopes {
+            let _ = counters.pbs_depth.fetch_max(pbs_depth, Ordering::Relaxed);
+        }
+    });
+}
+
+pub(crate) fn record_keyswitch(count: u64) {
+    record(|c| &c.keyswitch, count);
+}
+
+pub(crate) fn record_packing_keyswitch(count: u64) {
+    record(|c| &c.packing_keyswitch, count);
+}
+
+pub(crate) fn record_compression() {
+    record(|c| &c.compression, 1);
+}
+
+pub(crate) fn record_decompression() {
+    record(|c| &c.decompression, 1);
+}
+
+pub(crate) fn record_noise_squashing(count: u64) {
+    record(|c| &c.noise_squashing, count);
+}
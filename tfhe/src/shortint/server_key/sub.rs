@@ -405,6 +405,7 @@ impl ServerKey {
             ct_left.noise_level() + ct_right.noise_level(),
             self.max_noise_level,
         );
+        ct_left.merge_pbs_depth(ct_right);
         ct_left.degree = Degree::new(ct_left.degree.get() + z);
 
         z
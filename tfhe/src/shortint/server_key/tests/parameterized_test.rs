@@ -339,6 +339,8 @@ where
             {
                 #[cfg(feature = "pbs-stats")]
                 crate::reset_pbs_count();
+                #[cfg(feature = "pbs-stats")]
+                let stats_scope = crate::Stats::scope();
 
                 let ctxt_0 = cks.encrypt(clear_0);
                 let vec_res = sks.apply_many_lookup_table(&ctxt_0, &acc);
@@ -355,7 +357,36 @@ where
                 }
 
                 #[cfg(feature = "pbs-stats")]
-                assert_eq!(crate::get_pbs_count(), 1, "Invalid PBS Count");
+                {
+                    assert_eq!(crate::get_pbs_count(), 1, "Invalid PBS Count");
+
+                    let stats = stats_scope.stats();
+                    assert_eq!(stats.pbs, 1, "Invalid scoped PBS Count");
+                    assert_eq!(stats.many_lut_pbs, 1, "Invalid scoped many LUT PBS Count");
+                    let expected_keyswitch = match sks.pbs_order {
+                        PBSOrder::KeyswitchBootstrap => 1,
+                        PBSOrder::BootstrapKeyswitch => fn_count as u64,
+                    };
+                    assert_eq!(
+                        stats.keyswitch, expected_keyswitch,
+                        "Invalid scoped keyswitch Count"
+                    );
+                    use crate::shortint::server_key::ShortintBootstrappingKey;
+                    let (expected_classic, expected_multi_bit) = match &sks.bootstrapping_key {
+                        ShortintBootstrappingKey::Classic { .. } => (1, 0),
+                        ShortintBootstrappingKey::MultiBit { .. } => (0, 1),
+                    };
+                    assert_eq!(
+                        (stats.classic_pbs, stats.multi_bit_pbs),
+                        (expected_classic, expected_multi_bit),
+                        "Invalid scoped classic and multi-bit PBS Count"
+                    );
+                    assert_eq!(stats.pbs_depth, 1, "Invalid scoped PBS depth");
+                }
+                assert!(
+                    vec_res.iter().all(|res| res.pbs_depth() == 1),
+                    "Invalid PBS depth"
+                );
             }
 
             // Test on a trivial
@@ -753,6 +753,8 @@ mod experimental {
             ShortintEngine::with_thread_local_mut(|engine| {
                 let (mut ciphertext_buffers, buffers) = engine.get_buffers(&self.pbs_server_key);
                 // Compute a key switch
+                #[cfg(feature = "pbs-stats")]
+                crate::shortint::server_key::pbs_stats::record_keyswitch(1);
                 keyswitch_lwe_ciphertext(
                     &self.pbs_server_key.key_switching_key,
                     &ct_in.ct,
@@ -823,6 +825,8 @@ mod experimental {
             );
 
             // Compute a key switch
+            #[cfg(feature = "pbs-stats")]
+            crate::shortint::server_key::pbs_stats::record_keyswitch(1);
             keyswitch_lwe_ciphertext(
                 &self.ksk_pbs_to_wopbs,
                 &ct_clean.ct,
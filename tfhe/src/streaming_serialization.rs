@@ -0,0 +1,2118 @@
+//! Streaming serialization of large keys
+//!
+//! [`safe_serialization`](crate::safe_serialization) decodes objects with bincode, which requires
+//! the whole serialized object to be available before the key data can be copied to its final
+//! allocation. For bootstrap and keyswitch keys that weigh several gigabytes, this module provides
+//! a simpler format where the raw key data follows a small header:
+//!
+//! - the 8 bytes magic `TFHESTRM`,
+//! - the size of the header as a little endian `u64`,
+//! - the header, encoded with bincode, holding the type name, the number of elements of the data
+//!   and the parameters of the object,
+//! - zero padding, so that the data starts at a multiple of [`STREAMING_DATA_ALIGNMENT`] bytes,
+//! - the data of the object, as little endian scalars.
+//!
+//! Keys made of several entities, such as the server keys of the high level API, store the
+//! parameters of all their entities in the header, followed by the data of each entity.
+//!
+//! The data is read chunk by chunk from any [reader](std::io::Read) directly into the memory of the
+//! deserialized object, so loading a key only requires the memory of the key itself. The
+//! parameters are checked before any allocation, so that invalid data results in an error. Since
+//! the data is stored contiguously and aligned, a [`FourierLweBootstrapKey`] stored in this format
+//! can also be used without copy from a buffer holding the whole stream, see
+//! [`fourier_lwe_bootstrap_key_from_bytes`]. With the `mmap` feature,
+//! [`MappedFourierLweBootstrapKey`] memory-maps such a file so that multiple processes can share a
+//! single copy of the key.
+
+// Types in this file should never be versioned because they are a wrapper around the versioning
+// process
+#![cfg_attr(dylint_lib = "tfhe_lints", allow(serialize_without_versionize))]
+
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::io::{Read, Write};
+
+use crate::core_crypto::commons::math::random::CompressionSeed;
+use crate::core_crypto::commons::numeric::UnsignedInteger;
+use crate::core_crypto::commons::parameters::*;
+use crate::core_crypto::entities::*;
+use crate::core_crypto::fft_impl::fft64::c64;
+use crate::core_crypto::fft_impl::fft64::crypto::bootstrap::FourierLweBootstrapKeyView;
+use crate::named::Named;
+use bincode::Options;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Magic bytes written at the start of each stream
+const STREAMING_MAGIC: [u8; 8] = *b"TFHESTRM";
+
+/// Version of the streaming format. This should be updated when the [`StreamingHeader`] or the
+/// layout of the stream are modified.
+const STREAMING_VERSION: &str = "0.1";
+
+/// Size in bytes of the magic and of the header size that start each stream
+const STREAMING_PREFIX_SIZE: usize = STREAMING_MAGIC.len() + std::mem::size_of::<u64>();
+
+/// Maximum size in bytes of a header. Headers only hold a few parameters, this prevents large
+/// allocations when reading invalid data.
+const STREAMING_MAX_HEADER_SIZE: usize = 1 << 16;
+
+/// Alignment in bytes of the data section, relative to the start of the stream. This is enough
+/// for any scalar type stored in a key and allows a memory-mapped file to be used without copy.
+pub const STREAMING_DATA_ALIGNMENT: usize = 64;
+
+/// Default number of bytes written or read at once
+const DEFAULT_CHUNK_BYTE_SIZE: usize = 1 << 22;
+
+/// Error returned when a stream can't be written or read
+#[derive(Debug)]
+pub enum StreamingError {
+    /// The underlying writer or reader returned an error
+    Io(std::io::Error),
+    /// The stream does not start with a valid header for the requested type
+    InvalidHeader(String),
+    /// The parameters of the entity are not consistent with its data
+    InvalidMetadata(String),
+    /// The serialized object is larger than the configured limit
+    SizeLimitExceeded { size: u64, limit: u64 },
+    /// The stream can't be used on the current platform
+    Unsupported(String),
+}
+
+impl Display for StreamingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{err}"),
+            Self::InvalidHeader(msg) | Self::InvalidMetadata(msg) | Self::Unsupported(msg) => {
+                write!(f, "{msg}")
+            }
+            Self::SizeLimitExceeded { size, limit } => write!(
+                f,
+                "The serialized object has a size of {size} bytes, which exceeds the limit of \
+{limit} bytes"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StreamingError {}
+
+impl From<std::io::Error> for StreamingError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+/// Objects that can be written and read with the streaming format.
+///
+/// The data of an object is a contiguous list of [`Self::Element`], and its parameters, called
+/// metadata, are required to interpret it. Keys made of several entities, such as the server keys,
+/// use bytes as elements and write the data of each of their entities one after the other.
+pub trait StreamingSerializable: Sized {
+    /// Scalar type of the data of the object
+    type Element: bytemuck::Pod;
+    /// Parameters required to rebuild the object around its data
+    type Metadata: Serialize + DeserializeOwned;
+
+    /// Return the parameters of the object
+    fn streaming_metadata(&self) -> Self::Metadata;
+
+    /// Return the number of elements in the data of an object built with `metadata`, or an error
+    /// if the parameters are invalid. This is called on untrusted parameters, before any
+    /// allocation, so it must check every parameter that [`Self::read_streaming_data`] relies on.
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String>;
+
+    /// Write the data of the object
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError>;
+
+    /// Allocate a new object built with `metadata`, which has been validated by
+    /// [`Self::streaming_data_len`], and fill its data from the stream.
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError>;
+}
+
+/// Writes the data of an object, see [`StreamingSerializable::write_streaming_data`]
+pub struct StreamingDataWriter<'a> {
+    writer: &'a mut dyn Write,
+    chunk_byte_size: usize,
+    written_bytes: usize,
+}
+
+impl StreamingDataWriter<'_> {
+    /// Write `data` to the stream, as little endian scalars
+    pub fn write<E: bytemuck::Pod>(&mut self, data: &[E]) -> Result<(), StreamingError> {
+        for chunk in bytemuck::cast_slice::<_, u8>(data).chunks(self.chunk_byte_size) {
+            self.writer.write_all(chunk)?;
+        }
+        self.written_bytes += std::mem::size_of_val(data);
+
+        Ok(())
+    }
+}
+
+/// Reads the data of an object, see [`StreamingSerializable::read_streaming_data`]
+pub struct StreamingDataReader<'a> {
+    reader: &'a mut dyn Read,
+    chunk_byte_size: usize,
+    remaining_bytes: usize,
+}
+
+impl StreamingDataReader<'_> {
+    /// Fill `data` from the stream
+    ///
+    /// Returns an error if the stream does not hold enough data for `data`, according to the
+    /// parameters of the object.
+    pub fn read<E: bytemuck::Pod>(&mut self, data: &mut [E]) -> Result<(), StreamingError> {
+        let size = std::mem::size_of_val(data);
+        if size > self.remaining_bytes {
+            return Err(StreamingError::InvalidMetadata(
+                "The object reads more data than its parameters declare".to_string(),
+            ));
+        }
+
+        for chunk in bytemuck::cast_slice_mut::<_, u8>(data).chunks_mut(self.chunk_byte_size) {
+            self.reader.read_exact(chunk)?;
+        }
+        self.remaining_bytes -= size;
+
+        Ok(())
+    }
+}
+
+/// Header of the streaming format, written after the magic and the header size
+#[derive(Serialize, Deserialize)]
+struct StreamingHeader {
+    version: Cow<'static, str>,
+    name: Cow<'static, str>,
+    element_size: u64,
+    element_count: u64,
+    /// The [`StreamingSerializable::Metadata`] of the entity, encoded with bincode
+    metadata: Vec<u8>,
+}
+
+fn bincode_options() -> impl Options {
+    bincode::DefaultOptions::new().with_fixint_encoding()
+}
+
+/// The raw data is reinterpreted as little endian scalars without conversion
+fn check_endianness() -> Result<(), StreamingError> {
+    if cfg!(target_endian = "big") {
+        return Err(StreamingError::Unsupported(
+            "Streaming serialization is only supported on little endian platforms".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Return the offset of the data section for a header of `header_size` bytes
+fn data_offset(header_size: usize) -> usize {
+    (STREAMING_PREFIX_SIZE + header_size).next_multiple_of(STREAMING_DATA_ALIGNMENT)
+}
+
+/// Return the product of `factors`, or an error if one of them is zero or if the product
+/// overflows. This is used to compute the data size of an entity from untrusted parameters.
+fn checked_data_len(factors: &[usize]) -> Result<usize, String> {
+    factors.iter().try_fold(1usize, |acc, &factor| {
+        if factor == 0 {
+            return Err("Got a null parameter".to_string());
+        }
+
+        acc.checked_mul(factor).ok_or_else(size_overflow)
+    })
+}
+
+fn size_overflow() -> String {
+    "The size of the entity overflows".to_string()
+}
+
+/// Return the size in bytes of the data of an object built with `metadata`, or an error if the
+/// parameters are invalid. This is used by keys made of several entities, whose elements are
+/// bytes.
+pub fn streaming_data_size<T: StreamingSerializable>(
+    metadata: &T::Metadata,
+) -> Result<usize, String> {
+    T::streaming_data_len(metadata)?
+        .checked_mul(std::mem::size_of::<T::Element>())
+        .ok_or_else(size_overflow)
+}
+
+/// Return the sum of `sizes`, or an error if it overflows
+pub fn checked_data_size(sizes: &[usize]) -> Result<usize, String> {
+    sizes.iter().try_fold(0usize, |acc, &size| {
+        acc.checked_add(size).ok_or_else(size_overflow)
+    })
+}
+
+/// Return the size of a ciphertext of dimension `dimension`, or an error if it overflows
+fn checked_size(dimension: usize) -> Result<usize, String> {
+    dimension.checked_add(1).ok_or_else(size_overflow)
+}
+
+/// Check that `polynomial_size` is a power of two, as required by the polynomial algorithms
+fn check_polynomial_size(polynomial_size: PolynomialSize) -> Result<(), String> {
+    if !polynomial_size.0.is_power_of_two() {
+        return Err(format!(
+            "Invalid polynomial size {}, expected a power of two",
+            polynomial_size.0
+        ));
+    }
+
+    Ok(())
+}
+
+/// Check that a decomposition is not empty and does not use more bits than `Scalar`
+fn check_decomposition<Scalar: UnsignedInteger>(
+    base_log: DecompositionBaseLog,
+    level_count: DecompositionLevelCount,
+) -> Result<(), String> {
+    let is_valid = base_log.0 > 0
+        && level_count.0 > 0
+        && base_log
+            .0
+            .checked_mul(level_count.0)
+            .is_some_and(|bits| bits <= Scalar::BITS);
+
+    if !is_valid {
+        return Err(format!(
+            "Invalid decomposition with a base log of {} and {} levels for {} bits scalars",
+            base_log.0,
+            level_count.0,
+            Scalar::BITS
+        ));
+    }
+
+    Ok(())
+}
+
+/// Check that a custom `ciphertext_modulus` fits in `Scalar`. Seeded entities additionally
+/// require a modulus compatible with the native one.
+pub(crate) fn check_ciphertext_modulus<Scalar: UnsignedInteger>(
+    ciphertext_modulus: CiphertextModulus<Scalar>,
+    is_seeded: bool,
+) -> Result<(), String> {
+    if ciphertext_modulus.is_native_modulus() {
+        return Ok(());
+    }
+
+    CiphertextModulus::<Scalar>::try_new(ciphertext_modulus.get_custom_modulus())
+        .map_err(|err| format!("Invalid ciphertext modulus: {}", err.const_err_msg()))?;
+
+    if is_seeded && !ciphertext_modulus.is_compatible_with_native_modulus() {
+        return Err("Seeded entities only support power of two ciphertext moduli".to_string());
+    }
+
+    Ok(())
+}
+
+/// Check the parameters of the GGSW ciphertexts of a bootstrap key
+fn check_ggsw_parameters<Scalar: UnsignedInteger>(
+    polynomial_size: PolynomialSize,
+    decomposition_base_log: DecompositionBaseLog,
+    decomposition_level_count: DecompositionLevelCount,
+) -> Result<(), String> {
+    check_polynomial_size(polynomial_size)?;
+    check_decomposition::<Scalar>(decomposition_base_log, decomposition_level_count)
+}
+
+/// Check the grouping factor of a multi bit bootstrap key and return its number of GGSW
+/// ciphertexts
+fn multi_bit_ggsw_count(
+    input_lwe_dimension: LweDimension,
+    grouping_factor: LweBskGroupingFactor,
+) -> Result<usize, String> {
+    let factor = grouping_factor.0;
+
+    if factor == 0 || factor >= usize::BITS as usize || input_lwe_dimension.0 % factor != 0 {
+        return Err(format!(
+            "Invalid grouping factor {factor} for an input LWE dimension of {}",
+            input_lwe_dimension.0
+        ));
+    }
+
+    checked_data_len(&[
+        input_lwe_dimension.0 / factor,
+        grouping_factor.ggsw_per_multi_bit_element().0,
+    ])
+}
+
+/// Decode and validate the header of a stream, returning the metadata and the number of elements
+/// of the data
+fn parse_header<T: StreamingSerializable + Named>(
+    header_bytes: &[u8],
+) -> Result<(T::Metadata, usize), StreamingError> {
+    let header: StreamingHeader = bincode_options()
+        .with_limit(header_bytes.len() as u64)
+        .deserialize(header_bytes)
+        .map_err(|err| StreamingError::InvalidHeader(err.to_string()))?;
+
+    if header.version != STREAMING_VERSION {
+        return Err(StreamingError::InvalidHeader(format!(
+            "Expected streaming format version {STREAMING_VERSION}, got version {}",
+            header.version
+        )));
+    }
+
+    if header.name != T::NAME
+        && T::BACKWARD_COMPATIBILITY_ALIASES
+            .iter()
+            .all(|alias| header.name != *alias)
+    {
+        return Err(StreamingError::InvalidHeader(format!(
+            "Expected type {}, got type {}",
+            T::NAME,
+            header.name
+        )));
+    }
+
+    let element_size = std::mem::size_of::<T::Element>();
+    if header.element_size != element_size as u64 {
+        return Err(StreamingError::InvalidHeader(format!(
+            "Expected elements of {element_size} bytes, got {} bytes",
+            header.element_size
+        )));
+    }
+
+    let metadata: T::Metadata = bincode_options()
+        .with_limit(header.metadata.len() as u64)
+        .deserialize(&header.metadata)
+        .map_err(|err| StreamingError::InvalidMetadata(err.to_string()))?;
+
+    let element_count =
+        T::streaming_data_len(&metadata).map_err(StreamingError::InvalidMetadata)?;
+
+    if header.element_count != element_count as u64 {
+        return Err(StreamingError::InvalidMetadata(format!(
+            "The parameters of the {} require {element_count} elements, but the stream holds {}",
+            T::NAME,
+            header.element_count
+        )));
+    }
+
+    Ok((metadata, element_count))
+}
+
+/// Return the size in bytes of a stream with the given header and data sizes
+fn stream_size(
+    header_size: usize,
+    element_count: usize,
+    element_size: usize,
+) -> Result<u64, StreamingError> {
+    (element_count as u64)
+        .checked_mul(element_size as u64)
+        .and_then(|data_size| data_size.checked_add(data_offset(header_size) as u64))
+        .ok_or_else(|| {
+            StreamingError::InvalidMetadata("The size of the serialized object overflows".into())
+        })
+}
+
+/// A configuration used to write and read *TFHE-rs* keys with the streaming format
+#[derive(Copy, Clone, Debug)]
+pub struct StreamingConfig {
+    serialized_size_limit: Option<u64>,
+    chunk_byte_size: usize,
+}
+
+impl StreamingConfig {
+    /// Creates a new streaming config.
+    /// `serialized_size_limit` is the size limit (in number of byte) of the serialized object
+    /// (including the header).
+    pub fn new(serialized_size_limit: u64) -> Self {
+        Self {
+            serialized_size_limit: Some(serialized_size_limit),
+            chunk_byte_size: DEFAULT_CHUNK_BYTE_SIZE,
+        }
+    }
+
+    /// Creates a new streaming config without any size check.
+    pub fn new_with_unlimited_size() -> Self {
+        Self {
+            serialized_size_limit: None,
+            chunk_byte_size: DEFAULT_CHUNK_BYTE_SIZE,
+        }
+    }
+
+    /// Disables the size limit for serialized objects
+    pub fn disable_size_limit(self) -> Self {
+        Self {
+            serialized_size_limit: None,
+            ..self
+        }
+    }
+
+    /// Sets the size limit for this config
+    pub fn with_size_limit(self, size: u64) -> Self {
+        Self {
+            serialized_size_limit: Some(size),
+            ..self
+        }
+    }
+
+    /// Sets the number of bytes of data written to the writer or read from the reader at once
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_byte_size` is 0.
+    pub fn with_chunk_byte_size(self, chunk_byte_size: usize) -> Self {
+        assert!(chunk_byte_size > 0, "The chunk size must be non zero");
+
+        Self {
+            chunk_byte_size,
+            ..self
+        }
+    }
+
+    fn check_size(&self, size: u64) -> Result<(), StreamingError> {
+        match self.serialized_size_limit {
+            Some(limit) if size > limit => Err(StreamingError::SizeLimitExceeded { size, limit }),
+            _ => Ok(()),
+        }
+    }
+
+    fn create_header_bytes<T: StreamingSerializable + Named>(
+        object: &T,
+    ) -> Result<(Vec<u8>, usize), StreamingError> {
+        let encoding_error = |err: bincode::Error| StreamingError::InvalidMetadata(err.to_string());
+
+        let metadata = object.streaming_metadata();
+        let element_count =
+            T::streaming_data_len(&metadata).map_err(StreamingError::InvalidMetadata)?;
+
+        let header = StreamingHeader {
+            version: Cow::Borrowed(STREAMING_VERSION),
+            name: Cow::Borrowed(T::NAME),
+            element_size: std::mem::size_of::<T::Element>() as u64,
+            element_count: element_count as u64,
+            metadata: bincode_options()
+                .serialize(&metadata)
+                .map_err(encoding_error)?,
+        };
+
+        let header_bytes = bincode_options()
+            .serialize(&header)
+            .map_err(encoding_error)?;
+
+        Ok((header_bytes, element_count))
+    }
+
+    /// Returns the size the object would take if serialized using the current config
+    pub fn serialized_size<T: StreamingSerializable + Named>(
+        &self,
+        object: &T,
+    ) -> Result<u64, StreamingError> {
+        let (header_bytes, element_count) = Self::create_header_bytes(object)?;
+
+        stream_size(
+            header_bytes.len(),
+            element_count,
+            std::mem::size_of::<T::Element>(),
+        )
+    }
+
+    /// Serializes an object into a [writer](std::io::Write), based on the current config.
+    /// The written bytes can be deserialized using [`StreamingConfig::deserialize_from`].
+    pub fn serialize_into<T: StreamingSerializable + Named>(
+        &self,
+        object: &T,
+        mut writer: impl std::io::Write,
+    ) -> Result<(), StreamingError> {
+        check_endianness()?;
+
+        let (header_bytes, element_count) = Self::create_header_bytes(object)?;
+
+        self.check_size(stream_size(
+            header_bytes.len(),
+            element_count,
+            std::mem::size_of::<T::Element>(),
+        )?)?;
+
+        // Can't overflow, this has been checked by stream_size
+        let data_size = element_count * std::mem::size_of::<T::Element>();
+
+        writer.write_all(&STREAMING_MAGIC)?;
+        writer.write_all(&(header_bytes.len() as u64).to_le_bytes())?;
+        writer.write_all(&header_bytes)?;
+
+        let padding = data_offset(header_bytes.len()) - STREAMING_PREFIX_SIZE - header_bytes.len();
+        writer.write_all(&[0u8; STREAMING_DATA_ALIGNMENT][..padding])?;
+
+        let mut data_writer = StreamingDataWriter {
+            writer: &mut writer,
+            chunk_byte_size: self.chunk_byte_size,
+            written_bytes: 0,
+        };
+        object.write_streaming_data(&mut data_writer)?;
+
+        if data_writer.written_bytes != data_size {
+            return Err(StreamingError::InvalidMetadata(format!(
+                "The parameters of the {} declare {data_size} bytes of data, but {} bytes were \
+written",
+                T::NAME,
+                data_writer.written_bytes
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Deserializes an object serialized by [`StreamingConfig::serialize_into`] from a
+    /// [reader](std::io::Read). The data is read chunk by chunk directly into the allocation of the
+    /// deserialized object.
+    pub fn deserialize_from<T: StreamingSerializable + Named>(
+        &self,
+        mut reader: impl std::io::Read,
+    ) -> Result<T, StreamingError> {
+        check_endianness()?;
+
+        let mut prefix = [0u8; STREAMING_PREFIX_SIZE];
+        reader.read_exact(&mut prefix)?;
+        let header_size = parse_prefix(&prefix)?;
+
+        self.check_size((STREAMING_PREFIX_SIZE + header_size) as u64)?;
+
+        let mut header_bytes = vec![0u8; header_size];
+        reader.read_exact(&mut header_bytes)?;
+
+        let (metadata, element_count) = parse_header::<T>(&header_bytes)?;
+
+        self.check_size(stream_size(
+            header_size,
+            element_count,
+            std::mem::size_of::<T::Element>(),
+        )?)?;
+
+        let padding = data_offset(header_size) - STREAMING_PREFIX_SIZE - header_size;
+        reader.read_exact(&mut [0u8; STREAMING_DATA_ALIGNMENT][..padding])?;
+
+        let mut data_reader = StreamingDataReader {
+            reader: &mut reader,
+            chunk_byte_size: self.chunk_byte_size,
+            // Can't overflow, this has been checked by stream_size
+            remaining_bytes: element_count * std::mem::size_of::<T::Element>(),
+        };
+        let object = T::read_streaming_data(metadata, &mut data_reader)?;
+
+        if data_reader.remaining_bytes != 0 {
+            return Err(StreamingError::InvalidMetadata(format!(
+                "The {} did not read all the data declared by its parameters",
+                T::NAME
+            )));
+        }
+
+        Ok(object)
+    }
+}
+
+/// Check the magic of a stream and return the size of its header
+fn parse_prefix(prefix: &[u8; STREAMING_PREFIX_SIZE]) -> Result<usize, StreamingError> {
+    let (magic, header_size) = prefix.split_at(STREAMING_MAGIC.len());
+
+    if magic != STREAMING_MAGIC {
+        return Err(StreamingError::InvalidHeader(
+            "The data does not start with the streaming format magic".to_string(),
+        ));
+    }
+
+    let header_size = u64::from_le_bytes(header_size.try_into().unwrap());
+
+    if header_size > STREAMING_MAX_HEADER_SIZE as u64 {
+        return Err(StreamingError::InvalidHeader(format!(
+            "Invalid header size {header_size}"
+        )));
+    }
+
+    Ok(header_size as usize)
+}
+
+/// Parse a whole stream held in memory, and return the metadata of the entity and a view of its
+/// data, without copy.
+///
+/// The data section of the stream must be correctly aligned for [`StreamingSerializable::Element`]
+/// in memory, this is the case if `bytes` starts at an address aligned on
+/// [`STREAMING_DATA_ALIGNMENT`], for example in a memory-mapped file.
+pub fn streaming_data_from_bytes<T: StreamingSerializable + Named>(
+    bytes: &[u8],
+) -> Result<(T::Metadata, &[T::Element]), StreamingError> {
+    check_endianness()?;
+
+    let truncated = || {
+        StreamingError::Io(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            "The stream is truncated",
+        ))
+    };
+
+    let prefix: &[u8; STREAMING_PREFIX_SIZE] = bytes
+        .get(..STREAMING_PREFIX_SIZE)
+        .ok_or_else(truncated)?
+        .try_into()
+        .unwrap();
+    let header_size = parse_prefix(prefix)?;
+
+    let header_bytes = STREAMING_PREFIX_SIZE
+        .checked_add(header_size)
+        .and_then(|header_end| bytes.get(STREAMING_PREFIX_SIZE..header_end))
+        .ok_or_else(truncated)?;
+
+    let (metadata, element_count) = parse_header::<T>(header_bytes)?;
+
+    let data_end = stream_size(
+        header_size,
+        element_count,
+        std::mem::size_of::<T::Element>(),
+    )?;
+    let data_end = usize::try_from(data_end).map_err(|_| truncated())?;
+
+    let data_bytes = bytes
+        .get(data_offset(header_size)..data_end)
+        .ok_or_else(truncated)?;
+
+    let data = bytemuck::try_cast_slice(data_bytes).map_err(|err| {
+        StreamingError::Unsupported(format!(
+            "The data of the stream can't be used in place: {err:?}"
+        ))
+    })?;
+
+    Ok((metadata, data))
+}
+
+/// Return a view of a [`FourierLweBootstrapKey`] stored in a stream held in memory, without copy.
+///
+/// See [`streaming_data_from_bytes`] for the alignment requirements of `bytes`.
+pub fn fourier_lwe_bootstrap_key_from_bytes(
+    bytes: &[u8],
+) -> Result<FourierLweBootstrapKeyView<'_>, StreamingError> {
+    let (metadata, data) = streaming_data_from_bytes::<FourierLweBootstrapKeyOwned>(bytes)?;
+
+    Ok(FourierLweBootstrapKey::from_container(
+        data,
+        metadata.input_lwe_dimension,
+        metadata.glwe_size,
+        metadata.polynomial_size,
+        metadata.decomposition_base_log,
+        metadata.decomposition_level_count,
+    ))
+}
+
+/// A [`FourierLweBootstrapKey`] stored in a read-only memory-mapped file, written with
+/// [`StreamingConfig::serialize_into`].
+///
+/// The key is loaded lazily by the operating system and the memory is shared between all the
+/// processes that map the same file.
+#[cfg(feature = "mmap")]
+pub struct MappedFourierLweBootstrapKey {
+    mmap: memmap2::Mmap,
+    metadata: FourierLweBootstrapKeyStreamingMetadata,
+    data_range: std::ops::Range<usize>,
+}
+
+#[cfg(feature = "mmap")]
+impl MappedFourierLweBootstrapKey {
+    /// Map the file at `path` and check that it holds a valid [`FourierLweBootstrapKey`].
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified while it is mapped, see [`memmap2::Mmap::map`].
+    pub unsafe fn open(path: impl AsRef<std::path::Path>) -> Result<Self, StreamingError> {
+        let file = std::fs::File::open(path)?;
+        let mmap = memmap2::Mmap::map(&file)?;
+
+        let (metadata, data) = streaming_data_from_bytes::<FourierLweBootstrapKeyOwned>(&mmap)?;
+
+        let data_start = data.as_ptr() as usize - mmap.as_ptr() as usize;
+        let data_range = data_start..data_start + std::mem::size_of_val(data);
+
+        Ok(Self {
+            mmap,
+            metadata,
+            data_range,
+        })
+    }
+
+    /// Return a view of the key, to be used in the bootstrapping algorithms
+    pub fn as_view(&self) -> FourierLweBootstrapKeyView<'_> {
+        FourierLweBootstrapKey::from_container(
+            bytemuck::cast_slice(&self.mmap[self.data_range.clone()]),
+            self.metadata.input_lwe_dimension,
+            self.metadata.glwe_size,
+            self.metadata.polynomial_size,
+            self.metadata.decomposition_base_log,
+            self.metadata.decomposition_level_count,
+        )
+    }
+}
+
+/// Free function equivalent of [`StreamingConfig::serialize_into`] with the given size limit
+pub fn streaming_serialize<T: StreamingSerializable + Named>(
+    object: &T,
+    writer: impl std::io::Write,
+    serialized_size_limit: u64,
+) -> Result<(), StreamingError> {
+    StreamingConfig::new(serialized_size_limit).serialize_into(object, writer)
+}
+
+/// Free function equivalent of [`StreamingConfig::deserialize_from`] with the given size limit
+pub fn streaming_deserialize<T: StreamingSerializable + Named>(
+    reader: impl std::io::Read,
+    serialized_size_limit: u64,
+) -> Result<T, StreamingError> {
+    StreamingConfig::new(serialized_size_limit).deserialize_from(reader)
+}
+
+/// Parameters of an [`LweBootstrapKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct LweBootstrapKeyStreamingMetadata<Scalar: UnsignedInteger> {
+    pub glwe_size: GlweSize,
+    pub polynomial_size: PolynomialSize,
+    pub decomposition_base_log: DecompositionBaseLog,
+    pub decomposition_level_count: DecompositionLevelCount,
+    pub input_lwe_dimension: LweDimension,
+    pub ciphertext_modulus: CiphertextModulus<Scalar>,
+}
+
+impl<Scalar: UnsignedInteger> StreamingSerializable for LweBootstrapKeyOwned<Scalar> {
+    type Element = Scalar;
+    type Metadata = LweBootstrapKeyStreamingMetadata<Scalar>;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        LweBootstrapKeyStreamingMetadata {
+            glwe_size: self.glwe_size(),
+            polynomial_size: self.polynomial_size(),
+            decomposition_base_log: self.decomposition_base_log(),
+            decomposition_level_count: self.decomposition_level_count(),
+            input_lwe_dimension: self.input_lwe_dimension(),
+            ciphertext_modulus: self.ciphertext_modulus(),
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        check_ggsw_parameters::<Scalar>(
+            metadata.polynomial_size,
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+        )?;
+        check_ciphertext_modulus(metadata.ciphertext_modulus, false)?;
+
+        checked_data_len(&[
+            metadata.input_lwe_dimension.0,
+            metadata.decomposition_level_count.0,
+            metadata.glwe_size.0,
+            metadata.glwe_size.0,
+            metadata.polynomial_size.0,
+        ])
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        let ggsw_list: &GgswCiphertextList<Vec<Scalar>> = self;
+        writer.write(ggsw_list.as_ref())
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        let mut bsk = Self::new(
+            Scalar::ZERO,
+            metadata.glwe_size,
+            metadata.polynomial_size,
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+            metadata.input_lwe_dimension,
+            metadata.ciphertext_modulus,
+        );
+
+        let ggsw_list: &mut GgswCiphertextList<Vec<Scalar>> = &mut bsk;
+        reader.read(ggsw_list.as_mut())?;
+
+        Ok(bsk)
+    }
+}
+
+/// Parameters of a [`SeededLweBootstrapKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct SeededLweBootstrapKeyStreamingMetadata<Scalar: UnsignedInteger> {
+    pub glwe_size: GlweSize,
+    pub polynomial_size: PolynomialSize,
+    pub decomposition_base_log: DecompositionBaseLog,
+    pub decomposition_level_count: DecompositionLevelCount,
+    pub input_lwe_dimension: LweDimension,
+    pub compression_seed: CompressionSeed,
+    pub ciphertext_modulus: CiphertextModulus<Scalar>,
+}
+
+impl<Scalar: UnsignedInteger> StreamingSerializable for SeededLweBootstrapKeyOwned<Scalar> {
+    type Element = Scalar;
+    type Metadata = SeededLweBootstrapKeyStreamingMetadata<Scalar>;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        SeededLweBootstrapKeyStreamingMetadata {
+            glwe_size: self.glwe_size(),
+            polynomial_size: self.polynomial_size(),
+            decomposition_base_log: self.decomposition_base_log(),
+            decomposition_level_count: self.decomposition_level_count(),
+            input_lwe_dimension: self.input_lwe_dimension(),
+            compression_seed: self.compression_seed(),
+            ciphertext_modulus: self.ciphertext_modulus(),
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        check_ggsw_parameters::<Scalar>(
+            metadata.polynomial_size,
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+        )?;
+        check_ciphertext_modulus(metadata.ciphertext_modulus, true)?;
+
+        // Only the bodies of the GLWE ciphertexts are stored
+        checked_data_len(&[
+            metadata.input_lwe_dimension.0,
+            metadata.decomposition_level_count.0,
+            metadata.glwe_size.0,
+            metadata.polynomial_size.0,
+        ])
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        let ggsw_list: &SeededGgswCiphertextList<Vec<Scalar>> = self;
+        writer.write(ggsw_list.as_ref())
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        let mut bsk = Self::new(
+            Scalar::ZERO,
+            metadata.glwe_size,
+            metadata.polynomial_size,
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+            metadata.input_lwe_dimension,
+            metadata.compression_seed,
+            metadata.ciphertext_modulus,
+        );
+
+        let ggsw_list: &mut SeededGgswCiphertextList<Vec<Scalar>> = &mut bsk;
+        reader.read(ggsw_list.as_mut())?;
+
+        Ok(bsk)
+    }
+}
+
+/// Parameters of an [`LweMultiBitBootstrapKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct LweMultiBitBootstrapKeyStreamingMetadata<Scalar: UnsignedInteger> {
+    pub glwe_size: GlweSize,
+    pub polynomial_size: PolynomialSize,
+    pub decomposition_base_log: DecompositionBaseLog,
+    pub decomposition_level_count: DecompositionLevelCount,
+    pub input_lwe_dimension: LweDimension,
+    pub grouping_factor: LweBskGroupingFactor,
+    pub ciphertext_modulus: CiphertextModulus<Scalar>,
+}
+
+impl<Scalar: UnsignedInteger> StreamingSerializable for LweMultiBitBootstrapKeyOwned<Scalar> {
+    type Element = Scalar;
+    type Metadata = LweMultiBitBootstrapKeyStreamingMetadata<Scalar>;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        LweMultiBitBootstrapKeyStreamingMetadata {
+            glwe_size: self.glwe_size(),
+            polynomial_size: self.polynomial_size(),
+            decomposition_base_log: self.decomposition_base_log(),
+            decomposition_level_count: self.decomposition_level_count(),
+            input_lwe_dimension: self.input_lwe_dimension(),
+            grouping_factor: self.grouping_factor(),
+            ciphertext_modulus: self.ciphertext_modulus(),
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        check_ggsw_parameters::<Scalar>(
+            metadata.polynomial_size,
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+        )?;
+        check_ciphertext_modulus(metadata.ciphertext_modulus, false)?;
+
+        checked_data_len(&[
+            multi_bit_ggsw_count(metadata.input_lwe_dimension, metadata.grouping_factor)?,
+            metadata.decomposition_level_count.0,
+            metadata.glwe_size.0,
+            metadata.glwe_size.0,
+            metadata.polynomial_size.0,
+        ])
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        let ggsw_list: &GgswCiphertextList<Vec<Scalar>> = self;
+        writer.write(ggsw_list.as_ref())
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        let mut bsk = Self::new(
+            Scalar::ZERO,
+            metadata.glwe_size,
+            metadata.polynomial_size,
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+            metadata.input_lwe_dimension,
+            metadata.grouping_factor,
+            metadata.ciphertext_modulus,
+        );
+
+        let ggsw_list: &mut GgswCiphertextList<Vec<Scalar>> = &mut bsk;
+        reader.read(ggsw_list.as_mut())?;
+
+        Ok(bsk)
+    }
+}
+
+/// Parameters of a [`SeededLweMultiBitBootstrapKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct SeededLweMultiBitBootstrapKeyStreamingMetadata<Scalar: UnsignedInteger> {
+    pub glwe_size: GlweSize,
+    pub polynomial_size: PolynomialSize,
+    pub decomposition_base_log: DecompositionBaseLog,
+    pub decomposition_level_count: DecompositionLevelCount,
+    pub input_lwe_dimension: LweDimension,
+    pub grouping_factor: LweBskGroupingFactor,
+    pub compression_seed: CompressionSeed,
+    pub ciphertext_modulus: CiphertextModulus<Scalar>,
+}
+
+impl<Scalar: UnsignedInteger> StreamingSerializable for SeededLweMultiBitBootstrapKeyOwned<Scalar> {
+    type Element = Scalar;
+    type Metadata = SeededLweMultiBitBootstrapKeyStreamingMetadata<Scalar>;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        SeededLweMultiBitBootstrapKeyStreamingMetadata {
+            glwe_size: self.glwe_size(),
+            polynomial_size: self.polynomial_size(),
+            decomposition_base_log: self.decomposition_base_log(),
+            decomposition_level_count: self.decomposition_level_count(),
+            input_lwe_dimension: self.input_lwe_dimension(),
+            grouping_factor: self.grouping_factor(),
+            compression_seed: self.compression_seed(),
+            ciphertext_modulus: self.ciphertext_modulus(),
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        check_ggsw_parameters::<Scalar>(
+            metadata.polynomial_size,
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+        )?;
+        check_ciphertext_modulus(metadata.ciphertext_modulus, true)?;
+
+        // Only the bodies of the GLWE ciphertexts are stored
+        checked_data_len(&[
+            multi_bit_ggsw_count(metadata.input_lwe_dimension, metadata.grouping_factor)?,
+            metadata.decomposition_level_count.0,
+            metadata.glwe_size.0,
+            metadata.polynomial_size.0,
+        ])
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        let ggsw_list: &SeededGgswCiphertextList<Vec<Scalar>> = self;
+        writer.write(ggsw_list.as_ref())
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        let mut bsk = Self::new(
+            Scalar::ZERO,
+            metadata.glwe_size,
+            metadata.polynomial_size,
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+            metadata.input_lwe_dimension,
+            metadata.grouping_factor,
+            metadata.compression_seed,
+            metadata.ciphertext_modulus,
+        );
+
+        let ggsw_list: &mut SeededGgswCiphertextList<Vec<Scalar>> = &mut bsk;
+        reader.read(ggsw_list.as_mut())?;
+
+        Ok(bsk)
+    }
+}
+
+/// Parameters of an [`LweKeyswitchKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct LweKeyswitchKeyStreamingMetadata<Scalar: UnsignedInteger> {
+    pub decomposition_base_log: DecompositionBaseLog,
+    pub decomposition_level_count: DecompositionLevelCount,
+    pub input_key_lwe_dimension: LweDimension,
+    pub output_key_lwe_dimension: LweDimension,
+    pub ciphertext_modulus: CiphertextModulus<Scalar>,
+}
+
+impl<Scalar: UnsignedInteger> StreamingSerializable for LweKeyswitchKeyOwned<Scalar> {
+    type Element = Scalar;
+    type Metadata = LweKeyswitchKeyStreamingMetadata<Scalar>;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        LweKeyswitchKeyStreamingMetadata {
+            decomposition_base_log: self.decomposition_base_log(),
+            decomposition_level_count: self.decomposition_level_count(),
+            input_key_lwe_dimension: self.input_key_lwe_dimension(),
+            output_key_lwe_dimension: self.output_key_lwe_dimension(),
+            ciphertext_modulus: self.ciphertext_modulus(),
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        check_decomposition::<Scalar>(
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+        )?;
+        check_ciphertext_modulus(metadata.ciphertext_modulus, false)?;
+
+        checked_data_len(&[
+            metadata.input_key_lwe_dimension.0,
+            metadata.decomposition_level_count.0,
+            checked_size(metadata.output_key_lwe_dimension.0)?,
+        ])
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        writer.write(self.as_ref())
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        let mut ksk = Self::new(
+            Scalar::ZERO,
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+            metadata.input_key_lwe_dimension,
+            metadata.output_key_lwe_dimension,
+            metadata.ciphertext_modulus,
+        );
+        reader.read(ksk.as_mut())?;
+
+        Ok(ksk)
+    }
+}
+
+/// Parameters of a [`SeededLweKeyswitchKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct SeededLweKeyswitchKeyStreamingMetadata<Scalar: UnsignedInteger> {
+    pub decomposition_base_log: DecompositionBaseLog,
+    pub decomposition_level_count: DecompositionLevelCount,
+    pub input_key_lwe_dimension: LweDimension,
+    pub output_key_lwe_dimension: LweDimension,
+    pub compression_seed: CompressionSeed,
+    pub ciphertext_modulus: CiphertextModulus<Scalar>,
+}
+
+impl<Scalar: UnsignedInteger> StreamingSerializable for SeededLweKeyswitchKeyOwned<Scalar> {
+    type Element = Scalar;
+    type Metadata = SeededLweKeyswitchKeyStreamingMetadata<Scalar>;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        SeededLweKeyswitchKeyStreamingMetadata {
+            decomposition_base_log: self.decomposition_base_log(),
+            decomposition_level_count: self.decomposition_level_count(),
+            input_key_lwe_dimension: self.input_key_lwe_dimension(),
+            output_key_lwe_dimension: self.output_key_lwe_dimension(),
+            compression_seed: self.compression_seed(),
+            ciphertext_modulus: self.ciphertext_modulus(),
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        check_decomposition::<Scalar>(
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+        )?;
+        check_ciphertext_modulus(metadata.ciphertext_modulus, true)?;
+        // The output LWE size is computed when the key is built
+        checked_size(metadata.output_key_lwe_dimension.0)?;
+
+        // Only the bodies of the LWE ciphertexts are stored
+        checked_data_len(&[
+            metadata.input_key_lwe_dimension.0,
+            metadata.decomposition_level_count.0,
+        ])
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        writer.write(self.as_ref())
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        let mut ksk = Self::new(
+            Scalar::ZERO,
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+            metadata.input_key_lwe_dimension,
+            metadata.output_key_lwe_dimension,
+            metadata.compression_seed,
+            metadata.ciphertext_modulus,
+        );
+        reader.read(ksk.as_mut())?;
+
+        Ok(ksk)
+    }
+}
+
+/// Parameters of an [`LwePackingKeyswitchKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct LwePackingKeyswitchKeyStreamingMetadata<Scalar: UnsignedInteger> {
+    pub decomposition_base_log: DecompositionBaseLog,
+    pub decomposition_level_count: DecompositionLevelCount,
+    pub input_key_lwe_dimension: LweDimension,
+    pub output_key_glwe_dimension: GlweDimension,
+    pub output_key_polynomial_size: PolynomialSize,
+    pub ciphertext_modulus: CiphertextModulus<Scalar>,
+}
+
+impl<Scalar: UnsignedInteger> StreamingSerializable for LwePackingKeyswitchKeyOwned<Scalar> {
+    type Element = Scalar;
+    type Metadata = LwePackingKeyswitchKeyStreamingMetadata<Scalar>;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        LwePackingKeyswitchKeyStreamingMetadata {
+            decomposition_base_log: self.decomposition_base_log(),
+            decomposition_level_count: self.decomposition_level_count(),
+            input_key_lwe_dimension: self.input_key_lwe_dimension(),
+            output_key_glwe_dimension: self.output_key_glwe_dimension(),
+            output_key_polynomial_size: self.output_key_polynomial_size(),
+            ciphertext_modulus: self.ciphertext_modulus(),
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        check_polynomial_size(metadata.output_key_polynomial_size)?;
+        check_decomposition::<Scalar>(
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+        )?;
+        check_ciphertext_modulus(metadata.ciphertext_modulus, false)?;
+
+        checked_data_len(&[
+            metadata.input_key_lwe_dimension.0,
+            metadata.decomposition_level_count.0,
+            checked_size(metadata.output_key_glwe_dimension.0)?,
+            metadata.output_key_polynomial_size.0,
+        ])
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        writer.write(self.as_ref())
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        let mut pksk = Self::new(
+            Scalar::ZERO,
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+            metadata.input_key_lwe_dimension,
+            metadata.output_key_glwe_dimension,
+            metadata.output_key_polynomial_size,
+            metadata.ciphertext_modulus,
+        );
+        reader.read(pksk.as_mut())?;
+
+        Ok(pksk)
+    }
+}
+
+/// Parameters of a [`SeededLwePackingKeyswitchKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct SeededLwePackingKeyswitchKeyStreamingMetadata<Scalar: UnsignedInteger> {
+    pub decomposition_base_log: DecompositionBaseLog,
+    pub decomposition_level_count: DecompositionLevelCount,
+    pub input_key_lwe_dimension: LweDimension,
+    pub output_key_glwe_dimension: GlweDimension,
+    pub output_key_polynomial_size: PolynomialSize,
+    pub compression_seed: CompressionSeed,
+    pub ciphertext_modulus: CiphertextModulus<Scalar>,
+}
+
+impl<Scalar: UnsignedInteger> StreamingSerializable for SeededLwePackingKeyswitchKeyOwned<Scalar> {
+    type Element = Scalar;
+    type Metadata = SeededLwePackingKeyswitchKeyStreamingMetadata<Scalar>;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        SeededLwePackingKeyswitchKeyStreamingMetadata {
+            decomposition_base_log: self.decomposition_base_log(),
+            decomposition_level_count: self.decomposition_level_count(),
+            input_key_lwe_dimension: self.input_key_lwe_dimension(),
+            output_key_glwe_dimension: self.output_key_glwe_dimension(),
+            output_key_polynomial_size: self.output_key_polynomial_size(),
+            compression_seed: self.compression_seed(),
+            ciphertext_modulus: self.ciphertext_modulus(),
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        check_polynomial_size(metadata.output_key_polynomial_size)?;
+        check_decomposition::<Scalar>(
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+        )?;
+        check_ciphertext_modulus(metadata.ciphertext_modulus, true)?;
+        // The output GLWE size is computed when the key is built
+        checked_size(metadata.output_key_glwe_dimension.0)?;
+
+        // Only the bodies of the GLWE ciphertexts are stored
+        checked_data_len(&[
+            metadata.input_key_lwe_dimension.0,
+            metadata.decomposition_level_count.0,
+            metadata.output_key_polynomial_size.0,
+        ])
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        writer.write(self.as_ref())
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        let mut pksk = Self::new(
+            Scalar::ZERO,
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+            metadata.input_key_lwe_dimension,
+            metadata.output_key_glwe_dimension,
+            metadata.output_key_polynomial_size,
+            metadata.compression_seed,
+            metadata.ciphertext_modulus,
+        );
+        reader.read(pksk.as_mut())?;
+
+        Ok(pksk)
+    }
+}
+
+/// Parameters of an [`LweCiphertextList`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct LweCiphertextListStreamingMetadata<Scalar: UnsignedInteger> {
+    pub lwe_size: LweSize,
+    pub lwe_ciphertext_count: LweCiphertextCount,
+    pub ciphertext_modulus: CiphertextModulus<Scalar>,
+}
+
+impl<Scalar: UnsignedInteger> StreamingSerializable for LweCiphertextListOwned<Scalar> {
+    type Element = Scalar;
+    type Metadata = LweCiphertextListStreamingMetadata<Scalar>;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        LweCiphertextListStreamingMetadata {
+            lwe_size: self.lwe_size(),
+            lwe_ciphertext_count: self.lwe_ciphertext_count(),
+            ciphertext_modulus: self.ciphertext_modulus(),
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        check_ciphertext_modulus(metadata.ciphertext_modulus, false)?;
+
+        checked_data_len(&[metadata.lwe_size.0, metadata.lwe_ciphertext_count.0])
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        writer.write(self.as_ref())
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        let mut list = Self::new(
+            Scalar::ZERO,
+            metadata.lwe_size,
+            metadata.lwe_ciphertext_count,
+            metadata.ciphertext_modulus,
+        );
+        reader.read(list.as_mut())?;
+
+        Ok(list)
+    }
+}
+
+/// Parameters of a [`SeededLweCiphertextList`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct SeededLweCiphertextListStreamingMetadata<Scalar: UnsignedInteger> {
+    pub lwe_size: LweSize,
+    pub lwe_ciphertext_count: LweCiphertextCount,
+    pub compression_seed: CompressionSeed,
+    pub ciphertext_modulus: CiphertextModulus<Scalar>,
+}
+
+impl<Scalar: UnsignedInteger> StreamingSerializable for SeededLweCiphertextListOwned<Scalar> {
+    type Element = Scalar;
+    type Metadata = SeededLweCiphertextListStreamingMetadata<Scalar>;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        SeededLweCiphertextListStreamingMetadata {
+            lwe_size: self.lwe_size(),
+            lwe_ciphertext_count: self.lwe_ciphertext_count(),
+            compression_seed: self.compression_seed(),
+            ciphertext_modulus: self.ciphertext_modulus(),
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        check_ciphertext_modulus(metadata.ciphertext_modulus, true)?;
+
+        // Only the bodies of the LWE ciphertexts are stored
+        checked_data_len(&[metadata.lwe_size.0, metadata.lwe_ciphertext_count.0])?;
+        Ok(metadata.lwe_ciphertext_count.0)
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        writer.write(self.as_ref())
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        let mut list = Self::new(
+            Scalar::ZERO,
+            metadata.lwe_size,
+            metadata.lwe_ciphertext_count,
+            metadata.compression_seed,
+            metadata.ciphertext_modulus,
+        );
+        reader.read(list.as_mut())?;
+
+        Ok(list)
+    }
+}
+
+/// Parameters of a [`FourierLweBootstrapKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct FourierLweBootstrapKeyStreamingMetadata {
+    pub input_lwe_dimension: LweDimension,
+    pub glwe_size: GlweSize,
+    pub polynomial_size: PolynomialSize,
+    pub decomposition_base_log: DecompositionBaseLog,
+    pub decomposition_level_count: DecompositionLevelCount,
+}
+
+/// Check the polynomial size of a key in the Fourier domain, which stores half of the
+/// coefficients of each polynomial
+fn check_fourier_polynomial_size(polynomial_size: PolynomialSize) -> Result<(), String> {
+    check_polynomial_size(polynomial_size)?;
+
+    if polynomial_size.0 < 2 {
+        return Err(format!(
+            "Invalid polynomial size {} for a key in the Fourier domain",
+            polynomial_size.0
+        ));
+    }
+
+    Ok(())
+}
+
+impl StreamingSerializable for FourierLweBootstrapKeyOwned {
+    type Element = c64;
+    type Metadata = FourierLweBootstrapKeyStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        FourierLweBootstrapKeyStreamingMetadata {
+            input_lwe_dimension: self.input_lwe_dimension(),
+            glwe_size: self.glwe_size(),
+            polynomial_size: self.polynomial_size(),
+            decomposition_base_log: self.decomposition_base_log(),
+            decomposition_level_count: self.decomposition_level_count(),
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        check_fourier_polynomial_size(metadata.polynomial_size)?;
+        check_decomposition::<u64>(
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+        )?;
+
+        checked_data_len(&[
+            metadata.input_lwe_dimension.0,
+            metadata.decomposition_level_count.0,
+            metadata.glwe_size.0,
+            metadata.glwe_size.0,
+            metadata.polynomial_size.to_fourier_polynomial_size().0,
+        ])
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        writer.write(self.as_view().data())
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        let mut fourier_bsk = Self::new(
+            metadata.input_lwe_dimension,
+            metadata.glwe_size,
+            metadata.polynomial_size,
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+        );
+        reader.read(fourier_bsk.as_mut_view().data())?;
+
+        Ok(fourier_bsk)
+    }
+}
+
+/// Parameters of a [`FourierLweMultiBitBootstrapKey`] in the streaming format
+#[derive(Serialize, Deserialize)]
+pub struct FourierLweMultiBitBootstrapKeyStreamingMetadata {
+    pub input_lwe_dimension: LweDimension,
+    pub glwe_size: GlweSize,
+    pub polynomial_size: PolynomialSize,
+    pub decomposition_base_log: DecompositionBaseLog,
+    pub decomposition_level_count: DecompositionLevelCount,
+    pub grouping_factor: LweBskGroupingFactor,
+}
+
+impl StreamingSerializable for FourierLweMultiBitBootstrapKeyOwned {
+    type Element = c64;
+    type Metadata = FourierLweMultiBitBootstrapKeyStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        FourierLweMultiBitBootstrapKeyStreamingMetadata {
+            input_lwe_dimension: self.input_lwe_dimension(),
+            glwe_size: self.glwe_size(),
+            polynomial_size: self.polynomial_size(),
+            decomposition_base_log: self.decomposition_base_log(),
+            decomposition_level_count: self.decomposition_level_count(),
+            grouping_factor: self.grouping_factor(),
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        check_fourier_polynomial_size(metadata.polynomial_size)?;
+        check_decomposition::<u64>(
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+        )?;
+
+        checked_data_len(&[
+            multi_bit_ggsw_count(metadata.input_lwe_dimension, metadata.grouping_factor)?,
+            metadata.decomposition_level_count.0,
+            metadata.glwe_size.0,
+            metadata.glwe_size.0,
+            metadata.polynomial_size.to_fourier_polynomial_size().0,
+        ])
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        writer.write(self.as_view().data())
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        let mut fourier_bsk = Self::new(
+            metadata.input_lwe_dimension,
+            metadata.glwe_size,
+            metadata.polynomial_size,
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+            metadata.grouping_factor,
+        );
+        reader.read(fourier_bsk.as_mut_view().data())?;
+
+        Ok(fourier_bsk)
+    }
+}
+
+/// The data of a [`Fourier128LweBootstrapKey`] is made of four lists of `f64`, written one after
+/// the other
+impl StreamingSerializable for Fourier128LweBootstrapKeyOwned {
+    type Element = f64;
+    type Metadata = FourierLweBootstrapKeyStreamingMetadata;
+
+    fn streaming_metadata(&self) -> Self::Metadata {
+        FourierLweBootstrapKeyStreamingMetadata {
+            input_lwe_dimension: self.input_lwe_dimension(),
+            glwe_size: self.glwe_size(),
+            polynomial_size: self.polynomial_size(),
+            decomposition_base_log: self.decomposition_base_log(),
+            decomposition_level_count: self.decomposition_level_count(),
+        }
+    }
+
+    fn streaming_data_len(metadata: &Self::Metadata) -> Result<usize, String> {
+        check_fourier_polynomial_size(metadata.polynomial_size)?;
+        check_decomposition::<u128>(
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+        )?;
+
+        checked_data_len(&[
+            4,
+            metadata.input_lwe_dimension.0,
+            metadata.decomposition_level_count.0,
+            metadata.glwe_size.0,
+            metadata.glwe_size.0,
+            metadata.polynomial_size.to_fourier_polynomial_size().0,
+        ])
+    }
+
+    fn write_streaming_data(
+        &self,
+        writer: &mut StreamingDataWriter<'_>,
+    ) -> Result<(), StreamingError> {
+        let (data_re0, data_re1, data_im0, data_im1) = self.as_view().data();
+
+        for data in [data_re0, data_re1, data_im0, data_im1] {
+            writer.write(data)?;
+        }
+
+        Ok(())
+    }
+
+    fn read_streaming_data(
+        metadata: Self::Metadata,
+        reader: &mut StreamingDataReader<'_>,
+    ) -> Result<Self, StreamingError> {
+        let mut fourier_bsk = Self::new(
+            metadata.input_lwe_dimension,
+            metadata.glwe_size,
+            metadata.polynomial_size,
+            metadata.decomposition_base_log,
+            metadata.decomposition_level_count,
+        );
+
+        let (data_re0, data_re1, data_im0, data_im1) = fourier_bsk.as_mut_view().data();
+        for data in [data_re0, data_re1, data_im0, data_im1] {
+            reader.read(data)?;
+        }
+
+        Ok(fourier_bsk)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core_crypto::prelude::*;
+
+    fn fill_with_pattern<T: Copy>(data: &mut [T], f: impl Fn(usize) -> T) {
+        for (idx, val) in data.iter_mut().enumerate() {
+            *val = f(idx);
+        }
+    }
+
+    #[test]
+    fn streaming_lwe_bootstrap_key_round_trip() {
+        let glwe_size = GlweSize(2);
+        let polynomial_size = PolynomialSize(256);
+        let decomp_base_log = DecompositionBaseLog(10);
+        let decomp_level_count = DecompositionLevelCount(2);
+        let ciphertext_modulus = CiphertextModulus::new_native();
+
+        let mut data = LweBootstrapKey::new(
+            0u64,
+            glwe_size,
+            polynomial_size,
+            decomp_base_log,
+            decomp_level_count,
+            LweDimension(10),
+            ciphertext_modulus,
+        )
+        .into_container();
+        fill_with_pattern(&mut data, |idx| {
+            (idx as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        });
+
+        let bsk = LweBootstrapKey::from_container(
+            data,
+            glwe_size,
+            polynomial_size,
+            decomp_base_log,
+            decomp_level_count,
+            ciphertext_modulus,
+        );
+
+        // Use a small chunk size to go through the chunked read and write
+        let config = StreamingConfig::new(1 << 20).with_chunk_byte_size(1000);
+
+        let mut buffer = vec![];
+        config.serialize_into(&bsk, &mut buffer).unwrap();
+
+        assert_eq!(config.serialized_size(&bsk).unwrap() as usize, buffer.len());
+
+        let deser: LweBootstrapKeyOwned<u64> = config.deserialize_from(buffer.as_slice()).unwrap();
+        assert_eq!(deser, bsk);
+
+        // Wrong type
+        assert!(matches!(
+            config.deserialize_from::<LweKeyswitchKeyOwned<u64>>(buffer.as_slice()),
+            Err(StreamingError::InvalidHeader(_))
+        ));
+
+        // Wrong scalar type
+        assert!(matches!(
+            config.deserialize_from::<LweBootstrapKeyOwned<u32>>(buffer.as_slice()),
+            Err(StreamingError::InvalidHeader(_))
+        ));
+
+        // Size limit
+        assert!(matches!(
+            StreamingConfig::new(buffer.len() as u64 - 1)
+                .deserialize_from::<LweBootstrapKeyOwned<u64>>(buffer.as_slice()),
+            Err(StreamingError::SizeLimitExceeded { .. })
+        ));
+        assert!(matches!(
+            StreamingConfig::new(buffer.len() as u64 - 1).serialize_into(&bsk, &mut vec![]),
+            Err(StreamingError::SizeLimitExceeded { .. })
+        ));
+
+        // Truncated data
+        assert!(matches!(
+            config.deserialize_from::<LweBootstrapKeyOwned<u64>>(&buffer[..buffer.len() - 1]),
+            Err(StreamingError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn streaming_keyswitch_keys_round_trip() {
+        let mut ksk = LweKeyswitchKey::new(
+            0u32,
+            DecompositionBaseLog(3),
+            DecompositionLevelCount(5),
+            LweDimension(64),
+            LweDimension(32),
+            CiphertextModulus::new_native(),
+        );
+        fill_with_pattern(ksk.as_mut(), |idx| idx as u32);
+
+        let mut buffer = vec![];
+        streaming_serialize(&ksk, &mut buffer, 1 << 20).unwrap();
+        let deser: LweKeyswitchKeyOwned<u32> =
+            streaming_deserialize(buffer.as_slice(), 1 << 20).unwrap();
+        assert_eq!(deser, ksk);
+
+        let mut pksk = LwePackingKeyswitchKey::new(
+            0u64,
+            DecompositionBaseLog(3),
+            DecompositionLevelCount(2),
+            LweDimension(32),
+            GlweDimension(1),
+            PolynomialSize(128),
+            CiphertextModulus::try_new_power_of_2(62).unwrap(),
+        );
+        fill_with_pattern(pksk.as_mut(), |idx| idx as u64);
+
+        let mut buffer = vec![];
+        streaming_serialize(&pksk, &mut buffer, 1 << 20).unwrap();
+        let deser: LwePackingKeyswitchKeyOwned<u64> =
+            streaming_deserialize(buffer.as_slice(), 1 << 20).unwrap();
+        assert_eq!(deser, pksk);
+    }
+
+    #[test]
+    fn streaming_seeded_and_multi_bit_keys_round_trip() {
+        let compression_seed = CompressionSeed {
+            seed: crate::Seed(42),
+        };
+
+        let mut seeded_bsk = SeededLweBootstrapKey::new(
+            0u64,
+            GlweSize(2),
+            PolynomialSize(256),
+            DecompositionBaseLog(10),
+            DecompositionLevelCount(2),
+            LweDimension(10),
+            compression_seed,
+            CiphertextModulus::new_native(),
+        );
+        fill_with_pattern(seeded_bsk.as_mut(), |idx| idx as u64);
+
+        let mut buffer = vec![];
+        streaming_serialize(&seeded_bsk, &mut buffer, 1 << 20).unwrap();
+        let deser: SeededLweBootstrapKeyOwned<u64> =
+            streaming_deserialize(buffer.as_slice(), 1 << 20).unwrap();
+        assert_eq!(deser, seeded_bsk);
+
+        let mut multi_bit_bsk = LweMultiBitBootstrapKey::new(
+            0u64,
+            GlweSize(2),
+            PolynomialSize(128),
+            DecompositionBaseLog(10),
+            DecompositionLevelCount(2),
+            LweDimension(6),
+            LweBskGroupingFactor(3),
+            CiphertextModulus::new_native(),
+        );
+        fill_with_pattern(multi_bit_bsk.as_mut(), |idx| idx as u64);
+
+        let mut buffer = vec![];
+        streaming_serialize(&multi_bit_bsk, &mut buffer, 1 << 22).unwrap();
+        let deser: LweMultiBitBootstrapKeyOwned<u64> =
+            streaming_deserialize(buffer.as_slice(), 1 << 22).unwrap();
+        assert_eq!(deser, multi_bit_bsk);
+
+        let mut seeded_ksk = SeededLweKeyswitchKey::new(
+            0u64,
+            DecompositionBaseLog(3),
+            DecompositionLevelCount(5),
+            LweDimension(64),
+            LweDimension(32),
+            compression_seed,
+            CiphertextModulus::new_native(),
+        );
+        fill_with_pattern(seeded_ksk.as_mut(), |idx| idx as u64);
+
+        let mut buffer = vec![];
+        streaming_serialize(&seeded_ksk, &mut buffer, 1 << 20).unwrap();
+        let deser: SeededLweKeyswitchKeyOwned<u64> =
+            streaming_deserialize(buffer.as_slice(), 1 << 20).unwrap();
+        assert_eq!(deser, seeded_ksk);
+
+        let mut fourier_128_bsk = Fourier128LweBootstrapKey::new(
+            LweDimension(4),
+            GlweSize(2),
+            PolynomialSize(256),
+            DecompositionBaseLog(20),
+            DecompositionLevelCount(3),
+        );
+        let (data_re0, data_re1, data_im0, data_im1) = fourier_128_bsk.as_mut_view().data();
+        for (part, data) in [data_re0, data_re1, data_im0, data_im1]
+            .into_iter()
+            .enumerate()
+        {
+            fill_with_pattern(data, |idx| (part * idx) as f64);
+        }
+
+        let mut buffer = vec![];
+        streaming_serialize(&fourier_128_bsk, &mut buffer, 1 << 22).unwrap();
+        let deser: Fourier128LweBootstrapKeyOwned =
+            streaming_deserialize(buffer.as_slice(), 1 << 22).unwrap();
+        assert_eq!(deser, fourier_128_bsk);
+    }
+
+    /// Build a stream holding `metadata` without validating it, followed by `element_count` zero
+    /// elements
+    fn unchecked_stream<T: StreamingSerializable + Named>(
+        metadata: &T::Metadata,
+        element_count: usize,
+    ) -> Vec<u8> {
+        let header = StreamingHeader {
+            version: Cow::Borrowed(STREAMING_VERSION),
+            name: Cow::Borrowed(T::NAME),
+            element_size: std::mem::size_of::<T::Element>() as u64,
+            element_count: element_count as u64,
+            metadata: bincode_options().serialize(metadata).unwrap(),
+        };
+        let header_bytes = bincode_options().serialize(&header).unwrap();
+
+        let mut stream = STREAMING_MAGIC.to_vec();
+        stream.extend_from_slice(&(header_bytes.len() as u64).to_le_bytes());
+        stream.extend_from_slice(&header_bytes);
+        stream.resize(
+            data_offset(header_bytes.len()) + element_count * std::mem::size_of::<T::Element>(),
+            0,
+        );
+        stream
+    }
+
+    fn assert_invalid_metadata<T: StreamingSerializable + Named>(
+        metadata: &T::Metadata,
+        element_count: usize,
+    ) {
+        let stream = unchecked_stream::<T>(metadata, element_count);
+        assert!(matches!(
+            StreamingConfig::new_with_unlimited_size().deserialize_from::<T>(stream.as_slice()),
+            Err(StreamingError::InvalidMetadata(_))
+        ));
+    }
+
+    #[test]
+    fn streaming_malicious_metadata() {
+        let bsk = LweBootstrapKey::new(
+            0u64,
+            GlweSize(2),
+            PolynomialSize(256),
+            DecompositionBaseLog(10),
+            DecompositionLevelCount(2),
+            LweDimension(10),
+            CiphertextModulus::new_native(),
+        );
+        let element_count = bsk.as_ref().len();
+
+        // The parameters are consistent with the data
+        let stream =
+            unchecked_stream::<LweBootstrapKeyOwned<u64>>(&bsk.streaming_metadata(), element_count);
+        let deser: LweBootstrapKeyOwned<u64> =
+            streaming_deserialize(stream.as_slice(), 1 << 20).unwrap();
+        assert_eq!(deser, bsk);
+
+        // Wrong number of elements
+        assert_invalid_metadata::<LweBootstrapKeyOwned<u64>>(
+            &bsk.streaming_metadata(),
+            element_count - 1,
+        );
+
+        // Polynomial size that is not a power of two
+        let mut metadata = bsk.streaming_metadata();
+        metadata.polynomial_size = PolynomialSize(200);
+        assert_invalid_metadata::<LweBootstrapKeyOwned<u64>>(&metadata, 0);
+
+        // Decomposition larger than the scalar
+        let mut metadata = bsk.streaming_metadata();
+        metadata.decomposition_base_log = DecompositionBaseLog(40);
+        assert_invalid_metadata::<LweBootstrapKeyOwned<u64>>(&metadata, element_count);
+
+        // Null parameters
+        let mut metadata = bsk.streaming_metadata();
+        metadata.decomposition_level_count = DecompositionLevelCount(0);
+        assert_invalid_metadata::<LweBootstrapKeyOwned<u64>>(&metadata, 0);
+        let mut metadata = bsk.streaming_metadata();
+        metadata.input_lwe_dimension = LweDimension(0);
+        assert_invalid_metadata::<LweBootstrapKeyOwned<u64>>(&metadata, 0);
+
+        // Sizes overflowing
+        let mut metadata = bsk.streaming_metadata();
+        metadata.input_lwe_dimension = LweDimension(usize::MAX);
+        assert_invalid_metadata::<LweBootstrapKeyOwned<u64>>(&metadata, 0);
+        let mut metadata = bsk.streaming_metadata();
+        metadata.glwe_size = GlweSize(usize::MAX);
+        assert_invalid_metadata::<LweBootstrapKeyOwned<u64>>(&metadata, 0);
+
+        // Seeded entities require a power of two modulus
+        let seeded_ksk = SeededLweKeyswitchKey::new(
+            0u64,
+            DecompositionBaseLog(3),
+            DecompositionLevelCount(5),
+            LweDimension(64),
+            LweDimension(32),
+            CompressionSeed {
+                seed: crate::Seed(42),
+            },
+            CiphertextModulus::new_native(),
+        );
+        let mut metadata = seeded_ksk.streaming_metadata();
+        metadata.ciphertext_modulus =
+            CiphertextModulus::try_new((1 << 64) - (1 << 32) + 1).unwrap();
+        assert_invalid_metadata::<SeededLweKeyswitchKeyOwned<u64>>(
+            &metadata,
+            seeded_ksk.as_ref().len(),
+        );
+
+        // Grouping factor not dividing the input dimension
+        let multi_bit_bsk = LweMultiBitBootstrapKey::new(
+            0u64,
+            GlweSize(2),
+            PolynomialSize(128),
+            DecompositionBaseLog(10),
+            DecompositionLevelCount(2),
+            LweDimension(6),
+            LweBskGroupingFactor(3),
+            CiphertextModulus::new_native(),
+        );
+        let mut metadata = multi_bit_bsk.streaming_metadata();
+        metadata.grouping_factor = LweBskGroupingFactor(4);
+        assert_invalid_metadata::<LweMultiBitBootstrapKeyOwned<u64>>(&metadata, 0);
+        metadata.grouping_factor = LweBskGroupingFactor(0);
+        assert_invalid_metadata::<LweMultiBitBootstrapKeyOwned<u64>>(&metadata, 0);
+
+        // Keys in the Fourier domain require at least two coefficients
+        let mut metadata = test_fourier_lwe_bootstrap_key().streaming_metadata();
+        metadata.polynomial_size = PolynomialSize(1);
+        assert_invalid_metadata::<FourierLweBootstrapKeyOwned>(&metadata, 0);
+    }
+
+    #[test]
+    fn streaming_high_level_server_keys_round_trip() {
+        use crate::shortint::parameters::{
+            COMP_PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128,
+            NOISE_SQUASHING_PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128,
+            PARAM_KEYSWITCH_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128,
+            PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128,
+            PARAM_PKE_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128,
+        };
+        use crate::shortint::server_key::ShortintBootstrappingKeyStreamingMetadata;
+        use crate::{ClientKey, CompressedServerKey, ConfigBuilder, ServerKey};
+
+        let config =
+            ConfigBuilder::with_custom_parameters(PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128)
+                .use_dedicated_compact_public_key_parameters((
+                    PARAM_PKE_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128,
+                    PARAM_KEYSWITCH_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128,
+                ))
+                .enable_compression(COMP_PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128)
+                .enable_noise_squashing(
+                    NOISE_SQUASHING_PARAM_MESSAGE_2_CARRY_2_KS_PBS_TUNIFORM_2M128,
+                )
+                .build();
+
+        let cks = ClientKey::generate(config);
+        let compressed_sks = CompressedServerKey::new(&cks);
+        let sks = compressed_sks.decompress();
+
+        // The keys don't implement PartialEq, they are compared through their serde encoding
+        let config = StreamingConfig::new_with_unlimited_size();
+
+        let mut buffer = vec![];
+        config.serialize_into(&sks, &mut buffer).unwrap();
+        let deser: ServerKey = config.deserialize_from(buffer.as_slice()).unwrap();
+        assert_eq!(
+            bincode::serialize(&deser).unwrap(),
+            bincode::serialize(&sks).unwrap()
+        );
+
+        let mut buffer = vec![];
+        config.serialize_into(&compressed_sks, &mut buffer).unwrap();
+        let deser: CompressedServerKey = config.deserialize_from(buffer.as_slice()).unwrap();
+        assert_eq!(
+            bincode::serialize(&deser).unwrap(),
+            bincode::serialize(&compressed_sks).unwrap()
+        );
+
+        let (_, _, _, decompression_key, _, _) = sks.clone().into_raw_parts();
+        let decompression_key = decompression_key.unwrap();
+
+        let mut buffer = vec![];
+        config
+            .serialize_into(&decompression_key, &mut buffer)
+            .unwrap();
+        let deser: crate::integer::compression_keys::DecompressionKey =
+            config.deserialize_from(buffer.as_slice()).unwrap();
+        assert_eq!(
+            bincode::serialize(&deser).unwrap(),
+            bincode::serialize(&decompression_key).unwrap()
+        );
+
+        // Invalid parameters of an inner entity are rejected before any allocation
+        let mut metadata = sks.streaming_metadata();
+        match &mut metadata.integer_key.key.bootstrapping_key {
+            ShortintBootstrappingKeyStreamingMetadata::Classic { bsk, .. } => {
+                bsk.polynomial_size = PolynomialSize(3);
+            }
+            ShortintBootstrappingKeyStreamingMetadata::MultiBit { .. } => unreachable!(),
+        }
+        assert_invalid_metadata::<ServerKey>(&metadata, 0);
+    }
+
+    fn test_fourier_lwe_bootstrap_key() -> FourierLweBootstrapKeyOwned {
+        let mut fourier_bsk = FourierLweBootstrapKey::new(
+            LweDimension(4),
+            GlweSize(2),
+            PolynomialSize(256),
+            DecompositionBaseLog(10),
+            DecompositionLevelCount(2),
+        );
+        fill_with_pattern(fourier_bsk.as_mut_view().data(), |idx| {
+            c64::new(idx as f64, -(idx as f64))
+        });
+
+        fourier_bsk
+    }
+
+    #[test]
+    fn streaming_fourier_lwe_bootstrap_key_from_bytes() {
+        let fourier_bsk = test_fourier_lwe_bootstrap_key();
+
+        let mut buffer = vec![];
+        streaming_serialize(&fourier_bsk, &mut buffer, 1 << 20).unwrap();
+
+        let deser: FourierLweBootstrapKeyOwned =
+            streaming_deserialize(buffer.as_slice(), 1 << 20).unwrap();
+        assert_eq!(deser, fourier_bsk);
+
+        // Copy the stream to an aligned buffer, as a memory-mapped file would be
+        let mut aligned = aligned_vec::avec![0u8; buffer.len()];
+        aligned.copy_from_slice(&buffer);
+
+        let view = fourier_lwe_bootstrap_key_from_bytes(&aligned).unwrap();
+        assert_eq!(view, fourier_bsk.as_view());
+
+        // The view borrows the data at the end of the stream
+        let data = view.data();
+        assert_eq!(
+            data.as_ptr() as usize,
+            aligned.as_ptr() as usize + aligned.len() - std::mem::size_of_val(data)
+        );
+
+        assert!(fourier_lwe_bootstrap_key_from_bytes(&aligned[..aligned.len() - 1]).is_err());
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn streaming_mapped_fourier_lwe_bootstrap_key() {
+        let fourier_bsk = test_fourier_lwe_bootstrap_key();
+
+        let path =
+            std::env::temp_dir().join(format!("tfhe_streaming_fourier_bsk_{}", std::process::id()));
+
+        let file = std::fs::File::create(&path).unwrap();
+        streaming_serialize(&fourier_bsk, std::io::BufWriter::new(file), 1 << 20).unwrap();
+
+        let mapped = unsafe { MappedFourierLweBootstrapKey::open(&path) }.unwrap();
+        assert_eq!(mapped.as_view(), fourier_bsk.as_view());
+
+        drop(mapped);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
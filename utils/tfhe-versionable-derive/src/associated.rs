@@ -160,6 +160,16 @@ pub(crate) trait AssociatedType: Sized {
     /// which is the latest variant of the dispatch enum. The `Version` trait does not need a
     /// parameter.
     fn as_trait_param(&self) -> Option<syn::Result<&Type>>;
+
+    /// Generates the method of the associating trait that describes the serialized layout of this
+    /// type, see the `schema` module of `tfhe-versionable`.
+    fn generate_schema_method(&self) -> syn::Result<TokenStream>;
+
+    /// Generates the method of the associating trait that converts the original type into an older
+    /// version, if this is supported by this type.
+    fn generate_downgrade_method(&self) -> syn::Result<TokenStream> {
+        Ok(TokenStream::new())
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -228,10 +238,17 @@ impl<T: AssociatedType> AssociatingTrait<T> {
 
         let trait_ident = &self.trait_path;
 
+        let schema_method = self.owned_type.generate_schema_method()?;
+        let downgrade_method = self.owned_type.generate_downgrade_method()?;
+
         Ok(quote! {
             impl #impl_generics #trait_ident<#trait_param> for #orig_ident #orig_generics #where_clause {
                 type Ref<#lifetime> = #ref_ident #ref_generics #ref_where_clause;
                 type Owned = #owned_ident #orig_generics;
+
+                #schema_method
+
+                #downgrade_method
             }
         })
     }
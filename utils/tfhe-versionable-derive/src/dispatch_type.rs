@@ -12,8 +12,11 @@ use crate::associated::{
     generate_from_trait_impl, generate_try_from_trait_impl, AssociatedType, AssociatedTypeKind,
     ConversionDirection,
 };
+use crate::versionize_attribute::is_downgradable;
 use crate::{
-    parse_const_str, LIFETIME_NAME, UNVERSIONIZE_ERROR_NAME, UPGRADE_TRAIT_NAME, VERSION_TRAIT_NAME,
+    parse_const_str, DOWNGRADE_ERROR_NAME, DOWNGRADE_TRAIT_NAME, LIFETIME_NAME,
+    SCHEMA_REGISTRY_NAME, UNVERSIONIZE_ERROR_NAME, UPGRADE_TRAIT_NAME, VERSION_SCHEMA_NAME,
+    VERSION_TRAIT_NAME,
 };
 
 /// This is the enum that holds all the versions of a specific type. Each variant of the enum is
@@ -24,6 +27,8 @@ use crate::{
 pub(crate) struct DispatchType {
     orig_type: ItemEnum,
     kind: AssociatedTypeKind,
+    /// True if the enum has the `#[versionize(downgrade)]` attribute
+    downgrade: bool,
 }
 
 /// The `VersionsDispatch` macro can only be used on enum. This converts the
@@ -73,6 +78,7 @@ impl AssociatedType for DispatchType {
         Ok(Self {
             orig_type: derive_input_to_enum(orig_type)?,
             kind: AssociatedTypeKind::Ref(Some(lifetime)),
+            downgrade: is_downgradable(&orig_type.attrs)?,
         })
     }
 
@@ -80,6 +86,7 @@ impl AssociatedType for DispatchType {
         Ok(Self {
             orig_type: derive_input_to_enum(orig_type)?,
             kind: AssociatedTypeKind::Owned,
+            downgrade: is_downgradable(&orig_type.attrs)?,
         })
     }
 
@@ -135,6 +142,17 @@ impl AssociatedType for DispatchType {
                     let next_ty = self.version_type_at(src_idx + 1)?;
                     preds.push(parse_quote! { #src_ty: #upgrade_trait<#next_ty> })
                 }
+
+                if self.downgrade {
+                    let downgrade_trait: Path = parse_const_str(DOWNGRADE_TRAIT_NAME);
+
+                    // Add a bound for each version to be downgradable into the previous one
+                    for src_idx in 1..self.versions_count() {
+                        let src_ty = self.version_type_at(src_idx)?;
+                        let prev_ty = self.version_type_at(src_idx - 1)?;
+                        preds.push(parse_quote! { #src_ty: #downgrade_trait<#prev_ty> })
+                    }
+                }
             }
         }
 
@@ -235,6 +253,63 @@ impl AssociatedType for DispatchType {
     fn as_trait_param(&self) -> Option<syn::Result<&Type>> {
         Some(self.latest_version_type())
     }
+
+    fn generate_schema_method(&self) -> syn::Result<TokenStream> {
+        let registry_ty: Type = parse_const_str(SCHEMA_REGISTRY_NAME);
+        let version_schema: Path = parse_const_str(VERSION_SCHEMA_NAME);
+        let version_trait: Path = parse_const_str(VERSION_TRAIT_NAME);
+
+        let versions = self
+            .orig_type
+            .variants
+            .iter()
+            .enumerate()
+            .map(|(idx, variant)| -> syn::Result<_> {
+                let version_ty = self.version_type_at(idx)?;
+                let version_name = variant.ident.to_string();
+
+                // Each version is upgraded into the next variant of the dispatch enum
+                let upgrades_to = match self.orig_type.variants.iter().nth(idx + 1) {
+                    Some(next) => {
+                        let next_name = next.ident.to_string();
+                        quote! { Some(#next_name.to_string()) }
+                    }
+                    None => quote! { None },
+                };
+
+                Ok(quote! {
+                    #version_schema {
+                        version: #version_name.to_string(),
+                        type_name: ::core::any::type_name::<#version_ty>().to_string(),
+                        layout: <#version_ty as #version_trait>::layout(registry),
+                        upgrades_to: #upgrades_to,
+                    }
+                })
+            })
+            .collect::<syn::Result<Vec<TokenStream>>>()?;
+
+        Ok(quote! {
+            fn versions(registry: &mut #registry_ty) -> Vec<#version_schema> {
+                vec![#(#versions),*]
+            }
+        })
+    }
+
+    fn generate_downgrade_method(&self) -> syn::Result<TokenStream> {
+        if !self.downgrade {
+            return Ok(TokenStream::new());
+        }
+
+        let latest_type = self.latest_version_type()?;
+        let error_ty: Type = parse_const_str(DOWNGRADE_ERROR_NAME);
+        let body = self.generate_downgrade_body("value", "version")?;
+
+        Ok(quote! {
+            fn downgrade(value: &#latest_type, version: usize) -> Result<Option<Self::Owned>, #error_ty> {
+                #body
+            }
+        })
+    }
 }
 
 impl DispatchType {
@@ -338,6 +413,70 @@ impl DispatchType {
         })
     }
 
+    /// Generates the conversion from a reference to the original type into an older version of the
+    /// `owned` dispatch type. This generates a `match` on the target version that calls the
+    /// downgrade method on each version enough times to get to the target one.
+    fn generate_downgrade_body(
+        &self,
+        arg_name: &str,
+        version_arg_name: &str,
+    ) -> syn::Result<TokenStream> {
+        let arg_ident = Ident::new(arg_name, Span::call_site());
+        let version_ident = Ident::new(version_arg_name, Span::call_site());
+        let error_ty: Type = parse_const_str(DOWNGRADE_ERROR_NAME);
+        let downgrade_trait: Path = parse_const_str(DOWNGRADE_TRAIT_NAME);
+        let enum_ident = self.ident();
+        let last_version = self.versions_count() - 1;
+        let latest_type = self.latest_version_type()?;
+
+        let match_cases = (0..last_version)
+            .map(|target_idx| -> syn::Result<_> {
+                let target_variant = &self.variant_at(target_idx)?.ident;
+
+                // Add chained calls to the downgrade method, with error handling
+                let downgrades_chain = (target_idx..last_version)
+                    .rev()
+                    .map(|dest_idx| -> syn::Result<_> {
+                        let src_type = self.version_type_at(dest_idx + 1)?;
+                        let dest_type = self.version_type_at(dest_idx)?;
+                        let src_variant = self.variant_at(dest_idx + 1)?.ident.to_string();
+                        let dest_variant = self.variant_at(dest_idx)?.ident.to_string();
+
+                        // The first call is done on the original value, which is already a
+                        // reference
+                        let src_value = if dest_idx + 1 == last_version {
+                            quote! { #arg_ident }
+                        } else {
+                            quote! { &#arg_ident }
+                        };
+
+                        Ok(quote! {
+                            let #arg_ident = <#src_type as #downgrade_trait<#dest_type>>::downgrade(#src_value)
+                                .map_err(|e| #error_ty::downgrade(#src_variant, #dest_variant, e))?;
+                        })
+                    })
+                    .collect::<syn::Result<Vec<TokenStream>>>()?;
+
+                Ok(quote! {
+                    #target_idx => {
+                        #(#downgrades_chain)*
+                        Ok(Some(#enum_ident::#target_variant(#arg_ident.into())))
+                    }
+                })
+            })
+            .collect::<syn::Result<Vec<TokenStream>>>()?;
+
+        let versions_count = self.versions_count();
+
+        Ok(quote! {
+            match #version_ident {
+                #(#match_cases)*
+                #last_version => Ok(None),
+                _ => Err(#error_ty::unknown_version::<#latest_type>(#version_ident, #versions_count)),
+            }
+        })
+    }
+
     /// Generates conversion from the `owned` dispatch type to the original type. This generates a
     /// `match` on the dispatch enum that calls the update method on each version enough times to
     /// get to the latest version.
@@ -41,7 +41,15 @@ pub(crate) const VERSIONIZE_VEC_TRAIT_NAME: &str = crate_full_path!("VersionizeV
 pub(crate) const UNVERSIONIZE_TRAIT_NAME: &str = crate_full_path!("Unversionize");
 pub(crate) const UNVERSIONIZE_VEC_TRAIT_NAME: &str = crate_full_path!("UnversionizeVec");
 pub(crate) const UPGRADE_TRAIT_NAME: &str = crate_full_path!("Upgrade");
+pub(crate) const DOWNGRADE_TRAIT_NAME: &str = crate_full_path!("Downgrade");
+pub(crate) const DOWNGRADE_ERROR_NAME: &str = crate_full_path!("downgrade::DowngradeError");
 pub(crate) const UNVERSIONIZE_ERROR_NAME: &str = crate_full_path!("UnversionizeError");
+pub(crate) const SCHEMA_REGISTRY_NAME: &str = crate_full_path!("schema::SchemaRegistry");
+pub(crate) const DATA_SCHEMA_NAME: &str = crate_full_path!("schema::DataSchema");
+pub(crate) const FIELDS_SCHEMA_NAME: &str = crate_full_path!("schema::FieldsSchema");
+pub(crate) const NAMED_FIELD_SCHEMA_NAME: &str = crate_full_path!("schema::NamedFieldSchema");
+pub(crate) const VARIANT_SCHEMA_NAME: &str = crate_full_path!("schema::VariantSchema");
+pub(crate) const VERSION_SCHEMA_NAME: &str = crate_full_path!("schema::VersionSchema");
 
 pub(crate) const SERIALIZE_TRAIT_NAME: &str = "::serde::Serialize";
 pub(crate) const DESERIALIZE_TRAIT_NAME: &str = "::serde::Deserialize";
@@ -105,7 +113,11 @@ fn impl_version_trait(input: &DeriveInput) -> proc_macro2::TokenStream {
 /// Implement the `VersionsDispatch` trait for the target type. The type where this macro is
 /// applied should be an enum where each variant is a version of the type that we want to
 /// versionize.
-#[proc_macro_derive(VersionsDispatch)]
+///
+/// If the enum is marked with `#[versionize(downgrade)]`, the type can also be versioned into
+/// any of its previous versions. In that case, each version except the first one should implement
+/// the `Downgrade` trait into the version that precedes it.
+#[proc_macro_derive(VersionsDispatch, attributes(versionize))]
 pub fn derive_versions_dispatch(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -239,6 +251,21 @@ pub fn derive_versionize(input: TokenStream) -> TokenStream {
     let unversionize_arg_name = Ident::new("versioned", Span::call_site());
     let unversionize_body = implementor.unversionize_method_body(&unversionize_arg_name);
     let unversionize_error: Path = parse_const_str(UNVERSIONIZE_ERROR_NAME);
+    let versioned_schema_body = implementor.versioned_schema_method_body(&input_generics);
+    let schema_registry: Path = parse_const_str(SCHEMA_REGISTRY_NAME);
+    let data_schema: Path = parse_const_str(DATA_SCHEMA_NAME);
+    let version_arg_name = Ident::new("version", Span::call_site());
+    let downgrade_error: Path = parse_const_str(DOWNGRADE_ERROR_NAME);
+    let versionize_downgraded_method = implementor
+        .versionize_downgraded_method_body(&input_generics, &version_arg_name)
+        .map(|body| {
+            quote! {
+                fn versionize_downgraded(&self, #version_arg_name: usize)
+                    -> Result<Option<Self::VersionedOwned>, #downgrade_error> {
+                    #body
+                }
+            }
+        });
 
     quote! {
         #version_trait_impl
@@ -263,6 +290,12 @@ pub fn derive_versionize(input: TokenStream) -> TokenStream {
             fn versionize_owned(self) -> Self::VersionedOwned {
                 #versionize_owned_body
             }
+
+            fn versioned_schema(registry: &mut #schema_registry) -> #data_schema {
+                #versioned_schema_body
+            }
+
+            #versionize_downgraded_method
         }
 
         #[automatically_derived]
@@ -295,6 +328,10 @@ pub fn derive_versionize(input: TokenStream) -> TokenStream {
             fn versionize_vec(vec: Vec<Self>) -> Self::VersionedVec {
                 vec.into_iter().map(|val| #versionize_owned_trait::versionize_owned(val)).collect()
             }
+
+            fn versioned_vec_schema(registry: &mut #schema_registry) -> #data_schema {
+                #data_schema::seq(<Self as #versionize_owned_trait>::versioned_schema(registry))
+            }
         }
 
         #[automatically_derived]
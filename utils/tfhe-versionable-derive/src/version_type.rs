@@ -2,6 +2,7 @@ use std::iter::zip;
 
 use proc_macro2::{Literal, Span, TokenStream};
 use quote::{format_ident, quote};
+use syn::ext::IdentExt;
 use syn::spanned::Spanned;
 use syn::{
     parse_quote, Data, DataEnum, DataStruct, DataUnion, DeriveInput, Field, Fields, FieldsNamed,
@@ -16,8 +17,9 @@ use crate::associated::{
 use crate::versionize_attribute::{is_skipped, is_transparent, replace_versionize_skip_with_serde};
 use crate::{
     add_trait_where_clause, parse_const_str, parse_trait_bound, punctuated_from_iter_result,
-    DEFAULT_TRAIT_NAME, INTO_TRAIT_NAME, LIFETIME_NAME, TRY_INTO_TRAIT_NAME,
-    UNVERSIONIZE_ERROR_NAME, UNVERSIONIZE_TRAIT_NAME, VERSIONIZE_OWNED_TRAIT_NAME,
+    DATA_SCHEMA_NAME, DEFAULT_TRAIT_NAME, FIELDS_SCHEMA_NAME, INTO_TRAIT_NAME, LIFETIME_NAME,
+    NAMED_FIELD_SCHEMA_NAME, SCHEMA_REGISTRY_NAME, TRY_INTO_TRAIT_NAME, UNVERSIONIZE_ERROR_NAME,
+    UNVERSIONIZE_TRAIT_NAME, VARIANT_SCHEMA_NAME, VERSIONIZE_OWNED_TRAIT_NAME,
     VERSIONIZE_TRAIT_NAME, VERSION_TRAIT_NAME,
 };
 
@@ -244,6 +246,19 @@ impl AssociatedType for VersionType {
 
         Ok(generics)
     }
+
+    fn generate_schema_method(&self) -> syn::Result<TokenStream> {
+        let registry_ty: Type = parse_const_str(SCHEMA_REGISTRY_NAME);
+        let data_schema: Path = parse_const_str(DATA_SCHEMA_NAME);
+        let layout = self.generate_layout()?;
+
+        Ok(quote! {
+            #[allow(unused_variables)]
+            fn layout(registry: &mut #registry_ty) -> #data_schema {
+                #layout
+            }
+        })
+    }
 }
 
 impl VersionType {
@@ -422,6 +437,105 @@ impl VersionType {
             })
     }
 
+    /// Generates the expression that describes the layout of the owned version type, using the
+    /// schema of each of its fields.
+    fn generate_layout(&self) -> syn::Result<TokenStream> {
+        let data_schema: Path = parse_const_str(DATA_SCHEMA_NAME);
+        let fields_schema: Path = parse_const_str(FIELDS_SCHEMA_NAME);
+        let variant_schema: Path = parse_const_str(VARIANT_SCHEMA_NAME);
+
+        if self.is_transparent {
+            // The owned type is the "Version" of the inner type, so we reuse its layout
+            let version_trait: Path = parse_const_str(VERSION_TRAIT_NAME);
+            let inner_types = self.inner_types()?;
+            let inner_ty = inner_types.first().ok_or_else(|| {
+                syn::Error::new(
+                    self.orig_type.span(),
+                    "A transparent version type should have exactly one field",
+                )
+            })?;
+
+            return Ok(quote! {
+                <#inner_ty as #version_trait>::layout(registry)
+            });
+        }
+
+        match &self.orig_type.data {
+            Data::Struct(stru) => {
+                let fields = self.generate_fields_schema(&stru.fields)?;
+                Ok(quote! {
+                    #data_schema::Struct { fields: #fields }
+                })
+            }
+            Data::Enum(enu) => {
+                let variants = enu
+                    .variants
+                    .iter()
+                    .map(|variant| {
+                        let variant_name = variant.ident.unraw().to_string();
+                        // Skipped variants are kept as units in the version type
+                        let fields = if is_skipped(&variant.attrs)? {
+                            quote! { #fields_schema::Unit }
+                        } else {
+                            self.generate_fields_schema(&variant.fields)?
+                        };
+
+                        Ok(quote! {
+                            #variant_schema::new(#variant_name, #fields)
+                        })
+                    })
+                    .collect::<syn::Result<Vec<TokenStream>>>()?;
+
+                Ok(quote! {
+                    #data_schema::Enum { variants: vec![#(#variants),*] }
+                })
+            }
+            Data::Union(uni) => {
+                let fields = self.generate_fields_schema(&Fields::Named(uni.fields.clone()))?;
+                Ok(quote! {
+                    #data_schema::Struct { fields: #fields }
+                })
+            }
+        }
+    }
+
+    /// Generates the schema of the fields of a struct or an enum variant. Skipped fields are not
+    /// present in the serialized data so they are ignored.
+    fn generate_fields_schema(&self, fields: &Fields) -> syn::Result<TokenStream> {
+        let fields_schema: Path = parse_const_str(FIELDS_SCHEMA_NAME);
+        let named_field_schema: Path = parse_const_str(NAMED_FIELD_SCHEMA_NAME);
+        let versionize_owned_trait: Path = parse_const_str(VERSIONIZE_OWNED_TRAIT_NAME);
+
+        let kept_fields = iter_fields(fields)
+            .filter_map(filter_skipped_field)
+            .collect::<syn::Result<Vec<&Field>>>()?;
+
+        Ok(match fields {
+            Fields::Named(_) => {
+                let named = kept_fields.iter().map(|field| {
+                    let ty = &field.ty;
+                    // Ok to unwrap because the fields are named
+                    let name = field.ident.as_ref().unwrap().unraw().to_string();
+                    quote! {
+                        #named_field_schema::new(
+                            #name,
+                            <#ty as #versionize_owned_trait>::versioned_schema(registry)
+                        )
+                    }
+                });
+                quote! { #fields_schema::Named(vec![#(#named),*]) }
+            }
+            Fields::Unnamed(_) => {
+                let unnamed = kept_fields.iter().map(|field| {
+                    let ty = &field.ty;
+                    quote! { <#ty as #versionize_owned_trait>::versioned_schema(registry) }
+                });
+                quote! { #fields_schema::Unnamed(vec![#(#unnamed),*]) }
+            }
+            Fields::Unit => quote! { #fields_schema::Unit },
+        })
+    }
+
     /// Generates the constructor part of the conversion impl block. This will create the dest type
     /// using fields of the src one. This is easy since they both have the same shape.
     /// If the conversion is from the original type to a reference version type, this is done by
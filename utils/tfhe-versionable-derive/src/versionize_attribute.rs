@@ -321,6 +321,35 @@ pub(crate) fn is_transparent(attributes: &[Attribute]) -> syn::Result<bool> {
     Ok(false)
 }
 
+/// Check if a dispatch enum has the `#[versionize(downgrade)]` attribute in its attributes list
+pub(crate) fn is_downgradable(attributes: &[Attribute]) -> syn::Result<bool> {
+    let mut downgrade = false;
+
+    for attr in attributes
+        .iter()
+        .filter(|attr| attr.path().is_ident(VERSIONIZE_ATTR_NAME))
+    {
+        let nested = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+
+        for meta in nested.iter() {
+            match meta {
+                Meta::Path(path) if path.is_ident("downgrade") && !downgrade => {
+                    downgrade = true;
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        meta.span(),
+                        "Malformed `versionize` attribute, only `downgrade` is accepted on a \
+dispatch enum",
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(downgrade)
+}
+
 /// Check if a field has the `#[serde(skip)]` or `#[versionize(skip)]` attribute in
 /// its attributes list
 pub(crate) fn is_skipped(attributes: &[Attribute]) -> syn::Result<bool> {
@@ -358,6 +358,80 @@ impl VersionizeImplementor {
         }
     }
 
+    /// Return the body of the versioned_schema method.
+    ///
+    /// If the type is directly versioned, its versions are added to the registry. Otherwise the
+    /// schema is the one of the conversion target or of the inner type.
+    pub(crate) fn versioned_schema_method_body(
+        &self,
+        input_generics: &Generics,
+    ) -> proc_macro2::TokenStream {
+        let versionize_owned_trait: Path = parse_const_str(VERSIONIZE_OWNED_TRAIT_NAME);
+
+        match self {
+            Self::Classic(attr) => {
+                let (_, ty_generics, _) = input_generics.split_for_impl();
+                let dispatch_trait: Path = parse_const_str(DISPATCH_TRAIT_NAME);
+                let dispatch_enum_path = &attr.dispatch_enum;
+
+                quote! {
+                    registry.register_versioned::<Self, #dispatch_enum_path #ty_generics>(|registry| {
+                        <#dispatch_enum_path #ty_generics as #dispatch_trait<Self>>::versions(registry)
+                    })
+                }
+            }
+            Self::Convert(convert_attr) => {
+                let convert_type_path = &convert_attr.conversion_target;
+                quote! {
+                    <#convert_type_path as #versionize_owned_trait>::versioned_schema(registry)
+                }
+            }
+            Self::Transparent(transparent) => {
+                let inner_type = &transparent.inner_type;
+                quote! {
+                    <#inner_type as #versionize_owned_trait>::versioned_schema(registry)
+                }
+            }
+        }
+    }
+
+    /// Return the body of the versionize_downgraded method.
+    ///
+    /// If the type is directly versioned, the downgrade is done by the dispatch enum. If the type
+    /// is transparent, the inner type is downgraded. Types that are converted before versioning do
+    /// not support downgrades, so this returns None and the default implementation is used.
+    pub(crate) fn versionize_downgraded_method_body(
+        &self,
+        input_generics: &Generics,
+        version_arg_name: &Ident,
+    ) -> Option<proc_macro2::TokenStream> {
+        let versionize_owned_trait: Path = parse_const_str(VERSIONIZE_OWNED_TRAIT_NAME);
+
+        match self {
+            Self::Classic(attr) => {
+                let (_, ty_generics, _) = input_generics.split_for_impl();
+                let dispatch_trait: Path = parse_const_str(DISPATCH_TRAIT_NAME);
+                let dispatch_enum_path = &attr.dispatch_enum;
+
+                Some(quote! {
+                    <#dispatch_enum_path #ty_generics as #dispatch_trait<Self>>::downgrade(self, #version_arg_name)
+                })
+            }
+            Self::Convert(_) => None,
+            Self::Transparent(transparent) => {
+                let inner_type = &transparent.inner_type;
+                let field = match &transparent.kind {
+                    TransparentStructKind::NewType => quote! { 0 },
+                    TransparentStructKind::SingleField(field_name) => quote! { #field_name },
+                };
+
+                Some(quote! {
+                    <#inner_type as #versionize_owned_trait>::versionize_downgraded(&self.#field, #version_arg_name)
+                })
+            }
+        }
+    }
+
     /// Return the body of the unversionize method.
     pub(crate) fn unversionize_method_body(&self, arg_name: &Ident) -> proc_macro2::TokenStream {
         let error: Type = parse_const_str(UNVERSIONIZE_ERROR_NAME);
@@ -6,7 +6,8 @@ use std::marker::PhantomData;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{UnversionizeError, Upgrade, Version};
+use crate::schema::{DataSchema, SchemaRegistry};
+use crate::{Downgrade, UnversionizeError, Upgrade, Version};
 
 /// This trait should be implemented for types that have deprecated versions. You can then use them
 /// inside the dispatch enum by wrapping them into the [`Deprecated`] type.
@@ -105,6 +106,12 @@ impl<T: Deprecable> Version for Deprecated<T> {
         T: 'vers;
 
     type Owned = DeprecatedVersion<T>;
+
+    fn layout(_registry: &mut SchemaRegistry) -> DataSchema {
+        DataSchema::Deprecated {
+            name: T::TYPE_NAME.to_string(),
+        }
+    }
 }
 
 impl<T: Deprecable> From<Deprecated<T>> for DeprecatedVersion<T> {
@@ -138,3 +145,11 @@ impl<T: Deprecable, U> Upgrade<U> for Deprecated<T> {
         Err(T::error())
     }
 }
+
+impl<T: Deprecable, U> Downgrade<Deprecated<T>> for U {
+    type Error = DeprecatedVersionError;
+
+    fn downgrade(&self) -> Result<Deprecated<T>, Self::Error> {
+        Err(T::error())
+    }
+}
@@ -4,6 +4,8 @@
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
+use crate::downgrade::DowngradeError;
+use crate::schema::{DataSchema, SchemaRegistry, VersionSchema};
 use crate::UnversionizeError;
 
 /// This trait is used to mark a specific version of a given type
@@ -12,6 +14,11 @@ pub trait Version: Sized {
     where
         Self: 'vers;
     type Owned: From<Self> + TryInto<Self, Error = UnversionizeError> + DeserializeOwned + Serialize;
+
+    /// Describes the layout of [`Self::Owned`] in the serialized data
+    fn layout(_registry: &mut SchemaRegistry) -> DataSchema {
+        DataSchema::opaque::<Self::Owned>()
+    }
 }
 
 /// This trait is implemented on the dispatch enum for a given type. The dispatch enum
@@ -25,4 +32,22 @@ pub trait VersionsDispatch<Unversioned>: Sized {
         + TryInto<Unversioned, Error = UnversionizeError>
         + DeserializeOwned
         + Serialize;
+
+    /// Describes each version found in the dispatch enum, in declaration order
+    fn versions(_registry: &mut SchemaRegistry) -> Vec<VersionSchema> {
+        Vec::new()
+    }
+
+    /// Converts `value` into the version at index `version` of the dispatch enum, by calling the
+    /// [`Downgrade`] implementations in chain. Returns `None` if `version` is the latest one.
+    ///
+    /// This is only supported if the dispatch enum is marked with `#[versionize(downgrade)]`.
+    ///
+    /// [`Downgrade`]: crate::Downgrade
+    fn downgrade(
+        _value: &Unversioned,
+        _version: usize,
+    ) -> Result<Option<Self::Owned>, DowngradeError> {
+        Err(DowngradeError::unsupported::<Unversioned>())
+    }
 }
@@ -0,0 +1,174 @@
+//! How to perform conversion from one version to the previous one.
+//!
+//! This allows newer versions of an application to produce data that can be loaded by older ones.
+//! Downgrading is opt-in: the dispatch enum of a type should be marked with
+//! `#[versionize(downgrade)]` and [`Downgrade`] should be implemented for every version of the
+//! type, except the first one, into the version that precedes it.
+//!
+//! ```rust
+//! use std::convert::Infallible;
+//! use tfhe_versionable::{Downgrade, Upgrade, Version, Versionize, VersionizeOwned, VersionsDispatch};
+//!
+//! #[derive(Versionize)]
+//! #[versionize(MyStructVersions)]
+//! struct MyStruct {
+//!     attr: u64,
+//!     extra: Option<u32>,
+//! }
+//!
+//! #[derive(Version)]
+//! struct MyStructV0 {
+//!     attr: u64,
+//! }
+//!
+//! impl Upgrade<MyStruct> for MyStructV0 {
+//!     type Error = Infallible;
+//!
+//!     fn upgrade(self) -> Result<MyStruct, Self::Error> {
+//!         Ok(MyStruct {
+//!             attr: self.attr,
+//!             extra: None,
+//!         })
+//!     }
+//! }
+//!
+//! #[derive(Debug)]
+//! struct ExtraFieldError;
+//!
+//! impl std::fmt::Display for ExtraFieldError {
+//!     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+//!         write!(f, "the extra field cannot be represented in V0")
+//!     }
+//! }
+//!
+//! impl std::error::Error for ExtraFieldError {}
+//!
+//! impl Downgrade<MyStructV0> for MyStruct {
+//!     type Error = ExtraFieldError;
+//!
+//!     fn downgrade(&self) -> Result<MyStructV0, Self::Error> {
+//!         match self.extra {
+//!             None => Ok(MyStructV0 { attr: self.attr }),
+//!             Some(_) => Err(ExtraFieldError),
+//!         }
+//!     }
+//! }
+//!
+//! #[derive(VersionsDispatch)]
+//! #[versionize(downgrade)]
+//! #[allow(unused)]
+//! enum MyStructVersions {
+//!     V0(MyStructV0),
+//!     V1(MyStruct),
+//! }
+//!
+//! let ms = MyStruct {
+//!     attr: 37,
+//!     extra: None,
+//! };
+//! let v0 = ms.versionize_downgraded(0).unwrap().unwrap();
+//! let _serialized = bincode::serialize(&v0).unwrap();
+//!
+//! // The latest version does not need to be downgraded
+//! assert!(ms.versionize_downgraded(1).unwrap().is_none());
+//! ```
+
+use std::error::Error;
+use std::fmt::Display;
+
+/// This trait should be implemented for each version of the original type that is not the first
+/// one, to convert it into the version that precedes it. The downgrade method is called in chains
+/// until we get to the target version of the type.
+///
+/// Since downgrading is done before serialization, the conversion is done from a reference.
+pub trait Downgrade<T> {
+    type Error: std::error::Error + Send + Sync + 'static;
+    fn downgrade(&self) -> Result<T, Self::Error>;
+}
+
+#[derive(Debug)]
+/// Errors that can arise when versioning a type into an older version.
+pub enum DowngradeError {
+    /// An error in the downgrade between `vers_from` and `vers_into`, for example because a field
+    /// cannot be represented in the older version
+    Downgrade {
+        from_vers: String,
+        into_vers: String,
+        source: Box<dyn Error + Send + Sync>,
+    },
+
+    /// The requested version does not exist for this type
+    UnknownVersion {
+        type_name: String,
+        version: usize,
+        versions_count: usize,
+    },
+
+    /// The type does not support being versioned into an older version
+    Unsupported { type_name: String },
+}
+
+impl Display for DowngradeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Downgrade {
+                from_vers,
+                into_vers,
+                source,
+            } => write!(
+                f,
+                "Failed to downgrade from {from_vers} into {into_vers}: {source}"
+            ),
+            Self::UnknownVersion {
+                type_name,
+                version,
+                versions_count,
+            } => write!(
+                f,
+                "Cannot downgrade {type_name} into version {version}, \
+only {versions_count} versions are known"
+            ),
+            Self::Unsupported { type_name } => {
+                write!(f, "{type_name} cannot be downgraded into an older version")
+            }
+        }
+    }
+}
+
+impl Error for DowngradeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DowngradeError::Downgrade { source, .. } => Some(source.as_ref()),
+            DowngradeError::UnknownVersion { .. } => None,
+            DowngradeError::Unsupported { .. } => None,
+        }
+    }
+}
+
+impl DowngradeError {
+    pub fn downgrade<E: Error + 'static + Send + Sync>(
+        from_vers: &str,
+        into_vers: &str,
+        source: E,
+    ) -> Self {
+        Self::Downgrade {
+            from_vers: from_vers.to_string(),
+            into_vers: into_vers.to_string(),
+            source: Box::new(source),
+        }
+    }
+
+    pub fn unknown_version<T: ?Sized>(version: usize, versions_count: usize) -> Self {
+        Self::UnknownVersion {
+            type_name: std::any::type_name::<T>().to_string(),
+            version,
+            versions_count,
+        }
+    }
+
+    pub fn unsupported<T: ?Sized>() -> Self {
+        Self::Unsupported {
+            type_name: std::any::type_name::<T>().to_string(),
+        }
+    }
+}
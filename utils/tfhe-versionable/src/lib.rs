@@ -8,11 +8,15 @@
 
 pub mod deprecation;
 pub mod derived_traits;
+pub mod downgrade;
+pub mod schema;
 pub mod upgrade;
 
 use aligned_vec::{ABox, AVec};
 use deprecation::DeprecatedVersionError;
+use downgrade::DowngradeError;
 use num_complex::Complex;
+use schema::{DataSchema, FieldsSchema, NamedFieldSchema, SchemaRegistry};
 use std::collections::{HashMap, HashSet};
 use std::convert::Infallible;
 use std::error::Error;
@@ -22,6 +26,7 @@ use std::num::Wrapping;
 use std::sync::Arc;
 
 pub use derived_traits::{Version, VersionsDispatch};
+pub use downgrade::Downgrade;
 pub use upgrade::Upgrade;
 
 use serde::de::DeserializeOwned;
@@ -48,6 +53,23 @@ pub trait VersionizeOwned {
     /// Wraps the object into a versioned enum with a variant for each version. This will
     /// clone the underlying types.
     fn versionize_owned(self) -> Self::VersionedOwned;
+
+    /// Describes the layout of [`Self::VersionedOwned`] in the serialized data, see [`schema`].
+    fn versioned_schema(_registry: &mut SchemaRegistry) -> DataSchema {
+        DataSchema::opaque::<Self::VersionedOwned>()
+    }
+
+    /// Wraps the object into the variant of the versioned enum at index `version`, downgrading
+    /// it if needed, see [`downgrade`]. This will clone the underlying types.
+    ///
+    /// Returns `None` if `version` is the current version of the type, in which case the object
+    /// can be versioned as usual.
+    fn versionize_downgraded(
+        &self,
+        _version: usize,
+    ) -> Result<Option<Self::VersionedOwned>, DowngradeError> {
+        Err(DowngradeError::unsupported::<Self>())
+    }
 }
 
 /// This trait is used as a proxy to be more flexible when deriving Versionize for `Vec<T>`.
@@ -67,6 +89,11 @@ pub trait VersionizeVec: Sized {
     type VersionedVec: Serialize + DeserializeOwned;
 
     fn versionize_vec(vec: Vec<Self>) -> Self::VersionedVec;
+
+    /// Describes the layout of [`Self::VersionedVec`] in the serialized data, see [`schema`].
+    fn versioned_vec_schema(_registry: &mut SchemaRegistry) -> DataSchema {
+        DataSchema::opaque::<Self::VersionedVec>()
+    }
 }
 
 #[derive(Debug)]
@@ -191,7 +218,12 @@ pub trait UnversionizeVec: VersionizeVec {
 
 /// Marker trait for a type that it not really versioned, where the `versionize` method returns
 /// Self or &Self.
-pub trait NotVersioned: Versionize {}
+pub trait NotVersioned: Versionize {
+    /// Describes the layout of this type in the serialized data, see [`schema`].
+    fn not_versioned_schema() -> DataSchema {
+        DataSchema::opaque::<Self>()
+    }
+}
 
 impl<T: NotVersioned + Serialize + DeserializeOwned + Clone> VersionizeSlice for T {
     type VersionedSlice<'vers>
@@ -210,6 +242,10 @@ impl<T: NotVersioned + Serialize + DeserializeOwned + Clone> VersionizeVec for T
     fn versionize_vec(vec: Vec<Self>) -> Self::VersionedVec {
         vec
     }
+
+    fn versioned_vec_schema(_registry: &mut SchemaRegistry) -> DataSchema {
+        DataSchema::seq(T::not_versioned_schema())
+    }
 }
 
 impl<T: NotVersioned + Serialize + DeserializeOwned + Clone> UnversionizeVec for T {
@@ -236,6 +272,10 @@ macro_rules! impl_scalar_versionize {
             fn versionize_owned(self) -> Self::VersionedOwned {
                 self
             }
+
+            fn versioned_schema(_registry: &mut SchemaRegistry) -> DataSchema {
+                DataSchema::primitive::<$t>()
+            }
         }
 
         impl Unversionize for $t {
@@ -244,7 +284,11 @@ macro_rules! impl_scalar_versionize {
             }
         }
 
-        impl NotVersioned for $t {}
+        impl NotVersioned for $t {
+            fn not_versioned_schema() -> DataSchema {
+                DataSchema::primitive::<$t>()
+            }
+        }
     };
 }
 
@@ -285,6 +329,10 @@ impl<T: VersionizeOwned> VersionizeOwned for Wrapping<T> {
     fn versionize_owned(self) -> Self::VersionedOwned {
         Wrapping(T::versionize_owned(self.0))
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        T::versioned_schema(registry)
+    }
 }
 
 impl<T: Unversionize> Unversionize for Wrapping<T> {
@@ -293,7 +341,11 @@ impl<T: Unversionize> Unversionize for Wrapping<T> {
     }
 }
 
-impl<T: NotVersioned> NotVersioned for Wrapping<T> {}
+impl<T: NotVersioned> NotVersioned for Wrapping<T> {
+    fn not_versioned_schema() -> DataSchema {
+        T::not_versioned_schema()
+    }
+}
 
 impl<T: Versionize> Versionize for Box<T> {
     type Versioned<'vers>
@@ -312,6 +364,10 @@ impl<T: VersionizeOwned> VersionizeOwned for Box<T> {
     fn versionize_owned(self) -> Self::VersionedOwned {
         Box::new(T::versionize_owned(*self))
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        T::versioned_schema(registry)
+    }
 }
 
 impl<T: Unversionize> Unversionize for Box<T> {
@@ -337,6 +393,10 @@ impl<T: VersionizeVec + Clone> VersionizeOwned for Box<[T]> {
     fn versionize_owned(self) -> Self::VersionedOwned {
         T::versionize_vec(self.to_vec())
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        T::versioned_vec_schema(registry)
+    }
 }
 
 impl<T: UnversionizeVec + Clone> Unversionize for Box<[T]> {
@@ -353,6 +413,10 @@ impl<T: VersionizeVec + Clone> VersionizeVec for Box<[T]> {
             .map(|inner| inner.versionize_owned())
             .collect()
     }
+
+    fn versioned_vec_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        DataSchema::seq(T::versioned_vec_schema(registry))
+    }
 }
 
 impl<T: VersionizeSlice> VersionizeSlice for Box<[T]> {
@@ -395,6 +459,10 @@ impl<T: VersionizeVec> VersionizeOwned for Vec<T> {
     fn versionize_owned(self) -> Self::VersionedOwned {
         T::versionize_vec(self)
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        T::versioned_vec_schema(registry)
+    }
 }
 
 impl<T: UnversionizeVec> Unversionize for Vec<T> {
@@ -411,6 +479,10 @@ impl<T: VersionizeVec> VersionizeVec for Vec<T> {
             .map(|inner| T::versionize_vec(inner))
             .collect()
     }
+
+    fn versioned_vec_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        DataSchema::seq(T::versioned_vec_schema(registry))
+    }
 }
 
 impl<T: VersionizeSlice> VersionizeSlice for Vec<T> {
@@ -453,6 +525,10 @@ impl<T: VersionizeVec + Clone> VersionizeOwned for &[T] {
     fn versionize_owned(self) -> Self::VersionedOwned {
         T::versionize_vec(self.to_vec())
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        T::versioned_vec_schema(registry)
+    }
 }
 
 impl<T: VersionizeVec + Clone> VersionizeVec for &[T] {
@@ -463,6 +539,10 @@ impl<T: VersionizeVec + Clone> VersionizeVec for &[T] {
             .map(|inner| T::versionize_vec(inner.to_vec()))
             .collect()
     }
+
+    fn versioned_vec_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        DataSchema::seq(T::versioned_vec_schema(registry))
+    }
 }
 
 impl<'a, T: VersionizeSlice> VersionizeSlice for &'a [T] {
@@ -499,6 +579,10 @@ impl<const N: usize, T: VersionizeVec + Clone> VersionizeOwned for [T; N] {
     fn versionize_owned(self) -> Self::VersionedOwned {
         T::versionize_vec(self.to_vec())
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        T::versioned_vec_schema(registry)
+    }
 }
 
 impl<const N: usize, T: UnversionizeVec + Clone> Unversionize for [T; N] {
@@ -522,6 +606,10 @@ impl<const N: usize, T: VersionizeVec + Clone> VersionizeVec for [T; N] {
             .map(|inner| inner.versionize_owned())
             .collect()
     }
+
+    fn versioned_vec_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        DataSchema::seq(T::versioned_vec_schema(registry))
+    }
 }
 
 impl<const N: usize, T: VersionizeSlice> VersionizeSlice for [T; N] {
@@ -558,6 +646,10 @@ impl VersionizeOwned for String {
     fn versionize_owned(self) -> Self::VersionedOwned {
         self
     }
+
+    fn versioned_schema(_registry: &mut SchemaRegistry) -> DataSchema {
+        DataSchema::primitive::<String>()
+    }
 }
 
 impl Unversionize for String {
@@ -566,7 +658,11 @@ impl Unversionize for String {
     }
 }
 
-impl NotVersioned for String {}
+impl NotVersioned for String {
+    fn not_versioned_schema() -> DataSchema {
+        DataSchema::primitive::<String>()
+    }
+}
 
 impl Versionize for str {
     type Versioned<'vers> = &'vers str;
@@ -582,9 +678,17 @@ impl VersionizeOwned for &str {
     fn versionize_owned(self) -> Self::VersionedOwned {
         self.to_string()
     }
+
+    fn versioned_schema(_registry: &mut SchemaRegistry) -> DataSchema {
+        DataSchema::primitive::<String>()
+    }
 }
 
-impl NotVersioned for str {}
+impl NotVersioned for str {
+    fn not_versioned_schema() -> DataSchema {
+        DataSchema::primitive::<String>()
+    }
+}
 
 impl<T: Versionize> Versionize for Option<T> {
     type Versioned<'vers>
@@ -603,6 +707,10 @@ impl<T: VersionizeOwned> VersionizeOwned for Option<T> {
     fn versionize_owned(self) -> Self::VersionedOwned {
         self.map(|val| val.versionize_owned())
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        DataSchema::option(T::versioned_schema(registry))
+    }
 }
 
 impl<T: Unversionize> Unversionize for Option<T> {
@@ -611,7 +719,11 @@ impl<T: Unversionize> Unversionize for Option<T> {
     }
 }
 
-impl<T: NotVersioned> NotVersioned for Option<T> {}
+impl<T: NotVersioned> NotVersioned for Option<T> {
+    fn not_versioned_schema() -> DataSchema {
+        DataSchema::option(T::not_versioned_schema())
+    }
+}
 
 impl<T> Versionize for PhantomData<T> {
     type Versioned<'vers>
@@ -657,6 +769,10 @@ impl<T: VersionizeOwned + Clone> VersionizeOwned for Arc<T> {
     fn versionize_owned(self) -> Self::VersionedOwned {
         Arc::unwrap_or_clone(self).versionize_owned()
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        T::versioned_schema(registry)
+    }
 }
 
 impl<T: Unversionize + Clone> Unversionize for Arc<T> {
@@ -665,7 +781,11 @@ impl<T: Unversionize + Clone> Unversionize for Arc<T> {
     }
 }
 
-impl<T: NotVersioned> NotVersioned for Arc<T> {}
+impl<T: NotVersioned> NotVersioned for Arc<T> {
+    fn not_versioned_schema() -> DataSchema {
+        T::not_versioned_schema()
+    }
+}
 
 impl<T: Versionize> Versionize for Complex<T> {
     type Versioned<'vers>
@@ -690,6 +810,10 @@ impl<T: VersionizeOwned> VersionizeOwned for Complex<T> {
             im: self.im.versionize_owned(),
         }
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        complex_schema(T::versioned_schema(registry))
+    }
 }
 
 impl<T: Unversionize> Unversionize for Complex<T> {
@@ -701,7 +825,20 @@ impl<T: Unversionize> Unversionize for Complex<T> {
     }
 }
 
-impl<T: NotVersioned> NotVersioned for Complex<T> {}
+impl<T: NotVersioned> NotVersioned for Complex<T> {
+    fn not_versioned_schema() -> DataSchema {
+        complex_schema(T::not_versioned_schema())
+    }
+}
+
+fn complex_schema(part: DataSchema) -> DataSchema {
+    DataSchema::Struct {
+        fields: FieldsSchema::Named(vec![
+            NamedFieldSchema::new("re", part.clone()),
+            NamedFieldSchema::new("im", part),
+        ]),
+    }
+}
 
 impl<T: Versionize> Versionize for ABox<T> {
     type Versioned<'vers>
@@ -721,6 +858,10 @@ impl<T: VersionizeOwned + Clone> VersionizeOwned for ABox<T> {
     fn versionize_owned(self) -> Self::VersionedOwned {
         Box::new(T::versionize_owned(T::clone(&self)))
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        T::versioned_schema(registry)
+    }
 }
 
 impl<T: Unversionize + Clone> Unversionize for ABox<T>
@@ -749,6 +890,10 @@ impl<T: VersionizeVec + Clone> VersionizeOwned for ABox<[T]> {
     fn versionize_owned(self) -> Self::VersionedOwned {
         T::versionize_vec(self.iter().cloned().collect())
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        T::versioned_vec_schema(registry)
+    }
 }
 
 impl<T: UnversionizeVec + Clone> Unversionize for ABox<[T]> {
@@ -757,7 +902,11 @@ impl<T: UnversionizeVec + Clone> Unversionize for ABox<[T]> {
     }
 }
 
-impl<T: NotVersioned + Clone + Serialize + DeserializeOwned> NotVersioned for ABox<[T]> {}
+impl<T: NotVersioned + Clone + Serialize + DeserializeOwned> NotVersioned for ABox<[T]> {
+    fn not_versioned_schema() -> DataSchema {
+        DataSchema::seq(T::not_versioned_schema())
+    }
+}
 
 impl<T: VersionizeSlice> Versionize for AVec<T> {
     type Versioned<'vers>
@@ -777,6 +926,10 @@ impl<T: VersionizeVec + Clone> VersionizeOwned for AVec<T> {
     fn versionize_owned(self) -> Self::VersionedOwned {
         T::versionize_vec(self.to_vec())
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        T::versioned_vec_schema(registry)
+    }
 }
 
 impl<T: UnversionizeVec + Clone> Unversionize for AVec<T> {
@@ -785,7 +938,11 @@ impl<T: UnversionizeVec + Clone> Unversionize for AVec<T> {
     }
 }
 
-impl<T: NotVersioned + Clone + Serialize + DeserializeOwned> NotVersioned for AVec<T> {}
+impl<T: NotVersioned + Clone + Serialize + DeserializeOwned> NotVersioned for AVec<T> {
+    fn not_versioned_schema() -> DataSchema {
+        DataSchema::seq(T::not_versioned_schema())
+    }
+}
 
 impl Versionize for () {
     type Versioned<'vers> = ();
@@ -797,6 +954,10 @@ impl VersionizeOwned for () {
     type VersionedOwned = ();
 
     fn versionize_owned(self) -> Self::VersionedOwned {}
+
+    fn versioned_schema(_registry: &mut SchemaRegistry) -> DataSchema {
+        DataSchema::primitive::<()>()
+    }
 }
 
 impl Unversionize for () {
@@ -805,7 +966,11 @@ impl Unversionize for () {
     }
 }
 
-impl NotVersioned for () {}
+impl NotVersioned for () {
+    fn not_versioned_schema() -> DataSchema {
+        DataSchema::primitive::<()>()
+    }
+}
 
 // TODO: use a macro for more tuple sizes
 impl<T: Versionize, U: Versionize> Versionize for (T, U) {
@@ -826,6 +991,12 @@ impl<T: VersionizeOwned, U: VersionizeOwned> VersionizeOwned for (T, U) {
     fn versionize_owned(self) -> Self::VersionedOwned {
         (self.0.versionize_owned(), self.1.versionize_owned())
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        DataSchema::Tuple {
+            items: vec![T::versioned_schema(registry), U::versioned_schema(registry)],
+        }
+    }
 }
 
 impl<T: Unversionize, U: Unversionize> Unversionize for (T, U) {
@@ -857,6 +1028,10 @@ impl<T: VersionizeOwned, U: VersionizeOwned> VersionizeVec for (T, U) {
             .map(|(t, u)| (t.versionize_owned(), u.versionize_owned()))
             .collect()
     }
+
+    fn versioned_vec_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        DataSchema::seq(Self::versioned_schema(registry))
+    }
 }
 
 impl<T: Unversionize, U: Unversionize> UnversionizeVec for (T, U) {
@@ -899,6 +1074,16 @@ impl<T: VersionizeOwned, U: VersionizeOwned, V: VersionizeOwned> VersionizeOwned
             self.2.versionize_owned(),
         )
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        DataSchema::Tuple {
+            items: vec![
+                T::versioned_schema(registry),
+                U::versioned_schema(registry),
+                V::versioned_schema(registry),
+            ],
+        }
+    }
 }
 
 impl<T: Unversionize, U: Unversionize, V: Unversionize> Unversionize for (T, U, V) {
@@ -945,6 +1130,10 @@ impl<T: VersionizeOwned, U: VersionizeOwned, V: VersionizeOwned> VersionizeVec f
             })
             .collect()
     }
+
+    fn versioned_vec_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        DataSchema::seq(Self::versioned_schema(registry))
+    }
 }
 
 impl<T: Unversionize, U: Unversionize, V: Unversionize> UnversionizeVec for (T, U, V) {
@@ -981,6 +1170,10 @@ impl<T: VersionizeOwned> VersionizeOwned for HashSet<T> {
     fn versionize_owned(self) -> Self::VersionedOwned {
         self.into_iter().map(|val| val.versionize_owned()).collect()
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        DataSchema::seq(T::versioned_schema(registry))
+    }
 }
 
 impl<T: Unversionize + std::hash::Hash + Eq> Unversionize for HashSet<T> {
@@ -1016,6 +1209,10 @@ impl<K: VersionizeOwned, V: VersionizeOwned> VersionizeOwned for HashMap<K, V> {
             .map(|(key, val)| (key.versionize_owned(), val.versionize_owned()))
             .collect()
     }
+
+    fn versioned_schema(registry: &mut SchemaRegistry) -> DataSchema {
+        DataSchema::seq(<(K, V)>::versioned_schema(registry))
+    }
 }
 
 impl<K: Unversionize + std::hash::Hash + Eq, V: Unversionize> Unversionize for HashMap<K, V> {
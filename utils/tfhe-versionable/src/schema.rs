@@ -0,0 +1,234 @@
+//! Machine-readable description of the serialized layout of versioned types.
+//!
+//! Each type that implements [`VersionizeOwned`] can describe the shape of its
+//! [`VersionizeOwned::VersionedOwned`] type as a [`DataSchema`]. For types that derive
+//! `Versionize`, this schema lists every version found in the dispatch enum, with the fields of
+//! each version and the upgrade edges between them. The descriptions of all the versioned types
+//! reachable from a given type are collected in a [`SchemaRegistry`], which can be serialized (for
+//! example to json) to be consumed by other tools or diffed between releases.
+//!
+//! The names used in the schema are the ones returned by [`std::any::type_name`].
+//!
+//! ```rust
+//! use tfhe_versionable::schema::VersionedSchema;
+//! use tfhe_versionable::{Versionize, VersionsDispatch};
+//!
+//! #[derive(Versionize)]
+//! #[versionize(MyStructVersions)]
+//! struct MyStruct {
+//!     attr: u64,
+//! }
+//!
+//! #[derive(VersionsDispatch)]
+//! #[allow(unused)]
+//! enum MyStructVersions {
+//!     V0(MyStruct),
+//! }
+//!
+//! let registry = MyStruct::schema_registry();
+//! let schema = registry.get(std::any::type_name::<MyStruct>()).unwrap();
+//! assert_eq!(schema.versions.len(), 1);
+//! ```
+
+use std::any::type_name;
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::VersionizeOwned;
+
+/// Describes how a value is represented in the serialized data.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DataSchema {
+    /// A builtin type that is serialized natively (integers, floats, bool, strings...)
+    Primitive { name: String },
+    /// A type that does not describe its layout, it is serialized using its own `Serialize` impl
+    Opaque { name: String },
+    /// An optional value
+    Option { item: Box<DataSchema> },
+    /// A sequence of values of the same type
+    Seq { item: Box<DataSchema> },
+    /// A fixed size list of values that may have different types
+    Tuple { items: Vec<DataSchema> },
+    /// A struct, with the fields as they are found in the serialized data
+    Struct { fields: FieldsSchema },
+    /// An enum, with its variants in declaration order
+    Enum { variants: Vec<VariantSchema> },
+    /// A versioned type. Its versions are described in the [`SchemaRegistry`] under this name.
+    Versioned { name: String },
+    /// A version that is not supported anymore and cannot be loaded
+    Deprecated { name: String },
+}
+
+impl DataSchema {
+    pub fn primitive<T: ?Sized>() -> Self {
+        Self::Primitive {
+            name: type_name::<T>().to_string(),
+        }
+    }
+
+    pub fn opaque<T: ?Sized>() -> Self {
+        Self::Opaque {
+            name: type_name::<T>().to_string(),
+        }
+    }
+
+    pub fn option(item: Self) -> Self {
+        Self::Option {
+            item: Box::new(item),
+        }
+    }
+
+    pub fn seq(item: Self) -> Self {
+        Self::Seq {
+            item: Box::new(item),
+        }
+    }
+}
+
+/// The fields of a struct or of an enum variant
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldsSchema {
+    Named(Vec<NamedFieldSchema>),
+    Unnamed(Vec<DataSchema>),
+    Unit,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct NamedFieldSchema {
+    pub name: String,
+    pub schema: DataSchema,
+}
+
+impl NamedFieldSchema {
+    pub fn new(name: &str, schema: DataSchema) -> Self {
+        Self {
+            name: name.to_string(),
+            schema,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct VariantSchema {
+    pub name: String,
+    pub fields: FieldsSchema,
+}
+
+impl VariantSchema {
+    pub fn new(name: &str, fields: FieldsSchema) -> Self {
+        Self {
+            name: name.to_string(),
+            fields,
+        }
+    }
+}
+
+/// A single version of a versioned type, which is a variant of its dispatch enum.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct VersionSchema {
+    /// Name of the variant in the dispatch enum, for example `V0`
+    pub version: String,
+    /// Rust type used for this version
+    pub type_name: String,
+    /// Layout of this version in the serialized data
+    pub layout: DataSchema,
+    /// Version that this one is upgraded into when it is loaded, if this is not the latest one
+    pub upgrades_to: Option<String>,
+}
+
+/// All the versions of a versioned type
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct VersionedTypeSchema {
+    /// Name of the dispatch enum of this type
+    pub dispatch: String,
+    /// The versions, in the order of the dispatch enum. The variant index of each version is the
+    /// one used in the serialized data.
+    pub versions: Vec<VersionSchema>,
+}
+
+/// Holds the description of a set of versioned types and of all the versioned types that they
+/// depend on.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SchemaRegistry {
+    roots: BTreeMap<String, DataSchema>,
+    types: BTreeMap<String, VersionedTypeSchema>,
+}
+
+impl SchemaRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the schema of `T` and of all the versioned types that it references to the registry
+    pub fn add<T: VersionizeOwned + ?Sized>(&mut self) -> &mut Self {
+        let schema = T::versioned_schema(self);
+        self.roots.insert(type_name::<T>().to_string(), schema);
+        self
+    }
+
+    /// Registers the versions of the versioned type `T` that uses `Dispatch` as dispatch enum, and
+    /// returns a reference to it. The versions are only computed the first time a type is
+    /// registered.
+    ///
+    /// This is used by the `Versionize` derive macro and should not be called manually.
+    pub fn register_versioned<T: ?Sized, Dispatch: ?Sized>(
+        &mut self,
+        versions: impl FnOnce(&mut Self) -> Vec<VersionSchema>,
+    ) -> DataSchema {
+        let name = type_name::<T>().to_string();
+
+        if !self.types.contains_key(&name) {
+            // Insert the entry before describing the versions, so that it is only done once even if
+            // the type is found again while walking its fields
+            self.types.insert(
+                name.clone(),
+                VersionedTypeSchema {
+                    dispatch: type_name::<Dispatch>().to_string(),
+                    versions: Vec::new(),
+                },
+            );
+
+            let versions = versions(self);
+            if let Some(schema) = self.types.get_mut(&name) {
+                schema.versions = versions;
+            }
+        }
+
+        DataSchema::Versioned { name }
+    }
+
+    /// The schema of the types that have been added with [`Self::add`]
+    pub fn roots(&self) -> impl Iterator<Item = (&str, &DataSchema)> {
+        self.roots
+            .iter()
+            .map(|(name, schema)| (name.as_str(), schema))
+    }
+
+    /// The versioned types found in the registry
+    pub fn types(&self) -> impl Iterator<Item = (&str, &VersionedTypeSchema)> {
+        self.types
+            .iter()
+            .map(|(name, schema)| (name.as_str(), schema))
+    }
+
+    /// Returns the versions of the versioned type with the given name
+    pub fn get(&self, name: &str) -> Option<&VersionedTypeSchema> {
+        self.types.get(name)
+    }
+}
+
+/// Gives access to the schema of a type that can be versioned
+pub trait VersionedSchema: VersionizeOwned {
+    /// Returns a registry with the description of this type and all the versioned types it
+    /// depends on
+    fn schema_registry() -> SchemaRegistry {
+        let mut registry = SchemaRegistry::new();
+        registry.add::<Self>();
+        registry
+    }
+}
+
+impl<T: VersionizeOwned> VersionedSchema for T {}
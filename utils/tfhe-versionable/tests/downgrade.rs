@@ -0,0 +1,194 @@
+//! Test the conversion of a type into its previous versions
+
+use std::convert::Infallible;
+use std::fmt::Display;
+
+use tfhe_versionable::deprecation::{Deprecable, Deprecated};
+use tfhe_versionable::downgrade::DowngradeError;
+use tfhe_versionable::{
+    Downgrade, Unversionize, Upgrade, Version, Versionize, VersionizeOwned, VersionsDispatch,
+};
+
+#[derive(Debug)]
+struct NonZeroOffsetError;
+
+impl Display for NonZeroOffsetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "offset cannot be represented in this version")
+    }
+}
+
+impl std::error::Error for NonZeroOffsetError {}
+
+#[derive(Version)]
+struct MyStructV1 {
+    count: u32,
+}
+
+impl Upgrade<MyStructV2> for MyStructV1 {
+    type Error = Infallible;
+
+    fn upgrade(self) -> Result<MyStructV2, Self::Error> {
+        Ok(MyStructV2 {
+            count: self.count as u64,
+        })
+    }
+}
+
+#[derive(Version)]
+struct MyStructV2 {
+    count: u64,
+}
+
+impl Upgrade<MyStruct> for MyStructV2 {
+    type Error = Infallible;
+
+    fn upgrade(self) -> Result<MyStruct, Self::Error> {
+        Ok(MyStruct {
+            count: self.count,
+            offset: 0,
+        })
+    }
+}
+
+impl Downgrade<MyStructV1> for MyStructV2 {
+    type Error = std::num::TryFromIntError;
+
+    fn downgrade(&self) -> Result<MyStructV1, Self::Error> {
+        Ok(MyStructV1 {
+            count: self.count.try_into()?,
+        })
+    }
+}
+
+#[derive(Versionize, Debug, PartialEq, Eq)]
+#[versionize(MyStructVersions)]
+struct MyStruct {
+    count: u64,
+    offset: u8,
+}
+
+impl Downgrade<MyStructV2> for MyStruct {
+    type Error = NonZeroOffsetError;
+
+    fn downgrade(&self) -> Result<MyStructV2, Self::Error> {
+        if self.offset != 0 {
+            return Err(NonZeroOffsetError);
+        }
+
+        Ok(MyStructV2 { count: self.count })
+    }
+}
+
+impl Deprecable for MyStruct {
+    const TYPE_NAME: &'static str = "MyStruct";
+    const MIN_SUPPORTED_APP_VERSION: &'static str = "my_app v1";
+}
+
+#[derive(VersionsDispatch)]
+#[versionize(downgrade)]
+#[allow(unused)]
+enum MyStructVersions {
+    V0(Deprecated<MyStruct>),
+    V1(MyStructV1),
+    V2(MyStructV2),
+    V3(MyStruct),
+}
+
+#[derive(Versionize)]
+#[versionize(transparent)]
+struct Wrapper(MyStruct);
+
+#[derive(Versionize)]
+#[versionize(NotDowngradableVersions)]
+struct NotDowngradable {
+    count: u64,
+}
+
+#[derive(VersionsDispatch)]
+#[allow(unused)]
+enum NotDowngradableVersions {
+    V0(NotDowngradable),
+}
+
+#[test]
+fn test_downgrade() {
+    let value = MyStruct {
+        count: 42,
+        offset: 0,
+    };
+
+    // The latest version is versioned as usual
+    assert!(value.versionize_downgraded(3).unwrap().is_none());
+
+    // Data produced for an older version can be loaded by the current one
+    for version in [1, 2] {
+        let downgraded = value.versionize_downgraded(version).unwrap().unwrap();
+        let serialized = bincode::serialize(&downgraded).unwrap();
+
+        let versioned = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(MyStruct::unversionize(versioned).unwrap(), value);
+    }
+
+    // The transparent wrapper uses the versions of the inner type
+    let wrapper = Wrapper(value);
+    let downgraded = wrapper.versionize_downgraded(1).unwrap().unwrap();
+    let versioned = bincode::deserialize(&bincode::serialize(&downgraded).unwrap()).unwrap();
+    assert_eq!(
+        MyStruct::unversionize(versioned).unwrap(),
+        MyStruct {
+            count: 42,
+            offset: 0,
+        }
+    );
+}
+
+#[test]
+fn test_downgrade_errors() {
+    // Fields that cannot be represented in the target version
+    let value = MyStruct {
+        count: 42,
+        offset: 3,
+    };
+    assert!(matches!(
+        value.versionize_downgraded(1),
+        Err(DowngradeError::Downgrade { from_vers, into_vers, .. })
+            if from_vers == "V3" && into_vers == "V2"
+    ));
+
+    let value = MyStruct {
+        count: u64::MAX,
+        offset: 0,
+    };
+    assert!(matches!(
+        value.versionize_downgraded(1),
+        Err(DowngradeError::Downgrade { from_vers, into_vers, .. })
+            if from_vers == "V2" && into_vers == "V1"
+    ));
+
+    // Deprecated versions cannot be produced
+    let value = MyStruct {
+        count: 42,
+        offset: 0,
+    };
+    assert!(matches!(
+        value.versionize_downgraded(0),
+        Err(DowngradeError::Downgrade { .. })
+    ));
+
+    assert!(matches!(
+        value.versionize_downgraded(4),
+        Err(DowngradeError::UnknownVersion {
+            version: 4,
+            versions_count: 4,
+            ..
+        })
+    ));
+
+    // Downgrade is opt-in
+    let value = NotDowngradable { count: 42 };
+    assert!(matches!(
+        value.versionize_downgraded(0),
+        Err(DowngradeError::Unsupported { .. })
+    ));
+}
@@ -0,0 +1,167 @@
+//! Test the schema generated for versioned types
+
+use std::any::type_name;
+use std::convert::Infallible;
+
+use tfhe_versionable::deprecation::{Deprecable, Deprecated};
+use tfhe_versionable::schema::{
+    DataSchema, FieldsSchema, NamedFieldSchema, VariantSchema, VersionedSchema,
+};
+use tfhe_versionable::{Upgrade, Version, Versionize, VersionsDispatch};
+
+#[derive(Versionize)]
+#[versionize(InnerVersions)]
+enum Inner {
+    Empty,
+    Value(u32),
+    #[versionize(skip)]
+    #[allow(dead_code)]
+    Skipped(String),
+}
+
+#[derive(VersionsDispatch)]
+#[allow(unused)]
+enum InnerVersions {
+    V0(Inner),
+}
+
+#[derive(Version)]
+struct MyStructV1 {
+    count: u32,
+}
+
+impl Upgrade<MyStruct> for MyStructV1 {
+    type Error = Infallible;
+
+    fn upgrade(self) -> Result<MyStruct, Self::Error> {
+        Ok(MyStruct {
+            count: self.count as u64,
+            inners: Vec::new(),
+            last: None,
+            cache: 0,
+        })
+    }
+}
+
+#[derive(Versionize)]
+#[versionize(MyStructVersions)]
+struct MyStruct {
+    count: u64,
+    inners: Vec<Inner>,
+    last: Option<Inner>,
+    #[versionize(skip)]
+    #[allow(dead_code)]
+    cache: u8,
+}
+
+impl Deprecable for MyStruct {
+    const TYPE_NAME: &'static str = "MyStruct";
+    const MIN_SUPPORTED_APP_VERSION: &'static str = "my_app v1";
+}
+
+#[derive(VersionsDispatch)]
+#[allow(unused)]
+enum MyStructVersions {
+    V0(Deprecated<MyStruct>),
+    V1(MyStructV1),
+    V2(MyStruct),
+}
+
+#[derive(Versionize)]
+#[versionize(transparent)]
+struct Wrapper(MyStruct);
+
+#[test]
+fn test_schema() {
+    let registry = Wrapper::schema_registry();
+
+    let my_struct_name = type_name::<MyStruct>();
+    let inner_name = type_name::<Inner>();
+
+    // The transparent wrapper is not versioned itself
+    assert_eq!(
+        registry.roots().collect::<Vec<_>>(),
+        vec![(
+            type_name::<Wrapper>(),
+            &DataSchema::Versioned {
+                name: my_struct_name.to_string()
+            }
+        )]
+    );
+    assert_eq!(
+        registry.types().map(|(name, _)| name).collect::<Vec<_>>(),
+        {
+            let mut names = vec![my_struct_name, inner_name];
+            names.sort();
+            names
+        }
+    );
+
+    let my_struct = registry.get(my_struct_name).unwrap();
+    assert_eq!(my_struct.dispatch, type_name::<MyStructVersions>());
+
+    let versions: Vec<_> = my_struct
+        .versions
+        .iter()
+        .map(|vers| (vers.version.as_str(), vers.upgrades_to.as_deref()))
+        .collect();
+    assert_eq!(
+        versions,
+        vec![("V0", Some("V1")), ("V1", Some("V2")), ("V2", None)]
+    );
+
+    assert_eq!(
+        my_struct.versions[0].layout,
+        DataSchema::Deprecated {
+            name: "MyStruct".to_string()
+        }
+    );
+    assert_eq!(
+        my_struct.versions[1].layout,
+        DataSchema::Struct {
+            fields: FieldsSchema::Named(vec![NamedFieldSchema::new(
+                "count",
+                DataSchema::primitive::<u32>()
+            )])
+        }
+    );
+
+    // Skipped fields are not serialized, and types used several times are only described once
+    let versioned_inner = DataSchema::Versioned {
+        name: inner_name.to_string(),
+    };
+    assert_eq!(
+        my_struct.versions[2].layout,
+        DataSchema::Struct {
+            fields: FieldsSchema::Named(vec![
+                NamedFieldSchema::new("count", DataSchema::primitive::<u64>()),
+                NamedFieldSchema::new("inners", DataSchema::seq(versioned_inner.clone())),
+                NamedFieldSchema::new("last", DataSchema::option(versioned_inner)),
+            ])
+        }
+    );
+
+    // Skipped variants are kept as units
+    let inner = registry.get(inner_name).unwrap();
+    assert_eq!(
+        inner.versions[0].layout,
+        DataSchema::Enum {
+            variants: vec![
+                VariantSchema::new("Empty", FieldsSchema::Unit),
+                VariantSchema::new(
+                    "Value",
+                    FieldsSchema::Unnamed(vec![DataSchema::primitive::<u32>()])
+                ),
+                VariantSchema::new("Skipped", FieldsSchema::Unit),
+            ]
+        }
+    );
+
+    // The registry can be exported
+    let json = serde_json::to_value(&registry).unwrap();
+    assert_eq!(
+        json["types"][my_struct_name]["versions"][1]["layout"]["fields"]["named"][0]["schema"]
+            ["kind"],
+        "primitive"
+    );
+}